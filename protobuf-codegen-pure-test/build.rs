@@ -158,6 +158,7 @@ fn main() {
     env_logger::init();
 
     cfg_serde();
+    cfg_rkyv();
 
     clean_old_files();
     generate_pb_rs();
@@ -15,6 +15,9 @@ extern crate serde_derive;
 #[cfg(feature = "with-serde")]
 extern crate serde_json;
 
+#[cfg(feature = "with-rkyv")]
+extern crate rkyv;
+
 mod v2;
 mod v3;
 
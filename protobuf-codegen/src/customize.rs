@@ -24,6 +24,22 @@ pub struct Customize {
     pub serde_derive: Option<bool>,
     /// When `serde_derive` is set, serde annotations will be guarded with `#[cfg(cfg, ...)]`.
     pub serde_derive_cfg: Option<String>,
+    /// Implement `rkyv` `Archive`, `Serialize` and `Deserialize` for messages,
+    /// so parsed messages can be stored in zero-copy readable form.
+    ///
+    /// Generated code requires `with-rkyv` feature of `protobuf` crate.
+    /// Fields of `bytes::Bytes` or `Chars` types are not supported.
+    pub rkyv_derive: Option<bool>,
+    /// When `rkyv_derive` is set, rkyv annotations will be guarded with `#[cfg(cfg, ...)]`.
+    pub rkyv_derive_cfg: Option<String>,
+    /// Implement `TryFrom<&[u8]>` for messages (parse)
+    /// and `TryFrom<&Message>` for `Vec<u8>` (serialize).
+    pub impl_bytes_conversions: Option<bool>,
+    /// Store singular message fields with `[lazy = true]` option as `LazyMessage`,
+    /// which is parsed on first access.
+    ///
+    /// Not supported together with `rkyv_derive`.
+    pub lazy_message_fields: Option<bool>,
     /// Enable lite runtime
     pub lite_runtime: Option<bool>,
     /// Generate `mod.rs` in the output directory.
@@ -35,6 +51,30 @@ pub struct Customize {
     /// Used internally to generate protos bundled in protobuf crate
     /// like `descriptor.proto`
     pub inside_protobuf: Option<bool>,
+    /// Format generated files with `prettyplease`,
+    /// so formatting does not depend on code generator version.
+    ///
+    /// Requires `with-prettyplease` feature of `protobuf-codegen` crate.
+    pub prettyplease: Option<bool>,
+    /// Attributes added to generated messages, enums and oneof enums,
+    /// as pairs of path and attribute, e.g.
+    /// `(".my_pkg.MyMessage", "#[derive(Hash)]")`.
+    ///
+    /// Path is a fully-qualified protobuf name starting with dot,
+    /// and matches the element with this name and all elements nested in it
+    /// (oneof enums are named like `.my_pkg.MyMessage.my_oneof`).
+    /// Path `.` matches all elements.
+    ///
+    /// This option cannot be specified with protoc parameter.
+    pub type_attributes: Vec<(String, String)>,
+    /// Attributes added to fields of generated messages,
+    /// as pairs of path and attribute, e.g.
+    /// `(".my_pkg.MyMessage.my_field", "#[serde(default)]")`.
+    ///
+    /// Paths are matched like in [`type_attributes`](Customize::type_attributes).
+    ///
+    /// This option cannot be specified with protoc parameter.
+    pub field_attributes: Vec<(String, String)>,
 
     // When adding more options please keep in sync with `parse_from_parameter` below.
     /// Make sure `Customize` is always used with `..Default::default()`
@@ -78,6 +118,18 @@ impl Customize {
         if let Some(ref v) = that.serde_derive_cfg {
             self.serde_derive_cfg = Some(v.clone());
         }
+        if let Some(v) = that.rkyv_derive {
+            self.rkyv_derive = Some(v);
+        }
+        if let Some(ref v) = that.rkyv_derive_cfg {
+            self.rkyv_derive_cfg = Some(v.clone());
+        }
+        if let Some(v) = that.impl_bytes_conversions {
+            self.impl_bytes_conversions = Some(v);
+        }
+        if let Some(v) = that.lazy_message_fields {
+            self.lazy_message_fields = Some(v);
+        }
         if let Some(v) = that.lite_runtime {
             self.lite_runtime = Some(v);
         }
@@ -87,6 +139,31 @@ impl Customize {
         if let Some(v) = that.inside_protobuf {
             self.inside_protobuf = Some(v);
         }
+        if let Some(v) = that.prettyplease {
+            self.prettyplease = Some(v);
+        }
+        if !that.type_attributes.is_empty() {
+            self.type_attributes = that.type_attributes.clone();
+        }
+        if !that.field_attributes.is_empty() {
+            self.field_attributes = that.field_attributes.clone();
+        }
+    }
+
+    /// Add an attribute to generated types matching given path,
+    /// see [`type_attributes`](Customize::type_attributes).
+    pub fn type_attribute(mut self, path: &str, attribute: &str) -> Customize {
+        self.type_attributes
+            .push((path.to_owned(), attribute.to_owned()));
+        self
+    }
+
+    /// Add an attribute to generated fields matching given path,
+    /// see [`field_attributes`](Customize::field_attributes).
+    pub fn field_attribute(mut self, path: &str, attribute: &str) -> Customize {
+        self.field_attributes
+            .push((path.to_owned(), attribute.to_owned()));
+        self
     }
 
     /// Update unset fields of self with fields from other customize
@@ -129,12 +206,22 @@ impl Customize {
                 r.serde_derive = Some(parse_bool(v)?);
             } else if n == "serde_derive_cfg" {
                 r.serde_derive_cfg = Some(v.to_owned());
+            } else if n == "rkyv_derive" {
+                r.rkyv_derive = Some(parse_bool(v)?);
+            } else if n == "rkyv_derive_cfg" {
+                r.rkyv_derive_cfg = Some(v.to_owned());
+            } else if n == "impl_bytes_conversions" {
+                r.impl_bytes_conversions = Some(parse_bool(v)?);
+            } else if n == "lazy_message_fields" {
+                r.lazy_message_fields = Some(parse_bool(v)?);
             } else if n == "lite_runtime" {
                 r.lite_runtime = Some(parse_bool(v)?);
             } else if n == "gen_mod_rs" {
                 r.gen_mod_rs = Some(parse_bool(v)?);
             } else if n == "inside_protobuf" {
                 r.inside_protobuf = Some(parse_bool(v)?);
+            } else if n == "prettyplease" {
+                r.prettyplease = Some(parse_bool(v)?);
             } else {
                 return Err(CustomizeParseParameterError::UnknownOptionName(
                     n.to_owned(),
@@ -154,9 +241,14 @@ pub fn customize_from_rustproto_for_message(source: &MessageOptions) -> Customiz
     let carllerche_bytes_for_string = rustproto::exts::carllerche_bytes_for_string.get(source);
     let serde_derive = rustproto::exts::serde_derive.get(source);
     let serde_derive_cfg = rustproto::exts::serde_derive_cfg.get(source);
+    let rkyv_derive = rustproto::exts::rkyv_derive.get(source);
+    let rkyv_derive_cfg = rustproto::exts::rkyv_derive_cfg.get(source);
+    let impl_bytes_conversions = rustproto::exts::impl_bytes_conversions.get(source);
+    let lazy_message_fields = None;
     let lite_runtime = None;
     let gen_mod_rs = None;
     let inside_protobuf = None;
+    let prettyplease = None;
     Customize {
         expose_oneof,
         expose_fields,
@@ -166,9 +258,16 @@ pub fn customize_from_rustproto_for_message(source: &MessageOptions) -> Customiz
         carllerche_bytes_for_string,
         serde_derive,
         serde_derive_cfg,
+        rkyv_derive,
+        rkyv_derive_cfg,
+        impl_bytes_conversions,
+        lazy_message_fields,
         lite_runtime,
         gen_mod_rs,
         inside_protobuf,
+        prettyplease,
+        type_attributes: Vec::new(),
+        field_attributes: Vec::new(),
         _future_options: (),
     }
 }
@@ -183,9 +282,14 @@ pub fn customize_from_rustproto_for_field(source: &FieldOptions) -> Customize {
         rustproto::exts::carllerche_bytes_for_string_field.get(source);
     let serde_derive = None;
     let serde_derive_cfg = None;
+    let rkyv_derive = None;
+    let rkyv_derive_cfg = None;
+    let impl_bytes_conversions = None;
+    let lazy_message_fields = None;
     let lite_runtime = None;
     let gen_mod_rs = None;
     let inside_protobuf = None;
+    let prettyplease = None;
     Customize {
         expose_oneof,
         expose_fields,
@@ -195,9 +299,16 @@ pub fn customize_from_rustproto_for_field(source: &FieldOptions) -> Customize {
         carllerche_bytes_for_string,
         serde_derive,
         serde_derive_cfg,
+        rkyv_derive,
+        rkyv_derive_cfg,
+        impl_bytes_conversions,
+        lazy_message_fields,
         lite_runtime,
         gen_mod_rs,
         inside_protobuf,
+        prettyplease,
+        type_attributes: Vec::new(),
+        field_attributes: Vec::new(),
         _future_options: (),
     }
 }
@@ -211,9 +322,14 @@ pub fn customize_from_rustproto_for_file(source: &FileOptions) -> Customize {
     let carllerche_bytes_for_string = rustproto::exts::carllerche_bytes_for_string_all.get(source);
     let serde_derive = rustproto::exts::serde_derive_all.get(source);
     let serde_derive_cfg = rustproto::exts::serde_derive_cfg_all.get(source);
+    let rkyv_derive = rustproto::exts::rkyv_derive_all.get(source);
+    let rkyv_derive_cfg = rustproto::exts::rkyv_derive_cfg_all.get(source);
+    let impl_bytes_conversions = rustproto::exts::impl_bytes_conversions_all.get(source);
+    let lazy_message_fields = None;
     let lite_runtime = rustproto::exts::lite_runtime_all.get(source);
     let gen_mod_rs = None;
     let inside_protobuf = None;
+    let prettyplease = None;
     Customize {
         expose_oneof,
         expose_fields,
@@ -223,9 +339,16 @@ pub fn customize_from_rustproto_for_file(source: &FileOptions) -> Customize {
         carllerche_bytes_for_string,
         serde_derive,
         serde_derive_cfg,
+        rkyv_derive,
+        rkyv_derive_cfg,
+        impl_bytes_conversions,
+        lazy_message_fields,
         lite_runtime,
         inside_protobuf,
         gen_mod_rs,
+        prettyplease,
+        type_attributes: Vec::new(),
+        field_attributes: Vec::new(),
         _future_options: (),
     }
 }
@@ -8,6 +8,7 @@ use protobuf_parse::ProtoPathBuf;
 use crate::compiler_plugin;
 use crate::gen::file::gen_file;
 use crate::gen::mod_rs::gen_mod_rs;
+use crate::gen::pretty::pretty_print;
 use crate::gen::scope::RootScope;
 use crate::gen::well_known_types::gen_well_known_types_mod;
 use crate::Customize;
@@ -51,5 +52,9 @@ pub(crate) fn gen_all(
         results.push(gen_mod_rs(&mods));
     }
 
+    if customize.prettyplease.unwrap_or(false) {
+        pretty_print(&mut results)?;
+    }
+
     Ok(results)
 }
@@ -0,0 +1,52 @@
+use crate::customize::Customize;
+use crate::gen::code_writer::CodeWriter;
+
+/// Does path pattern match protobuf element with given full name.
+///
+/// Both pattern and name start with dot. Pattern `.` matches everything,
+/// and other patterns match the element with the same name
+/// and elements nested in it.
+fn path_matches(pattern: &str, full_name: &str) -> bool {
+    if pattern == "." {
+        return true;
+    }
+    match full_name.strip_prefix(pattern) {
+        Some(rem) => rem.is_empty() || rem.starts_with('.'),
+        None => false,
+    }
+}
+
+fn write_attributes(w: &mut CodeWriter, attributes: &[(String, String)], full_name: &str) {
+    for (pattern, attribute) in attributes {
+        if path_matches(pattern, full_name) {
+            w.write_line(attribute);
+        }
+    }
+}
+
+/// Write attributes for message, enum or oneof enum
+/// specified with [`Customize::type_attributes`].
+pub(crate) fn write_type_attributes(w: &mut CodeWriter, customize: &Customize, full_name: &str) {
+    write_attributes(w, &customize.type_attributes, full_name);
+}
+
+/// Write attributes for message field
+/// specified with [`Customize::field_attributes`].
+pub(crate) fn write_field_attributes(w: &mut CodeWriter, customize: &Customize, full_name: &str) {
+    write_attributes(w, &customize.field_attributes, full_name);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn matches() {
+        assert!(path_matches(".", ".foo.Bar"));
+        assert!(path_matches(".foo", ".foo.Bar"));
+        assert!(path_matches(".foo.Bar", ".foo.Bar"));
+        assert!(path_matches(".foo.Bar", ".foo.Bar.baz"));
+        assert!(!path_matches(".foo.Bar", ".foo.Barr"));
+        assert!(!path_matches(".foo.Bar.baz", ".foo.Bar"));
+    }
+}
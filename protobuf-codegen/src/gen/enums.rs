@@ -2,9 +2,11 @@ use std::collections::HashSet;
 
 use protobuf::descriptor::*;
 
+use crate::gen::attributes;
 use crate::gen::code_writer::*;
 use crate::gen::file_index::FileIndex;
 use crate::gen::inside::protobuf_crate_path;
+use crate::gen::rkyv;
 use crate::gen::rust::EXPR_NONE;
 use crate::gen::rust_name::RustIdent;
 use crate::gen::rust_name::RustIdentWithPath;
@@ -37,6 +39,11 @@ impl<'a> EnumValueGen<'a> {
         self.value.proto.get_proto().get_number()
     }
 
+    // name of enum value in .proto file
+    fn proto_name(&self) -> &str {
+        self.value.proto.get_name()
+    }
+
     // name of enum variant in generated rust code
     pub fn rust_name_inner(&self) -> RustIdent {
         self.value.rust_name()
@@ -138,6 +145,8 @@ impl<'a> EnumGen<'a> {
         w.write_line("");
         self.write_impl_default(w);
         w.write_line("");
+        self.write_impl_from_str(w);
+        w.write_line("");
         self.write_impl_value(w);
         w.write_line("");
         self.write_impl_self(w);
@@ -171,6 +180,12 @@ impl<'a> EnumGen<'a> {
             &self.customize,
             "derive(::serde::Serialize, ::serde::Deserialize)",
         );
+        rkyv::write_rkyv_derive(w, &self.customize);
+        attributes::write_type_attributes(
+            w,
+            &self.customize,
+            &self.enum_with_scope.name_absolute().to_string(),
+        );
         let ref type_name = self.type_name;
         w.expr_block(&format!("pub enum {}", type_name), |w| {
             for value in self.values_all() {
@@ -238,6 +253,37 @@ impl<'a> EnumGen<'a> {
                     },
                 );
 
+                w.write_line("");
+                w.def_fn("name(&self) -> &'static str", |w| {
+                    w.match_expr("*self", |w| {
+                        for value in self.values_all() {
+                            w.case_expr(
+                                &format!("{}", value.rust_name_outer()),
+                                &format!("\"{}\"", value.proto_name()),
+                            );
+                        }
+                    });
+                });
+
+                w.write_line("");
+                w.def_fn(
+                    &format!(
+                        "from_name(name: &str) -> ::std::option::Option<{}>",
+                        type_name
+                    ),
+                    |w| {
+                        w.match_expr("name", |w| {
+                            for value in self.values_all() {
+                                w.write_line(&format!(
+                                    "\"{}\" => ::std::option::Option::Some({}),",
+                                    value.proto_name(),
+                                    value.rust_name_outer()
+                                ));
+                            }
+                            w.write_line(&format!("_ => {}", EXPR_NONE));
+                        });
+                    },
+                );
                 w.write_line("");
                 w.def_fn(&format!("values() -> &'static [Self]"), |w| {
                     w.write_line(&format!("static values: &'static [{}] = &[", type_name));
@@ -347,6 +393,26 @@ impl<'a> EnumGen<'a> {
         });
     }
 
+    fn write_impl_from_str(&self, w: &mut CodeWriter) {
+        w.impl_for_block("::std::str::FromStr", &format!("{}", self.type_name), |w| {
+            w.write_line(&format!(
+                "type Err = {}::ParseEnumError;",
+                protobuf_crate_path(&self.customize)
+            ));
+            w.write_line("");
+            w.def_fn(
+                "from_str(s: &str) -> ::std::result::Result<Self, Self::Err>",
+                |w| {
+                    w.write_line(&format!(
+                        "{}::rt::enum_from_str(\"{}\", s)",
+                        protobuf_crate_path(&self.customize),
+                        self.enum_with_scope.name_to_package(),
+                    ));
+                },
+            );
+        });
+    }
+
     fn write_impl_default(&self, w: &mut CodeWriter) {
         let first_value = &self.enum_with_scope.values()[0];
         if first_value.proto.get_proto().get_number() != 0 {
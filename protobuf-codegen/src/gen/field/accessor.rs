@@ -70,22 +70,24 @@ impl FieldGen<'_> {
         ]
     }
 
-    fn make_accessor_fns_has_get_set(&self) -> Vec<String> {
+    fn make_accessor_fns_has_get_set_clear(&self) -> Vec<String> {
         let message = self.proto_field.message.rust_name();
         vec![
             format!("{}::has_{}", message, self.rust_name),
             format!("{}::get_{}", message, self.rust_name),
             format!("{}::set_{}", message, self.rust_name),
+            format!("{}::clear_{}", message, self.rust_name),
         ]
     }
 
-    fn make_accessor_fns_has_get_mut_set(&self) -> Vec<String> {
+    fn make_accessor_fns_has_get_mut_set_clear(&self) -> Vec<String> {
         let message = self.proto_field.message.rust_name();
         vec![
             format!("{}::has_{}", message, self.rust_name),
             format!("{}::get_{}", message, self.rust_name),
             format!("{}::mut_{}", message, self.rust_name),
             format!("{}::set_{}", message, self.rust_name),
+            format!("{}::clear_{}", message, self.rust_name),
         ]
     }
 
@@ -134,9 +136,17 @@ impl FieldGen<'_> {
     fn accessor_fn_singular_with_flag(
         &self,
         elem: &FieldElem,
-        _option_kind: OptionKind,
+        option_kind: OptionKind,
     ) -> AccessorFn {
         match elem {
+            FieldElem::Message(m) if option_kind == OptionKind::LazyMessage => AccessorFn {
+                name: "make_lazy_message_field_accessor".to_owned(),
+                type_params: vec![format!(
+                    "{}",
+                    m.rust_name_relative(&self.get_file_and_mod())
+                )],
+                callback_params: self.make_accessor_fns_lambda(),
+            },
             FieldElem::Message(m) => AccessorFn {
                 name: "make_message_field_accessor".to_owned(),
                 type_params: vec![format!(
@@ -184,7 +194,7 @@ impl FieldGen<'_> {
             return AccessorFn {
                 name: "make_oneof_copy_has_get_set_simpler_accessors".to_owned(),
                 type_params: vec![format!("_")],
-                callback_params: self.make_accessor_fns_has_get_set(),
+                callback_params: self.make_accessor_fns_has_get_set_clear(),
             };
         }
 
@@ -192,7 +202,7 @@ impl FieldGen<'_> {
             return AccessorFn {
                 name: "make_oneof_copy_has_get_set_simpler_accessors".to_owned(),
                 type_params: vec![format!("_")],
-                callback_params: self.make_accessor_fns_has_get_set(),
+                callback_params: self.make_accessor_fns_has_get_set_clear(),
             };
         }
 
@@ -200,7 +210,7 @@ impl FieldGen<'_> {
             return AccessorFn {
                 name: "make_oneof_message_has_get_mut_set_accessor".to_owned(),
                 type_params: vec![format!("{}", name)],
-                callback_params: self.make_accessor_fns_has_get_mut_set(),
+                callback_params: self.make_accessor_fns_has_get_mut_set_clear(),
             };
         }
 
@@ -208,7 +218,7 @@ impl FieldGen<'_> {
         AccessorFn {
             name: "make_oneof_deref_has_get_set_simpler_accessor".to_owned(),
             type_params: vec![format!("_")],
-            callback_params: self.make_accessor_fns_has_get_set(),
+            callback_params: self.make_accessor_fns_has_get_set_clear(),
         }
     }
 
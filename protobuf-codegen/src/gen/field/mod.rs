@@ -7,6 +7,7 @@ use protobuf_parse::ProtobufAbsolutePath;
 
 use crate::customize::customize_from_rustproto_for_field;
 use crate::customize::Customize;
+use crate::gen::attributes;
 use crate::gen::code_writer::CodeWriter;
 use crate::gen::code_writer::Visibility;
 use crate::gen::file_and_mod::FileAndMod;
@@ -14,6 +15,7 @@ use crate::gen::inside::protobuf_crate_path;
 use crate::gen::map::map_entry;
 use crate::gen::message::RustTypeMessage;
 use crate::gen::oneof::OneofField;
+use crate::gen::rkyv;
 use crate::gen::rust;
 use crate::gen::rust::EXPR_NONE;
 use crate::gen::rust::EXPR_VEC_NEW;
@@ -127,6 +129,23 @@ fn field_type_protobuf_name<'a>(field: &'a FieldDescriptorProto) -> &'a str {
     }
 }
 
+/// Repeated field uses packed encoding: explicit `packed` option is used if present,
+/// otherwise proto3 repeated scalar fields are packed by default.
+fn is_packed(field: &FieldDescriptorProto, syntax: Syntax) -> bool {
+    let options = field.options.get_or_default();
+    if options.has_packed() {
+        return options.get_packed();
+    }
+    syntax == Syntax::PROTO3
+        && match field.get_field_type() {
+            field_descriptor_proto::Type::TYPE_STRING
+            | field_descriptor_proto::Type::TYPE_BYTES
+            | field_descriptor_proto::Type::TYPE_MESSAGE
+            | field_descriptor_proto::Type::TYPE_GROUP => false,
+            _ => true,
+        }
+}
+
 // size of value for type, None if variable
 fn field_type_size(field_type: field_descriptor_proto::Type) -> Option<u32> {
     match field_type {
@@ -144,6 +163,8 @@ pub enum OptionKind {
     Option,
     /// Field is `SingularPtrField<T>`
     MessageField,
+    /// Field is `LazyMessage<T>`
+    LazyMessage,
 }
 
 impl OptionKind {
@@ -152,6 +173,7 @@ impl OptionKind {
         match self {
             OptionKind::Option => RustType::Option(element_type),
             OptionKind::MessageField => RustType::MessageField(element_type),
+            OptionKind::LazyMessage => RustType::LazyMessage(element_type),
         }
     }
 
@@ -160,12 +182,15 @@ impl OptionKind {
         match self {
             OptionKind::Option => RustType::Option(Box::new(element_type.ref_type())),
             OptionKind::MessageField => RustType::MessageField(Box::new(element_type.ref_type())),
+            OptionKind::LazyMessage => RustType::LazyMessage(Box::new(element_type.ref_type())),
         }
     }
 
     fn _as_option_ref(&self, v: &str) -> String {
         match self {
-            OptionKind::Option | OptionKind::MessageField => format!("{}.as_ref()", v),
+            OptionKind::Option | OptionKind::MessageField | OptionKind::LazyMessage => {
+                format!("{}.as_ref()", v)
+            }
         }
     }
 
@@ -189,6 +214,11 @@ impl OptionKind {
                 protobuf_crate_path(customize),
                 value
             ),
+            OptionKind::LazyMessage => format!(
+                "{}::LazyMessage::some({})",
+                protobuf_crate_path(customize),
+                value
+            ),
         }
     }
 }
@@ -626,12 +656,7 @@ impl<'a> FieldGen<'a> {
                 // regular repeated field
                 elem => FieldKind::Repeated(RepeatedField {
                     elem,
-                    packed: field
-                        .field
-                        .get_proto()
-                        .options
-                        .get_or_default()
-                        .get_packed(),
+                    packed: is_packed(field.field.get_proto(), syntax),
                 }),
             }
         } else if let Some(oneof) = field.oneof() {
@@ -645,7 +670,11 @@ impl<'a> FieldGen<'a> {
             } else {
                 let required = field.field.get_proto().get_label()
                     == field_descriptor_proto::Label::LABEL_REQUIRED;
+                let lazy = customize.lazy_message_fields.unwrap_or(false)
+                    && !customize.rkyv_derive.unwrap_or(false)
+                    && field.field.get_proto().options.get_or_default().get_lazy();
                 let option_kind = match field.field.get_proto().get_field_type() {
+                    field_descriptor_proto::Type::TYPE_MESSAGE if lazy => OptionKind::LazyMessage,
                     field_descriptor_proto::Type::TYPE_MESSAGE => OptionKind::MessageField,
                     _ => OptionKind::Option,
                 };
@@ -695,6 +724,21 @@ impl<'a> FieldGen<'a> {
         }
     }
 
+    /// Singular message field stored as `LazyMessage`
+    pub fn is_lazy_message(&self) -> bool {
+        match self.kind {
+            FieldKind::Singular(SingularField {
+                flag:
+                    SingularFieldFlag::WithFlag {
+                        option_kind: OptionKind::LazyMessage,
+                        ..
+                    },
+                ..
+            }) => true,
+            _ => false,
+        }
+    }
+
     fn is_repeated_not_map(&self) -> bool {
         match self.kind {
             FieldKind::Repeated(..) => true,
@@ -1173,6 +1217,18 @@ impl<'a> FieldGen<'a> {
             w.all_documentation(self.info, &self.path);
 
             self.write_serde_attr(w);
+            if self.proto_type == field_descriptor_proto::Type::TYPE_MESSAGE {
+                rkyv::write_rkyv_omit_bounds(w, &self.customize);
+            }
+            attributes::write_field_attributes(
+                w,
+                &self.customize,
+                &format!(
+                    "{}.{}",
+                    self.proto_field.message.name_absolute(),
+                    self.proto_field.name()
+                ),
+            );
             let vis = self.visibility();
             w.field_decl_vis(
                 vis,
@@ -1454,7 +1510,7 @@ impl<'a> FieldGen<'a> {
                 // with default value
                 match singular.flag {
                     SingularFieldFlag::WithFlag { option_kind, .. } => match option_kind {
-                        OptionKind::MessageField => {
+                        OptionKind::MessageField | OptionKind::LazyMessage => {
                             let self_field = self.self_field();
                             w.write_line(&format!("{}.set_default();", self_field));
                         }
@@ -1495,7 +1551,8 @@ impl<'a> FieldGen<'a> {
     fn self_field_vec_packed_fixed_data_size(&self) -> String {
         assert!(self.is_fixed());
         format!(
-            "({}.len() * {}) as u32",
+            "{}::rt::vec_packed_fixed_data_size({}.len(), {})",
+            protobuf_crate_path(&self.customize),
             self.self_field(),
             field_type_size(self.proto_type).unwrap()
         )
@@ -1529,11 +1586,11 @@ impl<'a> FieldGen<'a> {
     fn self_field_vec_packed_fixed_size(&self) -> String {
         // zero is filtered outside
         format!(
-            "{} + {}::rt::compute_raw_varint32_size({}) + {}",
-            self.tag_size(),
+            "{}::rt::vec_packed_fixed_size({}, {}.len(), {})",
             protobuf_crate_path(&self.customize),
-            self.self_field_vec_packed_fixed_data_size(),
-            self.self_field_vec_packed_fixed_data_size()
+            self.proto_field.number(),
+            self.self_field(),
+            field_type_size(self.proto_type).unwrap()
         )
     }
 
@@ -1605,16 +1662,24 @@ impl<'a> FieldGen<'a> {
             FieldElem::Message(..) => "into_field",
             _ => "into",
         };
+        let lazy = match s.flag {
+            SingularFieldFlag::WithFlag {
+                option_kind: OptionKind::LazyMessage,
+                ..
+            } => "lazy_",
+            _ => "",
+        };
         let carllerche = match s.elem.primitive_type_variant() {
             PrimitiveTypeVariant::Carllerche => "carllerche_",
             PrimitiveTypeVariant::Default => "",
         };
         let type_name_for_fn = protobuf_name(self.proto_type);
         w.write_line(&format!(
-            "{}::rt::read_{}_{}{}_{}(wire_type, is, &mut self.{})?;",
+            "{}::rt::read_{}_{}{}{}_{}(wire_type, is, &mut self.{})?;",
             protobuf_crate_path(&self.customize),
             singular_or_proto3,
             carllerche,
+            lazy,
             type_name_for_fn,
             suffix,
             self.rust_name,
@@ -1806,24 +1871,32 @@ impl<'a> FieldGen<'a> {
                 w.write_line(&format!("let len = {}.compute_size();", item_var));
                 let tag_size = self.tag_size();
                 w.write_line(&format!(
-                    "{} += {} + {}::rt::compute_raw_varint32_size(len) + len;",
+                    "{} += {} + {}::rt::compute_raw_varint32_size(len) as u64 + len as u64;",
                     sum_var,
                     tag_size,
                     protobuf_crate_path(&self.customize),
                 ));
             }
             _ => {
-                w.write_line(&format!(
-                    "{} += {};",
-                    sum_var,
-                    self.element_size(item_var, item_var_type)
-                ));
+                let size = self.element_size(item_var, item_var_type);
+                match field_type_size(self.proto_type) {
+                    Some(..) => w.write_line(&format!("{} += {};", sum_var, size)),
+                    None => w.write_line(&format!("{} += {} as u64;", sum_var, size)),
+                }
             }
         }
     }
 
     pub fn write_message_write_field(&self, w: &mut CodeWriter) {
         match self.kind {
+            _ if self.is_lazy_message() => {
+                w.write_line(&format!(
+                    "{}::rt::write_lazy_message_field_with_cached_size({}, &{}, os)?;",
+                    protobuf_crate_path(&self.customize),
+                    self.proto_field.number(),
+                    self.self_field()
+                ));
+            }
             FieldKind::Singular(ref s) => {
                 self.write_if_let_self_field_is_some(s, w, |v, w| {
                     self.write_write_element(w, "os", &v);
@@ -1879,6 +1952,15 @@ impl<'a> FieldGen<'a> {
 
     pub fn write_message_compute_field_size(&self, sum_var: &str, w: &mut CodeWriter) {
         match self.kind {
+            _ if self.is_lazy_message() => {
+                w.write_line(&format!(
+                    "{} += {}::rt::lazy_message_size({}, &{});",
+                    sum_var,
+                    protobuf_crate_path(&self.customize),
+                    self.proto_field.number(),
+                    self.self_field()
+                ));
+            }
             FieldKind::Singular(ref s) => {
                 self.write_if_let_self_field_is_some(s, w, |v, w| {
                     match field_type_size(self.proto_type) {
@@ -1898,7 +1980,7 @@ impl<'a> FieldGen<'a> {
                         let tag_size = self.tag_size();
                         let self_field = self.self_field();
                         w.write_line(&format!(
-                            "{} += {} * {}.len() as u32;",
+                            "{} += {} * {}.len() as u64;",
                             sum_var,
                             (s + tag_size) as isize,
                             self_field
@@ -1915,7 +1997,7 @@ impl<'a> FieldGen<'a> {
                 ref key, ref value, ..
             }) => {
                 w.write_line(&format!(
-                    "{} += {}::rt::compute_map_size::<{}, {}>({}, &{});",
+                    "{} += {}::rt::compute_map_size::<{}, {}>({}, &{}) as u64;",
                     sum_var,
                     protobuf_crate_path(&self.customize),
                     key.lib_protobuf_type(&self.get_file_and_mod()),
@@ -1927,7 +2009,7 @@ impl<'a> FieldGen<'a> {
             FieldKind::Repeated(RepeatedField { packed: true, .. }) => {
                 self.write_if_self_field_is_not_empty(w, |w| {
                     let size_expr = self.self_field_vec_packed_size();
-                    w.write_line(&format!("{} += {};", sum_var, size_expr));
+                    w.write_line(&format!("{} += {} as u64;", sum_var, size_expr));
                 });
             }
             FieldKind::Oneof(..) => unreachable!(),
@@ -2238,6 +2320,183 @@ impl<'a> FieldGen<'a> {
         );
     }
 
+    fn is_repeated_message(&self) -> bool {
+        match self.kind {
+            FieldKind::Repeated(RepeatedField {
+                elem: FieldElem::Message(..),
+                ..
+            }) => true,
+            _ => false,
+        }
+    }
+
+    fn write_message_field_set_from_iter(&self, w: &mut CodeWriter) {
+        let elem_type = self.elem().rust_storage_elem_type(
+            &self
+                .proto_field
+                .message
+                .scope
+                .get_file_and_mod(self.customize.clone()),
+        );
+        w.comment("Replace field contents with elements produced by iterator");
+        w.pub_fn(
+            &format!(
+                "set_{}_from_iter(&mut self, iter: impl ::std::iter::IntoIterator<Item = {}>)",
+                self.rust_name,
+                elem_type.to_code(&self.customize)
+            ),
+            |w| {
+                let self_field = self.self_field();
+                w.write_line(&format!("{}.clear();", self_field));
+                w.write_line(&format!("{}.extend(iter);", self_field));
+            },
+        );
+    }
+
+    fn write_message_field_add(&self, w: &mut CodeWriter) {
+        let elem_type = self.elem().rust_storage_elem_type(
+            &self
+                .proto_field
+                .message
+                .scope
+                .get_file_and_mod(self.customize.clone()),
+        );
+        w.comment("Append element to the field, return a mutable reference to it");
+        w.pub_fn(
+            &format!(
+                "add_{}(&mut self, v: {}) -> &mut {}",
+                self.rust_name,
+                elem_type.to_code(&self.customize),
+                elem_type.to_code(&self.customize)
+            ),
+            |w| {
+                let self_field = self.self_field();
+                w.write_line(&format!("{}.push(v);", self_field));
+                w.write_line(&format!("{}.last_mut().unwrap()", self_field));
+            },
+        );
+    }
+
+    fn singular_message_field(&self) -> Option<&SingularField> {
+        match self.kind {
+            FieldKind::Singular(
+                ref s @ SingularField {
+                    elem: FieldElem::Message(..),
+                    flag: SingularFieldFlag::WithFlag { .. },
+                },
+            ) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn write_message_field_opt(&self, w: &mut CodeWriter) {
+        let elem_type = self.elem().rust_storage_elem_type(
+            &self
+                .proto_field
+                .message
+                .scope
+                .get_file_and_mod(self.customize.clone()),
+        );
+        w.comment("Reference to the field value if the field is set");
+        w.pub_fn(
+            &format!(
+                "{}_opt(&self) -> ::std::option::Option<&{}>",
+                self.rust_name,
+                elem_type.to_code(&self.customize)
+            ),
+            |w| {
+                w.write_line(&format!("{}.as_ref()", self.self_field()));
+            },
+        );
+    }
+
+    fn write_message_field_mut_or_insert_with(&self, s: &SingularField, w: &mut CodeWriter) {
+        let elem_type = self.elem().rust_storage_elem_type(
+            &self
+                .proto_field
+                .message
+                .scope
+                .get_file_and_mod(self.customize.clone()),
+        );
+        w.comment("Mutable pointer to the field.");
+        w.comment(
+            "If field is not initialized, it is initialized with value returned by `f` first.",
+        );
+        w.pub_fn(
+            &format!(
+                "{}_mut_or_insert_with(&mut self, f: impl ::std::ops::FnOnce() -> {}) -> &mut {}",
+                self.rust_name,
+                elem_type.to_code(&self.customize),
+                elem_type.to_code(&self.customize)
+            ),
+            |w| {
+                self.write_if_self_field_is_none(w, |w| {
+                    self.write_self_field_assign_some(w, s, "f()");
+                });
+                w.write_line(&format!("{}.as_mut().unwrap()", self.self_field()));
+            },
+        );
+    }
+
+    /// Repeated enum field which values can be stored in `ProtobufEnumBitSet`.
+    fn repeated_enum_bit_set_elem(&self) -> Option<&FieldElemEnum> {
+        match self.kind {
+            FieldKind::Repeated(RepeatedField {
+                elem: FieldElem::Enum(ref e),
+                ..
+            }) if e
+                .default_value
+                .en
+                .values()
+                .iter()
+                .all(|v| (0..64).contains(&v.proto.get_proto().get_number())) =>
+            {
+                Some(e)
+            }
+            _ => None,
+        }
+    }
+
+    fn write_message_field_bits(&self, e: &FieldElemEnum, w: &mut CodeWriter) {
+        let enum_type = e.enum_rust_type(
+            &self
+                .proto_field
+                .message
+                .scope
+                .get_file_and_mod(self.customize.clone()),
+        );
+        let bit_set_type = format!(
+            "{}::ProtobufEnumBitSet<{}>",
+            protobuf_crate_path(&self.customize),
+            enum_type.to_code(&self.customize)
+        );
+        w.comment("Field value as a bit set, `None` if any value number is not in `0..64`");
+        w.pub_fn(
+            &format!(
+                "get_{}_bits(&self) -> ::std::option::Option<{}>",
+                self.rust_name, bit_set_type
+            ),
+            |w| {
+                w.write_line(&format!(
+                    "{}::ProtobufEnumBitSet::from_repeated(&{})",
+                    protobuf_crate_path(&self.customize),
+                    self.self_field()
+                ));
+            },
+        );
+        w.write_line("");
+        w.comment("Replace field value with values from the bit set");
+        w.pub_fn(
+            &format!(
+                "set_{}_bits(&mut self, bits: {})",
+                self.rust_name, bit_set_type
+            ),
+            |w| {
+                w.write_line(&format!("{} = bits.to_repeated();", self.self_field()));
+            },
+        );
+    }
+
     fn write_message_field_mut_singular(&self, s: &SingularField, w: &mut CodeWriter) {
         match s {
             SingularField {
@@ -2540,11 +2799,32 @@ impl<'a> FieldGen<'a> {
         w.write_line("");
         self.write_message_field_set(w);
 
+        if self.is_repeated_message() {
+            w.write_line("");
+            self.write_message_field_set_from_iter(w);
+
+            w.write_line("");
+            self.write_message_field_add(w);
+        }
+
         if self.has_mut() {
             w.write_line("");
             self.write_message_field_mut(w);
         }
 
+        if let Some(e) = self.repeated_enum_bit_set_elem() {
+            w.write_line("");
+            self.write_message_field_bits(e, w);
+        }
+
+        if let Some(s) = self.singular_message_field() {
+            w.write_line("");
+            self.write_message_field_opt(w);
+
+            w.write_line("");
+            self.write_message_field_mut_or_insert_with(s, w);
+        }
+
         if self.has_take() {
             w.write_line("");
             self.write_message_field_take(w);
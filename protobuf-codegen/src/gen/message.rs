@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt;
 
 use protobuf::descriptor::*;
@@ -6,6 +8,7 @@ use protobuf::reflect::MessageDescriptor;
 use protobuf_parse::snake_case;
 
 use crate::customize::customize_from_rustproto_for_message;
+use crate::gen::attributes;
 use crate::gen::code_writer::*;
 use crate::gen::enums::*;
 use crate::gen::field::FieldGen;
@@ -15,6 +18,7 @@ use crate::gen::file_index::FileIndex;
 use crate::gen::inside::protobuf_crate_path;
 use crate::gen::oneof::OneofGen;
 use crate::gen::oneof::OneofVariantGen;
+use crate::gen::rkyv;
 use crate::gen::rust::is_rust_keyword;
 use crate::gen::rust::EXPR_NONE;
 use crate::gen::rust::EXPR_VEC_NEW;
@@ -331,7 +335,9 @@ impl<'a> MessageGen<'a> {
         w.allow(&["unused_variables"]);
         w.def_fn("compute_size(&self) -> u32", |w| {
             // To have access to its methods but not polute the name space.
-            w.write_line("let mut my_size = 0;");
+            // Sizes are accumulated in `u64` to not overflow on huge messages,
+            // and saturated to `u32` in the end.
+            w.write_line("let mut my_size: u64 = 0;");
             for field in self.fields_except_oneof_and_group() {
                 field.write_message_compute_field_size("my_size", w);
             }
@@ -339,7 +345,11 @@ impl<'a> MessageGen<'a> {
                 variant.field.write_element_size(w, v, vtype, "my_size");
             });
             w.write_line(&format!(
-                "my_size += {}::rt::unknown_fields_size(self.get_unknown_fields());",
+                "my_size += {}::rt::unknown_fields_size(self.get_unknown_fields()) as u64;",
+                protobuf_crate_path(&self.customize)
+            ));
+            w.write_line(&format!(
+                "let my_size = {}::rt::saturating_size(my_size);",
                 protobuf_crate_path(&self.customize)
             ));
             w.write_line("self.cached_size.set(my_size);");
@@ -353,6 +363,73 @@ impl<'a> MessageGen<'a> {
         }
     }
 
+    /// Fields with names of their number constants.
+    ///
+    /// Field names which differ only in case or underscores (like `fooBar` and `foo_bar`)
+    /// map to the same constant name, such names are suffixed with field number.
+    fn fields_with_number_const_names(&self) -> Vec<(&FieldGen, String)> {
+        let names: Vec<String> = self
+            .fields
+            .iter()
+            .map(|f| field_number_const_name(f.proto_field.name()))
+            .collect();
+        let mut count = HashMap::new();
+        for name in &names {
+            *count.entry(name.as_str()).or_insert(0) += 1;
+        }
+        let mut taken: HashSet<String> = names
+            .iter()
+            .filter(|name| count[name.as_str()] == 1)
+            .cloned()
+            .collect();
+        self.fields
+            .iter()
+            .zip(&names)
+            .map(|(f, name)| {
+                if count[name.as_str()] == 1 {
+                    return (f, name.clone());
+                }
+                let mut name = format!("{}_{}", name, f.proto_field.number());
+                while !taken.insert(name.clone()) {
+                    name.push('_');
+                }
+                (f, name)
+            })
+            .collect()
+    }
+
+    fn write_field_number_consts(&self, w: &mut CodeWriter) {
+        for (f, name) in self.fields_with_number_const_names() {
+            w.write_line("");
+            w.write_line(&format!(
+                "/// Field number of `{}` field.",
+                f.proto_field.name()
+            ));
+            w.pub_const(
+                &format!("FIELD_{}_NUMBER", name),
+                "u32",
+                &f.proto_field.number().to_string(),
+            );
+        }
+    }
+
+    /// `field_numbers` module is not generated if nested message module has the same name.
+    fn write_field_numbers_mod(&self, w: &mut CodeWriter) {
+        w.write_line(&format!(
+            "/// Field numbers of message `{}`",
+            self.message.message.get_name()
+        ));
+        w.pub_mod("field_numbers", |w| {
+            for (f, name) in self.fields_with_number_const_names() {
+                w.write_line(&format!(
+                    "/// Field number of `{}` field.",
+                    f.proto_field.name()
+                ));
+                w.pub_const(&name, "u32", &f.proto_field.number().to_string());
+            }
+        });
+    }
+
     fn write_impl_self(&self, w: &mut CodeWriter) {
         w.impl_self_block(&format!("{}", self.type_name), |w| {
             // TODO: new should probably be a part of Message trait
@@ -360,6 +437,8 @@ impl<'a> MessageGen<'a> {
                 w.write_line("::std::default::Default::default()");
             });
 
+            self.write_field_number_consts(w);
+
             self.write_field_accessors(w);
             w.write_line("");
             self.write_generated_message_descriptor_data(w);
@@ -473,6 +552,14 @@ impl<'a> MessageGen<'a> {
                     continue;
                 }
 
+                // Do not parse lazy message just to check it
+                if f.is_lazy_message() {
+                    w.if_stmt(format!("!self.{}.is_initialized()", f.rust_name), |w| {
+                        w.write_line("return false;");
+                    });
+                    continue;
+                }
+
                 // TODO:
                 // if message is declared in this file and has no message fields,
                 // we could skip the check here
@@ -546,6 +633,48 @@ impl<'a> MessageGen<'a> {
         });
     }
 
+    fn write_impl_bytes_conversions(&self, w: &mut CodeWriter) {
+        let protobuf_crate = protobuf_crate_path(&self.customize);
+        w.impl_for_block(
+            "::std::convert::TryFrom<&[u8]>",
+            &format!("{}", self.type_name),
+            |w| {
+                w.write_line(&format!("type Error = {}::ProtobufError;", protobuf_crate));
+                w.write_line("");
+                w.def_fn(
+                    "try_from(bytes: &[u8]) -> ::std::result::Result<Self, Self::Error>",
+                    |w| {
+                        w.write_line(&format!(
+                            "<Self as {}::Message>::parse_from_bytes(bytes)",
+                            protobuf_crate
+                        ));
+                    },
+                );
+            },
+        );
+        w.write_line("");
+        w.impl_for_block(
+            &format!("::std::convert::TryFrom<&{}>", self.type_name),
+            "::std::vec::Vec<u8>",
+            |w| {
+                w.write_line(&format!("type Error = {}::ProtobufError;", protobuf_crate));
+                w.write_line("");
+                w.def_fn(
+                    &format!(
+                        "try_from(message: &{}) -> ::std::result::Result<Self, Self::Error>",
+                        self.type_name
+                    ),
+                    |w| {
+                        w.write_line(&format!(
+                            "{}::Message::write_to_bytes(message)",
+                            protobuf_crate
+                        ));
+                    },
+                );
+            },
+        );
+    }
+
     fn write_impl_clear(&self, w: &mut CodeWriter) {
         w.impl_for_block(
             &format!("{}::Clear", protobuf_crate_path(&self.customize)),
@@ -582,6 +711,12 @@ impl<'a> MessageGen<'a> {
             &self.customize,
             "derive(::serde::Serialize, ::serde::Deserialize)",
         );
+        rkyv::write_rkyv_derive_message(w, &self.customize);
+        attributes::write_type_attributes(
+            w,
+            &self.customize,
+            &self.message.name_absolute().to_string(),
+        );
         w.pub_struct(&format!("{}", self.type_name), |w| {
             if !self.fields_except_oneof().is_empty() {
                 w.comment("message fields");
@@ -596,6 +731,7 @@ impl<'a> MessageGen<'a> {
                         true => Visibility::Public,
                         false => Visibility::Default,
                     };
+                    rkyv::write_rkyv_omit_bounds(w, &self.customize);
                     w.field_decl_vis(
                         vis,
                         &oneof.oneof.field_name().to_string(),
@@ -606,11 +742,13 @@ impl<'a> MessageGen<'a> {
             w.comment("special fields");
 
             serde::write_serde_attr(w, &self.customize, "serde(skip)");
+            rkyv::write_rkyv_attr(w, &self.customize, "with(::rkyv::with::Skip)");
             w.pub_field_decl(
                 "unknown_fields",
                 &format!("{}::UnknownFields", protobuf_crate_path(&self.customize)),
             );
             serde::write_serde_attr(w, &self.customize, "serde(skip)");
+            rkyv::write_rkyv_attr(w, &self.customize, "with(::rkyv::with::Skip)");
             w.pub_field_decl(
                 "cached_size",
                 &format!("{}::rt::CachedSize", protobuf_crate_path(&self.customize)),
@@ -672,6 +810,10 @@ impl<'a> MessageGen<'a> {
         }
         w.write_line("");
         self.write_impl_value(w);
+        if self.customize.impl_bytes_conversions.unwrap_or(false) {
+            w.write_line("");
+            self.write_impl_bytes_conversions(w);
+        }
 
         let mod_name = message_name_to_nested_mod_name(&self.message.message.get_name());
 
@@ -687,8 +829,16 @@ impl<'a> MessageGen<'a> {
             })
             .collect();
         let nested_enums = self.message.to_scope().get_enums();
+        let field_numbers_mod = !self.fields.is_empty()
+            && nested_messages.iter().all(|nested| {
+                message_name_to_nested_mod_name(nested.message.get_name()).get() != "field_numbers"
+            });
 
-        if !oneofs.is_empty() || !nested_messages.is_empty() || !nested_enums.is_empty() {
+        if !oneofs.is_empty()
+            || !nested_messages.is_empty()
+            || !nested_enums.is_empty()
+            || field_numbers_mod
+        {
             w.write_line("");
             w.write_line(&format!(
                 "/// Nested message and enums of message `{}`",
@@ -697,6 +847,11 @@ impl<'a> MessageGen<'a> {
             w.pub_mod(mod_name.get(), |w| {
                 let mut first = true;
 
+                if field_numbers_mod {
+                    w.write_line("");
+                    self.write_field_numbers_mod(w);
+                }
+
                 for oneof in &oneofs {
                     w.write_line("");
                     oneof.write(w);
@@ -767,6 +922,11 @@ impl<'a> MessageGen<'a> {
     }
 }
 
+/// Field name converted to `UPPER_SNAKE_CASE` for constant names.
+fn field_number_const_name(field_name: &str) -> String {
+    snake_case(field_name).to_uppercase()
+}
+
 pub(crate) fn message_name_to_nested_mod_name(message_name: &str) -> RustIdent {
     let mut mod_name = snake_case(message_name);
     if is_rust_keyword(&mod_name) {
@@ -1,4 +1,5 @@
 pub(crate) mod all;
+mod attributes;
 pub(crate) mod code_writer;
 pub(crate) mod enums;
 pub(crate) mod extensions;
@@ -13,6 +14,8 @@ pub(crate) mod message;
 pub(crate) mod mod_rs;
 pub(crate) mod oneof;
 pub(crate) mod paths;
+mod pretty;
+pub(crate) mod rkyv;
 pub(crate) mod rust;
 pub(crate) mod rust_name;
 pub(crate) mod rust_types_values;
@@ -6,7 +6,11 @@ pub(crate) fn gen_mod_rs(mods: &[String]) -> compiler_plugin::GenResult {
     let mut w = CodeWriter::new(&mut v);
     w.comment(&format!("{}generated", "@"));
     w.write_line("");
-    for m in mods {
+    // sorted, so `mod.rs` does not change when files are passed in different order
+    let mut mods = mods.to_vec();
+    mods.sort();
+    mods.dedup();
+    for m in &mods {
         w.write_line(&format!("pub mod {};", m));
     }
     drop(w);
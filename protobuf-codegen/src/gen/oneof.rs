@@ -6,12 +6,14 @@ use protobuf::descriptor::field_descriptor_proto;
 use protobuf_parse::ProtobufAbsolutePath;
 
 use crate::customize::Customize;
+use crate::gen::attributes;
 use crate::gen::code_writer::CodeWriter;
 use crate::gen::field::FieldElem;
 use crate::gen::field::FieldGen;
 use crate::gen::file_and_mod::FileAndMod;
 use crate::gen::inside::protobuf_crate_path;
 use crate::gen::message::MessageGen;
+use crate::gen::rkyv;
 use crate::gen::rust_name::RustIdent;
 use crate::gen::rust_name::RustIdentWithPath;
 use crate::gen::rust_name::RustPath;
@@ -243,6 +245,16 @@ impl<'a> OneofGen<'a> {
             &self.customize,
             "derive(::serde::Serialize, ::serde::Deserialize)",
         );
+        rkyv::write_rkyv_derive(w, &self.customize);
+        attributes::write_type_attributes(
+            w,
+            &self.customize,
+            &format!(
+                "{}.{}",
+                self.oneof.message.name_absolute(),
+                self.oneof.oneof.get_name()
+            ),
+        );
         w.pub_enum(&self.oneof.rust_name().ident.to_string(), |w| {
             for variant in self.variants_except_group() {
                 w.write_line(&format!(
@@ -0,0 +1,56 @@
+use crate::compiler_plugin;
+
+/// Leading `//` comments, which are dropped by `prettyplease`.
+#[cfg(feature = "with-prettyplease")]
+fn header_comments(content: &str) -> String {
+    content
+        .lines()
+        .take_while(|l| l.starts_with("//") && !l.starts_with("//!"))
+        .map(|l| format!("{}\n", l))
+        .collect()
+}
+
+#[cfg(feature = "with-prettyplease")]
+fn format(content: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let content = std::str::from_utf8(content)?;
+    let file = syn::parse_file(content)?;
+    let mut r = header_comments(content);
+    if !r.is_empty() {
+        r.push('\n');
+    }
+    r.push_str(&prettyplease::unparse(&file));
+    Ok(r.into_bytes())
+}
+
+#[cfg(not(feature = "with-prettyplease"))]
+fn format(_content: &[u8]) -> anyhow::Result<Vec<u8>> {
+    Err(anyhow::anyhow!(
+        "`prettyplease` option requires `with-prettyplease` feature of `protobuf-codegen`"
+    ))
+}
+
+/// Format generated `.rs` files with `prettyplease`.
+///
+/// Regular `//` comments except file header are lost, doc comments are preserved.
+pub(crate) fn pretty_print(results: &mut [compiler_plugin::GenResult]) -> anyhow::Result<()> {
+    for result in results {
+        if result.name.ends_with(".rs") {
+            result.content = format(&result.content)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "with-prettyplease"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn format_keeps_header() {
+        let formatted = format(b"// @generated\n\n/// Doc\npub struct   A{x:u32}").unwrap();
+        assert_eq!(
+            "// @generated\n\n/// Doc\npub struct A {\n    x: u32,\n}\n",
+            String::from_utf8(formatted).unwrap()
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use crate::customize::Customize;
+use crate::gen::code_writer::CodeWriter;
+
+/// Write rkyv attr according to specified codegen option.
+pub(crate) fn write_rkyv_attr(w: &mut CodeWriter, customize: &Customize, attr: &str) {
+    if customize.rkyv_derive.unwrap_or(false) {
+        if let Some(ref cfg) = customize.rkyv_derive_cfg {
+            w.write_line(&format!("#[cfg_attr({}, {})]", cfg, attr));
+        } else {
+            w.write_line(&format!("#[{}]", attr));
+        }
+    }
+}
+
+/// Write derive of rkyv traits for message or enum.
+pub(crate) fn write_rkyv_derive(w: &mut CodeWriter, customize: &Customize) {
+    write_rkyv_attr(
+        w,
+        customize,
+        "derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize)",
+    );
+    write_rkyv_attr(w, customize, "archive(check_bytes)");
+}
+
+/// Write derive of rkyv traits for message.
+///
+/// Message-typed fields are written with omitted bounds
+/// (so recursive messages are supported), thus bounds are specified explicitly.
+pub(crate) fn write_rkyv_derive_message(w: &mut CodeWriter, customize: &Customize) {
+    write_rkyv_derive(w, customize);
+    write_rkyv_attr(
+        w,
+        customize,
+        "archive(bound(serialize = \"__S: ::rkyv::ser::Serializer + ::rkyv::ser::ScratchSpace\"))",
+    );
+    write_rkyv_attr(
+        w,
+        customize,
+        "archive_attr(check_bytes(bound = \"__C: ::rkyv::validation::ArchiveContext, \
+         <__C as ::rkyv::Fallible>::Error: ::std::error::Error\"))",
+    );
+}
+
+/// Write rkyv attrs for message-typed field or oneof field.
+pub(crate) fn write_rkyv_omit_bounds(w: &mut CodeWriter, customize: &Customize) {
+    write_rkyv_attr(w, customize, "omit_bounds");
+    write_rkyv_attr(w, customize, "archive_attr(omit_bounds)");
+}
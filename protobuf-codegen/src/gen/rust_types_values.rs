@@ -34,6 +34,7 @@ pub(crate) enum RustType {
     Str,
     Option(Box<RustType>),
     MessageField(Box<RustType>),
+    LazyMessage(Box<RustType>),
     // Box<T>
     Uniq(Box<RustType>),
     // &T
@@ -79,6 +80,11 @@ impl RustType {
                 protobuf_crate_path(customize),
                 param.to_code(customize)
             ),
+            RustType::LazyMessage(ref param) => format!(
+                "{}::LazyMessage<{}>",
+                protobuf_crate_path(customize),
+                param.to_code(customize)
+            ),
             RustType::Uniq(ref param) => format!("::std::boxed::Box<{}>", param.to_code(customize)),
             RustType::Ref(ref param) => format!("&{}", param.to_code(customize)),
             RustType::Message(ref name) => format!("{}", name),
@@ -207,6 +213,9 @@ impl RustType {
             RustType::MessageField(..) => {
                 format!("{}::MessageField::none()", protobuf_crate_path(customize))
             }
+            RustType::LazyMessage(..) => {
+                format!("{}::LazyMessage::none()", protobuf_crate_path(customize))
+            }
             RustType::Message(ref name) => format!("{}::new()", name),
             RustType::Ref(ref m) if m.is_message() => match **m {
                 RustType::Message(ref name) => name.default_instance(customize),
@@ -244,6 +253,7 @@ impl RustType {
             | RustType::Bytes
             | RustType::String
             | RustType::MessageField(..)
+            | RustType::LazyMessage(..)
             | RustType::HashMap(..) => format!("{}.clear()", v),
             RustType::Chars => format!(
                 "{}::Clear::clear(&mut {})",
@@ -379,7 +389,7 @@ impl RustType {
     pub fn elem_type(&self) -> RustType {
         match self {
             &RustType::Option(ref ty) => (**ty).clone(),
-            &RustType::MessageField(ref ty) => (**ty).clone(),
+            &RustType::MessageField(ref ty) | &RustType::LazyMessage(ref ty) => (**ty).clone(),
             x => panic!("cannot get elem type of {:?}", x),
         }
     }
@@ -389,7 +399,8 @@ impl RustType {
         match self {
             &RustType::Vec(ref ty)
             | &RustType::Option(ref ty)
-            | &RustType::MessageField(ref ty) => RustType::Ref(ty.clone()),
+            | &RustType::MessageField(ref ty)
+            | &RustType::LazyMessage(ref ty) => RustType::Ref(ty.clone()),
             x => panic!("cannot iterate {:?}", x),
         }
     }
@@ -0,0 +1,195 @@
+//! Derive `protobuf::describe::DescribeMessage` for structs with named fields.
+//!
+//! ```
+//! use protobuf::describe::FileDescriptorBuilder;
+//! use protobuf_describe_derive::DescribeMessage;
+//!
+//! #[derive(DescribeMessage)]
+//! #[proto(name = "Person")]
+//! struct User {
+//!     name: String,
+//!     #[proto(number = 5)]
+//!     email: Option<String>,
+//!     // number 6
+//!     tags: Vec<String>,
+//!     #[proto(skip)]
+//!     cache: Vec<u8>,
+//! }
+//!
+//! let mut builder = FileDescriptorBuilder::new("user.proto", "app");
+//! builder.add_message::<User>();
+//! let file = builder.build();
+//! assert_eq!("Person", file.message_type[0].get_name());
+//! ```
+//!
+//! Struct attributes:
+//! * `#[proto(name = "Name")]` — message name, struct name by default.
+//!
+//! Field attributes:
+//! * `#[proto(number = N)]` — field number, by default previous field number plus one,
+//!   starting with `1`.
+//! * `#[proto(skip)]` — do not describe the field.
+//!
+//! Field numbers must be unique and valid in a `.proto` file,
+//! so this does not compile:
+//!
+//! ```compile_fail
+//! use protobuf_describe_derive::DescribeMessage;
+//!
+//! #[derive(DescribeMessage)]
+//! struct User {
+//!     #[proto(number = 2)]
+//!     name: String,
+//!     #[proto(number = 2)]
+//!     email: String,
+//! }
+//! ```
+
+use std::collections::HashSet;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse_macro_input;
+use syn::Attribute;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Fields;
+use syn::LitInt;
+use syn::LitStr;
+
+/// Derive `DescribeMessage` and `DescribeField` for a struct.
+#[proc_macro_derive(DescribeMessage, attributes(proto))]
+pub fn derive_describe_message(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match derive(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+#[derive(Default)]
+struct ProtoAttrs {
+    name: Option<String>,
+    number: Option<u32>,
+    skip: bool,
+}
+
+fn parse_attrs(attrs: &[Attribute]) -> syn::Result<ProtoAttrs> {
+    let mut r = ProtoAttrs::default();
+    for attr in attrs {
+        if !attr.path().is_ident("proto") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                let name: LitStr = meta.value()?.parse()?;
+                r.name = Some(name.value());
+            } else if meta.path.is_ident("number") {
+                let number: LitInt = meta.value()?.parse()?;
+                r.number = Some(number.base10_parse()?);
+            } else if meta.path.is_ident("skip") {
+                r.skip = true;
+            } else {
+                return Err(meta.error("unknown proto attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(r)
+}
+
+/// Field number can be used in a `.proto` file:
+/// in range `1..=536870911`, but not in reserved range `19000..=19999`.
+fn is_valid_field_number(number: u32) -> bool {
+    (1..=0x1fffffff).contains(&number) && !(19000..=19999).contains(&number)
+}
+
+fn derive(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "DescribeMessage can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "DescribeMessage can only be derived for structs",
+            ))
+        }
+    };
+
+    let struct_attrs = parse_attrs(&input.attrs)?;
+    if struct_attrs.number.is_some() || struct_attrs.skip {
+        return Err(syn::Error::new_spanned(
+            input,
+            "only `name` attribute is allowed on struct",
+        ));
+    }
+    let message_name = struct_attrs.name.unwrap_or_else(|| input.ident.to_string());
+
+    let mut number = 0;
+    let mut numbers = HashSet::new();
+    let mut describe_fields = Vec::new();
+    for field in fields {
+        let attrs = parse_attrs(&field.attrs)?;
+        if attrs.name.is_some() {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`name` attribute is not allowed on field",
+            ));
+        }
+        if attrs.skip {
+            continue;
+        }
+        number = match attrs.number {
+            Some(n) => n,
+            None => number + 1,
+        };
+        if !is_valid_field_number(number) {
+            return Err(syn::Error::new_spanned(
+                field,
+                format!("invalid field number {}", number),
+            ));
+        }
+        if !numbers.insert(number) {
+            return Err(syn::Error::new_spanned(
+                field,
+                format!("duplicate field number {}", number),
+            ));
+        }
+        let name = field.ident.as_ref().unwrap().to_string();
+        let name = name.trim_start_matches("r#");
+        let ty = &field.ty;
+        describe_fields.push(quote! {
+            m.field::<#ty>(#name, #number);
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::protobuf::describe::DescribeMessage for #ident #ty_generics #where_clause {
+            fn message_name() -> ::std::string::String {
+                ::std::string::String::from(#message_name)
+            }
+
+            fn describe_fields(m: &mut ::protobuf::describe::MessageBuilder) {
+                #(#describe_fields)*
+            }
+        }
+
+        impl #impl_generics ::protobuf::describe::DescribeField for #ident #ty_generics #where_clause {
+            fn describe_field(
+                file: &mut ::protobuf::describe::FileDescriptorBuilder,
+            ) -> ::protobuf::describe::FieldType {
+                ::protobuf::describe::message_field_type::<Self>(file)
+            }
+        }
+    })
+}
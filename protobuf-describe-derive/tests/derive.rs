@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use protobuf::describe::FileDescriptorBuilder;
+use protobuf::descriptor::field_descriptor_proto::Label;
+use protobuf::descriptor::field_descriptor_proto::Type;
+use protobuf::descriptor::FileDescriptorProto;
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::ReflectValueBox;
+use protobuf_describe_derive::DescribeMessage;
+
+#[derive(DescribeMessage)]
+#[allow(dead_code)]
+struct Node {
+    name: String,
+    #[proto(number = 10)]
+    children: Vec<Node>,
+    parent: Option<Box<Node>>,
+    #[proto(skip)]
+    cache: Vec<u8>,
+    attributes: HashMap<String, Attribute>,
+    r#type: u32,
+}
+
+#[derive(DescribeMessage)]
+#[proto(name = "Attr")]
+#[allow(dead_code)]
+struct Attribute {
+    value: Option<i64>,
+}
+
+fn file() -> FileDescriptorProto {
+    let mut builder = FileDescriptorBuilder::new("node.proto", "n");
+    builder.add_message::<Node>();
+    builder.build()
+}
+
+#[test]
+fn fields() {
+    let file = file();
+    let names: Vec<&str> = file.message_type.iter().map(|m| m.get_name()).collect();
+    assert_eq!(vec!["Attr", "Node"], names);
+
+    let node = &file.message_type[1];
+    let fields: Vec<(&str, i32, Label)> = node
+        .field
+        .iter()
+        .map(|f| (f.get_name(), f.get_number(), f.get_label()))
+        .collect();
+    assert_eq!(
+        vec![
+            ("name", 1, Label::LABEL_OPTIONAL),
+            ("children", 10, Label::LABEL_REPEATED),
+            ("parent", 11, Label::LABEL_OPTIONAL),
+            ("attributes", 12, Label::LABEL_REPEATED),
+            ("type", 13, Label::LABEL_OPTIONAL),
+        ],
+        fields
+    );
+    assert_eq!(".n.Node", node.field[1].get_type_name());
+    assert_eq!(".n.Node.AttributesEntry", node.field[3].get_type_name());
+    assert_eq!(Type::TYPE_UINT32, node.field[4].get_field_type());
+    assert!(!node.field[2].get_proto3_optional());
+
+    let attr = &file.message_type[0];
+    assert!(attr.field[0].get_proto3_optional());
+    assert_eq!("_value", attr.oneof_decl[0].get_name());
+}
+
+#[test]
+fn dynamic() {
+    let file = FileDescriptor::new_dynamic(file(), Vec::new());
+    let node = file.message_by_package_relative_name("Node").unwrap();
+    let mut m = node.new_instance();
+    node.get_field_by_name("name")
+        .unwrap()
+        .set_singular_field(&mut *m, ReflectValueBox::String("root".to_owned()));
+    let bytes = m.write_to_bytes_dyn().unwrap();
+    assert_eq!(b"\x0a\x04root", &bytes[..]);
+}
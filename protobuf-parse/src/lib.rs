@@ -16,6 +16,7 @@ mod protobuf_path;
 mod protobuf_rel_path;
 pub mod protoc;
 pub mod pure;
+pub mod refactor;
 mod rel_path;
 mod test_against_protobuf_protos;
 mod which_parser;
@@ -450,12 +450,22 @@ impl<'a> Resolver<'a> {
         name: &str,
         fields: &[model::WithLoc<model::Field>],
     ) -> ConvertResult<protobuf::descriptor::DescriptorProto> {
+        let mut nested_scope = scope.clone();
+        nested_scope.push_simple(ProtobufIdent::from(name));
+
         let mut output = protobuf::descriptor::DescriptorProto::new();
 
         output.set_name(name.to_owned());
 
         for f in fields {
-            output.field.push(self.field(scope, f, None)?);
+            output.field.push(self.field(&nested_scope, f, None)?);
+            if let model::FieldType::Group(group) = &f.t.typ {
+                output.nested_type.push(self.group_message(
+                    &nested_scope,
+                    &group.name,
+                    &group.fields,
+                )?);
+            }
         }
 
         Ok(output)
@@ -0,0 +1,559 @@
+//! Apply schema refactorings to `FileDescriptorProto`.
+//!
+//! Refactorings operate on descriptors rather than on `.proto` source,
+//! and are validated: after a refactoring is applied, every message
+//! must still contain the same field numbers with the same types,
+//! so the refactored schema stays wire-compatible with the original.
+//!
+//! ```
+//! # use protobuf::descriptor::FileDescriptorProto;
+//! # use protobuf_parse::refactor::*;
+//! # fn foo(file: &mut FileDescriptorProto) -> Result<(), RefactorError> {
+//! apply_refactorings(
+//!     file,
+//!     &[
+//!         RefactorOp::RenameField {
+//!             message: "Outer.Inner".to_owned(),
+//!             field: "old_name".to_owned(),
+//!             new_name: "new_name".to_owned(),
+//!         },
+//!         RefactorOp::MoveIntoOneof {
+//!             message: "Outer".to_owned(),
+//!             field: "url".to_owned(),
+//!             oneof: "source".to_owned(),
+//!         },
+//!     ],
+//! )?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+
+use protobuf::descriptor::field_descriptor_proto;
+use protobuf::descriptor::DescriptorProto;
+use protobuf::descriptor::FieldDescriptorProto;
+use protobuf::descriptor::FileDescriptorProto;
+use protobuf::descriptor::OneofDescriptorProto;
+use protobuf::json::json_name;
+
+/// Single refactoring operation.
+///
+/// `message` is a message name relative to the file package,
+/// with nested messages separated by dots, e.g. `Outer.Inner`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RefactorOp {
+    /// Rename a field.
+    ///
+    /// JSON name of the field is kept (if `json_name` is not set,
+    /// it is set to the name derived from the old field name),
+    /// so JSON representation of messages does not change;
+    /// use [`RefactorOp::SetJsonName`] to change it.
+    RenameField {
+        message: String,
+        field: String,
+        new_name: String,
+    },
+    /// Move a singular field into a oneof, creating the oneof if it does not exist.
+    MoveIntoOneof {
+        message: String,
+        field: String,
+        oneof: String,
+    },
+    /// Change `json_name` of a field.
+    SetJsonName {
+        message: String,
+        field: String,
+        json_name: String,
+    },
+}
+
+/// Refactoring cannot be applied.
+#[derive(Debug, thiserror::Error)]
+pub enum RefactorError {
+    #[error("message `{0}` not found")]
+    MessageNotFound(String),
+    #[error("field `{1}` not found in message `{0}`")]
+    FieldNotFound(String, String),
+    #[error("`{0}` is not a valid identifier")]
+    InvalidIdentifier(String),
+    #[error("name `{1}` is already used in message `{0}`")]
+    NameConflict(String, String),
+    #[error("JSON name `{1}` is already used in message `{0}`")]
+    JsonNameConflict(String, String),
+    #[error("field `{1}` of message `{0}` cannot be moved into oneof: {2}")]
+    CannotMoveIntoOneof(String, String, &'static str),
+    #[error("field number {1} of message `{0}` changed")]
+    FieldNumberChanged(String, i32),
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn find_message<'a>(
+    messages: &'a mut [DescriptorProto],
+    path: &str,
+) -> Option<&'a mut DescriptorProto> {
+    let (first, rem) = match path.find('.') {
+        Some(pos) => (&path[..pos], Some(&path[pos + 1..])),
+        None => (path, None),
+    };
+    let message = messages.iter_mut().find(|m| m.get_name() == first)?;
+    match rem {
+        Some(rem) => find_message(&mut message.nested_type, rem),
+        None => Some(message),
+    }
+}
+
+fn field_index(message: &DescriptorProto, name: &str, path: &str) -> Result<usize, RefactorError> {
+    message
+        .field
+        .iter()
+        .position(|f| f.get_name() == name)
+        .ok_or_else(|| RefactorError::FieldNotFound(path.to_owned(), name.to_owned()))
+}
+
+fn effective_json_name(field: &FieldDescriptorProto) -> String {
+    if field.has_json_name() {
+        field.get_json_name().to_owned()
+    } else {
+        json_name(field.get_name())
+    }
+}
+
+/// Oneof is synthetic if it was generated for a proto3 `optional` field.
+fn is_synthetic_oneof(message: &DescriptorProto, index: i32) -> bool {
+    let mut fields = message
+        .field
+        .iter()
+        .filter(|f| f.has_oneof_index() && f.get_oneof_index() == index)
+        .peekable();
+    fields.peek().is_some() && fields.all(|f| f.get_proto3_optional())
+}
+
+fn rename_field(
+    message: &mut DescriptorProto,
+    path: &str,
+    field: &str,
+    new_name: &str,
+) -> Result<(), RefactorError> {
+    if !is_identifier(new_name) {
+        return Err(RefactorError::InvalidIdentifier(new_name.to_owned()));
+    }
+    let index = field_index(message, field, path)?;
+    let conflict = message
+        .field
+        .iter()
+        .enumerate()
+        .any(|(i, f)| i != index && f.get_name() == new_name);
+    if conflict {
+        return Err(RefactorError::NameConflict(
+            path.to_owned(),
+            new_name.to_owned(),
+        ));
+    }
+    let field = &mut message.field[index];
+    if !field.has_json_name() {
+        let json_name = json_name(field.get_name());
+        field.set_json_name(json_name);
+    }
+    field.set_name(new_name.to_owned());
+    Ok(())
+}
+
+fn set_json_name(
+    message: &mut DescriptorProto,
+    path: &str,
+    field: &str,
+    new_json_name: &str,
+) -> Result<(), RefactorError> {
+    if new_json_name.is_empty() {
+        return Err(RefactorError::InvalidIdentifier(new_json_name.to_owned()));
+    }
+    let index = field_index(message, field, path)?;
+    let conflict = message
+        .field
+        .iter()
+        .enumerate()
+        .any(|(i, f)| i != index && effective_json_name(f) == new_json_name);
+    if conflict {
+        return Err(RefactorError::JsonNameConflict(
+            path.to_owned(),
+            new_json_name.to_owned(),
+        ));
+    }
+    message.field[index].set_json_name(new_json_name.to_owned());
+    Ok(())
+}
+
+fn move_into_oneof(
+    message: &mut DescriptorProto,
+    path: &str,
+    field: &str,
+    oneof: &str,
+) -> Result<(), RefactorError> {
+    if !is_identifier(oneof) {
+        return Err(RefactorError::InvalidIdentifier(oneof.to_owned()));
+    }
+    let index = field_index(message, field, path)?;
+    let cannot_move =
+        |reason| RefactorError::CannotMoveIntoOneof(path.to_owned(), field.to_owned(), reason);
+    {
+        let f = &message.field[index];
+        if f.get_label() == field_descriptor_proto::Label::LABEL_REPEATED {
+            return Err(cannot_move("field is repeated"));
+        }
+        if f.get_label() == field_descriptor_proto::Label::LABEL_REQUIRED {
+            return Err(cannot_move("field is required"));
+        }
+        if f.get_proto3_optional() {
+            return Err(cannot_move("field is proto3 optional"));
+        }
+        if f.has_oneof_index() {
+            return Err(cannot_move("field is already in a oneof"));
+        }
+    }
+
+    let oneof_index = match message
+        .oneof_decl
+        .iter()
+        .position(|o| o.get_name() == oneof)
+    {
+        Some(i) if is_synthetic_oneof(message, i as i32) => {
+            return Err(RefactorError::NameConflict(
+                path.to_owned(),
+                oneof.to_owned(),
+            ));
+        }
+        Some(i) => i as i32,
+        None => {
+            if message.field.iter().any(|f| f.get_name() == oneof) {
+                return Err(RefactorError::NameConflict(
+                    path.to_owned(),
+                    oneof.to_owned(),
+                ));
+            }
+            // Synthetic oneofs must follow all real oneofs.
+            let insert_at = (0..message.oneof_decl.len() as i32)
+                .find(|&i| is_synthetic_oneof(message, i))
+                .unwrap_or(message.oneof_decl.len() as i32);
+            for f in message.field.iter_mut() {
+                if f.has_oneof_index() && f.get_oneof_index() >= insert_at {
+                    let shifted = f.get_oneof_index() + 1;
+                    f.set_oneof_index(shifted);
+                }
+            }
+            let mut decl = OneofDescriptorProto::new();
+            decl.set_name(oneof.to_owned());
+            message.oneof_decl.insert(insert_at as usize, decl);
+            insert_at
+        }
+    };
+
+    // Fields of a oneof must be declared consecutively.
+    let mut f = message.field.remove(index);
+    f.set_oneof_index(oneof_index);
+    let insert_at = message
+        .field
+        .iter()
+        .rposition(|f| f.has_oneof_index() && f.get_oneof_index() == oneof_index)
+        .map(|i| i + 1)
+        .unwrap_or(index);
+    message.field.insert(insert_at, f);
+    Ok(())
+}
+
+type FieldSignature = (
+    field_descriptor_proto::Type,
+    String,
+    field_descriptor_proto::Label,
+);
+
+fn collect_signatures(
+    prefix: &str,
+    messages: &[DescriptorProto],
+    result: &mut BTreeMap<String, BTreeMap<i32, FieldSignature>>,
+) {
+    for message in messages {
+        let path = if prefix.is_empty() {
+            message.get_name().to_owned()
+        } else {
+            format!("{}.{}", prefix, message.get_name())
+        };
+        let fields = message
+            .field
+            .iter()
+            .map(|f| {
+                (
+                    f.get_number(),
+                    (
+                        f.get_field_type(),
+                        f.get_type_name().to_owned(),
+                        f.get_label(),
+                    ),
+                )
+            })
+            .collect();
+        collect_signatures(&path, &message.nested_type, result);
+        result.insert(path, fields);
+    }
+}
+
+/// Check that every message has the same field numbers with the same types.
+fn check_numbers_stable(
+    before: &FileDescriptorProto,
+    after: &FileDescriptorProto,
+) -> Result<(), RefactorError> {
+    let mut before_signatures = BTreeMap::new();
+    let mut after_signatures = BTreeMap::new();
+    collect_signatures("", &before.message_type, &mut before_signatures);
+    collect_signatures("", &after.message_type, &mut after_signatures);
+    for (path, before_fields) in &before_signatures {
+        let after_fields = &after_signatures[path];
+        let numbers: HashSet<i32> = before_fields
+            .keys()
+            .chain(after_fields.keys())
+            .cloned()
+            .collect();
+        for number in numbers {
+            if before_fields.get(&number) != after_fields.get(&number) {
+                return Err(RefactorError::FieldNumberChanged(path.clone(), number));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Apply a single refactoring to the file.
+///
+/// File is not modified if an error is returned.
+pub fn apply_refactoring(
+    file: &mut FileDescriptorProto,
+    op: &RefactorOp,
+) -> Result<(), RefactorError> {
+    apply_refactorings(file, std::slice::from_ref(op))
+}
+
+/// Apply refactorings to the file in order.
+///
+/// Either all refactorings are applied or, if an error is returned,
+/// the file is not modified.
+pub fn apply_refactorings(
+    file: &mut FileDescriptorProto,
+    ops: &[RefactorOp],
+) -> Result<(), RefactorError> {
+    let mut result = file.clone();
+    for op in ops {
+        let path = match op {
+            RefactorOp::RenameField { message, .. }
+            | RefactorOp::MoveIntoOneof { message, .. }
+            | RefactorOp::SetJsonName { message, .. } => message,
+        };
+        let message = find_message(&mut result.message_type, path)
+            .ok_or_else(|| RefactorError::MessageNotFound(path.to_owned()))?;
+        match op {
+            RefactorOp::RenameField {
+                field, new_name, ..
+            } => rename_field(message, path, field, new_name)?,
+            RefactorOp::MoveIntoOneof { field, oneof, .. } => {
+                move_into_oneof(message, path, field, oneof)?
+            }
+            RefactorOp::SetJsonName {
+                field, json_name, ..
+            } => set_json_name(message, path, field, json_name)?,
+        }
+    }
+    check_numbers_stable(file, &result)?;
+    *file = result;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn field(name: &str, number: i32) -> FieldDescriptorProto {
+        let mut field = FieldDescriptorProto::new();
+        field.set_name(name.to_owned());
+        field.set_json_name(json_name(name));
+        field.set_number(number);
+        field.set_label(field_descriptor_proto::Label::LABEL_OPTIONAL);
+        field.set_field_type(field_descriptor_proto::Type::TYPE_STRING);
+        field
+    }
+
+    fn file() -> FileDescriptorProto {
+        let mut inner = DescriptorProto::new();
+        inner.set_name("Inner".to_owned());
+        inner.field.push(field("old_name", 1));
+        inner.field.push(field("other", 2));
+
+        let mut outer = DescriptorProto::new();
+        outer.set_name("Outer".to_owned());
+        outer.field.push(field("url", 1));
+        outer.field.push(field("id", 2));
+        outer.field.push(field("path", 3));
+        let mut opt = field("opt", 4);
+        opt.set_proto3_optional(true);
+        opt.set_oneof_index(0);
+        outer.field.push(opt);
+        let mut synthetic = OneofDescriptorProto::new();
+        synthetic.set_name("_opt".to_owned());
+        outer.oneof_decl.push(synthetic);
+        outer.nested_type.push(inner);
+
+        let mut file = FileDescriptorProto::new();
+        file.set_name("test.proto".to_owned());
+        file.set_syntax("proto3".to_owned());
+        file.message_type.push(outer);
+        file
+    }
+
+    fn rename(message: &str, field: &str, new_name: &str) -> RefactorOp {
+        RefactorOp::RenameField {
+            message: message.to_owned(),
+            field: field.to_owned(),
+            new_name: new_name.to_owned(),
+        }
+    }
+
+    fn move_into_oneof(field: &str, oneof: &str) -> RefactorOp {
+        RefactorOp::MoveIntoOneof {
+            message: "Outer".to_owned(),
+            field: field.to_owned(),
+            oneof: oneof.to_owned(),
+        }
+    }
+
+    #[test]
+    fn rename_nested() {
+        let mut file = file();
+        apply_refactoring(&mut file, &rename("Outer.Inner", "old_name", "new_name")).unwrap();
+        let f = &file.message_type[0].nested_type[0].field[0];
+        assert_eq!("new_name", f.get_name());
+        assert_eq!("oldName", f.get_json_name());
+        assert_eq!(1, f.get_number());
+    }
+
+    #[test]
+    fn rename_errors() {
+        let mut file = file();
+        let original = file.clone();
+        assert!(matches!(
+            apply_refactoring(&mut file, &rename("Outer.Inner", "old_name", "other")),
+            Err(RefactorError::NameConflict(..))
+        ));
+        assert!(matches!(
+            apply_refactoring(&mut file, &rename("Outer.Inner", "old_name", "1x")),
+            Err(RefactorError::InvalidIdentifier(..))
+        ));
+        assert!(matches!(
+            apply_refactoring(&mut file, &rename("Outer.Missing", "old_name", "x")),
+            Err(RefactorError::MessageNotFound(..))
+        ));
+        // Earlier successful operations are rolled back
+        assert!(matches!(
+            apply_refactorings(
+                &mut file,
+                &[
+                    rename("Outer", "url", "uri"),
+                    rename("Outer", "missing", "x"),
+                ]
+            ),
+            Err(RefactorError::FieldNotFound(..))
+        ));
+        assert_eq!(original, file);
+    }
+
+    #[test]
+    fn move_fields_into_oneof() {
+        let mut file = file();
+        apply_refactorings(
+            &mut file,
+            &[
+                move_into_oneof("url", "source"),
+                move_into_oneof("path", "source"),
+            ],
+        )
+        .unwrap();
+        let outer = &file.message_type[0];
+        let oneofs: Vec<_> = outer.oneof_decl.iter().map(|o| o.get_name()).collect();
+        assert_eq!(vec!["source", "_opt"], oneofs);
+        let fields: Vec<_> = outer
+            .field
+            .iter()
+            .map(|f| {
+                (
+                    f.get_name(),
+                    f.has_oneof_index().then(|| f.get_oneof_index()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                ("url", Some(0)),
+                ("path", Some(0)),
+                ("id", None),
+                ("opt", Some(1)),
+            ],
+            fields
+        );
+    }
+
+    #[test]
+    fn move_into_oneof_errors() {
+        let mut file = file();
+        assert!(matches!(
+            apply_refactoring(&mut file, &move_into_oneof("opt", "source")),
+            Err(RefactorError::CannotMoveIntoOneof(..))
+        ));
+        assert!(matches!(
+            apply_refactoring(&mut file, &move_into_oneof("url", "_opt")),
+            Err(RefactorError::NameConflict(..))
+        ));
+        assert!(matches!(
+            apply_refactoring(&mut file, &move_into_oneof("url", "id")),
+            Err(RefactorError::NameConflict(..))
+        ));
+    }
+
+    #[test]
+    fn set_json_name() {
+        let mut file = file();
+        let op = |json_name: &str| RefactorOp::SetJsonName {
+            message: "Outer".to_owned(),
+            field: "url".to_owned(),
+            json_name: json_name.to_owned(),
+        };
+        assert!(matches!(
+            apply_refactoring(&mut file, &op("path")),
+            Err(RefactorError::JsonNameConflict(..))
+        ));
+        apply_refactoring(&mut file, &op("sourceUrl")).unwrap();
+        assert_eq!("sourceUrl", file.message_type[0].field[0].get_json_name());
+    }
+
+    #[test]
+    fn number_stability() {
+        let before = file();
+        let mut after = before.clone();
+        after.message_type[0].field[1].set_number(5);
+        assert!(matches!(
+            check_numbers_stable(&before, &after),
+            Err(RefactorError::FieldNumberChanged(..))
+        ));
+        let mut after = before.clone();
+        after.message_type[0].field[1].set_field_type(field_descriptor_proto::Type::TYPE_BYTES);
+        assert!(matches!(
+            check_numbers_stable(&before, &after),
+            Err(RefactorError::FieldNumberChanged(..))
+        ));
+    }
+}
@@ -100,6 +100,16 @@ pub fn cfg_serde() {
     }
 }
 
+pub fn cfg_rkyv() {
+    match env::var("CARGO_FEATURE_WITH_RKYV") {
+        Ok(_) => {
+            println!("cargo:rustc-cfg=rkyv");
+        }
+        Err(VarError::NotUnicode(..)) => panic!(),
+        Err(VarError::NotPresent) => {}
+    }
+}
+
 #[derive(Default)]
 pub struct GenInDirArgs<'a> {
     pub out_dir: &'a str,
@@ -223,8 +233,22 @@ where
 
     let customize = Customize {
         serde_derive_cfg: Some("serde".to_owned()),
+        rkyv_derive_cfg: Some("rkyv".to_owned()),
+        lazy_message_fields: Some(true),
         ..Default::default()
-    };
+    }
+    .type_attribute(
+        ".test_customize_attributes.WithAttributes",
+        "#[derive(Eq, Hash)]",
+    )
+    .type_attribute(
+        ".test_customize_attributes.Color",
+        "#[derive(PartialOrd, Ord)]",
+    )
+    .field_attribute(
+        ".test_customize_attributes.WithAttributes.name",
+        "#[doc(alias = \"title\")]",
+    );
 
     gen(GenInDirArgs {
         out_dir: dir,
@@ -0,0 +1,113 @@
+//! Check that dynamic messages are serialized and parsed exactly like generated messages.
+
+use protobuf::corpus::RandomMessageGenerator;
+use protobuf::descriptor::DescriptorProto;
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::reflect::ReflectEqMode;
+use protobuf::MessageDyn;
+
+/// Number of random messages generated for each message type.
+const ITERATIONS: u64 = 50;
+
+/// Messages deeper than this have only required fields set.
+const MAX_DEPTH: u32 = 3;
+
+fn generator(seed: u64) -> RandomMessageGenerator {
+    let mut generator = RandomMessageGenerator::new(seed);
+    generator.set_max_depth(MAX_DEPTH);
+    // Generated and dynamic maps are iterated in different order,
+    // so serialized bytes of maps with several entries differ
+    generator.set_max_map_len(1);
+    generator
+}
+
+/// Names of all messages except map entries, relative to the package.
+fn message_names(file: &FileDescriptor) -> Vec<String> {
+    fn add(scope: &str, message: &DescriptorProto, r: &mut Vec<String>) {
+        if message.options.get_or_default().get_map_entry() {
+            return;
+        }
+        let name = if scope.is_empty() {
+            message.get_name().to_owned()
+        } else {
+            format!("{}.{}", scope, message.get_name())
+        };
+        for nested in &message.nested_type {
+            add(&name, nested, r);
+        }
+        r.push(name);
+    }
+
+    let mut r = Vec::new();
+    for message in &file.proto().message_type {
+        add("", message, &mut r);
+    }
+    r
+}
+
+fn parse(d: &MessageDescriptor, bytes: &[u8]) -> Box<dyn MessageDyn> {
+    let mut m = d.new_instance();
+    m.merge_from_bytes_dyn(bytes)
+        .unwrap_or_else(|e| panic!("{}: {}", d.full_name(), e));
+    m
+}
+
+fn assert_same_bytes(name: &str, generated: &dyn MessageDyn, dynamic: &dyn MessageDyn) {
+    let g = generated.write_to_bytes_dyn().unwrap();
+    let d = dynamic.write_to_bytes_dyn().unwrap();
+    assert_eq!(g, d, "{}: generated {:?}", name, generated);
+    assert_eq!(
+        g.len() as u32,
+        dynamic.compute_size_dyn(),
+        "{}: generated {:?}",
+        name,
+        generated
+    );
+}
+
+fn assert_same_value(name: &str, a: &dyn MessageDyn, b: &dyn MessageDyn) {
+    assert!(
+        a.reflect_eq_dyn(b, &ReflectEqMode::nan_equal()),
+        "{}: {:?} != {:?}",
+        name,
+        a,
+        b
+    );
+}
+
+/// For every message type in the generated file, serialize random messages
+/// with generated code and parse them as dynamic messages and vice versa.
+///
+/// Serialized bytes of generated and dynamic messages must be identical,
+/// and generated messages must be equal after passing through dynamic message.
+pub fn test_dynamic_roundtrip(file: &FileDescriptor) {
+    let dynamic_file =
+        FileDescriptor::new_dynamic(file.proto().clone(), file.dependencies().to_vec());
+
+    for name in message_names(file) {
+        let g = file.message_by_package_relative_name(&name).unwrap();
+        let d = dynamic_file
+            .message_by_package_relative_name(&name)
+            .unwrap();
+
+        for seed in 0..ITERATIONS {
+            let mut generator = generator(seed);
+
+            // generated -> dynamic
+            let generated = generator.generate(&g);
+            let dynamic = parse(&d, &generated.write_to_bytes_dyn().unwrap());
+            assert_same_bytes(&name, &*generated, &*dynamic);
+            let generated_again = parse(&g, &dynamic.write_to_bytes_dyn().unwrap());
+            assert_same_value(&name, &*generated, &*generated_again);
+
+            // dynamic -> generated
+            let dynamic = generator.generate(&d);
+            let generated = parse(&g, &dynamic.write_to_bytes_dyn().unwrap());
+            assert_same_bytes(&name, &*generated, &*dynamic);
+            let dynamic_again = parse(&d, &generated.write_to_bytes_dyn().unwrap());
+            let generated_again = parse(&g, &dynamic_again.write_to_bytes_dyn().unwrap());
+            assert_same_value(&name, &*generated, &*generated_again);
+        }
+    }
+}
@@ -22,5 +22,8 @@ pub use json_tests::*;
 mod reflect_tests;
 pub use reflect_tests::*;
 
+mod dynamic_roundtrip_tests;
+pub use dynamic_roundtrip_tests::*;
+
 mod cargo;
 pub use cargo::*;
@@ -100,6 +100,7 @@ fn main() {
     env_logger::init();
 
     cfg_serde();
+    cfg_rkyv();
 
     clean_old_files();
 
@@ -1,8 +1,13 @@
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::FileDescriptorPool;
+use protobuf::text_format;
 use protobuf::well_known_types::Any;
+use protobuf::well_known_types::Duration;
 use protobuf::Message;
 
 use super::test_any_pb::MessageOne;
 use super::test_any_pb::MessageTwo;
+use super::test_any_pb::MessageWithAny;
 
 #[test]
 fn test_static() {
@@ -37,3 +42,111 @@ fn test_dynamic() {
         .unwrap()
         .is_none());
 }
+
+#[test]
+fn test_type_url_prefix() {
+    let mut m1 = MessageOne::new();
+    m1.set_i(10);
+    for prefix in &["example.com/types", "example.com/types/"] {
+        let any = Any::pack_with_type_url_prefix(&m1, prefix).unwrap();
+        assert_eq!("example.com/types/test_any.MessageOne", any.type_url);
+        assert_eq!(Some("example.com/types"), any.type_url_prefix());
+        assert_eq!(Some("test_any.MessageOne"), any.type_name());
+        assert!(any.is::<MessageOne>());
+        assert_eq!(Some(m1.clone()), any.unpack::<MessageOne>().unwrap());
+    }
+
+    let any = Any::pack(&m1).unwrap();
+    assert_eq!(Some(Any::DEFAULT_TYPE_URL_PREFIX), any.type_url_prefix());
+
+    let m: MessageWithAny =
+        text_format::parse_from_str("any { [example.com/types/test_any.MessageOne] { i: 10 } }")
+            .unwrap();
+    assert_eq!(Some("example.com/types"), m.get_any().type_url_prefix());
+    assert_eq!(Some(m1), m.get_any().unpack::<MessageOne>().unwrap());
+}
+
+#[test]
+fn test_text_format_expanded() {
+    let m: MessageWithAny =
+        text_format::parse_from_str("any { [type.googleapis.com/test_any.MessageOne] { i: 10 } }")
+            .unwrap();
+    let mut m1 = MessageOne::new();
+    m1.set_i(10);
+    assert_eq!(Some(m1), m.get_any().unpack::<MessageOne>().unwrap());
+
+    let m: MessageWithAny = text_format::parse_from_str(
+        "any { [type.googleapis.com/google.protobuf.Duration]: < seconds: 3 > }",
+    )
+    .unwrap();
+    let mut duration = Duration::new();
+    duration.seconds = 3;
+    assert_eq!(Some(duration), m.get_any().unpack::<Duration>().unwrap());
+
+    let e = text_format::parse_from_str::<MessageWithAny>(
+        "any { [type.googleapis.com/test_any.Unknown] {} }",
+    )
+    .unwrap_err();
+    assert!(
+        e.to_string()
+            .ends_with("message type of Any not found: type.googleapis.com/test_any.Unknown"),
+        "{}",
+        e
+    );
+}
+
+#[test]
+fn test_text_format_expanded_dynamic() {
+    let file = MessageWithAny::descriptor_static()
+        .file_descriptor()
+        .clone();
+    let dynamic_file =
+        FileDescriptor::new_dynamic(file.proto().clone(), file.dependencies().to_vec());
+    let d = dynamic_file
+        .message_by_package_relative_name("MessageWithAny")
+        .unwrap();
+    let text = "any { [type.googleapis.com/test_any.MessageOne] { i: 10 } }";
+    let dynamic = text_format::parse_dyn(&d, text).unwrap();
+    let generated: MessageWithAny = text_format::parse_from_str(text).unwrap();
+    assert_eq!(
+        generated.write_to_bytes().unwrap(),
+        dynamic.write_to_bytes_dyn().unwrap()
+    );
+}
+
+#[test]
+fn test_unpack_with_pool() {
+    let mut m1 = MessageOne::new();
+    m1.set_i(10);
+    let any = Any::pack(&m1).unwrap();
+    assert_eq!(Some("test_any.MessageOne"), any.type_name());
+
+    let mut pool = FileDescriptorPool::new();
+    assert!(any.unpack_with_pool(&pool).unwrap().is_none());
+
+    // well known types are always found
+    let mut duration = Duration::new();
+    duration.seconds = 3;
+    let unpacked = Any::pack(&duration)
+        .unwrap()
+        .unpack_with_pool(&pool)
+        .unwrap()
+        .unwrap();
+    assert_eq!(&duration, unpacked.downcast_ref::<Duration>().unwrap());
+
+    let file = MessageOne::descriptor_static().file_descriptor().clone();
+    let dynamic_file =
+        FileDescriptor::new_dynamic(file.proto().clone(), file.dependencies().to_vec());
+    pool.add_file_descriptor(&dynamic_file).unwrap();
+    let unpacked = any.unpack_with_pool(&pool).unwrap().unwrap();
+    assert_eq!(
+        dynamic_file
+            .message_by_package_relative_name("MessageOne")
+            .unwrap(),
+        unpacked.descriptor_dyn()
+    );
+    assert_eq!(
+        m1.write_to_bytes().unwrap(),
+        unpacked.write_to_bytes_dyn().unwrap()
+    );
+}
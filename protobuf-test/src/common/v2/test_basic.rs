@@ -314,3 +314,197 @@ fn test_serialize_too_large_message() {
     let mut s = CodedOutputStream::bytes(&mut bytes);
     test1.write_to_with_cached_sizes(&mut s).unwrap_err();
 }
+
+macro_rules! fill_repeated {
+    ($m:expr, $n:expr) => {
+        for i in 0..$n {
+            let i = i as i64;
+            $m.double_field.push(i as f64);
+            $m.float_field.push(i as f32);
+            $m.int32_field.push(-i as i32);
+            $m.int64_field.push(i << 20);
+            $m.uint32_field.push(i as u32 * 1000);
+            $m.uint64_field.push(i as u64 * 1_000_000);
+            $m.sint32_field.push(-i as i32);
+            $m.sint64_field.push(-i << 30);
+            $m.fixed32_field.push(i as u32);
+            $m.fixed64_field.push(i as u64);
+            $m.sfixed32_field.push(-i as i32);
+            $m.sfixed64_field.push(-i);
+            $m.bool_field.push(i % 2 == 0);
+            $m.string_field.push("x".repeat(i as usize % 200));
+            $m.bytes_field.push(vec![0; i as usize % 200]);
+            $m.enum_field
+                .push(protobuf::ProtobufEnumOrUnknown::from_i32(i as i32 % 4));
+        }
+    };
+}
+
+/// Computed size must be equal to serialized size,
+/// in particular, around varint length boundaries.
+#[test]
+fn test_compute_size_matches_serialized_size() {
+    for &n in &[0, 1, 2, 127, 128, 129, 16383, 16384, 16385] {
+        let mut m = TestTypesRepeated::new();
+        fill_repeated!(m, n);
+        let bytes = m.write_to_bytes().unwrap();
+        assert_eq!(bytes.len(), m.compute_size() as usize, "{}", n);
+        assert_eq!(m, TestTypesRepeated::parse_from_bytes(&bytes).unwrap());
+
+        let mut m = TestTypesRepeatedPacked::new();
+        fill_repeated!(m, n);
+        let bytes = m.write_to_bytes().unwrap();
+        assert_eq!(bytes.len(), m.compute_size() as usize, "{}", n);
+        assert_eq!(
+            m,
+            TestTypesRepeatedPacked::parse_from_bytes(&bytes).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_write_to_vec_with_capacity() {
+    let mut m = TestTypesRepeatedPacked::new();
+    fill_repeated!(m, 300);
+    let expected = m.write_to_bytes().unwrap();
+
+    let mut v = vec![1, 2, 3];
+    m.write_to_vec_with_capacity(&mut v).unwrap();
+    assert_eq!(&[1, 2, 3], &v[..3]);
+    assert_eq!(expected, &v[3..]);
+
+    let mut v = vec![1, 2, 3];
+    (&m as &dyn protobuf::MessageDyn)
+        .write_to_vec_with_capacity_dyn(&mut v)
+        .unwrap();
+    assert_eq!(expected, &v[3..]);
+}
+
+#[test]
+fn test_concat_serialized() {
+    let mut a = Test1::new();
+    a.set_a(10);
+    let mut b = Test1::new();
+    b.set_a(20);
+
+    let a_bytes = a.write_to_bytes().unwrap();
+    let b_bytes = b.write_to_bytes().unwrap();
+
+    let concat = protobuf::concat_serialized(&[&a_bytes, &b_bytes]);
+    assert_eq!(a_bytes.len() + b_bytes.len(), concat.len());
+    // last value of singular field wins
+    assert_eq!(b, Test1::parse_from_bytes(&concat).unwrap());
+
+    let mut r1 = TestTypesRepeated::new();
+    r1.int32_field = vec![1, 2];
+    r1.string_field = vec!["a".to_owned()];
+    let mut r2 = TestTypesRepeated::new();
+    r2.int32_field = vec![3];
+    r2.string_field = vec!["b".to_owned()];
+
+    let concat = protobuf::concat_serialized(&[
+        &r1.write_to_bytes().unwrap(),
+        &r2.write_to_bytes().unwrap(),
+    ]);
+    let parsed = TestTypesRepeated::parse_from_bytes(&concat).unwrap();
+    // repeated fields are appended
+    assert_eq!(vec![1, 2, 3], parsed.int32_field);
+    assert_eq!(vec!["a".to_owned(), "b".to_owned()], parsed.string_field);
+
+    // same as merging parts one by one
+    let mut merged = r1.clone();
+    merged
+        .merge_from_bytes(&r2.write_to_bytes().unwrap())
+        .unwrap();
+    assert_eq!(merged, parsed);
+
+    assert_eq!(Vec::<u8>::new(), protobuf::concat_serialized(&[]));
+}
+
+#[test]
+fn test_allocation_monitor() {
+    struct Monitor {
+        bytes: Vec<u64>,
+        repeated: Vec<u64>,
+    }
+
+    impl protobuf::AllocationMonitor for Monitor {
+        fn on_large_bytes(&mut self, len: u64) -> protobuf::ProtobufResult<()> {
+            self.bytes.push(len);
+            Ok(())
+        }
+
+        fn on_large_repeated(&mut self, len: u64) -> protobuf::ProtobufResult<()> {
+            self.repeated.push(len);
+            Ok(())
+        }
+    }
+
+    let mut m = TestTypesRepeated::new();
+    m.int32_field = vec![1, 2, 3, 4, 5];
+    m.string_field = vec!["a".to_owned(), "abcdef".to_owned()];
+    let bytes = m.write_to_bytes().unwrap();
+
+    let mut monitor = Monitor {
+        bytes: Vec::new(),
+        repeated: Vec::new(),
+    };
+    {
+        let mut is = CodedInputStream::from_bytes(&bytes);
+        is.set_allocation_monitor(&mut monitor, 5, 2);
+        let parsed = TestTypesRepeated::parse_from(&mut is).unwrap();
+        assert_eq!(m, parsed);
+    }
+    assert_eq!(vec![6], monitor.bytes);
+    assert_eq!(vec![2, 4, 2], monitor.repeated);
+}
+
+#[test]
+fn test_parse_from_bytes_until_fields() {
+    let mut m = TestTypesSingular::new();
+    m.set_int32_field(10);
+    m.set_int64_field(20);
+    m.set_uint32_field(30);
+    let bytes = m.write_to_bytes().unwrap();
+
+    let (header, rest) = TestTypesSingular::parse_from_bytes_until_fields(&bytes, &[3, 4]).unwrap();
+    assert_eq!(10, header.get_int32_field());
+    assert_eq!(20, header.get_int64_field());
+    assert_eq!(0, header.get_uint32_field());
+    assert_eq!(&bytes[bytes.len() - rest.len()..], rest);
+    assert!(!rest.is_empty());
+
+    // merging the rest completes the message
+    let mut full = header;
+    full.merge_from_bytes(rest).unwrap();
+    assert_eq!(m, full);
+
+    // absent field: whole message is read
+    let (all, rest) = TestTypesSingular::parse_from_bytes_until_fields(&bytes, &[100]).unwrap();
+    assert_eq!(m, all);
+    assert!(rest.is_empty());
+
+    let mut dynamic = TestTypesSingular::descriptor_static().new_instance();
+    let rest = dynamic
+        .merge_from_bytes_until_fields_dyn(&bytes, &[3])
+        .unwrap();
+    assert_eq!(
+        bytes.len() - rest.len(),
+        dynamic.write_to_bytes_dyn().unwrap().len()
+    );
+}
+
+#[test]
+fn test_field_number_consts() {
+    assert_eq!(2, Test2::FIELD_B_NUMBER);
+    assert_eq!(6, TestTypesSingular::FIELD_UINT64_FIELD_NUMBER);
+    assert_eq!(6, test_types_singular::field_numbers::UINT64_FIELD);
+    assert_eq!(
+        TestTypesSingular::descriptor_static()
+            .get_field_by_name("uint64_field")
+            .unwrap()
+            .get_proto()
+            .get_number() as u32,
+        TestTypesSingular::FIELD_UINT64_FIELD_NUMBER
+    );
+}
@@ -0,0 +1,49 @@
+use std::convert::TryFrom;
+
+use protobuf::Message;
+
+use super::test_bytes_conversions_pb::*;
+
+#[test]
+fn try_from_bytes() {
+    let mut m = BytesConversions::new();
+    m.set_s("abc".to_owned());
+    m.mut_nested().set_x(10);
+
+    let bytes = Vec::<u8>::try_from(&m).unwrap();
+    assert_eq!(m.write_to_bytes().unwrap(), bytes);
+    assert_eq!(m, BytesConversions::try_from(&bytes[..]).unwrap());
+}
+
+#[test]
+fn try_from_nested() {
+    let mut nested = bytes_conversions::Nested::new();
+    nested.set_x(20);
+
+    let bytes = Vec::<u8>::try_from(&nested).unwrap();
+    assert_eq!(
+        nested,
+        bytes_conversions::Nested::try_from(&bytes[..]).unwrap()
+    );
+}
+
+#[test]
+fn try_from_malformed_bytes() {
+    // string field with length past the end of input
+    assert!(BytesConversions::try_from(&[0x0a, 0x05, b'a'][..]).is_err());
+}
+
+/// Function accepting any payload convertible to bytes.
+fn payload_len<'a, T>(payload: &'a T) -> usize
+where
+    Vec<u8>: TryFrom<&'a T>,
+{
+    Vec::<u8>::try_from(payload).ok().unwrap().len()
+}
+
+#[test]
+fn generic_bound() {
+    let mut m = BytesConversions::new();
+    m.set_s("abc".to_owned());
+    assert_eq!(m.compute_size() as usize, payload_len(&m));
+}
@@ -0,0 +1,21 @@
+use std::collections::HashSet;
+
+use super::test_customize_attributes_pb::*;
+
+#[test]
+fn test_type_attributes() {
+    // `Eq` and `Hash` are derived with `Customize::type_attributes`
+    let mut set = HashSet::new();
+    let mut m = WithAttributes::new();
+    m.set_id(1);
+    m.set_number(10);
+    set.insert(m.clone());
+    set.insert(m);
+    assert_eq!(1, set.len());
+}
+
+#[test]
+fn test_type_attributes_enum() {
+    // `Ord` is derived with `Customize::type_attributes`
+    assert!(Color::RED < Color::GREEN);
+}
@@ -0,0 +1,191 @@
+use protobuf::reflect::EnumDescriptor;
+use protobuf::reflect::FieldDescriptor;
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::reflect::ReflectValueBox;
+use protobuf::reflect::ReflectValueRef;
+use protobuf::reflect::RuntimeFieldType;
+use protobuf::reflect::RuntimeTypeBox;
+use protobuf::text_format;
+use protobuf::Message;
+use protobuf::MessageDyn;
+use protobuf::ProtobufEnum;
+use protobuf_test_common::hex::decode_hex;
+
+use super::test_dynamic_enum_pb::*;
+
+fn dynamic_descriptor() -> MessageDescriptor {
+    let file = FileDescriptor::new_dynamic(file_descriptor().proto().clone(), Vec::new());
+    file.message_by_package_relative_name("ForDynamicEnumTest")
+        .unwrap()
+}
+
+fn parse(d: &MessageDescriptor, hex: &str) -> Box<dyn MessageDyn> {
+    let mut m = d.new_instance();
+    m.merge_from_bytes_dyn(&decode_hex(hex)).unwrap();
+    m
+}
+
+fn enum_number(field: ReflectValueRef) -> i32 {
+    field.to_enum_value().unwrap()
+}
+
+#[test]
+fn known_value_by_number() {
+    let d = dynamic_descriptor();
+    let m = parse(&d, "08 14");
+    let v = d
+        .get_field_by_name("enum_field")
+        .unwrap()
+        .get_singular(&*m)
+        .unwrap();
+    assert_eq!(20, enum_number(v.clone()));
+    match v {
+        ReflectValueRef::Enum(e, n) => assert_eq!(
+            "DYNAMIC_ENUM_TWENTY",
+            e.get_value_by_number(n).unwrap().get_name()
+        ),
+        v => panic!("not an enum: {:?}", v),
+    }
+    assert_eq!(decode_hex("08 14"), m.write_to_bytes_dyn().unwrap());
+}
+
+#[test]
+fn unknown_value_preserved() {
+    let d = dynamic_descriptor();
+    let m = parse(&d, "08 1e");
+    let f = d.get_field_by_name("enum_field").unwrap();
+    assert_eq!(30, enum_number(f.get_singular(&*m).unwrap()));
+    assert_eq!(decode_hex("08 1e"), m.write_to_bytes_dyn().unwrap());
+    assert_eq!("enum_field: 30", text_format::print_to_string(&*m));
+}
+
+#[test]
+fn unknown_repeated_values_preserved() {
+    let d = dynamic_descriptor();
+    // repeated: 10, 30 (unpacked), 40 (packed on the wire)
+    // packed: 5, 20
+    let m = parse(&d, "10 0a 10 1e 12 01 28 1a 02 05 14");
+    let repeated = d
+        .get_field_by_name("repeated_enum_field")
+        .unwrap()
+        .get_repeated(&*m);
+    let numbers: Vec<i32> = (0..repeated.len())
+        .map(|i| enum_number(repeated.get(i)))
+        .collect();
+    assert_eq!(vec![10, 30, 40], numbers);
+    assert_eq!(
+        decode_hex("10 0a 10 1e 10 28 1a 02 05 14"),
+        m.write_to_bytes_dyn().unwrap()
+    );
+}
+
+#[test]
+fn same_as_generated() {
+    let bytes = decode_hex("08 1e 10 0a 10 63 1a 03 07 00 14");
+    let generated = ForDynamicEnumTest::parse_from_bytes(&bytes).unwrap();
+    let dynamic = parse(&dynamic_descriptor(), "08 1e 10 0a 10 63 1a 03 07 00 14");
+    assert_eq!(
+        generated.write_to_bytes().unwrap(),
+        dynamic.write_to_bytes_dyn().unwrap()
+    );
+}
+
+fn enum_type(field: &FieldDescriptor) -> EnumDescriptor {
+    match field.runtime_field_type() {
+        RuntimeFieldType::Singular(RuntimeTypeBox::Enum(e))
+        | RuntimeFieldType::Repeated(RuntimeTypeBox::Enum(e)) => e,
+        _ => panic!("not an enum field: {}", field),
+    }
+}
+
+fn set_enum_field(m: &mut dyn MessageDyn, number: i32) -> bool {
+    let field = m.descriptor_dyn().get_field_by_name("enum_field").unwrap();
+    let e = enum_type(&field);
+    field
+        .try_set_singular_field(m, ReflectValueBox::Enum(e, number))
+        .is_ok()
+}
+
+fn push_enum_field(m: &mut dyn MessageDyn, number: i32) -> bool {
+    let field = m
+        .descriptor_dyn()
+        .get_field_by_name("repeated_enum_field")
+        .unwrap();
+    let e = enum_type(&field);
+    field
+        .mut_repeated(m)
+        .try_push(ReflectValueBox::Enum(e, number))
+        .is_ok()
+}
+
+#[test]
+fn contains_number() {
+    let e = DynamicEnum::enum_descriptor_static();
+    assert!(e.contains_number(0));
+    assert!(e.contains_number(20));
+    assert!(!e.contains_number(30));
+    assert_eq!(
+        file_descriptor().proto().get_syntax() != "proto3",
+        e.is_closed()
+    );
+}
+
+#[test]
+fn enum_checked() {
+    let e = DynamicEnum::enum_descriptor_static();
+    assert_eq!(
+        Some(ReflectValueBox::Enum(e.clone(), 20)),
+        ReflectValueBox::enum_checked(e.clone(), 20)
+    );
+    assert_eq!(
+        !e.is_closed(),
+        ReflectValueBox::enum_checked(e.clone(), 30).is_some()
+    );
+}
+
+#[test]
+fn try_set_field_checks_closed_enum_number() {
+    let closed = DynamicEnum::enum_descriptor_static().is_closed();
+    let mut generated = ForDynamicEnumTest::new();
+    let mut dynamic = dynamic_descriptor().new_instance();
+    for m in vec![&mut generated as &mut dyn MessageDyn, &mut *dynamic] {
+        assert!(set_enum_field(m, 20));
+        assert!(push_enum_field(m, 10));
+        assert_eq!(!closed, set_enum_field(m, 30));
+        assert_eq!(!closed, push_enum_field(m, 30));
+        // value is either set or left unchanged
+        let field = m.descriptor_dyn().get_field_by_name("enum_field").unwrap();
+        let expected = if closed { 20 } else { 30 };
+        assert_eq!(
+            expected,
+            field.get_singular(m).unwrap().to_enum_value().unwrap()
+        );
+    }
+}
+
+#[test]
+fn set_field_stores_undeclared_number() {
+    let mut generated = ForDynamicEnumTest::new();
+    let mut dynamic = dynamic_descriptor().new_instance();
+    for m in vec![&mut generated as &mut dyn MessageDyn, &mut *dynamic] {
+        let descriptor = m.descriptor_dyn();
+        let field = descriptor.get_field_by_name("enum_field").unwrap();
+        field.set_singular_field(m, ReflectValueBox::Enum(enum_type(&field), 30));
+        let repeated = descriptor.get_field_by_name("repeated_enum_field").unwrap();
+        let e = enum_type(&repeated);
+        repeated.mut_repeated(m).push(ReflectValueBox::Enum(e, 30));
+        assert_eq!(decode_hex("08 1e 10 1e"), m.write_to_bytes_dyn().unwrap());
+    }
+
+    // Parsed undeclared numbers can be copied back
+    let parsed = parse(&dynamic_descriptor(), "08 1e");
+    let parsed_field = parsed
+        .descriptor_dyn()
+        .get_field_by_name("enum_field")
+        .unwrap();
+    let value = parsed_field.get_singular(&*parsed).unwrap().to_box();
+    let mut copy = parsed.descriptor_dyn().new_instance();
+    parsed_field.set_singular_field(&mut *copy, value);
+    assert_eq!(decode_hex("08 1e"), copy.write_to_bytes_dyn().unwrap());
+}
@@ -0,0 +1,152 @@
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::reflect::ReflectValueRef;
+use protobuf::Message;
+use protobuf_test_common::hex::decode_hex;
+
+use super::test_dynamic_map_pb::*;
+
+fn dynamic_descriptor() -> MessageDescriptor {
+    let file = FileDescriptor::new_dynamic(
+        file_descriptor().proto().clone(),
+        vec![protobuf::rustproto::file_descriptor()],
+    );
+    file.message_by_package_relative_name("ForDynamicMapTest")
+        .unwrap()
+}
+
+fn test_message() -> ForDynamicMapTest {
+    let mut m = ForDynamicMapTest::new();
+    m.mut_string_int32().insert("a".to_owned(), 10);
+    m.mut_string_int32().insert("b".to_owned(), -20);
+    m.mut_sint64_string().insert(-5, "minus five".to_owned());
+    m.mut_uint32_enum()
+        .insert(7, DynamicMapEnum::DYNAMIC_MAP_TWO.into());
+    let mut value = DynamicMapValue::new();
+    value.set_x(33);
+    m.mut_bool_message().insert(true, value);
+    m.mut_fixed32_bytes().insert(9, b"nine".to_vec());
+    m
+}
+
+#[test]
+fn merge_from() {
+    let bytes = test_message().write_to_bytes().unwrap();
+
+    let d = dynamic_descriptor();
+    let mut dm = d.new_instance();
+    dm.merge_from_bytes_dyn(&bytes).unwrap();
+
+    let string_int32 = d.get_field_by_name("string_int32").unwrap();
+    let string_int32 = string_int32.get_map(&*dm);
+    assert_eq!(2, string_int32.len());
+    assert_eq!(
+        Some(ReflectValueRef::I32(10)),
+        string_int32.get(ReflectValueRef::String("a"))
+    );
+    assert_eq!(
+        Some(ReflectValueRef::I32(-20)),
+        string_int32.get(ReflectValueRef::String("b"))
+    );
+
+    let sint64_string = d.get_field_by_name("sint64_string").unwrap();
+    assert_eq!(
+        Some(ReflectValueRef::String("minus five")),
+        sint64_string.get_map(&*dm).get(ReflectValueRef::I64(-5))
+    );
+
+    let uint32_enum = d.get_field_by_name("uint32_enum").unwrap();
+    let uint32_enum = uint32_enum.get_map(&*dm);
+    let enum_value = uint32_enum.get(ReflectValueRef::U32(7)).unwrap();
+    assert_eq!(Some(2), enum_value.to_enum_value());
+
+    let bool_message = d.get_field_by_name("bool_message").unwrap();
+    let bool_message = bool_message.get_map(&*dm);
+    let message = bool_message
+        .get(ReflectValueRef::Bool(true))
+        .unwrap()
+        .to_message()
+        .unwrap();
+    let x = message.descriptor_dyn().get_field_by_name("x").unwrap();
+    assert_eq!(Some(ReflectValueRef::I32(33)), x.get_singular(&*message));
+
+    let fixed32_bytes = d.get_field_by_name("fixed32_bytes").unwrap();
+    assert_eq!(
+        Some(ReflectValueRef::Bytes(b"nine")),
+        fixed32_bytes.get_map(&*dm).get(ReflectValueRef::U32(9))
+    );
+}
+
+#[test]
+fn merge_from_missing_key_or_value() {
+    let d = dynamic_descriptor();
+    let string_int32 = d.get_field_by_name("string_int32").unwrap();
+
+    // entry with value only
+    let mut dm = d.new_instance();
+    dm.merge_from_bytes_dyn(&decode_hex("0a 02 10 05")).unwrap();
+    assert_eq!(
+        Some(ReflectValueRef::I32(5)),
+        string_int32.get_map(&*dm).get(ReflectValueRef::String(""))
+    );
+
+    // entry with key only
+    let mut dm = d.new_instance();
+    dm.merge_from_bytes_dyn(&decode_hex("0a 03 0a 01 61"))
+        .unwrap();
+    assert_eq!(
+        Some(ReflectValueRef::I32(0)),
+        string_int32.get_map(&*dm).get(ReflectValueRef::String("a"))
+    );
+
+    // empty entry and unknown entry field
+    let mut dm = d.new_instance();
+    dm.merge_from_bytes_dyn(&decode_hex("0a 00 0a 02 18 01"))
+        .unwrap();
+    assert_eq!(1, string_int32.get_map(&*dm).len());
+    assert_eq!(
+        Some(ReflectValueRef::I32(0)),
+        string_int32.get_map(&*dm).get(ReflectValueRef::String(""))
+    );
+}
+
+#[test]
+fn merge_from_same_key_twice() {
+    let d = dynamic_descriptor();
+    let string_int32 = d.get_field_by_name("string_int32").unwrap();
+
+    let mut dm = d.new_instance();
+    dm.merge_from_bytes_dyn(&decode_hex("0a 05 0a 01 61 10 01 0a 05 0a 01 61 10 02"))
+        .unwrap();
+    assert_eq!(1, string_int32.get_map(&*dm).len());
+    assert_eq!(
+        Some(ReflectValueRef::I32(2)),
+        string_int32.get_map(&*dm).get(ReflectValueRef::String("a"))
+    );
+}
+
+#[test]
+fn write_to() {
+    let m = test_message();
+
+    let d = dynamic_descriptor();
+    let mut dm = d.new_instance();
+    dm.merge_from_bytes_dyn(&m.write_to_bytes().unwrap())
+        .unwrap();
+
+    let bytes = dm.write_to_bytes_dyn().unwrap();
+    assert_eq!(bytes.len() as u32, dm.compute_size_dyn());
+    assert_eq!(m, ForDynamicMapTest::parse_from_bytes(&bytes).unwrap());
+}
+
+#[test]
+fn write_to_default_key_and_value() {
+    let d = dynamic_descriptor();
+    let mut dm = d.new_instance();
+    dm.merge_from_bytes_dyn(&decode_hex("0a 00")).unwrap();
+
+    // map entries are written even if key and value are default
+    let bytes = dm.write_to_bytes_dyn().unwrap();
+    assert_eq!(decode_hex("0a 04 0a 00 10 00"), bytes);
+    assert_eq!(6, dm.compute_size_dyn());
+}
@@ -0,0 +1,73 @@
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::Message;
+use protobuf_test_common::hex::decode_hex;
+
+use super::test_dynamic_packed_pb::*;
+
+fn dynamic_descriptor() -> MessageDescriptor {
+    let file = FileDescriptor::new_dynamic(
+        file_descriptor().proto().clone(),
+        vec![protobuf::rustproto::file_descriptor()],
+    );
+    file.message_by_package_relative_name("ForDynamicPackedTest")
+        .unwrap()
+}
+
+fn test_message() -> ForDynamicPackedTest {
+    let mut m = ForDynamicPackedTest::new();
+    m.set_double_field(vec![1.5, -2.0]);
+    m.set_float_field(vec![3.5]);
+    m.set_int32_field(vec![1, -1, 300]);
+    m.set_int64_field(vec![-5, 1 << 40]);
+    m.set_uint32_field(vec![0, 150]);
+    m.set_uint64_field(vec![u64::MAX]);
+    m.set_sint32_field(vec![-3, 3]);
+    m.set_sint64_field(vec![-1 << 40]);
+    m.set_fixed32_field(vec![7, 8]);
+    m.set_fixed64_field(vec![9]);
+    m.set_sfixed32_field(vec![-10]);
+    m.set_sfixed64_field(vec![-11, 12]);
+    m.set_bool_field(vec![true, false, true]);
+    m.set_enum_field(vec![
+        DynamicPackedEnum::DYNAMIC_PACKED_ONE.into(),
+        DynamicPackedEnum::DYNAMIC_PACKED_ZERO.into(),
+    ]);
+    m.set_unpacked_field(vec![4, 5]);
+    m
+}
+
+#[test]
+fn write_to_same_as_generated() {
+    let m = test_message();
+    let bytes = m.write_to_bytes().unwrap();
+
+    let mut dm = dynamic_descriptor().new_instance();
+    dm.merge_from_bytes_dyn(&bytes).unwrap();
+
+    assert_eq!(bytes, dm.write_to_bytes_dyn().unwrap());
+    assert_eq!(bytes.len() as u32, dm.compute_size_dyn());
+}
+
+#[test]
+fn write_to_packed() {
+    let mut m = ForDynamicPackedTest::new();
+    m.set_int32_field(vec![1, 2, 150]);
+    m.set_unpacked_field(vec![4, 5]);
+
+    let mut dm = dynamic_descriptor().new_instance();
+    dm.merge_from_bytes_dyn(&m.write_to_bytes().unwrap())
+        .unwrap();
+
+    assert_eq!(
+        decode_hex("1a 04 01 02 96 01 78 04 78 05"),
+        dm.write_to_bytes_dyn().unwrap()
+    );
+}
+
+#[test]
+fn write_to_empty() {
+    let dm = dynamic_descriptor().new_instance();
+    assert_eq!(Vec::<u8>::new(), dm.write_to_bytes_dyn().unwrap());
+    assert_eq!(0, dm.compute_size_dyn());
+}
@@ -0,0 +1,75 @@
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::Message;
+
+use super::test_dynamic_presence_pb::*;
+
+fn dynamic_descriptor() -> MessageDescriptor {
+    let file = FileDescriptor::new_dynamic(
+        file_descriptor().proto().clone(),
+        vec![protobuf::rustproto::file_descriptor()],
+    );
+    file.message_by_package_relative_name("ForDynamicPresenceTest")
+        .unwrap()
+}
+
+#[test]
+fn write_to_default_values_same_as_generated() {
+    let mut m = ForDynamicPresenceTest::new();
+    m.set_int32_field(0);
+    m.set_double_field(0.0);
+    m.set_bool_field(false);
+    m.set_string_field(String::new());
+    m.set_bytes_field(Vec::new());
+    m.set_enum_field(DynamicPresenceEnum::DYNAMIC_PRESENCE_ZERO);
+    m.set_message_field(ForDynamicPresenceTest::new());
+    m.set_oneof_uint64_field(0);
+    let bytes = m.write_to_bytes().unwrap();
+
+    let mut dm = dynamic_descriptor().new_instance();
+    dm.merge_from_bytes_dyn(&bytes).unwrap();
+
+    assert_eq!(bytes, dm.write_to_bytes_dyn().unwrap());
+    assert_eq!(bytes.len() as u32, dm.compute_size_dyn());
+}
+
+#[test]
+fn set_default_values_same_as_generated() {
+    let generated = ForDynamicPresenceTest::descriptor_static();
+    let dynamic = dynamic_descriptor();
+    for field in generated.fields() {
+        let mut m = generated.new_instance();
+        field.set_singular_field(&mut *m, field.singular_default_value().to_box());
+
+        let dynamic_field = dynamic.get_field_by_name(field.get_name()).unwrap();
+        let mut dm = dynamic.new_instance();
+        dynamic_field.set_singular_field(&mut *dm, dynamic_field.singular_default_value().to_box());
+
+        assert_eq!(
+            m.write_to_bytes_dyn().unwrap(),
+            dm.write_to_bytes_dyn().unwrap(),
+            "{}",
+            field.get_name()
+        );
+    }
+}
+
+#[test]
+fn oneof_unset_after_merge() {
+    let mut m = ForDynamicPresenceTest::new();
+    m.set_oneof_string_field(String::new());
+
+    let d = dynamic_descriptor();
+    let mut dm = d.new_instance();
+    dm.merge_from_bytes_dyn(&m.write_to_bytes().unwrap())
+        .unwrap();
+
+    let string_field = d.get_field_by_name("oneof_string_field").unwrap();
+    let uint64_field = d.get_field_by_name("oneof_uint64_field").unwrap();
+    assert!(string_field.has_field(&*dm));
+    assert!(!uint64_field.has_field(&*dm));
+    assert_eq!(
+        m.write_to_bytes().unwrap(),
+        dm.write_to_bytes_dyn().unwrap()
+    );
+}
@@ -0,0 +1,50 @@
+use protobuf_test_common::test_dynamic_roundtrip;
+
+use super::test_dynamic_enum_pb;
+use super::test_dynamic_map_pb;
+use super::test_dynamic_packed_pb;
+use super::test_dynamic_presence_pb;
+use super::test_dynamic_repeated_pb;
+use super::test_dynamic_roundtrip_pb;
+use super::test_dynamic_singular_pb;
+use super::test_dynamic_unknown_pb;
+
+#[test]
+fn all_types() {
+    test_dynamic_roundtrip(&test_dynamic_roundtrip_pb::file_descriptor());
+}
+
+#[test]
+fn singular() {
+    test_dynamic_roundtrip(&test_dynamic_singular_pb::file_descriptor());
+}
+
+#[test]
+fn repeated() {
+    test_dynamic_roundtrip(&test_dynamic_repeated_pb::file_descriptor());
+}
+
+#[test]
+fn enums() {
+    test_dynamic_roundtrip(&test_dynamic_enum_pb::file_descriptor());
+}
+
+#[test]
+fn maps() {
+    test_dynamic_roundtrip(&test_dynamic_map_pb::file_descriptor());
+}
+
+#[test]
+fn packed() {
+    test_dynamic_roundtrip(&test_dynamic_packed_pb::file_descriptor());
+}
+
+#[test]
+fn presence() {
+    test_dynamic_roundtrip(&test_dynamic_presence_pb::file_descriptor());
+}
+
+#[test]
+fn unknown() {
+    test_dynamic_roundtrip(&test_dynamic_unknown_pb::file_descriptor());
+}
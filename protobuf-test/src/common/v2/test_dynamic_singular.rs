@@ -1,6 +1,9 @@
+use protobuf::reflect::DynamicMessage;
 use protobuf::reflect::FileDescriptor;
 use protobuf::reflect::ReflectValueBox;
 use protobuf::reflect::ReflectValueRef;
+use protobuf::Clear;
+use protobuf::MessageDyn;
 
 use super::test_dynamic_singular_pb;
 
@@ -56,3 +59,36 @@ fn generated_set_panic_on_wrong_field_type() {
 fn dynamic_set_panic_on_wrong_field_type() {
     do_test_set_panic_on_wrong_field_type(&dynamic_file_descriptor());
 }
+
+#[test]
+fn new_dynamic_instance() {
+    let file_descriptor = dynamic_file_descriptor();
+    let d = file_descriptor
+        .message_by_package_relative_name("ForDynamicTest")
+        .unwrap();
+    assert!(d.is_dynamic());
+    let f = d.get_field_by_name("ff").unwrap();
+
+    let mut m = d.new_dynamic_instance();
+    f.set_singular_field(&mut *m, 10u32.into());
+    m.merge_from_bytes_dyn(&[0x08, 0x14, 0x10, 0x01]).unwrap();
+
+    let dm: &mut DynamicMessage = m.downcast_mut().unwrap();
+    assert_eq!(Some(ReflectValueRef::from(20u32)), f.get_singular(dm));
+    dm.clear();
+    assert_eq!(None, f.get_singular(dm));
+    assert!(m.write_to_bytes_dyn().unwrap().is_empty());
+
+    let m: Box<DynamicMessage> = m.downcast_box().unwrap();
+    assert_eq!(d, m.descriptor_dyn());
+}
+
+#[test]
+#[should_panic]
+fn new_dynamic_instance_of_generated() {
+    let d = test_dynamic_singular_pb::file_descriptor()
+        .message_by_package_relative_name("ForDynamicTest")
+        .unwrap();
+    assert!(!d.is_dynamic());
+    d.new_dynamic_instance();
+}
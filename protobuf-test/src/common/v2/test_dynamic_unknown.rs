@@ -0,0 +1,59 @@
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::reflect::ReflectValueRef;
+use protobuf::Message;
+
+use super::test_dynamic_unknown_pb::*;
+
+fn dynamic_partial_descriptor() -> MessageDescriptor {
+    let file = FileDescriptor::new_dynamic(
+        file_descriptor().proto().clone(),
+        vec![protobuf::rustproto::file_descriptor()],
+    );
+    file.message_by_package_relative_name("DynamicUnknownPartial")
+        .unwrap()
+}
+
+fn full_message() -> DynamicUnknownFull {
+    let mut m = DynamicUnknownFull::new();
+    m.set_a(17);
+    m.set_b("bb".to_owned());
+    m.mut_c().push(30);
+    m.mut_c().push(31);
+    m.set_d(40);
+    m.set_e(-50);
+    m
+}
+
+#[test]
+fn merge_from_preserves_unknown_fields() {
+    let d = dynamic_partial_descriptor();
+    let mut dm = d.new_instance();
+    dm.merge_from_bytes_dyn(&full_message().write_to_bytes().unwrap())
+        .unwrap();
+
+    let a = d.get_field_by_name("a").unwrap();
+    assert_eq!(Some(ReflectValueRef::I32(17)), a.get_singular(&*dm));
+
+    let unknown_fields = dm.get_unknown_fields_dyn();
+    assert_eq!(
+        &[b"bb".to_vec()],
+        &unknown_fields.get(2).unwrap().length_delimited[..]
+    );
+    assert_eq!(&[30, 31], &unknown_fields.get(3).unwrap().fixed64[..]);
+    assert_eq!(&[40], &unknown_fields.get(4).unwrap().fixed32[..]);
+    assert_eq!(&[-50i64 as u64], &unknown_fields.get(5).unwrap().varint[..]);
+}
+
+#[test]
+fn write_to_emits_unknown_fields() {
+    let m = full_message();
+
+    let mut dm = dynamic_partial_descriptor().new_instance();
+    dm.merge_from_bytes_dyn(&m.write_to_bytes().unwrap())
+        .unwrap();
+
+    let bytes = dm.write_to_bytes_dyn().unwrap();
+    assert_eq!(bytes.len() as u32, dm.compute_size_dyn());
+    assert_eq!(m, DynamicUnknownFull::parse_from_bytes(&bytes).unwrap());
+}
@@ -1,3 +1,6 @@
+use protobuf::json;
+use protobuf::reflect::EnumAliasName;
+use protobuf::text_format;
 use protobuf::ProtobufEnum;
 use protobuf_test_common::*;
 
@@ -25,3 +28,70 @@ fn test_enum_in_message() {
     m.set_en(EnumWithAlias::A);
     test_serialize_deserialize("08 0a", &m);
 }
+
+#[test]
+fn test_enum_alias_name() {
+    assert_eq!("A", EnumWithAlias::A.name());
+    assert_eq!("A_AGAIN", EnumWithAlias::A_AGAIN.name());
+    assert_eq!(Ok(EnumWithAlias::A_AGAIN), "A_AGAIN".parse());
+}
+
+#[test]
+fn test_enum_alias_descriptor() {
+    assert_eq!("A", EnumWithAlias::A.descriptor().get_name());
+    assert_eq!("A_AGAIN", EnumWithAlias::A_AGAIN.descriptor().get_name());
+
+    let descriptor = EnumWithAlias::enum_descriptor_static();
+    assert_eq!("A", descriptor.get_value_by_number(10).unwrap().get_name());
+    assert_eq!(
+        "A_AGAIN",
+        descriptor
+            .get_value_by_number_with_alias(10, EnumAliasName::LastDeclared)
+            .unwrap()
+            .get_name()
+    );
+    assert_eq!(
+        "B",
+        descriptor
+            .get_value_by_number_with_alias(20, EnumAliasName::LastDeclared)
+            .unwrap()
+            .get_name()
+    );
+}
+
+#[test]
+fn test_enum_alias_print() {
+    let mut m = TestEnumWithAlias::new();
+    m.set_en(EnumWithAlias::A_AGAIN);
+
+    assert_eq!("en: A", text_format::print_to_string(&m));
+    assert_eq!("{\"en\": \"A\"}", json::print_to_string(&m).unwrap());
+
+    let text_options = text_format::PrintOptions {
+        enum_alias_name: EnumAliasName::LastDeclared,
+        ..Default::default()
+    };
+    assert_eq!(
+        "en: A_AGAIN",
+        text_format::print_to_string_with_options(&m, &text_options)
+    );
+    let json_options = json::PrintOptions {
+        enum_alias_name: EnumAliasName::LastDeclared,
+        ..Default::default()
+    };
+    assert_eq!(
+        "{\"en\": \"A_AGAIN\"}",
+        json::print_to_string_with_options(&m, &json_options).unwrap()
+    );
+}
+
+#[test]
+fn test_enum_alias_parse() {
+    for name in &["A", "A_AGAIN"] {
+        let m: TestEnumWithAlias = text_format::parse_from_str(&format!("en: {}", name)).unwrap();
+        assert_eq!(10, m.get_en().value());
+        let m: TestEnumWithAlias =
+            json::parse_from_str(&format!("{{\"en\": \"{}\"}}", name)).unwrap();
+        assert_eq!(10, m.get_en().value());
+    }
+}
@@ -13,3 +13,26 @@ fn test_enum_values() {
     ];
     assert_eq!(expected, TestEnumValuesEnum::values());
 }
+
+#[test]
+fn test_enum_values_iter() {
+    assert_eq!(
+        TestEnumValuesEnum::values(),
+        &TestEnumValuesEnum::values_iter().collect::<Vec<_>>()[..]
+    );
+}
+
+#[test]
+fn test_enum_name() {
+    for &e in TestEnumValuesEnum::values() {
+        assert_eq!(e.descriptor().get_name(), e.name());
+        assert_eq!(Some(e), TestEnumValuesEnum::from_name(e.name()));
+        assert_eq!(Ok(e), e.name().parse());
+    }
+    assert_eq!(None, TestEnumValuesEnum::from_name("winter"));
+    let err = "winter".parse::<TestEnumValuesEnum>().unwrap_err();
+    assert_eq!(
+        "unknown TestEnumValuesEnum value name: winter",
+        err.to_string()
+    );
+}
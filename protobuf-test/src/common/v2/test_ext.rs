@@ -1,3 +1,6 @@
+use protobuf::reflect::ExtensionRegistry;
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::ReflectValueBox;
 use protobuf::Message;
 
 use super::test_ext_pb::*;
@@ -32,3 +35,57 @@ fn test_get() {
         assert_eq!(22, exts::message_field.get(message).unwrap().get_n());
     }
 }
+
+#[test]
+fn test_custom_options_dynamic() {
+    let mut registry = ExtensionRegistry::new();
+    registry.add_file(&file_descriptor());
+
+    let file = FileDescriptor::new_dynamic_with_extensions(
+        file_descriptor_proto().clone(),
+        vec![
+            protobuf::descriptor::file_descriptor(),
+            protobuf::rustproto::file_descriptor(),
+        ],
+        &registry,
+    );
+    let message = file.message_by_package_relative_name("MyMessage").unwrap();
+    let option = |name: &str| {
+        message
+            .custom_options()
+            .iter()
+            .find(|o| o.name() == name)
+            .unwrap()
+            .value()
+            .unwrap()
+            .clone()
+    };
+    assert_eq!(ReflectValueBox::F64(10.5), option("test_ext.double_field"));
+    assert_eq!(ReflectValueBox::F32(-8.5), option("test_ext.float_field"));
+    assert_eq!(ReflectValueBox::I32(-3), option("test_ext.int32_field"));
+    assert_eq!(ReflectValueBox::I32(-4), option("test_ext.sint32_field"));
+    assert_eq!(ReflectValueBox::I64(-14), option("test_ext.sint64_field"));
+    assert_eq!(ReflectValueBox::U64(16), option("test_ext.fixed64_field"));
+    assert_eq!(ReflectValueBox::I64(-17), option("test_ext.sfixed64_field"));
+    assert_eq!(ReflectValueBox::Bool(true), option("test_ext.bool_field"));
+    assert_eq!(
+        ReflectValueBox::String("Hello world!".to_owned()),
+        option("test_ext.string_field")
+    );
+    assert_eq!(
+        ReflectValueBox::Bytes(b"bytes".to_vec()),
+        option("test_ext.bytes_field")
+    );
+    assert_eq!(50001, message.custom_options()[0].number());
+
+    // Without registry options remain unknown fields
+    let file = FileDescriptor::new_dynamic(
+        file_descriptor_proto().clone(),
+        vec![
+            protobuf::descriptor::file_descriptor(),
+            protobuf::rustproto::file_descriptor(),
+        ],
+    );
+    let message = file.message_by_package_relative_name("MyMessage").unwrap();
+    assert!(message.custom_options().is_empty());
+}
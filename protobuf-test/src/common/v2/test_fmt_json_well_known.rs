@@ -1,4 +1,10 @@
+use protobuf::json;
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::FileDescriptorPool;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::text_format;
 use protobuf::well_known_types::*;
+use protobuf::Message;
 use protobuf_test_common::*;
 
 use super::test_fmt_json_well_known_pb::*;
@@ -17,6 +23,12 @@ fn test_duration() {
     d.seconds = 1;
     m.set_duration(d);
     test_json_parse_message("{\"duration\": \"1s\"}", &m);
+
+    let mut m = TestFmtJsonWellKnownTypes::new();
+    let mut d = Duration::new();
+    d.nanos = -5000;
+    m.set_duration(d);
+    test_json_print_parse_message("{\"duration\": \"-0.000005000s\"}", &m);
 }
 
 #[test]
@@ -129,8 +141,55 @@ fn test_wrappers() {
 #[test]
 fn test_any() {
     let mut m = TestFmtJsonWellKnownTypes::new();
-    m.any_value.mut_or_default();
-    // TODO
+    let mut d = Duration::new();
+    d.seconds = 1;
+    m.set_any_value(Any::pack(&d).unwrap());
+    test_json_print_parse_message(
+        "{\"anyValue\": {\"@type\": \"type.googleapis.com/google.protobuf.Duration\", \"value\": \"1.000000000s\"}}",
+        &m,
+    );
+    test_json_parse_message(
+        "{\"anyValue\": {\"value\": \"1s\", \"@type\": \"type.googleapis.com/google.protobuf.Duration\"}}",
+        &m,
+    );
+
+    let mut inner = TestFmtJsonWellKnownTypes::new();
+    inner.mut_int32_value().value = 1;
+    m.set_any_value(Any::pack(&inner).unwrap());
+    // Type of non-well-known message is only found in the pool
+    assert!(json::print_to_string(&m).is_err());
+
+    let json = "{\"anyValue\": {\"@type\": \"type.googleapis.com/TestFmtJsonWellKnownTypes\", \"int32Value\": 1}}";
+    assert!(json::parse_from_str::<TestFmtJsonWellKnownTypes>(json).is_err());
+
+    let mut pool = FileDescriptorPool::new();
+    pool.add_file_descriptor(&file_descriptor()).unwrap();
+    let print_options = json::PrintOptions {
+        type_pool: Some(pool.clone()),
+        ..Default::default()
+    };
+    assert_eq!(
+        json,
+        json::print_to_string_with_options(&m, &print_options).unwrap()
+    );
+    let parse_options = json::ParseOptions {
+        type_pool: Some(pool),
+        ..Default::default()
+    };
+    assert_eq!(
+        m,
+        json::parse_from_str_with_options(json, &parse_options).unwrap()
+    );
+}
+
+#[test]
+fn test_null() {
+    let mut m = TestFmtJsonWellKnownTypes::new();
+    m.mut_value().set_null_value(NullValue::NULL_VALUE);
+    test_json_parse_message(
+        "{\"duration\": null, \"int32Value\": null, \"anyValue\": null, \"value\": null}",
+        &m,
+    );
 }
 
 #[test]
@@ -154,3 +213,137 @@ fn test_field_mask() {
     });
     test_json_print_parse_message("{\"fieldMask\": \"ab,c.d.e\"}", &m);
 }
+
+#[test]
+fn test_value_max_depth() {
+    let mut value = Value::new();
+    value.set_bool_value(true);
+    for _ in 0..10 {
+        let mut list = ListValue::new();
+        list.values.push(value);
+        value = Value::new();
+        value.set_list_value(list);
+    }
+    let mut m = TestFmtJsonWellKnownTypes::new();
+    m.set_value(value);
+
+    let print_options = json::PrintOptions {
+        max_depth: Some(3),
+        ..Default::default()
+    };
+    assert_eq!(
+        format!("{{\"value\": [\"{}\"]}}", json::MAX_DEPTH_PLACEHOLDER),
+        json::print_to_string_with_options(&m, &print_options).unwrap()
+    );
+}
+
+/// JSON and text format printers truncate messages at the same depth.
+#[test]
+fn test_max_depth_same_as_text_format() {
+    let mut value = Value::new();
+    value.set_bool_value(true);
+    for i in 0..6 {
+        let mut nested = Value::new();
+        if i % 2 == 0 {
+            let mut list = ListValue::new();
+            list.values.push(value);
+            nested.set_list_value(list);
+        } else {
+            let mut s = Struct::new();
+            s.fields.insert("a".to_owned(), value);
+            nested.set_struct_value(s);
+        }
+        value = nested;
+    }
+    let mut m = TestFmtJsonWellKnownTypes::new();
+    m.set_value(value);
+    m.set_duration(Duration::new());
+
+    for max_depth in 0..16 {
+        let json = json::print_to_string_with_options(
+            &m,
+            &json::PrintOptions {
+                max_depth: Some(max_depth),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let text = text_format::print_to_string_with_options(
+            &m,
+            &text_format::PrintOptions {
+                max_depth: Some(max_depth),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            text.matches(text_format::MAX_DEPTH_PLACEHOLDER).count(),
+            json.matches(json::MAX_DEPTH_PLACEHOLDER).count(),
+            "{}: {} {}",
+            max_depth,
+            text,
+            json
+        );
+        assert_eq!(max_depth >= 13, !json.contains(json::MAX_DEPTH_PLACEHOLDER));
+    }
+}
+
+/// Descriptor of `TestFmtJsonWellKnownTypes` with well known types also dynamic.
+fn dynamic_descriptor() -> MessageDescriptor {
+    let file = file_descriptor();
+    let dependencies = file
+        .dependencies()
+        .iter()
+        .map(|d| {
+            if d.proto().get_name().starts_with("google/protobuf/") {
+                FileDescriptor::new_dynamic(d.proto().clone(), Vec::new())
+            } else {
+                d.clone()
+            }
+        })
+        .collect();
+    FileDescriptor::new_dynamic(file.proto().clone(), dependencies)
+        .message_by_package_relative_name("TestFmtJsonWellKnownTypes")
+        .unwrap()
+}
+
+#[test]
+fn test_dynamic() {
+    let mut m = TestFmtJsonWellKnownTypes::new();
+    m.mut_duration().seconds = -3;
+    m.mut_timestamp().seconds = 1;
+    m.mut_field_mask().paths.push("a.b".to_owned());
+    m.mut_value().set_string_value("ab".to_owned());
+    m.mut_struct_value().fields.insert("ab".to_owned(), {
+        let mut v = Value::new();
+        v.set_number_value(3.0);
+        v
+    });
+    m.mut_int64_value().value = -13;
+    m.mut_bytes_value().value = b"ab".to_vec();
+    m.set_any_value(Any::pack(&Timestamp::new()).unwrap());
+    m.set_null_values(vec![NullValue::NULL_VALUE.into()]);
+
+    let d = dynamic_descriptor();
+    let mut dynamic = d.new_instance();
+    dynamic
+        .merge_from_bytes_dyn(&m.write_to_bytes().unwrap())
+        .unwrap();
+
+    let print_options = json::PrintOptions {
+        proto_field_name: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        json::print_to_string_with_options(&m, &print_options).unwrap(),
+        json::print_to_string_with_options(&*dynamic, &print_options).unwrap()
+    );
+    let json = json::print_to_string(&m).unwrap();
+    assert_eq!(json, json::print_to_string(&*dynamic).unwrap());
+
+    let parsed = json::parse_dynamic_from_str(&d, &json).unwrap();
+    assert!(parsed.descriptor_dyn().is_dynamic());
+    assert_eq!(
+        m.write_to_bytes().unwrap(),
+        parsed.write_to_bytes_dyn().unwrap()
+    );
+}
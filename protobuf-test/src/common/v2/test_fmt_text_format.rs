@@ -1,4 +1,7 @@
+use protobuf::reflect::FileDescriptor;
+use protobuf::text_format;
 use protobuf::text_format::print_to_string;
+use protobuf::well_known_types::Empty;
 use protobuf::Message;
 use protobuf_test_common::*;
 
@@ -182,6 +185,29 @@ fn test_reflect() {
     test_text_format_message(&l);
 }
 
+#[test]
+fn test_parse_lists_and_separators() {
+    let d = TestTypes::descriptor_static();
+    test_text_format_str_descriptor("int32_repeated: [1, -2, 3]", &d);
+    test_text_format_str_descriptor("int32_repeated: []", &d);
+    test_text_format_str_descriptor("int32_repeated: [1] int32_repeated: 2", &d);
+    test_text_format_str_descriptor("test_enum_repeated: [DARK, LIGHT]", &d);
+    test_text_format_str_descriptor(
+        "test_message_repeated: [{value: 1}, <value: 2>] test_message_repeated [{}]",
+        &d,
+    );
+    test_text_format_str_descriptor(
+        "int32_singular: 1, uint32_singular: 2; bool_singular: true",
+        &d,
+    );
+    test_text_format_str_descriptor("test_message_singular: { value: 1, }", &d);
+    test_text_format_str_descriptor(
+        "message_map_field: [{key: \"a\", value: {value: 1}}, {key: \"b\"}]",
+        &d,
+    );
+    test_text_format_str_descriptor("enum_map_field: { key: 1 value: LIGHT }", &d);
+}
+
 #[test]
 fn test_parse_error() {
     let e = protobuf::text_format::parse_from_str::<TestTypes>("nonexistent: 42").unwrap_err();
@@ -283,3 +309,93 @@ fn test_string_escaped() {
         &*format!("{:?}", m)
     );
 }
+
+#[test]
+fn test_max_depth() {
+    let mut tm = TestMessage::new();
+    tm.set_value(23);
+    let mut m = TestTypes::new();
+    m.set_test_message_singular(tm);
+    m.set_int32_singular(10);
+
+    let print_options = text_format::PrintOptions {
+        max_depth: Some(0),
+        ..Default::default()
+    };
+    assert_eq!(
+        format!(
+            "int32_singular: 10 test_message_singular: {}",
+            text_format::MAX_DEPTH_PLACEHOLDER
+        ),
+        text_format::print_to_string_with_options(&m, &print_options)
+    );
+
+    let print_options = text_format::PrintOptions {
+        max_depth: Some(1),
+        ..Default::default()
+    };
+    assert_eq!(
+        print_to_string(&m),
+        text_format::print_to_string_with_options(&m, &print_options)
+    );
+}
+
+#[test]
+fn test_dynamic() {
+    let file = file_descriptor();
+    let dynamic_file =
+        FileDescriptor::new_dynamic(file.proto().clone(), file.dependencies().to_vec());
+    let d = dynamic_file
+        .message_by_package_relative_name("TestTypes")
+        .unwrap();
+    for m in special_messages_typed::<TestTypes>() {
+        let mut dynamic = d.new_instance();
+        dynamic
+            .merge_from_bytes_dyn(&m.write_to_bytes().unwrap())
+            .unwrap();
+        assert_eq!(
+            text_format::print_normalized(&m),
+            text_format::print_normalized(&*dynamic)
+        );
+    }
+}
+
+#[test]
+fn test_decode_unknown_messages() {
+    let mut m = TestTypes::new();
+    m.set_string_singular("abc".to_owned());
+    m.mut_test_message_singular().set_value(23);
+    let unknown = Empty::parse_from_bytes(&m.write_to_bytes().unwrap()).unwrap();
+
+    assert_eq!("14: \"abc\" 17: \"P\\027\"", print_to_string(&unknown));
+    let print_options = text_format::PrintOptions {
+        decode_unknown_messages: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        "14: \"abc\" 17 {10: 23}",
+        text_format::print_to_string_with_options(&unknown, &print_options)
+    );
+}
+
+#[test]
+fn test_parse_dyn() {
+    let file = file_descriptor();
+    let dynamic_file =
+        FileDescriptor::new_dynamic(file.proto().clone(), file.dependencies().to_vec());
+    let d = dynamic_file
+        .message_by_package_relative_name("TestTypes")
+        .unwrap();
+    let text = "int32_singular: 10 \
+                test_message_singular { value: 23 } \
+                test_enum_repeated: DARK test_enum_repeated: LIGHT \
+                string_repeated: \"a\" string_repeated: \"b\" \
+                enum_map_field { key: 1 value: LIGHT }";
+    let dynamic = text_format::parse_dyn(&d, text).unwrap();
+    let generated: TestTypes = text_format::parse_from_str(text).unwrap();
+    assert_eq!(
+        generated.write_to_bytes().unwrap(),
+        dynamic.write_to_bytes_dyn().unwrap()
+    );
+    assert!(text_format::parse_dyn(&d, "unknown_field: 1").is_err());
+}
@@ -1,3 +1,5 @@
+use protobuf::ProtobufEnumOrUnknown;
+
 use super::test_generate_accessors_pb::*;
 
 #[test]
@@ -10,3 +12,53 @@ fn test() {
     // even if it's not requested explicitly
     WithoutAccessors::new().f;
 }
+
+#[test]
+fn test_repeated_message_accessors() {
+    let mut m = WithAccessors::new();
+    m.set_fr_from_iter((0..3).map(|_| FooBar::new()));
+    assert_eq!(3, m.get_fr().len());
+    m.set_fr_from_iter(vec![FooBar::new()]);
+    assert_eq!(1, m.get_fr().len());
+
+    let added: *const FooBar = m.add_fr(FooBar::new());
+    assert_eq!(2, m.get_fr().len());
+    assert_eq!(added, &m.get_fr()[1] as *const FooBar);
+}
+
+#[test]
+fn test_singular_message_accessors() {
+    let mut m = WithAccessors::new();
+    assert!(m.f_opt().is_none());
+
+    let inserted: *const FooBar = m.f_mut_or_insert_with(FooBar::new);
+    assert_eq!(Some(inserted), m.f_opt().map(|f| f as *const FooBar));
+
+    // existing value is returned, closure is not called
+    let existing: *const FooBar = m.f_mut_or_insert_with(|| panic!());
+    assert_eq!(inserted, existing);
+}
+
+#[test]
+fn test_repeated_enum_bits_accessors() {
+    let mut m = WithAccessors::new();
+    m.mut_flags()
+        .push(ProtobufEnumOrUnknown::new(Flag::FLAG_WRITE));
+    m.mut_flags()
+        .push(ProtobufEnumOrUnknown::new(Flag::FLAG_READ));
+
+    let mut bits = m.get_flags_bits().unwrap();
+    assert!(bits.contains(Flag::FLAG_READ));
+    assert!(!bits.contains(Flag::FLAG_NONE));
+    assert_eq!(0b110, bits.bits());
+
+    bits.remove(Flag::FLAG_WRITE);
+    m.set_flags_bits(bits);
+    assert_eq!(
+        &[ProtobufEnumOrUnknown::new(Flag::FLAG_READ)],
+        m.get_flags()
+    );
+
+    m.mut_flags().push(ProtobufEnumOrUnknown::from_i32(100));
+    assert_eq!(None, m.get_flags_bits());
+}
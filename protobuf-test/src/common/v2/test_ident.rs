@@ -13,3 +13,11 @@ fn test_reflect() {
     // instantiate reflection
     assert_eq!("Self", message_Self::descriptor_static().name());
 }
+
+#[test]
+fn test_field_number_const_names() {
+    assert_eq!(1, FieldNumberConstNames::FIELD_FOO_1_NUMBER);
+    assert_eq!(2, FieldNumberConstNames::FIELD_FOO_2_NUMBER);
+    assert_eq!(3, FieldNumberConstNames::FIELD_BAR_NUMBER);
+    assert_eq!(2, field_number_const_names::field_numbers::FOO_2);
+}
@@ -0,0 +1,79 @@
+use protobuf::Message;
+
+use super::test_lazy_message_pb::*;
+
+// `payload` field with `values` written before `text`
+const ENVELOPE: &[u8] = b"\x0a\x01h\x12\x05\x10\x07\x0a\x01t";
+
+#[test]
+fn parse_on_access() {
+    let m = Envelope::parse_from_bytes(ENVELOPE).unwrap();
+    assert_eq!("h", m.get_header());
+    assert!(m.payload.is_some());
+    assert!(!m.payload.is_parsed());
+
+    let payload = m.payload.as_ref().unwrap();
+    assert_eq!("t", payload.get_text());
+    assert_eq!(&[7], payload.get_values());
+    assert!(m.payload.is_parsed());
+}
+
+#[test]
+fn write_original_bytes() {
+    let m = Envelope::parse_from_bytes(ENVELOPE).unwrap();
+    assert_eq!(ENVELOPE, &m.write_to_bytes().unwrap()[..]);
+
+    // Still unmodified after parsing
+    assert_eq!("t", m.payload.get_or_default().get_text());
+    assert_eq!(ENVELOPE, &m.write_to_bytes().unwrap()[..]);
+}
+
+#[test]
+fn write_modified() {
+    let mut m = Envelope::parse_from_bytes(ENVELOPE).unwrap();
+    m.payload.mut_or_default().set_text("u".to_owned());
+
+    let mut expected = Envelope::new();
+    expected.set_header("h".to_owned());
+    expected.payload.mut_or_default().set_text("u".to_owned());
+    expected.payload.mut_or_default().mut_values().push(7);
+    assert_eq!(
+        expected.write_to_bytes().unwrap(),
+        m.write_to_bytes().unwrap()
+    );
+    assert_eq!(expected, m);
+}
+
+#[test]
+fn same_as_eager() {
+    let mut payload = Payload::new();
+    payload.set_text("t".to_owned());
+    payload.mut_values().push(1);
+
+    let mut lazy = Envelope::new();
+    lazy.payload.set(payload.clone());
+    let mut eager = Envelope::new();
+    eager.eager = Some(payload).into();
+
+    let lazy_bytes = lazy.write_to_bytes().unwrap();
+    let eager_bytes = eager.write_to_bytes().unwrap();
+    // Only field number differs
+    assert_eq!(lazy_bytes[1..], eager_bytes[1..]);
+
+    let parsed = Envelope::parse_from_bytes(&lazy_bytes).unwrap();
+    assert_eq!(eager.eager.as_ref(), parsed.payload.as_ref());
+}
+
+#[test]
+fn reflect() {
+    let m = Envelope::parse_from_bytes(ENVELOPE).unwrap();
+    let field = Envelope::descriptor_static()
+        .get_field_by_name("payload")
+        .unwrap();
+    let payload = field.get_singular(&m).unwrap().to_message().unwrap();
+    assert_eq!("t", payload.downcast_ref::<Payload>().unwrap().get_text());
+
+    let mut m = Envelope::new();
+    field.mut_message(&mut m);
+    assert!(m.payload.is_some());
+}
@@ -1,4 +1,6 @@
 use bytes::Bytes;
+use protobuf::reflect::ReflectValueBox;
+use protobuf::reflect::ReflectValueRef;
 use protobuf::*;
 use protobuf_test_common::*;
 
@@ -27,3 +29,48 @@ fn test_int32_to_bytes() {
     map.int32_to_bytes.insert(17, Bytes::from("abc"));
     test_serialize_deserialize("1a 07 08 11 12 03 61 62 63", &map);
 }
+
+#[test]
+fn test_string_to_bytes_accessors() {
+    let mut map = TestMapCarllerche::new();
+    map.mut_string_to_bytes()
+        .insert(Chars::from("abc"), Bytes::from("de"));
+    assert_eq!(
+        Some(&Bytes::from("de")),
+        map.get_string_to_bytes().get("abc")
+    );
+    test_serialize_deserialize("22 09 0a 03 61 62 63 12 02 64 65", &map);
+}
+
+#[test]
+fn test_reflect() {
+    let mut map = TestMapCarllerche::new();
+    map.mut_string_to_bytes()
+        .insert(Chars::from("abc"), Bytes::from("de"));
+    map.mut_string_to_int32().insert(Chars::from("x"), 17);
+
+    let descriptor = TestMapCarllerche::descriptor_static();
+
+    let string_to_bytes = descriptor.get_field_by_name("string_to_bytes").unwrap();
+    let reflect_map = string_to_bytes.get_map(&map);
+    assert_eq!(1, reflect_map.len());
+    assert_eq!(
+        Some(ReflectValueRef::Bytes(b"de")),
+        reflect_map.get(ReflectValueRef::String("abc"))
+    );
+
+    let string_to_int32 = descriptor.get_field_by_name("string_to_int32").unwrap();
+    assert_eq!(
+        Some(ReflectValueRef::U32(17)),
+        string_to_int32
+            .get_map(&map)
+            .get(ReflectValueRef::String("x"))
+    );
+
+    let mut copy = TestMapCarllerche::new();
+    string_to_bytes.mut_map(&mut copy).insert(
+        ReflectValueBox::String("abc".to_owned()),
+        ReflectValueBox::Bytes(b"de".to_vec()),
+    );
+    assert_eq!(map.get_string_to_bytes(), copy.get_string_to_bytes());
+}
@@ -1,3 +1,9 @@
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::reflect::OneofDescriptor;
+use protobuf::reflect::ReflectValueBox;
+use protobuf::Message;
+use protobuf::MessageDyn;
 use protobuf_test_common::*;
 
 use super::test_oneof_pb::*;
@@ -61,3 +67,89 @@ fn test_types() {
     t(|o| o.set_enum_field(EnumForOneof::A));
     t(|o| o.mut_message_field().set_f(22));
 }
+
+fn oneof_one(d: &MessageDescriptor) -> OneofDescriptor {
+    d.oneofs().find(|o| o.get_name() == "one").unwrap()
+}
+
+fn which_oneof_name(m: &dyn MessageDyn) -> Option<String> {
+    let one = oneof_one(&m.descriptor_dyn());
+    m.which_oneof(&one).map(|f| f.get_name().to_owned())
+}
+
+#[test]
+fn test_which_oneof() {
+    let mut test_message = TestOneof::new();
+    assert_eq!(None, which_oneof_name(&test_message));
+
+    test_message.set_int32_field(0);
+    assert_eq!(
+        Some("int32_field".to_owned()),
+        which_oneof_name(&test_message)
+    );
+
+    test_message.set_string_field("x".to_owned());
+    assert_eq!(
+        Some("string_field".to_owned()),
+        which_oneof_name(&test_message)
+    );
+}
+
+#[test]
+fn test_dynamic_set_field_clears_other_fields() {
+    let file = FileDescriptor::new_dynamic(
+        file_descriptor().proto().clone(),
+        vec![protobuf::rustproto::file_descriptor()],
+    );
+    let d = file.message_by_package_relative_name("TestOneof").unwrap();
+    let int32_field = d.get_field_by_name("int32_field").unwrap();
+    let string_field = d.get_field_by_name("string_field").unwrap();
+    let message_field = d.get_field_by_name("message_field").unwrap();
+    let s = d.get_field_by_name("s").unwrap();
+
+    let mut m = d.new_instance();
+    s.set_singular_field(&mut *m, ReflectValueBox::String("s".to_owned()));
+    int32_field.set_singular_field(&mut *m, ReflectValueBox::I32(10));
+    assert_eq!(Some("int32_field".to_owned()), which_oneof_name(&*m));
+
+    string_field.set_singular_field(&mut *m, ReflectValueBox::String("x".to_owned()));
+    assert_eq!(Some("string_field".to_owned()), which_oneof_name(&*m));
+    assert!(!int32_field.has_field(&*m));
+    // field outside of oneof is not cleared
+    assert!(s.has_field(&*m));
+
+    message_field.mut_message(&mut *m);
+    assert_eq!(Some("message_field".to_owned()), which_oneof_name(&*m));
+    assert!(!string_field.has_field(&*m));
+
+    let mut expected = TestOneof::new();
+    expected.set_s("s".to_owned());
+    expected.mut_message_field();
+    // dynamic messages are written in field number order, so compare parsed messages
+    assert_eq!(
+        expected,
+        TestOneof::parse_from_bytes(&m.write_to_bytes_dyn().unwrap()).unwrap()
+    );
+}
+
+#[test]
+fn test_dynamic_parse_last_oneof_field_wins() {
+    let file = FileDescriptor::new_dynamic(
+        file_descriptor().proto().clone(),
+        vec![protobuf::rustproto::file_descriptor()],
+    );
+    let d = file.message_by_package_relative_name("TestOneof").unwrap();
+
+    // int32_field = 10, bool_field = true
+    let bytes = hex::decode_hex("18 0a 68 01");
+    let mut m = d.new_instance();
+    m.merge_from_bytes_dyn(&bytes).unwrap();
+    assert_eq!(Some("bool_field".to_owned()), which_oneof_name(&*m));
+    assert_eq!(
+        TestOneof::parse_from_bytes(&bytes)
+            .unwrap()
+            .write_to_bytes()
+            .unwrap(),
+        m.write_to_bytes_dyn().unwrap()
+    );
+}
@@ -0,0 +1,65 @@
+use protobuf::proto_msg;
+use protobuf::reflect::FileDescriptor;
+use protobuf::Message;
+
+use super::test_proto_msg_pb::*;
+
+fn expected() -> Order {
+    let mut first = Item::new();
+    first.set_name("a".to_owned());
+    let mut second = Item::new();
+    second.set_count(-2);
+    let mut order = Order::new();
+    order.set_id(10);
+    order.first = Some(first.clone()).into();
+    order.items = vec![first, second];
+    order.colors = vec![Color::GREEN.into(), Color::RED.into()];
+    order.tags.insert("x".to_owned(), 1);
+    order.tags.insert("y".to_owned(), 2);
+    order.set_payload(b"\x00\x01".to_vec());
+    order
+}
+
+#[test]
+fn test_generated() {
+    let order = proto_msg!(Order {
+        id: 10,
+        first { name: "a" },
+        items: [{ name: "a" }, { count: -2 }],
+        colors: [GREEN, RED],
+        tags: [{ key: "x", value: 1 }],
+        tags { key: "y" value: 2 }
+        payload: "\x00\x01",
+    });
+    assert_eq!(expected(), order);
+
+    assert_eq!(Order::new(), proto_msg!(Order {}));
+}
+
+#[test]
+fn test_dynamic() {
+    let file = file_descriptor();
+    let dynamic_file =
+        FileDescriptor::new_dynamic(file.proto().clone(), file.dependencies().to_vec());
+    let d = dynamic_file
+        .message_by_package_relative_name("Order")
+        .unwrap();
+    let order = proto_msg!(dyn d => {
+        id: 10,
+        first { name: "a" },
+        items: [{ name: "a" }, { count: -2 }],
+        colors: [GREEN, RED],
+        tags: [{ key: "x", value: 1 }, { key: "y", value: 2 }],
+        payload: "\x00\x01",
+    });
+    assert_eq!(
+        expected(),
+        Order::parse_from_bytes(&order.write_to_bytes_dyn().unwrap()).unwrap()
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_unknown_field() {
+    proto_msg!(Order { unknown: 1 });
+}
@@ -1,4 +1,5 @@
 use protobuf::reflect::FieldDescriptor;
+use protobuf::reflect::FileDescriptor;
 use protobuf::reflect::ReflectFieldRef;
 use protobuf::reflect::ReflectValueBox;
 use protobuf::reflect::ReflectValueRef;
@@ -265,3 +266,64 @@ fn test_json_name() {
     // the descriptor itself.
     assert_eq!("subM", field_descriptor.get_proto().get_json_name());
 }
+
+#[test]
+fn test_truncate_large_fields() {
+    let mut m = TestTypesSingular::new();
+    m.set_string_field("abcdef".to_owned());
+    m.set_bytes_field(b"ab".to_vec());
+    protobuf::reflect::truncate_large_fields(&mut m, 3);
+    assert_eq!(
+        format!("abc{}", protobuf::reflect::TRUNCATED_MARKER),
+        m.get_string_field()
+    );
+    assert_eq!(b"ab", m.get_bytes_field());
+
+    let mut m = TestTypesRepeated::new();
+    m.set_string_field(vec!["a".to_owned(), "abcdef".to_owned()].into());
+    protobuf::reflect::truncate_large_fields(&mut m, 3);
+    assert_eq!("a", m.get_string_field()[0]);
+    assert_eq!(
+        format!("abc{}", protobuf::reflect::TRUNCATED_MARKER),
+        m.get_string_field()[1]
+    );
+}
+
+fn do_test_clone_into_pool<M: Message + PartialEq>(
+    fill: fn(&mut dyn MessageDyn, &FieldDescriptor),
+) {
+    let mut m = M::new();
+    for field in m.descriptor_dyn().fields() {
+        fill(&mut m, &field);
+    }
+    m.mut_unknown_fields().add_varint(1000, 17);
+
+    let dynamic_pool = vec![FileDescriptor::new_dynamic(
+        file_descriptor().proto().clone(),
+        vec![protobuf::rustproto::file_descriptor()],
+    )];
+    let dynamic = (&m as &dyn MessageDyn)
+        .clone_into_pool(&dynamic_pool)
+        .unwrap();
+    assert_eq!(dynamic_pool[0], *dynamic.descriptor_dyn().file_descriptor());
+    assert!(dynamic.downcast_ref::<M>().is_none());
+    assert_eq!(m.get_unknown_fields(), dynamic.get_unknown_fields_dyn());
+
+    let generated = dynamic.clone_into_pool(&[file_descriptor()]).unwrap();
+    assert!(m == *generated.downcast_box::<M>().unwrap());
+}
+
+#[test]
+fn test_clone_into_pool() {
+    do_test_clone_into_pool::<TestTypesSingular>(test_singular_field);
+    do_test_clone_into_pool::<TestTypesRepeated>(test_repeated_field);
+    do_test_clone_into_pool::<TestTypesMap>(test_map_field);
+}
+
+#[test]
+fn test_clone_into_pool_missing_type() {
+    let m = M::new();
+    assert!((&m as &dyn MessageDyn)
+        .clone_into_pool(&[protobuf::descriptor::file_descriptor()])
+        .is_none());
+}
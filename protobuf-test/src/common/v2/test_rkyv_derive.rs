@@ -0,0 +1,74 @@
+#![cfg(feature = "with-rkyv")]
+
+use protobuf::Message;
+use rkyv::Deserialize;
+
+use super::test_rkyv_derive_pb::*;
+
+fn archive_round_trip<M>(message: &M) -> M
+where
+    M: rkyv::Archive + rkyv::Serialize<rkyv::ser::serializers::AllocSerializer<256>>,
+    M::Archived: Deserialize<M, rkyv::Infallible>
+        + for<'a> rkyv::CheckBytes<rkyv::validation::validators::DefaultValidator<'a>>,
+{
+    let bytes = rkyv::to_bytes::<_, 256>(message).unwrap();
+    let archived = rkyv::check_archived_root::<M>(&bytes).unwrap();
+    archived.deserialize(&mut rkyv::Infallible).unwrap()
+}
+
+fn make_message() -> RkyvMessage {
+    let mut nested = RkyvNested::new();
+    nested.set_name("nested".to_owned());
+    nested.mut_values().extend(vec![1, -2, 3]);
+
+    let mut message = RkyvMessage::new();
+    message.set_id(17);
+    message.set_ratio(0.5);
+    message.set_data(b"data".to_vec());
+    message.set_kind(RkyvEnum::RKYV_SECOND);
+    message.mut_kinds().push(RkyvEnum::RKYV_FIRST.into());
+    message.set_nested(nested.clone());
+    message.mut_nested_list().push(nested.clone());
+    message.mut_counts().insert("a".to_owned(), 10);
+    message.set_nested_choice(nested);
+    message
+}
+
+#[test]
+fn test_round_trip() {
+    let message = make_message();
+    assert_eq!(message, archive_round_trip(&message));
+
+    let mut message = make_message();
+    message.set_text("text".to_owned());
+    assert_eq!(message, archive_round_trip(&message));
+}
+
+#[test]
+fn test_access_archived() {
+    let bytes = rkyv::to_bytes::<_, 256>(&make_message()).unwrap();
+    let archived = rkyv::check_archived_root::<RkyvMessage>(&bytes).unwrap();
+    assert_eq!(1, archived.nested_list.len());
+    assert_eq!(3, archived.nested_list[0].values.len());
+    assert_eq!(Some(&10), archived.counts.get("a"));
+}
+
+#[test]
+fn test_recursive() {
+    let mut leaf = RkyvTree::new();
+    leaf.set_label("leaf".to_owned());
+    let mut root = RkyvTree::new();
+    root.set_label("root".to_owned());
+    root.mut_children().push(leaf.clone());
+    root.set_parent(leaf);
+    assert_eq!(root, archive_round_trip(&root));
+}
+
+#[test]
+fn test_unknown_fields_skipped() {
+    let mut message = make_message();
+    message.mut_unknown_fields().add_varint(1000, 1);
+    let copy = archive_round_trip(&message);
+    assert_eq!(0, copy.get_unknown_fields().iter().count());
+    assert_eq!(message.get_id(), copy.get_id());
+}
@@ -0,0 +1,77 @@
+use protobuf::transcode::FieldNumberRemap;
+use protobuf::Message;
+use protobuf::MessageField;
+
+use super::test_transcode_pb::*;
+
+fn nested(x: i32, next: Option<OldNested>) -> OldNested {
+    let mut m = OldNested::new();
+    m.set_x(x);
+    if let Some(next) = next {
+        m.next = MessageField::some(next);
+    }
+    m
+}
+
+#[test]
+fn test_transcode_by_name() {
+    let mut old = Old::new();
+    old.set_a(10);
+    old.set_b("bb".to_owned());
+    old.nested = MessageField::some(nested(1, Some(nested(2, None))));
+    old.items.push(nested(3, None));
+    old.items.push(nested(4, Some(nested(5, None))));
+    old.by_name.insert("k".to_owned(), nested(6, None));
+    old.set_removed(7);
+
+    let remap = FieldNumberRemap::by_name(&Old::descriptor_static(), &New::descriptor_static());
+    let bytes = remap
+        .transcode_bytes(&old.write_to_bytes().unwrap())
+        .unwrap();
+    let new = New::parse_from_bytes(&bytes).unwrap();
+
+    assert_eq!(10, new.get_a());
+    assert_eq!("bb", new.get_b());
+    assert_eq!(1, new.nested.get_or_default().get_x());
+    assert_eq!(2, new.nested.get_or_default().next.get_or_default().get_x());
+    assert_eq!(
+        vec![3, 4],
+        new.items.iter().map(|m| m.get_x()).collect::<Vec<_>>()
+    );
+    assert_eq!(5, new.items[1].next.get_or_default().get_x());
+    assert_eq!(6, new.by_name["k"].get_x());
+    // field without a counterpart keeps its number
+    assert_eq!(&[7], &new.get_unknown_fields().get(6).unwrap().varint[..]);
+}
+
+#[test]
+fn test_transcode_override() {
+    let mut old = Old::new();
+    old.set_a(10);
+    old.set_removed(7);
+
+    let mut remap = FieldNumberRemap::by_name(&Old::descriptor_static(), &New::descriptor_static());
+    assert_eq!(Some(2), remap.field_number("test_transcode.Old", 1));
+    assert_eq!(None, remap.field_number("test_transcode.Old", 6));
+    remap.set_field_number("test_transcode.Old", 1, 100);
+    remap.set_field_number("test_transcode.Old", 6, 2);
+    let bytes = remap
+        .transcode_bytes(&old.write_to_bytes().unwrap())
+        .unwrap();
+    let new = New::parse_from_bytes(&bytes).unwrap();
+    assert_eq!(7, new.get_a());
+    assert_eq!(
+        &[10],
+        &new.get_unknown_fields().get(100).unwrap().varint[..]
+    );
+}
+
+#[test]
+fn test_transcode_invalid_input() {
+    let remap = FieldNumberRemap::by_name(&Old::descriptor_static(), &New::descriptor_static());
+    // nested message length larger than the input must not be preallocated
+    let bytes = [0x1a, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f];
+    let _ = remap.transcode_bytes(&bytes);
+    // unexpected end group tag in nested message
+    assert!(remap.transcode_bytes(&[0x1a, 0x01, 0x0c]).is_err());
+}
@@ -0,0 +1,74 @@
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::reflect::ReflectValueRef;
+use protobuf::MessageDyn;
+use protobuf_test_common::hex::decode_hex;
+
+use super::test_dynamic_group_pb::file_descriptor;
+
+fn dynamic_descriptor() -> MessageDescriptor {
+    let file = FileDescriptor::new_dynamic(file_descriptor().proto().clone(), Vec::new());
+    file.message_by_package_relative_name("ForDynamicGroupTest")
+        .unwrap()
+}
+
+fn get_singular<'a>(m: &'a dyn MessageDyn, name: &str) -> ReflectValueRef<'a> {
+    m.descriptor_dyn()
+        .get_field_by_name(name)
+        .unwrap()
+        .get_singular(m)
+        .unwrap()
+}
+
+// a = 150, g { x = 5, inner { s = "hi" } }, r { y = 1 }, r { y = 2 }
+const NESTED_GROUPS: &str = "08 96 01 13 18 05 23 2a 02 68 69 24 14 33 38 01 34 33 38 02 34";
+
+#[test]
+fn merge_from() {
+    let d = dynamic_descriptor();
+    let mut m = d.new_instance();
+    m.merge_from_bytes_dyn(&decode_hex(NESTED_GROUPS)).unwrap();
+
+    assert_eq!(ReflectValueRef::I32(150), get_singular(&*m, "a"));
+
+    let g = get_singular(&*m, "g").to_message().unwrap();
+    assert_eq!(ReflectValueRef::I32(5), get_singular(&*g, "x"));
+    let inner = get_singular(&*g, "inner").to_message().unwrap();
+    assert_eq!(ReflectValueRef::String("hi"), get_singular(&*inner, "s"));
+
+    let r = d.get_field_by_name("r").unwrap();
+    let r = r.get_repeated(&*m);
+    assert_eq!(2, r.len());
+    for (i, y) in [1, 2].iter().enumerate() {
+        let item = r.get(i).to_message().unwrap();
+        assert_eq!(ReflectValueRef::U32(*y), get_singular(&*item, "y"));
+    }
+}
+
+#[test]
+fn write_to() {
+    let mut m = dynamic_descriptor().new_instance();
+    m.merge_from_bytes_dyn(&decode_hex(NESTED_GROUPS)).unwrap();
+
+    let bytes = m.write_to_bytes_dyn().unwrap();
+    assert_eq!(decode_hex(NESTED_GROUPS), bytes);
+    assert_eq!(bytes.len() as u32, m.compute_size_dyn());
+}
+
+#[test]
+fn malformed() {
+    let d = dynamic_descriptor();
+    for hex in &[
+        // no end group
+        "13 18 05",
+        // end group with wrong field number
+        "13 18 05 1c",
+        // end group without start group
+        "14",
+        // group field with length-delimited wire type
+        "12 00",
+    ] {
+        let mut m = d.new_instance();
+        assert!(m.merge_from_bytes_dyn(&decode_hex(hex)).is_err(), "{}", hex);
+    }
+}
@@ -1,3 +1,5 @@
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::ReflectValueRef;
 use protobuf::Message;
 
 use super::test_reflect_default_pb::*;
@@ -43,3 +45,24 @@ fn test_oneof() {
             .unwrap()
     );
 }
+
+#[test]
+fn test_default_value() {
+    let generated = TestReflectDefault::descriptor_static();
+    let file = generated.file_descriptor();
+    let dynamic_file = FileDescriptor::new_dynamic(file.proto().clone(), Vec::new());
+    let dynamic = dynamic_file
+        .message_by_package_relative_name("TestReflectDefault")
+        .unwrap();
+
+    for descriptor in &[generated, dynamic] {
+        let field = |name| descriptor.get_field_by_name(name).unwrap();
+        assert_eq!(ReflectValueRef::I32(10), field("i").default_value());
+        assert_eq!(ReflectValueRef::String("sss"), field("s").default_value());
+        assert_eq!(Some(2), field("e").default_value().to_enum_value());
+        // first declared value
+        assert_eq!(Some(1), field("f").default_value().to_enum_value());
+        assert_eq!(ReflectValueRef::String(""), field("r").default_value());
+        assert_eq!(ReflectValueRef::I32(10), field("oi").default_value());
+    }
+}
@@ -1,3 +1,5 @@
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::ReflectValueBox;
 use protobuf::Message;
 
 use super::test_required_pb::*;
@@ -21,3 +23,104 @@ fn test_is_initialized_is_recursive() {
     m.inner.as_mut().unwrap().set_b(false);
     assert!(m.is_initialized());
 }
+
+fn paths(errors: Vec<protobuf::reflect::FieldPath>) -> Vec<String> {
+    errors.iter().map(|p| p.to_string()).collect()
+}
+
+#[test]
+fn test_find_initialization_errors() {
+    let mut m = TestRequiredOuter::new();
+    assert_eq!(vec!["inner"], paths(m.find_initialization_errors()));
+    m.inner = Some(Default::default()).into();
+    assert_eq!(vec!["inner.b"], paths(m.find_initialization_errors()));
+    m.inner.as_mut().unwrap().set_b(false);
+    assert!(m.find_initialization_errors().is_empty());
+}
+
+#[test]
+fn test_find_initialization_errors_collections() {
+    let mut m = TestRequiredCollections::new();
+    assert!(m.find_initialization_errors().is_empty());
+
+    let mut initialized = TestRequired::new();
+    initialized.set_b(true);
+    m.repeated_inner.push(initialized.clone());
+    m.repeated_inner.push(TestRequired::new());
+    m.map_inner.insert("x".to_owned(), initialized);
+    m.map_inner.insert("y".to_owned(), TestRequired::new());
+    assert_eq!(
+        vec!["repeated_inner[1].b", "map_inner[\"y\"].b"],
+        paths(m.find_initialization_errors())
+    );
+}
+
+#[test]
+fn test_check_initialized_error_contains_paths() {
+    let mut m = TestRequiredOuter::new();
+    m.inner = Some(Default::default()).into();
+    let error = m.write_to_bytes().unwrap_err();
+    assert_eq!(
+        "not all message fields set: TestRequiredOuter (missing: inner.b)",
+        error.to_string()
+    );
+}
+
+#[test]
+fn test_find_initialization_errors_dynamic() {
+    let file_descriptor =
+        FileDescriptor::new_dynamic(file_descriptor().proto().clone(), Vec::new());
+    let descriptor = file_descriptor
+        .message_by_package_relative_name("TestRequiredOuter")
+        .unwrap();
+    let inner = descriptor.get_field_by_name("inner").unwrap();
+
+    let mut m = descriptor.new_instance();
+    assert!(!m.is_initialized_dyn());
+    assert_eq!(vec!["inner"], paths(m.find_initialization_errors_dyn()));
+
+    inner.mut_message(&mut *m);
+    assert!(!m.is_initialized_dyn());
+    assert_eq!(vec!["inner.b"], paths(m.find_initialization_errors_dyn()));
+    assert_eq!(
+        "not all message fields set: TestRequiredOuter (missing: inner.b)",
+        m.check_initialized_dyn().unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn test_is_initialized_dynamic_map() {
+    let file_descriptor =
+        FileDescriptor::new_dynamic(file_descriptor().proto().clone(), Vec::new());
+    let descriptor = file_descriptor
+        .message_by_package_relative_name("TestRequiredCollections")
+        .unwrap();
+    let inner_descriptor = file_descriptor
+        .message_by_package_relative_name("TestRequired")
+        .unwrap();
+    let map_inner = descriptor.get_field_by_name("map_inner").unwrap();
+
+    let mut m = descriptor.new_instance();
+    assert!(m.is_initialized_dyn());
+
+    map_inner.mut_map(&mut *m).insert(
+        ReflectValueBox::String("x".to_owned()),
+        ReflectValueBox::Message(inner_descriptor.new_instance()),
+    );
+    assert!(!m.is_initialized_dyn());
+    assert_eq!(
+        vec!["map_inner[\"x\"].b"],
+        paths(m.find_initialization_errors_dyn())
+    );
+    assert!(m.write_to_bytes_dyn().is_err());
+
+    let mut inner = inner_descriptor.new_instance();
+    let b = inner_descriptor.get_field_by_name("b").unwrap();
+    b.set_singular_field(&mut *inner, ReflectValueBox::Bool(true));
+    map_inner.mut_map(&mut *m).insert(
+        ReflectValueBox::String("x".to_owned()),
+        ReflectValueBox::Message(inner),
+    );
+    assert!(m.is_initialized_dyn());
+    assert!(m.find_initialization_errors_dyn().is_empty());
+}
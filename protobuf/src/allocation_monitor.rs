@@ -0,0 +1,54 @@
+use crate::error::ProtobufResult;
+
+/// Callback interface to account memory allocated while parsing.
+///
+/// Monitor is installed with
+/// [`CodedInputStream::set_allocation_monitor`](crate::CodedInputStream::set_allocation_monitor)
+/// and is invoked only for large allocations, so it can be used to attribute
+/// and limit memory per request without affecting parsing of small values.
+///
+/// Error returned from a callback aborts parsing with that error.
+pub trait AllocationMonitor {
+    /// Called before reading `string` or `bytes` value or unknown length-delimited
+    /// field which is at least `bytes_threshold` bytes long.
+    fn on_large_bytes(&mut self, len: u64) -> ProtobufResult<()>;
+
+    /// Called each time the number of elements in repeated or map field
+    /// of generated message reaches a multiple of `repeated_threshold`.
+    fn on_large_repeated(&mut self, len: u64) -> ProtobufResult<()>;
+}
+
+/// Monitor installed into `CodedInputStream`.
+pub(crate) struct InstalledAllocationMonitor<'a> {
+    monitor: &'a mut dyn AllocationMonitor,
+    bytes_threshold: u64,
+    repeated_threshold: u64,
+}
+
+impl<'a> InstalledAllocationMonitor<'a> {
+    pub fn new(
+        monitor: &'a mut dyn AllocationMonitor,
+        bytes_threshold: u64,
+        repeated_threshold: u64,
+    ) -> InstalledAllocationMonitor<'a> {
+        InstalledAllocationMonitor {
+            monitor,
+            bytes_threshold,
+            repeated_threshold: repeated_threshold.max(1),
+        }
+    }
+
+    pub fn bytes(&mut self, len: u64) -> ProtobufResult<()> {
+        if len >= self.bytes_threshold {
+            self.monitor.on_large_bytes(len)?;
+        }
+        Ok(())
+    }
+
+    pub fn repeated(&mut self, len: u64) -> ProtobufResult<()> {
+        if len != 0 && len.is_multiple_of(self.repeated_threshold) {
+            self.monitor.on_large_repeated(len)?;
+        }
+        Ok(())
+    }
+}
@@ -221,6 +221,12 @@ impl<'ignore> BufReadIter<'ignore> {
         Ok(r)
     }
 
+    /// Input is a `Bytes` object, so it can be sliced without copying.
+    #[cfg(feature = "bytes")]
+    pub fn is_carllerche_bytes(&self) -> bool {
+        matches!(self.input_source, InputSource::Bytes(..))
+    }
+
     #[cfg(feature = "bytes")]
     pub fn read_exact_bytes(&mut self, len: usize) -> ProtobufResult<Bytes> {
         if let InputSource::Bytes(bytes) = self.input_source {
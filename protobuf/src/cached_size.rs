@@ -8,6 +8,18 @@ use std::sync::atomic::Ordering;
 /// It is always equal to itself to simplify generated code.
 /// (Generated code can use `#[derive(Eq)]`).
 ///
+/// Cached size is populated by [`Message::compute_size`](crate::Message::compute_size),
+/// which stores the size of the message and of all nested messages, and is read by
+/// [`Message::write_to_with_cached_sizes`](crate::Message::write_to_with_cached_sizes)
+/// to write length prefixes of nested messages. It is never invalidated automatically:
+/// any mutation of the message (including mutation through reflection)
+/// leaves it stale until the next `compute_size` call.
+///
+/// All `write_to*` functions call `compute_size` before writing, so cached sizes only
+/// matter when calling `write_to_with_cached_sizes` directly; call
+/// [`Message::refresh_cached_sizes`](crate::Message::refresh_cached_sizes)
+/// after mutation in that case.
+///
 /// This type should rarely be used directly.
 #[derive(Debug, Default)]
 pub struct CachedSize {
@@ -31,6 +43,13 @@ impl CachedSize {
     pub fn set(&self, size: u32) {
         self.size.store(size as usize, Ordering::Relaxed)
     }
+
+    /// Reset cached size to zero.
+    ///
+    /// This does not affect cached sizes of nested messages.
+    pub fn clear(&self) {
+        self.set(0)
+    }
 }
 
 impl Clone for CachedSize {
@@ -2,6 +2,8 @@
 
 use std::borrow::Borrow;
 use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
 use std::ops::Deref;
 use std::str;
 
@@ -11,7 +13,7 @@ use crate::clear::Clear;
 
 /// Thin wrapper around `Bytes` which guarantees that bytes are valid UTF-8 string.
 /// Should be API-compatible to `String`.
-#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Chars(Bytes);
 
 impl Chars {
@@ -78,6 +80,14 @@ impl Borrow<str> for Chars {
     }
 }
 
+// Hash must be consistent with `Borrow<str>`,
+// so `HashMap<Chars, V>` can be queried with `&str`.
+impl Hash for Chars {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
 impl Clear for Chars {
     fn clear(&mut self) {
         self.0.clear();
@@ -100,6 +110,8 @@ impl fmt::Debug for Chars {
 
 #[cfg(test)]
 mod test {
+    use std::collections::HashMap;
+
     use super::Chars;
 
     #[test]
@@ -111,4 +123,11 @@ mod test {
         assert_eq!(format!("{}", string), format!("{}", chars));
         assert_eq!(format!("{:?}", string), format!("{:?}", chars));
     }
+
+    #[test]
+    fn test_hash_map_get_by_str() {
+        let mut map = HashMap::new();
+        map.insert(Chars::from("abc"), 1);
+        assert_eq!(Some(&1), map.get("abc"));
+    }
 }
@@ -1,8 +1,12 @@
+use std::cmp;
 use std::io;
 use std::io::BufRead;
 use std::io::Read;
 use std::mem;
+use std::slice;
 
+use crate::allocation_monitor::AllocationMonitor;
+use crate::allocation_monitor::InstalledAllocationMonitor;
 use crate::buf_read_iter::BufReadIter;
 #[cfg(feature = "bytes")]
 use crate::bytes::Bytes;
@@ -15,6 +19,7 @@ use crate::error::ProtobufResult;
 use crate::error::WireError;
 use crate::message::Message;
 use crate::message_dyn::MessageDyn;
+use crate::parse_stats::ParseStats;
 use crate::reflect::types::ProtobufType;
 use crate::reflect::types::ProtobufTypeBool;
 use crate::reflect::types::ProtobufTypeDouble;
@@ -43,11 +48,81 @@ const DEFAULT_RECURSION_LIMIT: u32 = 100;
 // Max allocated vec when reading length-delimited from unknown input stream
 pub(crate) const READ_RAW_BYTES_MAX_ALLOC: usize = 10_000_000;
 
+/// Limits for reading untrusted input,
+/// see [`CodedInputStream::with_limits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputLimits {
+    /// Maximum nesting depth of messages and groups.
+    pub recursion_limit: u32,
+    /// Maximum length in bytes of a length-delimited value:
+    /// nested message, `string`, `bytes`, packed repeated field
+    /// or unknown length-delimited field.
+    pub max_length: u64,
+}
+
+impl Default for InputLimits {
+    fn default() -> InputLimits {
+        InputLimits {
+            recursion_limit: DEFAULT_RECURSION_LIMIT,
+            max_length: u64::MAX,
+        }
+    }
+}
+
+/// Fixed-size number which is encoded as little-endian bytes.
+///
+/// # Safety
+///
+/// Any bit pattern must be a valid value of the type,
+/// so bytes can be read directly into memory of the values.
+pub(crate) unsafe trait FixedLe: Copy + Default {
+    /// Convert from little-endian, no-op on little-endian targets.
+    fn le_to_native(self) -> Self;
+}
+
+unsafe impl FixedLe for u32 {
+    fn le_to_native(self) -> Self {
+        u32::from_le(self)
+    }
+}
+
+unsafe impl FixedLe for u64 {
+    fn le_to_native(self) -> Self {
+        u64::from_le(self)
+    }
+}
+
+unsafe impl FixedLe for i32 {
+    fn le_to_native(self) -> Self {
+        i32::from_le(self)
+    }
+}
+
+unsafe impl FixedLe for i64 {
+    fn le_to_native(self) -> Self {
+        i64::from_le(self)
+    }
+}
+
+unsafe impl FixedLe for f32 {
+    fn le_to_native(self) -> Self {
+        f32::from_bits(u32::from_le(self.to_bits()))
+    }
+}
+
+unsafe impl FixedLe for f64 {
+    fn le_to_native(self) -> Self {
+        f64::from_bits(u64::from_le(self.to_bits()))
+    }
+}
+
 /// Buffered read with handy utilities.
 pub struct CodedInputStream<'a> {
     source: BufReadIter<'a>,
     recursion_level: u32,
-    recursion_limit: u32,
+    limits: InputLimits,
+    allocation_monitor: Option<InstalledAllocationMonitor<'a>>,
+    parse_stats: Option<ParseStats>,
 }
 
 impl<'a> CodedInputStream<'a> {
@@ -84,21 +159,130 @@ impl<'a> CodedInputStream<'a> {
         CodedInputStream {
             source: source,
             recursion_level: 0,
-            recursion_limit: DEFAULT_RECURSION_LIMIT,
+            limits: InputLimits::default(),
+            allocation_monitor: None,
+            parse_stats: None,
         }
     }
 
     /// Set the recursion limit.
     pub fn set_recursion_limit(&mut self, limit: u32) {
-        self.recursion_limit = limit;
+        self.limits.recursion_limit = limit;
+    }
+
+    /// Replace the limits, e. g. tighten them to parse untrusted input.
+    ///
+    /// Exceeding a limit is reported as an error.
+    ///
+    /// ```
+    /// # use protobuf::CodedInputStream;
+    /// # use protobuf::InputLimits;
+    /// # use protobuf::Message;
+    /// # fn foo<M: Message>(bytes: &[u8]) -> protobuf::ProtobufResult<M> {
+    /// let mut is = CodedInputStream::from_bytes(bytes).with_limits(InputLimits {
+    ///     recursion_limit: 10,
+    ///     max_length: 1 << 20,
+    /// });
+    /// M::parse_from(&mut is)
+    /// # }
+    /// ```
+    pub fn with_limits(mut self, limits: InputLimits) -> CodedInputStream<'a> {
+        self.limits = limits;
+        self
+    }
+
+    /// Current limits.
+    pub fn limits(&self) -> InputLimits {
+        self.limits
+    }
+
+    #[inline]
+    fn check_length(&self, len: u64) -> ProtobufResult<()> {
+        if len > self.limits.max_length {
+            return Err(ProtobufError::WireError(WireError::OverLengthLimit));
+        }
+        Ok(())
+    }
+
+    /// Install allocation monitor.
+    ///
+    /// `monitor` is notified about `string` and `bytes` values
+    /// of at least `bytes_threshold` bytes, and about repeated fields each time
+    /// the number of elements reaches a multiple of `repeated_threshold`.
+    pub fn set_allocation_monitor(
+        &mut self,
+        monitor: &'a mut dyn AllocationMonitor,
+        bytes_threshold: u64,
+        repeated_threshold: u64,
+    ) {
+        self.allocation_monitor = Some(InstalledAllocationMonitor::new(
+            monitor,
+            bytes_threshold,
+            repeated_threshold,
+        ));
+    }
+
+    /// Start collecting [`ParseStats`], resetting previously collected statistics.
+    ///
+    /// ```
+    /// # use protobuf::CodedInputStream;
+    /// # use protobuf::Message;
+    /// # fn foo<M: Message>(bytes: &[u8]) -> protobuf::ProtobufResult<()> {
+    /// let mut is = CodedInputStream::from_bytes(bytes);
+    /// is.enable_parse_stats();
+    /// let message = M::parse_from(&mut is)?;
+    /// let stats = is.parse_stats().unwrap();
+    /// if stats.unknown_fields() != 0 {
+    ///     println!("{} unknown fields", stats.unknown_fields());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn enable_parse_stats(&mut self) {
+        self.parse_stats = Some(ParseStats::new());
+    }
+
+    /// Statistics collected since [`enable_parse_stats`](Self::enable_parse_stats),
+    /// `None` if statistics are not enabled.
+    pub fn parse_stats(&self) -> Option<&ParseStats> {
+        self.parse_stats.as_ref()
+    }
+
+    /// Called after unknown field value starting at `start_pos` is read or skipped.
+    #[inline]
+    pub(crate) fn report_unknown_field(&mut self, start_pos: u64) {
+        if let Some(stats) = &mut self.parse_stats {
+            stats.unknown_fields += 1;
+            stats.unknown_bytes += self.source.pos() - start_pos;
+        }
+    }
+
+    #[inline]
+    fn report_bytes(&mut self, len: u64) -> ProtobufResult<()> {
+        match &mut self.allocation_monitor {
+            Some(monitor) => monitor.bytes(len),
+            None => Ok(()),
+        }
+    }
+
+    /// Called after element is added to repeated or map field.
+    #[inline]
+    pub(crate) fn report_repeated_len(&mut self, len: usize) -> ProtobufResult<()> {
+        match &mut self.allocation_monitor {
+            Some(monitor) => monitor.repeated(len as u64),
+            None => Ok(()),
+        }
     }
 
     #[inline]
     pub(crate) fn incr_recursion(&mut self) -> ProtobufResult<()> {
-        if self.recursion_level >= self.recursion_limit {
+        if self.recursion_level >= self.limits.recursion_limit {
             return Err(ProtobufError::WireError(WireError::OverRecursionLimit));
         }
         self.recursion_level += 1;
+        if let Some(stats) = &mut self.parse_stats {
+            stats.max_depth = cmp::max(stats.max_depth, self.recursion_level);
+        }
         Ok(())
     }
 
@@ -129,6 +313,8 @@ impl<'a> CodedInputStream<'a> {
     /// constructed with `Bytes` parameter.
     #[cfg(feature = "bytes")]
     fn read_raw_callerche_bytes(&mut self, count: usize) -> ProtobufResult<Bytes> {
+        self.check_length(count as u64)?;
+        self.report_bytes(count as u64)?;
         self.source.read_exact_bytes(count)
     }
 
@@ -139,7 +325,10 @@ impl<'a> CodedInputStream<'a> {
     }
 
     /// Push new limit, return previous limit.
+    ///
+    /// Error is returned if the limit exceeds [`InputLimits::max_length`].
     pub fn push_limit(&mut self, limit: u64) -> ProtobufResult<u64> {
+        self.check_length(limit)?;
         self.source.push_limit(limit)
     }
 
@@ -169,7 +358,7 @@ impl<'a> CodedInputStream<'a> {
         let mut r: u64 = 0;
         let mut i = 0;
         loop {
-            if i == 10 {
+            if i == wire_format::MAX_VARINT_ENCODED_LEN {
                 return Err(ProtobufError::WireError(WireError::IncorrectVarint));
             }
             let b = self.read_raw_byte()?;
@@ -204,13 +393,13 @@ impl<'a> CodedInputStream<'a> {
             // Handle the case of two bytes too
             ret = (rem[0] & 0x7f) as u64 | (rem[1] as u64) << 7;
             consume = 2;
-        } else if rem.len() >= 10 {
+        } else if rem.len() >= wire_format::MAX_VARINT_ENCODED_LEN {
             // Read from array when buf at at least 10 bytes,
             // max len for varint.
             let mut r: u64 = 0;
             let mut i: usize = 0;
             loop {
-                if i == 10 {
+                if i == wire_format::MAX_VARINT_ENCODED_LEN {
                     return Err(ProtobufError::WireError(WireError::IncorrectVarint));
                 }
 
@@ -263,6 +452,19 @@ impl<'a> CodedInputStream<'a> {
     /// Read tag
     #[inline]
     pub fn read_tag(&mut self) -> ProtobufResult<wire_format::Tag> {
+        let tag = self.read_tag_not_counted()?;
+        if let Some(stats) = &mut self.parse_stats {
+            if tag.wire_type() != wire_format::WireTypeEndGroup {
+                stats.fields += 1;
+            }
+        }
+        Ok(tag)
+    }
+
+    /// Read tag without counting the field in [`ParseStats`],
+    /// used for fields of skipped groups, which are a part of a single unknown field.
+    #[inline]
+    pub(crate) fn read_tag_not_counted(&mut self) -> ProtobufResult<wire_format::Tag> {
         let v = self.read_raw_varint32()?;
         match wire_format::Tag::new(v) {
             Some(tag) => Ok(tag),
@@ -359,30 +561,69 @@ impl<'a> CodedInputStream<'a> {
         Ok(ProtobufEnumOrUnknown::from_i32(self.read_int32()?))
     }
 
+    /// Read fixed-size values filling the whole slice.
+    ///
+    /// Bytes are copied directly into the slice, and converted
+    /// in place only on big-endian targets.
+    fn read_fixed_into_slice<V: FixedLe>(&mut self, target: &mut [V]) -> ProtobufResult<()> {
+        // Safe because of `FixedLe` contract
+        let bytes = unsafe {
+            slice::from_raw_parts_mut(target.as_mut_ptr() as *mut u8, mem::size_of_val(target))
+        };
+        self.read_exact(bytes)?;
+        if cfg!(target_endian = "big") {
+            for v in target.iter_mut() {
+                *v = v.le_to_native();
+            }
+        }
+        Ok(())
+    }
+
     fn read_repeated_packed_fixed_into<T: ProtobufTypeFixed>(
         &mut self,
         target: &mut Vec<T::ProtobufValue>,
-    ) -> ProtobufResult<()> {
+    ) -> ProtobufResult<()>
+    where
+        T::ProtobufValue: FixedLe,
+    {
         let len_bytes = self.read_raw_varint64()?;
-
-        let reserve = if len_bytes <= READ_RAW_BYTES_MAX_ALLOC as u64 {
-            (len_bytes as usize) / (T::ENCODED_SIZE as usize)
-        } else {
-            // prevent OOM on malformed input
-            // probably should truncate
-            READ_RAW_BYTES_MAX_ALLOC / (T::ENCODED_SIZE as usize)
-        };
-
-        target.reserve(reserve);
+        if len_bytes % T::ENCODED_SIZE as u64 != 0 {
+            return Err(ProtobufError::WireError(WireError::IncorrectPackedLength));
+        }
 
         let old_limit = self.push_limit(len_bytes)?;
-        while !self.eof()? {
-            target.push(T::read(self)?);
+        let mut remaining = len_bytes / T::ENCODED_SIZE as u64;
+        while remaining != 0 {
+            // read in chunks to prevent OOM on malformed input
+            let chunk = cmp::min(
+                remaining,
+                (READ_RAW_BYTES_MAX_ALLOC / T::ENCODED_SIZE as usize) as u64,
+            ) as usize;
+            let start = target.len();
+            target.resize(start + chunk, Default::default());
+            if let Err(e) = self.read_fixed_into_slice(&mut target[start..]) {
+                target.truncate(start);
+                return Err(e);
+            }
+            self.report_repeated_len(target.len())?;
+            remaining -= chunk as u64;
         }
         self.pop_limit(old_limit);
         Ok(())
     }
 
+    /// Read packed field into a slice, length of field must be exactly the size of the slice.
+    fn read_repeated_packed_fixed_into_exact<V: FixedLe>(
+        &mut self,
+        target: &mut [V],
+    ) -> ProtobufResult<()> {
+        let len_bytes = self.read_raw_varint64()?;
+        if len_bytes != mem::size_of_val(target) as u64 {
+            return Err(ProtobufError::WireError(WireError::IncorrectPackedLength));
+        }
+        self.read_fixed_into_slice(target)
+    }
+
     fn read_repeated_packed_into<T: ProtobufType>(
         &mut self,
         target: &mut Vec<T::ProtobufValue>,
@@ -402,6 +643,7 @@ impl<'a> CodedInputStream<'a> {
         let old_limit = self.push_limit(len_bytes)?;
         while !self.eof()? {
             target.push(T::read(self)?);
+            self.report_repeated_len(target.len())?;
         }
         self.pop_limit(old_limit);
         Ok(())
@@ -494,6 +736,87 @@ impl<'a> CodedInputStream<'a> {
         self.read_repeated_packed_fixed_into::<ProtobufTypeSfixed32>(target)
     }
 
+    /// Read repeated packed `double` into a slice.
+    ///
+    /// Packed field must contain exactly `target.len()` values,
+    /// otherwise an error is returned.
+    pub fn read_repeated_packed_double_into_exact(
+        &mut self,
+        target: &mut [f64],
+    ) -> ProtobufResult<()> {
+        self.read_repeated_packed_fixed_into_exact(target)
+    }
+
+    /// Read repeated packed `float` into a slice.
+    ///
+    /// Packed field must contain exactly `target.len()` values,
+    /// otherwise an error is returned.
+    pub fn read_repeated_packed_float_into_exact(
+        &mut self,
+        target: &mut [f32],
+    ) -> ProtobufResult<()> {
+        self.read_repeated_packed_fixed_into_exact(target)
+    }
+
+    /// Read repeated packed `fixed64` into a slice.
+    ///
+    /// Packed field must contain exactly `target.len()` values,
+    /// otherwise an error is returned.
+    pub fn read_repeated_packed_fixed64_into_exact(
+        &mut self,
+        target: &mut [u64],
+    ) -> ProtobufResult<()> {
+        self.read_repeated_packed_fixed_into_exact(target)
+    }
+
+    /// Read repeated packed `fixed32` into a slice.
+    ///
+    /// Packed field must contain exactly `target.len()` values,
+    /// otherwise an error is returned.
+    pub fn read_repeated_packed_fixed32_into_exact(
+        &mut self,
+        target: &mut [u32],
+    ) -> ProtobufResult<()> {
+        self.read_repeated_packed_fixed_into_exact(target)
+    }
+
+    /// Read repeated packed `sfixed64` into a slice.
+    ///
+    /// Packed field must contain exactly `target.len()` values,
+    /// otherwise an error is returned.
+    pub fn read_repeated_packed_sfixed64_into_exact(
+        &mut self,
+        target: &mut [i64],
+    ) -> ProtobufResult<()> {
+        self.read_repeated_packed_fixed_into_exact(target)
+    }
+
+    /// Read repeated packed `sfixed32` into a slice.
+    ///
+    /// Packed field must contain exactly `target.len()` values,
+    /// otherwise an error is returned.
+    pub fn read_repeated_packed_sfixed32_into_exact(
+        &mut self,
+        target: &mut [i32],
+    ) -> ProtobufResult<()> {
+        self.read_repeated_packed_fixed_into_exact(target)
+    }
+
+    /// Read raw little-endian content of a packed fixed-size field into a slice.
+    ///
+    /// Field length must be exactly `target.len()` bytes,
+    /// otherwise an error is returned.
+    pub fn read_repeated_packed_fixed_bytes_into_exact(
+        &mut self,
+        target: &mut [u8],
+    ) -> ProtobufResult<()> {
+        let len_bytes = self.read_raw_varint64()?;
+        if len_bytes != target.len() as u64 {
+            return Err(ProtobufError::WireError(WireError::IncorrectPackedLength));
+        }
+        self.read_exact(target)
+    }
+
     /// Read repeated packed `bool`
     pub fn read_repeated_packed_bool_into(&mut self, target: &mut Vec<bool>) -> ProtobufResult<()> {
         self.read_repeated_packed_into::<ProtobufTypeBool>(target)
@@ -531,12 +854,17 @@ impl<'a> CodedInputStream<'a> {
 
     /// Skip field
     pub fn skip_field(&mut self, wire_type: wire_format::WireType) -> ProtobufResult<()> {
-        self.read_unknown(wire_type).map(|_| ())
+        let start_pos = self.pos();
+        self.read_unknown(wire_type)?;
+        self.report_unknown_field(start_pos);
+        Ok(())
     }
 
     /// Read raw bytes into the supplied vector.  The vector will be resized as needed and
     /// overwritten.
     pub fn read_raw_bytes_into(&mut self, count: u32, target: &mut Vec<u8>) -> ProtobufResult<()> {
+        self.check_length(count as u64)?;
+        self.report_bytes(count as u64)?;
         self.source.read_exact_to_vec(count as usize, target)
     }
 
@@ -560,6 +888,13 @@ impl<'a> CodedInputStream<'a> {
         Ok(r)
     }
 
+    /// Input is a `Bytes` object, so `read_carllerche_*` functions
+    /// return shared views of it instead of copies.
+    #[cfg(feature = "bytes")]
+    pub(crate) fn is_carllerche_bytes(&self) -> bool {
+        self.source.is_carllerche_bytes()
+    }
+
     /// Read `bytes` field, length delimited
     #[cfg(feature = "bytes")]
     pub fn read_carllerche_bytes(&mut self) -> ProtobufResult<Bytes> {
@@ -655,10 +990,17 @@ mod test {
     use std::io::Read;
 
     use super::CodedInputStream;
+    use super::InputLimits;
     use super::READ_RAW_BYTES_MAX_ALLOC;
+    use crate::allocation_monitor::AllocationMonitor;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FieldDescriptorProto;
     use crate::error::ProtobufError;
     use crate::error::ProtobufResult;
+    use crate::error::WireError;
     use crate::hex::decode_hex;
+    use crate::well_known_types::Empty;
+    use crate::Message;
 
     fn test_read_partial<F>(hex: &str, mut callback: F)
     where
@@ -781,6 +1123,60 @@ mod test {
         test_read_v("40 d5 ab 68 b3 07 3d 46", 23e29, |is| is.read_double());
     }
 
+    #[test]
+    fn test_input_stream_read_repeated_packed_fixed() {
+        test_read_v("08 01 00 00 00 ff ff ff ff", vec![1, u32::MAX], |is| {
+            let mut v = Vec::new();
+            is.read_repeated_packed_fixed32_into(&mut v).map(|()| v)
+        });
+        test_read_v("08 ff ff ff ff ff ff ff ff", vec![-1], |is| {
+            let mut v = Vec::new();
+            is.read_repeated_packed_sfixed64_into(&mut v).map(|()| v)
+        });
+        test_read_v("04 95 73 13 61", vec![17e19], |is| {
+            let mut v = vec![1.0];
+            is.read_repeated_packed_float_into(&mut v)
+                .map(|()| v[1..].to_vec())
+        });
+        // length is not multiple of element size
+        test_read_partial("03 01 00 00", |is| {
+            let mut v = Vec::new();
+            assert!(is.read_repeated_packed_fixed32_into(&mut v).is_err());
+        });
+        // field is truncated
+        test_read_partial("08 01 00 00 00 02", |is| {
+            let mut v = Vec::new();
+            assert!(is.read_repeated_packed_fixed32_into(&mut v).is_err());
+        });
+    }
+
+    #[test]
+    fn test_input_stream_read_repeated_packed_fixed_into_exact() {
+        test_read_v("08 01 00 00 00 02 00 00 00", [1, 2], |is| {
+            let mut v = [0; 2];
+            is.read_repeated_packed_fixed32_into_exact(&mut v)
+                .map(|()| v)
+        });
+        test_read_v("08 40 d5 ab 68 b3 07 3d 46", [23e29], |is| {
+            let mut v = [0.0];
+            is.read_repeated_packed_double_into_exact(&mut v)
+                .map(|()| v)
+        });
+        test_read_v("04 01 00 00 00", [1, 0, 0, 0], |is| {
+            let mut v = [0; 4];
+            is.read_repeated_packed_fixed_bytes_into_exact(&mut v)
+                .map(|()| v)
+        });
+        test_read_partial("08 01 00 00 00 02 00 00 00", |is| {
+            let mut v = [0; 3];
+            assert!(is.read_repeated_packed_fixed32_into_exact(&mut v).is_err());
+        });
+        test_read_partial("08 01 00 00 00", |is| {
+            let mut v = [0; 2];
+            assert!(is.read_repeated_packed_fixed32_into_exact(&mut v).is_err());
+        });
+    }
+
     #[test]
     fn test_input_stream_skip_raw_bytes() {
         test_read("", |reader| {
@@ -863,4 +1259,144 @@ mod test {
 
         assert!(is.eof().expect("eof"));
     }
+
+    #[derive(Default)]
+    struct RecordingMonitor {
+        bytes: Vec<u64>,
+        repeated: Vec<u64>,
+        max_bytes: u64,
+    }
+
+    impl AllocationMonitor for RecordingMonitor {
+        fn on_large_bytes(&mut self, len: u64) -> ProtobufResult<()> {
+            self.bytes.push(len);
+            if len > self.max_bytes {
+                return Err(ProtobufError::IoError(io::Error::other("quota exceeded")));
+            }
+            Ok(())
+        }
+
+        fn on_large_repeated(&mut self, len: u64) -> ProtobufResult<()> {
+            self.repeated.push(len);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_input_stream_allocation_monitor() {
+        let mut monitor = RecordingMonitor {
+            max_bytes: 10,
+            ..Default::default()
+        };
+        {
+            let bytes = decode_hex("01 61 03 61 62 63 05 01 02 03 04 05");
+            let mut is = CodedInputStream::from_bytes(&bytes);
+            is.set_allocation_monitor(&mut monitor, 3, 2);
+            assert_eq!("a", is.read_string().unwrap());
+            assert_eq!("abc", is.read_string().unwrap());
+            let mut v = Vec::new();
+            is.read_repeated_packed_int32_into(&mut v).unwrap();
+            assert_eq!(vec![1, 2, 3, 4, 5], v);
+        }
+        assert_eq!(vec![3], monitor.bytes);
+        assert_eq!(vec![2, 4], monitor.repeated);
+    }
+
+    #[test]
+    fn test_input_stream_allocation_monitor_error() {
+        let mut monitor = RecordingMonitor {
+            max_bytes: 2,
+            ..Default::default()
+        };
+        let bytes = decode_hex("03 61 62 63");
+        let mut is = CodedInputStream::from_bytes(&bytes);
+        is.set_allocation_monitor(&mut monitor, 0, 1);
+        assert!(is.read_bytes().is_err());
+    }
+
+    #[test]
+    fn test_input_stream_parse_stats() {
+        let mut field = FieldDescriptorProto::new();
+        field.set_name("f".to_owned());
+        field.options.mut_or_default().set_deprecated(true);
+        field.mut_unknown_fields().add_varint(1000, 300);
+        field
+            .mut_unknown_fields()
+            .add_length_delimited(1001, b"abc".to_vec());
+        let mut message = DescriptorProto::new();
+        message.field.push(field);
+        let bytes = message.write_to_bytes().unwrap();
+
+        let mut is = CodedInputStream::from_bytes(&bytes);
+        assert!(is.parse_stats().is_none());
+        is.enable_parse_stats();
+        let parsed = DescriptorProto::parse_from(&mut is).unwrap();
+        assert_eq!(message, parsed);
+
+        let stats = is.parse_stats().unwrap();
+        // field, name, options, deprecated and two unknown fields
+        assert_eq!(6, stats.fields());
+        assert_eq!(2, stats.unknown_fields());
+        // varint 300 is two bytes, "abc" is length and three bytes
+        assert_eq!(6, stats.unknown_bytes());
+        assert_eq!(2, stats.max_depth());
+    }
+
+    #[test]
+    fn test_input_stream_parse_stats_skip_group() {
+        // group 1 with varint field 2, then varint field 3
+        let bytes = decode_hex("0b 10 01 0c 18 01");
+        let mut is = CodedInputStream::from_bytes(&bytes);
+        is.enable_parse_stats();
+        let parsed = Empty::parse_from(&mut is).unwrap();
+        // Groups are skipped, other unknown fields are stored
+        assert_eq!(1, parsed.get_unknown_fields().iter().count());
+        let stats = is.parse_stats().unwrap();
+        // Fields of the skipped group are not counted
+        assert_eq!(2, stats.fields());
+        assert_eq!(2, stats.unknown_fields());
+        assert_eq!(0, stats.max_depth());
+    }
+
+    #[test]
+    fn test_input_stream_with_limits() {
+        let mut field = FieldDescriptorProto::new();
+        field.set_name("f".to_owned());
+        let mut message = DescriptorProto::new();
+        message.set_name("message".to_owned());
+        message.field.push(field);
+        let bytes = message.write_to_bytes().unwrap();
+
+        let parse = |limits| {
+            let mut is = CodedInputStream::from_bytes(&bytes).with_limits(limits);
+            assert_eq!(limits, is.limits());
+            DescriptorProto::parse_from(&mut is)
+        };
+
+        let default = InputLimits::default();
+        assert_eq!(message, parse(default).unwrap());
+        assert_eq!(
+            message,
+            parse(InputLimits {
+                recursion_limit: 1,
+                max_length: 7,
+            })
+            .unwrap()
+        );
+        match parse(InputLimits {
+            recursion_limit: 0,
+            ..default
+        }) {
+            Err(ProtobufError::WireError(WireError::OverRecursionLimit)) => {}
+            r => panic!("{:?}", r),
+        }
+        // Length of "message" is 7
+        match parse(InputLimits {
+            max_length: 6,
+            ..default
+        }) {
+            Err(ProtobufError::WireError(WireError::OverLengthLimit)) => {}
+            r => panic!("{:?}", r),
+        }
+    }
 }
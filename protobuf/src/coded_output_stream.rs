@@ -1,6 +1,7 @@
 use std::io;
 use std::io::Write;
 
+use crate::coded_input_stream::CodedInputStream;
 use crate::misc::remaining_capacity_as_slice_mut;
 use crate::misc::remove_lifetime_mut;
 use crate::varint;
@@ -13,6 +14,7 @@ use crate::ProtobufEnum;
 use crate::ProtobufEnumOrUnknown;
 use crate::ProtobufError;
 use crate::ProtobufResult;
+use crate::SerializeScratch;
 use crate::UnknownFields;
 use crate::UnknownValueRef;
 
@@ -26,15 +28,21 @@ pub trait WithCodedOutputStream {
         F: FnOnce(&mut CodedOutputStream) -> ProtobufResult<T>;
 }
 
+// Output is buffered in the thread-local scratch buffer, so writer gets
+// one `write_all` call per `OUTPUT_STREAM_BUFFER_SIZE` bytes of output
+// regardless of whether the writer is buffered itself
+// (`dyn Write` cannot tell that), and the buffer is not allocated on each call.
 impl<'a> WithCodedOutputStream for &'a mut (dyn Write + 'a) {
     fn with_coded_output_stream<T, F>(self, cb: F) -> ProtobufResult<T>
     where
         F: FnOnce(&mut CodedOutputStream) -> ProtobufResult<T>,
     {
-        let mut os = CodedOutputStream::new(self);
-        let r = cb(&mut os)?;
-        os.flush()?;
-        Ok(r)
+        SerializeScratch::with_thread_local(|scratch| {
+            let mut os = CodedOutputStream::with_scratch(self, scratch);
+            let r = cb(&mut os)?;
+            os.flush()?;
+            Ok(r)
+        })
     }
 }
 
@@ -86,6 +94,21 @@ impl<'a> CodedOutputStream<'a> {
         }
     }
 
+    /// Construct from given `Write` with output buffer taken from the scratch.
+    ///
+    /// Unlike [`new`](CodedOutputStream::new), this function does not allocate
+    /// when called repeatedly with the same scratch.
+    pub fn with_scratch(
+        writer: &'a mut dyn Write,
+        scratch: &'a mut SerializeScratch,
+    ) -> CodedOutputStream<'a> {
+        CodedOutputStream {
+            target: OutputTarget::Write(writer, Vec::new()),
+            buffer: scratch.output_buffer(OUTPUT_STREAM_BUFFER_SIZE),
+            position: 0,
+        }
+    }
+
     /// `CodedOutputStream` which writes directly to bytes.
     ///
     /// Attempt to write more than bytes capacity results in error.
@@ -106,6 +129,20 @@ impl<'a> CodedOutputStream<'a> {
         }
     }
 
+    /// Number of bytes written to the slice given to [`bytes`](CodedOutputStream::bytes).
+    ///
+    /// # Panics
+    ///
+    /// If underlying write is not a slice
+    pub(crate) fn bytes_written(&self) -> usize {
+        match self.target {
+            OutputTarget::Bytes => self.position,
+            OutputTarget::Write(..) | OutputTarget::Vec(..) => {
+                panic!("must not be called with Writer or Vec");
+            }
+        }
+    }
+
     /// Check if EOF is reached.
     ///
     /// # Panics
@@ -217,6 +254,28 @@ impl<'a> CodedOutputStream<'a> {
         self.write_raw_varint32(wire_format::Tag::make(field_number, wire_type).value())
     }
 
+    /// Write a field with already encoded value.
+    ///
+    /// `bytes` must contain field value exactly as it follows the tag on the wire:
+    /// varint, 4 or 8 bytes of fixed-width value, or length prefix followed
+    /// by content for length-delimited fields. Groups are not supported.
+    ///
+    /// Bytes are copied as is, and validated only in debug builds.
+    pub fn write_raw_field(
+        &mut self,
+        field_number: u32,
+        wire_type: wire_format::WireType,
+        bytes: &[u8],
+    ) -> ProtobufResult<()> {
+        debug_assert!(
+            is_valid_raw_field_value(wire_type, bytes),
+            "malformed {:?} field value",
+            wire_type
+        );
+        self.write_tag(field_number, wire_type)?;
+        self.write_raw_bytes(bytes)
+    }
+
     /// Write varint
     pub fn write_raw_varint32(&mut self, value: u32) -> ProtobufResult<()> {
         if self.buffer.len() - self.position >= 5 {
@@ -234,14 +293,14 @@ impl<'a> CodedOutputStream<'a> {
 
     /// Write varint
     pub fn write_raw_varint64(&mut self, value: u64) -> ProtobufResult<()> {
-        if self.buffer.len() - self.position >= 10 {
+        if self.buffer.len() - self.position >= wire_format::MAX_VARINT_ENCODED_LEN {
             // fast path
             let len = varint::encode_varint64(value, &mut self.buffer[self.position..]);
             self.position += len;
             Ok(())
         } else {
             // slow path
-            let buf = &mut [0u8; 10];
+            let buf = &mut [0u8; wire_format::MAX_VARINT_ENCODED_LEN];
             let len = varint::encode_varint64(value, buf);
             self.write_raw_bytes(&buf[..len])
         }
@@ -492,6 +551,17 @@ impl<'a> CodedOutputStream<'a> {
                 self.write_unknown(number, value)?;
             }
         }
+        self.write_unknown_groups(fields)
+    }
+
+    fn write_unknown_groups(&mut self, fields: &UnknownFields) -> ProtobufResult<()> {
+        for (number, groups) in fields.iter_groups() {
+            for contents in groups {
+                self.write_tag(number, wire_format::WireTypeStartGroup)?;
+                self.write_raw_bytes(contents)?;
+                self.write_tag(number, wire_format::WireTypeEndGroup)?;
+            }
+        }
         Ok(())
     }
 
@@ -500,14 +570,14 @@ impl<'a> CodedOutputStream<'a> {
         &mut self,
         fields: &UnknownFields,
     ) -> ProtobufResult<()> {
-        let mut fields: Vec<_> = fields.iter().collect();
-        fields.sort_by_key(|(n, _)| *n);
-        for (number, values) in fields {
+        let mut sorted: Vec<_> = fields.iter().collect();
+        sorted.sort_by_key(|(n, _)| *n);
+        for (number, values) in sorted {
             for value in values {
                 self.write_unknown(number, value)?;
             }
         }
-        Ok(())
+        self.write_unknown_groups(fields)
     }
 
     /// Write bytes
@@ -579,6 +649,12 @@ impl<'a> Write for CodedOutputStream<'a> {
     }
 }
 
+/// Check that bytes contain exactly one field value of given wire type.
+fn is_valid_raw_field_value(wire_type: wire_format::WireType, bytes: &[u8]) -> bool {
+    let mut is = CodedInputStream::from_bytes(bytes);
+    is.skip_field(wire_type).is_ok() && is.eof().unwrap_or(false)
+}
+
 impl<'a> Drop for CodedOutputStream<'a> {
     fn drop(&mut self) {
         // This may panic
@@ -665,6 +741,57 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_output_stream_write_raw_field() {
+        test_write("08 96 01", |os| {
+            os.write_raw_field(1, wire_format::WireTypeVarint, &[0x96, 0x01])
+        });
+        test_write("15 01 02 03 04", |os| {
+            os.write_raw_field(2, wire_format::WireTypeFixed32, &[1, 2, 3, 4])
+        });
+        test_write("1a 02 61 62", |os| {
+            os.write_raw_field(3, wire_format::WireTypeLengthDelimited, &[2, 0x61, 0x62])
+        });
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_output_stream_write_raw_field_malformed() {
+        let mut buf = [0; 16];
+        let mut os = CodedOutputStream::bytes(&mut buf);
+        // truncated varint
+        let _ = os.write_raw_field(1, wire_format::WireTypeVarint, &[0x96]);
+    }
+
+    #[test]
+    fn test_is_valid_raw_field_value() {
+        assert!(is_valid_raw_field_value(
+            wire_format::WireTypeVarint,
+            &[0x96, 0x01]
+        ));
+        assert!(!is_valid_raw_field_value(
+            wire_format::WireTypeVarint,
+            &[0x96]
+        ));
+        assert!(!is_valid_raw_field_value(
+            wire_format::WireTypeVarint,
+            &[1, 2]
+        ));
+        assert!(is_valid_raw_field_value(
+            wire_format::WireTypeFixed64,
+            &[0; 8]
+        ));
+        assert!(!is_valid_raw_field_value(
+            wire_format::WireTypeFixed64,
+            &[0; 4]
+        ));
+        assert!(!is_valid_raw_field_value(
+            wire_format::WireTypeStartGroup,
+            &[]
+        ));
+    }
+
     #[test]
     fn test_output_stream_write_raw_varint32() {
         test_write("96 01", |os| os.write_raw_varint32(150));
@@ -0,0 +1,309 @@
+//! Compression of serialized messages with per-type zstd dictionaries.
+//!
+//! Serialized messages of the same type share field tags, enum values
+//! and often string values, so a dictionary trained on samples of one message type
+//! compresses small messages of that type much better than a generic one.
+//!
+//! ```
+//! # use protobuf::compression::DictionaryBuilder;
+//! # use protobuf::MessageDyn;
+//! # fn foo(samples: &[Box<dyn MessageDyn>], message: &dyn MessageDyn) -> protobuf::ProtobufResult<()> {
+//! let mut builder = DictionaryBuilder::new();
+//! for sample in samples {
+//!     builder.add_sample(&**sample)?;
+//! }
+//! let dictionaries = builder.build()?;
+//!
+//! let compressed = dictionaries.compress(message, 3)?;
+//! let decompressed = dictionaries.decompress(&message.descriptor_dyn(), &compressed)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! This module is available with `with-zstd` feature.
+
+use std::collections::HashMap;
+use std::io;
+use std::io::Read;
+
+use crate::message_dyn::MessageDyn;
+use crate::reflect::MessageDescriptor;
+use crate::Message;
+use crate::ProtobufError;
+use crate::ProtobufResult;
+
+/// Collects serialized samples of messages and trains
+/// a zstd dictionary for each message type.
+#[derive(Debug, Clone)]
+pub struct DictionaryBuilder {
+    samples: HashMap<String, Vec<Vec<u8>>>,
+    max_samples: usize,
+    max_dictionary_size: usize,
+}
+
+impl DictionaryBuilder {
+    /// Create a builder with default limits: 10000 samples per type
+    /// and dictionaries up to 110 KiB (default of `zstd --train`).
+    pub fn new() -> DictionaryBuilder {
+        DictionaryBuilder {
+            samples: HashMap::new(),
+            max_samples: 10000,
+            max_dictionary_size: 110 * 1024,
+        }
+    }
+
+    /// Maximum number of samples kept per message type,
+    /// samples added after the limit is reached are ignored.
+    pub fn set_max_samples(&mut self, max_samples: usize) {
+        self.max_samples = max_samples;
+    }
+
+    /// Maximum size of each dictionary in bytes.
+    pub fn set_max_dictionary_size(&mut self, max_dictionary_size: usize) {
+        self.max_dictionary_size = max_dictionary_size;
+    }
+
+    /// Serialize the message and add it as a sample for its type.
+    pub fn add_sample(&mut self, message: &dyn MessageDyn) -> ProtobufResult<()> {
+        let bytes = message.write_to_bytes_dyn()?;
+        self.add_serialized_sample(&message.descriptor_dyn(), bytes);
+        Ok(())
+    }
+
+    /// Add already serialized message of given type as a sample.
+    pub fn add_serialized_sample(&mut self, descriptor: &MessageDescriptor, bytes: Vec<u8>) {
+        let samples = self
+            .samples
+            .entry(descriptor.full_name().to_owned())
+            .or_default();
+        if samples.len() < self.max_samples {
+            samples.push(bytes);
+        }
+    }
+
+    /// Train a dictionary for each message type which has samples.
+    ///
+    /// Training fails if there are too few samples of some type,
+    /// zstd recommends total size of samples about 100 times the dictionary size.
+    pub fn build(&self) -> ProtobufResult<Dictionaries> {
+        let mut dictionaries = Dictionaries::new();
+        for (name, samples) in &self.samples {
+            let dictionary = zstd::dict::from_samples(samples, self.max_dictionary_size)?;
+            dictionaries.insert(name.clone(), dictionary);
+        }
+        Ok(dictionaries)
+    }
+}
+
+impl Default for DictionaryBuilder {
+    fn default() -> DictionaryBuilder {
+        DictionaryBuilder::new()
+    }
+}
+
+/// Default limit of decompressed message size, 64 MiB.
+const DEFAULT_MAX_DECOMPRESSED_SIZE: u64 = 64 << 20;
+
+/// Zstd dictionaries keyed by message full name.
+#[derive(Debug, Clone)]
+pub struct Dictionaries {
+    dictionaries: HashMap<String, Vec<u8>>,
+    max_decompressed_size: u64,
+}
+
+impl Default for Dictionaries {
+    fn default() -> Dictionaries {
+        Dictionaries {
+            dictionaries: HashMap::new(),
+            max_decompressed_size: DEFAULT_MAX_DECOMPRESSED_SIZE,
+        }
+    }
+}
+
+impl Dictionaries {
+    /// Empty set of dictionaries.
+    pub fn new() -> Dictionaries {
+        Dictionaries::default()
+    }
+
+    /// Maximum size in bytes of a decompressed message, 64 MiB by default.
+    ///
+    /// Decompression fails if data decompresses to more bytes,
+    /// so small malicious inputs cannot exhaust memory.
+    pub fn set_max_decompressed_size(&mut self, max_decompressed_size: u64) {
+        self.max_decompressed_size = max_decompressed_size;
+    }
+
+    /// Add a dictionary, e. g. previously built and stored,
+    /// for the message type with given full name.
+    pub fn insert(&mut self, message_full_name: String, dictionary: Vec<u8>) {
+        self.dictionaries.insert(message_full_name, dictionary);
+    }
+
+    /// Dictionary for the message type with given full name.
+    pub fn get(&self, message_full_name: &str) -> Option<&[u8]> {
+        self.dictionaries
+            .get(message_full_name)
+            .map(|d| d.as_slice())
+    }
+
+    /// Message full names and dictionaries, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        self.dictionaries
+            .iter()
+            .map(|(name, d)| (name.as_str(), d.as_slice()))
+    }
+
+    fn dictionary(&self, descriptor: &MessageDescriptor) -> ProtobufResult<&[u8]> {
+        self.get(descriptor.full_name()).ok_or_else(|| {
+            ProtobufError::IoError(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no dictionary for message {}", descriptor.full_name()),
+            ))
+        })
+    }
+
+    /// Serialize the message and compress it with the dictionary of its type
+    /// using given zstd compression level.
+    ///
+    /// Error is returned if there is no dictionary for the message type.
+    pub fn compress(&self, message: &dyn MessageDyn, level: i32) -> ProtobufResult<Vec<u8>> {
+        let dictionary = self.dictionary(&message.descriptor_dyn())?;
+        let bytes = message.write_to_bytes_dyn()?;
+        let mut compressor = zstd::bulk::Compressor::with_dictionary(level, dictionary)?;
+        Ok(compressor.compress(&bytes)?)
+    }
+
+    /// Decompress data produced by [`compress`](Dictionaries::compress)
+    /// and parse message of given type.
+    pub fn decompress(
+        &self,
+        descriptor: &MessageDescriptor,
+        data: &[u8],
+    ) -> ProtobufResult<Box<dyn MessageDyn>> {
+        let bytes = self.decompress_bytes(descriptor, data)?;
+        let mut message = descriptor.new_instance();
+        message.merge_from_bytes_dyn(&bytes)?;
+        message.check_initialized_dyn()?;
+        Ok(message)
+    }
+
+    /// Decompress data produced by [`compress`](Dictionaries::compress)
+    /// and parse message of generated type.
+    pub fn decompress_message<M: Message>(&self, data: &[u8]) -> ProtobufResult<M> {
+        let bytes = self.decompress_bytes(&M::descriptor_static(), data)?;
+        M::parse_from_bytes(&bytes)
+    }
+
+    fn decompress_bytes(
+        &self,
+        descriptor: &MessageDescriptor,
+        data: &[u8],
+    ) -> ProtobufResult<Vec<u8>> {
+        let dictionary = self.dictionary(descriptor)?;
+        let decoder = zstd::stream::read::Decoder::with_dictionary(data, dictionary)?;
+        let mut bytes = Vec::new();
+        // Read one byte more than the limit to detect exceeding it
+        decoder
+            .take(self.max_decompressed_size.saturating_add(1))
+            .read_to_end(&mut bytes)?;
+        if bytes.len() as u64 > self.max_decompressed_size {
+            return Err(ProtobufError::IoError(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "decompressed message exceeds {} bytes",
+                    self.max_decompressed_size
+                ),
+            )));
+        }
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::corpus::RandomMessageGenerator;
+    use crate::descriptor::FieldDescriptorProto;
+
+    fn samples(seed: u64, count: usize) -> Vec<Box<dyn MessageDyn>> {
+        let mut generator = RandomMessageGenerator::new(seed);
+        generator.set_max_depth(1);
+        let descriptor = FieldDescriptorProto::descriptor_static();
+        (0..count)
+            .map(|_| generator.generate(&descriptor))
+            .collect()
+    }
+
+    #[test]
+    fn compress_decompress() {
+        let mut builder = DictionaryBuilder::new();
+        builder.set_max_dictionary_size(4 * 1024);
+        for sample in samples(1, 2000) {
+            builder.add_sample(&*sample).unwrap();
+        }
+        let dictionaries = builder.build().unwrap();
+        assert_eq!(
+            vec!["google.protobuf.FieldDescriptorProto"],
+            dictionaries
+                .iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>()
+        );
+
+        let descriptor = FieldDescriptorProto::descriptor_static();
+        for message in samples(2, 10) {
+            let compressed = dictionaries.compress(&*message, 3).unwrap();
+            let decompressed = dictionaries.decompress(&descriptor, &compressed).unwrap();
+            assert_eq!(
+                message.write_to_bytes_dyn().unwrap(),
+                decompressed.write_to_bytes_dyn().unwrap()
+            );
+            let generated: FieldDescriptorProto =
+                dictionaries.decompress_message(&compressed).unwrap();
+            assert_eq!(
+                message.write_to_bytes_dyn().unwrap(),
+                generated.write_to_bytes().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn max_decompressed_size() {
+        let mut builder = DictionaryBuilder::new();
+        builder.set_max_dictionary_size(4 * 1024);
+        for sample in samples(1, 2000) {
+            builder.add_sample(&*sample).unwrap();
+        }
+        let mut dictionaries = builder.build().unwrap();
+
+        let descriptor = FieldDescriptorProto::descriptor_static();
+        let message = samples(2, 1).pop().unwrap();
+        let size = message.write_to_bytes_dyn().unwrap().len() as u64;
+        let compressed = dictionaries.compress(&*message, 3).unwrap();
+
+        dictionaries.set_max_decompressed_size(size);
+        assert!(dictionaries.decompress(&descriptor, &compressed).is_ok());
+        dictionaries.set_max_decompressed_size(size - 1);
+        assert!(dictionaries.decompress(&descriptor, &compressed).is_err());
+    }
+
+    #[test]
+    fn max_samples() {
+        let mut builder = DictionaryBuilder::new();
+        builder.set_max_samples(3);
+        for sample in samples(1, 5) {
+            builder.add_sample(&*sample).unwrap();
+        }
+        assert_eq!(
+            3,
+            builder.samples["google.protobuf.FieldDescriptorProto"].len()
+        );
+    }
+
+    #[test]
+    fn no_dictionary() {
+        let message = FieldDescriptorProto::new();
+        assert!(Dictionaries::new().compress(&message, 3).is_err());
+    }
+}
@@ -0,0 +1,327 @@
+//! Deterministic pseudo-random message generation.
+//!
+//! Messages are generated from a seed using reflection, so the same seed
+//! and the same message type always produce the same messages.
+//! This is useful to build reproducible benchmark and regression corpora.
+//!
+//! ```
+//! # use protobuf::corpus::RandomMessageGenerator;
+//! # use protobuf::reflect::MessageDescriptor;
+//! # fn foo(descriptor: &MessageDescriptor) {
+//! let mut generator = RandomMessageGenerator::new(17);
+//! let message = generator.generate(descriptor);
+//! # }
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::descriptor::field_descriptor_proto;
+use crate::message_dyn::MessageDyn;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::text_format;
+
+const CHARS: &[char] = &[
+    'a', 'b', 'c', 'x', 'y', 'z', 'A', 'Z', '0', '9', ' ', '_', '"', '\\', '\n', 'я', '€', '😀',
+];
+
+/// Seeded generator of pseudo-random messages.
+///
+/// Generated values do not depend on platform or crate version
+/// of the random number generator, only on the seed and the message type.
+#[derive(Debug, Clone)]
+pub struct RandomMessageGenerator {
+    state: u64,
+    max_depth: u32,
+    max_repeated_len: u32,
+    max_map_len: u32,
+    max_bytes_len: u32,
+}
+
+impl RandomMessageGenerator {
+    /// Create a generator with given seed and default limits.
+    pub fn new(seed: u64) -> RandomMessageGenerator {
+        RandomMessageGenerator {
+            state: seed,
+            max_depth: 5,
+            max_repeated_len: 5,
+            max_map_len: 5,
+            max_bytes_len: 20,
+        }
+    }
+
+    /// Maximum nesting level of optional and repeated message fields.
+    ///
+    /// Required message fields are always populated.
+    pub fn set_max_depth(&mut self, max_depth: u32) {
+        self.max_depth = max_depth;
+    }
+
+    /// Maximum number of elements in repeated fields.
+    pub fn set_max_repeated_len(&mut self, max_repeated_len: u32) {
+        self.max_repeated_len = max_repeated_len;
+    }
+
+    /// Maximum number of entries in map fields.
+    ///
+    /// Map entries are serialized in hash map iteration order,
+    /// so maps with at most one entry are needed to compare serialized bytes.
+    pub fn set_max_map_len(&mut self, max_map_len: u32) {
+        self.max_map_len = max_map_len;
+    }
+
+    /// Maximum length of `string` (in chars) and `bytes` values.
+    pub fn set_max_bytes_len(&mut self, max_bytes_len: u32) {
+        self.max_bytes_len = max_bytes_len;
+    }
+
+    // splitmix64
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Random number in `0..=max`.
+    fn next_up_to(&mut self, max: u32) -> u32 {
+        (self.next_u64() % (max as u64 + 1)) as u32
+    }
+
+    fn next_bool(&mut self) -> bool {
+        self.next_u64() & 1 != 0
+    }
+
+    /// Integers are biased towards small and boundary values
+    /// which are more interesting for varint encoding.
+    fn next_int(&mut self) -> u64 {
+        match self.next_up_to(3) {
+            0 => self.next_up_to(3) as u64,
+            1 => self.next_up_to(200) as u64,
+            2 => !(self.next_up_to(3) as u64),
+            _ => self.next_u64(),
+        }
+    }
+
+    fn next_float(&mut self) -> f64 {
+        // Finite values exactly representable in both `f32` and `f64`
+        (self.next_int() as i16) as f64 / 4.0
+    }
+
+    fn next_string(&mut self) -> String {
+        let len = self.next_up_to(self.max_bytes_len);
+        (0..len)
+            .map(|_| CHARS[self.next_up_to(CHARS.len() as u32 - 1) as usize])
+            .collect()
+    }
+
+    fn next_bytes(&mut self) -> Vec<u8> {
+        let len = self.next_up_to(self.max_bytes_len);
+        (0..len).map(|_| self.next_u64() as u8).collect()
+    }
+
+    fn value(&mut self, t: &RuntimeTypeBox, depth: u32) -> ReflectValueBox {
+        match t {
+            RuntimeTypeBox::I32 => ReflectValueBox::I32(self.next_int() as i32),
+            RuntimeTypeBox::I64 => ReflectValueBox::I64(self.next_int() as i64),
+            RuntimeTypeBox::U32 => ReflectValueBox::U32(self.next_int() as u32),
+            RuntimeTypeBox::U64 => ReflectValueBox::U64(self.next_int()),
+            RuntimeTypeBox::F32 => ReflectValueBox::F32(self.next_float() as f32),
+            RuntimeTypeBox::F64 => ReflectValueBox::F64(self.next_float()),
+            RuntimeTypeBox::Bool => ReflectValueBox::Bool(self.next_bool()),
+            RuntimeTypeBox::String => ReflectValueBox::String(self.next_string()),
+            RuntimeTypeBox::VecU8 => ReflectValueBox::Bytes(self.next_bytes()),
+            RuntimeTypeBox::Enum(e) => {
+                let values: Vec<_> = e.values().collect();
+                let value = &values[self.next_up_to(values.len() as u32 - 1) as usize];
+                ReflectValueBox::Enum(e.clone(), value.value())
+            }
+            RuntimeTypeBox::Message(m) => ReflectValueBox::Message(self.message(m, depth + 1)),
+        }
+    }
+
+    fn field(&mut self, m: &mut dyn MessageDyn, field: &FieldDescriptor, depth: u32) {
+        let required =
+            field.get_proto().get_label() == field_descriptor_proto::Label::LABEL_REQUIRED;
+        match field.runtime_field_type() {
+            RuntimeFieldType::Singular(t) => {
+                if let RuntimeTypeBox::Message(..) = t {
+                    if !required && depth >= self.max_depth {
+                        return;
+                    }
+                }
+                if required || self.next_bool() {
+                    let value = self.value(&t, depth);
+                    field.set_singular_field(m, value);
+                }
+            }
+            RuntimeFieldType::Repeated(t) => {
+                if let RuntimeTypeBox::Message(..) = t {
+                    if depth >= self.max_depth {
+                        return;
+                    }
+                }
+                let len = self.next_up_to(self.max_repeated_len);
+                for _ in 0..len {
+                    let value = self.value(&t, depth);
+                    field.mut_repeated(m).push(value);
+                }
+            }
+            RuntimeFieldType::Map(k, v) => {
+                if let RuntimeTypeBox::Message(..) = v {
+                    if depth >= self.max_depth {
+                        return;
+                    }
+                }
+                let len = self.next_up_to(self.max_map_len);
+                for _ in 0..len {
+                    let key = self.value(&k, depth);
+                    let value = self.value(&v, depth);
+                    field.mut_map(m).insert(key, value);
+                }
+            }
+        }
+    }
+
+    fn message(&mut self, descriptor: &MessageDescriptor, depth: u32) -> Box<dyn MessageDyn> {
+        let mut m = descriptor.new_instance();
+        for field in descriptor.fields() {
+            match field.containing_oneof() {
+                None => self.field(&mut *m, &field, depth),
+                Some(oneof) => {
+                    // At most one field of a oneof is set,
+                    // so it is chosen once, when the first field is met
+                    let fields: Vec<_> = oneof.fields().collect();
+                    if fields[0] != field {
+                        continue;
+                    }
+                    let index = self.next_up_to(fields.len() as u32) as usize;
+                    if let Some(chosen) = fields.get(index) {
+                        self.field(&mut *m, chosen, depth);
+                    }
+                }
+            }
+        }
+        m
+    }
+
+    /// Generate next message of given type.
+    ///
+    /// All required fields of generated messages are set.
+    pub fn generate(&mut self, descriptor: &MessageDescriptor) -> Box<dyn MessageDyn> {
+        self.message(descriptor, 0)
+    }
+}
+
+/// Generate `count` messages of given type with given seed and write them to `dir`.
+///
+/// Each message is written twice: in binary format to `NNNN.bin`
+/// and in [normalized](text_format::print_normalized) text format to `NNNN.txt`.
+/// Directory is created if it does not exist.
+///
+/// Note map entries are serialized in hash map iteration order,
+/// so binary files of messages with map fields are not byte-to-byte reproducible,
+/// although they contain equal messages.
+pub fn write_corpus(
+    dir: &Path,
+    descriptor: &MessageDescriptor,
+    seed: u64,
+    count: usize,
+) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let mut generator = RandomMessageGenerator::new(seed);
+    for i in 0..count {
+        let m = generator.generate(descriptor);
+        fs::write(dir.join(format!("{:04}.bin", i)), m.write_to_bytes_dyn()?)?;
+        fs::write(
+            dir.join(format!("{:04}.txt", i)),
+            text_format::print_normalized(&*m),
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::reflect::FileDescriptor;
+    use crate::well_known_types::Value;
+    use crate::Message;
+
+    #[test]
+    fn deterministic() {
+        let descriptor = FileDescriptorProto::descriptor_static();
+        let a = RandomMessageGenerator::new(1).generate(&descriptor);
+        let b = RandomMessageGenerator::new(1).generate(&descriptor);
+        assert!(descriptor.eq(&*a, &*b));
+
+        let mut generator = RandomMessageGenerator::new(1);
+        generator.generate(&descriptor);
+        let c = generator.generate(&descriptor);
+        assert!(!descriptor.eq(&*a, &*c));
+    }
+
+    #[test]
+    fn max_depth() {
+        let descriptor = DescriptorProto::descriptor_static();
+        let mut generator = RandomMessageGenerator::new(2);
+        generator.set_max_depth(0);
+        for _ in 0..10 {
+            let m = generator.generate(&descriptor);
+            let m: &DescriptorProto = m.downcast_ref().unwrap();
+            assert!(m.nested_type.is_empty());
+            assert!(m.options.is_none());
+        }
+    }
+
+    /// Oneof fields are chosen uniformly, not overwritten by later fields.
+    #[test]
+    fn oneof() {
+        let generated = Value::descriptor_static();
+        let dynamic =
+            FileDescriptor::new_dynamic(generated.file_descriptor_proto().clone(), Vec::new())
+                .message_by_package_relative_name(generated.name())
+                .unwrap();
+        let mut generator = RandomMessageGenerator::new(4);
+        generator.set_max_depth(1);
+        for descriptor in &[generated, dynamic] {
+            let kind = descriptor.oneofs().next().unwrap();
+            let mut counts = vec![0; kind.fields().count()];
+            for _ in 0..700 {
+                let m = generator.generate(descriptor);
+                for (i, f) in kind.fields().enumerate() {
+                    if f.has_field(&*m) {
+                        counts[i] += 1;
+                    }
+                }
+            }
+            assert!(counts.iter().all(|&c| c > 25), "{:?}", counts);
+        }
+    }
+
+    #[test]
+    fn corpus_files() {
+        let dir = std::env::temp_dir().join(format!("protobuf-corpus-test-{}", std::process::id()));
+        let descriptor = FileDescriptorProto::descriptor_static();
+        write_corpus(&dir, &descriptor, 3, 2).unwrap();
+
+        let mut generator = RandomMessageGenerator::new(3);
+        for i in 0..2 {
+            let expected = generator.generate(&descriptor);
+            let bytes = fs::read(dir.join(format!("{:04}.bin", i))).unwrap();
+            let parsed = FileDescriptorProto::parse_from_bytes(&bytes).unwrap();
+            assert!(descriptor.eq(&*expected, &parsed));
+            let text = fs::read_to_string(dir.join(format!("{:04}.txt", i))).unwrap();
+            assert_eq!(text_format::print_normalized(&*expected), text);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
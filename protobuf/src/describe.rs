@@ -0,0 +1,516 @@
+//! Describe Rust types as protobuf messages (experimental).
+//!
+//! This is the reverse of code generation: a schema is derived from
+//! Rust types, so the schema can be published when Rust types exist first.
+//! [`DescribeMessage`] is usually derived with `#[derive(DescribeMessage)]`
+//! from `protobuf-describe-derive` crate, but it can be implemented manually:
+//!
+//! ```
+//! # use protobuf::describe::DescribeMessage;
+//! # use protobuf::describe::FileDescriptorBuilder;
+//! # use protobuf::describe::MessageBuilder;
+//! struct Point {
+//!     x: i32,
+//!     y: i32,
+//!     label: Option<String>,
+//! }
+//!
+//! impl DescribeMessage for Point {
+//!     fn message_name() -> String {
+//!         "Point".to_owned()
+//!     }
+//!
+//!     fn describe_fields(m: &mut MessageBuilder) {
+//!         m.field::<i32>("x", 1);
+//!         m.field::<i32>("y", 2);
+//!         m.field::<Option<String>>("label", 3);
+//!     }
+//! }
+//!
+//! let mut builder = FileDescriptorBuilder::new("geometry.proto", "geometry");
+//! builder.add_message::<Point>();
+//! let file = builder.build();
+//! assert_eq!("Point", file.message_type[0].get_name());
+//! ```
+//!
+//! Files are described in `proto3` syntax. `Option` of a scalar type
+//! is described as a `proto3` `optional` field, `Vec` as a repeated field,
+//! `HashMap` and `BTreeMap` as map fields, `Vec<u8>` as `bytes`.
+//! Nested containers like `Option<Vec<T>>` have no protobuf equivalent.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use crate::descriptor::field_descriptor_proto::Label;
+use crate::descriptor::field_descriptor_proto::Type;
+use crate::descriptor::DescriptorProto;
+use crate::descriptor::FieldDescriptorProto;
+use crate::descriptor::FileDescriptorProto;
+use crate::descriptor::MessageOptions;
+use crate::descriptor::OneofDescriptorProto;
+use crate::wire_format;
+use crate::MessageField;
+
+/// Type of a single value of a field: a scalar type or a message type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValueType {
+    /// Protobuf type.
+    pub field_type: Type,
+    /// Full name with leading dot for message types.
+    pub type_name: Option<String>,
+}
+
+impl ValueType {
+    /// Scalar type.
+    pub fn scalar(field_type: Type) -> ValueType {
+        ValueType {
+            field_type,
+            type_name: None,
+        }
+    }
+
+    /// Message type with given full name with leading dot.
+    pub fn message(type_name: String) -> ValueType {
+        ValueType {
+            field_type: Type::TYPE_MESSAGE,
+            type_name: Some(type_name),
+        }
+    }
+}
+
+/// Type of a field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldType {
+    /// Field without container: scalar field without presence or message field.
+    Singular(ValueType),
+    /// Field with explicit presence.
+    Optional(ValueType),
+    /// Repeated field.
+    Repeated(ValueType),
+    /// Map field.
+    Map(ValueType, ValueType),
+}
+
+/// Rust type of a message field.
+pub trait DescribeField {
+    /// Describe the field type, adding referenced message types to the file.
+    fn describe_field(file: &mut FileDescriptorBuilder) -> FieldType;
+}
+
+/// Rust type described as a protobuf message.
+pub trait DescribeMessage {
+    /// Message name without package.
+    fn message_name() -> String;
+
+    /// Describe message fields.
+    fn describe_fields(m: &mut MessageBuilder);
+}
+
+/// Field type of a message type, to implement [`DescribeField`]
+/// for a type implementing [`DescribeMessage`].
+pub fn message_field_type<M: DescribeMessage>(file: &mut FileDescriptorBuilder) -> FieldType {
+    FieldType::Singular(ValueType::message(file.add_message::<M>()))
+}
+
+macro_rules! describe_scalar {
+    ($t:ty, $field_type:ident) => {
+        impl DescribeField for $t {
+            fn describe_field(_file: &mut FileDescriptorBuilder) -> FieldType {
+                FieldType::Singular(ValueType::scalar(Type::$field_type))
+            }
+        }
+    };
+}
+
+describe_scalar!(bool, TYPE_BOOL);
+describe_scalar!(i32, TYPE_INT32);
+describe_scalar!(i64, TYPE_INT64);
+describe_scalar!(u32, TYPE_UINT32);
+describe_scalar!(u64, TYPE_UINT64);
+describe_scalar!(f32, TYPE_FLOAT);
+describe_scalar!(f64, TYPE_DOUBLE);
+describe_scalar!(String, TYPE_STRING);
+describe_scalar!(Vec<u8>, TYPE_BYTES);
+
+fn singular<T: DescribeField>(file: &mut FileDescriptorBuilder, container: &str) -> ValueType {
+    match T::describe_field(file) {
+        FieldType::Singular(v) => v,
+        t => panic!(
+            "{} of {:?} cannot be described as protobuf field",
+            container, t
+        ),
+    }
+}
+
+impl<T: DescribeField> DescribeField for Option<T> {
+    fn describe_field(file: &mut FileDescriptorBuilder) -> FieldType {
+        FieldType::Optional(singular::<T>(file, "Option"))
+    }
+}
+
+impl<T: DescribeField> DescribeField for Box<T> {
+    fn describe_field(file: &mut FileDescriptorBuilder) -> FieldType {
+        T::describe_field(file)
+    }
+}
+
+impl<T: DescribeField> DescribeField for Vec<T> {
+    fn describe_field(file: &mut FileDescriptorBuilder) -> FieldType {
+        FieldType::Repeated(singular::<T>(file, "Vec"))
+    }
+}
+
+fn map_field_type<K: DescribeField, V: DescribeField>(
+    file: &mut FileDescriptorBuilder,
+) -> FieldType {
+    let key = singular::<K>(file, "map");
+    match key.field_type {
+        Type::TYPE_FLOAT | Type::TYPE_DOUBLE | Type::TYPE_BYTES | Type::TYPE_MESSAGE => {
+            panic!("{:?} cannot be a map key", key.field_type)
+        }
+        _ => {}
+    }
+    FieldType::Map(key, singular::<V>(file, "map"))
+}
+
+impl<K: DescribeField, V: DescribeField, S> DescribeField for HashMap<K, V, S> {
+    fn describe_field(file: &mut FileDescriptorBuilder) -> FieldType {
+        map_field_type::<K, V>(file)
+    }
+}
+
+impl<K: DescribeField, V: DescribeField> DescribeField for BTreeMap<K, V> {
+    fn describe_field(file: &mut FileDescriptorBuilder) -> FieldType {
+        map_field_type::<K, V>(file)
+    }
+}
+
+/// Build `FileDescriptorProto` from Rust types.
+#[derive(Debug)]
+pub struct FileDescriptorBuilder {
+    file: FileDescriptorProto,
+    /// Rust type names of messages added or being added by full name,
+    /// so each message is described once even if it is recursive.
+    added: HashMap<String, &'static str>,
+}
+
+impl FileDescriptorBuilder {
+    /// Empty `proto3` file.
+    pub fn new(name: &str, package: &str) -> FileDescriptorBuilder {
+        let mut file = FileDescriptorProto::new();
+        file.set_name(name.to_owned());
+        if !package.is_empty() {
+            file.set_package(package.to_owned());
+        }
+        file.set_syntax("proto3".to_owned());
+        FileDescriptorBuilder {
+            file,
+            added: HashMap::new(),
+        }
+    }
+
+    /// Add message described by the type and all message types
+    /// referenced by its fields, return message full name with leading dot.
+    ///
+    /// Adding the same message more than once does nothing.
+    ///
+    /// # Panics
+    ///
+    /// If a different Rust type with the same message name is already added.
+    pub fn add_message<M: DescribeMessage>(&mut self) -> String {
+        let name = M::message_name();
+        let full_name = match self.file.get_package() {
+            "" => format!(".{}", name),
+            package => format!(".{}.{}", package, name),
+        };
+        let rust_type = std::any::type_name::<M>();
+        if let Some(added) = self.added.insert(full_name.clone(), rust_type) {
+            assert!(
+                added == rust_type,
+                "message {} is described by both {} and {}",
+                full_name,
+                added,
+                rust_type
+            );
+            return full_name;
+        }
+
+        let mut message = DescriptorProto::new();
+        message.set_name(name);
+        let mut builder = MessageBuilder {
+            file: self,
+            full_name: full_name.clone(),
+            message,
+        };
+        M::describe_fields(&mut builder);
+        let message = builder.message;
+        self.file.message_type.push(message);
+        full_name
+    }
+
+    /// Built file.
+    pub fn build(self) -> FileDescriptorProto {
+        self.file
+    }
+}
+
+/// Add fields to a message, passed to [`DescribeMessage::describe_fields`].
+#[derive(Debug)]
+pub struct MessageBuilder<'a> {
+    file: &'a mut FileDescriptorBuilder,
+    /// Message full name with leading dot.
+    full_name: String,
+    message: DescriptorProto,
+}
+
+/// `foo_bar` to `FooBar`.
+fn camel_case(name: &str) -> String {
+    let mut r = String::new();
+    let mut upper = true;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            r.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            r.push(c);
+        }
+    }
+    r
+}
+
+fn set_value_type(field: &mut FieldDescriptorProto, value: ValueType) {
+    field.set_field_type(value.field_type);
+    if let Some(type_name) = value.type_name {
+        field.set_type_name(type_name);
+    }
+}
+
+fn new_field(name: &str, number: u32, label: Label, value: ValueType) -> FieldDescriptorProto {
+    let mut field = FieldDescriptorProto::new();
+    field.set_name(name.to_owned());
+    field.set_number(number as i32);
+    field.set_label(label);
+    set_value_type(&mut field, value);
+    field
+}
+
+impl<'a> MessageBuilder<'a> {
+    /// Add a field of given Rust type.
+    ///
+    /// # Panics
+    ///
+    /// If the field number is not valid in a `.proto` file,
+    /// a field with the same name or number is already added,
+    /// or the Rust type cannot be described as a protobuf field.
+    pub fn field<T: DescribeField>(&mut self, name: &str, number: u32) -> &mut Self {
+        assert!(
+            wire_format::is_valid_field_number(number)
+                && !(wire_format::FIRST_RESERVED_FIELD_NUMBER
+                    ..=wire_format::LAST_RESERVED_FIELD_NUMBER)
+                    .contains(&number),
+            "invalid field number {} = {} in {}",
+            name,
+            number,
+            self.full_name
+        );
+        assert!(
+            self.message
+                .field
+                .iter()
+                .all(|f| f.get_name() != name && f.get_number() != number as i32),
+            "duplicate field {} = {} in {}",
+            name,
+            number,
+            self.full_name
+        );
+
+        let field = match T::describe_field(self.file) {
+            FieldType::Singular(v) => new_field(name, number, Label::LABEL_OPTIONAL, v),
+            FieldType::Optional(v) => {
+                let is_message = v.field_type == Type::TYPE_MESSAGE;
+                let mut field = new_field(name, number, Label::LABEL_OPTIONAL, v);
+                if !is_message {
+                    // `proto3` optional field is a field in a synthetic oneof
+                    let mut oneof = OneofDescriptorProto::new();
+                    oneof.set_name(format!("_{}", name));
+                    field.set_oneof_index(self.message.oneof_decl.len() as i32);
+                    field.set_proto3_optional(true);
+                    self.message.oneof_decl.push(oneof);
+                }
+                field
+            }
+            FieldType::Repeated(v) => new_field(name, number, Label::LABEL_REPEATED, v),
+            FieldType::Map(k, v) => {
+                let entry_name = format!("{}Entry", camel_case(name));
+                let mut entry = DescriptorProto::new();
+                entry.set_name(entry_name.clone());
+                entry
+                    .field
+                    .push(new_field("key", 1, Label::LABEL_OPTIONAL, k));
+                entry
+                    .field
+                    .push(new_field("value", 2, Label::LABEL_OPTIONAL, v));
+                let mut options = MessageOptions::new();
+                options.set_map_entry(true);
+                entry.options = MessageField::some(options);
+                self.message.nested_type.push(entry);
+
+                let entry_type = ValueType::message(format!("{}.{}", self.full_name, entry_name));
+                new_field(name, number, Label::LABEL_REPEATED, entry_type)
+            }
+        };
+        self.message.field.push(field);
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::reflect::FileDescriptor;
+    use crate::reflect::ReflectValueBox;
+    use crate::reflect::RuntimeFieldType;
+    use crate::reflect::RuntimeTypeBox;
+
+    struct Tree {
+        _name: String,
+        _children: Vec<Tree>,
+        _weight: Option<f64>,
+        _attributes: HashMap<String, Box<Tree>>,
+        _data: Vec<u8>,
+    }
+
+    impl DescribeMessage for Tree {
+        fn message_name() -> String {
+            "Tree".to_owned()
+        }
+
+        fn describe_fields(m: &mut MessageBuilder) {
+            m.field::<String>("name", 1)
+                .field::<Vec<Tree>>("children", 2)
+                .field::<Option<f64>>("weight", 3)
+                .field::<HashMap<String, Box<Tree>>>("attributes", 4)
+                .field::<Vec<u8>>("data", 5);
+        }
+    }
+
+    impl DescribeField for Tree {
+        fn describe_field(file: &mut FileDescriptorBuilder) -> FieldType {
+            message_field_type::<Self>(file)
+        }
+    }
+
+    #[test]
+    fn describe() {
+        let mut builder = FileDescriptorBuilder::new("tree.proto", "t");
+        assert_eq!(".t.Tree", builder.add_message::<Tree>());
+        assert_eq!(".t.Tree", builder.add_message::<Tree>());
+        let proto = builder.build();
+        assert_eq!(1, proto.message_type.len());
+
+        let file = FileDescriptor::new_dynamic(proto, Vec::new());
+        let tree = file.message_by_package_relative_name("Tree").unwrap();
+        let field = |name| tree.get_field_by_name(name).unwrap();
+        match field("name").runtime_field_type() {
+            RuntimeFieldType::Singular(RuntimeTypeBox::String) => {}
+            _ => panic!("wrong field type"),
+        }
+        match field("children").runtime_field_type() {
+            RuntimeFieldType::Repeated(RuntimeTypeBox::Message(m)) => assert_eq!(tree, m),
+            _ => panic!("wrong field type"),
+        }
+        match field("attributes").runtime_field_type() {
+            RuntimeFieldType::Map(RuntimeTypeBox::String, RuntimeTypeBox::Message(m)) => {
+                assert_eq!(tree, m)
+            }
+            _ => panic!("wrong field type"),
+        }
+        match field("data").runtime_field_type() {
+            RuntimeFieldType::Singular(RuntimeTypeBox::VecU8) => {}
+            _ => panic!("wrong field type"),
+        }
+
+        // Optional field has presence, other scalar fields do not
+        let mut m = tree.new_instance();
+        field("weight").set_singular_field(&mut *m, ReflectValueBox::F64(0.0));
+        field("name").set_singular_field(&mut *m, ReflectValueBox::String(String::new()));
+        assert_eq!(
+            vec![0x19, 0, 0, 0, 0, 0, 0, 0, 0],
+            m.write_to_bytes_dyn().unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn nested_containers() {
+        struct M;
+        impl DescribeMessage for M {
+            fn message_name() -> String {
+                "M".to_owned()
+            }
+
+            fn describe_fields(m: &mut MessageBuilder) {
+                m.field::<Option<Vec<i32>>>("f", 1);
+            }
+        }
+        FileDescriptorBuilder::new("m.proto", "").add_message::<M>();
+    }
+
+    #[test]
+    #[should_panic]
+    fn reserved_number() {
+        struct M;
+        impl DescribeMessage for M {
+            fn message_name() -> String {
+                "M".to_owned()
+            }
+
+            fn describe_fields(m: &mut MessageBuilder) {
+                m.field::<i32>("a", 19000);
+            }
+        }
+        FileDescriptorBuilder::new("m.proto", "").add_message::<M>();
+    }
+
+    #[test]
+    #[should_panic]
+    fn duplicate_message_name() {
+        struct A;
+        impl DescribeMessage for A {
+            fn message_name() -> String {
+                "M".to_owned()
+            }
+
+            fn describe_fields(_m: &mut MessageBuilder) {}
+        }
+        struct B;
+        impl DescribeMessage for B {
+            fn message_name() -> String {
+                "M".to_owned()
+            }
+
+            fn describe_fields(_m: &mut MessageBuilder) {}
+        }
+        let mut builder = FileDescriptorBuilder::new("m.proto", "");
+        builder.add_message::<A>();
+        builder.add_message::<B>();
+    }
+
+    #[test]
+    #[should_panic]
+    fn duplicate_number() {
+        struct M;
+        impl DescribeMessage for M {
+            fn message_name() -> String {
+                "M".to_owned()
+            }
+
+            fn describe_fields(m: &mut MessageBuilder) {
+                m.field::<i32>("a", 1).field::<i32>("b", 1);
+            }
+        }
+        FileDescriptorBuilder::new("m.proto", "").add_message::<M>();
+    }
+}
@@ -46,6 +46,9 @@ impl FileDescriptorSet {
         ::std::default::Default::default()
     }
 
+    /// Field number of `file` field.
+    pub const FIELD_FILE_NUMBER: u32 = 1;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
@@ -89,12 +92,13 @@ impl crate::Message for FileDescriptorSet {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         for value in &self.file {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -154,6 +158,16 @@ impl crate::reflect::ProtobufValue for FileDescriptorSet {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `FileDescriptorSet`
+pub mod file_descriptor_set {
+
+    /// Field numbers of message `FileDescriptorSet`
+    pub mod field_numbers {
+        /// Field number of `file` field.
+        pub const FILE: u32 = 1;
+    }
+}
+
 ///  Describes a complete .proto file.
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
@@ -200,6 +214,42 @@ impl FileDescriptorProto {
         ::std::default::Default::default()
     }
 
+    /// Field number of `name` field.
+    pub const FIELD_NAME_NUMBER: u32 = 1;
+
+    /// Field number of `package` field.
+    pub const FIELD_PACKAGE_NUMBER: u32 = 2;
+
+    /// Field number of `dependency` field.
+    pub const FIELD_DEPENDENCY_NUMBER: u32 = 3;
+
+    /// Field number of `public_dependency` field.
+    pub const FIELD_PUBLIC_DEPENDENCY_NUMBER: u32 = 10;
+
+    /// Field number of `weak_dependency` field.
+    pub const FIELD_WEAK_DEPENDENCY_NUMBER: u32 = 11;
+
+    /// Field number of `message_type` field.
+    pub const FIELD_MESSAGE_TYPE_NUMBER: u32 = 4;
+
+    /// Field number of `enum_type` field.
+    pub const FIELD_ENUM_TYPE_NUMBER: u32 = 5;
+
+    /// Field number of `service` field.
+    pub const FIELD_SERVICE_NUMBER: u32 = 6;
+
+    /// Field number of `extension` field.
+    pub const FIELD_EXTENSION_NUMBER: u32 = 7;
+
+    /// Field number of `options` field.
+    pub const FIELD_OPTIONS_NUMBER: u32 = 8;
+
+    /// Field number of `source_code_info` field.
+    pub const FIELD_SOURCE_CODE_INFO_NUMBER: u32 = 9;
+
+    /// Field number of `syntax` field.
+    pub const FIELD_SYNTAX_NUMBER: u32 = 12;
+
     // optional string name = 1;
 
     pub fn get_name(&self) -> &str {
@@ -476,50 +526,51 @@ impl crate::Message for FileDescriptorProto {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if let Some(v) = self.name.as_ref() {
-            my_size += crate::rt::string_size(1, &v);
+            my_size += crate::rt::string_size(1, &v) as u64;
         }
         if let Some(v) = self.package.as_ref() {
-            my_size += crate::rt::string_size(2, &v);
+            my_size += crate::rt::string_size(2, &v) as u64;
         }
         for value in &self.dependency {
-            my_size += crate::rt::string_size(3, &value);
+            my_size += crate::rt::string_size(3, &value) as u64;
         };
         for value in &self.public_dependency {
-            my_size += crate::rt::value_size(10, *value, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(10, *value, crate::wire_format::WireTypeVarint) as u64;
         };
         for value in &self.weak_dependency {
-            my_size += crate::rt::value_size(11, *value, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(11, *value, crate::wire_format::WireTypeVarint) as u64;
         };
         for value in &self.message_type {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         for value in &self.enum_type {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         for value in &self.service {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         for value in &self.extension {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         if let Some(v) = self.options.as_ref() {
             let len = v.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         }
         if let Some(v) = self.source_code_info.as_ref() {
             let len = v.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         }
         if let Some(v) = self.syntax.as_ref() {
-            my_size += crate::rt::string_size(12, &v);
+            my_size += crate::rt::string_size(12, &v) as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -634,6 +685,38 @@ impl crate::reflect::ProtobufValue for FileDescriptorProto {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `FileDescriptorProto`
+pub mod file_descriptor_proto {
+
+    /// Field numbers of message `FileDescriptorProto`
+    pub mod field_numbers {
+        /// Field number of `name` field.
+        pub const NAME: u32 = 1;
+        /// Field number of `package` field.
+        pub const PACKAGE: u32 = 2;
+        /// Field number of `dependency` field.
+        pub const DEPENDENCY: u32 = 3;
+        /// Field number of `public_dependency` field.
+        pub const PUBLIC_DEPENDENCY: u32 = 10;
+        /// Field number of `weak_dependency` field.
+        pub const WEAK_DEPENDENCY: u32 = 11;
+        /// Field number of `message_type` field.
+        pub const MESSAGE_TYPE: u32 = 4;
+        /// Field number of `enum_type` field.
+        pub const ENUM_TYPE: u32 = 5;
+        /// Field number of `service` field.
+        pub const SERVICE: u32 = 6;
+        /// Field number of `extension` field.
+        pub const EXTENSION: u32 = 7;
+        /// Field number of `options` field.
+        pub const OPTIONS: u32 = 8;
+        /// Field number of `source_code_info` field.
+        pub const SOURCE_CODE_INFO: u32 = 9;
+        /// Field number of `syntax` field.
+        pub const SYNTAX: u32 = 12;
+    }
+}
+
 ///  Describes a message type.
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
@@ -669,6 +752,36 @@ impl DescriptorProto {
         ::std::default::Default::default()
     }
 
+    /// Field number of `name` field.
+    pub const FIELD_NAME_NUMBER: u32 = 1;
+
+    /// Field number of `field` field.
+    pub const FIELD_FIELD_NUMBER: u32 = 2;
+
+    /// Field number of `extension` field.
+    pub const FIELD_EXTENSION_NUMBER: u32 = 6;
+
+    /// Field number of `nested_type` field.
+    pub const FIELD_NESTED_TYPE_NUMBER: u32 = 3;
+
+    /// Field number of `enum_type` field.
+    pub const FIELD_ENUM_TYPE_NUMBER: u32 = 4;
+
+    /// Field number of `extension_range` field.
+    pub const FIELD_EXTENSION_RANGE_NUMBER: u32 = 5;
+
+    /// Field number of `oneof_decl` field.
+    pub const FIELD_ONEOF_DECL_NUMBER: u32 = 8;
+
+    /// Field number of `options` field.
+    pub const FIELD_OPTIONS_NUMBER: u32 = 7;
+
+    /// Field number of `reserved_range` field.
+    pub const FIELD_RESERVED_RANGE_NUMBER: u32 = 9;
+
+    /// Field number of `reserved_name` field.
+    pub const FIELD_RESERVED_NAME_NUMBER: u32 = 10;
+
     // optional string name = 1;
 
     pub fn get_name(&self) -> &str {
@@ -859,46 +972,47 @@ impl crate::Message for DescriptorProto {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if let Some(v) = self.name.as_ref() {
-            my_size += crate::rt::string_size(1, &v);
+            my_size += crate::rt::string_size(1, &v) as u64;
         }
         for value in &self.field {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         for value in &self.extension {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         for value in &self.nested_type {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         for value in &self.enum_type {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         for value in &self.extension_range {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         for value in &self.oneof_decl {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         if let Some(v) = self.options.as_ref() {
             let len = v.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         }
         for value in &self.reserved_range {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         for value in &self.reserved_name {
-            my_size += crate::rt::string_size(10, &value);
+            my_size += crate::rt::string_size(10, &value) as u64;
         };
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -1005,6 +1119,30 @@ impl crate::reflect::ProtobufValue for DescriptorProto {
 
 /// Nested message and enums of message `DescriptorProto`
 pub mod descriptor_proto {
+
+    /// Field numbers of message `DescriptorProto`
+    pub mod field_numbers {
+        /// Field number of `name` field.
+        pub const NAME: u32 = 1;
+        /// Field number of `field` field.
+        pub const FIELD: u32 = 2;
+        /// Field number of `extension` field.
+        pub const EXTENSION: u32 = 6;
+        /// Field number of `nested_type` field.
+        pub const NESTED_TYPE: u32 = 3;
+        /// Field number of `enum_type` field.
+        pub const ENUM_TYPE: u32 = 4;
+        /// Field number of `extension_range` field.
+        pub const EXTENSION_RANGE: u32 = 5;
+        /// Field number of `oneof_decl` field.
+        pub const ONEOF_DECL: u32 = 8;
+        /// Field number of `options` field.
+        pub const OPTIONS: u32 = 7;
+        /// Field number of `reserved_range` field.
+        pub const RESERVED_RANGE: u32 = 9;
+        /// Field number of `reserved_name` field.
+        pub const RESERVED_NAME: u32 = 10;
+    }
     #[derive(PartialEq,Clone,Default)]
     #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
     pub struct ExtensionRange {
@@ -1030,6 +1168,15 @@ pub mod descriptor_proto {
             ::std::default::Default::default()
         }
 
+        /// Field number of `start` field.
+        pub const FIELD_START_NUMBER: u32 = 1;
+
+        /// Field number of `end` field.
+        pub const FIELD_END_NUMBER: u32 = 2;
+
+        /// Field number of `options` field.
+        pub const FIELD_OPTIONS_NUMBER: u32 = 3;
+
         // optional int32 start = 1;
 
         pub fn get_start(&self) -> i32 {
@@ -1135,18 +1282,19 @@ pub mod descriptor_proto {
         // Compute sizes of nested messages
         #[allow(unused_variables)]
         fn compute_size(&self) -> u32 {
-            let mut my_size = 0;
+            let mut my_size: u64 = 0;
             if let Some(v) = self.start {
-                my_size += crate::rt::value_size(1, v, crate::wire_format::WireTypeVarint);
+                my_size += crate::rt::value_size(1, v, crate::wire_format::WireTypeVarint) as u64;
             }
             if let Some(v) = self.end {
-                my_size += crate::rt::value_size(2, v, crate::wire_format::WireTypeVarint);
+                my_size += crate::rt::value_size(2, v, crate::wire_format::WireTypeVarint) as u64;
             }
             if let Some(v) = self.options.as_ref() {
                 let len = v.compute_size();
-                my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+                my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
             }
-            my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+            my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+            let my_size = crate::rt::saturating_size(my_size);
             self.cached_size.set(my_size);
             my_size
         }
@@ -1216,6 +1364,20 @@ pub mod descriptor_proto {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
     }
 
+    /// Nested message and enums of message `ExtensionRange`
+    pub mod extension_range {
+
+        /// Field numbers of message `ExtensionRange`
+        pub mod field_numbers {
+            /// Field number of `start` field.
+            pub const START: u32 = 1;
+            /// Field number of `end` field.
+            pub const END: u32 = 2;
+            /// Field number of `options` field.
+            pub const OPTIONS: u32 = 3;
+        }
+    }
+
     ///  Range of reserved tag numbers. Reserved tag numbers may not be used by
     ///  fields or extension ranges in the same message. Reserved ranges may
     ///  not overlap.
@@ -1243,6 +1405,12 @@ pub mod descriptor_proto {
             ::std::default::Default::default()
         }
 
+        /// Field number of `start` field.
+        pub const FIELD_START_NUMBER: u32 = 1;
+
+        /// Field number of `end` field.
+        pub const FIELD_END_NUMBER: u32 = 2;
+
         // optional int32 start = 1;
 
         pub fn get_start(&self) -> i32 {
@@ -1335,14 +1503,15 @@ pub mod descriptor_proto {
         // Compute sizes of nested messages
         #[allow(unused_variables)]
         fn compute_size(&self) -> u32 {
-            let mut my_size = 0;
+            let mut my_size: u64 = 0;
             if let Some(v) = self.start {
-                my_size += crate::rt::value_size(1, v, crate::wire_format::WireTypeVarint);
+                my_size += crate::rt::value_size(1, v, crate::wire_format::WireTypeVarint) as u64;
             }
             if let Some(v) = self.end {
-                my_size += crate::rt::value_size(2, v, crate::wire_format::WireTypeVarint);
+                my_size += crate::rt::value_size(2, v, crate::wire_format::WireTypeVarint) as u64;
             }
-            my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+            my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+            let my_size = crate::rt::saturating_size(my_size);
             self.cached_size.set(my_size);
             my_size
         }
@@ -1406,6 +1575,18 @@ pub mod descriptor_proto {
     impl crate::reflect::ProtobufValue for ReservedRange {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
     }
+
+    /// Nested message and enums of message `ReservedRange`
+    pub mod reserved_range {
+
+        /// Field numbers of message `ReservedRange`
+        pub mod field_numbers {
+            /// Field number of `start` field.
+            pub const START: u32 = 1;
+            /// Field number of `end` field.
+            pub const END: u32 = 2;
+        }
+    }
 }
 
 #[derive(PartialEq,Clone,Default)]
@@ -1432,6 +1613,9 @@ impl ExtensionRangeOptions {
         ::std::default::Default::default()
     }
 
+    /// Field number of `uninterpreted_option` field.
+    pub const FIELD_UNINTERPRETED_OPTION_NUMBER: u32 = 999;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
@@ -1475,12 +1659,13 @@ impl crate::Message for ExtensionRangeOptions {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         for value in &self.uninterpreted_option {
             let len = value.compute_size();
-            my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 2 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -1540,6 +1725,16 @@ impl crate::reflect::ProtobufValue for ExtensionRangeOptions {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `ExtensionRangeOptions`
+pub mod extension_range_options {
+
+    /// Field numbers of message `ExtensionRangeOptions`
+    pub mod field_numbers {
+        /// Field number of `uninterpreted_option` field.
+        pub const UNINTERPRETED_OPTION: u32 = 999;
+    }
+}
+
 ///  Describes a field within a message.
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
@@ -1616,6 +1811,39 @@ impl FieldDescriptorProto {
         ::std::default::Default::default()
     }
 
+    /// Field number of `name` field.
+    pub const FIELD_NAME_NUMBER: u32 = 1;
+
+    /// Field number of `number` field.
+    pub const FIELD_NUMBER_NUMBER: u32 = 3;
+
+    /// Field number of `label` field.
+    pub const FIELD_LABEL_NUMBER: u32 = 4;
+
+    /// Field number of `type` field.
+    pub const FIELD_TYPE_NUMBER: u32 = 5;
+
+    /// Field number of `type_name` field.
+    pub const FIELD_TYPE_NAME_NUMBER: u32 = 6;
+
+    /// Field number of `extendee` field.
+    pub const FIELD_EXTENDEE_NUMBER: u32 = 2;
+
+    /// Field number of `default_value` field.
+    pub const FIELD_DEFAULT_VALUE_NUMBER: u32 = 7;
+
+    /// Field number of `oneof_index` field.
+    pub const FIELD_ONEOF_INDEX_NUMBER: u32 = 9;
+
+    /// Field number of `json_name` field.
+    pub const FIELD_JSON_NAME_NUMBER: u32 = 10;
+
+    /// Field number of `options` field.
+    pub const FIELD_OPTIONS_NUMBER: u32 = 8;
+
+    /// Field number of `proto3_optional` field.
+    pub const FIELD_PROTO3_OPTIONAL_NUMBER: u32 = 17;
+
     // optional string name = 1;
 
     pub fn get_name(&self) -> &str {
@@ -2060,42 +2288,43 @@ impl crate::Message for FieldDescriptorProto {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if let Some(v) = self.name.as_ref() {
-            my_size += crate::rt::string_size(1, &v);
+            my_size += crate::rt::string_size(1, &v) as u64;
         }
         if let Some(v) = self.number {
-            my_size += crate::rt::value_size(3, v, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(3, v, crate::wire_format::WireTypeVarint) as u64;
         }
         if let Some(v) = self.label {
-            my_size += crate::rt::enum_or_unknown_size(4, v);
+            my_size += crate::rt::enum_or_unknown_size(4, v) as u64;
         }
         if let Some(v) = self.field_type {
-            my_size += crate::rt::enum_or_unknown_size(5, v);
+            my_size += crate::rt::enum_or_unknown_size(5, v) as u64;
         }
         if let Some(v) = self.type_name.as_ref() {
-            my_size += crate::rt::string_size(6, &v);
+            my_size += crate::rt::string_size(6, &v) as u64;
         }
         if let Some(v) = self.extendee.as_ref() {
-            my_size += crate::rt::string_size(2, &v);
+            my_size += crate::rt::string_size(2, &v) as u64;
         }
         if let Some(v) = self.default_value.as_ref() {
-            my_size += crate::rt::string_size(7, &v);
+            my_size += crate::rt::string_size(7, &v) as u64;
         }
         if let Some(v) = self.oneof_index {
-            my_size += crate::rt::value_size(9, v, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(9, v, crate::wire_format::WireTypeVarint) as u64;
         }
         if let Some(v) = self.json_name.as_ref() {
-            my_size += crate::rt::string_size(10, &v);
+            my_size += crate::rt::string_size(10, &v) as u64;
         }
         if let Some(v) = self.options.as_ref() {
             let len = v.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         }
         if let Some(v) = self.proto3_optional {
             my_size += 3;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -2207,6 +2436,32 @@ impl crate::reflect::ProtobufValue for FieldDescriptorProto {
 
 /// Nested message and enums of message `FieldDescriptorProto`
 pub mod field_descriptor_proto {
+
+    /// Field numbers of message `FieldDescriptorProto`
+    pub mod field_numbers {
+        /// Field number of `name` field.
+        pub const NAME: u32 = 1;
+        /// Field number of `number` field.
+        pub const NUMBER: u32 = 3;
+        /// Field number of `label` field.
+        pub const LABEL: u32 = 4;
+        /// Field number of `type` field.
+        pub const TYPE: u32 = 5;
+        /// Field number of `type_name` field.
+        pub const TYPE_NAME: u32 = 6;
+        /// Field number of `extendee` field.
+        pub const EXTENDEE: u32 = 2;
+        /// Field number of `default_value` field.
+        pub const DEFAULT_VALUE: u32 = 7;
+        /// Field number of `oneof_index` field.
+        pub const ONEOF_INDEX: u32 = 9;
+        /// Field number of `json_name` field.
+        pub const JSON_NAME: u32 = 10;
+        /// Field number of `options` field.
+        pub const OPTIONS: u32 = 8;
+        /// Field number of `proto3_optional` field.
+        pub const PROTO3_OPTIONAL: u32 = 17;
+    }
     #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
     #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
     pub enum Type {
@@ -2259,6 +2514,53 @@ pub mod field_descriptor_proto {
             }
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                Type::TYPE_DOUBLE => "TYPE_DOUBLE",
+                Type::TYPE_FLOAT => "TYPE_FLOAT",
+                Type::TYPE_INT64 => "TYPE_INT64",
+                Type::TYPE_UINT64 => "TYPE_UINT64",
+                Type::TYPE_INT32 => "TYPE_INT32",
+                Type::TYPE_FIXED64 => "TYPE_FIXED64",
+                Type::TYPE_FIXED32 => "TYPE_FIXED32",
+                Type::TYPE_BOOL => "TYPE_BOOL",
+                Type::TYPE_STRING => "TYPE_STRING",
+                Type::TYPE_GROUP => "TYPE_GROUP",
+                Type::TYPE_MESSAGE => "TYPE_MESSAGE",
+                Type::TYPE_BYTES => "TYPE_BYTES",
+                Type::TYPE_UINT32 => "TYPE_UINT32",
+                Type::TYPE_ENUM => "TYPE_ENUM",
+                Type::TYPE_SFIXED32 => "TYPE_SFIXED32",
+                Type::TYPE_SFIXED64 => "TYPE_SFIXED64",
+                Type::TYPE_SINT32 => "TYPE_SINT32",
+                Type::TYPE_SINT64 => "TYPE_SINT64",
+            }
+        }
+
+        fn from_name(name: &str) -> ::std::option::Option<Type> {
+            match name {
+                "TYPE_DOUBLE" => ::std::option::Option::Some(Type::TYPE_DOUBLE),
+                "TYPE_FLOAT" => ::std::option::Option::Some(Type::TYPE_FLOAT),
+                "TYPE_INT64" => ::std::option::Option::Some(Type::TYPE_INT64),
+                "TYPE_UINT64" => ::std::option::Option::Some(Type::TYPE_UINT64),
+                "TYPE_INT32" => ::std::option::Option::Some(Type::TYPE_INT32),
+                "TYPE_FIXED64" => ::std::option::Option::Some(Type::TYPE_FIXED64),
+                "TYPE_FIXED32" => ::std::option::Option::Some(Type::TYPE_FIXED32),
+                "TYPE_BOOL" => ::std::option::Option::Some(Type::TYPE_BOOL),
+                "TYPE_STRING" => ::std::option::Option::Some(Type::TYPE_STRING),
+                "TYPE_GROUP" => ::std::option::Option::Some(Type::TYPE_GROUP),
+                "TYPE_MESSAGE" => ::std::option::Option::Some(Type::TYPE_MESSAGE),
+                "TYPE_BYTES" => ::std::option::Option::Some(Type::TYPE_BYTES),
+                "TYPE_UINT32" => ::std::option::Option::Some(Type::TYPE_UINT32),
+                "TYPE_ENUM" => ::std::option::Option::Some(Type::TYPE_ENUM),
+                "TYPE_SFIXED32" => ::std::option::Option::Some(Type::TYPE_SFIXED32),
+                "TYPE_SFIXED64" => ::std::option::Option::Some(Type::TYPE_SFIXED64),
+                "TYPE_SINT32" => ::std::option::Option::Some(Type::TYPE_SINT32),
+                "TYPE_SINT64" => ::std::option::Option::Some(Type::TYPE_SINT64),
+                _ => ::std::option::Option::None
+            }
+        }
+
         fn values() -> &'static [Self] {
             static values: &'static [Type] = &[
                 Type::TYPE_DOUBLE,
@@ -2295,6 +2597,14 @@ pub mod field_descriptor_proto {
         }
     }
 
+    impl ::std::str::FromStr for Type {
+        type Err = crate::ParseEnumError;
+
+        fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+            crate::rt::enum_from_str("FieldDescriptorProto.Type", s)
+        }
+    }
+
     impl crate::reflect::ProtobufValue for Type {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
     }
@@ -2327,6 +2637,23 @@ pub mod field_descriptor_proto {
             }
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                Label::LABEL_OPTIONAL => "LABEL_OPTIONAL",
+                Label::LABEL_REQUIRED => "LABEL_REQUIRED",
+                Label::LABEL_REPEATED => "LABEL_REPEATED",
+            }
+        }
+
+        fn from_name(name: &str) -> ::std::option::Option<Label> {
+            match name {
+                "LABEL_OPTIONAL" => ::std::option::Option::Some(Label::LABEL_OPTIONAL),
+                "LABEL_REQUIRED" => ::std::option::Option::Some(Label::LABEL_REQUIRED),
+                "LABEL_REPEATED" => ::std::option::Option::Some(Label::LABEL_REPEATED),
+                _ => ::std::option::Option::None
+            }
+        }
+
         fn values() -> &'static [Self] {
             static values: &'static [Label] = &[
                 Label::LABEL_OPTIONAL,
@@ -2348,6 +2675,14 @@ pub mod field_descriptor_proto {
         }
     }
 
+    impl ::std::str::FromStr for Label {
+        type Err = crate::ParseEnumError;
+
+        fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+            crate::rt::enum_from_str("FieldDescriptorProto.Label", s)
+        }
+    }
+
     impl crate::reflect::ProtobufValue for Label {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
     }
@@ -2384,6 +2719,12 @@ impl OneofDescriptorProto {
         ::std::default::Default::default()
     }
 
+    /// Field number of `name` field.
+    pub const FIELD_NAME_NUMBER: u32 = 1;
+
+    /// Field number of `options` field.
+    pub const FIELD_OPTIONS_NUMBER: u32 = 2;
+
     // optional string name = 1;
 
     pub fn get_name(&self) -> &str {
@@ -2475,15 +2816,16 @@ impl crate::Message for OneofDescriptorProto {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if let Some(v) = self.name.as_ref() {
-            my_size += crate::rt::string_size(1, &v);
+            my_size += crate::rt::string_size(1, &v) as u64;
         }
         if let Some(v) = self.options.as_ref() {
             let len = v.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -2548,6 +2890,18 @@ impl crate::reflect::ProtobufValue for OneofDescriptorProto {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `OneofDescriptorProto`
+pub mod oneof_descriptor_proto {
+
+    /// Field numbers of message `OneofDescriptorProto`
+    pub mod field_numbers {
+        /// Field number of `name` field.
+        pub const NAME: u32 = 1;
+        /// Field number of `options` field.
+        pub const OPTIONS: u32 = 2;
+    }
+}
+
 ///  Describes an enum type.
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
@@ -2581,6 +2935,21 @@ impl EnumDescriptorProto {
         ::std::default::Default::default()
     }
 
+    /// Field number of `name` field.
+    pub const FIELD_NAME_NUMBER: u32 = 1;
+
+    /// Field number of `value` field.
+    pub const FIELD_VALUE_NUMBER: u32 = 2;
+
+    /// Field number of `options` field.
+    pub const FIELD_OPTIONS_NUMBER: u32 = 3;
+
+    /// Field number of `reserved_range` field.
+    pub const FIELD_RESERVED_RANGE_NUMBER: u32 = 4;
+
+    /// Field number of `reserved_name` field.
+    pub const FIELD_RESERVED_NAME_NUMBER: u32 = 5;
+
     // optional string name = 1;
 
     pub fn get_name(&self) -> &str {
@@ -2706,26 +3075,27 @@ impl crate::Message for EnumDescriptorProto {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if let Some(v) = self.name.as_ref() {
-            my_size += crate::rt::string_size(1, &v);
+            my_size += crate::rt::string_size(1, &v) as u64;
         }
         for value in &self.value {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         if let Some(v) = self.options.as_ref() {
             let len = v.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         }
         for value in &self.reserved_range {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         for value in &self.reserved_name {
-            my_size += crate::rt::string_size(5, &value);
+            my_size += crate::rt::string_size(5, &value) as u64;
         };
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -2807,6 +3177,20 @@ impl crate::reflect::ProtobufValue for EnumDescriptorProto {
 
 /// Nested message and enums of message `EnumDescriptorProto`
 pub mod enum_descriptor_proto {
+
+    /// Field numbers of message `EnumDescriptorProto`
+    pub mod field_numbers {
+        /// Field number of `name` field.
+        pub const NAME: u32 = 1;
+        /// Field number of `value` field.
+        pub const VALUE: u32 = 2;
+        /// Field number of `options` field.
+        pub const OPTIONS: u32 = 3;
+        /// Field number of `reserved_range` field.
+        pub const RESERVED_RANGE: u32 = 4;
+        /// Field number of `reserved_name` field.
+        pub const RESERVED_NAME: u32 = 5;
+    }
     ///  Range of reserved numeric values. Reserved values may not be used by
     ///  entries in the same enum. Reserved ranges may not overlap.
     ///
@@ -2837,6 +3221,12 @@ pub mod enum_descriptor_proto {
             ::std::default::Default::default()
         }
 
+        /// Field number of `start` field.
+        pub const FIELD_START_NUMBER: u32 = 1;
+
+        /// Field number of `end` field.
+        pub const FIELD_END_NUMBER: u32 = 2;
+
         // optional int32 start = 1;
 
         pub fn get_start(&self) -> i32 {
@@ -2929,14 +3319,15 @@ pub mod enum_descriptor_proto {
         // Compute sizes of nested messages
         #[allow(unused_variables)]
         fn compute_size(&self) -> u32 {
-            let mut my_size = 0;
+            let mut my_size: u64 = 0;
             if let Some(v) = self.start {
-                my_size += crate::rt::value_size(1, v, crate::wire_format::WireTypeVarint);
+                my_size += crate::rt::value_size(1, v, crate::wire_format::WireTypeVarint) as u64;
             }
             if let Some(v) = self.end {
-                my_size += crate::rt::value_size(2, v, crate::wire_format::WireTypeVarint);
+                my_size += crate::rt::value_size(2, v, crate::wire_format::WireTypeVarint) as u64;
             }
-            my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+            my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+            let my_size = crate::rt::saturating_size(my_size);
             self.cached_size.set(my_size);
             my_size
         }
@@ -3000,6 +3391,18 @@ pub mod enum_descriptor_proto {
     impl crate::reflect::ProtobufValue for EnumReservedRange {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
     }
+
+    /// Nested message and enums of message `EnumReservedRange`
+    pub mod enum_reserved_range {
+
+        /// Field numbers of message `EnumReservedRange`
+        pub mod field_numbers {
+            /// Field number of `start` field.
+            pub const START: u32 = 1;
+            /// Field number of `end` field.
+            pub const END: u32 = 2;
+        }
+    }
 }
 
 ///  Describes a value within an enum.
@@ -3028,6 +3431,15 @@ impl EnumValueDescriptorProto {
         ::std::default::Default::default()
     }
 
+    /// Field number of `name` field.
+    pub const FIELD_NAME_NUMBER: u32 = 1;
+
+    /// Field number of `number` field.
+    pub const FIELD_NUMBER_NUMBER: u32 = 2;
+
+    /// Field number of `options` field.
+    pub const FIELD_OPTIONS_NUMBER: u32 = 3;
+
     // optional string name = 1;
 
     pub fn get_name(&self) -> &str {
@@ -3150,18 +3562,19 @@ impl crate::Message for EnumValueDescriptorProto {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if let Some(v) = self.name.as_ref() {
-            my_size += crate::rt::string_size(1, &v);
+            my_size += crate::rt::string_size(1, &v) as u64;
         }
         if let Some(v) = self.number {
-            my_size += crate::rt::value_size(2, v, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(2, v, crate::wire_format::WireTypeVarint) as u64;
         }
         if let Some(v) = self.options.as_ref() {
             let len = v.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -3231,6 +3644,20 @@ impl crate::reflect::ProtobufValue for EnumValueDescriptorProto {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `EnumValueDescriptorProto`
+pub mod enum_value_descriptor_proto {
+
+    /// Field numbers of message `EnumValueDescriptorProto`
+    pub mod field_numbers {
+        /// Field number of `name` field.
+        pub const NAME: u32 = 1;
+        /// Field number of `number` field.
+        pub const NUMBER: u32 = 2;
+        /// Field number of `options` field.
+        pub const OPTIONS: u32 = 3;
+    }
+}
+
 ///  Describes a service.
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
@@ -3257,6 +3684,15 @@ impl ServiceDescriptorProto {
         ::std::default::Default::default()
     }
 
+    /// Field number of `name` field.
+    pub const FIELD_NAME_NUMBER: u32 = 1;
+
+    /// Field number of `method` field.
+    pub const FIELD_METHOD_NUMBER: u32 = 2;
+
+    /// Field number of `options` field.
+    pub const FIELD_OPTIONS_NUMBER: u32 = 3;
+
     // optional string name = 1;
 
     pub fn get_name(&self) -> &str {
@@ -3361,19 +3797,20 @@ impl crate::Message for ServiceDescriptorProto {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if let Some(v) = self.name.as_ref() {
-            my_size += crate::rt::string_size(1, &v);
+            my_size += crate::rt::string_size(1, &v) as u64;
         }
         for value in &self.method {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         if let Some(v) = self.options.as_ref() {
             let len = v.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -3443,6 +3880,20 @@ impl crate::reflect::ProtobufValue for ServiceDescriptorProto {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `ServiceDescriptorProto`
+pub mod service_descriptor_proto {
+
+    /// Field numbers of message `ServiceDescriptorProto`
+    pub mod field_numbers {
+        /// Field number of `name` field.
+        pub const NAME: u32 = 1;
+        /// Field number of `method` field.
+        pub const METHOD: u32 = 2;
+        /// Field number of `options` field.
+        pub const OPTIONS: u32 = 3;
+    }
+}
+
 ///  Describes a method of a service.
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
@@ -3476,6 +3927,24 @@ impl MethodDescriptorProto {
         ::std::default::Default::default()
     }
 
+    /// Field number of `name` field.
+    pub const FIELD_NAME_NUMBER: u32 = 1;
+
+    /// Field number of `input_type` field.
+    pub const FIELD_INPUT_TYPE_NUMBER: u32 = 2;
+
+    /// Field number of `output_type` field.
+    pub const FIELD_OUTPUT_TYPE_NUMBER: u32 = 3;
+
+    /// Field number of `options` field.
+    pub const FIELD_OPTIONS_NUMBER: u32 = 4;
+
+    /// Field number of `client_streaming` field.
+    pub const FIELD_CLIENT_STREAMING_NUMBER: u32 = 5;
+
+    /// Field number of `server_streaming` field.
+    pub const FIELD_SERVER_STREAMING_NUMBER: u32 = 6;
+
     // optional string name = 1;
 
     pub fn get_name(&self) -> &str {
@@ -3725,19 +4194,19 @@ impl crate::Message for MethodDescriptorProto {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if let Some(v) = self.name.as_ref() {
-            my_size += crate::rt::string_size(1, &v);
+            my_size += crate::rt::string_size(1, &v) as u64;
         }
         if let Some(v) = self.input_type.as_ref() {
-            my_size += crate::rt::string_size(2, &v);
+            my_size += crate::rt::string_size(2, &v) as u64;
         }
         if let Some(v) = self.output_type.as_ref() {
-            my_size += crate::rt::string_size(3, &v);
+            my_size += crate::rt::string_size(3, &v) as u64;
         }
         if let Some(v) = self.options.as_ref() {
             let len = v.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         }
         if let Some(v) = self.client_streaming {
             my_size += 2;
@@ -3745,7 +4214,8 @@ impl crate::Message for MethodDescriptorProto {
         if let Some(v) = self.server_streaming {
             my_size += 2;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -3830,6 +4300,26 @@ impl crate::reflect::ProtobufValue for MethodDescriptorProto {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `MethodDescriptorProto`
+pub mod method_descriptor_proto {
+
+    /// Field numbers of message `MethodDescriptorProto`
+    pub mod field_numbers {
+        /// Field number of `name` field.
+        pub const NAME: u32 = 1;
+        /// Field number of `input_type` field.
+        pub const INPUT_TYPE: u32 = 2;
+        /// Field number of `output_type` field.
+        pub const OUTPUT_TYPE: u32 = 3;
+        /// Field number of `options` field.
+        pub const OPTIONS: u32 = 4;
+        /// Field number of `client_streaming` field.
+        pub const CLIENT_STREAMING: u32 = 5;
+        /// Field number of `server_streaming` field.
+        pub const SERVER_STREAMING: u32 = 6;
+    }
+}
+
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct FileOptions {
@@ -3936,6 +4426,69 @@ impl FileOptions {
         ::std::default::Default::default()
     }
 
+    /// Field number of `java_package` field.
+    pub const FIELD_JAVA_PACKAGE_NUMBER: u32 = 1;
+
+    /// Field number of `java_outer_classname` field.
+    pub const FIELD_JAVA_OUTER_CLASSNAME_NUMBER: u32 = 8;
+
+    /// Field number of `java_multiple_files` field.
+    pub const FIELD_JAVA_MULTIPLE_FILES_NUMBER: u32 = 10;
+
+    /// Field number of `java_generate_equals_and_hash` field.
+    pub const FIELD_JAVA_GENERATE_EQUALS_AND_HASH_NUMBER: u32 = 20;
+
+    /// Field number of `java_string_check_utf8` field.
+    pub const FIELD_JAVA_STRING_CHECK_UTF8_NUMBER: u32 = 27;
+
+    /// Field number of `optimize_for` field.
+    pub const FIELD_OPTIMIZE_FOR_NUMBER: u32 = 9;
+
+    /// Field number of `go_package` field.
+    pub const FIELD_GO_PACKAGE_NUMBER: u32 = 11;
+
+    /// Field number of `cc_generic_services` field.
+    pub const FIELD_CC_GENERIC_SERVICES_NUMBER: u32 = 16;
+
+    /// Field number of `java_generic_services` field.
+    pub const FIELD_JAVA_GENERIC_SERVICES_NUMBER: u32 = 17;
+
+    /// Field number of `py_generic_services` field.
+    pub const FIELD_PY_GENERIC_SERVICES_NUMBER: u32 = 18;
+
+    /// Field number of `php_generic_services` field.
+    pub const FIELD_PHP_GENERIC_SERVICES_NUMBER: u32 = 42;
+
+    /// Field number of `deprecated` field.
+    pub const FIELD_DEPRECATED_NUMBER: u32 = 23;
+
+    /// Field number of `cc_enable_arenas` field.
+    pub const FIELD_CC_ENABLE_ARENAS_NUMBER: u32 = 31;
+
+    /// Field number of `objc_class_prefix` field.
+    pub const FIELD_OBJC_CLASS_PREFIX_NUMBER: u32 = 36;
+
+    /// Field number of `csharp_namespace` field.
+    pub const FIELD_CSHARP_NAMESPACE_NUMBER: u32 = 37;
+
+    /// Field number of `swift_prefix` field.
+    pub const FIELD_SWIFT_PREFIX_NUMBER: u32 = 39;
+
+    /// Field number of `php_class_prefix` field.
+    pub const FIELD_PHP_CLASS_PREFIX_NUMBER: u32 = 40;
+
+    /// Field number of `php_namespace` field.
+    pub const FIELD_PHP_NAMESPACE_NUMBER: u32 = 41;
+
+    /// Field number of `php_metadata_namespace` field.
+    pub const FIELD_PHP_METADATA_NAMESPACE_NUMBER: u32 = 44;
+
+    /// Field number of `ruby_package` field.
+    pub const FIELD_RUBY_PACKAGE_NUMBER: u32 = 45;
+
+    /// Field number of `uninterpreted_option` field.
+    pub const FIELD_UNINTERPRETED_OPTION_NUMBER: u32 = 999;
+
     // optional string java_package = 1;
 
     pub fn get_java_package(&self) -> &str {
@@ -4772,12 +5325,12 @@ impl crate::Message for FileOptions {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if let Some(v) = self.java_package.as_ref() {
-            my_size += crate::rt::string_size(1, &v);
+            my_size += crate::rt::string_size(1, &v) as u64;
         }
         if let Some(v) = self.java_outer_classname.as_ref() {
-            my_size += crate::rt::string_size(8, &v);
+            my_size += crate::rt::string_size(8, &v) as u64;
         }
         if let Some(v) = self.java_multiple_files {
             my_size += 2;
@@ -4789,10 +5342,10 @@ impl crate::Message for FileOptions {
             my_size += 3;
         }
         if let Some(v) = self.optimize_for {
-            my_size += crate::rt::enum_or_unknown_size(9, v);
+            my_size += crate::rt::enum_or_unknown_size(9, v) as u64;
         }
         if let Some(v) = self.go_package.as_ref() {
-            my_size += crate::rt::string_size(11, &v);
+            my_size += crate::rt::string_size(11, &v) as u64;
         }
         if let Some(v) = self.cc_generic_services {
             my_size += 3;
@@ -4813,31 +5366,32 @@ impl crate::Message for FileOptions {
             my_size += 3;
         }
         if let Some(v) = self.objc_class_prefix.as_ref() {
-            my_size += crate::rt::string_size(36, &v);
+            my_size += crate::rt::string_size(36, &v) as u64;
         }
         if let Some(v) = self.csharp_namespace.as_ref() {
-            my_size += crate::rt::string_size(37, &v);
+            my_size += crate::rt::string_size(37, &v) as u64;
         }
         if let Some(v) = self.swift_prefix.as_ref() {
-            my_size += crate::rt::string_size(39, &v);
+            my_size += crate::rt::string_size(39, &v) as u64;
         }
         if let Some(v) = self.php_class_prefix.as_ref() {
-            my_size += crate::rt::string_size(40, &v);
+            my_size += crate::rt::string_size(40, &v) as u64;
         }
         if let Some(v) = self.php_namespace.as_ref() {
-            my_size += crate::rt::string_size(41, &v);
+            my_size += crate::rt::string_size(41, &v) as u64;
         }
         if let Some(v) = self.php_metadata_namespace.as_ref() {
-            my_size += crate::rt::string_size(44, &v);
+            my_size += crate::rt::string_size(44, &v) as u64;
         }
         if let Some(v) = self.ruby_package.as_ref() {
-            my_size += crate::rt::string_size(45, &v);
+            my_size += crate::rt::string_size(45, &v) as u64;
         }
         for value in &self.uninterpreted_option {
             let len = value.compute_size();
-            my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 2 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -4999,6 +5553,52 @@ impl crate::reflect::ProtobufValue for FileOptions {
 
 /// Nested message and enums of message `FileOptions`
 pub mod file_options {
+
+    /// Field numbers of message `FileOptions`
+    pub mod field_numbers {
+        /// Field number of `java_package` field.
+        pub const JAVA_PACKAGE: u32 = 1;
+        /// Field number of `java_outer_classname` field.
+        pub const JAVA_OUTER_CLASSNAME: u32 = 8;
+        /// Field number of `java_multiple_files` field.
+        pub const JAVA_MULTIPLE_FILES: u32 = 10;
+        /// Field number of `java_generate_equals_and_hash` field.
+        pub const JAVA_GENERATE_EQUALS_AND_HASH: u32 = 20;
+        /// Field number of `java_string_check_utf8` field.
+        pub const JAVA_STRING_CHECK_UTF8: u32 = 27;
+        /// Field number of `optimize_for` field.
+        pub const OPTIMIZE_FOR: u32 = 9;
+        /// Field number of `go_package` field.
+        pub const GO_PACKAGE: u32 = 11;
+        /// Field number of `cc_generic_services` field.
+        pub const CC_GENERIC_SERVICES: u32 = 16;
+        /// Field number of `java_generic_services` field.
+        pub const JAVA_GENERIC_SERVICES: u32 = 17;
+        /// Field number of `py_generic_services` field.
+        pub const PY_GENERIC_SERVICES: u32 = 18;
+        /// Field number of `php_generic_services` field.
+        pub const PHP_GENERIC_SERVICES: u32 = 42;
+        /// Field number of `deprecated` field.
+        pub const DEPRECATED: u32 = 23;
+        /// Field number of `cc_enable_arenas` field.
+        pub const CC_ENABLE_ARENAS: u32 = 31;
+        /// Field number of `objc_class_prefix` field.
+        pub const OBJC_CLASS_PREFIX: u32 = 36;
+        /// Field number of `csharp_namespace` field.
+        pub const CSHARP_NAMESPACE: u32 = 37;
+        /// Field number of `swift_prefix` field.
+        pub const SWIFT_PREFIX: u32 = 39;
+        /// Field number of `php_class_prefix` field.
+        pub const PHP_CLASS_PREFIX: u32 = 40;
+        /// Field number of `php_namespace` field.
+        pub const PHP_NAMESPACE: u32 = 41;
+        /// Field number of `php_metadata_namespace` field.
+        pub const PHP_METADATA_NAMESPACE: u32 = 44;
+        /// Field number of `ruby_package` field.
+        pub const RUBY_PACKAGE: u32 = 45;
+        /// Field number of `uninterpreted_option` field.
+        pub const UNINTERPRETED_OPTION: u32 = 999;
+    }
     ///  Generated classes can be optimized for speed or code size.
     #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
     #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
@@ -5022,6 +5622,23 @@ pub mod file_options {
             }
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                OptimizeMode::SPEED => "SPEED",
+                OptimizeMode::CODE_SIZE => "CODE_SIZE",
+                OptimizeMode::LITE_RUNTIME => "LITE_RUNTIME",
+            }
+        }
+
+        fn from_name(name: &str) -> ::std::option::Option<OptimizeMode> {
+            match name {
+                "SPEED" => ::std::option::Option::Some(OptimizeMode::SPEED),
+                "CODE_SIZE" => ::std::option::Option::Some(OptimizeMode::CODE_SIZE),
+                "LITE_RUNTIME" => ::std::option::Option::Some(OptimizeMode::LITE_RUNTIME),
+                _ => ::std::option::Option::None
+            }
+        }
+
         fn values() -> &'static [Self] {
             static values: &'static [OptimizeMode] = &[
                 OptimizeMode::SPEED,
@@ -5043,6 +5660,14 @@ pub mod file_options {
         }
     }
 
+    impl ::std::str::FromStr for OptimizeMode {
+        type Err = crate::ParseEnumError;
+
+        fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+            crate::rt::enum_from_str("FileOptions.OptimizeMode", s)
+        }
+    }
+
     impl crate::reflect::ProtobufValue for OptimizeMode {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
     }
@@ -5089,6 +5714,21 @@ impl MessageOptions {
         ::std::default::Default::default()
     }
 
+    /// Field number of `message_set_wire_format` field.
+    pub const FIELD_MESSAGE_SET_WIRE_FORMAT_NUMBER: u32 = 1;
+
+    /// Field number of `no_standard_descriptor_accessor` field.
+    pub const FIELD_NO_STANDARD_DESCRIPTOR_ACCESSOR_NUMBER: u32 = 2;
+
+    /// Field number of `deprecated` field.
+    pub const FIELD_DEPRECATED_NUMBER: u32 = 3;
+
+    /// Field number of `map_entry` field.
+    pub const FIELD_MAP_ENTRY_NUMBER: u32 = 7;
+
+    /// Field number of `uninterpreted_option` field.
+    pub const FIELD_UNINTERPRETED_OPTION_NUMBER: u32 = 999;
+
     // optional bool message_set_wire_format = 1;
 
     pub fn get_message_set_wire_format(&self) -> bool {
@@ -5256,7 +5896,7 @@ impl crate::Message for MessageOptions {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if let Some(v) = self.message_set_wire_format {
             my_size += 2;
         }
@@ -5271,9 +5911,10 @@ impl crate::Message for MessageOptions {
         }
         for value in &self.uninterpreted_option {
             let len = value.compute_size();
-            my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 2 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -5353,6 +5994,24 @@ impl crate::reflect::ProtobufValue for MessageOptions {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `MessageOptions`
+pub mod message_options {
+
+    /// Field numbers of message `MessageOptions`
+    pub mod field_numbers {
+        /// Field number of `message_set_wire_format` field.
+        pub const MESSAGE_SET_WIRE_FORMAT: u32 = 1;
+        /// Field number of `no_standard_descriptor_accessor` field.
+        pub const NO_STANDARD_DESCRIPTOR_ACCESSOR: u32 = 2;
+        /// Field number of `deprecated` field.
+        pub const DEPRECATED: u32 = 3;
+        /// Field number of `map_entry` field.
+        pub const MAP_ENTRY: u32 = 7;
+        /// Field number of `uninterpreted_option` field.
+        pub const UNINTERPRETED_OPTION: u32 = 999;
+    }
+}
+
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct FieldOptions {
@@ -5436,6 +6095,27 @@ impl FieldOptions {
         ::std::default::Default::default()
     }
 
+    /// Field number of `ctype` field.
+    pub const FIELD_CTYPE_NUMBER: u32 = 1;
+
+    /// Field number of `packed` field.
+    pub const FIELD_PACKED_NUMBER: u32 = 2;
+
+    /// Field number of `jstype` field.
+    pub const FIELD_JSTYPE_NUMBER: u32 = 6;
+
+    /// Field number of `lazy` field.
+    pub const FIELD_LAZY_NUMBER: u32 = 5;
+
+    /// Field number of `deprecated` field.
+    pub const FIELD_DEPRECATED_NUMBER: u32 = 3;
+
+    /// Field number of `weak` field.
+    pub const FIELD_WEAK_NUMBER: u32 = 10;
+
+    /// Field number of `uninterpreted_option` field.
+    pub const FIELD_UNINTERPRETED_OPTION_NUMBER: u32 = 999;
+
     // optional .google.protobuf.FieldOptions.CType ctype = 1;
 
     pub fn get_ctype(&self) -> field_options::CType {
@@ -5671,15 +6351,15 @@ impl crate::Message for FieldOptions {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if let Some(v) = self.ctype {
-            my_size += crate::rt::enum_or_unknown_size(1, v);
+            my_size += crate::rt::enum_or_unknown_size(1, v) as u64;
         }
         if let Some(v) = self.packed {
             my_size += 2;
         }
         if let Some(v) = self.jstype {
-            my_size += crate::rt::enum_or_unknown_size(6, v);
+            my_size += crate::rt::enum_or_unknown_size(6, v) as u64;
         }
         if let Some(v) = self.lazy {
             my_size += 2;
@@ -5692,9 +6372,10 @@ impl crate::Message for FieldOptions {
         }
         for value in &self.uninterpreted_option {
             let len = value.compute_size();
-            my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 2 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -5786,6 +6467,24 @@ impl crate::reflect::ProtobufValue for FieldOptions {
 
 /// Nested message and enums of message `FieldOptions`
 pub mod field_options {
+
+    /// Field numbers of message `FieldOptions`
+    pub mod field_numbers {
+        /// Field number of `ctype` field.
+        pub const CTYPE: u32 = 1;
+        /// Field number of `packed` field.
+        pub const PACKED: u32 = 2;
+        /// Field number of `jstype` field.
+        pub const JSTYPE: u32 = 6;
+        /// Field number of `lazy` field.
+        pub const LAZY: u32 = 5;
+        /// Field number of `deprecated` field.
+        pub const DEPRECATED: u32 = 3;
+        /// Field number of `weak` field.
+        pub const WEAK: u32 = 10;
+        /// Field number of `uninterpreted_option` field.
+        pub const UNINTERPRETED_OPTION: u32 = 999;
+    }
     #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
     #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
     pub enum CType {
@@ -5808,6 +6507,23 @@ pub mod field_options {
             }
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                CType::STRING => "STRING",
+                CType::CORD => "CORD",
+                CType::STRING_PIECE => "STRING_PIECE",
+            }
+        }
+
+        fn from_name(name: &str) -> ::std::option::Option<CType> {
+            match name {
+                "STRING" => ::std::option::Option::Some(CType::STRING),
+                "CORD" => ::std::option::Option::Some(CType::CORD),
+                "STRING_PIECE" => ::std::option::Option::Some(CType::STRING_PIECE),
+                _ => ::std::option::Option::None
+            }
+        }
+
         fn values() -> &'static [Self] {
             static values: &'static [CType] = &[
                 CType::STRING,
@@ -5828,6 +6544,14 @@ pub mod field_options {
         }
     }
 
+    impl ::std::str::FromStr for CType {
+        type Err = crate::ParseEnumError;
+
+        fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+            crate::rt::enum_from_str("FieldOptions.CType", s)
+        }
+    }
+
     impl crate::reflect::ProtobufValue for CType {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
     }
@@ -5860,6 +6584,23 @@ pub mod field_options {
             }
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                JSType::JS_NORMAL => "JS_NORMAL",
+                JSType::JS_STRING => "JS_STRING",
+                JSType::JS_NUMBER => "JS_NUMBER",
+            }
+        }
+
+        fn from_name(name: &str) -> ::std::option::Option<JSType> {
+            match name {
+                "JS_NORMAL" => ::std::option::Option::Some(JSType::JS_NORMAL),
+                "JS_STRING" => ::std::option::Option::Some(JSType::JS_STRING),
+                "JS_NUMBER" => ::std::option::Option::Some(JSType::JS_NUMBER),
+                _ => ::std::option::Option::None
+            }
+        }
+
         fn values() -> &'static [Self] {
             static values: &'static [JSType] = &[
                 JSType::JS_NORMAL,
@@ -5880,6 +6621,14 @@ pub mod field_options {
         }
     }
 
+    impl ::std::str::FromStr for JSType {
+        type Err = crate::ParseEnumError;
+
+        fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+            crate::rt::enum_from_str("FieldOptions.JSType", s)
+        }
+    }
+
     impl crate::reflect::ProtobufValue for JSType {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
     }
@@ -5915,6 +6664,9 @@ impl OneofOptions {
         ::std::default::Default::default()
     }
 
+    /// Field number of `uninterpreted_option` field.
+    pub const FIELD_UNINTERPRETED_OPTION_NUMBER: u32 = 999;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
@@ -5958,12 +6710,13 @@ impl crate::Message for OneofOptions {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         for value in &self.uninterpreted_option {
             let len = value.compute_size();
-            my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 2 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -6023,6 +6776,16 @@ impl crate::reflect::ProtobufValue for OneofOptions {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `OneofOptions`
+pub mod oneof_options {
+
+    /// Field numbers of message `OneofOptions`
+    pub mod field_numbers {
+        /// Field number of `uninterpreted_option` field.
+        pub const UNINTERPRETED_OPTION: u32 = 999;
+    }
+}
+
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct EnumOptions {
@@ -6055,6 +6818,15 @@ impl EnumOptions {
         ::std::default::Default::default()
     }
 
+    /// Field number of `allow_alias` field.
+    pub const FIELD_ALLOW_ALIAS_NUMBER: u32 = 2;
+
+    /// Field number of `deprecated` field.
+    pub const FIELD_DEPRECATED_NUMBER: u32 = 3;
+
+    /// Field number of `uninterpreted_option` field.
+    pub const FIELD_UNINTERPRETED_OPTION_NUMBER: u32 = 999;
+
     // optional bool allow_alias = 2;
 
     pub fn get_allow_alias(&self) -> bool {
@@ -6160,7 +6932,7 @@ impl crate::Message for EnumOptions {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if let Some(v) = self.allow_alias {
             my_size += 2;
         }
@@ -6169,9 +6941,10 @@ impl crate::Message for EnumOptions {
         }
         for value in &self.uninterpreted_option {
             let len = value.compute_size();
-            my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 2 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -6241,6 +7014,20 @@ impl crate::reflect::ProtobufValue for EnumOptions {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `EnumOptions`
+pub mod enum_options {
+
+    /// Field numbers of message `EnumOptions`
+    pub mod field_numbers {
+        /// Field number of `allow_alias` field.
+        pub const ALLOW_ALIAS: u32 = 2;
+        /// Field number of `deprecated` field.
+        pub const DEPRECATED: u32 = 3;
+        /// Field number of `uninterpreted_option` field.
+        pub const UNINTERPRETED_OPTION: u32 = 999;
+    }
+}
+
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct EnumValueOptions {
@@ -6270,6 +7057,12 @@ impl EnumValueOptions {
         ::std::default::Default::default()
     }
 
+    /// Field number of `deprecated` field.
+    pub const FIELD_DEPRECATED_NUMBER: u32 = 1;
+
+    /// Field number of `uninterpreted_option` field.
+    pub const FIELD_UNINTERPRETED_OPTION_NUMBER: u32 = 999;
+
     // optional bool deprecated = 1;
 
     pub fn get_deprecated(&self) -> bool {
@@ -6344,15 +7137,16 @@ impl crate::Message for EnumValueOptions {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if let Some(v) = self.deprecated {
             my_size += 2;
         }
         for value in &self.uninterpreted_option {
             let len = value.compute_size();
-            my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 2 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -6417,6 +7211,18 @@ impl crate::reflect::ProtobufValue for EnumValueOptions {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `EnumValueOptions`
+pub mod enum_value_options {
+
+    /// Field numbers of message `EnumValueOptions`
+    pub mod field_numbers {
+        /// Field number of `deprecated` field.
+        pub const DEPRECATED: u32 = 1;
+        /// Field number of `uninterpreted_option` field.
+        pub const UNINTERPRETED_OPTION: u32 = 999;
+    }
+}
+
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct ServiceOptions {
@@ -6446,6 +7252,12 @@ impl ServiceOptions {
         ::std::default::Default::default()
     }
 
+    /// Field number of `deprecated` field.
+    pub const FIELD_DEPRECATED_NUMBER: u32 = 33;
+
+    /// Field number of `uninterpreted_option` field.
+    pub const FIELD_UNINTERPRETED_OPTION_NUMBER: u32 = 999;
+
     // optional bool deprecated = 33;
 
     pub fn get_deprecated(&self) -> bool {
@@ -6520,15 +7332,16 @@ impl crate::Message for ServiceOptions {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if let Some(v) = self.deprecated {
             my_size += 3;
         }
         for value in &self.uninterpreted_option {
             let len = value.compute_size();
-            my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 2 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -6593,6 +7406,18 @@ impl crate::reflect::ProtobufValue for ServiceOptions {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `ServiceOptions`
+pub mod service_options {
+
+    /// Field numbers of message `ServiceOptions`
+    pub mod field_numbers {
+        /// Field number of `deprecated` field.
+        pub const DEPRECATED: u32 = 33;
+        /// Field number of `uninterpreted_option` field.
+        pub const UNINTERPRETED_OPTION: u32 = 999;
+    }
+}
+
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct MethodOptions {
@@ -6623,6 +7448,15 @@ impl MethodOptions {
         ::std::default::Default::default()
     }
 
+    /// Field number of `deprecated` field.
+    pub const FIELD_DEPRECATED_NUMBER: u32 = 33;
+
+    /// Field number of `idempotency_level` field.
+    pub const FIELD_IDEMPOTENCY_LEVEL_NUMBER: u32 = 34;
+
+    /// Field number of `uninterpreted_option` field.
+    pub const FIELD_UNINTERPRETED_OPTION_NUMBER: u32 = 999;
+
     // optional bool deprecated = 33;
 
     pub fn get_deprecated(&self) -> bool {
@@ -6731,18 +7565,19 @@ impl crate::Message for MethodOptions {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if let Some(v) = self.deprecated {
             my_size += 3;
         }
         if let Some(v) = self.idempotency_level {
-            my_size += crate::rt::enum_or_unknown_size(34, v);
+            my_size += crate::rt::enum_or_unknown_size(34, v) as u64;
         }
         for value in &self.uninterpreted_option {
             let len = value.compute_size();
-            my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 2 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -6814,6 +7649,16 @@ impl crate::reflect::ProtobufValue for MethodOptions {
 
 /// Nested message and enums of message `MethodOptions`
 pub mod method_options {
+
+    /// Field numbers of message `MethodOptions`
+    pub mod field_numbers {
+        /// Field number of `deprecated` field.
+        pub const DEPRECATED: u32 = 33;
+        /// Field number of `idempotency_level` field.
+        pub const IDEMPOTENCY_LEVEL: u32 = 34;
+        /// Field number of `uninterpreted_option` field.
+        pub const UNINTERPRETED_OPTION: u32 = 999;
+    }
     ///  Is this method side-effect-free (or safe in HTTP parlance), or idempotent,
     ///  or neither? HTTP based RPC implementation may choose GET verb for safe
     ///  methods, and PUT verb for idempotent methods instead of the default POST.
@@ -6839,6 +7684,23 @@ pub mod method_options {
             }
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                IdempotencyLevel::IDEMPOTENCY_UNKNOWN => "IDEMPOTENCY_UNKNOWN",
+                IdempotencyLevel::NO_SIDE_EFFECTS => "NO_SIDE_EFFECTS",
+                IdempotencyLevel::IDEMPOTENT => "IDEMPOTENT",
+            }
+        }
+
+        fn from_name(name: &str) -> ::std::option::Option<IdempotencyLevel> {
+            match name {
+                "IDEMPOTENCY_UNKNOWN" => ::std::option::Option::Some(IdempotencyLevel::IDEMPOTENCY_UNKNOWN),
+                "NO_SIDE_EFFECTS" => ::std::option::Option::Some(IdempotencyLevel::NO_SIDE_EFFECTS),
+                "IDEMPOTENT" => ::std::option::Option::Some(IdempotencyLevel::IDEMPOTENT),
+                _ => ::std::option::Option::None
+            }
+        }
+
         fn values() -> &'static [Self] {
             static values: &'static [IdempotencyLevel] = &[
                 IdempotencyLevel::IDEMPOTENCY_UNKNOWN,
@@ -6859,6 +7721,14 @@ pub mod method_options {
         }
     }
 
+    impl ::std::str::FromStr for IdempotencyLevel {
+        type Err = crate::ParseEnumError;
+
+        fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+            crate::rt::enum_from_str("MethodOptions.IdempotencyLevel", s)
+        }
+    }
+
     impl crate::reflect::ProtobufValue for IdempotencyLevel {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
     }
@@ -6907,6 +7777,27 @@ impl UninterpretedOption {
         ::std::default::Default::default()
     }
 
+    /// Field number of `name` field.
+    pub const FIELD_NAME_NUMBER: u32 = 2;
+
+    /// Field number of `identifier_value` field.
+    pub const FIELD_IDENTIFIER_VALUE_NUMBER: u32 = 3;
+
+    /// Field number of `positive_int_value` field.
+    pub const FIELD_POSITIVE_INT_VALUE_NUMBER: u32 = 4;
+
+    /// Field number of `negative_int_value` field.
+    pub const FIELD_NEGATIVE_INT_VALUE_NUMBER: u32 = 5;
+
+    /// Field number of `double_value` field.
+    pub const FIELD_DOUBLE_VALUE_NUMBER: u32 = 6;
+
+    /// Field number of `string_value` field.
+    pub const FIELD_STRING_VALUE_NUMBER: u32 = 7;
+
+    /// Field number of `aggregate_value` field.
+    pub const FIELD_AGGREGATE_VALUE_NUMBER: u32 = 8;
+
     // optional string identifier_value = 3;
 
     pub fn get_identifier_value(&self) -> &str {
@@ -7187,30 +8078,31 @@ impl crate::Message for UninterpretedOption {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         for value in &self.name {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         if let Some(v) = self.identifier_value.as_ref() {
-            my_size += crate::rt::string_size(3, &v);
+            my_size += crate::rt::string_size(3, &v) as u64;
         }
         if let Some(v) = self.positive_int_value {
-            my_size += crate::rt::value_size(4, v, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(4, v, crate::wire_format::WireTypeVarint) as u64;
         }
         if let Some(v) = self.negative_int_value {
-            my_size += crate::rt::value_size(5, v, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(5, v, crate::wire_format::WireTypeVarint) as u64;
         }
         if let Some(v) = self.double_value {
             my_size += 9;
         }
         if let Some(v) = self.string_value.as_ref() {
-            my_size += crate::rt::bytes_size(7, &v);
+            my_size += crate::rt::bytes_size(7, &v) as u64;
         }
         if let Some(v) = self.aggregate_value.as_ref() {
-            my_size += crate::rt::string_size(8, &v);
+            my_size += crate::rt::string_size(8, &v) as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -7302,6 +8194,24 @@ impl crate::reflect::ProtobufValue for UninterpretedOption {
 
 /// Nested message and enums of message `UninterpretedOption`
 pub mod uninterpreted_option {
+
+    /// Field numbers of message `UninterpretedOption`
+    pub mod field_numbers {
+        /// Field number of `name` field.
+        pub const NAME: u32 = 2;
+        /// Field number of `identifier_value` field.
+        pub const IDENTIFIER_VALUE: u32 = 3;
+        /// Field number of `positive_int_value` field.
+        pub const POSITIVE_INT_VALUE: u32 = 4;
+        /// Field number of `negative_int_value` field.
+        pub const NEGATIVE_INT_VALUE: u32 = 5;
+        /// Field number of `double_value` field.
+        pub const DOUBLE_VALUE: u32 = 6;
+        /// Field number of `string_value` field.
+        pub const STRING_VALUE: u32 = 7;
+        /// Field number of `aggregate_value` field.
+        pub const AGGREGATE_VALUE: u32 = 8;
+    }
     ///  The name of the uninterpreted option.  Each string represents a segment in
     ///  a dot-separated name.  is_extension is true iff a segment represents an
     ///  extension (denoted with parentheses in options specs in .proto files).
@@ -7331,6 +8241,12 @@ pub mod uninterpreted_option {
             ::std::default::Default::default()
         }
 
+        /// Field number of `name_part` field.
+        pub const FIELD_NAME_PART_NUMBER: u32 = 1;
+
+        /// Field number of `is_extension` field.
+        pub const FIELD_IS_EXTENSION_NUMBER: u32 = 2;
+
         // required string name_part = 1;
 
         pub fn get_name_part(&self) -> &str {
@@ -7446,14 +8362,15 @@ pub mod uninterpreted_option {
         // Compute sizes of nested messages
         #[allow(unused_variables)]
         fn compute_size(&self) -> u32 {
-            let mut my_size = 0;
+            let mut my_size: u64 = 0;
             if let Some(v) = self.name_part.as_ref() {
-                my_size += crate::rt::string_size(1, &v);
+                my_size += crate::rt::string_size(1, &v) as u64;
             }
             if let Some(v) = self.is_extension {
                 my_size += 2;
             }
-            my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+            my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+            let my_size = crate::rt::saturating_size(my_size);
             self.cached_size.set(my_size);
             my_size
         }
@@ -7517,6 +8434,18 @@ pub mod uninterpreted_option {
     impl crate::reflect::ProtobufValue for NamePart {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
     }
+
+    /// Nested message and enums of message `NamePart`
+    pub mod name_part {
+
+        /// Field numbers of message `NamePart`
+        pub mod field_numbers {
+            /// Field number of `name_part` field.
+            pub const NAME_PART: u32 = 1;
+            /// Field number of `is_extension` field.
+            pub const IS_EXTENSION: u32 = 2;
+        }
+    }
 }
 
 ///  Encapsulates information about the original source file from which a
@@ -7544,6 +8473,9 @@ impl SourceCodeInfo {
         ::std::default::Default::default()
     }
 
+    /// Field number of `location` field.
+    pub const FIELD_LOCATION_NUMBER: u32 = 1;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
@@ -7587,12 +8519,13 @@ impl crate::Message for SourceCodeInfo {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         for value in &self.location {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -7654,6 +8587,12 @@ impl crate::reflect::ProtobufValue for SourceCodeInfo {
 
 /// Nested message and enums of message `SourceCodeInfo`
 pub mod source_code_info {
+
+    /// Field numbers of message `SourceCodeInfo`
+    pub mod field_numbers {
+        /// Field number of `location` field.
+        pub const LOCATION: u32 = 1;
+    }
     #[derive(PartialEq,Clone,Default)]
     #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
     pub struct Location {
@@ -7686,6 +8625,21 @@ pub mod source_code_info {
             ::std::default::Default::default()
         }
 
+        /// Field number of `path` field.
+        pub const FIELD_PATH_NUMBER: u32 = 1;
+
+        /// Field number of `span` field.
+        pub const FIELD_SPAN_NUMBER: u32 = 2;
+
+        /// Field number of `leading_comments` field.
+        pub const FIELD_LEADING_COMMENTS_NUMBER: u32 = 3;
+
+        /// Field number of `trailing_comments` field.
+        pub const FIELD_TRAILING_COMMENTS_NUMBER: u32 = 4;
+
+        /// Field number of `leading_detached_comments` field.
+        pub const FIELD_LEADING_DETACHED_COMMENTS_NUMBER: u32 = 6;
+
         // optional string leading_comments = 3;
 
         pub fn get_leading_comments(&self) -> &str {
@@ -7836,23 +8790,24 @@ pub mod source_code_info {
         // Compute sizes of nested messages
         #[allow(unused_variables)]
         fn compute_size(&self) -> u32 {
-            let mut my_size = 0;
+            let mut my_size: u64 = 0;
             if !self.path.is_empty() {
-                my_size += crate::rt::vec_packed_varint_size(1, &self.path);
+                my_size += crate::rt::vec_packed_varint_size(1, &self.path) as u64;
             }
             if !self.span.is_empty() {
-                my_size += crate::rt::vec_packed_varint_size(2, &self.span);
+                my_size += crate::rt::vec_packed_varint_size(2, &self.span) as u64;
             }
             if let Some(v) = self.leading_comments.as_ref() {
-                my_size += crate::rt::string_size(3, &v);
+                my_size += crate::rt::string_size(3, &v) as u64;
             }
             if let Some(v) = self.trailing_comments.as_ref() {
-                my_size += crate::rt::string_size(4, &v);
+                my_size += crate::rt::string_size(4, &v) as u64;
             }
             for value in &self.leading_detached_comments {
-                my_size += crate::rt::string_size(6, &value);
+                my_size += crate::rt::string_size(6, &value) as u64;
             };
-            my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+            my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+            let my_size = crate::rt::saturating_size(my_size);
             self.cached_size.set(my_size);
             my_size
         }
@@ -7941,6 +8896,24 @@ pub mod source_code_info {
     impl crate::reflect::ProtobufValue for Location {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
     }
+
+    /// Nested message and enums of message `Location`
+    pub mod location {
+
+        /// Field numbers of message `Location`
+        pub mod field_numbers {
+            /// Field number of `path` field.
+            pub const PATH: u32 = 1;
+            /// Field number of `span` field.
+            pub const SPAN: u32 = 2;
+            /// Field number of `leading_comments` field.
+            pub const LEADING_COMMENTS: u32 = 3;
+            /// Field number of `trailing_comments` field.
+            pub const TRAILING_COMMENTS: u32 = 4;
+            /// Field number of `leading_detached_comments` field.
+            pub const LEADING_DETACHED_COMMENTS: u32 = 6;
+        }
+    }
 }
 
 ///  Describes the relationship between generated code and its original source
@@ -7971,6 +8944,9 @@ impl GeneratedCodeInfo {
         ::std::default::Default::default()
     }
 
+    /// Field number of `annotation` field.
+    pub const FIELD_ANNOTATION_NUMBER: u32 = 1;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
@@ -8014,12 +8990,13 @@ impl crate::Message for GeneratedCodeInfo {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         for value in &self.annotation {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -8081,6 +9058,12 @@ impl crate::reflect::ProtobufValue for GeneratedCodeInfo {
 
 /// Nested message and enums of message `GeneratedCodeInfo`
 pub mod generated_code_info {
+
+    /// Field numbers of message `GeneratedCodeInfo`
+    pub mod field_numbers {
+        /// Field number of `annotation` field.
+        pub const ANNOTATION: u32 = 1;
+    }
     #[derive(PartialEq,Clone,Default)]
     #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
     pub struct Annotation {
@@ -8115,6 +9098,18 @@ pub mod generated_code_info {
             ::std::default::Default::default()
         }
 
+        /// Field number of `path` field.
+        pub const FIELD_PATH_NUMBER: u32 = 1;
+
+        /// Field number of `source_file` field.
+        pub const FIELD_SOURCE_FILE_NUMBER: u32 = 2;
+
+        /// Field number of `begin` field.
+        pub const FIELD_BEGIN_NUMBER: u32 = 3;
+
+        /// Field number of `end` field.
+        pub const FIELD_END_NUMBER: u32 = 4;
+
         // optional string source_file = 2;
 
         pub fn get_source_file(&self) -> &str {
@@ -8263,20 +9258,21 @@ pub mod generated_code_info {
         // Compute sizes of nested messages
         #[allow(unused_variables)]
         fn compute_size(&self) -> u32 {
-            let mut my_size = 0;
+            let mut my_size: u64 = 0;
             if !self.path.is_empty() {
-                my_size += crate::rt::vec_packed_varint_size(1, &self.path);
+                my_size += crate::rt::vec_packed_varint_size(1, &self.path) as u64;
             }
             if let Some(v) = self.source_file.as_ref() {
-                my_size += crate::rt::string_size(2, &v);
+                my_size += crate::rt::string_size(2, &v) as u64;
             }
             if let Some(v) = self.begin {
-                my_size += crate::rt::value_size(3, v, crate::wire_format::WireTypeVarint);
+                my_size += crate::rt::value_size(3, v, crate::wire_format::WireTypeVarint) as u64;
             }
             if let Some(v) = self.end {
-                my_size += crate::rt::value_size(4, v, crate::wire_format::WireTypeVarint);
+                my_size += crate::rt::value_size(4, v, crate::wire_format::WireTypeVarint) as u64;
             }
-            my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+            my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+            let my_size = crate::rt::saturating_size(my_size);
             self.cached_size.set(my_size);
             my_size
         }
@@ -8355,6 +9351,22 @@ pub mod generated_code_info {
     impl crate::reflect::ProtobufValue for Annotation {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
     }
+
+    /// Nested message and enums of message `Annotation`
+    pub mod annotation {
+
+        /// Field numbers of message `Annotation`
+        pub mod field_numbers {
+            /// Field number of `path` field.
+            pub const PATH: u32 = 1;
+            /// Field number of `source_file` field.
+            pub const SOURCE_FILE: u32 = 2;
+            /// Field number of `begin` field.
+            pub const BEGIN: u32 = 3;
+            /// Field number of `end` field.
+            pub const END: u32 = 4;
+        }
+    }
 }
 
 static file_descriptor_proto_data: &'static [u8] = b"\
@@ -0,0 +1,163 @@
+use std::fmt;
+use std::marker;
+
+use crate::enums::ProtobufEnum;
+use crate::enums::ProtobufEnumOrUnknown;
+
+/// Set of enum values stored as a bitmask: bit `n` is set
+/// if set contains enum value with number `n`.
+///
+/// Only enum values with numbers in `0..64` can be stored in the set.
+/// This is a compact alternative to repeated enum fields
+/// used as sets of flags (capabilities, permissions etc).
+#[derive(Eq, PartialEq, Hash, Copy, Clone)]
+pub struct ProtobufEnumBitSet<E> {
+    bits: u64,
+    _marker: marker::PhantomData<E>,
+}
+
+fn bit(value: i32) -> Option<u64> {
+    if (0..64).contains(&value) {
+        Some(1 << value)
+    } else {
+        None
+    }
+}
+
+impl<E: ProtobufEnum> ProtobufEnumBitSet<E> {
+    /// Empty set.
+    pub fn new() -> ProtobufEnumBitSet<E> {
+        ProtobufEnumBitSet::from_bits(0)
+    }
+
+    /// Construct a set from bitmask.
+    ///
+    /// Note bits are not required to correspond to known enum values.
+    pub fn from_bits(bits: u64) -> ProtobufEnumBitSet<E> {
+        ProtobufEnumBitSet {
+            bits,
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Get the bitmask.
+    pub fn bits(&self) -> u64 {
+        self.bits
+    }
+
+    /// Is the set empty.
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Check if the set contains enum value.
+    pub fn contains(&self, value: E) -> bool {
+        match bit(value.value()) {
+            Some(bit) => self.bits & bit != 0,
+            None => false,
+        }
+    }
+
+    /// Add enum value to the set.
+    ///
+    /// # Panics
+    ///
+    /// If enum value number is not in `0..64`.
+    pub fn insert(&mut self, value: E) {
+        match bit(value.value()) {
+            Some(bit) => self.bits |= bit,
+            None => panic!("enum value {:?} cannot be stored in bit set", value),
+        }
+    }
+
+    /// Remove enum value from the set.
+    pub fn remove(&mut self, value: E) {
+        if let Some(bit) = bit(value.value()) {
+            self.bits &= !bit;
+        }
+    }
+
+    /// Construct a set from repeated enum field.
+    ///
+    /// Return `None` if any value number is not in `0..64`.
+    pub fn from_repeated(values: &[ProtobufEnumOrUnknown<E>]) -> Option<ProtobufEnumBitSet<E>> {
+        let mut bits = 0;
+        for value in values {
+            bits |= bit(value.value())?;
+        }
+        Some(ProtobufEnumBitSet::from_bits(bits))
+    }
+
+    /// Convert the set to repeated enum field value.
+    ///
+    /// Values are sorted by number, unknown values are preserved.
+    pub fn to_repeated(&self) -> Vec<ProtobufEnumOrUnknown<E>> {
+        (0..64)
+            .filter(|n| self.bits & (1 << n) != 0)
+            .map(ProtobufEnumOrUnknown::from_i32)
+            .collect()
+    }
+}
+
+impl<E: ProtobufEnum> Default for ProtobufEnumBitSet<E> {
+    fn default() -> ProtobufEnumBitSet<E> {
+        ProtobufEnumBitSet::new()
+    }
+}
+
+impl<E: ProtobufEnum> fmt::Debug for ProtobufEnumBitSet<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.to_repeated()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::field_descriptor_proto::Type;
+
+    #[test]
+    fn insert_contains_remove() {
+        let mut set = ProtobufEnumBitSet::new();
+        assert!(set.is_empty());
+        set.insert(Type::TYPE_INT32);
+        set.insert(Type::TYPE_STRING);
+        assert!(set.contains(Type::TYPE_INT32));
+        assert!(set.contains(Type::TYPE_STRING));
+        assert!(!set.contains(Type::TYPE_BOOL));
+        assert_eq!((1 << 5) | (1 << 9), set.bits());
+        set.remove(Type::TYPE_INT32);
+        assert!(!set.contains(Type::TYPE_INT32));
+        assert_eq!(1 << 9, set.bits());
+    }
+
+    #[test]
+    fn repeated_round_trip() {
+        let repeated = vec![
+            ProtobufEnumOrUnknown::new(Type::TYPE_STRING),
+            ProtobufEnumOrUnknown::new(Type::TYPE_INT32),
+            ProtobufEnumOrUnknown::from_i32(63),
+        ];
+        let set = ProtobufEnumBitSet::<Type>::from_repeated(&repeated).unwrap();
+        assert_eq!(
+            vec![
+                ProtobufEnumOrUnknown::new(Type::TYPE_INT32),
+                ProtobufEnumOrUnknown::new(Type::TYPE_STRING),
+                ProtobufEnumOrUnknown::from_i32(63),
+            ],
+            set.to_repeated()
+        );
+    }
+
+    #[test]
+    fn repeated_out_of_range() {
+        assert_eq!(
+            None,
+            ProtobufEnumBitSet::<Type>::from_repeated(&[ProtobufEnumOrUnknown::from_i32(64)])
+        );
+        assert_eq!(
+            None,
+            ProtobufEnumBitSet::<Type>::from_repeated(&[ProtobufEnumOrUnknown::from_i32(-1)])
+        );
+    }
+}
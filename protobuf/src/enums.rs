@@ -1,5 +1,8 @@
+use std::error::Error;
 use std::fmt;
+use std::iter;
 use std::marker;
+use std::slice;
 
 use crate::reflect::runtime_types::RuntimeTypeEnumOrUnknown;
 use crate::reflect::EnumDescriptor;
@@ -15,13 +18,46 @@ pub trait ProtobufEnum: Eq + Sized + Copy + 'static + ProtobufValue + fmt::Debug
     /// Return `None` if value is unknown.
     fn from_i32(v: i32) -> Option<Self>;
 
+    /// Get enum value name as specified in `.proto` file.
+    ///
+    /// Default implementation finds the name in the enum descriptor
+    /// by position of the value in [`values`](ProtobufEnum::values).
+    /// Generated enums override it.
+    fn name(&self) -> &'static str {
+        let index = Self::values().iter().position(|v| v == self).unwrap();
+        let proto = Self::enum_descriptor_static()
+            .get_static_proto()
+            .expect("name must be implemented for enums with dynamic descriptor");
+        proto.value[index].get_name()
+    }
+
+    /// Find enum value by name as specified in `.proto` file.
+    /// Return `None` if there's no value with such name.
+    ///
+    /// Default implementation finds the name in the enum descriptor.
+    /// Generated enums override it.
+    fn from_name(name: &str) -> Option<Self> {
+        let index = Self::enum_descriptor_static()
+            .get_value_by_name(name)?
+            .index;
+        Self::values().get(index).copied()
+    }
+
     /// Get all enum values for enum type.
     fn values() -> &'static [Self];
 
+    /// Iterate over all enum values for enum type.
+    fn values_iter() -> iter::Copied<slice::Iter<'static, Self>> {
+        Self::values().iter().copied()
+    }
+
     /// Get enum value descriptor.
+    ///
+    /// For enums with `allow_alias` option this is the descriptor
+    /// of this variant, not of the first variant with the same number.
     fn descriptor(&self) -> EnumValueDescriptor {
         self.enum_descriptor()
-            .get_value_by_number(self.value())
+            .get_value_by_name(self.name())
             .unwrap()
     }
 
@@ -36,6 +72,31 @@ pub trait ProtobufEnum: Eq + Sized + Copy + 'static + ProtobufValue + fmt::Debug
     }
 }
 
+/// Error returned by `FromStr` implementation of generated enums
+/// when string is not a name of any enum value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEnumError {
+    enum_name: &'static str,
+    name: String,
+}
+
+impl ParseEnumError {
+    pub(crate) fn new(enum_name: &'static str, name: &str) -> ParseEnumError {
+        ParseEnumError {
+            enum_name,
+            name: name.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for ParseEnumError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown {} value name: {}", self.enum_name, self.name)
+    }
+}
+
+impl Error for ParseEnumError {}
+
 /// Protobuf enums with possibly unknown values are preserved in this struct.
 #[derive(Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
 #[repr(transparent)]
@@ -119,6 +180,34 @@ impl<E: ProtobufEnum> fmt::Debug for ProtobufEnumOrUnknown<E> {
     }
 }
 
+/// Archived as plain `i32`, so unknown values are preserved.
+#[cfg(feature = "with-rkyv")]
+impl<E> rkyv::Archive for ProtobufEnumOrUnknown<E> {
+    type Archived = rkyv::Archived<i32>;
+    type Resolver = ();
+
+    unsafe fn resolve(&self, pos: usize, resolver: (), out: *mut Self::Archived) {
+        self.value.resolve(pos, resolver, out)
+    }
+}
+
+#[cfg(feature = "with-rkyv")]
+impl<E, S: rkyv::Fallible + ?Sized> rkyv::Serialize<S> for ProtobufEnumOrUnknown<E> {
+    fn serialize(&self, _serializer: &mut S) -> Result<(), S::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "with-rkyv")]
+impl<E, D: rkyv::Fallible + ?Sized> rkyv::Deserialize<ProtobufEnumOrUnknown<E>, D>
+    for rkyv::Archived<i32>
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<ProtobufEnumOrUnknown<E>, D::Error> {
+        let value: i32 = rkyv::Deserialize::deserialize(self, deserializer)?;
+        Ok(ProtobufEnumOrUnknown::from_i32(value))
+    }
+}
+
 impl<E: ProtobufEnum + ProtobufValue> ProtobufValue for ProtobufEnumOrUnknown<E> {
     type RuntimeType = RuntimeTypeEnumOrUnknown<E>;
 }
@@ -148,3 +237,52 @@ impl<'de, E: serde::Deserialize<'de> + ProtobufEnum> serde::Deserialize<'de>
         Ok(ProtobufEnumOrUnknown::new(E::deserialize(deserializer)?))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::field_descriptor_proto;
+    use crate::reflect::runtime_types::RuntimeTypeEnum;
+
+    /// Enum implemented by hand, without `name` and `from_name`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    enum Label {
+        #[default]
+        Optional = 1,
+        Required = 2,
+        Repeated = 3,
+    }
+
+    impl ProtobufValue for Label {
+        type RuntimeType = RuntimeTypeEnum<Self>;
+    }
+
+    impl ProtobufEnum for Label {
+        fn value(&self) -> i32 {
+            *self as i32
+        }
+
+        fn from_i32(v: i32) -> Option<Self> {
+            Self::values().iter().copied().find(|e| e.value() == v)
+        }
+
+        fn values() -> &'static [Self] {
+            &[Label::Optional, Label::Required, Label::Repeated]
+        }
+
+        fn enum_descriptor_static() -> EnumDescriptor {
+            field_descriptor_proto::Label::enum_descriptor_static()
+        }
+    }
+
+    #[test]
+    fn default_name() {
+        assert_eq!("LABEL_REQUIRED", Label::Required.name());
+        assert_eq!(Some(Label::Repeated), Label::from_name("LABEL_REPEATED"));
+        assert_eq!(None, Label::from_name("Repeated"));
+        assert_eq!(
+            field_descriptor_proto::Label::LABEL_OPTIONAL.descriptor(),
+            Label::Optional.descriptor()
+        );
+    }
+}
@@ -21,10 +21,13 @@ pub enum WireError {
     Utf8Error,
     InvalidEnumValue(i32),
     OverRecursionLimit,
+    OverLengthLimit,
     TruncatedMessage,
     // not really possible
     LimitOverflow,
     LimitIncrease,
+    IncorrectMessageSetItem,
+    IncorrectPackedLength,
 }
 
 impl fmt::Display for WireError {
@@ -38,11 +41,14 @@ impl fmt::Display for WireError {
             WireError::IncompleteMap => write!(f, "incomplete map"),
             WireError::UnexpectedEof => write!(f, "unexpected EOF"),
             WireError::OverRecursionLimit => write!(f, "over recursion limit"),
+            WireError::OverLengthLimit => write!(f, "over length limit"),
             WireError::TruncatedMessage => write!(f, "truncated message"),
             WireError::LimitOverflow => write!(f, "limit overflow"),
             WireError::LimitIncrease => {
                 write!(f, "new limit must be not greater than current limit")
             }
+            WireError::IncorrectMessageSetItem => write!(f, "incorrect message set item"),
+            WireError::IncorrectPackedLength => write!(f, "incorrect packed field length"),
         }
     }
 }
@@ -57,17 +63,24 @@ pub enum ProtobufError {
     /// Protocol contains a string which is not valid UTF-8 string
     Utf8(str::Utf8Error),
     /// Not all required fields of message set.
+    ///
+    /// Contains message name followed by paths to missing fields.
     MessageNotInitialized(String),
+    /// Serialized message size exceeds protobuf limit of 2 GiB.
+    MessageTooLarge,
 }
 
 impl fmt::Display for ProtobufError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             // not sure that cause should be included in message
-            &ProtobufError::IoError(ref e) => write!(f, "IO error: {}", e),
-            &ProtobufError::WireError(ref e) => fmt::Display::fmt(e, f),
-            &ProtobufError::Utf8(ref e) => write!(f, "{}", e),
-            &ProtobufError::MessageNotInitialized { .. } => write!(f, "not all message fields set"),
+            ProtobufError::IoError(e) => write!(f, "IO error: {}", e),
+            ProtobufError::WireError(e) => fmt::Display::fmt(e, f),
+            ProtobufError::Utf8(e) => write!(f, "{}", e),
+            ProtobufError::MessageNotInitialized(message) => {
+                write!(f, "not all message fields set: {}", message)
+            }
+            ProtobufError::MessageTooLarge => write!(f, "message is too large to serialize"),
         }
     }
 }
@@ -75,10 +88,11 @@ impl fmt::Display for ProtobufError {
 impl Error for ProtobufError {
     fn cause(&self) -> Option<&dyn Error> {
         match self {
-            &ProtobufError::IoError(ref e) => Some(e),
-            &ProtobufError::Utf8(ref e) => Some(e),
-            &ProtobufError::WireError(..) => None,
-            &ProtobufError::MessageNotInitialized { .. } => None,
+            ProtobufError::IoError(e) => Some(e),
+            ProtobufError::Utf8(e) => Some(e),
+            ProtobufError::WireError(..) => None,
+            ProtobufError::MessageNotInitialized(..) => None,
+            ProtobufError::MessageTooLarge => None,
         }
     }
 }
@@ -106,6 +120,9 @@ impl From<ProtobufError> for io::Error {
                 io::ErrorKind::InvalidInput,
                 ProtobufError::MessageNotInitialized(message),
             ),
+            ProtobufError::MessageTooLarge => {
+                io::Error::new(io::ErrorKind::InvalidInput, ProtobufError::MessageTooLarge)
+            }
             e => io::Error::new(io::ErrorKind::Other, Box::new(e)),
         }
     }
@@ -0,0 +1,218 @@
+//! Read and write single messages with a length prefix.
+//!
+//! Functions in this module read exactly one message from [`Read`],
+//! so the reader can be used to read more messages or other data after that.
+
+use std::io;
+use std::io::Read;
+use std::io::Write;
+
+use crate::error::WireError;
+use crate::message::check_message_size;
+use crate::reflect::MessageDescriptor;
+use crate::CodedOutputStream;
+use crate::Message;
+use crate::MessageDyn;
+use crate::ProtobufError;
+use crate::ProtobufResult;
+
+/// How message boundaries are encoded in a stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Framing {
+    /// Message is prefixed with its length encoded as varint,
+    /// like in [`Message::write_length_delimited_to_writer`]
+    /// and Java `writeDelimitedTo`.
+    Varint,
+    /// Message is prefixed with its length encoded as big-endian 4 byte integer.
+    FixedU32,
+    /// Message is not prefixed, length is known from other source.
+    ///
+    /// The length is only used when reading.
+    None(u32),
+}
+
+/// Read varint length prefix byte by byte to not consume message bytes.
+fn read_varint32(r: &mut dyn Read) -> ProtobufResult<u32> {
+    let mut value: u64 = 0;
+    for i in 0..10 {
+        let mut b = [0u8];
+        r.read_exact(&mut b)?;
+        if i == 9 && (b[0] & 0x7f) > 1 {
+            return Err(ProtobufError::WireError(WireError::IncorrectVarint));
+        }
+        value |= ((b[0] & 0x7f) as u64) << (i * 7);
+        if b[0] < 0x80 {
+            if value > u32::MAX as u64 {
+                return Err(ProtobufError::WireError(WireError::IncorrectVarint));
+            }
+            return Ok(value as u32);
+        }
+    }
+    Err(ProtobufError::WireError(WireError::IncorrectVarint))
+}
+
+/// Read message bytes according to framing.
+fn read_message_bytes(r: &mut dyn Read, framing: Framing) -> ProtobufResult<Vec<u8>> {
+    let len = match framing {
+        Framing::Varint => read_varint32(r)?,
+        Framing::FixedU32 => {
+            let mut len = [0u8; 4];
+            r.read_exact(&mut len)?;
+            u32::from_be_bytes(len)
+        }
+        Framing::None(len) => len,
+    };
+    // Do not trust the length to preallocate the buffer
+    let mut bytes = Vec::new();
+    r.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len as usize {
+        return Err(ProtobufError::WireError(WireError::UnexpectedEof));
+    }
+    Ok(bytes)
+}
+
+/// Read message bytes according to framing,
+/// or return `None` if the reader is at EOF before the first byte.
+pub(crate) fn read_message_bytes_or_eof(
+    r: &mut dyn Read,
+    framing: Framing,
+) -> ProtobufResult<Option<Vec<u8>>> {
+    let mut first = [0u8];
+    loop {
+        match r.read(&mut first) {
+            Ok(0) => return Ok(None),
+            Ok(_) => break,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    read_message_bytes(&mut (&first[..]).chain(r), framing).map(Some)
+}
+
+/// Read exactly one message from the reader.
+pub fn read_one<M: Message>(r: &mut dyn Read, framing: Framing) -> ProtobufResult<M> {
+    M::parse_from_bytes(&read_message_bytes(r, framing)?)
+}
+
+/// Read exactly one message of given type from the reader.
+pub fn read_one_dyn(
+    r: &mut dyn Read,
+    descriptor: &MessageDescriptor,
+    framing: Framing,
+) -> ProtobufResult<Box<dyn MessageDyn>> {
+    let bytes = read_message_bytes(r, framing)?;
+    let mut message = descriptor.new_instance();
+    message.merge_from_bytes_dyn(&bytes)?;
+    message.check_initialized_dyn()?;
+    Ok(message)
+}
+
+/// Write one message to the writer.
+pub fn write_one<M: Message>(m: &M, w: &mut dyn Write, framing: Framing) -> ProtobufResult<()> {
+    write_one_dyn(m, w, framing)
+}
+
+/// Write one message to the writer.
+pub fn write_one_dyn(
+    m: &dyn MessageDyn,
+    w: &mut dyn Write,
+    framing: Framing,
+) -> ProtobufResult<()> {
+    m.check_initialized_dyn()?;
+    let size = check_message_size(m.compute_size_dyn())?;
+    let mut os = CodedOutputStream::new(w);
+    match framing {
+        Framing::Varint => os.write_raw_varint32(size)?,
+        Framing::FixedU32 => os.write_raw_bytes(&size.to_be_bytes())?,
+        Framing::None(_) => {}
+    }
+    m.write_to_with_cached_sizes_dyn(&mut os)?;
+    os.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Read;
+
+    use super::read_one;
+    use super::read_one_dyn;
+    use super::write_one;
+    use super::write_one_dyn;
+    use super::Framing;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::Message;
+
+    fn message(name: &str) -> FileDescriptorProto {
+        let mut m = FileDescriptorProto::new();
+        m.set_name(name.to_owned());
+        m
+    }
+
+    #[test]
+    fn round_trip() {
+        for &framing in &[Framing::Varint, Framing::FixedU32] {
+            let mut bytes = Vec::new();
+            write_one(&message("a.proto"), &mut bytes, framing).unwrap();
+            write_one(&message("b.proto"), &mut bytes, framing).unwrap();
+            bytes.extend_from_slice(b"rest");
+
+            let mut r = &bytes[..];
+            assert_eq!(message("a.proto"), read_one(&mut r, framing).unwrap());
+            assert_eq!(message("b.proto"), read_one(&mut r, framing).unwrap());
+            let mut rest = Vec::new();
+            r.read_to_end(&mut rest).unwrap();
+            assert_eq!(b"rest", &rest[..]);
+        }
+    }
+
+    #[test]
+    fn prefix() {
+        let m = message("a.proto");
+        let len = m.compute_size();
+
+        let mut bytes = Vec::new();
+        write_one(&m, &mut bytes, Framing::FixedU32).unwrap();
+        assert_eq!(&len.to_be_bytes(), &bytes[..4]);
+
+        let mut bytes = Vec::new();
+        write_one(&m, &mut bytes, Framing::Varint).unwrap();
+        assert_eq!(m.write_length_delimited_to_bytes().unwrap(), bytes);
+
+        let mut bytes = Vec::new();
+        write_one(&m, &mut bytes, Framing::None(0)).unwrap();
+        assert_eq!(m.write_to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn none() {
+        let bytes = message("a.proto").write_to_bytes().unwrap();
+        let mut both = bytes.clone();
+        both.extend_from_slice(&message("b.proto").write_to_bytes().unwrap());
+
+        let mut r = &both[..];
+        let m: FileDescriptorProto = read_one(&mut r, Framing::None(bytes.len() as u32)).unwrap();
+        assert_eq!(message("a.proto"), m);
+        assert_eq!(both.len() - bytes.len(), r.len());
+    }
+
+    #[test]
+    fn dyn_round_trip() {
+        let descriptor = FileDescriptorProto::descriptor_static();
+        let mut bytes = Vec::new();
+        write_one_dyn(&message("a.proto"), &mut bytes, Framing::Varint).unwrap();
+        let m = read_one_dyn(&mut &bytes[..], &descriptor, Framing::Varint).unwrap();
+        assert_eq!(
+            &message("a.proto"),
+            m.downcast_ref::<FileDescriptorProto>().unwrap()
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let mut bytes = Vec::new();
+        write_one(&message("a.proto"), &mut bytes, Framing::FixedU32).unwrap();
+        bytes.pop();
+        assert!(read_one::<FileDescriptorProto>(&mut &bytes[..], Framing::FixedU32).is_err());
+        assert!(read_one::<FileDescriptorProto>(&mut &[0x80u8][..], Framing::Varint).is_err());
+    }
+}
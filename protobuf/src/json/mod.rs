@@ -7,6 +7,9 @@ mod json_name;
 mod parse;
 mod print;
 mod rfc_3339;
+#[cfg(feature = "with-serde-json")]
+mod serde_wrapper;
+mod well_known;
 mod well_known_wrapper;
 
 #[doc(hidden)]
@@ -21,5 +24,11 @@ pub use self::parse::ParseError;
 pub use self::parse::ParseOptions;
 pub use self::print::print_to_string;
 pub use self::print::print_to_string_with_options;
+pub(crate) use self::print::quote_json_string_to;
 pub use self::print::PrintError;
 pub use self::print::PrintOptions;
+pub use self::print::MAX_DEPTH_PLACEHOLDER;
+#[cfg(feature = "with-serde-json")]
+pub use self::serde_wrapper::MessageSeed;
+#[cfg(feature = "with-serde-json")]
+pub use self::serde_wrapper::SerdeWrapper;
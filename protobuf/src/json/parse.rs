@@ -7,14 +7,20 @@ use std::num::ParseIntError;
 use super::base64;
 use super::float;
 use super::rfc_3339;
+use crate::clear::Clear;
 use crate::enums::ProtobufEnum;
 use crate::json::base64::FromBase64Error;
+use crate::json::well_known::has_special_json_form;
+use crate::json::well_known::is_null_value;
 use crate::json::well_known_wrapper::WellKnownWrapper;
 use crate::message::Message;
 use crate::message_dyn::MessageDyn;
+use crate::reflect::DynamicMessage;
 use crate::reflect::EnumDescriptor;
 use crate::reflect::EnumValueDescriptor;
+use crate::reflect::ExtensionRegistry;
 use crate::reflect::FieldDescriptor;
+use crate::reflect::FileDescriptorPool;
 use crate::reflect::MessageDescriptor;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::RuntimeFieldType;
@@ -45,6 +51,8 @@ use crate::well_known_types::Timestamp;
 use crate::well_known_types::UInt32Value;
 use crate::well_known_types::UInt64Value;
 use crate::well_known_types::Value;
+use crate::well_known_types_util::well_known_type_descriptor;
+use crate::ProtobufError;
 
 #[derive(Debug)]
 enum ParseErrorWithoutLocInner {
@@ -62,8 +70,10 @@ enum ParseErrorWithoutLocInner {
     ExpectingStrOrInt,
     ExpectingNumber,
     UnexpectedToken,
-    AnyParsingIsNotImplemented,
+    AnyTypeUrlMissing,
+    AnyTypeNotFound(String),
     MessageNotInitialized,
+    ProtobufError(ProtobufError),
 }
 
 /// JSON parse error.
@@ -95,12 +105,16 @@ impl fmt::Display for ParseErrorWithoutLoc {
             }
             ParseErrorWithoutLocInner::ExpectingNumber => write!(f, "expecting number"),
             ParseErrorWithoutLocInner::UnexpectedToken => write!(f, "unexpected token"),
-            ParseErrorWithoutLocInner::AnyParsingIsNotImplemented => {
-                write!(f, "Any parsing is not implemented")
+            ParseErrorWithoutLocInner::AnyTypeUrlMissing => {
+                write!(f, "Any object has no @type field")
+            }
+            ParseErrorWithoutLocInner::AnyTypeNotFound(type_url) => {
+                write!(f, "message type of Any not found: {}", type_url)
             }
             ParseErrorWithoutLocInner::MessageNotInitialized => {
                 write!(f, "Message not initialized")
             }
+            ParseErrorWithoutLocInner::ProtobufError(e) => write!(f, "{}", e),
         }
     }
 }
@@ -131,6 +145,12 @@ impl From<ParseFloatError> for ParseErrorWithoutLoc {
     }
 }
 
+impl From<ProtobufError> for ParseErrorWithoutLoc {
+    fn from(e: ProtobufError) -> Self {
+        ParseErrorWithoutLoc(ParseErrorWithoutLocInner::ProtobufError(e))
+    }
+}
+
 impl From<rfc_3339::Rfc3339ParseError> for ParseErrorWithoutLoc {
     fn from(e: rfc_3339::Rfc3339ParseError) -> Self {
         ParseErrorWithoutLoc(ParseErrorWithoutLocInner::Rfc3339(e))
@@ -158,7 +178,7 @@ type ParseResult<A> = Result<A, ParseError>;
 #[derive(Clone)]
 struct Parser<'a> {
     tokenizer: Tokenizer<'a>,
-    parse_options: ParseOptions,
+    parse_options: &'a ParseOptions,
 }
 
 trait FromJsonNumber: PartialEq + Sized {
@@ -393,8 +413,10 @@ impl<'a> Parser<'a> {
         &mut self,
         descriptor: &EnumDescriptor,
     ) -> ParseResultWithoutLoc<EnumValueDescriptor> {
-        if descriptor.is::<NullValue>() {
-            return Ok(self.read_wk_null_value()?.descriptor());
+        if is_null_value(descriptor) {
+            let null_value = self.read_wk_null_value()?;
+            // Descriptor may be dynamic, so do not use generated enum descriptor
+            return Ok(descriptor.get_value_by_number(null_value.value()).unwrap());
         }
 
         if self.tokenizer.lookahead_is_str_lit()? {
@@ -466,6 +488,17 @@ impl<'a> Parser<'a> {
         field: &FieldDescriptor,
         t: &RuntimeTypeBox,
     ) -> ParseResultWithoutLoc<()> {
+        // `null` means default value, so the field is not set,
+        // except for `google.protobuf.Value` and `NullValue`, where `null` is a value
+        let null_is_value = match t {
+            RuntimeTypeBox::Message(m) => m.full_name() == "google.protobuf.Value",
+            RuntimeTypeBox::Enum(e) => is_null_value(e),
+            _ => false,
+        };
+        if !null_is_value && self.tokenizer.next_ident_if_eq("null")? {
+            return Ok(());
+        }
+
         field.set_singular_field(message, self.read_value(t)?);
         Ok(())
     }
@@ -631,7 +664,82 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Merge value of a field with given JSON or protobuf name, `:` is already consumed.
+    fn merge_field_by_name(
+        &mut self,
+        message: &mut dyn MessageDyn,
+        descriptor: &MessageDescriptor,
+        field_name: String,
+    ) -> ParseResultWithoutLoc<()> {
+        if field_name.starts_with('[') && field_name.ends_with(']') {
+            return self.merge_extension_by_name(message, descriptor, field_name);
+        }
+        // Proto3 JSON parsers are required to accept both
+        // the converted `lowerCamelCase` name and the proto field name.
+        match descriptor.get_field_by_name_or_json_name(&field_name) {
+            Some(field) => self.merge_field(message, &field),
+            None if self.parse_options.ignore_unknown_fields => self.skip_json_value(),
+            None => Err(ParseErrorWithoutLoc(
+                ParseErrorWithoutLocInner::UnknownFieldName(field_name),
+            )),
+        }
+    }
+
+    /// Merge value of an extension with `"[full.name]"` field name
+    /// found in the extension registry, `:` is already consumed.
+    fn merge_extension_by_name(
+        &mut self,
+        message: &mut dyn MessageDyn,
+        descriptor: &MessageDescriptor,
+        field_name: String,
+    ) -> ParseResultWithoutLoc<()> {
+        let name = &field_name[1..field_name.len() - 1];
+        let extension = self
+            .parse_options
+            .extension_registry
+            .as_ref()
+            .and_then(|r| r.find_extension_by_name(descriptor.full_name(), name))
+            .cloned();
+        let extension = match extension {
+            Some(extension) => extension,
+            None if self.parse_options.ignore_unknown_fields => return self.skip_json_value(),
+            None => {
+                return Err(ParseErrorWithoutLoc(
+                    ParseErrorWithoutLocInner::UnknownFieldName(field_name),
+                ))
+            }
+        };
+        let mut values = Vec::new();
+        match extension.runtime_field_type() {
+            RuntimeFieldType::Repeated(t) => self.read_list(|s| {
+                values.push(s.read_value(&t)?);
+                Ok(())
+            })?,
+            RuntimeFieldType::Singular(t) => {
+                if !self.tokenizer.next_ident_if_eq("null")? {
+                    values.push(self.read_value(&t)?);
+                }
+            }
+            RuntimeFieldType::Map(..) => unreachable!(),
+        }
+        message.set_extension_dyn(&extension, values);
+        Ok(())
+    }
+
     fn merge_inner(&mut self, message: &mut dyn MessageDyn) -> ParseResultWithoutLoc<()> {
+        let descriptor = message.descriptor_dyn();
+        if descriptor.is_dynamic() && has_special_json_form(descriptor.full_name()) {
+            // Parse dynamic well known type like generated one
+            if let Some(generated) = well_known_type_descriptor(descriptor.full_name()) {
+                let mut converted = generated.new_instance();
+                converted.merge_from_bytes_dyn(&message.write_to_bytes_dyn()?)?;
+                self.merge_inner(&mut *converted)?;
+                DynamicMessage::downcast_mut(message).clear();
+                message.merge_from_bytes_dyn(&converted.write_to_bytes_dyn()?)?;
+                return Ok(());
+            }
+        }
+
         if let Some(duration) = message.downcast_mut() {
             return self.merge_wk_duration(duration);
         }
@@ -696,8 +804,6 @@ impl<'a> Parser<'a> {
             return self.merge_wk_struct(value);
         }
 
-        let descriptor = message.descriptor_dyn();
-
         self.tokenizer.next_symbol_expect_eq('{')?;
         let mut first = true;
         while !self.tokenizer.next_symbol_if_eq('}')? {
@@ -707,23 +813,8 @@ impl<'a> Parser<'a> {
             first = false;
 
             let field_name = self.read_string()?;
-            // Proto3 JSON parsers are required to accept both
-            // the converted `lowerCamelCase` name and the proto field name.
-            match descriptor.get_field_by_name_or_json_name(&field_name) {
-                Some(field) => {
-                    self.tokenizer.next_symbol_expect_eq(':')?;
-                    self.merge_field(message, &field)?;
-                }
-                None if self.parse_options.ignore_unknown_fields => {
-                    self.tokenizer.next_symbol_expect_eq(':')?;
-                    self.skip_json_value()?;
-                }
-                None => {
-                    return Err(ParseErrorWithoutLoc(
-                        ParseErrorWithoutLocInner::UnknownFieldName(field_name),
-                    ))
-                }
-            };
+            self.tokenizer.next_symbol_expect_eq(':')?;
+            self.merge_field_by_name(message, &descriptor, field_name)?;
         }
         Ok(())
     }
@@ -853,10 +944,77 @@ impl<'a> Parser<'a> {
         Ok(())
     }
 
-    fn merge_wk_any(&mut self, _value: &mut Any) -> ParseResultWithoutLoc<()> {
-        Err(ParseErrorWithoutLoc(
-            ParseErrorWithoutLocInner::AnyParsingIsNotImplemented,
-        ))
+    /// Read `@type` field of `Any` object, skipping other fields.
+    fn read_any_type_url(&mut self) -> ParseResultWithoutLoc<Option<String>> {
+        let mut type_url = None;
+        self.read_map(
+            |_, k| Ok(k),
+            |s, k| {
+                if k == "@type" {
+                    type_url = Some(s.read_string()?);
+                } else {
+                    s.skip_json_value()?;
+                }
+                Ok(())
+            },
+        )?;
+        Ok(type_url)
+    }
+
+    fn merge_wk_any(&mut self, any: &mut Any) -> ParseResultWithoutLoc<()> {
+        // `@type` is not necessarily the first field,
+        // so find it first with a copy of the parser.
+        let type_url = match self.clone().read_any_type_url()? {
+            Some(type_url) => type_url,
+            None => {
+                // Empty object is default `Any`
+                self.tokenizer.next_symbol_expect_eq('{')?;
+                self.tokenizer.next_symbol_expect_eq('}').map_err(|_| {
+                    ParseErrorWithoutLoc(ParseErrorWithoutLocInner::AnyTypeUrlMissing)
+                })?;
+                return Ok(());
+            }
+        };
+        let descriptor = match Any::find_type(&type_url, self.parse_options.type_pool.as_ref()) {
+            Some(descriptor) => descriptor,
+            None => {
+                return Err(ParseErrorWithoutLoc(
+                    ParseErrorWithoutLocInner::AnyTypeNotFound(type_url),
+                ))
+            }
+        };
+        // Types with special JSON form are stored under `value` key
+        let special = has_special_json_form(descriptor.full_name());
+
+        let mut message = descriptor.new_instance();
+        self.tokenizer.next_symbol_expect_eq('{')?;
+        let mut first = true;
+        while !self.tokenizer.next_symbol_if_eq('}')? {
+            if !first {
+                self.tokenizer.next_symbol_expect_eq(',')?;
+            }
+            first = false;
+
+            let field_name = self.read_string()?;
+            self.tokenizer.next_symbol_expect_eq(':')?;
+            if field_name == "@type" {
+                self.read_string()?;
+            } else if !special {
+                self.merge_field_by_name(&mut *message, &descriptor, field_name)?;
+            } else if field_name == "value" {
+                self.merge_inner(&mut *message)?;
+            } else if self.parse_options.ignore_unknown_fields {
+                self.skip_json_value()?;
+            } else {
+                return Err(ParseErrorWithoutLoc(
+                    ParseErrorWithoutLocInner::UnknownFieldName(field_name),
+                ));
+            }
+        }
+
+        any.type_url = type_url;
+        any.value = message.write_to_bytes_dyn()?;
+        Ok(())
     }
 
     fn read_wk_value(&mut self) -> ParseResultWithoutLoc<Value> {
@@ -894,6 +1052,16 @@ pub struct ParseOptions {
     /// When `true` fields with unknown names are ignored.
     /// When `false` parser returns an error on unknown field.
     pub ignore_unknown_fields: bool,
+    /// Pool used to find message types of `google.protobuf.Any` values.
+    ///
+    /// Well known types are found without the pool.
+    /// Parsing `Any` of a type which cannot be found is an error.
+    pub type_pool: Option<FileDescriptorPool>,
+    /// Extensions parsed from `"[full.name]"` fields, as printed by
+    /// [`print_to_string_with_options`](crate::json::print_to_string_with_options).
+    ///
+    /// Extensions not found in the registry are unknown fields.
+    pub extension_registry: Option<ExtensionRegistry>,
     /// Prevent initializing `ParseOptions` enumerating all field.
     pub _future_options: (),
 }
@@ -906,7 +1074,7 @@ pub fn merge_from_str_with_options(
 ) -> ParseResult<()> {
     let mut parser = Parser {
         tokenizer: Tokenizer::new(json, ParserLanguage::Json),
-        parse_options: parse_options.clone(),
+        parse_options,
     };
     parser.merge(message)
 }
@@ -6,10 +6,15 @@ use std::fmt::Write as fmt_Write;
 use crate::json::base64;
 use crate::json::float;
 use crate::json::rfc_3339::TmUtc;
+use crate::json::well_known::has_special_json_form;
+use crate::json::well_known::is_null_value;
 use crate::json::well_known_wrapper::WellKnownWrapper;
 use crate::message_dyn::MessageDyn;
+use crate::reflect::EnumAliasName;
 use crate::reflect::EnumDescriptor;
 use crate::reflect::EnumValueDescriptor;
+use crate::reflect::ExtensionRegistry;
+use crate::reflect::FileDescriptorPool;
 use crate::reflect::MessageRef;
 use crate::reflect::ReflectFieldRef;
 use crate::reflect::ReflectMapRef;
@@ -35,11 +40,17 @@ use crate::well_known_types::Timestamp;
 use crate::well_known_types::UInt32Value;
 use crate::well_known_types::UInt64Value;
 use crate::well_known_types::Value;
+use crate::well_known_types_util::well_known_type_descriptor;
+use crate::ProtobufError;
+
+/// Printed (as JSON string) instead of messages nested deeper than [`PrintOptions::max_depth`].
+pub const MAX_DEPTH_PLACEHOLDER: &str = "<max depth exceeded>";
 
 #[derive(Debug)]
 enum PrintErrorInner {
     Fmt(fmt::Error),
-    AnyPrintingIsNotImplemented,
+    Protobuf(ProtobufError),
+    AnyTypeNotFound(String),
     TimestampNegativeNanos,
 }
 
@@ -47,17 +58,40 @@ enum PrintErrorInner {
 #[derive(Debug)]
 pub struct PrintError(PrintErrorInner);
 
+impl fmt::Display for PrintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            PrintErrorInner::Fmt(e) => write!(f, "{}", e),
+            PrintErrorInner::Protobuf(e) => write!(f, "{}", e),
+            PrintErrorInner::AnyTypeNotFound(type_url) => {
+                write!(f, "message type of Any not found: {}", type_url)
+            }
+            PrintErrorInner::TimestampNegativeNanos => write!(f, "timestamp with negative nanos"),
+        }
+    }
+}
+
+impl std::error::Error for PrintError {}
+
 impl From<fmt::Error> for PrintError {
     fn from(e: fmt::Error) -> Self {
         PrintError(PrintErrorInner::Fmt(e))
     }
 }
 
+impl From<ProtobufError> for PrintError {
+    fn from(e: ProtobufError) -> Self {
+        PrintError(PrintErrorInner::Protobuf(e))
+    }
+}
+
 pub type PrintResult<T> = Result<T, PrintError>;
 
-struct Printer {
+struct Printer<'a> {
     buf: String,
-    print_options: PrintOptions,
+    print_options: &'a PrintOptions,
+    /// Number of messages currently being printed.
+    depth: usize,
 }
 
 trait PrintableToJson {
@@ -154,21 +188,26 @@ impl PrintableToJson for bool {
     }
 }
 
+/// Append string as quoted JSON string.
+pub(crate) fn quote_json_string_to(s: &str, buf: &mut String) {
+    buf.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            c if c.is_control() => write!(buf, "\\u{:04x}", c as u32).unwrap(),
+            c => buf.push(c),
+        }
+    }
+    buf.push('"');
+}
+
 impl PrintableToJson for str {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        write!(w.buf, "\"")?;
-        for c in self.chars() {
-            match c {
-                '"' => write!(w.buf, "\\\""),
-                '\\' => write!(w.buf, "\\\\"),
-                '\n' => write!(w.buf, "\\n"),
-                '\r' => write!(w.buf, "\\r"),
-                '\t' => write!(w.buf, "\\t"),
-                c if c.is_control() => write!(w.buf, "\\u{:04x}", c as u32),
-                c => write!(w.buf, "{}", c),
-            }?;
-        }
-        write!(w.buf, "\"")?;
+        quote_json_string_to(self, &mut w.buf);
         Ok(())
     }
 }
@@ -212,7 +251,11 @@ impl<'a> PrintableToJson for ReflectValueRef<'a> {
 
 impl PrintableToJson for Duration {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        let sign = if self.seconds >= 0 { "" } else { "-" };
+        let sign = if self.seconds < 0 || self.nanos < 0 {
+            "-"
+        } else {
+            ""
+        };
         Ok(write!(
             w.buf,
             "\"{}{}.{:09}s\"",
@@ -240,8 +283,8 @@ impl PrintableToJson for FieldMask {
 }
 
 impl PrintableToJson for Any {
-    fn print_to_json(&self, _w: &mut Printer) -> PrintResult<()> {
-        Err(PrintError(PrintErrorInner::AnyPrintingIsNotImplemented))
+    fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
+        w.print_any(self)
     }
 }
 
@@ -257,21 +300,31 @@ impl PrintableToJson for Value {
             Some(value::Kind::bool_value(b)) => w.print_printable(&b),
             Some(value::Kind::number_value(n)) => w.print_printable(&n),
             Some(value::Kind::string_value(ref s)) => w.print_printable::<String>(&s),
-            Some(value::Kind::struct_value(ref s)) => w.print_printable(&s),
-            Some(value::Kind::list_value(ref l)) => w.print_printable(&l),
+            Some(value::Kind::struct_value(ref s)) => w.print_nested(|w| w.print_printable(&s)),
+            Some(value::Kind::list_value(ref l)) => w.print_nested(|w| w.print_printable(&l)),
         }
     }
 }
 
 impl PrintableToJson for ListValue {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        w.print_list(&self.values)
+        w.print_list(self.values.iter().map(Nested))
     }
 }
 
 impl PrintableToJson for Struct {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        w.print_object(&self.fields)
+        w.print_object(self.fields.iter().map(|(k, v)| (k, Nested(v))))
+    }
+}
+
+/// Message printed as a value of a list or an object,
+/// counted as a nesting level like message fields are.
+struct Nested<P>(P);
+
+impl<P: PrintableToJson> PrintableToJson for Nested<P> {
+    fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
+        w.print_nested(|w| w.print_printable(&self.0))
     }
 }
 
@@ -334,7 +387,7 @@ impl<'a, O: ObjectKey> ObjectKey for &'a O {
     }
 }
 
-impl Printer {
+impl<'a> Printer<'a> {
     fn print_comma_but_first(&mut self, first: &mut bool) -> fmt::Result {
         if *first {
             *first = false;
@@ -396,8 +449,8 @@ impl Printer {
     }
 
     fn print_enum_known(&mut self, value: &EnumValueDescriptor) -> PrintResult<()> {
-        if let Some(null_value) = value.cast() {
-            self.print_wk_null_value(&null_value)
+        if is_null_value(value.enum_descriptor()) {
+            self.print_wk_null_value(&NullValue::NULL_VALUE)
         } else {
             if self.print_options.enum_values_int {
                 self.print_printable(&value.value())
@@ -411,14 +464,44 @@ impl Printer {
         if self.print_options.enum_values_int {
             self.print_printable(&v)
         } else {
-            match descriptor.get_value_by_number(v) {
+            match descriptor.get_value_by_number_with_alias(v, self.print_options.enum_alias_name) {
                 Some(value) => self.print_enum_known(&value),
                 None => self.print_printable(&v),
             }
         }
     }
 
+    /// Print a nested value unless max depth is reached.
+    fn print_nested<F>(&mut self, print: F) -> PrintResult<()>
+    where
+        F: FnOnce(&mut Printer) -> PrintResult<()>,
+    {
+        if let Some(max_depth) = self.print_options.max_depth {
+            if self.depth > max_depth {
+                return self.print_printable(MAX_DEPTH_PLACEHOLDER);
+            }
+        }
+        self.depth += 1;
+        let r = print(self);
+        self.depth -= 1;
+        r
+    }
+
     fn print_message(&mut self, message: &MessageRef) -> PrintResult<()> {
+        self.print_nested(|w| w.print_message_impl(message))
+    }
+
+    fn print_message_impl(&mut self, message: &MessageRef) -> PrintResult<()> {
+        let descriptor = message.descriptor_dyn();
+        if descriptor.is_dynamic() && has_special_json_form(descriptor.full_name()) {
+            // Print dynamic well known type like generated one
+            if let Some(generated) = well_known_type_descriptor(descriptor.full_name()) {
+                let mut converted = generated.new_instance();
+                converted.merge_from_bytes_dyn(&message.write_to_bytes_dyn()?)?;
+                return self.print_message_impl(&MessageRef::from(&*converted));
+            }
+        }
+
         if let Some(duration) = message.downcast_ref::<Duration>() {
             self.print_printable(duration)
         } else if let Some(timestamp) = message.downcast_ref::<Timestamp>() {
@@ -457,10 +540,16 @@ impl Printer {
     }
 
     fn print_regular_message(&mut self, message: &MessageRef) -> Result<(), PrintError> {
-        let descriptor = message.descriptor_dyn();
-
         write!(self.buf, "{{")?;
         let mut first = true;
+        self.print_message_fields(message, &mut first)?;
+        write!(self.buf, "}}")?;
+        Ok(())
+    }
+
+    /// Print fields of a message without enclosing braces.
+    fn print_message_fields(&mut self, message: &MessageRef, first: &mut bool) -> PrintResult<()> {
+        let descriptor = message.descriptor_dyn();
         for field in descriptor.fields() {
             let json_field_name = if self.print_options.proto_field_name {
                 field.get_name()
@@ -485,33 +574,81 @@ impl Printer {
 
                         if !is_message && !is_oneof {
                             let v = field.get_singular_field_or_default(&**message);
-                            self.print_comma_but_first(&mut first)?;
+                            self.print_comma_but_first(first)?;
                             write!(self.buf, "\"{}\": ", json_field_name)?;
                             self.print_printable(&v)?;
                         }
                     }
                 }
                 ReflectFieldRef::Optional(Some(v)) => {
-                    self.print_comma_but_first(&mut first)?;
+                    self.print_comma_but_first(first)?;
                     write!(self.buf, "\"{}\": ", json_field_name)?;
                     self.print_printable(&v)?;
                 }
                 ReflectFieldRef::Repeated(v) => {
                     if !v.is_empty() || self.print_options.always_output_default_values {
-                        self.print_comma_but_first(&mut first)?;
+                        self.print_comma_but_first(first)?;
                         write!(self.buf, "\"{}\": ", json_field_name)?;
                         self.print_repeated(&v)?;
                     }
                 }
                 ReflectFieldRef::Map(v) => {
                     if !v.is_empty() || self.print_options.always_output_default_values {
-                        self.print_comma_but_first(&mut first)?;
+                        self.print_comma_but_first(first)?;
                         write!(self.buf, "\"{}\": ", json_field_name)?;
                         self.print_map(&v)?;
                     }
                 }
             }
         }
+        self.print_extensions(message, first)
+    }
+
+    /// Print set extensions of a message as `"[full.name]": value` fields.
+    fn print_extensions(&mut self, message: &MessageRef, first: &mut bool) -> PrintResult<()> {
+        let print_options = self.print_options;
+        let empty_registry = ExtensionRegistry::new();
+        let registry = print_options
+            .extension_registry
+            .as_ref()
+            .unwrap_or(&empty_registry);
+        for (e, values) in message.extensions(registry) {
+            self.print_comma_but_first(first)?;
+            write!(self.buf, "\"[{}]\": ", e.full_name())?;
+            match e.runtime_field_type() {
+                RuntimeFieldType::Repeated(..) => {
+                    self.print_list(values.iter().map(|v| v.as_value_ref()))?
+                }
+                _ => self.print_printable(&values[0].as_value_ref())?,
+            }
+        }
+        Ok(())
+    }
+
+    fn print_any(&mut self, any: &Any) -> PrintResult<()> {
+        let descriptor = match Any::find_type(&any.type_url, self.print_options.type_pool.as_ref())
+        {
+            Some(descriptor) => descriptor,
+            None => {
+                return Err(PrintError(PrintErrorInner::AnyTypeNotFound(
+                    any.type_url.clone(),
+                )))
+            }
+        };
+        let mut message = descriptor.new_instance();
+        message.merge_from_bytes_dyn(&any.value)?;
+        let message = MessageRef::from(&*message);
+
+        write!(self.buf, "{{\"@type\": ")?;
+        self.print_printable(&any.type_url)?;
+        if has_special_json_form(descriptor.full_name()) {
+            write!(self.buf, ", \"value\": ")?;
+            // Packed message is printed at the level of `Any`
+            self.print_message_impl(&message)?;
+        } else {
+            let mut first = false;
+            self.print_message_fields(&message, &mut first)?;
+        }
         write!(self.buf, "}}")?;
         Ok(())
     }
@@ -546,11 +683,35 @@ pub struct PrintOptions {
     ///
     /// Note both string or int can be parsed.
     pub enum_values_int: bool,
+    /// Name printed for enum numbers with several names (`allow_alias` option).
+    pub enum_alias_name: EnumAliasName,
     /// Use protobuf field names instead of `lowerCamelCase` which is used by default.
     /// Note both names are supported when JSON is parsed.
     pub proto_field_name: bool,
     /// Output field default values.
     pub always_output_default_values: bool,
+    /// Maximum number of levels of messages nested in the printed message.
+    ///
+    /// Messages nested deeper are printed as [`MAX_DEPTH_PLACEHOLDER`] string
+    /// instead of being traversed. Levels are counted the same way as
+    /// in [`text_format::PrintOptions::max_depth`](crate::text_format::PrintOptions::max_depth):
+    /// each message is a level, including `google.protobuf.Value`, `ListValue`
+    /// and `Struct`, even though they are not printed as JSON objects.
+    /// Map entries and messages packed in `Any` are not separate levels.
+    /// `None` means unlimited.
+    pub max_depth: Option<usize>,
+    /// Pool used to find message types of `google.protobuf.Any` values.
+    ///
+    /// Well known types are found without the pool.
+    /// Printing `Any` of a type which cannot be found is an error.
+    pub type_pool: Option<FileDescriptorPool>,
+    /// Extensions printed as `"[full.name]"` fields.
+    ///
+    /// Generated messages store extensions in unknown fields, which are not
+    /// printed, so extensions are omitted unless they are found in the registry.
+    /// Extensions decoded when parsing dynamic messages are printed
+    /// even without the registry.
+    pub extension_registry: Option<ExtensionRegistry>,
     /// Prevent initializing `PrintOptions` enumerating all field.
     pub _future_options: (),
 }
@@ -562,7 +723,8 @@ pub fn print_to_string_with_options(
 ) -> PrintResult<String> {
     let mut printer = Printer {
         buf: String::new(),
-        print_options: print_options.clone(),
+        print_options,
+        depth: 0,
     };
     printer.print_message(&MessageRef::from(message))?;
     Ok(printer.buf)
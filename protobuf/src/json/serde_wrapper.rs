@@ -0,0 +1,141 @@
+use serde::de;
+use serde::de::DeserializeSeed;
+use serde::ser;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+
+use crate::json::parse_dynamic_from_str;
+use crate::json::print_to_string;
+use crate::message_dyn::MessageDyn;
+use crate::reflect::MessageDescriptor;
+
+/// Serialize message with serde using proto3 JSON representation.
+///
+/// This allows passing dynamic messages to serde-based serializers.
+/// Message is printed with [`print_to_string`] and the resulting JSON
+/// is fed to the serializer.
+///
+/// ```
+/// # use protobuf::json::SerdeWrapper;
+/// # use protobuf::MessageDyn;
+/// # fn foo(message: &dyn MessageDyn) -> serde_json::Result<String> {
+/// serde_json::to_string(&SerdeWrapper(message))
+/// # }
+/// ```
+pub struct SerdeWrapper<T>(pub T);
+
+impl Serialize for SerdeWrapper<&dyn MessageDyn> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let json = print_to_string(self.0).map_err(ser::Error::custom)?;
+        let value: serde_json::Value = serde_json::from_str(&json).map_err(ser::Error::custom)?;
+        value.serialize(serializer)
+    }
+}
+
+/// Deserialize message of given type with serde from proto3 JSON representation.
+///
+/// Deserialized value is converted to JSON and parsed with
+/// [`parse_dynamic_from_str`](crate::json::parse_dynamic_from_str).
+///
+/// ```
+/// # use protobuf::json::MessageSeed;
+/// # use protobuf::reflect::MessageDescriptor;
+/// # use serde::de::DeserializeSeed;
+/// # fn foo(descriptor: MessageDescriptor, json: &str) -> serde_json::Result<()> {
+/// let mut deserializer = serde_json::Deserializer::from_str(json);
+/// let message = MessageSeed::new(descriptor).deserialize(&mut deserializer)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct MessageSeed {
+    descriptor: MessageDescriptor,
+}
+
+impl MessageSeed {
+    /// Seed to deserialize messages of given type.
+    pub fn new(descriptor: MessageDescriptor) -> MessageSeed {
+        MessageSeed { descriptor }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for MessageSeed {
+    type Value = Box<dyn MessageDyn>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        parse_dynamic_from_str(&self.descriptor, &value.to_string()).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::de::DeserializeSeed;
+
+    use super::MessageSeed;
+    use super::SerdeWrapper;
+    use crate::descriptor::file_descriptor;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::reflect::FileDescriptor;
+    use crate::Message;
+    use crate::MessageDyn;
+
+    fn file() -> FileDescriptorProto {
+        let mut file = FileDescriptorProto::new();
+        file.set_name("a.proto".to_owned());
+        file.public_dependency = vec![1, 2];
+        file.options
+            .mut_or_default()
+            .set_java_package("j".to_owned());
+        file
+    }
+
+    #[test]
+    fn serialize() {
+        let json = serde_json::to_value(&SerdeWrapper(&file() as &dyn MessageDyn)).unwrap();
+        assert_eq!(
+            serde_json::json!({
+                "name": "a.proto",
+                "publicDependency": [1, 2],
+                "options": {"javaPackage": "j"},
+            }),
+            json
+        );
+    }
+
+    #[test]
+    fn round_trip_dynamic() {
+        let dynamic = FileDescriptor::new_dynamic(file_descriptor().proto().clone(), Vec::new());
+        let d = dynamic
+            .message_by_package_relative_name("FileDescriptorProto")
+            .unwrap();
+
+        let json = serde_json::to_string(&SerdeWrapper(&file() as &dyn MessageDyn)).unwrap();
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let message = MessageSeed::new(d).deserialize(&mut deserializer).unwrap();
+        assert_eq!(
+            file(),
+            FileDescriptorProto::parse_from_bytes(&message.write_to_bytes_dyn().unwrap()).unwrap()
+        );
+
+        let json = serde_json::to_string(&SerdeWrapper(&*message)).unwrap();
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let message = MessageSeed::new(FileDescriptorProto::descriptor_static())
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(
+            &file(),
+            message.downcast_ref::<FileDescriptorProto>().unwrap()
+        );
+    }
+
+    #[test]
+    fn deserialize_error() {
+        let mut deserializer = serde_json::Deserializer::from_str(r#"{"unknown": 1}"#);
+        assert!(MessageSeed::new(FileDescriptorProto::descriptor_static())
+            .deserialize(&mut deserializer)
+            .is_err());
+    }
+}
@@ -0,0 +1,34 @@
+use crate::reflect::EnumDescriptor;
+
+/// Well known types which have JSON representation other than regular JSON object.
+const SPECIAL_JSON_TYPES: &[&str] = &[
+    "google.protobuf.Any",
+    "google.protobuf.Duration",
+    "google.protobuf.Timestamp",
+    "google.protobuf.FieldMask",
+    "google.protobuf.Value",
+    "google.protobuf.ListValue",
+    "google.protobuf.Struct",
+    "google.protobuf.DoubleValue",
+    "google.protobuf.FloatValue",
+    "google.protobuf.Int64Value",
+    "google.protobuf.UInt64Value",
+    "google.protobuf.Int32Value",
+    "google.protobuf.UInt32Value",
+    "google.protobuf.BoolValue",
+    "google.protobuf.StringValue",
+    "google.protobuf.BytesValue",
+];
+
+/// Message with given full name is printed and parsed not as JSON object with fields.
+///
+/// `google.protobuf.Any` is an object, but it is listed here because
+/// when packed into another `Any` it is printed under `"value"` key.
+pub(crate) fn has_special_json_form(full_name: &str) -> bool {
+    SPECIAL_JSON_TYPES.contains(&full_name)
+}
+
+/// `google.protobuf.NullValue` enum, generated or dynamic, which is printed as JSON `null`.
+pub(crate) fn is_null_value(descriptor: &EnumDescriptor) -> bool {
+    descriptor.full_name() == "google.protobuf.NullValue"
+}
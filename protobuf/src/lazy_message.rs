@@ -0,0 +1,354 @@
+use std::fmt;
+use std::option;
+use std::sync::OnceLock;
+
+use crate::Message;
+use crate::ProtobufResult;
+
+/// Message field which is parsed on first access.
+///
+/// When a message is parsed, the field stores the serialized bytes of the
+/// sub-message without parsing them. The bytes are parsed when the field
+/// is accessed for the first time, and if the field is not modified,
+/// serialization writes the original bytes without serializing the message.
+///
+/// Fields are generated with this type when the field has `[lazy = true]`
+/// option and `lazy_message_fields` codegen option is enabled.
+///
+/// ```
+/// # use protobuf::descriptor::FileDescriptorProto;
+/// # use protobuf::LazyMessage;
+/// # use protobuf::Message;
+/// let mut file = FileDescriptorProto::new();
+/// file.set_name("a.proto".to_owned());
+/// let bytes = file.write_to_bytes().unwrap();
+///
+/// let mut lazy = LazyMessage::<FileDescriptorProto>::from_bytes(bytes.clone());
+/// assert!(!lazy.is_parsed());
+/// assert_eq!("a.proto", lazy.get_or_default().get_name());
+/// assert!(lazy.is_parsed());
+/// // Original bytes are kept until the message is modified
+/// assert_eq!(Some(&bytes[..]), lazy.raw_bytes());
+///
+/// lazy.mut_or_default().set_package("p".to_owned());
+/// assert_eq!(None, lazy.raw_bytes());
+/// ```
+///
+/// If the bytes cannot be parsed, accessor functions like
+/// [`as_ref`](LazyMessage::as_ref) return default instance of the message,
+/// use [`try_get`](LazyMessage::try_get) to get the error.
+pub struct LazyMessage<M> {
+    // Serialized message, `None` if the field is not set or if the message was modified
+    bytes: Option<Vec<u8>>,
+    message: OnceLock<Box<M>>,
+}
+
+impl<M> LazyMessage<M> {
+    /// Construct an empty field.
+    #[inline]
+    pub const fn none() -> LazyMessage<M> {
+        LazyMessage {
+            bytes: None,
+            message: OnceLock::new(),
+        }
+    }
+
+    /// Construct field from given message.
+    #[inline]
+    pub fn some(message: M) -> LazyMessage<M> {
+        LazyMessage {
+            bytes: None,
+            message: OnceLock::from(Box::new(message)),
+        }
+    }
+
+    /// Construct field from serialized message, which will be parsed on first access.
+    #[inline]
+    pub fn from_bytes(bytes: Vec<u8>) -> LazyMessage<M> {
+        LazyMessage {
+            bytes: Some(bytes),
+            message: OnceLock::new(),
+        }
+    }
+
+    /// True iff the field is set.
+    #[inline]
+    pub fn is_some(&self) -> bool {
+        self.bytes.is_some() || self.message.get().is_some()
+    }
+
+    /// True iff the field is not set.
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        !self.is_some()
+    }
+
+    /// True iff the field is not set or the message is already parsed.
+    #[inline]
+    pub fn is_parsed(&self) -> bool {
+        self.bytes.is_none() || self.message.get().is_some()
+    }
+
+    /// Serialized message, `None` if the field is not set or the message was modified
+    /// after parsing.
+    #[inline]
+    pub fn raw_bytes(&self) -> Option<&[u8]> {
+        self.bytes.as_deref()
+    }
+
+    /// Clear the field.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = LazyMessage::none();
+    }
+
+    /// Replace the field value with given message.
+    #[inline]
+    pub fn set(&mut self, message: M) {
+        *self = LazyMessage::some(message);
+    }
+}
+
+impl<M: Message> LazyMessage<M> {
+    /// Parse the message if it is not parsed yet and return it.
+    ///
+    /// Parse error is not cached, so failed parse is retried on next access.
+    pub fn try_get(&self) -> ProtobufResult<Option<&M>> {
+        if let Some(m) = self.message.get() {
+            return Ok(Some(m));
+        }
+        match self.bytes {
+            Some(ref bytes) => {
+                let m = M::parse_from_bytes(bytes)?;
+                Ok(Some(self.message.get_or_init(|| Box::new(m))))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// View data as reference option, parsing the message if needed.
+    ///
+    /// Default instance is returned if the message cannot be parsed.
+    pub fn as_ref(&self) -> Option<&M> {
+        self.try_get()
+            .unwrap_or_else(|_| Some(M::default_instance()))
+    }
+
+    /// View data as mutable reference option, parsing the message if needed.
+    ///
+    /// Original bytes are discarded, so the message will be serialized
+    /// on write. Default message is used if the message cannot be parsed.
+    pub fn as_mut(&mut self) -> Option<&mut M> {
+        if let Some(bytes) = self.bytes.take() {
+            if self.message.get().is_none() {
+                let m = M::parse_from_bytes(&bytes).unwrap_or_else(|_| M::new());
+                self.message = OnceLock::from(Box::new(m));
+            }
+        }
+        self.message.get_mut().map(|m| &mut **m)
+    }
+
+    /// Get a reference to contained value or a default instance.
+    pub fn get_or_default(&self) -> &M {
+        self.as_ref().unwrap_or_else(|| M::default_instance())
+    }
+
+    /// Get a mutable reference to contained value, initialize if not initialized yet.
+    pub fn mut_or_default(&mut self) -> &mut M {
+        if self.is_none() {
+            return self.set_default();
+        }
+        self.as_mut().unwrap()
+    }
+
+    /// Set the field to default message.
+    pub fn set_default(&mut self) -> &mut M {
+        *self = LazyMessage::some(M::new());
+        self.as_mut().unwrap()
+    }
+
+    /// Take data as option, leaving this field empty.
+    pub fn take(&mut self) -> Option<M> {
+        self.as_mut();
+        self.message.take().map(|m| *m)
+    }
+
+    /// Convert into `Option<M>`.
+    pub fn into_option(mut self) -> Option<M> {
+        self.take()
+    }
+
+    /// View data as iterator.
+    pub fn iter(&self) -> option::IntoIter<&M> {
+        self.as_ref().into_iter()
+    }
+
+    /// Check if the message has all required fields set.
+    ///
+    /// Not yet parsed message is assumed to be initialized:
+    /// required fields are checked when the message is parsed.
+    pub fn is_initialized(&self) -> bool {
+        match self.message.get() {
+            Some(m) => m.is_initialized(),
+            None => true,
+        }
+    }
+
+    /// Size of serialized message, without tag and length.
+    ///
+    /// Original bytes length is returned if the message was not modified,
+    /// otherwise the message size is computed and cached.
+    pub(crate) fn compute_size(&self) -> Option<u32> {
+        match self.bytes {
+            Some(ref bytes) => Some(bytes.len() as u32),
+            None => self.message.get().map(|m| m.compute_size()),
+        }
+    }
+
+    pub(crate) fn message(&self) -> Option<&M> {
+        self.message.get().map(|m| &**m)
+    }
+}
+
+impl<M> Default for LazyMessage<M> {
+    #[inline]
+    fn default() -> LazyMessage<M> {
+        LazyMessage::none()
+    }
+}
+
+impl<M: Clone> Clone for LazyMessage<M> {
+    fn clone(&self) -> LazyMessage<M> {
+        LazyMessage {
+            bytes: self.bytes.clone(),
+            message: self.message.clone(),
+        }
+    }
+}
+
+impl<M: Message + PartialEq> PartialEq for LazyMessage<M> {
+    fn eq(&self, other: &LazyMessage<M>) -> bool {
+        match (&self.bytes, &other.bytes) {
+            (Some(a), Some(b)) if a == b => true,
+            _ => self.as_ref() == other.as_ref(),
+        }
+    }
+}
+
+impl<M: Message + fmt::Debug> fmt::Debug for LazyMessage<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LazyMessage").field(&self.as_ref()).finish()
+    }
+}
+
+impl<M> From<Option<M>> for LazyMessage<M> {
+    fn from(o: Option<M>) -> Self {
+        match o {
+            Some(m) => LazyMessage::some(m),
+            None => LazyMessage::none(),
+        }
+    }
+}
+
+impl<'a, M: Message> IntoIterator for &'a LazyMessage<M> {
+    type Item = &'a M;
+    type IntoIter = option::IntoIter<&'a M>;
+
+    fn into_iter(self) -> option::IntoIter<&'a M> {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "with-serde")]
+impl<M: Message + serde::Serialize> serde::Serialize for LazyMessage<M> {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> Result<<S as serde::Serializer>::Ok, <S as serde::Serializer>::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "with-serde")]
+impl<'de, M: serde::Deserialize<'de>> serde::Deserialize<'de> for LazyMessage<M> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as serde::Deserializer<'de>>::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Option::deserialize(deserializer).map(LazyMessage::from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+
+    fn file() -> FileDescriptorProto {
+        let mut file = FileDescriptorProto::new();
+        file.set_name("a.proto".to_owned());
+        file.message_type.push(DescriptorProto::new());
+        file
+    }
+
+    #[test]
+    fn parse_on_access() {
+        let bytes = file().write_to_bytes().unwrap();
+        let lazy = LazyMessage::<FileDescriptorProto>::from_bytes(bytes.clone());
+        assert!(lazy.is_some());
+        assert!(!lazy.is_parsed());
+        assert_eq!(Some(file()), lazy.as_ref().cloned());
+        assert!(lazy.is_parsed());
+        assert_eq!(Some(&bytes[..]), lazy.raw_bytes());
+        assert_eq!(Some(bytes.len() as u32), lazy.compute_size());
+    }
+
+    #[test]
+    fn modify() {
+        let bytes = file().write_to_bytes().unwrap();
+        let mut lazy = LazyMessage::<FileDescriptorProto>::from_bytes(bytes);
+        lazy.mut_or_default().set_package("p".to_owned());
+        assert!(lazy.is_parsed());
+        assert_eq!(None, lazy.raw_bytes());
+
+        let mut expected = file();
+        expected.set_package("p".to_owned());
+        assert_eq!(Some(expected.compute_size()), lazy.compute_size());
+        assert_eq!(Some(expected), lazy.take());
+        assert!(lazy.is_none());
+    }
+
+    #[test]
+    fn none() {
+        let mut lazy = LazyMessage::<FileDescriptorProto>::none();
+        assert!(lazy.is_none());
+        assert!(lazy.is_parsed());
+        assert_eq!(None, lazy.as_ref());
+        assert_eq!(None, lazy.compute_size());
+        assert!(lazy.mut_or_default().get_name().is_empty());
+        assert!(lazy.is_some());
+    }
+
+    #[test]
+    fn parse_error() {
+        let lazy = LazyMessage::<FileDescriptorProto>::from_bytes(vec![0x0a]);
+        assert!(lazy.try_get().is_err());
+        assert_eq!(Some(FileDescriptorProto::default_instance()), lazy.as_ref());
+        assert!(!lazy.is_parsed());
+    }
+
+    #[test]
+    fn eq() {
+        let bytes = file().write_to_bytes().unwrap();
+        let a = LazyMessage::<FileDescriptorProto>::from_bytes(bytes.clone());
+        let b = LazyMessage::from_bytes(bytes);
+        assert_eq!(a, b);
+        assert!(!a.is_parsed());
+        assert_eq!(a, LazyMessage::some(file()));
+        assert_ne!(a, LazyMessage::none());
+    }
+}
@@ -14,26 +14,37 @@ extern crate serde;
 #[macro_use]
 #[cfg(feature = "with-serde")]
 extern crate serde_derive;
+pub use crate::allocation_monitor::AllocationMonitor;
 pub use crate::clear::Clear;
 pub use crate::coded_input_stream::CodedInputStream;
+pub use crate::coded_input_stream::InputLimits;
 pub use crate::coded_output_stream::CodedOutputStream;
+pub use crate::enum_bit_set::ProtobufEnumBitSet;
+pub use crate::enums::ParseEnumError;
 pub use crate::enums::ProtobufEnum;
 pub use crate::enums::ProtobufEnumOrUnknown;
+pub use crate::lazy_message::LazyMessage;
+pub use crate::message::concat_serialized;
 pub use crate::message::Message;
 pub use crate::message_dyn::MessageDyn;
 pub use crate::message_field::MessageField;
 pub use crate::oneof::Oneof;
+pub use crate::parse_stats::ParseStats;
+pub use crate::serialize_scratch::SerializeScratch;
 pub use crate::unknown::UnknownFields;
 pub use crate::unknown::UnknownFieldsIter;
 pub use crate::unknown::UnknownValue;
 pub use crate::unknown::UnknownValueRef;
 pub use crate::unknown::UnknownValues;
 pub use crate::unknown::UnknownValuesIter;
+pub mod wire;
 pub mod wire_format;
+pub mod wire_scan;
 #[cfg(feature = "bytes")]
 pub use crate::chars::Chars;
 pub use crate::error::ProtobufError;
 pub use crate::error::ProtobufResult;
+pub use crate::well_known_types_util::TimeConversionError;
 
 // generated
 pub mod descriptor;
@@ -41,21 +52,34 @@ pub mod descriptor;
 pub mod plugin;
 pub mod rustproto;
 
+mod allocation_monitor;
 mod clear;
 mod coded_input_stream;
 mod coded_output_stream;
+#[cfg(feature = "with-zstd")]
+pub mod compression;
+pub mod corpus;
+pub mod describe;
+mod enum_bit_set;
 mod enums;
 mod error;
 pub mod ext;
+pub mod framing;
 pub mod json;
+mod lazy_message;
 mod lazy_v2;
 mod message;
 mod message_dyn;
 mod message_field;
+pub mod message_set;
 mod oneof;
+mod parse_stats;
 pub mod reflect;
 pub mod rt;
+pub mod stream;
+mod serialize_scratch;
 pub mod text_format;
+pub mod transcode;
 pub mod well_known_types;
 mod well_known_types_util;
 
@@ -1,4 +1,6 @@
 use std::fmt;
+use std::hash::Hasher;
+use std::io;
 use std::io::Read;
 use std::io::Write;
 
@@ -12,9 +14,97 @@ use crate::coded_output_stream::WithCodedOutputStream;
 use crate::error::ProtobufError;
 use crate::error::ProtobufResult;
 use crate::message_dyn::MessageDyn;
+use crate::reflect::initialization;
 use crate::reflect::reflect_eq::ReflectEqMode;
+use crate::reflect::FieldPath;
 use crate::reflect::MessageDescriptor;
+use crate::serialize_scratch::SerializeScratch;
 use crate::unknown::UnknownFields;
+use crate::wire_scan;
+
+/// Maximum size of serialized message allowed by protobuf specification.
+pub(crate) const MESSAGE_SIZE_LIMIT: u32 = i32::MAX as u32;
+
+/// Return error if computed message size exceeds protobuf limit.
+///
+/// Sizes are saturated on overflow, so this check also catches
+/// messages which size does not fit in `u32`.
+pub(crate) fn check_message_size(size: u32) -> ProtobufResult<u32> {
+    if size > MESSAGE_SIZE_LIMIT {
+        Err(ProtobufError::MessageTooLarge)
+    } else {
+        Ok(size)
+    }
+}
+
+/// Append exactly `size` bytes written by `write` to the vec.
+///
+/// Vec is left unchanged on error, including when `write` writes
+/// a different number of bytes than `size` (e.g. cached sizes are stale).
+pub(crate) fn write_to_vec_with_size(
+    v: &mut Vec<u8>,
+    size: u32,
+    write: impl FnOnce(&mut CodedOutputStream) -> ProtobufResult<()>,
+) -> ProtobufResult<()> {
+    let size = size as usize;
+    let start = v.len();
+    v.reserve_exact(size);
+    v.resize(start + size, 0);
+    let r = {
+        let mut os = CodedOutputStream::bytes(&mut v[start..]);
+        write(&mut os).map(|()| os.bytes_written())
+    };
+    match r {
+        Ok(written) if written == size => Ok(()),
+        Ok(written) => {
+            v.truncate(start);
+            debug_assert_eq!(written, size, "written size differs from computed size");
+            Err(ProtobufError::IoError(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "written size differs from computed size",
+            )))
+        }
+        Err(e) => {
+            v.truncate(start);
+            Err(e)
+        }
+    }
+}
+
+/// Concatenate serialized messages.
+///
+/// Parsing the result is equivalent to parsing the first message and then
+/// merging the rest into it with [`Message::merge_from_bytes`] in order.
+///
+/// Buffer for the result is allocated once. Parts are not validated.
+pub fn concat_serialized(parts: &[&[u8]]) -> Vec<u8> {
+    let len = parts.iter().map(|p| p.len()).sum();
+    let mut r = Vec::with_capacity(len);
+    for part in parts {
+        r.extend_from_slice(part);
+    }
+    r
+}
+
+/// Length of the shortest prefix of serialized message which contains
+/// each of given fields, or length of the whole message
+/// if some of the fields are not present.
+pub(crate) fn prefix_len_until_fields(
+    bytes: &[u8],
+    field_numbers: &[u32],
+) -> ProtobufResult<usize> {
+    let mut remaining: Vec<u32> = field_numbers.to_vec();
+    let mut len = 0;
+    for field in wire_scan::scan_fields(bytes) {
+        if remaining.is_empty() {
+            break;
+        }
+        let field = field?;
+        remaining.retain(|&n| n != field.field_number);
+        len = field.field.end;
+    }
+    Ok(len)
+}
 
 /// Trait implemented for all generated structs for protobuf messages.
 ///
@@ -62,15 +152,27 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
     /// Write message to the stream.
     ///
     /// Sizes of this messages and nested messages must be cached
-    /// by calling `compute_size` prior to this call.
+    /// by calling `compute_size` prior to this call, and the message must not be
+    /// modified after that (see [`CachedSize`](crate::rt::CachedSize)),
+    /// otherwise incorrect length prefixes of nested messages are written.
     fn write_to_with_cached_sizes(&self, os: &mut CodedOutputStream) -> ProtobufResult<()>;
 
     /// Compute and cache size of this message and all nested messages
     fn compute_size(&self) -> u32;
 
     /// Get size previously computed by `compute_size`.
+    ///
+    /// Returned value is stale if the message was modified after `compute_size` call.
     fn get_cached_size(&self) -> u32;
 
+    /// Recompute cached sizes of this message and all nested messages.
+    ///
+    /// Should be called after the message is modified (e. g. through reflection)
+    /// and before `write_to_with_cached_sizes`.
+    fn refresh_cached_sizes(&self) {
+        self.compute_size();
+    }
+
     /// Write the message to the stream.
     ///
     /// Results in error if message is not fully initialized.
@@ -78,7 +180,7 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
         self.check_initialized()?;
 
         // cache sizes
-        self.compute_size();
+        check_message_size(self.compute_size())?;
         // TODO: reserve additional
         self.write_to_with_cached_sizes(os)?;
 
@@ -88,7 +190,7 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
     /// Write the message to the stream prepending the message with message length
     /// encoded as varint.
     fn write_length_delimited_to(&self, os: &mut CodedOutputStream) -> ProtobufResult<()> {
-        let size = self.compute_size();
+        let size = check_message_size(self.compute_size())?;
         os.write_raw_varint32(size)?;
         self.write_to_with_cached_sizes(os)?;
 
@@ -106,12 +208,46 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
         Ok(())
     }
 
-    /// Update this message object with fields read from given stream.
+    /// Update this message object with fields read from given bytes.
+    ///
+    /// Protobuf wire format is concatenation-friendly: merging serialized
+    /// message into a message is the same as parsing concatenation
+    /// of both serialized messages. Singular scalar fields present in `bytes`
+    /// overwrite current values, singular message fields are merged recursively,
+    /// and repeated and map fields are appended to.
+    ///
+    /// So calling this function for several byte chunks is equivalent
+    /// to calling it once for [`concat_serialized`](crate::concat_serialized)
+    /// of these chunks.
     fn merge_from_bytes(&mut self, bytes: &[u8]) -> ProtobufResult<()> {
         let mut is = CodedInputStream::from_bytes(bytes);
         self.merge_from(&mut is)
     }
 
+    /// Parse message from the beginning of given bytes until each of given fields is read.
+    ///
+    /// Returns partially parsed message and the rest of bytes which were not read.
+    /// This is useful to route a message by header fields which are
+    /// by convention serialized first, without parsing the whole message.
+    ///
+    /// Merging the rest into the returned message with [`Message::merge_from_bytes`]
+    /// is equivalent to parsing the whole message. Note the rest may contain
+    /// another occurrence of the header field which overrides the value read.
+    ///
+    /// Required fields are not checked.
+    fn parse_from_bytes_until_fields<'b>(
+        bytes: &'b [u8],
+        field_numbers: &[u32],
+    ) -> ProtobufResult<(Self, &'b [u8])>
+    where
+        Self: Sized,
+    {
+        let len = prefix_len_until_fields(bytes, field_numbers)?;
+        let mut m = Self::new();
+        m.merge_from_bytes(&bytes[..len])?;
+        Ok((m, &bytes[len..]))
+    }
+
     /// Parse message from reader.
     /// Parse stops on EOF or when error encountered.
     fn parse_from_reader(reader: &mut dyn Read) -> ProtobufResult<Self>
@@ -149,21 +285,45 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
     }
 
     /// Check if all required fields of this object are initialized.
+    ///
+    /// Error contains paths to missing fields.
     fn check_initialized(&self) -> ProtobufResult<()> {
         if !self.is_initialized() {
             Err(ProtobufError::MessageNotInitialized(
-                self.descriptor_by_instance().name().to_owned(),
+                initialization::not_initialized_message(self),
             ))
         } else {
             Ok(())
         }
     }
 
+    /// Find all required fields which are not set in this message
+    /// or in nested messages.
+    ///
+    /// Returned list is empty iff message is initialized.
+    /// This function uses reflection, so it is slower than [`Message::is_initialized`].
+    fn find_initialization_errors(&self) -> Vec<FieldPath> {
+        initialization::find_initialization_errors(self)
+    }
+
     /// Write the message to the writer.
     fn write_to_writer(&self, w: &mut dyn Write) -> ProtobufResult<()> {
         w.with_coded_output_stream(|os| self.write_to(os))
     }
 
+    /// Write the message to the writer using output buffer from the scratch.
+    ///
+    /// See [`SerializeScratch`].
+    fn write_to_writer_with_scratch(
+        &self,
+        w: &mut dyn Write,
+        scratch: &mut SerializeScratch,
+    ) -> ProtobufResult<()> {
+        let mut os = CodedOutputStream::with_scratch(w, scratch);
+        self.write_to(&mut os)?;
+        os.flush()
+    }
+
     /// Write the message to bytes vec.
     fn write_to_vec(&self, v: &mut Vec<u8>) -> ProtobufResult<()> {
         v.with_coded_output_stream(|os| self.write_to(os))
@@ -174,20 +334,23 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
     /// > **Note**: You can use [`Message::parse_from_bytes`]
     /// to do the reverse.
     fn write_to_bytes(&self) -> ProtobufResult<Vec<u8>> {
+        let mut v = Vec::new();
+        self.write_to_vec_with_capacity(&mut v)?;
+        Ok(v)
+    }
+
+    /// Append the message to the vec.
+    ///
+    /// Message size is computed once, and exactly that many bytes are reserved
+    /// in the vec before serialization, so the vec is reallocated at most once.
+    ///
+    /// Results in error if message is not fully initialized.
+    /// The vec is left unchanged on error.
+    fn write_to_vec_with_capacity(&self, v: &mut Vec<u8>) -> ProtobufResult<()> {
         self.check_initialized()?;
 
-        let size = self.compute_size() as usize;
-        let mut v = Vec::with_capacity(size);
-        // skip zerofill
-        unsafe {
-            v.set_len(size);
-        }
-        {
-            let mut os = CodedOutputStream::bytes(&mut v);
-            self.write_to_with_cached_sizes(&mut os)?;
-            os.check_eof();
-        }
-        Ok(v)
+        let size = check_message_size(self.compute_size())?;
+        write_to_vec_with_size(v, size, |os| self.write_to_with_cached_sizes(os))
     }
 
     /// Write the message to the writer, prepend the message with message length
@@ -196,6 +359,20 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
         w.with_coded_output_stream(|os| self.write_length_delimited_to(os))
     }
 
+    /// Write the message to the writer using output buffer from the scratch,
+    /// prepend the message with message length encoded as varint.
+    ///
+    /// See [`SerializeScratch`].
+    fn write_length_delimited_to_writer_with_scratch(
+        &self,
+        w: &mut dyn Write,
+        scratch: &mut SerializeScratch,
+    ) -> ProtobufResult<()> {
+        let mut os = CodedOutputStream::with_scratch(w, scratch);
+        self.write_length_delimited_to(&mut os)?;
+        os.flush()
+    }
+
     /// Write the message to the bytes vec, prepend the message with message length
     /// encoded as varint.
     fn write_length_delimited_to_bytes(&self) -> ProtobufResult<Vec<u8>> {
@@ -237,4 +414,40 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
     fn reflect_eq(&self, other: &Self, mode: &ReflectEqMode) -> bool {
         <dyn MessageDyn>::reflect_eq_dyn(self, other, mode)
     }
+
+    /// Reflective hash, consistent with [`reflect_eq`](Message::reflect_eq).
+    ///
+    /// # See also
+    ///
+    /// [`dyn Message::reflect_hash_dyn()`], `dyn` version of this function.
+    fn reflect_hash<H: Hasher>(&self, state: &mut H) {
+        <dyn MessageDyn>::reflect_hash_dyn(self, state)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::write_to_vec_with_size;
+
+    #[test]
+    fn write_to_vec_with_size_too_long() {
+        let mut v = vec![1, 2];
+        assert!(write_to_vec_with_size(&mut v, 1, |os| os.write_raw_bytes(&[3, 4])).is_err());
+        assert_eq!(vec![1, 2], v);
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn write_to_vec_with_size_too_short() {
+        let mut v = vec![1, 2];
+        assert!(write_to_vec_with_size(&mut v, 3, |os| os.write_raw_bytes(&[3, 4])).is_err());
+        assert_eq!(vec![1, 2], v);
+    }
+
+    #[test]
+    fn write_to_vec_with_size_exact() {
+        let mut v = vec![1, 2];
+        write_to_vec_with_size(&mut v, 2, |os| os.write_raw_bytes(&[3, 4])).unwrap();
+        assert_eq!(vec![1, 2, 3, 4], v);
+    }
 }
@@ -1,16 +1,36 @@
 use std::any::Any;
 use std::any::TypeId;
 use std::fmt;
+use std::hash::Hasher;
 use std::io::Write;
 
 use crate::coded_output_stream::WithCodedOutputStream;
+use crate::message::check_message_size;
+use crate::message::prefix_len_until_fields;
+use crate::message::write_to_vec_with_size;
+use crate::reflect::custom_options::decode_extension;
+use crate::reflect::dynamic::extension::set_extension_in_unknown_fields;
+use crate::reflect::initialization;
+use crate::reflect::merge;
+use crate::reflect::pool_clone;
+use crate::reflect::reflect_hash;
+use crate::reflect::DynamicMessage;
+use crate::reflect::ExtensionDescriptor;
+use crate::reflect::ExtensionRegistry;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::FieldPath;
+use crate::reflect::FileDescriptor;
 use crate::reflect::MessageDescriptor;
+use crate::reflect::OneofDescriptor;
 use crate::reflect::ReflectEqMode;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::RuntimeFieldType;
 use crate::CodedInputStream;
 use crate::CodedOutputStream;
 use crate::Message;
 use crate::ProtobufError;
 use crate::ProtobufResult;
+use crate::SerializeScratch;
 use crate::UnknownFields;
 
 /// Dynamic-dispatch version of [`Message`].
@@ -27,6 +47,9 @@ pub trait MessageDyn: Any + fmt::Debug + Send + Sync + 'static {
     /// Compute (and cache) the message size.
     fn compute_size_dyn(&self) -> u32;
 
+    /// Get size previously computed by `compute_size_dyn`.
+    fn get_cached_size_dyn(&self) -> u32;
+
     /// True iff all required fields are initialized.
     /// Always returns `true` for protobuf 3.
     fn is_initialized_dyn(&self) -> bool;
@@ -54,6 +77,10 @@ impl<M: Message> MessageDyn for M {
         self.compute_size()
     }
 
+    fn get_cached_size_dyn(&self) -> u32 {
+        self.get_cached_size()
+    }
+
     fn is_initialized_dyn(&self) -> bool {
         self.is_initialized()
     }
@@ -69,21 +96,194 @@ impl<M: Message> MessageDyn for M {
 
 impl dyn MessageDyn {
     /// Check if all required fields of this object are initialized.
+    ///
+    /// Error contains paths to missing fields.
     pub fn check_initialized_dyn(&self) -> ProtobufResult<()> {
         if !self.is_initialized_dyn() {
             Err(ProtobufError::MessageNotInitialized(
-                self.descriptor_dyn().name().to_owned(),
+                initialization::not_initialized_message(self),
             ))
         } else {
             Ok(())
         }
     }
 
+    /// Deep clone the message into a message of the same type from another set of files.
+    ///
+    /// This is useful after descriptors are reloaded: returned message tree
+    /// references only descriptors from `pool`, so the old descriptors
+    /// can be released. Messages of generated types can be cloned into
+    /// dynamic messages and vice versa.
+    ///
+    /// Message type is found in `pool` by full name, fields are matched
+    /// by number, and must have the same names and types.
+    /// Unknown fields are copied.
+    ///
+    /// Return `None` if `pool` does not contain message type with the same name,
+    /// or if that message type or any nested message type has different fields.
+    pub fn clone_into_pool(&self, pool: &[FileDescriptor]) -> Option<Box<dyn MessageDyn>> {
+        pool_clone::clone_into_pool(self, pool)
+    }
+
+    /// Merge fields of `other` message into this message via reflection.
+    ///
+    /// Protobuf merge semantics is used: set singular fields overwrite fields
+    /// of this message, singular message fields are merged recursively,
+    /// repeated fields are concatenated and map entries are inserted.
+    /// Unknown fields are appended.
+    ///
+    /// Messages may have different descriptors of the same message type,
+    /// so generated message can be merged into dynamic message and vice versa.
+    ///
+    /// # Panics
+    ///
+    /// If messages are of different types, or if descriptors of the same type
+    /// have different fields.
+    ///
+    /// This function is not named `merge_from_dyn`, because
+    /// [`MessageDyn::merge_from_dyn`] merges a message from a stream.
+    #[doc(alias = "merge_from_dyn")]
+    pub fn merge_from_message_dyn(&mut self, other: &dyn MessageDyn) {
+        merge::merge_from_message(self, other)
+    }
+
+    /// Find all required fields which are not set in this message
+    /// or in nested messages.
+    ///
+    /// Returned list is empty iff message is initialized.
+    pub fn find_initialization_errors_dyn(&self) -> Vec<FieldPath> {
+        initialization::find_initialization_errors(self)
+    }
+
+    /// Field of given oneof which is set in this message, if any.
+    ///
+    /// # Panics
+    ///
+    /// If oneof belongs to a different message type.
+    pub fn which_oneof(&self, oneof: &OneofDescriptor) -> Option<FieldDescriptor> {
+        assert_eq!(oneof.containing_message(), &self.descriptor_dyn());
+        oneof.fields().find(|f| f.has_field(self))
+    }
+
+    /// Values of extension field: at most one value for singular extension,
+    /// all elements for repeated extension.
+    ///
+    /// Extensions decoded with
+    /// [`parse_from_bytes_with_extensions`](MessageDescriptor::parse_from_bytes_with_extensions)
+    /// are returned as is, other extensions are decoded from unknown fields.
+    ///
+    /// # Panics
+    ///
+    /// If the extension does not extend this message type.
+    pub fn get_extension_dyn(&self, extension: &ExtensionDescriptor) -> Vec<ReflectValueBox> {
+        assert_eq!(extension.extendee_name(), self.descriptor_dyn().full_name());
+        if let Some(m) = self.downcast_ref::<DynamicMessage>() {
+            if let Some(values) = m.get_extension(extension) {
+                return values.to_vec();
+            }
+        }
+        let mut values = decode_extension(extension, self.get_unknown_fields_dyn());
+        if let RuntimeFieldType::Singular(..) = extension.runtime_field_type() {
+            values = values.pop().into_iter().collect();
+        }
+        values
+    }
+
+    /// Set extension fields of this message with their values, sorted by field number.
+    ///
+    /// Extensions decoded with
+    /// [`parse_from_bytes_with_extensions`](MessageDescriptor::parse_from_bytes_with_extensions)
+    /// are always returned. Extensions stored in unknown fields, which includes
+    /// all extensions of generated messages, are returned if they are found
+    /// in the registry, other unknown fields are not extensions as far as
+    /// this function is concerned. Values with wire type not matching
+    /// the extension type are skipped, as in [`get_extension_dyn`](Self::get_extension_dyn).
+    pub fn extensions(
+        &self,
+        registry: &ExtensionRegistry,
+    ) -> Vec<(ExtensionDescriptor, Vec<ReflectValueBox>)> {
+        let mut r: Vec<(ExtensionDescriptor, Vec<ReflectValueBox>)> = Vec::new();
+        if let Some(m) = self.downcast_ref::<DynamicMessage>() {
+            r.extend(
+                m.decoded_extensions()
+                    .map(|(e, values)| (e.clone(), values.to_vec())),
+            );
+        }
+        let descriptor = self.descriptor_dyn();
+        let unknown_fields = self.get_unknown_fields_dyn();
+        let numbers = unknown_fields
+            .iter()
+            .map(|(number, _)| number)
+            .chain(unknown_fields.iter_groups().map(|(number, _)| number));
+        for number in numbers {
+            if r.iter().any(|(e, _)| e.number() == number) {
+                continue;
+            }
+            if let Some(e) = registry.find_extension(descriptor.full_name(), number) {
+                let values = self.get_extension_dyn(e);
+                if !values.is_empty() {
+                    r.push((e.clone(), values));
+                }
+            }
+        }
+        r.sort_by_key(|(e, _)| e.number());
+        r
+    }
+
+    /// Replace values of extension field, empty `values` clears the extension.
+    ///
+    /// Dynamic messages store extension values decoded,
+    /// generated messages store them encoded in unknown fields.
+    ///
+    /// # Panics
+    ///
+    /// If the extension does not extend this message type, value type
+    /// does not match extension type or more than one value
+    /// is given for singular extension.
+    pub fn set_extension_dyn(
+        &mut self,
+        extension: &ExtensionDescriptor,
+        values: Vec<ReflectValueBox>,
+    ) {
+        assert_eq!(extension.extendee_name(), self.descriptor_dyn().full_name());
+        if let RuntimeFieldType::Singular(..) = extension.runtime_field_type() {
+            assert!(
+                values.len() <= 1,
+                "more than one value for singular extension {}",
+                extension.full_name()
+            );
+        }
+        match self.downcast_mut::<DynamicMessage>() {
+            Some(m) => m.set_extension(extension, values),
+            None => {
+                set_extension_in_unknown_fields(extension, values, self.mut_unknown_fields_dyn())
+            }
+        }
+    }
+
     /// Write the message to the writer.
+    ///
+    /// Output is buffered internally in a reusable 8 KiB buffer, so the writer
+    /// gets one write per 8 KiB of output, and need not be wrapped in `BufWriter`.
+    /// Writer is not inspected, so a writer which is already buffered gets the same
+    /// writes (`BufWriter` passes writes of its buffer size through without copying).
     pub fn write_to_writer_dyn(&self, w: &mut dyn Write) -> ProtobufResult<()> {
         w.with_coded_output_stream(|os| self.write_to_dyn(os))
     }
 
+    /// Write the message to the writer using output buffer from the scratch.
+    ///
+    /// See [`SerializeScratch`].
+    pub fn write_to_writer_with_scratch_dyn(
+        &self,
+        w: &mut dyn Write,
+        scratch: &mut SerializeScratch,
+    ) -> ProtobufResult<()> {
+        let mut os = CodedOutputStream::with_scratch(w, scratch);
+        self.write_to_dyn(&mut os)?;
+        os.flush()
+    }
+
     /// Write the message to bytes vec.
     pub fn write_to_vec_dyn(&self, v: &mut Vec<u8>) -> ProtobufResult<()> {
         v.with_coded_output_stream(|os| self.write_to_dyn(os))
@@ -96,7 +296,7 @@ impl dyn MessageDyn {
         self.check_initialized_dyn()?;
 
         // cache sizes
-        self.compute_size_dyn();
+        check_message_size(self.compute_size_dyn())?;
         // TODO: reserve additional
         self.write_to_with_cached_sizes_dyn(os)?;
 
@@ -118,31 +318,56 @@ impl dyn MessageDyn {
         self.merge_from_dyn(&mut is)
     }
 
+    /// Update this message object with fields read from the beginning of given bytes
+    /// until each of given fields is read, and return the rest of bytes.
+    ///
+    /// See [`Message::parse_from_bytes_until_fields`].
+    pub fn merge_from_bytes_until_fields_dyn<'b>(
+        &mut self,
+        bytes: &'b [u8],
+        field_numbers: &[u32],
+    ) -> ProtobufResult<&'b [u8]> {
+        let len = prefix_len_until_fields(bytes, field_numbers)?;
+        self.merge_from_bytes_dyn(&bytes[..len])?;
+        Ok(&bytes[len..])
+    }
+
+    /// Recompute cached sizes of this message and all nested messages
+    /// after the message is modified through reflection.
+    ///
+    /// See [`Message::refresh_cached_sizes`].
+    pub fn refresh_cached_sizes_dyn(&self) {
+        self.compute_size_dyn();
+    }
+
     /// Write the message to bytes vec.
     ///
     /// > **Note**: You can use [`Message::parse_from_bytes`]
     /// to do the reverse.
     pub fn write_to_bytes_dyn(&self) -> ProtobufResult<Vec<u8>> {
+        let mut v = Vec::new();
+        self.write_to_vec_with_capacity_dyn(&mut v)?;
+        Ok(v)
+    }
+
+    /// Append the message to the vec.
+    ///
+    /// Message size is computed once, and exactly that many bytes are reserved
+    /// in the vec before serialization, so the vec is reallocated at most once.
+    ///
+    /// Results in error if message is not fully initialized.
+    /// The vec is left unchanged on error.
+    pub fn write_to_vec_with_capacity_dyn(&self, v: &mut Vec<u8>) -> ProtobufResult<()> {
         self.check_initialized_dyn()?;
 
-        let size = self.compute_size_dyn() as usize;
-        let mut v = Vec::with_capacity(size);
-        // skip zerofill
-        unsafe {
-            v.set_len(size);
-        }
-        {
-            let mut os = CodedOutputStream::bytes(&mut v);
-            self.write_to_with_cached_sizes_dyn(&mut os)?;
-            os.check_eof();
-        }
-        Ok(v)
+        let size = check_message_size(self.compute_size_dyn())?;
+        write_to_vec_with_size(v, size, |os| self.write_to_with_cached_sizes_dyn(os))
     }
 
     /// Write the message to the stream prepending the message with message length
     /// encoded as varint.
     pub fn write_length_delimited_to_dyn(&self, os: &mut CodedOutputStream) -> ProtobufResult<()> {
-        let size = self.compute_size_dyn();
+        let size = check_message_size(self.compute_size_dyn())?;
         os.write_raw_varint32(size)?;
         self.write_to_with_cached_sizes_dyn(os)?;
 
@@ -225,9 +450,20 @@ impl dyn MessageDyn {
     }
 
     /// Reflectively compare the messages.
+    ///
+    /// Messages of the same type are compared field by field even if they
+    /// have different descriptors, e. g. generated message and dynamic message.
     pub fn reflect_eq_dyn(&self, other: &dyn MessageDyn, mode: &ReflectEqMode) -> bool {
         MessageDescriptor::reflect_eq_maybe_unrelated(self, other, mode)
     }
+
+    /// Reflectively hash the message.
+    ///
+    /// Messages equal by [`reflect_eq_dyn`](Self::reflect_eq_dyn) with any mode
+    /// have equal hashes. Unknown fields are not hashed.
+    pub fn reflect_hash_dyn<H: Hasher>(&self, state: &mut H) {
+        reflect_hash::hash_message(self, state)
+    }
 }
 
 impl Clone for Box<dyn MessageDyn> {
@@ -244,9 +480,54 @@ impl PartialEq for Box<dyn MessageDyn> {
 
 #[cfg(test)]
 mod test {
+    use std::io;
+    use std::io::Write;
+
     use crate::descriptor::FileDescriptorProto;
+    use crate::descriptor::FileOptions;
+    use crate::reflect::ReflectValueBox;
+    use crate::CodedOutputStream;
+    use crate::Message;
     use crate::MessageDyn;
 
+    #[derive(Default)]
+    struct CountingWriter {
+        written: Vec<u8>,
+        writes: usize,
+    }
+
+    impl Write for CountingWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.writes += 1;
+            self.written.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn write_to_writer_buffered() {
+        let mut m = FileDescriptorProto::new();
+        m.dependency = (0..100).map(|i| format!("dep{}.proto", i)).collect();
+
+        let mut w = CountingWriter::default();
+        (&m as &dyn MessageDyn).write_to_writer_dyn(&mut w).unwrap();
+        assert_eq!(1, w.writes);
+        assert_eq!(m.write_to_bytes().unwrap(), w.written);
+
+        m.dependency = (0..2000).map(|i| format!("dep{}.proto", i)).collect();
+        let size = m.compute_size() as usize;
+        assert!(size > 8 * 1024);
+
+        let mut w = CountingWriter::default();
+        (&m as &dyn MessageDyn).write_to_writer_dyn(&mut w).unwrap();
+        assert_eq!(size.div_ceil(8 * 1024), w.writes);
+        assert_eq!(m.write_to_bytes().unwrap(), w.written);
+    }
+
     #[test]
     fn downcast_ref() {
         let m = FileDescriptorProto::new();
@@ -275,4 +556,29 @@ mod test {
         let d: Box<dyn MessageDyn> = Box::new(m);
         let mut _c: Box<FileDescriptorProto> = d.downcast_box().unwrap();
     }
+
+    #[test]
+    fn refresh_cached_sizes_after_reflective_mutation() {
+        let mut m = FileDescriptorProto::new();
+        m.set_name("a.proto".to_owned());
+        let d = &mut m as &mut dyn MessageDyn;
+        d.compute_size_dyn();
+
+        let mut options = FileOptions::new();
+        options.set_java_package("com.example".to_owned());
+        let options_field = d.descriptor_dyn().get_field_by_name("options").unwrap();
+        options_field.set_singular_field(d, ReflectValueBox::Message(Box::new(options)));
+        assert_eq!(9, d.get_cached_size_dyn());
+
+        d.refresh_cached_sizes_dyn();
+        assert_eq!(24, d.get_cached_size_dyn());
+
+        let mut bytes = Vec::new();
+        {
+            let mut os = CodedOutputStream::vec(&mut bytes);
+            d.write_to_with_cached_sizes_dyn(&mut os).unwrap();
+            os.flush().unwrap();
+        }
+        assert_eq!(m, FileDescriptorProto::parse_from_bytes(&bytes).unwrap());
+    }
 }
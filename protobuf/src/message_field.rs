@@ -233,3 +233,39 @@ impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for MessageField<T
         Option::deserialize(deserializer).map(MessageField::from)
     }
 }
+
+#[cfg(feature = "with-rkyv")]
+impl<T: rkyv::Archive> rkyv::Archive for MessageField<T> {
+    type Archived = rkyv::option::ArchivedOption<rkyv::boxed::ArchivedBox<T::Archived>>;
+    type Resolver = <Option<Box<T>> as rkyv::Archive>::Resolver;
+
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        self.0.resolve(pos, resolver, out)
+    }
+}
+
+#[cfg(feature = "with-rkyv")]
+impl<T, S> rkyv::Serialize<S> for MessageField<T>
+where
+    T: rkyv::Archive,
+    Option<Box<T>>: rkyv::Serialize<S>,
+    S: rkyv::Fallible + ?Sized,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "with-rkyv")]
+impl<T, D> rkyv::Deserialize<MessageField<T>, D>
+    for rkyv::option::ArchivedOption<rkyv::boxed::ArchivedBox<T::Archived>>
+where
+    T: rkyv::Archive,
+    T::Archived: rkyv::Deserialize<T, D>,
+    D: rkyv::Fallible + ?Sized,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<MessageField<T>, D::Error> {
+        let o: Option<Box<T>> = rkyv::Deserialize::deserialize(self, deserializer)?;
+        Ok(MessageField(o))
+    }
+}
@@ -0,0 +1,190 @@
+//! Legacy `MessageSet` wire format.
+//!
+//! Messages declared with `option message_set_wire_format = true`
+//! contain only message extensions, and each extension is encoded
+//! as a group:
+//!
+//! ```text
+//! repeated group Item = 1 {
+//!   required int32 type_id = 2;
+//!   required bytes message = 3;
+//! }
+//! ```
+//!
+//! where `type_id` is the extension field number.
+//!
+//! Generated and dynamic messages do not understand this encoding and
+//! skip these groups when parsing. Functions in this module transcode between
+//! `MessageSet` format and regular encoding where each extension is a
+//! length-delimited field, so extensions are available as unknown fields
+//! (and as [`ext`](crate::ext) accessors) and are preserved on round-trip.
+
+use crate::error::ProtobufError;
+use crate::error::ProtobufResult;
+use crate::error::WireError;
+use crate::message_dyn::MessageDyn;
+use crate::reflect::MessageDescriptor;
+use crate::wire_format;
+use crate::CodedInputStream;
+use crate::CodedOutputStream;
+
+const ITEM_FIELD_NUMBER: u32 = 1;
+const TYPE_ID_FIELD_NUMBER: u32 = 2;
+const MESSAGE_FIELD_NUMBER: u32 = 3;
+
+/// Is this message declared with `message_set_wire_format` option.
+pub fn is_message_set(descriptor: &MessageDescriptor) -> bool {
+    descriptor
+        .get_proto()
+        .options
+        .get_or_default()
+        .get_message_set_wire_format()
+}
+
+fn read_item(is: &mut CodedInputStream) -> ProtobufResult<(u32, Vec<u8>)> {
+    let mut type_id = None;
+    let mut message = None;
+    loop {
+        let (field_number, wire_type) = is.read_tag_unpack()?;
+        match (field_number, wire_type) {
+            (ITEM_FIELD_NUMBER, wire_format::WireTypeEndGroup) => break,
+            (TYPE_ID_FIELD_NUMBER, wire_format::WireTypeVarint) => {
+                type_id = Some(is.read_uint32()?);
+            }
+            (MESSAGE_FIELD_NUMBER, wire_format::WireTypeLengthDelimited) => {
+                message = Some(is.read_bytes()?);
+            }
+            (_, wire_format::WireTypeEndGroup) => {
+                return Err(ProtobufError::WireError(WireError::IncorrectMessageSetItem))
+            }
+            (_, wire_type) => is.skip_field(wire_type)?,
+        }
+    }
+    match (type_id, message) {
+        (Some(type_id), Some(message)) if wire_format::is_valid_field_number(type_id) => {
+            Ok((type_id, message))
+        }
+        _ => Err(ProtobufError::WireError(WireError::IncorrectMessageSetItem)),
+    }
+}
+
+/// Convert message serialized in `MessageSet` format to regular encoding.
+///
+/// Each item is converted to a length-delimited field with
+/// `type_id` field number. Fields which are not items are copied as is.
+pub fn message_set_to_regular(bytes: &[u8]) -> ProtobufResult<Vec<u8>> {
+    let mut is = CodedInputStream::from_bytes(bytes);
+    let mut r = Vec::with_capacity(bytes.len());
+    {
+        let mut os = CodedOutputStream::vec(&mut r);
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match (field_number, wire_type) {
+                (ITEM_FIELD_NUMBER, wire_format::WireTypeStartGroup) => {
+                    let (type_id, message) = read_item(&mut is)?;
+                    os.write_bytes(type_id, &message)?;
+                }
+                (_, wire_format::WireTypeStartGroup) | (_, wire_format::WireTypeEndGroup) => {
+                    return Err(ProtobufError::WireError(WireError::UnexpectedWireType(
+                        wire_type,
+                    )))
+                }
+                (_, wire_type) => {
+                    let value = is.read_unknown(wire_type)?;
+                    os.write_unknown(field_number, value.get_ref())?;
+                }
+            }
+        }
+        os.flush()?;
+    }
+    Ok(r)
+}
+
+/// Convert message serialized in regular encoding to `MessageSet` format.
+///
+/// Each length-delimited field is converted to an item.
+/// Other fields cannot be encoded in `MessageSet` format, so error is returned.
+pub fn regular_to_message_set(bytes: &[u8]) -> ProtobufResult<Vec<u8>> {
+    let mut is = CodedInputStream::from_bytes(bytes);
+    let mut r = Vec::with_capacity(bytes.len());
+    {
+        let mut os = CodedOutputStream::vec(&mut r);
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            if wire_type != wire_format::WireTypeLengthDelimited {
+                return Err(ProtobufError::WireError(WireError::UnexpectedWireType(
+                    wire_type,
+                )));
+            }
+            let message = is.read_bytes()?;
+            os.write_tag(ITEM_FIELD_NUMBER, wire_format::WireTypeStartGroup)?;
+            os.write_uint32(TYPE_ID_FIELD_NUMBER, field_number)?;
+            os.write_bytes(MESSAGE_FIELD_NUMBER, &message)?;
+            os.write_tag(ITEM_FIELD_NUMBER, wire_format::WireTypeEndGroup)?;
+        }
+        os.flush()?;
+    }
+    Ok(r)
+}
+
+/// Merge message serialized in `MessageSet` format into given message.
+///
+/// Extensions are stored as unknown fields of the message,
+/// or in regular fields if dynamic message descriptor declares them.
+pub fn merge_from_message_set_bytes(m: &mut dyn MessageDyn, bytes: &[u8]) -> ProtobufResult<()> {
+    m.merge_from_bytes_dyn(&message_set_to_regular(bytes)?)
+}
+
+/// Serialize message in `MessageSet` format.
+///
+/// Message must contain only message-typed fields and extensions.
+pub fn write_to_message_set_bytes(m: &dyn MessageDyn) -> ProtobufResult<Vec<u8>> {
+    regular_to_message_set(&m.write_to_bytes_dyn()?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::FileOptions;
+    use crate::hex::decode_hex;
+    use crate::Message;
+
+    #[test]
+    fn round_trip() {
+        // Item { type_id: 1000, message: "\x08\x01" }
+        let message_set = decode_hex("0b 10 e8 07 1a 02 08 01 0c");
+        let regular = message_set_to_regular(&message_set).unwrap();
+        assert_eq!(decode_hex("c2 3e 02 08 01"), regular);
+        assert_eq!(message_set, regular_to_message_set(&regular).unwrap());
+    }
+
+    #[test]
+    fn message_before_type_id() {
+        let message_set = decode_hex("0b 1a 02 08 01 10 e8 07 0c");
+        let regular = message_set_to_regular(&message_set).unwrap();
+        assert_eq!(decode_hex("c2 3e 02 08 01"), regular);
+    }
+
+    #[test]
+    fn incorrect_item() {
+        // no type_id
+        assert!(message_set_to_regular(&decode_hex("0b 1a 02 08 01 0c")).is_err());
+        // no end group
+        assert!(message_set_to_regular(&decode_hex("0b 10 e8 07 1a 02 08 01")).is_err());
+        // varint field cannot be written as item
+        assert!(regular_to_message_set(&decode_hex("08 01")).is_err());
+    }
+
+    #[test]
+    fn message_dyn() {
+        let message_set = decode_hex("0b 10 e8 07 1a 02 08 01 0c");
+        let mut m = FileOptions::new();
+        merge_from_message_set_bytes(&mut m, &message_set).unwrap();
+        assert_eq!(
+            &[vec![0x08, 0x01]][..],
+            &m.get_unknown_fields().get(1000).unwrap().length_delimited[..]
+        );
+        assert_eq!(message_set, write_to_message_set_bytes(&m).unwrap());
+        assert!(!is_message_set(&FileOptions::descriptor_static()));
+    }
+}
@@ -0,0 +1,51 @@
+/// Statistics of parsed data, collected by
+/// [`CodedInputStream`](crate::CodedInputStream) when enabled with
+/// [`enable_parse_stats`](crate::CodedInputStream::enable_parse_stats).
+///
+/// Growing number of unknown fields usually means that producers
+/// use a newer schema than the consumer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseStats {
+    pub(crate) fields: u64,
+    pub(crate) unknown_fields: u64,
+    pub(crate) unknown_bytes: u64,
+    pub(crate) max_depth: u32,
+}
+
+impl ParseStats {
+    /// Empty statistics.
+    pub fn new() -> ParseStats {
+        ParseStats::default()
+    }
+
+    /// Number of fields read, known or unknown, including fields of nested messages.
+    ///
+    /// Each element of non-packed repeated field is a separate field on the wire.
+    /// Skipped group is a single field, fields inside it are not counted.
+    pub fn fields(&self) -> u64 {
+        self.fields
+    }
+
+    /// Number of fields not found in message type, stored in unknown fields or skipped.
+    pub fn unknown_fields(&self) -> u64 {
+        self.unknown_fields
+    }
+
+    /// Total size of unknown fields values, excluding tags.
+    pub fn unknown_bytes(&self) -> u64 {
+        self.unknown_bytes
+    }
+
+    /// Maximum nesting depth of messages reached; zero if there were no nested messages.
+    pub fn max_depth(&self) -> u32 {
+        self.max_depth
+    }
+
+    /// Add statistics of another parse, e. g. to aggregate statistics of many messages.
+    pub fn merge(&mut self, other: &ParseStats) {
+        self.fields += other.fields;
+        self.unknown_fields += other.unknown_fields;
+        self.unknown_bytes += other.unknown_bytes;
+        self.max_depth = self.max_depth.max(other.max_depth);
+    }
+}
@@ -50,6 +50,18 @@ impl Version {
         ::std::default::Default::default()
     }
 
+    /// Field number of `major` field.
+    pub const FIELD_MAJOR_NUMBER: u32 = 1;
+
+    /// Field number of `minor` field.
+    pub const FIELD_MINOR_NUMBER: u32 = 2;
+
+    /// Field number of `patch` field.
+    pub const FIELD_PATCH_NUMBER: u32 = 3;
+
+    /// Field number of `suffix` field.
+    pub const FIELD_SUFFIX_NUMBER: u32 = 4;
+
     // optional int32 major = 1;
 
     pub fn get_major(&self) -> i32 {
@@ -221,20 +233,21 @@ impl crate::Message for Version {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if let Some(v) = self.major {
-            my_size += crate::rt::value_size(1, v, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(1, v, crate::wire_format::WireTypeVarint) as u64;
         }
         if let Some(v) = self.minor {
-            my_size += crate::rt::value_size(2, v, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(2, v, crate::wire_format::WireTypeVarint) as u64;
         }
         if let Some(v) = self.patch {
-            my_size += crate::rt::value_size(3, v, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(3, v, crate::wire_format::WireTypeVarint) as u64;
         }
         if let Some(v) = self.suffix.as_ref() {
-            my_size += crate::rt::string_size(4, &v);
+            my_size += crate::rt::string_size(4, &v) as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -309,6 +322,22 @@ impl crate::reflect::ProtobufValue for Version {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `Version`
+pub mod version {
+
+    /// Field numbers of message `Version`
+    pub mod field_numbers {
+        /// Field number of `major` field.
+        pub const MAJOR: u32 = 1;
+        /// Field number of `minor` field.
+        pub const MINOR: u32 = 2;
+        /// Field number of `patch` field.
+        pub const PATCH: u32 = 3;
+        /// Field number of `suffix` field.
+        pub const SUFFIX: u32 = 4;
+    }
+}
+
 ///  An encoded CodeGeneratorRequest is written to the plugin's stdin.
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
@@ -355,6 +384,18 @@ impl CodeGeneratorRequest {
         ::std::default::Default::default()
     }
 
+    /// Field number of `file_to_generate` field.
+    pub const FIELD_FILE_TO_GENERATE_NUMBER: u32 = 1;
+
+    /// Field number of `parameter` field.
+    pub const FIELD_PARAMETER_NUMBER: u32 = 2;
+
+    /// Field number of `proto_file` field.
+    pub const FIELD_PROTO_FILE_NUMBER: u32 = 15;
+
+    /// Field number of `compiler_version` field.
+    pub const FIELD_COMPILER_VERSION_NUMBER: u32 = 3;
+
     // optional string parameter = 2;
 
     pub fn get_parameter(&self) -> &str {
@@ -467,22 +508,23 @@ impl crate::Message for CodeGeneratorRequest {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         for value in &self.file_to_generate {
-            my_size += crate::rt::string_size(1, &value);
+            my_size += crate::rt::string_size(1, &value) as u64;
         };
         if let Some(v) = self.parameter.as_ref() {
-            my_size += crate::rt::string_size(2, &v);
+            my_size += crate::rt::string_size(2, &v) as u64;
         }
         for value in &self.proto_file {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         if let Some(v) = self.compiler_version.as_ref() {
             let len = v.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -557,6 +599,22 @@ impl crate::reflect::ProtobufValue for CodeGeneratorRequest {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `CodeGeneratorRequest`
+pub mod code_generator_request {
+
+    /// Field numbers of message `CodeGeneratorRequest`
+    pub mod field_numbers {
+        /// Field number of `file_to_generate` field.
+        pub const FILE_TO_GENERATE: u32 = 1;
+        /// Field number of `parameter` field.
+        pub const PARAMETER: u32 = 2;
+        /// Field number of `proto_file` field.
+        pub const PROTO_FILE: u32 = 15;
+        /// Field number of `compiler_version` field.
+        pub const COMPILER_VERSION: u32 = 3;
+    }
+}
+
 ///  The plugin writes an encoded CodeGeneratorResponse to stdout.
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
@@ -593,6 +651,15 @@ impl CodeGeneratorResponse {
         ::std::default::Default::default()
     }
 
+    /// Field number of `error` field.
+    pub const FIELD_ERROR_NUMBER: u32 = 1;
+
+    /// Field number of `supported_features` field.
+    pub const FIELD_SUPPORTED_FEATURES_NUMBER: u32 = 2;
+
+    /// Field number of `file` field.
+    pub const FIELD_FILE_NUMBER: u32 = 15;
+
     // optional string error = 1;
 
     pub fn get_error(&self) -> &str {
@@ -715,18 +782,19 @@ impl crate::Message for CodeGeneratorResponse {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if let Some(v) = self.error.as_ref() {
-            my_size += crate::rt::string_size(1, &v);
+            my_size += crate::rt::string_size(1, &v) as u64;
         }
         if let Some(v) = self.supported_features {
-            my_size += crate::rt::value_size(2, v, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(2, v, crate::wire_format::WireTypeVarint) as u64;
         }
         for value in &self.file {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -798,6 +866,16 @@ impl crate::reflect::ProtobufValue for CodeGeneratorResponse {
 
 /// Nested message and enums of message `CodeGeneratorResponse`
 pub mod code_generator_response {
+
+    /// Field numbers of message `CodeGeneratorResponse`
+    pub mod field_numbers {
+        /// Field number of `error` field.
+        pub const ERROR: u32 = 1;
+        /// Field number of `supported_features` field.
+        pub const SUPPORTED_FEATURES: u32 = 2;
+        /// Field number of `file` field.
+        pub const FILE: u32 = 15;
+    }
     ///  Represents a single generated file.
     #[derive(PartialEq,Clone,Default)]
     #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
@@ -877,6 +955,18 @@ pub mod code_generator_response {
             ::std::default::Default::default()
         }
 
+        /// Field number of `name` field.
+        pub const FIELD_NAME_NUMBER: u32 = 1;
+
+        /// Field number of `insertion_point` field.
+        pub const FIELD_INSERTION_POINT_NUMBER: u32 = 2;
+
+        /// Field number of `content` field.
+        pub const FIELD_CONTENT_NUMBER: u32 = 15;
+
+        /// Field number of `generated_code_info` field.
+        pub const FIELD_GENERATED_CODE_INFO_NUMBER: u32 = 16;
+
         // optional string name = 1;
 
         pub fn get_name(&self) -> &str {
@@ -1064,21 +1154,22 @@ pub mod code_generator_response {
         // Compute sizes of nested messages
         #[allow(unused_variables)]
         fn compute_size(&self) -> u32 {
-            let mut my_size = 0;
+            let mut my_size: u64 = 0;
             if let Some(v) = self.name.as_ref() {
-                my_size += crate::rt::string_size(1, &v);
+                my_size += crate::rt::string_size(1, &v) as u64;
             }
             if let Some(v) = self.insertion_point.as_ref() {
-                my_size += crate::rt::string_size(2, &v);
+                my_size += crate::rt::string_size(2, &v) as u64;
             }
             if let Some(v) = self.content.as_ref() {
-                my_size += crate::rt::string_size(15, &v);
+                my_size += crate::rt::string_size(15, &v) as u64;
             }
             if let Some(v) = self.generated_code_info.as_ref() {
                 let len = v.compute_size();
-                my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
+                my_size += 2 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
             }
-            my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+            my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+            let my_size = crate::rt::saturating_size(my_size);
             self.cached_size.set(my_size);
             my_size
         }
@@ -1153,6 +1244,22 @@ pub mod code_generator_response {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
     }
 
+    /// Nested message and enums of message `File`
+    pub mod file {
+
+        /// Field numbers of message `File`
+        pub mod field_numbers {
+            /// Field number of `name` field.
+            pub const NAME: u32 = 1;
+            /// Field number of `insertion_point` field.
+            pub const INSERTION_POINT: u32 = 2;
+            /// Field number of `content` field.
+            pub const CONTENT: u32 = 15;
+            /// Field number of `generated_code_info` field.
+            pub const GENERATED_CODE_INFO: u32 = 16;
+        }
+    }
+
     ///  Sync with code_generator.h.
     #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
     #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
@@ -1174,6 +1281,21 @@ pub mod code_generator_response {
             }
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                Feature::FEATURE_NONE => "FEATURE_NONE",
+                Feature::FEATURE_PROTO3_OPTIONAL => "FEATURE_PROTO3_OPTIONAL",
+            }
+        }
+
+        fn from_name(name: &str) -> ::std::option::Option<Feature> {
+            match name {
+                "FEATURE_NONE" => ::std::option::Option::Some(Feature::FEATURE_NONE),
+                "FEATURE_PROTO3_OPTIONAL" => ::std::option::Option::Some(Feature::FEATURE_PROTO3_OPTIONAL),
+                _ => ::std::option::Option::None
+            }
+        }
+
         fn values() -> &'static [Self] {
             static values: &'static [Feature] = &[
                 Feature::FEATURE_NONE,
@@ -1193,6 +1315,14 @@ pub mod code_generator_response {
         }
     }
 
+    impl ::std::str::FromStr for Feature {
+        type Err = crate::ParseEnumError;
+
+        fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+            crate::rt::enum_from_str("CodeGeneratorResponse.Feature", s)
+        }
+    }
+
     impl crate::reflect::ProtobufValue for Feature {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
     }
@@ -9,10 +9,12 @@ use crate::reflect::acc::v2::AccessorV2;
 use crate::reflect::acc::FieldAccessor;
 use crate::reflect::runtime_types::RuntimeTypeWithDeref;
 use crate::reflect::value::value_ref::ReflectValueMut;
+use crate::reflect::FieldDescriptor;
 use crate::reflect::MessageRef;
 use crate::reflect::ProtobufValue;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::ReflectValueRef;
+use crate::LazyMessage;
 use crate::MessageField;
 
 pub(crate) mod oneof;
@@ -23,6 +25,7 @@ trait OptionLike<T> {
     fn as_option_ref(&self) -> Option<&T>;
     fn as_option_mut(&mut self) -> Option<&mut T>;
     fn set_value(&mut self, value: T);
+    fn clear_value(&mut self);
 }
 
 impl<T> OptionLike<T> for Option<T> {
@@ -37,6 +40,10 @@ impl<T> OptionLike<T> for Option<T> {
     fn set_value(&mut self, value: T) {
         *self = Some(value);
     }
+
+    fn clear_value(&mut self) {
+        *self = None;
+    }
 }
 
 impl<T> OptionLike<T> for MessageField<T> {
@@ -51,6 +58,28 @@ impl<T> OptionLike<T> for MessageField<T> {
     fn set_value(&mut self, value: T) {
         *self = MessageField::some(value);
     }
+
+    fn clear_value(&mut self) {
+        *self = MessageField::none();
+    }
+}
+
+impl<T: Message> OptionLike<T> for LazyMessage<T> {
+    fn as_option_ref(&self) -> Option<&T> {
+        self.as_ref()
+    }
+
+    fn as_option_mut(&mut self) -> Option<&mut T> {
+        self.as_mut()
+    }
+
+    fn set_value(&mut self, value: T) {
+        self.set(value);
+    }
+
+    fn clear_value(&mut self) {
+        self.clear();
+    }
 }
 
 /// This trait should not be used directly, use `FieldDescriptor` instead
@@ -59,6 +88,7 @@ pub(crate) trait SingularFieldAccessor: Send + Sync + 'static {
     fn get_field_or_default<'a>(&self, m: &'a dyn MessageDyn) -> ReflectValueRef<'a>;
     fn mut_field_or_default<'a>(&self, m: &'a mut dyn MessageDyn) -> ReflectValueMut<'a>;
     fn set_field(&self, m: &mut dyn MessageDyn, value: ReflectValueBox);
+    fn clear_field(&self, m: &mut dyn MessageDyn, field: &FieldDescriptor);
 }
 
 pub(crate) struct SingularFieldAccessorHolder {
@@ -87,6 +117,10 @@ trait SetImpl<M>: Send + Sync + 'static {
     fn set_singular_field(&self, m: &mut M, value: ReflectValueBox);
 }
 
+trait ClearImpl<M>: Send + Sync + 'static {
+    fn clear_singular_field(&self, m: &mut M, field: &FieldDescriptor);
+}
+
 struct MutOrDefaultUnmplemented<M>
 where
     M: Message,
@@ -114,7 +148,7 @@ where
     }
 }
 
-struct SingularFieldAccessorImpl<M, V, G, D, E, S>
+struct SingularFieldAccessorImpl<M, V, G, D, E, S, C>
 where
     M: Message,
     V: ProtobufValue,
@@ -122,15 +156,17 @@ where
     D: GetOrDefaultImpl<M>,
     E: MutOrDefaultImpl<M>,
     S: SetImpl<M>,
+    C: ClearImpl<M>,
 {
     get_option_impl: G,
     get_or_default_impl: D,
     mut_or_default_impl: E,
     set_impl: S,
+    clear_impl: C,
     _marker: marker::PhantomData<(M, V)>,
 }
 
-impl<M, V, G, D, E, S> SingularFieldAccessor for SingularFieldAccessorImpl<M, V, G, D, E, S>
+impl<M, V, G, D, E, S, C> SingularFieldAccessor for SingularFieldAccessorImpl<M, V, G, D, E, S, C>
 where
     M: Message,
     V: ProtobufValue,
@@ -138,6 +174,7 @@ where
     D: GetOrDefaultImpl<M>,
     E: MutOrDefaultImpl<M>,
     S: SetImpl<M>,
+    C: ClearImpl<M>,
 {
     fn get_field<'a>(&self, m: &'a dyn MessageDyn) -> Option<ReflectValueRef<'a>> {
         let m = m.downcast_ref().unwrap();
@@ -160,6 +197,11 @@ where
         let m = m.downcast_mut().unwrap();
         self.set_impl.set_singular_field(m, value)
     }
+
+    fn clear_field(&self, m: &mut dyn MessageDyn, field: &FieldDescriptor) {
+        let m = m.downcast_mut().unwrap();
+        self.clear_impl.clear_singular_field(m, field)
+    }
 }
 
 struct GetOptionImplFieldPointer<M, V>
@@ -466,6 +508,67 @@ where
     }
 }
 
+struct ClearImplFieldPointer<M, V>
+where
+    M: Message,
+    V: ProtobufValue,
+{
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut V,
+}
+
+struct ClearImplOptionFieldPointer<M, V, O>
+where
+    M: Message,
+    V: ProtobufValue,
+    O: OptionLike<V> + Sync + Send + 'static,
+{
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut O,
+    _marker: marker::PhantomData<V>,
+}
+
+/// Clear `oneof` field with generated `clear_` function.
+///
+/// Generated function clears whichever case of the `oneof` is set,
+/// so it is called only when this field is the set case.
+struct ClearImplClearField<M>
+where
+    M: Message,
+{
+    clear_field: fn(&mut M),
+}
+
+impl<M, V> ClearImpl<M> for ClearImplFieldPointer<M, V>
+where
+    M: Message,
+    V: ProtobufValue,
+{
+    fn clear_singular_field(&self, m: &mut M, _field: &FieldDescriptor) {
+        *(self.mut_field)(m) = V::default();
+    }
+}
+
+impl<M, V, O> ClearImpl<M> for ClearImplOptionFieldPointer<M, V, O>
+where
+    M: Message,
+    V: ProtobufValue,
+    O: OptionLike<V> + Sync + Send + 'static,
+{
+    fn clear_singular_field(&self, m: &mut M, _field: &FieldDescriptor) {
+        (self.mut_field)(m).clear_value();
+    }
+}
+
+impl<M> ClearImpl<M> for ClearImplClearField<M>
+where
+    M: Message,
+{
+    fn clear_singular_field(&self, m: &mut M, field: &FieldDescriptor) {
+        if field.has_field(m) {
+            (self.clear_field)(m);
+        }
+    }
+}
+
 /// Make accessor for `SingularPtrField`
 pub fn make_message_field_accessor<M, V>(
     name: &'static str,
@@ -479,7 +582,47 @@ where
     FieldAccessor::new_v2(
         name,
         AccessorV2::Singular(SingularFieldAccessorHolder {
-            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _> {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _, _> {
+                get_option_impl: GetOptionImplOptionFieldPointer::<M, V, _> {
+                    get_field,
+                    _marker: marker::PhantomData,
+                },
+                get_or_default_impl: GetOrDefaultOptionRefTypeDefault::<M, V, _> {
+                    get_field,
+                    _marker: marker::PhantomData,
+                },
+                mut_or_default_impl: MutOrDefaultOptionMut::<M, V, _> {
+                    mut_field,
+                    _marker: marker::PhantomData,
+                },
+                set_impl: SetImplOptionFieldPointer::<M, V, _> {
+                    mut_field,
+                    _marker: marker::PhantomData,
+                },
+                clear_impl: ClearImplOptionFieldPointer::<M, V, _> {
+                    mut_field,
+                    _marker: marker::PhantomData,
+                },
+                _marker: marker::PhantomData,
+            }),
+        }),
+    )
+}
+
+/// Make accessor for `LazyMessage` field
+pub fn make_lazy_message_field_accessor<M, V>(
+    name: &'static str,
+    get_field: for<'a> fn(&'a M) -> &'a LazyMessage<V>,
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut LazyMessage<V>,
+) -> FieldAccessor
+where
+    M: Message + 'static,
+    V: Message + ProtobufValue + 'static,
+{
+    FieldAccessor::new_v2(
+        name,
+        AccessorV2::Singular(SingularFieldAccessorHolder {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _, _> {
                 get_option_impl: GetOptionImplOptionFieldPointer::<M, V, _> {
                     get_field,
                     _marker: marker::PhantomData,
@@ -496,6 +639,10 @@ where
                     mut_field,
                     _marker: marker::PhantomData,
                 },
+                clear_impl: ClearImplOptionFieldPointer::<M, V, _> {
+                    mut_field,
+                    _marker: marker::PhantomData,
+                },
                 _marker: marker::PhantomData,
             }),
         }),
@@ -516,7 +663,7 @@ where
     FieldAccessor::new_v2(
         name,
         AccessorV2::Singular(SingularFieldAccessorHolder {
-            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _> {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _, _> {
                 get_option_impl: GetOptionImplOptionFieldPointer::<M, V, _> {
                     get_field,
                     _marker: marker::PhantomData,
@@ -529,6 +676,10 @@ where
                     mut_field,
                     _marker: marker::PhantomData,
                 },
+                clear_impl: ClearImplOptionFieldPointer::<M, V, _> {
+                    mut_field,
+                    _marker: marker::PhantomData,
+                },
                 _marker: marker::PhantomData,
             }),
         }),
@@ -573,6 +724,7 @@ where
                 _,
                 _,
                 _,
+                _,
             > {
                 get_option_impl: GetOptionImplOptionFieldPointer::<
                     M,
@@ -595,6 +747,14 @@ where
                     mut_field,
                     _marker: marker::PhantomData,
                 },
+                clear_impl: ClearImplOptionFieldPointer::<
+                    M,
+                    ProtobufEnumOrUnknown<E>,
+                    Option<ProtobufEnumOrUnknown<E>>,
+                > {
+                    mut_field,
+                    _marker: marker::PhantomData,
+                },
                 _marker: marker::PhantomData,
             }),
         }),
@@ -616,7 +776,7 @@ where
     FieldAccessor::new_v2(
         name,
         AccessorV2::Singular(SingularFieldAccessorHolder {
-            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _> {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _, _> {
                 get_option_impl: GetOptionImplOptionFieldPointer::<M, V, _> {
                     get_field,
                     _marker: marker::PhantomData,
@@ -629,6 +789,10 @@ where
                     mut_field,
                     _marker: marker::PhantomData,
                 },
+                clear_impl: ClearImplOptionFieldPointer::<M, V, _> {
+                    mut_field,
+                    _marker: marker::PhantomData,
+                },
                 _marker: marker::PhantomData,
             }),
         }),
@@ -648,11 +812,12 @@ where
     FieldAccessor::new_v2(
         name,
         AccessorV2::Singular(SingularFieldAccessorHolder {
-            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _> {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _, _> {
                 get_option_impl: GetOptionImplFieldPointer::<M, V> { get_field },
                 get_or_default_impl: GetOrDefaultGetRef::<M, V> { get_field },
                 mut_or_default_impl: MutOrDefaultGetMut::<M, V> { mut_field },
                 set_impl: SetImplFieldPointer::<M, V> { mut_field },
+                clear_impl: ClearImplFieldPointer::<M, V> { mut_field },
                 _marker: marker::PhantomData,
             }),
         }),
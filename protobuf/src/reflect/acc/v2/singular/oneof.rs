@@ -1,5 +1,6 @@
 use std::marker;
 
+use crate::reflect::acc::v2::singular::ClearImplClearField;
 use crate::reflect::acc::v2::singular::GetOptionImplHasGetCopy;
 use crate::reflect::acc::v2::singular::GetOptionImplHasGetRef;
 use crate::reflect::acc::v2::singular::GetOptionImplHasGetRefDeref;
@@ -24,6 +25,7 @@ pub fn make_oneof_message_has_get_mut_set_accessor<M, F>(
     get_field: for<'a> fn(&'a M) -> &'a F,
     mut_field: for<'a> fn(&'a mut M) -> &'a mut F,
     set_field: fn(&mut M, F),
+    clear_field: fn(&mut M),
 ) -> FieldAccessor
 where
     M: Message + 'static,
@@ -32,7 +34,7 @@ where
     FieldAccessor::new_v2(
         name,
         AccessorV2::Singular(SingularFieldAccessorHolder {
-            accessor: Box::new(SingularFieldAccessorImpl::<M, F, _, _, _, _> {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, F, _, _, _, _, _> {
                 get_option_impl: GetOptionImplHasGetRef::<M, F> {
                     get: get_field,
                     has: has_field,
@@ -40,6 +42,7 @@ where
                 get_or_default_impl: GetOrDefaultGetRef::<M, F> { get_field },
                 mut_or_default_impl: MutOrDefaultGetMut::<M, F> { mut_field },
                 set_impl: SetImplSetField::<M, F> { set_field },
+                clear_impl: ClearImplClearField::<M> { clear_field },
                 _marker: marker::PhantomData,
             }),
         }),
@@ -52,6 +55,7 @@ pub fn make_oneof_copy_has_get_set_simpler_accessors<M, V>(
     has: fn(&M) -> bool,
     get: fn(&M) -> V,
     set: fn(&mut M, V),
+    clear: fn(&mut M),
 ) -> FieldAccessor
 where
     M: Message + 'static,
@@ -60,11 +64,12 @@ where
     FieldAccessor::new_v2(
         name,
         AccessorV2::Singular(SingularFieldAccessorHolder {
-            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _> {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _, _> {
                 get_option_impl: GetOptionImplHasGetCopy::<M, V> { has, get },
                 get_or_default_impl: GetOrDefaultGetCopy::<M, V> { get_field: get },
                 mut_or_default_impl: MutOrDefaultUnmplemented::new(),
                 set_impl: SetImplSetField::<M, V> { set_field: set },
+                clear_impl: ClearImplClearField::<M> { clear_field: clear },
                 _marker: marker::PhantomData,
             }),
         }),
@@ -77,6 +82,7 @@ pub fn make_oneof_deref_has_get_set_simpler_accessor<M, F>(
     has: fn(&M) -> bool,
     get: for<'a> fn(&'a M) -> &'a <F::RuntimeType as RuntimeTypeWithDeref>::DerefTarget,
     set: fn(&mut M, F),
+    clear: fn(&mut M),
 ) -> FieldAccessor
 where
     M: Message + 'static,
@@ -86,11 +92,12 @@ where
     FieldAccessor::new_v2(
         name,
         AccessorV2::Singular(SingularFieldAccessorHolder {
-            accessor: Box::new(SingularFieldAccessorImpl::<M, F, _, _, _, _> {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, F, _, _, _, _, _> {
                 get_option_impl: GetOptionImplHasGetRefDeref::<M, F> { has, get },
                 get_or_default_impl: GetOrDefaultGetRefDeref::<M, F> { get_field: get },
                 mut_or_default_impl: MutOrDefaultUnmplemented::new(),
                 set_impl: SetImplSetField::<M, F> { set_field: set },
+                clear_impl: ClearImplClearField::<M> { clear_field: clear },
                 _marker: marker::PhantomData,
             }),
         }),
@@ -0,0 +1,384 @@
+use std::cmp;
+
+use crate::message_dyn::MessageDyn;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::ReflectValueRef;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::text_format::compare_map_keys;
+
+/// How [`Anonymizer`] rewrites values of a field.
+///
+/// Policies apply to scalar values of singular and repeated fields,
+/// and to both keys and values of map fields.
+/// Message values are always anonymized recursively.
+///
+/// Map keys which become equal after anonymization are made distinct:
+/// hashed keys are hashed again with a salt, and for other policies
+/// only the entry with the smallest original key is kept.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnonymizePolicy {
+    /// Keep values as is.
+    Keep,
+    /// Omit the field from the anonymized message.
+    Clear,
+    /// Replace `string`, `bytes` and integer values with a keyed hash.
+    ///
+    /// Equal values are replaced with equal hashes, so identifiers
+    /// remain consistent within and across anonymized messages.
+    /// Signed integers are replaced with non-negative numbers.
+    /// Other values are kept.
+    Hash,
+    /// Round integer and floating point numbers down to a multiple of given bucket width.
+    ///
+    /// Zero width keeps values. Other values are kept.
+    Bucket(u64),
+}
+
+/// Produce anonymized copies of messages with per-field policies.
+///
+/// This is meant for capturing production payloads into test fixtures.
+/// By default `string` and `bytes` fields are hashed and other fields are kept,
+/// unknown fields are dropped.
+///
+/// Hashing is deterministic for the same key, but it is not cryptographically secure:
+/// use a secret key and do not rely on it to protect high-value secrets.
+#[derive(Clone)]
+pub struct Anonymizer {
+    key: u64,
+    field_policies: Vec<(FieldDescriptor, AnonymizePolicy)>,
+    max_repeated_len: Option<usize>,
+}
+
+impl Anonymizer {
+    /// New anonymizer with default policies and given hash key.
+    pub fn new(key: u64) -> Anonymizer {
+        Anonymizer {
+            key,
+            field_policies: Vec::new(),
+            max_repeated_len: None,
+        }
+    }
+
+    /// Override policy of given field.
+    pub fn set_field_policy(&mut self, field: FieldDescriptor, policy: AnonymizePolicy) {
+        self.field_policies.retain(|(f, _)| f != &field);
+        self.field_policies.push((field, policy));
+    }
+
+    /// Sample repeated fields: keep only first `max_len` elements of each repeated field.
+    ///
+    /// Map fields are not sampled.
+    pub fn set_max_repeated_len(&mut self, max_len: usize) {
+        self.max_repeated_len = Some(max_len);
+    }
+
+    /// Policy of given field, for map fields policy of map values.
+    pub fn field_policy(&self, field: &FieldDescriptor) -> AnonymizePolicy {
+        if let Some((_, policy)) = self.field_policies.iter().find(|(f, _)| f == field) {
+            return policy.clone();
+        }
+        match field.runtime_field_type() {
+            RuntimeFieldType::Singular(t) | RuntimeFieldType::Repeated(t) => default_policy(&t),
+            RuntimeFieldType::Map(_, v) => default_policy(&v),
+        }
+    }
+
+    /// Policy of map keys of given map field.
+    ///
+    /// Policy set for the field applies to both keys and values,
+    /// otherwise keys have the default policy of the key type,
+    /// so `string` keys are hashed whatever the value type is.
+    pub fn map_key_policy(&self, field: &FieldDescriptor) -> AnonymizePolicy {
+        if let Some((_, policy)) = self.field_policies.iter().find(|(f, _)| f == field) {
+            return policy.clone();
+        }
+        match field.runtime_field_type() {
+            RuntimeFieldType::Map(k, _) => default_policy(&k),
+            _ => AnonymizePolicy::Keep,
+        }
+    }
+
+    /// Anonymized copy of the message.
+    pub fn anonymize(&self, message: &dyn MessageDyn) -> Box<dyn MessageDyn> {
+        let descriptor = message.descriptor_dyn();
+        let mut anonymized = descriptor.new_instance();
+        for field in descriptor.fields() {
+            let policy = self.field_policy(&field);
+            if policy == AnonymizePolicy::Clear {
+                continue;
+            }
+            match field.get_reflect(message) {
+                ReflectFieldRef::Optional(Some(v)) => {
+                    field.set_singular_field(&mut *anonymized, self.anonymize_value(v, &policy))
+                }
+                ReflectFieldRef::Optional(None) => {}
+                ReflectFieldRef::Repeated(r) => {
+                    let len = cmp::min(r.len(), self.max_repeated_len.unwrap_or(usize::MAX));
+                    let mut anonymized_r = field.mut_repeated(&mut *anonymized);
+                    for v in r.into_iter().take(len) {
+                        anonymized_r.push(self.anonymize_value(v, &policy));
+                    }
+                }
+                ReflectFieldRef::Map(m) => {
+                    let key_policy = self.map_key_policy(&field);
+                    // Sorted, so colliding keys are resolved deterministically
+                    let mut entries: Vec<_> = m.into_iter().collect();
+                    entries.sort_by(|(a, _), (b, _)| compare_map_keys(a, b));
+                    let mut anonymized_m = field.mut_map(&mut *anonymized);
+                    for (k, v) in entries {
+                        let taken =
+                            |k: &ReflectValueBox| anonymized_m.get(k.as_value_ref()).is_some();
+                        if let Some(k) = self.anonymize_map_key(k, &key_policy, taken) {
+                            anonymized_m.insert(k, self.anonymize_value(v, &policy));
+                        }
+                    }
+                }
+            }
+        }
+        anonymized
+    }
+
+    /// Anonymized map key, `None` if the entry should be dropped
+    /// because the key collides with a key already in the anonymized map.
+    fn anonymize_map_key(
+        &self,
+        key: ReflectValueRef,
+        policy: &AnonymizePolicy,
+        taken: impl Fn(&ReflectValueBox) -> bool,
+    ) -> Option<ReflectValueBox> {
+        let anonymized = self.anonymize_value(key.clone(), policy);
+        if !taken(&anonymized) {
+            return Some(anonymized);
+        }
+        if policy != &AnonymizePolicy::Hash {
+            return None;
+        }
+        (1..).find_map(|salt| {
+            let anonymized = self.hash_value(key.clone(), salt)?;
+            match taken(&anonymized) {
+                true => None,
+                false => Some(anonymized),
+            }
+        })
+    }
+
+    fn anonymize_value(&self, value: ReflectValueRef, policy: &AnonymizePolicy) -> ReflectValueBox {
+        match (value, policy) {
+            (ReflectValueRef::Message(m), _) => ReflectValueBox::Message(self.anonymize(&*m)),
+            (value, AnonymizePolicy::Hash) => match self.hash_value(value.clone(), 0) {
+                Some(hashed) => hashed,
+                None => value.to_box(),
+            },
+            (ReflectValueRef::U32(v), &AnonymizePolicy::Bucket(w)) if w != 0 => {
+                ReflectValueBox::U32(bucket(v as i128, w) as u32)
+            }
+            (ReflectValueRef::U64(v), &AnonymizePolicy::Bucket(w)) if w != 0 => {
+                ReflectValueBox::U64(bucket(v as i128, w) as u64)
+            }
+            (ReflectValueRef::I32(v), &AnonymizePolicy::Bucket(w)) if w != 0 => {
+                ReflectValueBox::I32(bucket(v as i128, w).max(i32::MIN as i128) as i32)
+            }
+            (ReflectValueRef::I64(v), &AnonymizePolicy::Bucket(w)) if w != 0 => {
+                ReflectValueBox::I64(bucket(v as i128, w).max(i64::MIN as i128) as i64)
+            }
+            (ReflectValueRef::F32(v), &AnonymizePolicy::Bucket(w)) if w != 0 => {
+                ReflectValueBox::F32((v / w as f32).floor() * w as f32)
+            }
+            (ReflectValueRef::F64(v), &AnonymizePolicy::Bucket(w)) if w != 0 => {
+                ReflectValueBox::F64((v / w as f64).floor() * w as f64)
+            }
+            (value, _) => value.to_box(),
+        }
+    }
+
+    /// Hash of `string`, `bytes` or integer value, `None` for values of other types.
+    ///
+    /// Non-zero salt is used to make colliding hashes distinct.
+    fn hash_value(&self, value: ReflectValueRef, salt: u64) -> Option<ReflectValueBox> {
+        let hash = |bytes: &[u8]| self.hash(bytes, salt);
+        Some(match value {
+            ReflectValueRef::String(s) => {
+                ReflectValueBox::String(format!("{:016x}", hash(s.as_bytes())))
+            }
+            ReflectValueRef::Bytes(b) => ReflectValueBox::Bytes(hash(b).to_le_bytes().to_vec()),
+            ReflectValueRef::U32(v) => ReflectValueBox::U32(hash(&v.to_le_bytes()) as u32),
+            ReflectValueRef::U64(v) => ReflectValueBox::U64(hash(&v.to_le_bytes())),
+            ReflectValueRef::I32(v) => {
+                ReflectValueBox::I32((hash(&v.to_le_bytes()) & i32::MAX as u64) as i32)
+            }
+            ReflectValueRef::I64(v) => {
+                ReflectValueBox::I64((hash(&v.to_le_bytes()) & i64::MAX as u64) as i64)
+            }
+            _ => return None,
+        })
+    }
+
+    /// FNV-1a of key followed by given bytes, and by salt if salt is not zero.
+    fn hash(&self, bytes: &[u8], salt: u64) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+        let salt = salt.to_le_bytes();
+        let salt: &[u8] = if salt == [0; 8] { &[] } else { &salt };
+        let mut h = FNV_OFFSET_BASIS;
+        for &b in self.key.to_le_bytes().iter().chain(bytes).chain(salt) {
+            h ^= b as u64;
+            h = h.wrapping_mul(FNV_PRIME);
+        }
+        h
+    }
+}
+
+/// Default policy of values of given type.
+fn default_policy(t: &RuntimeTypeBox) -> AnonymizePolicy {
+    match t {
+        RuntimeTypeBox::String | RuntimeTypeBox::VecU8 => AnonymizePolicy::Hash,
+        _ => AnonymizePolicy::Keep,
+    }
+}
+
+/// Largest multiple of `width` not greater than `v`.
+fn bucket(v: i128, width: u64) -> i128 {
+    v.div_euclid(width as i128) * width as i128
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::file_options::OptimizeMode;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::well_known_types::Struct;
+    use crate::well_known_types::Value;
+    use crate::Message;
+
+    fn file() -> FileDescriptorProto {
+        let mut file = FileDescriptorProto::new();
+        file.set_name("secret.proto".to_owned());
+        file.dependency.push("a.proto".to_owned());
+        file.dependency.push("b.proto".to_owned());
+        file.dependency.push("a.proto".to_owned());
+        file.public_dependency = vec![17, -17, 1_000_000];
+        file.options
+            .mut_or_default()
+            .set_java_package("secret".to_owned());
+        file.options
+            .mut_or_default()
+            .set_optimize_for(OptimizeMode::CODE_SIZE);
+        file
+    }
+
+    fn field(name: &str) -> FieldDescriptor {
+        FileDescriptorProto::descriptor_static()
+            .get_field_by_name(name)
+            .unwrap()
+    }
+
+    fn anonymize(anonymizer: &Anonymizer, file: &FileDescriptorProto) -> FileDescriptorProto {
+        *anonymizer.anonymize(file).downcast_box().unwrap()
+    }
+
+    #[test]
+    fn test_bucket() {
+        assert_eq!(10, bucket(17, 10));
+        assert_eq!(-20, bucket(-17, 10));
+        assert_eq!(0, bucket(0, 10));
+    }
+
+    #[test]
+    fn default_policy() {
+        let anonymized = anonymize(&Anonymizer::new(1), &file());
+
+        assert_ne!("secret.proto", anonymized.get_name());
+        assert_eq!(16, anonymized.get_name().len());
+        // equal values are replaced with equal values
+        assert_eq!(3, anonymized.dependency.len());
+        assert_eq!(anonymized.dependency[0], anonymized.dependency[2]);
+        assert_ne!(anonymized.dependency[0], anonymized.dependency[1]);
+        // nested messages are anonymized
+        assert_ne!(
+            "secret",
+            anonymized.options.get_or_default().get_java_package()
+        );
+        // other values are kept
+        assert_eq!(file().public_dependency, anonymized.public_dependency);
+        assert_eq!(
+            file().options.get_or_default().get_optimize_for(),
+            anonymized.options.get_or_default().get_optimize_for()
+        );
+
+        // deterministic for the same key
+        assert_eq!(anonymized, anonymize(&Anonymizer::new(1), &file()));
+        assert_ne!(anonymized, anonymize(&Anonymizer::new(2), &file()));
+    }
+
+    #[test]
+    fn field_policies() {
+        let mut anonymizer = Anonymizer::new(1);
+        anonymizer.set_field_policy(field("name"), AnonymizePolicy::Keep);
+        anonymizer.set_field_policy(field("dependency"), AnonymizePolicy::Clear);
+        anonymizer.set_field_policy(field("public_dependency"), AnonymizePolicy::Bucket(10));
+        let anonymized = anonymize(&anonymizer, &file());
+        assert_eq!("secret.proto", anonymized.get_name());
+        assert!(anonymized.dependency.is_empty());
+        assert_eq!(vec![10, -20, 1_000_000], anonymized.public_dependency);
+
+        anonymizer.set_field_policy(field("public_dependency"), AnonymizePolicy::Hash);
+        let anonymized = anonymize(&anonymizer, &file());
+        assert_eq!(3, anonymized.public_dependency.len());
+        assert!(anonymized.public_dependency.iter().all(|&d| d >= 0));
+        assert_ne!(file().public_dependency, anonymized.public_dependency);
+    }
+
+    #[test]
+    fn map_keys() {
+        let mut s = Struct::new();
+        s.fields.insert("secret".to_owned(), Value::new());
+        let anonymized = Anonymizer::new(1).anonymize(&s);
+        let anonymized = anonymized.downcast_ref::<Struct>().unwrap();
+        // string keys are hashed even if values are messages
+        assert_eq!(1, anonymized.fields.len());
+        assert!(!anonymized.fields.contains_key("secret"));
+
+        let fields = Struct::descriptor_static()
+            .get_field_by_name("fields")
+            .unwrap();
+        let mut anonymizer = Anonymizer::new(1);
+        anonymizer.set_field_policy(fields, AnonymizePolicy::Keep);
+        let anonymized = anonymizer.anonymize(&s);
+        assert_eq!(Some(&s), anonymized.downcast_ref::<Struct>());
+    }
+
+    #[test]
+    fn map_key_collision() {
+        let anonymizer = Anonymizer::new(1);
+        let key = ReflectValueRef::String("secret");
+        let hashed = anonymizer.anonymize_value(key.clone(), &AnonymizePolicy::Hash);
+        let taken = |k: &ReflectValueBox| k == &hashed;
+        let rehashed = anonymizer
+            .anonymize_map_key(key.clone(), &AnonymizePolicy::Hash, taken)
+            .unwrap();
+        assert_ne!(hashed, rehashed);
+        assert_eq!(
+            Some(rehashed),
+            anonymizer.anonymize_map_key(key.clone(), &AnonymizePolicy::Hash, taken)
+        );
+
+        let kept = key.to_box();
+        let taken = |k: &ReflectValueBox| k == &kept;
+        assert_eq!(
+            None,
+            anonymizer.anonymize_map_key(key, &AnonymizePolicy::Keep, taken)
+        );
+    }
+
+    #[test]
+    fn max_repeated_len() {
+        let mut anonymizer = Anonymizer::new(1);
+        anonymizer.set_max_repeated_len(2);
+        let anonymized = anonymize(&anonymizer, &file());
+        assert_eq!(2, anonymized.dependency.len());
+        assert_eq!(vec![17, -17], anonymized.public_dependency);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::reflect::dynamic::ResolvedFields;
+use crate::reflect::message::MessageDescriptorImplRef;
+use crate::reflect::MessageDescriptor;
+use crate::MessageDyn;
+use crate::ProtobufResult;
+
+/// Message type resolved once for parsing many messages.
+enum BatchDecoder {
+    Generated(MessageDescriptor),
+    Dynamic(ResolvedFields),
+}
+
+impl BatchDecoder {
+    fn new(descriptor: &MessageDescriptor) -> BatchDecoder {
+        match descriptor.get_impl() {
+            MessageDescriptorImplRef::Generated(..) => BatchDecoder::Generated(descriptor.clone()),
+            MessageDescriptorImplRef::Dynamic(..) => {
+                BatchDecoder::Dynamic(ResolvedFields::new(descriptor))
+            }
+        }
+    }
+
+    fn decode(&self, bytes: &[u8]) -> ProtobufResult<Box<dyn MessageDyn>> {
+        let message: Box<dyn MessageDyn> = match self {
+            BatchDecoder::Generated(descriptor) => {
+                let mut message = descriptor.new_instance();
+                message.merge_from_bytes_dyn(bytes)?;
+                message
+            }
+            BatchDecoder::Dynamic(fields) => Box::new(fields.parse(bytes)?),
+        };
+        message.check_initialized_dyn()?;
+        Ok(message)
+    }
+}
+
+/// Parse many serialized messages of the same type.
+///
+/// This is equivalent to parsing each message separately,
+/// but for dynamic messages fields of the message type are resolved
+/// once for the whole batch rather than for each field of each message.
+///
+/// Error is returned if any message cannot be parsed
+/// or is not initialized.
+pub fn decode_batch<'a>(
+    descriptor: &MessageDescriptor,
+    records: impl IntoIterator<Item = &'a [u8]>,
+) -> ProtobufResult<Vec<Box<dyn MessageDyn>>> {
+    let decoder = BatchDecoder::new(descriptor);
+    records.into_iter().map(|r| decoder.decode(r)).collect()
+}
+
+/// Parse many serialized messages of the same type in parallel.
+///
+/// Same as [`decode_batch`], but messages are parsed in rayon thread pool.
+/// Result is in the same order as `records`.
+#[cfg(feature = "with-rayon")]
+pub fn decode_batch_parallel(
+    descriptor: &MessageDescriptor,
+    records: &[&[u8]],
+) -> ProtobufResult<Vec<Box<dyn MessageDyn>>> {
+    use rayon::prelude::*;
+
+    let decoder = BatchDecoder::new(descriptor);
+    records.par_iter().map(|r| decoder.decode(r)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::decode_batch;
+    use crate::descriptor::file_descriptor;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::descriptor::UninterpretedOption;
+    use crate::reflect::FileDescriptor;
+    use crate::reflect::MessageDescriptor;
+    use crate::reflect::ReflectEqMode;
+    use crate::Message;
+
+    fn records() -> Vec<Vec<u8>> {
+        let mut a = FileDescriptorProto::new();
+        a.set_name("a.proto".to_owned());
+        a.options.mut_or_default().set_java_package("a".to_owned());
+        let mut b = FileDescriptorProto::new();
+        b.set_package("b".to_owned());
+        b.dependency.push("a.proto".to_owned());
+        b.public_dependency.push(0);
+        vec![
+            a.write_to_bytes().unwrap(),
+            Vec::new(),
+            b.write_to_bytes().unwrap(),
+        ]
+    }
+
+    fn dynamic_descriptor(name: &str) -> MessageDescriptor {
+        let file = FileDescriptor::new_dynamic(file_descriptor().proto().clone(), Vec::new());
+        file.message_by_package_relative_name(name).unwrap()
+    }
+
+    fn check_same_as_parsed(descriptor: &MessageDescriptor) {
+        let records = records();
+        let messages = decode_batch(descriptor, records.iter().map(|r| &r[..])).unwrap();
+        assert_eq!(records.len(), messages.len());
+        for (record, message) in records.iter().zip(&messages) {
+            let mut parsed = descriptor.new_instance();
+            parsed.merge_from_bytes_dyn(record).unwrap();
+            assert!(message.reflect_eq_dyn(&*parsed, &ReflectEqMode::default()));
+            assert_eq!(record, &message.write_to_bytes_dyn().unwrap());
+        }
+    }
+
+    #[test]
+    fn generated() {
+        check_same_as_parsed(&FileDescriptorProto::descriptor_static());
+    }
+
+    #[test]
+    fn dynamic() {
+        check_same_as_parsed(&dynamic_descriptor("FileDescriptorProto"));
+    }
+
+    #[test]
+    fn dynamic_messages_are_independent() {
+        let d = dynamic_descriptor("FileDescriptorProto");
+        let dependency = d.get_field_by_name("dependency").unwrap();
+        let records = records();
+        let messages = decode_batch(&d, vec![&records[2][..], &records[2][..]]).unwrap();
+        assert_eq!(1, dependency.get_repeated(&*messages[0]).len());
+        assert_eq!(1, dependency.get_repeated(&*messages[1]).len());
+    }
+
+    #[test]
+    fn malformed() {
+        let d = dynamic_descriptor("FileDescriptorProto");
+        let records: Vec<&[u8]> = vec![b"", b"\x0a\x05a"];
+        assert!(decode_batch(&d, records).is_err());
+    }
+
+    #[test]
+    fn not_initialized() {
+        // name { } without required fields
+        let records: Vec<&[u8]> = vec![b"\x12\x00"];
+        assert!(decode_batch(&UninterpretedOption::descriptor_static(), records.clone()).is_err());
+        assert!(decode_batch(&dynamic_descriptor("UninterpretedOption"), records).is_err());
+    }
+
+    #[cfg(feature = "with-rayon")]
+    #[test]
+    fn parallel() {
+        let d = dynamic_descriptor("FileDescriptorProto");
+        let records = records();
+        let records: Vec<&[u8]> = records.iter().map(|r| &r[..]).collect();
+        let serial = decode_batch(&d, records.iter().cloned()).unwrap();
+        let parallel = super::decode_batch_parallel(&d, &records).unwrap();
+        assert_eq!(serial.len(), parallel.len());
+        for (s, p) in serial.iter().zip(&parallel) {
+            assert!(s.reflect_eq_dyn(&**p, &ReflectEqMode::default()));
+        }
+    }
+}
@@ -0,0 +1,536 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::message_dyn::MessageDyn;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::FieldPath;
+use crate::reflect::FieldPathElement;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+
+/// Size limits enforced by [`CheckedMessageBuilder`].
+#[derive(Debug, Clone)]
+pub struct BuilderLimits {
+    /// Maximum length of `string` field value in bytes.
+    pub max_string_len: usize,
+    /// Maximum length of `bytes` field value.
+    pub max_bytes_len: usize,
+    /// Maximum number of elements of repeated or map field.
+    pub max_repeated_len: usize,
+}
+
+impl Default for BuilderLimits {
+    fn default() -> BuilderLimits {
+        BuilderLimits {
+            max_string_len: 1 << 20,
+            max_bytes_len: 1 << 20,
+            max_repeated_len: 10_000,
+        }
+    }
+}
+
+/// Kind of [`BuildError`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildErrorKind {
+    /// Message has no field with given name.
+    UnknownField,
+    /// Field is singular, repeated or map, but was used as a different kind.
+    WrongFieldKind,
+    /// Value type does not match field type.
+    WrongType {
+        /// Field type.
+        expected: String,
+        /// Value type.
+        actual: String,
+    },
+    /// Integer value does not fit into field type.
+    OutOfRange,
+    /// Enum value is not declared in the enum.
+    UnknownEnumValue(i32),
+    /// Bytes value for `string` field is not valid UTF-8.
+    InvalidUtf8,
+    /// Value or collection exceeds the limit.
+    TooLong {
+        /// Length of the value or collection.
+        len: usize,
+        /// Configured limit.
+        max: usize,
+    },
+    /// Required field is not set.
+    MissingRequired,
+}
+
+impl fmt::Display for BuildErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildErrorKind::UnknownField => write!(f, "unknown field"),
+            BuildErrorKind::WrongFieldKind => write!(f, "wrong field kind"),
+            BuildErrorKind::WrongType { expected, actual } => {
+                write!(f, "expected {}, got {}", expected, actual)
+            }
+            BuildErrorKind::OutOfRange => write!(f, "value out of range"),
+            BuildErrorKind::UnknownEnumValue(v) => write!(f, "unknown enum value {}", v),
+            BuildErrorKind::InvalidUtf8 => write!(f, "invalid UTF-8"),
+            BuildErrorKind::TooLong { len, max } => {
+                write!(f, "length {} exceeds limit {}", len, max)
+            }
+            BuildErrorKind::MissingRequired => write!(f, "required field is not set"),
+        }
+    }
+}
+
+/// Validation error collected by [`CheckedMessageBuilder`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildError {
+    /// Path to the field in the message being built.
+    pub path: FieldPath,
+    /// What is wrong.
+    pub kind: BuildErrorKind,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.kind)
+    }
+}
+
+/// Builder of dynamic messages from untrusted input.
+///
+/// Unlike [`FieldDescriptor::set_singular_field`] and similar functions
+/// which panic on incorrect values, builder validates each value
+/// and collects all errors, which are returned from [`build`](Self::build).
+///
+/// Values are validated against field types: integers are converted to field type
+/// if they fit, enum values must be declared in the enum, `Bytes` values
+/// for `string` fields must be valid UTF-8, and lengths are checked
+/// against [`BuilderLimits`].
+///
+/// ```
+/// # use protobuf::reflect::CheckedMessageBuilder;
+/// # use protobuf::reflect::MessageDescriptor;
+/// # use protobuf::reflect::ReflectValueBox;
+/// # fn foo(descriptor: &MessageDescriptor) {
+/// let mut builder = CheckedMessageBuilder::new(descriptor);
+/// builder.set("id", ReflectValueBox::I64(10));
+/// builder.push("tags", ReflectValueBox::String("a".to_owned()));
+/// match builder.build() {
+///     Ok(message) => println!("{:?}", message),
+///     Err(errors) => {
+///         for e in errors {
+///             println!("{}", e);
+///         }
+///     }
+/// }
+/// # }
+/// ```
+pub struct CheckedMessageBuilder {
+    message: Box<dyn MessageDyn>,
+    limits: BuilderLimits,
+    errors: Vec<BuildError>,
+}
+
+fn type_name(t: &RuntimeTypeBox) -> String {
+    match t {
+        RuntimeTypeBox::Enum(e) => e.full_name().to_owned(),
+        RuntimeTypeBox::Message(m) => m.full_name().to_owned(),
+        t => format!("{:?}", t).to_lowercase(),
+    }
+}
+
+fn to_i128(value: &ReflectValueBox) -> Option<i128> {
+    match *value {
+        ReflectValueBox::I32(v) => Some(v as i128),
+        ReflectValueBox::I64(v) => Some(v as i128),
+        ReflectValueBox::U32(v) => Some(v as i128),
+        ReflectValueBox::U64(v) => Some(v as i128),
+        _ => None,
+    }
+}
+
+impl CheckedMessageBuilder {
+    /// Builder of a message of given type with default limits.
+    pub fn new(descriptor: &MessageDescriptor) -> CheckedMessageBuilder {
+        CheckedMessageBuilder::with_limits(descriptor, BuilderLimits::default())
+    }
+
+    /// Builder of a message of given type with given limits.
+    pub fn with_limits(
+        descriptor: &MessageDescriptor,
+        limits: BuilderLimits,
+    ) -> CheckedMessageBuilder {
+        CheckedMessageBuilder {
+            message: descriptor.new_instance(),
+            limits,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Errors collected so far.
+    pub fn errors(&self) -> &[BuildError] {
+        &self.errors
+    }
+
+    fn error(&mut self, path: &[FieldPathElement], kind: BuildErrorKind) {
+        self.errors.push(BuildError {
+            path: FieldPath::from(path.to_vec()),
+            kind,
+        });
+    }
+
+    fn field(&mut self, name: &str) -> Option<FieldDescriptor> {
+        let field = self.message.descriptor_dyn().get_field_by_name(name);
+        if field.is_none() {
+            self.error(
+                &[FieldPathElement::Field(name.to_owned())],
+                BuildErrorKind::UnknownField,
+            );
+        }
+        field
+    }
+
+    fn check_len(&mut self, path: &[FieldPathElement], len: usize, max: usize) -> bool {
+        if len > max {
+            self.error(path, BuildErrorKind::TooLong { len, max });
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Validate and convert value to given type.
+    fn check_value(
+        &mut self,
+        path: &[FieldPathElement],
+        t: &RuntimeTypeBox,
+        value: ReflectValueBox,
+    ) -> Option<ReflectValueBox> {
+        let wrong_type = |value: &ReflectValueBox| BuildErrorKind::WrongType {
+            expected: type_name(t),
+            actual: type_name(&value.get_type()),
+        };
+        let out_of_range = |_| BuildErrorKind::OutOfRange;
+        let r = match (t, value) {
+            (RuntimeTypeBox::String, ReflectValueBox::String(s)) => {
+                if !self.check_len(path, s.len(), self.limits.max_string_len) {
+                    return None;
+                }
+                Ok(ReflectValueBox::String(s))
+            }
+            (RuntimeTypeBox::String, ReflectValueBox::Bytes(b)) => {
+                if !self.check_len(path, b.len(), self.limits.max_string_len) {
+                    return None;
+                }
+                String::from_utf8(b)
+                    .map(ReflectValueBox::String)
+                    .map_err(|_| BuildErrorKind::InvalidUtf8)
+            }
+            (RuntimeTypeBox::VecU8, ReflectValueBox::Bytes(b)) => {
+                if !self.check_len(path, b.len(), self.limits.max_bytes_len) {
+                    return None;
+                }
+                Ok(ReflectValueBox::Bytes(b))
+            }
+            (RuntimeTypeBox::Enum(e), ReflectValueBox::Enum(ve, v))
+                if e.full_name() == ve.full_name() =>
+            {
+                if e.contains_number(v) {
+                    Ok(ReflectValueBox::Enum(e.clone(), v))
+                } else {
+                    Err(BuildErrorKind::UnknownEnumValue(v))
+                }
+            }
+            (RuntimeTypeBox::Enum(e), value) if to_i128(&value).is_some() => {
+                match i32::try_from(to_i128(&value).unwrap()) {
+                    Ok(v) if e.contains_number(v) => Ok(ReflectValueBox::Enum(e.clone(), v)),
+                    Ok(v) => Err(BuildErrorKind::UnknownEnumValue(v)),
+                    Err(_) => Err(BuildErrorKind::OutOfRange),
+                }
+            }
+            (RuntimeTypeBox::Message(d), ReflectValueBox::Message(m))
+                if m.descriptor_dyn().full_name() == d.full_name() =>
+            {
+                Ok(ReflectValueBox::Message(m))
+            }
+            (RuntimeTypeBox::I32, value) if to_i128(&value).is_some() => {
+                i32::try_from(to_i128(&value).unwrap())
+                    .map(ReflectValueBox::I32)
+                    .map_err(out_of_range)
+            }
+            (RuntimeTypeBox::I64, value) if to_i128(&value).is_some() => {
+                i64::try_from(to_i128(&value).unwrap())
+                    .map(ReflectValueBox::I64)
+                    .map_err(out_of_range)
+            }
+            (RuntimeTypeBox::U32, value) if to_i128(&value).is_some() => {
+                u32::try_from(to_i128(&value).unwrap())
+                    .map(ReflectValueBox::U32)
+                    .map_err(out_of_range)
+            }
+            (RuntimeTypeBox::U64, value) if to_i128(&value).is_some() => {
+                u64::try_from(to_i128(&value).unwrap())
+                    .map(ReflectValueBox::U64)
+                    .map_err(out_of_range)
+            }
+            (RuntimeTypeBox::F32, ReflectValueBox::F32(v)) => Ok(ReflectValueBox::F32(v)),
+            (RuntimeTypeBox::F64, ReflectValueBox::F64(v)) => Ok(ReflectValueBox::F64(v)),
+            (RuntimeTypeBox::F64, ReflectValueBox::F32(v)) => Ok(ReflectValueBox::F64(v as f64)),
+            (RuntimeTypeBox::Bool, ReflectValueBox::Bool(v)) => Ok(ReflectValueBox::Bool(v)),
+            (_, value) => Err(wrong_type(&value)),
+        };
+        match r {
+            Ok(v) => Some(v),
+            Err(kind) => {
+                self.error(path, kind);
+                None
+            }
+        }
+    }
+
+    /// Set singular field.
+    pub fn set(&mut self, field: &str, value: ReflectValueBox) -> &mut Self {
+        let path = [FieldPathElement::Field(field.to_owned())];
+        let field = match self.field(field) {
+            Some(field) => field,
+            None => return self,
+        };
+        match field.runtime_field_type() {
+            RuntimeFieldType::Singular(t) => {
+                if let Some(value) = self.check_value(&path, &t, value) {
+                    field.set_singular_field(&mut *self.message, value);
+                }
+            }
+            _ => self.error(&path, BuildErrorKind::WrongFieldKind),
+        }
+        self
+    }
+
+    /// Append an element to repeated field.
+    pub fn push(&mut self, field: &str, value: ReflectValueBox) -> &mut Self {
+        let field_name = field;
+        let field = match self.field(field_name) {
+            Some(field) => field,
+            None => return self,
+        };
+        let len = match field.runtime_field_type() {
+            RuntimeFieldType::Repeated(..) => field.get_repeated(&*self.message).len(),
+            _ => {
+                self.error(
+                    &[FieldPathElement::Field(field_name.to_owned())],
+                    BuildErrorKind::WrongFieldKind,
+                );
+                return self;
+            }
+        };
+        let path = [
+            FieldPathElement::Field(field_name.to_owned()),
+            FieldPathElement::Index(len),
+        ];
+        if !self.check_len(&path[..1], len + 1, self.limits.max_repeated_len) {
+            return self;
+        }
+        if let RuntimeFieldType::Repeated(t) = field.runtime_field_type() {
+            if let Some(value) = self.check_value(&path, &t, value) {
+                field.mut_repeated(&mut *self.message).push(value);
+            }
+        }
+        self
+    }
+
+    /// Insert an entry into map field.
+    pub fn insert(
+        &mut self,
+        field: &str,
+        key: ReflectValueBox,
+        value: ReflectValueBox,
+    ) -> &mut Self {
+        let field_name = field;
+        let field = match self.field(field_name) {
+            Some(field) => field,
+            None => return self,
+        };
+        let (kt, vt) = match field.runtime_field_type() {
+            RuntimeFieldType::Map(k, v) => (k, v),
+            _ => {
+                self.error(
+                    &[FieldPathElement::Field(field_name.to_owned())],
+                    BuildErrorKind::WrongFieldKind,
+                );
+                return self;
+            }
+        };
+        let field_path = [FieldPathElement::Field(field_name.to_owned())];
+        let key = match self.check_value(&field_path, &kt, key) {
+            Some(key) => key,
+            None => return self,
+        };
+        let path = [
+            FieldPathElement::Field(field_name.to_owned()),
+            FieldPathElement::MapKey(key.clone()),
+        ];
+        let len = field.get_map(&*self.message).len();
+        if !self.check_len(&field_path, len + 1, self.limits.max_repeated_len) {
+            return self;
+        }
+        if let Some(value) = self.check_value(&path, &vt, value) {
+            field.mut_map(&mut *self.message).insert(key, value);
+        }
+        self
+    }
+
+    /// Collect errors of nested builder prefixed with given path
+    /// and return nested message if there were no errors.
+    fn take_nested(
+        &mut self,
+        path: &[FieldPathElement],
+        nested: CheckedMessageBuilder,
+    ) -> Option<ReflectValueBox> {
+        match nested.build() {
+            Ok(m) => Some(ReflectValueBox::Message(m)),
+            Err(errors) => {
+                for e in errors {
+                    let mut elements = path.to_vec();
+                    elements.extend(e.path.elements().iter().cloned());
+                    self.errors.push(BuildError {
+                        path: FieldPath::from(elements),
+                        kind: e.kind,
+                    });
+                }
+                None
+            }
+        }
+    }
+
+    /// Set singular message field to a message built by nested builder.
+    ///
+    /// Errors of nested builder are added to errors of this builder.
+    pub fn set_message(&mut self, field: &str, nested: CheckedMessageBuilder) -> &mut Self {
+        let path = [FieldPathElement::Field(field.to_owned())];
+        if let Some(m) = self.take_nested(&path, nested) {
+            self.set(field, m);
+        }
+        self
+    }
+
+    /// Append a message built by nested builder to repeated field.
+    ///
+    /// Errors of nested builder are added to errors of this builder.
+    pub fn push_message(&mut self, field: &str, nested: CheckedMessageBuilder) -> &mut Self {
+        let index = self
+            .message
+            .descriptor_dyn()
+            .get_field_by_name(field)
+            .filter(|f| f.is_repeated())
+            .map(|f| f.get_repeated(&*self.message).len())
+            .unwrap_or(0);
+        let path = [
+            FieldPathElement::Field(field.to_owned()),
+            FieldPathElement::Index(index),
+        ];
+        if let Some(m) = self.take_nested(&path, nested) {
+            self.push(field, m);
+        }
+        self
+    }
+
+    /// Finish building, and return the message or all collected errors.
+    ///
+    /// Required fields which are not set are reported as errors too.
+    pub fn build(mut self) -> Result<Box<dyn MessageDyn>, Vec<BuildError>> {
+        for path in self.message.find_initialization_errors_dyn() {
+            self.errors.push(BuildError {
+                path,
+                kind: BuildErrorKind::MissingRequired,
+            });
+        }
+        if self.errors.is_empty() {
+            Ok(self.message)
+        } else {
+            Err(self.errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::field_descriptor_proto;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FieldDescriptorProto;
+    use crate::descriptor::UninterpretedOption;
+    use crate::Message;
+
+    #[test]
+    fn build() {
+        let mut builder = CheckedMessageBuilder::new(&FieldDescriptorProto::descriptor_static());
+        builder
+            .set("name", ReflectValueBox::Bytes(b"f".to_vec()))
+            .set("number", ReflectValueBox::I64(7))
+            .set(
+                "label",
+                ReflectValueBox::I32(field_descriptor_proto::Label::LABEL_REPEATED as i32),
+            );
+        let m = builder.build().unwrap();
+        let m: &FieldDescriptorProto = m.downcast_ref().unwrap();
+        assert_eq!("f", m.get_name());
+        assert_eq!(7, m.get_number());
+        assert_eq!(field_descriptor_proto::Label::LABEL_REPEATED, m.get_label());
+    }
+
+    #[test]
+    fn errors_are_aggregated() {
+        let limits = BuilderLimits {
+            max_string_len: 3,
+            ..BuilderLimits::default()
+        };
+        let descriptor = DescriptorProto::descriptor_static();
+        let mut builder = CheckedMessageBuilder::with_limits(&descriptor, limits.clone());
+        builder
+            .set("name", ReflectValueBox::String("long name".to_owned()))
+            .set("no_such_field", ReflectValueBox::I32(1))
+            .set("field", ReflectValueBox::I32(1))
+            .push("reserved_name", ReflectValueBox::Bytes(vec![0xff]));
+
+        let mut field =
+            CheckedMessageBuilder::with_limits(&FieldDescriptorProto::descriptor_static(), limits);
+        field
+            .set("number", ReflectValueBox::U64(1 << 40))
+            .set("label", ReflectValueBox::I32(100));
+        builder.push_message("field", field);
+
+        let errors: Vec<String> = builder
+            .build()
+            .unwrap_err()
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "name: length 9 exceeds limit 3",
+                "no_such_field: unknown field",
+                "field: wrong field kind",
+                "reserved_name[0]: invalid UTF-8",
+                "field[0].number: value out of range",
+                "field[0].label: unknown enum value 100",
+            ],
+            errors
+        );
+    }
+
+    #[test]
+    fn missing_required() {
+        let mut builder = CheckedMessageBuilder::new(&UninterpretedOption::descriptor_static());
+        builder.push_message(
+            "name",
+            CheckedMessageBuilder::new(
+                &crate::descriptor::uninterpreted_option::NamePart::descriptor_static(),
+            ),
+        );
+        let errors = builder.build().unwrap_err();
+        assert_eq!(2, errors.len());
+        assert_eq!("name[0].name_part", errors[0].path.to_string());
+        assert_eq!(BuildErrorKind::MissingRequired, errors[0].kind);
+        assert_eq!("name[0].is_extension", errors[1].path.to_string());
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::HashMap;
+
+use crate::descriptor::field_descriptor_proto;
+use crate::descriptor::DescriptorProto;
+use crate::descriptor::FileDescriptorProto;
+use crate::reflect::file::index::FileIndex;
+use crate::reflect::ExtensionDescriptor;
+use crate::reflect::FileDescriptor;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::unknown::UnknownFields;
+use crate::unknown::UnknownValueRef;
+use crate::zigzag::decode_zig_zag_32;
+use crate::zigzag::decode_zig_zag_64;
+use crate::CodedInputStream;
+use crate::Message;
+
+#[derive(Clone, Debug)]
+struct RegisteredExtension {
+    descriptor: ExtensionDescriptor,
+    proto_type: field_descriptor_proto::Type,
+    /// Type of extension value, or element type for repeated extensions.
+    runtime_type: RuntimeTypeBox,
+}
+
+impl RegisteredExtension {
+    /// # Panics
+    ///
+    /// If extension type cannot be found in the file or its dependencies.
+    fn new(descriptor: ExtensionDescriptor) -> RegisteredExtension {
+        match RegisteredExtension::try_new(descriptor) {
+            Ok(r) => r,
+            Err(descriptor) => panic!("type not found: {}", descriptor.get_proto().get_type_name()),
+        }
+    }
+
+    /// Extension descriptor is returned back if its type cannot be resolved.
+    fn try_new(
+        descriptor: ExtensionDescriptor,
+    ) -> Result<RegisteredExtension, ExtensionDescriptor> {
+        let runtime_type = match descriptor.try_runtime_field_type() {
+            Some(RuntimeFieldType::Singular(t)) | Some(RuntimeFieldType::Repeated(t)) => t,
+            Some(RuntimeFieldType::Map(..)) => unreachable!(),
+            None => return Err(descriptor),
+        };
+        Ok(RegisteredExtension {
+            proto_type: descriptor.get_proto().get_field_type(),
+            runtime_type,
+            descriptor,
+        })
+    }
+}
+
+/// Extensions used to decode custom options when building dynamic file descriptors.
+///
+/// Without a registry custom options remain unknown fields of options messages
+/// like `FieldOptions` or `MessageOptions`.
+///
+/// ```
+/// # use protobuf::reflect::ExtensionRegistry;
+/// # use protobuf::reflect::FileDescriptor;
+/// # use protobuf::descriptor::FileDescriptorProto;
+/// # fn foo(options_file: &FileDescriptor, proto: FileDescriptorProto, deps: Vec<FileDescriptor>) {
+/// let mut registry = ExtensionRegistry::new();
+/// registry.add_file(options_file);
+/// let file = FileDescriptor::new_dynamic_with_extensions(proto, deps, &registry);
+/// for message in file.messages() {
+///     for option in message.custom_options() {
+///         println!("{} = {:?}", option.name(), option.value());
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ExtensionRegistry {
+    /// Extendee full name (without leading dot) to extensions by number.
+    by_extendee: HashMap<String, HashMap<u32, RegisteredExtension>>,
+}
+
+impl ExtensionRegistry {
+    /// Empty registry.
+    pub fn new() -> ExtensionRegistry {
+        ExtensionRegistry::default()
+    }
+
+    /// Is this registry empty.
+    pub fn is_empty(&self) -> bool {
+        self.by_extendee.is_empty()
+    }
+
+    /// Register all extensions declared in given file, including
+    /// extensions declared inside messages.
+    ///
+    /// # Panics
+    ///
+    /// If extension type cannot be found in the file or its dependencies.
+    pub fn add_file(&mut self, file: &FileDescriptor) {
+        for extension in file.extensions() {
+            self.by_extendee
+                .entry(extension.extendee_name().to_owned())
+                .or_default()
+                .insert(extension.number(), RegisteredExtension::new(extension));
+        }
+    }
+
+    /// Find registered extension of a message by field number.
+    ///
+    /// `extendee` is a full name of extended message without leading dot.
+    pub fn find_extension(&self, extendee: &str, number: u32) -> Option<&ExtensionDescriptor> {
+        self.by_extendee
+            .get(extendee)?
+            .get(&number)
+            .map(|e| &e.descriptor)
+    }
+
+    /// Find registered extension of a message by extension full name.
+    ///
+    /// `extendee` and `name` are full names without leading dot.
+    pub fn find_extension_by_name(
+        &self,
+        extendee: &str,
+        name: &str,
+    ) -> Option<&ExtensionDescriptor> {
+        self.by_extendee
+            .get(extendee)?
+            .values()
+            .map(|e| &e.descriptor)
+            .find(|e| e.full_name() == name)
+    }
+
+    /// Remove registered extensions from unknown fields of a message
+    /// and return their decoded values.
+    ///
+    /// Fields with values which cannot be decoded, e. g. because of wire type
+    /// mismatch, are left in unknown fields.
+    pub(crate) fn take_extensions(
+        &self,
+        extendee: &str,
+        unknown_fields: &mut UnknownFields,
+    ) -> Vec<(ExtensionDescriptor, Vec<ReflectValueBox>)> {
+        let extensions = match self.by_extendee.get(extendee) {
+            Some(extensions) => extensions,
+            None => return Vec::new(),
+        };
+        let decoded: Vec<(ExtensionDescriptor, Vec<ReflectValueBox>)> = unknown_fields
+            .iter()
+            .filter_map(|(number, values)| {
+                let extension = extensions.get(&number)?;
+                let mut decoded = Vec::new();
+                for v in values {
+                    decoded.extend(decode_value(extension, v)?);
+                }
+                Some((extension.descriptor.clone(), decoded))
+            })
+            .collect();
+        for (extension, _) in &decoded {
+            unknown_fields.remove(extension.number());
+        }
+        decoded
+    }
+
+    /// Decode unknown fields of an options message into custom options.
+    ///
+    /// `extendee` is a full name of options message without leading dot.
+    fn decode(&self, extendee: &str, unknown_fields: &UnknownFields) -> Vec<CustomOption> {
+        let extensions = match self.by_extendee.get(extendee) {
+            Some(extensions) => extensions,
+            None => return Vec::new(),
+        };
+        let mut r: Vec<CustomOption> = unknown_fields
+            .iter()
+            .filter_map(|(number, values)| {
+                let extension = extensions.get(&number)?;
+                let values = values
+                    .iter()
+                    .flat_map(|v| decode_values(extension, v))
+                    .collect();
+                Some(CustomOption {
+                    name: extension.descriptor.full_name(),
+                    number,
+                    values,
+                })
+            })
+            .collect();
+        r.sort_by_key(|o| o.number);
+        r
+    }
+}
+
+fn decode_scalar(
+    extension: &RegisteredExtension,
+    value: UnknownValueRef,
+) -> Option<ReflectValueBox> {
+    use field_descriptor_proto::Type;
+    Some(match (extension.proto_type, value) {
+        (Type::TYPE_INT32, UnknownValueRef::Varint(v)) => ReflectValueBox::I32(v as i32),
+        (Type::TYPE_INT64, UnknownValueRef::Varint(v)) => ReflectValueBox::I64(v as i64),
+        (Type::TYPE_UINT32, UnknownValueRef::Varint(v)) => ReflectValueBox::U32(v as u32),
+        (Type::TYPE_UINT64, UnknownValueRef::Varint(v)) => ReflectValueBox::U64(v),
+        (Type::TYPE_SINT32, UnknownValueRef::Varint(v)) => {
+            ReflectValueBox::I32(decode_zig_zag_32(v as u32))
+        }
+        (Type::TYPE_SINT64, UnknownValueRef::Varint(v)) => {
+            ReflectValueBox::I64(decode_zig_zag_64(v))
+        }
+        (Type::TYPE_BOOL, UnknownValueRef::Varint(v)) => ReflectValueBox::Bool(v != 0),
+        (Type::TYPE_ENUM, UnknownValueRef::Varint(v)) => match &extension.runtime_type {
+            RuntimeTypeBox::Enum(e) => ReflectValueBox::Enum(e.clone(), v as i32),
+            _ => unreachable!(),
+        },
+        (Type::TYPE_FIXED32, UnknownValueRef::Fixed32(v)) => ReflectValueBox::U32(v),
+        (Type::TYPE_SFIXED32, UnknownValueRef::Fixed32(v)) => ReflectValueBox::I32(v as i32),
+        (Type::TYPE_FLOAT, UnknownValueRef::Fixed32(v)) => ReflectValueBox::F32(f32::from_bits(v)),
+        (Type::TYPE_FIXED64, UnknownValueRef::Fixed64(v)) => ReflectValueBox::U64(v),
+        (Type::TYPE_SFIXED64, UnknownValueRef::Fixed64(v)) => ReflectValueBox::I64(v as i64),
+        (Type::TYPE_DOUBLE, UnknownValueRef::Fixed64(v)) => ReflectValueBox::F64(f64::from_bits(v)),
+        (Type::TYPE_STRING, UnknownValueRef::LengthDelimited(v)) => {
+            ReflectValueBox::String(String::from_utf8(v.to_vec()).ok()?)
+        }
+        (Type::TYPE_BYTES, UnknownValueRef::LengthDelimited(v)) => {
+            ReflectValueBox::Bytes(v.to_vec())
+        }
+        (Type::TYPE_MESSAGE, UnknownValueRef::LengthDelimited(v)) => {
+            match &extension.runtime_type {
+                RuntimeTypeBox::Message(d) => {
+                    let mut m = d.new_instance();
+                    m.merge_from_bytes_dyn(v).ok()?;
+                    ReflectValueBox::Message(m)
+                }
+                _ => unreachable!(),
+            }
+        }
+        _ => return None,
+    })
+}
+
+fn decode_packed(extension: &RegisteredExtension, bytes: &[u8]) -> Option<Vec<ReflectValueBox>> {
+    use field_descriptor_proto::Type;
+    let mut is = CodedInputStream::from_bytes(bytes);
+    let mut r = Vec::new();
+    while !is.eof().ok()? {
+        let value = match extension.proto_type {
+            Type::TYPE_FIXED32 | Type::TYPE_SFIXED32 | Type::TYPE_FLOAT => {
+                UnknownValueRef::Fixed32(is.read_raw_little_endian32().ok()?)
+            }
+            Type::TYPE_FIXED64 | Type::TYPE_SFIXED64 | Type::TYPE_DOUBLE => {
+                UnknownValueRef::Fixed64(is.read_raw_little_endian64().ok()?)
+            }
+            _ => UnknownValueRef::Varint(is.read_raw_varint64().ok()?),
+        };
+        r.push(decode_scalar(extension, value)?);
+    }
+    Some(r)
+}
+
+/// Decode a value of extension field; length-delimited value
+/// of numeric field is decoded as packed repeated field.
+///
+/// `None` is returned if wire type does not match the extension type.
+fn decode_value(
+    extension: &RegisteredExtension,
+    value: UnknownValueRef,
+) -> Option<Vec<ReflectValueBox>> {
+    use field_descriptor_proto::Type;
+    match (extension.proto_type, value) {
+        (Type::TYPE_STRING, v)
+        | (Type::TYPE_BYTES, v)
+        | (Type::TYPE_MESSAGE, v)
+        | (Type::TYPE_GROUP, v) => decode_scalar(extension, v).map(|v| vec![v]),
+        (_, UnknownValueRef::LengthDelimited(bytes)) => decode_packed(extension, bytes),
+        (_, v) => decode_scalar(extension, v).map(|v| vec![v]),
+    }
+}
+
+/// Decode a value of extension field skipping values
+/// with wire type which does not match the extension type.
+fn decode_values(extension: &RegisteredExtension, value: UnknownValueRef) -> Vec<ReflectValueBox> {
+    decode_value(extension, value).unwrap_or_default()
+}
+
+/// Decode all values of given extension stored in unknown fields of an options message.
+///
+/// Nothing is decoded if the extension type cannot be resolved.
+pub(crate) fn decode_extension(
+    extension: &ExtensionDescriptor,
+    unknown_fields: &UnknownFields,
+) -> Vec<ReflectValueBox> {
+    let registered = match RegisteredExtension::try_new(extension.clone()) {
+        Ok(registered) => registered,
+        Err(_) => return Vec::new(),
+    };
+    if registered.proto_type == field_descriptor_proto::Type::TYPE_GROUP {
+        return unknown_fields
+            .groups(extension.number())
+            .iter()
+            .filter_map(|contents| decode_group(&registered, contents))
+            .collect();
+    }
+    let values = match unknown_fields.get(extension.number()) {
+        Some(values) => values,
+        None => return Vec::new(),
+    };
+    values
+        .iter()
+        .flat_map(|v| decode_values(&registered, v))
+        .collect()
+}
+
+/// Decode group from its encoded contents.
+fn decode_group(extension: &RegisteredExtension, contents: &[u8]) -> Option<ReflectValueBox> {
+    match &extension.runtime_type {
+        RuntimeTypeBox::Message(d) => {
+            let mut m = d.new_instance();
+            m.merge_from_bytes_dyn(contents).ok()?;
+            Some(ReflectValueBox::Message(m))
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Custom option value decoded using [`ExtensionRegistry`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomOption {
+    name: String,
+    number: u32,
+    values: Vec<ReflectValueBox>,
+}
+
+impl CustomOption {
+    /// Fully-qualified extension name without leading dot, e.g. `my.pkg.redact`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Extension field number.
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    /// Option value; for options set multiple times this is the last value.
+    pub fn value(&self) -> Option<&ReflectValueBox> {
+        self.values.last()
+    }
+
+    /// All option values in order, useful for repeated options.
+    pub fn values(&self) -> &[ReflectValueBox] {
+        &self.values
+    }
+}
+
+/// Custom options of dynamic file descriptor elements.
+#[derive(Debug, Default)]
+pub(crate) struct CustomOptionsIndex {
+    pub file: Vec<CustomOption>,
+    /// By message index in file.
+    pub messages: Vec<Vec<CustomOption>>,
+    /// By message index in file, then by field index in message.
+    pub fields: Vec<Vec<Vec<CustomOption>>>,
+}
+
+impl CustomOptionsIndex {
+    pub fn index(
+        proto: &FileDescriptorProto,
+        index: &FileIndex,
+        registry: &ExtensionRegistry,
+    ) -> CustomOptionsIndex {
+        let messages: Vec<&DescriptorProto> = index
+            .messages
+            .iter()
+            .map(|m| m.path.eval(proto).unwrap())
+            .collect();
+        CustomOptionsIndex {
+            file: registry.decode(
+                "google.protobuf.FileOptions",
+                proto.options.get_or_default().get_unknown_fields(),
+            ),
+            messages: messages
+                .iter()
+                .map(|m| {
+                    registry.decode(
+                        "google.protobuf.MessageOptions",
+                        m.options.get_or_default().get_unknown_fields(),
+                    )
+                })
+                .collect(),
+            fields: messages
+                .iter()
+                .map(|m| {
+                    m.field
+                        .iter()
+                        .map(|f| {
+                            registry.decode(
+                                "google.protobuf.FieldOptions",
+                                f.options.get_or_default().get_unknown_fields(),
+                            )
+                        })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,390 @@
+//! Reflective comparison of two messages of the same type.
+//!
+//! [`diff`] reports each difference as a path to the field with the values
+//! before and after, which is useful in tests and change-data-capture tooling:
+//!
+//! ```
+//! # use protobuf::MessageDyn;
+//! use protobuf::reflect::diff::*;
+//!
+//! # fn print_changes(before: &dyn MessageDyn, after: &dyn MessageDyn) {
+//! let options = DiffOptions {
+//!     ignore_repeated_order: true,
+//!     ..DiffOptions::default()
+//! };
+//! for d in diff(before, after, &options) {
+//!     println!("{}: {:?} -> {:?}", d.path, d.before, d.after);
+//! }
+//! # }
+//! ```
+
+use crate::reflect::has_explicit_presence;
+use crate::reflect::reflect_eq::ReflectEq;
+use crate::reflect::FieldPath;
+use crate::reflect::FieldPathElement;
+use crate::reflect::ReflectEqMode;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectMapRef;
+use crate::reflect::ReflectRepeatedRef;
+use crate::reflect::ReflectValueRef;
+use crate::text_format::compare_map_keys;
+use crate::MessageDyn;
+use crate::UnknownFields;
+use crate::UnknownValueRef;
+
+/// Options of [`diff`].
+#[derive(Debug, Clone, Default)]
+pub struct DiffOptions {
+    /// Compare repeated fields as multisets, so reordered elements are not a difference.
+    ///
+    /// Elements which are only in one message are reported
+    /// with their index in that message.
+    pub ignore_repeated_order: bool,
+    /// Report differences of unknown fields. Unknown fields are named by their numbers.
+    pub compare_unknown_fields: bool,
+}
+
+/// Difference of a field of two messages.
+#[derive(Debug, Clone)]
+pub struct FieldDiff<'a> {
+    /// Path to the field, repeated element or map value.
+    pub path: FieldPath,
+    /// Value in the first message, `None` if not set.
+    pub before: Option<ReflectValueRef<'a>>,
+    /// Value in the second message, `None` if not set.
+    pub after: Option<ReflectValueRef<'a>>,
+}
+
+/// Compare two messages of the same type field by field.
+///
+/// Message fields set in both messages are compared recursively,
+/// other values are reported as a whole. Differences are reported
+/// in field declaration order, map entries are ordered by key.
+/// Float `NaN` values are considered equal to each other.
+///
+/// # Panics
+///
+/// If messages have different types.
+pub fn diff<'a>(
+    before: &'a dyn MessageDyn,
+    after: &'a dyn MessageDyn,
+    options: &DiffOptions,
+) -> Vec<FieldDiff<'a>> {
+    let mut differ = Differ {
+        options,
+        path: FieldPath::new(),
+        diffs: Vec::new(),
+    };
+    differ.messages(before, after);
+    differ.diffs
+}
+
+struct Differ<'o, 'a> {
+    options: &'o DiffOptions,
+    path: FieldPath,
+    diffs: Vec<FieldDiff<'a>>,
+}
+
+impl<'o, 'a> Differ<'o, 'a> {
+    fn report(&mut self, before: Option<ReflectValueRef<'a>>, after: Option<ReflectValueRef<'a>>) {
+        self.diffs.push(FieldDiff {
+            path: self.path.clone(),
+            before,
+            after,
+        });
+    }
+
+    fn messages(&mut self, before: &'a dyn MessageDyn, after: &'a dyn MessageDyn) {
+        let descriptor = before.descriptor_dyn();
+        assert_eq!(descriptor, after.descriptor_dyn());
+        for field in descriptor.fields() {
+            self.path
+                .push(FieldPathElement::Field(field.get_name().to_owned()));
+            match (field.get_reflect(before), field.get_reflect(after)) {
+                (ReflectFieldRef::Optional(b), ReflectFieldRef::Optional(a)) => {
+                    // Dynamic messages may store default values of fields without presence
+                    let set =
+                        |v: &ReflectValueRef| has_explicit_presence(&field) || v.is_non_zero();
+                    self.optional(b.filter(set), a.filter(set));
+                }
+                (ReflectFieldRef::Repeated(b), ReflectFieldRef::Repeated(a)) => {
+                    if self.options.ignore_repeated_order {
+                        self.repeated_unordered(&b, &a);
+                    } else {
+                        self.repeated(&b, &a);
+                    }
+                }
+                (ReflectFieldRef::Map(b), ReflectFieldRef::Map(a)) => self.map(&b, &a),
+                _ => unreachable!(),
+            }
+            self.path.pop();
+        }
+        if self.options.compare_unknown_fields {
+            self.unknown_fields(
+                before.get_unknown_fields_dyn(),
+                after.get_unknown_fields_dyn(),
+            );
+        }
+    }
+
+    fn optional(
+        &mut self,
+        before: Option<ReflectValueRef<'a>>,
+        after: Option<ReflectValueRef<'a>>,
+    ) {
+        match (before, after) {
+            (None, None) => {}
+            (Some(b), Some(a)) => self.values(b, a),
+            (b, a) => self.report(b, a),
+        }
+    }
+
+    fn values(&mut self, before: ReflectValueRef<'a>, after: ReflectValueRef<'a>) {
+        if let (ReflectValueRef::Message(b), ReflectValueRef::Message(a)) = (&before, &after) {
+            if let (Some(b), Some(a)) = (b.as_borrowed(), a.as_borrowed()) {
+                self.messages(b, a);
+                return;
+            }
+        }
+        if !before.reflect_eq(&after, &ReflectEqMode::nan_equal()) {
+            self.report(Some(before), Some(after));
+        }
+    }
+
+    /// Values have no differences with the same options.
+    fn equal(&self, before: ReflectValueRef<'a>, after: ReflectValueRef<'a>) -> bool {
+        let mut differ = Differ {
+            options: self.options,
+            path: FieldPath::new(),
+            diffs: Vec::new(),
+        };
+        differ.values(before, after);
+        differ.diffs.is_empty()
+    }
+
+    fn repeated(&mut self, before: &ReflectRepeatedRef<'a>, after: &ReflectRepeatedRef<'a>) {
+        for i in 0..before.len().max(after.len()) {
+            self.path.push(FieldPathElement::Index(i));
+            self.optional(
+                Some(i).filter(|&i| i < before.len()).map(|i| before.get(i)),
+                Some(i).filter(|&i| i < after.len()).map(|i| after.get(i)),
+            );
+            self.path.pop();
+        }
+    }
+
+    fn repeated_unordered(
+        &mut self,
+        before: &ReflectRepeatedRef<'a>,
+        after: &ReflectRepeatedRef<'a>,
+    ) {
+        let mut matched = vec![false; after.len()];
+        let mut removed = Vec::new();
+        for i in 0..before.len() {
+            let found =
+                (0..after.len()).find(|&j| !matched[j] && self.equal(before.get(i), after.get(j)));
+            match found {
+                Some(j) => matched[j] = true,
+                None => removed.push(i),
+            }
+        }
+        for i in removed {
+            self.path.push(FieldPathElement::Index(i));
+            self.report(Some(before.get(i)), None);
+            self.path.pop();
+        }
+        for (j, _) in matched.iter().enumerate().filter(|(_, &m)| !m) {
+            self.path.push(FieldPathElement::Index(j));
+            self.report(None, Some(after.get(j)));
+            self.path.pop();
+        }
+    }
+
+    fn map(&mut self, before: &ReflectMapRef<'a>, after: &ReflectMapRef<'a>) {
+        let mut keys: Vec<ReflectValueRef<'a>> = before.into_iter().map(|(k, _)| k).collect();
+        keys.extend(
+            after
+                .into_iter()
+                .map(|(k, _)| k)
+                .filter(|k| before.get(k.clone()).is_none()),
+        );
+        keys.sort_by(compare_map_keys);
+
+        for key in keys {
+            self.path.push(FieldPathElement::MapKey(key.to_box()));
+            self.optional(before.get(key.clone()), after.get(key));
+            self.path.pop();
+        }
+    }
+
+    fn unknown_fields(&mut self, before: &'a UnknownFields, after: &'a UnknownFields) {
+        let mut numbers: Vec<u32> = before.iter().chain(after.iter()).map(|(n, _)| n).collect();
+        numbers.sort();
+        numbers.dedup();
+
+        let values = |fields: &'a UnknownFields, number: u32| -> Vec<ReflectValueRef<'a>> {
+            match fields.get(number) {
+                Some(values) => values.iter().map(unknown_value_ref).collect(),
+                None => Vec::new(),
+            }
+        };
+        for number in numbers {
+            let b = values(before, number);
+            let a = values(after, number);
+            self.path.push(FieldPathElement::Field(number.to_string()));
+            if b.len() <= 1 && a.len() <= 1 {
+                self.optional(b.into_iter().next(), a.into_iter().next());
+            } else {
+                for i in 0..b.len().max(a.len()) {
+                    self.path.push(FieldPathElement::Index(i));
+                    self.optional(b.get(i).cloned(), a.get(i).cloned());
+                    self.path.pop();
+                }
+            }
+            self.path.pop();
+        }
+    }
+}
+
+fn unknown_value_ref(value: UnknownValueRef) -> ReflectValueRef {
+    match value {
+        UnknownValueRef::Fixed32(v) => ReflectValueRef::U32(v),
+        UnknownValueRef::Fixed64(v) => ReflectValueRef::U64(v),
+        UnknownValueRef::Varint(v) => ReflectValueRef::U64(v),
+        UnknownValueRef::LengthDelimited(v) => ReflectValueRef::Bytes(v),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::well_known_types::Struct;
+    use crate::well_known_types::Value;
+
+    fn paths(diffs: &[FieldDiff]) -> Vec<String> {
+        diffs.iter().map(|d| d.path.to_string()).collect()
+    }
+
+    fn message(name: &str) -> DescriptorProto {
+        let mut message = DescriptorProto::new();
+        message.set_name(name.to_owned());
+        message
+    }
+
+    #[test]
+    fn singular() {
+        let mut before = FileDescriptorProto::new();
+        before.set_name("a.proto".to_owned());
+        before.options.mut_or_default();
+        let mut after = before.clone();
+        assert!(diff(&before, &after, &DiffOptions::default()).is_empty());
+
+        after.set_name("b.proto".to_owned());
+        after
+            .options
+            .mut_or_default()
+            .set_java_package("p".to_owned());
+        let diffs = diff(&before, &after, &DiffOptions::default());
+        assert_eq!(vec!["name", "options.java_package"], paths(&diffs));
+        assert_eq!(Some(ReflectValueRef::String("a.proto")), diffs[0].before);
+        assert_eq!(Some(ReflectValueRef::String("b.proto")), diffs[0].after);
+        assert_eq!(None, diffs[1].before);
+        assert_eq!(Some(ReflectValueRef::String("p")), diffs[1].after);
+
+        // message set only in one message is reported as a whole
+        before.options.clear();
+        assert_eq!(
+            vec!["name", "options"],
+            paths(&diff(&before, &after, &DiffOptions::default()))
+        );
+    }
+
+    #[test]
+    fn repeated() {
+        let mut before = FileDescriptorProto::new();
+        before.dependency = vec!["a".to_owned(), "b".to_owned()];
+        before.message_type = vec![message("M"), message("N")];
+        let mut after = FileDescriptorProto::new();
+        after.dependency = vec!["b".to_owned(), "a".to_owned(), "c".to_owned()];
+        after.message_type = vec![message("N"), message("M")];
+        after.message_type[1].field.push(Default::default());
+
+        assert_eq!(
+            vec![
+                "dependency[0]",
+                "dependency[1]",
+                "dependency[2]",
+                "message_type[0].name",
+                "message_type[1].name",
+                "message_type[1].field[0]",
+            ],
+            paths(&diff(&before, &after, &DiffOptions::default()))
+        );
+
+        let options = DiffOptions {
+            ignore_repeated_order: true,
+            ..DiffOptions::default()
+        };
+        let diffs = diff(&before, &after, &options);
+        assert_eq!(
+            vec!["dependency[2]", "message_type[0]", "message_type[1]"],
+            paths(&diffs)
+        );
+        assert_eq!(None, diffs[0].before);
+        assert_eq!(Some(ReflectValueRef::String("c")), diffs[0].after);
+        // message with added field is reported as removed and added
+        assert!(diffs[1].before.is_some() && diffs[1].after.is_none());
+        assert!(diffs[2].before.is_none() && diffs[2].after.is_some());
+    }
+
+    #[test]
+    fn map() {
+        fn number(n: f64) -> Value {
+            let mut value = Value::new();
+            value.set_number_value(n);
+            value
+        }
+
+        let mut before = Struct::new();
+        before.fields.insert("x".to_owned(), number(1.0));
+        before.fields.insert("y".to_owned(), number(2.0));
+        before.fields.insert("n".to_owned(), number(f64::NAN));
+        let mut after = Struct::new();
+        after.fields.insert("y".to_owned(), number(3.0));
+        after.fields.insert("z".to_owned(), number(4.0));
+        after.fields.insert("n".to_owned(), number(f64::NAN));
+
+        let diffs = diff(&before, &after, &DiffOptions::default());
+        assert_eq!(
+            vec![
+                "fields[\"x\"]",
+                "fields[\"y\"].number_value",
+                "fields[\"z\"]"
+            ],
+            paths(&diffs)
+        );
+        assert_eq!(Some(ReflectValueRef::F64(2.0)), diffs[1].before);
+        assert_eq!(Some(ReflectValueRef::F64(3.0)), diffs[1].after);
+    }
+
+    #[test]
+    fn unknown_fields() {
+        let before = FileDescriptorProto::new();
+        let mut after = FileDescriptorProto::new();
+        after.mut_unknown_fields_dyn().add_varint(100, 1);
+        after.mut_unknown_fields_dyn().add_varint(200, 2);
+        after.mut_unknown_fields_dyn().add_varint(200, 3);
+
+        assert!(diff(&before, &after, &DiffOptions::default()).is_empty());
+
+        let options = DiffOptions {
+            compare_unknown_fields: true,
+            ..DiffOptions::default()
+        };
+        let diffs = diff(&before, &after, &options);
+        assert_eq!(vec!["100", "200[0]", "200[1]"], paths(&diffs));
+        assert_eq!(Some(ReflectValueRef::U64(3)), diffs[2].after);
+    }
+}
@@ -0,0 +1,227 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::reflect::dynamic::DynamicMessage;
+use crate::reflect::MessageDescriptor;
+use crate::Clear;
+use crate::CodedInputStream;
+use crate::MessageDyn;
+use crate::ProtobufResult;
+
+/// Pool of dynamic messages reused to parse many message trees.
+///
+/// Parsing a dynamic message allocates the message, its field table
+/// and the vectors and maps of repeated and map fields, for every nested message.
+/// Messages returned to the arena with [`recycle`](DynamicArena::recycle)
+/// are cleared keeping these allocations, and reused by subsequent
+/// [`parse`](DynamicArena::parse) calls, so parsing a stream of similar
+/// messages allocates little after the first few messages.
+///
+/// Memory held by the arena is freed when the arena is dropped
+/// or [cleared](DynamicArena::clear).
+///
+/// Only dynamic messages (messages of descriptors created with
+/// [`FileDescriptor::new_dynamic`](crate::reflect::FileDescriptor::new_dynamic))
+/// are pooled, generated messages are allocated and dropped as usual.
+///
+/// ```
+/// # use protobuf::descriptor::FileDescriptorProto;
+/// # use protobuf::reflect::DynamicArena;
+/// # use protobuf::reflect::FileDescriptor;
+/// # use protobuf::Message;
+/// # let file = FileDescriptor::new_dynamic(
+/// #     FileDescriptorProto::descriptor_static().file_descriptor_proto().clone(),
+/// #     Vec::new(),
+/// # );
+/// # let descriptor = file.message_by_package_relative_name("FileDescriptorProto").unwrap();
+/// # let rows = vec![FileDescriptorProto::new().write_to_bytes().unwrap()];
+/// let arena = DynamicArena::new();
+/// for row in &rows {
+///     let message = arena.parse(&descriptor, row).unwrap();
+///     // process the message
+///     arena.recycle(message);
+/// }
+/// ```
+#[derive(Default)]
+pub struct DynamicArena {
+    free: RefCell<HashMap<MessageDescriptor, Vec<Box<dyn MessageDyn>>>>,
+}
+
+impl fmt::Debug for DynamicArena {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynamicArena")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl DynamicArena {
+    /// Create an empty arena.
+    pub fn new() -> DynamicArena {
+        DynamicArena::default()
+    }
+
+    /// Empty message of given type, reusing a recycled message if available.
+    pub fn new_instance(&self, descriptor: &MessageDescriptor) -> Box<dyn MessageDyn> {
+        let recycled = self
+            .free
+            .borrow_mut()
+            .get_mut(descriptor)
+            .and_then(|free| free.pop());
+        match recycled {
+            Some(m) => m,
+            None => descriptor.new_instance(),
+        }
+    }
+
+    /// Parse message of given type, allocating the message
+    /// and all nested dynamic messages from the arena.
+    pub fn parse(
+        &self,
+        descriptor: &MessageDescriptor,
+        bytes: &[u8],
+    ) -> ProtobufResult<Box<dyn MessageDyn>> {
+        let mut message = self.new_instance(descriptor);
+        match message.downcast_mut::<DynamicMessage>() {
+            Some(m) => {
+                let mut is = CodedInputStream::from_bytes(bytes);
+                m.set_fields_default();
+                m.merge_fields(&mut is, None, Some(self))?;
+            }
+            None => message.merge_from_bytes_dyn(bytes)?,
+        }
+        message.check_initialized_dyn()?;
+        Ok(message)
+    }
+
+    /// Return message and all its nested dynamic messages to the arena.
+    ///
+    /// Messages are cleared, but the memory of their fields is kept
+    /// to be reused by messages returned from [`parse`](DynamicArena::parse)
+    /// and [`new_instance`](DynamicArena::new_instance).
+    pub fn recycle(&self, message: Box<dyn MessageDyn>) {
+        let mut free = self.free.borrow_mut();
+        let mut stack = vec![message];
+        while let Some(mut message) = stack.pop() {
+            let m = match message.downcast_mut::<DynamicMessage>() {
+                Some(m) => m,
+                None => continue,
+            };
+            m.take_nested_messages(&mut stack);
+            m.clear();
+            let descriptor = m.descriptor.clone();
+            free.entry(descriptor).or_default().push(message);
+        }
+    }
+
+    /// Number of messages available for reuse.
+    pub fn len(&self) -> usize {
+        self.free.borrow().values().map(Vec::len).sum()
+    }
+
+    /// True iff there are no messages available for reuse.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Free all recycled messages.
+    pub fn clear(&self) {
+        self.free.borrow_mut().clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FieldDescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::reflect::FileDescriptor;
+    use crate::well_known_types::Struct;
+    use crate::well_known_types::Value;
+    use crate::Message;
+
+    fn dynamic_descriptor(descriptor: &MessageDescriptor) -> MessageDescriptor {
+        FileDescriptor::new_dynamic(descriptor.file_descriptor_proto().clone(), Vec::new())
+            .message_by_package_relative_name(descriptor.name())
+            .unwrap()
+    }
+
+    fn file(name: &str) -> FileDescriptorProto {
+        let mut message = DescriptorProto::new();
+        message.set_name("M".to_owned());
+        message.field.push(FieldDescriptorProto::new());
+        message.field.push(FieldDescriptorProto::new());
+        let mut file = FileDescriptorProto::new();
+        file.set_name(name.to_owned());
+        file.message_type.push(message.clone());
+        file.message_type.push(message);
+        file.options
+            .mut_or_default()
+            .set_java_package("p".to_owned());
+        file
+    }
+
+    #[test]
+    fn reuse() {
+        let descriptor = dynamic_descriptor(&FileDescriptorProto::descriptor_static());
+        let arena = DynamicArena::new();
+
+        let bytes = file("a.proto").write_to_bytes().unwrap();
+        let message = arena.parse(&descriptor, &bytes).unwrap();
+        assert_eq!(bytes, message.write_to_bytes_dyn().unwrap());
+        assert!(arena.is_empty());
+
+        arena.recycle(message);
+        // file, options, two messages and four fields
+        assert_eq!(8, arena.len());
+
+        let bytes = file("b.proto").write_to_bytes().unwrap();
+        let message = arena.parse(&descriptor, &bytes).unwrap();
+        assert_eq!(bytes, message.write_to_bytes_dyn().unwrap());
+        assert!(arena.is_empty());
+
+        // Recycled message is empty
+        arena.recycle(message);
+        let empty = arena.new_instance(&descriptor);
+        assert_eq!(Vec::<u8>::new(), empty.write_to_bytes_dyn().unwrap());
+
+        arena.clear();
+        assert!(arena.is_empty());
+    }
+
+    #[test]
+    fn map() {
+        let descriptor = dynamic_descriptor(&Struct::descriptor_static());
+        let arena = DynamicArena::new();
+
+        let mut s = Struct::new();
+        for k in &["a", "b"] {
+            let mut v = Value::new();
+            v.set_string_value(k.to_string());
+            s.fields.insert(k.to_string(), v);
+        }
+        let bytes = s.write_to_bytes().unwrap();
+        let message = arena.parse(&descriptor, &bytes).unwrap();
+        let parsed = Struct::parse_from_bytes(&message.write_to_bytes_dyn().unwrap()).unwrap();
+        assert_eq!(s, parsed);
+
+        arena.recycle(message);
+        assert_eq!(3, arena.len());
+    }
+
+    #[test]
+    fn generated() {
+        let descriptor = FileDescriptorProto::descriptor_static();
+        let arena = DynamicArena::new();
+        let bytes = file("a.proto").write_to_bytes().unwrap();
+        let message = arena.parse(&descriptor, &bytes).unwrap();
+        assert_eq!(
+            Some(&file("a.proto")),
+            message.downcast_ref::<FileDescriptorProto>()
+        );
+        arena.recycle(message);
+        assert!(arena.is_empty());
+    }
+}
@@ -0,0 +1,554 @@
+use std::collections::BTreeMap;
+use std::ops::RangeBounds;
+
+use crate::descriptor::field_descriptor_proto::Type;
+use crate::reflect::dynamic::compute_singular_size;
+use crate::reflect::dynamic::singular_write_to;
+use crate::reflect::ExtensionDescriptor;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::rt::read_unknown_or_skip_group;
+use crate::CodedInputStream;
+use crate::CodedOutputStream;
+use crate::ProtobufResult;
+use crate::UnknownFields;
+
+/// Extension with its values.
+#[derive(Debug, Clone)]
+struct DynamicExtension {
+    descriptor: ExtensionDescriptor,
+    /// Type of extension value, or element type for repeated extensions.
+    runtime_type: RuntimeTypeBox,
+    repeated: bool,
+    /// At most one value for singular extension.
+    values: Vec<ReflectValueBox>,
+}
+
+impl DynamicExtension {
+    fn new(descriptor: ExtensionDescriptor) -> DynamicExtension {
+        let (runtime_type, repeated) = match descriptor.runtime_field_type() {
+            RuntimeFieldType::Singular(t) => (t, false),
+            RuntimeFieldType::Repeated(t) => (t, true),
+            RuntimeFieldType::Map(..) => unreachable!(),
+        };
+        DynamicExtension {
+            descriptor,
+            runtime_type,
+            repeated,
+            values: Vec::new(),
+        }
+    }
+
+    /// Append values to repeated extension, or replace value of singular extension
+    /// with the last value.
+    fn merge(&mut self, values: Vec<ReflectValueBox>) {
+        for v in &values {
+            assert_eq!(
+                self.runtime_type,
+                v.get_type(),
+                "wrong value type for extension {}",
+                self.descriptor.full_name()
+            );
+        }
+        if self.repeated {
+            self.values.extend(values);
+        } else if let Some(v) = values.into_iter().last() {
+            self.values = vec![v];
+        }
+    }
+
+    fn compute_size(&self) -> u64 {
+        let proto_type = self.descriptor.get_proto().get_field_type();
+        self.values
+            .iter()
+            .map(|v| {
+                compute_singular_size(
+                    &self.runtime_type,
+                    &proto_type,
+                    self.descriptor.number(),
+                    &v.as_value_ref(),
+                ) as u64
+            })
+            .sum()
+    }
+
+    fn write_to(&self, os: &mut CodedOutputStream) -> ProtobufResult<()> {
+        let proto_type = self.descriptor.get_proto().get_field_type();
+        for v in &self.values {
+            singular_write_to(
+                &self.runtime_type,
+                &proto_type,
+                self.descriptor.number(),
+                &v.as_value_ref(),
+                os,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Decoded extensions of a dynamic message, by field number.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DynamicExtensions {
+    by_number: BTreeMap<u32, DynamicExtension>,
+}
+
+impl DynamicExtensions {
+    pub fn get(&self, extension: &ExtensionDescriptor) -> Option<&[ReflectValueBox]> {
+        match self.by_number.get(&extension.number()) {
+            Some(e) if &e.descriptor == extension => Some(&e.values),
+            _ => None,
+        }
+    }
+
+    pub fn merge(&mut self, extension: ExtensionDescriptor, values: Vec<ReflectValueBox>) {
+        let number = extension.number();
+        let e = match self.by_number.get_mut(&number) {
+            Some(e) if e.descriptor == extension => e,
+            _ => {
+                self.by_number
+                    .insert(number, DynamicExtension::new(extension));
+                self.by_number.get_mut(&number).unwrap()
+            }
+        };
+        e.merge(values);
+    }
+
+    pub fn set(&mut self, extension: ExtensionDescriptor, values: Vec<ReflectValueBox>) {
+        self.remove(extension.number());
+        if !values.is_empty() {
+            self.merge(extension, values);
+        }
+    }
+
+    pub fn remove(&mut self, number: u32) {
+        self.by_number.remove(&number);
+    }
+
+    pub fn clear(&mut self) {
+        self.by_number.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&ExtensionDescriptor, &[ReflectValueBox])> {
+        self.by_number
+            .values()
+            .map(|e| (&e.descriptor, &e.values[..]))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &ReflectValueBox> {
+        self.by_number.values().flat_map(|e| e.values.iter())
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut ReflectValueBox> {
+        self.by_number
+            .values_mut()
+            .flat_map(|e| e.values.iter_mut())
+    }
+
+    pub fn compute_size(&self) -> u64 {
+        self.by_number.values().map(|e| e.compute_size()).sum()
+    }
+
+    /// Write extensions with field numbers in given range,
+    /// so extensions can be interleaved with fields in field number order.
+    pub fn write_range(
+        &self,
+        numbers: impl RangeBounds<u32>,
+        os: &mut CodedOutputStream,
+    ) -> ProtobufResult<()> {
+        for (_, e) in self.by_number.range(numbers) {
+            e.write_to(os)?;
+        }
+        Ok(())
+    }
+}
+
+/// Replace extension values stored in unknown fields.
+///
+/// Unknown fields cannot hold decoded groups, so values of group-typed
+/// extensions are stored as encoded group contents.
+pub(crate) fn set_extension_in_unknown_fields(
+    extension: &ExtensionDescriptor,
+    values: Vec<ReflectValueBox>,
+    unknown_fields: &mut UnknownFields,
+) {
+    let mut e = DynamicExtension::new(extension.clone());
+    e.merge(values);
+    unknown_fields.remove(extension.number());
+
+    if extension.get_proto().get_field_type() == Type::TYPE_GROUP {
+        for v in &e.values {
+            let m = match v {
+                ReflectValueBox::Message(m) => m,
+                _ => unreachable!(),
+            };
+            let mut contents = Vec::with_capacity(m.compute_size_dyn() as usize);
+            {
+                let mut os = CodedOutputStream::vec(&mut contents);
+                m.write_to_with_cached_sizes_dyn(&mut os)
+                    .expect("write to vec");
+                os.flush().expect("write to vec");
+            }
+            unknown_fields.add_group(extension.number(), contents);
+        }
+        return;
+    }
+
+    let mut bytes = Vec::new();
+    {
+        let mut os = CodedOutputStream::vec(&mut bytes);
+        // Compute sizes of nested messages
+        e.compute_size();
+        e.write_to(&mut os).expect("write to vec");
+        os.flush().expect("write to vec");
+    }
+
+    let mut is = CodedInputStream::from_bytes(&bytes);
+    while !is.eof().expect("read written bytes") {
+        let (number, wire_type) = is.read_tag_unpack().expect("read written bytes");
+        read_unknown_or_skip_group(number, wire_type, &mut is, unknown_fields)
+            .expect("read written bytes");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::descriptor::descriptor_proto::ExtensionRange;
+    use crate::descriptor::field_descriptor_proto::Label;
+    use crate::descriptor::field_descriptor_proto::Type;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FieldDescriptorProto;
+    use crate::descriptor::FieldOptions;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::json;
+    use crate::reflect::ExtensionDescriptor;
+    use crate::reflect::ExtensionRegistry;
+    use crate::reflect::FileDescriptor;
+    use crate::reflect::ReflectValueBox;
+    use crate::reflect::ReflectValueRef;
+    use crate::text_format;
+    use crate::MessageDyn;
+
+    fn field(name: &str, number: i32, label: Label, t: Type) -> FieldDescriptorProto {
+        let mut field = FieldDescriptorProto::new();
+        field.set_name(name.to_owned());
+        field.set_number(number);
+        field.set_label(label);
+        field.set_field_type(t);
+        field
+    }
+
+    fn extension(
+        name: &str,
+        number: i32,
+        label: Label,
+        t: Type,
+        extendee: &str,
+    ) -> FieldDescriptorProto {
+        let mut field = field(name, number, label, t);
+        field.set_extendee(extendee.to_owned());
+        if t == Type::TYPE_MESSAGE || t == Type::TYPE_GROUP {
+            field.set_type_name(".e.M".to_owned());
+        }
+        field
+    }
+
+    /// `message M { optional int32 a = 1; optional int32 z = 300; extensions 100 to 200; }`
+    /// with extensions
+    /// of `M` and `google.protobuf.FieldOptions`.
+    fn file() -> FileDescriptor {
+        let mut message = DescriptorProto::new();
+        message.set_name("M".to_owned());
+        message
+            .field
+            .push(field("a", 1, Label::LABEL_OPTIONAL, Type::TYPE_INT32));
+        message
+            .field
+            .push(field("z", 300, Label::LABEL_OPTIONAL, Type::TYPE_INT32));
+        let mut range = ExtensionRange::new();
+        range.set_start(100);
+        range.set_end(201);
+        message.extension_range.push(range);
+
+        let mut file = FileDescriptorProto::new();
+        file.set_name("e.proto".to_owned());
+        file.set_package("e".to_owned());
+        file.dependency
+            .push("google/protobuf/descriptor.proto".to_owned());
+        file.message_type.push(message);
+        file.extension.push(extension(
+            "s",
+            100,
+            Label::LABEL_OPTIONAL,
+            Type::TYPE_STRING,
+            ".e.M",
+        ));
+        file.extension.push(extension(
+            "r",
+            101,
+            Label::LABEL_REPEATED,
+            Type::TYPE_SINT32,
+            ".e.M",
+        ));
+        file.extension.push(extension(
+            "m",
+            102,
+            Label::LABEL_OPTIONAL,
+            Type::TYPE_MESSAGE,
+            ".e.M",
+        ));
+        file.extension.push(extension(
+            "o",
+            50000,
+            Label::LABEL_OPTIONAL,
+            Type::TYPE_INT64,
+            ".google.protobuf.FieldOptions",
+        ));
+        file.extension.push(extension(
+            "g",
+            50001,
+            Label::LABEL_OPTIONAL,
+            Type::TYPE_GROUP,
+            ".google.protobuf.FieldOptions",
+        ));
+        FileDescriptor::new_dynamic(file, vec![crate::descriptor::file_descriptor()])
+    }
+
+    fn find(file: &FileDescriptor, name: &str) -> ExtensionDescriptor {
+        file.extensions()
+            .into_iter()
+            .find(|e| e.get_name() == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn dynamic() {
+        let file = file();
+        let descriptor = file.message_by_package_relative_name("M").unwrap();
+        let (s, r, m) = (find(&file, "s"), find(&file, "r"), find(&file, "m"));
+
+        let mut nested = descriptor.new_instance();
+        nested.set_extension_dyn(&s, vec![ReflectValueBox::String("nested".to_owned())]);
+        let mut message = descriptor.new_instance();
+        message.set_extension_dyn(&s, vec![ReflectValueBox::String("x".to_owned())]);
+        message.set_extension_dyn(&r, vec![ReflectValueBox::I32(-1), ReflectValueBox::I32(2)]);
+        message.set_extension_dyn(&m, vec![ReflectValueBox::Message(nested)]);
+        assert!(message.get_unknown_fields_dyn().iter().next().is_none());
+        let bytes = message.write_to_bytes_dyn().unwrap();
+
+        // Without registry extensions are decoded from unknown fields
+        let parsed = descriptor
+            .parse_from_bytes_with_extensions(&bytes, &ExtensionRegistry::new())
+            .unwrap();
+        assert!(parsed.get_unknown_fields_dyn().get(100).is_some());
+        assert_eq!(
+            vec![ReflectValueBox::I32(-1), ReflectValueBox::I32(2)],
+            parsed.get_extension_dyn(&r)
+        );
+
+        let mut registry = ExtensionRegistry::new();
+        registry.add_file(&file);
+        assert_eq!(Some(&s), registry.find_extension("e.M", 100));
+        let parsed = descriptor
+            .parse_from_bytes_with_extensions(&bytes, &registry)
+            .unwrap();
+        assert!(parsed.get_unknown_fields_dyn().iter().next().is_none());
+        assert_eq!(
+            vec![ReflectValueBox::String("x".to_owned())],
+            parsed.get_extension_dyn(&s)
+        );
+        assert_eq!(
+            vec![ReflectValueBox::I32(-1), ReflectValueBox::I32(2)],
+            parsed.get_extension_dyn(&r)
+        );
+        let nested = match parsed.get_extension_dyn(&m).pop() {
+            Some(ReflectValueBox::Message(nested)) => nested,
+            v => panic!("{:?}", v),
+        };
+        assert!(nested.get_unknown_fields_dyn().iter().next().is_none());
+        assert_eq!(
+            vec![ReflectValueBox::String("nested".to_owned())],
+            nested.get_extension_dyn(&s)
+        );
+        assert_eq!(bytes, parsed.write_to_bytes_dyn().unwrap());
+
+        let mut parsed = parsed;
+        parsed.set_extension_dyn(&r, Vec::new());
+        assert!(parsed.get_extension_dyn(&r).is_empty());
+    }
+
+    #[test]
+    fn generated() {
+        let o = find(&file(), "o");
+        let mut options = FieldOptions::new();
+        options.set_deprecated(true);
+        let options: &mut dyn MessageDyn = &mut options;
+        assert!(options.get_extension_dyn(&o).is_empty());
+        options.set_extension_dyn(&o, vec![ReflectValueBox::I64(-3)]);
+        assert_eq!(
+            vec![ReflectValueBox::I64(-3)],
+            options.get_extension_dyn(&o)
+        );
+        options.set_extension_dyn(&o, vec![ReflectValueBox::I64(4)]);
+        assert_eq!(vec![ReflectValueBox::I64(4)], options.get_extension_dyn(&o));
+        assert_eq!(
+            &[0x18, 0x01, 0x80, 0xb5, 0x18, 0x04][..],
+            &options.write_to_bytes_dyn().unwrap()[..]
+        );
+    }
+
+    #[test]
+    fn written_in_field_number_order() {
+        let file = file();
+        let descriptor = file.message_by_package_relative_name("M").unwrap();
+        let mut message = descriptor.new_instance();
+        for (name, value) in &[("z", 3), ("a", 1)] {
+            descriptor
+                .get_field_by_name(name)
+                .unwrap()
+                .set_singular_field(&mut *message, ReflectValueBox::I32(*value));
+        }
+        message.set_extension_dyn(&find(&file, "r"), vec![ReflectValueBox::I32(1)]);
+        assert_eq!(
+            &[0x08, 0x01, 0xa8, 0x06, 0x02, 0xe0, 0x12, 0x03][..],
+            &message.write_to_bytes_dyn().unwrap()[..]
+        );
+    }
+
+    #[test]
+    fn group_of_generated_message() {
+        let file = file();
+        let g = find(&file, "g");
+        let descriptor = file.message_by_package_relative_name("M").unwrap();
+        let a = descriptor.get_field_by_name("a").unwrap();
+        let mut group = descriptor.new_instance();
+        a.set_singular_field(&mut *group, ReflectValueBox::I32(5));
+
+        let mut options = FieldOptions::new();
+        let options: &mut dyn MessageDyn = &mut options;
+        options.set_extension_dyn(&g, vec![ReflectValueBox::Message(group)]);
+        let values = options.get_extension_dyn(&g);
+        let group = match &values[..] {
+            [ReflectValueBox::Message(m)] => m,
+            v => panic!("{:?}", v),
+        };
+        assert_eq!(Some(ReflectValueRef::I32(5)), a.get_singular(&**group));
+        let mut registry = ExtensionRegistry::new();
+        registry.add_file(&file);
+        let extensions = options.extensions(&registry);
+        assert_eq!(
+            vec![g.clone()],
+            extensions.into_iter().map(|(e, _)| e).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            &[0x8b, 0xb5, 0x18, 0x08, 0x05, 0x8c, 0xb5, 0x18][..],
+            &options.write_to_bytes_dyn().unwrap()[..]
+        );
+
+        options.set_extension_dyn(&g, Vec::new());
+        assert!(options.get_extension_dyn(&g).is_empty());
+        assert!(options.write_to_bytes_dyn().unwrap().is_empty());
+    }
+
+    #[test]
+    fn iterate_and_print() {
+        let file = file();
+        let o = find(&file, "o");
+        let mut registry = ExtensionRegistry::new();
+        registry.add_file(&file);
+
+        let mut options = FieldOptions::new();
+        options.set_deprecated(true);
+        (&mut options as &mut dyn MessageDyn).set_extension_dyn(&o, vec![ReflectValueBox::I64(4)]);
+        let options: &dyn MessageDyn = &options;
+        assert!(options.extensions(&ExtensionRegistry::new()).is_empty());
+        assert_eq!(
+            vec![(o.clone(), vec![ReflectValueBox::I64(4)])],
+            options.extensions(&registry)
+        );
+
+        assert_eq!(
+            "deprecated: true 50000: 4",
+            text_format::print_to_string(options)
+        );
+        let text_options = text_format::PrintOptions {
+            extension_registry: Some(registry.clone()),
+            ..Default::default()
+        };
+        assert_eq!(
+            "deprecated: true [e.o]: 4",
+            text_format::print_to_string_with_options(options, &text_options)
+        );
+        let json_options = json::PrintOptions {
+            extension_registry: Some(registry.clone()),
+            ..Default::default()
+        };
+        assert_eq!(
+            "{\"deprecated\": true, \"[e.o]\": \"4\"}",
+            json::print_to_string_with_options(options, &json_options).unwrap()
+        );
+        let json_parse_options = json::ParseOptions {
+            extension_registry: Some(registry.clone()),
+            ..Default::default()
+        };
+        let parsed: FieldOptions = json::parse_from_str_with_options(
+            "{\"deprecated\": true, \"[e.o]\": \"4\"}",
+            &json_parse_options,
+        )
+        .unwrap();
+        assert_eq!(
+            vec![ReflectValueBox::I64(4)],
+            (&parsed as &dyn MessageDyn).get_extension_dyn(&o)
+        );
+        assert!(json::parse_from_str::<FieldOptions>("{\"[e.o]\": \"4\"}").is_err());
+
+        // Decoded extensions of dynamic messages are iterated without registry
+        let descriptor = file.message_by_package_relative_name("M").unwrap();
+        let (s, r) = (find(&file, "s"), find(&file, "r"));
+        let mut message = descriptor.new_instance();
+        message.set_extension_dyn(&r, vec![ReflectValueBox::I32(1), ReflectValueBox::I32(2)]);
+        message.set_extension_dyn(&s, vec![ReflectValueBox::String("x".to_owned())]);
+        let bytes = message.write_to_bytes_dyn().unwrap();
+        let parsed = descriptor
+            .parse_from_bytes_with_extensions(&bytes, &registry)
+            .unwrap();
+        assert_eq!(
+            vec![s.clone(), r.clone()],
+            parsed
+                .extensions(&ExtensionRegistry::new())
+                .into_iter()
+                .map(|(e, _)| e)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "[e.s]: \"x\" [e.r]: 1 [e.r]: 2",
+            text_format::print_to_string(&*parsed)
+        );
+        assert_eq!(
+            "{\"[e.s]\": \"x\", \"[e.r]\": [1, 2]}",
+            json::print_to_string(&*parsed).unwrap()
+        );
+        let parsed = json::parse_dynamic_from_str_with_options(
+            &descriptor,
+            "{\"[e.s]\": \"x\", \"[e.r]\": [1, 2]}",
+            &json_parse_options,
+        )
+        .unwrap();
+        assert_eq!(
+            "[e.s]: \"x\" [e.r]: 1 [e.r]: 2",
+            text_format::print_to_string(&*parsed)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrong_type() {
+        let file = file();
+        let mut message = file
+            .message_by_package_relative_name("M")
+            .unwrap()
+            .new_instance();
+        message.set_extension_dyn(&find(&file, "s"), vec![ReflectValueBox::I32(1)]);
+    }
+}
@@ -2,23 +2,50 @@ use std::collections::hash_map;
 use std::collections::HashMap;
 use std::hash::Hash;
 
+#[cfg(feature = "bytes")]
+use crate::chars::Chars;
+use crate::reflect::dynamic::value::DynamicValue;
 use crate::reflect::map::ReflectMap;
 use crate::reflect::map::ReflectMapIter;
 use crate::reflect::map::ReflectMapIterTrait;
-use crate::reflect::runtime_types::RuntimeType;
 use crate::reflect::ProtobufValue;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::ReflectValueRef;
 use crate::reflect::RuntimeTypeBox;
 
+/// Key of `string` key map: keys parsed from `Bytes` input share memory with it.
+#[cfg(feature = "bytes")]
+type StringKey = Chars;
+#[cfg(not(feature = "bytes"))]
+type StringKey = String;
+
+#[cfg(feature = "bytes")]
+fn string_key(key: DynamicValue) -> StringKey {
+    match key {
+        DynamicValue::Chars(k) => k,
+        key => match key.into_box() {
+            ReflectValueBox::String(k) => Chars::from(k),
+            _ => panic!("wrong key type"),
+        },
+    }
+}
+
+#[cfg(not(feature = "bytes"))]
+fn string_key(key: DynamicValue) -> StringKey {
+    match key.into_box() {
+        ReflectValueBox::String(k) => k,
+        _ => panic!("wrong key type"),
+    }
+}
+
 #[derive(Debug, Clone)]
 enum Maps {
-    U32(HashMap<u32, ReflectValueBox>),
-    I32(HashMap<i32, ReflectValueBox>),
-    U64(HashMap<u64, ReflectValueBox>),
-    I64(HashMap<i64, ReflectValueBox>),
-    Bool(HashMap<bool, ReflectValueBox>),
-    String(HashMap<String, ReflectValueBox>),
+    U32(HashMap<u32, DynamicValue>),
+    I32(HashMap<i32, DynamicValue>),
+    U64(HashMap<u64, DynamicValue>),
+    I64(HashMap<i64, DynamicValue>),
+    Bool(HashMap<bool, DynamicValue>),
+    String(HashMap<StringKey, DynamicValue>),
 }
 
 impl Maps {
@@ -55,6 +82,17 @@ impl Maps {
         }
     }
 
+    fn values_mut(&mut self) -> Box<dyn Iterator<Item = &mut ReflectValueBox> + '_> {
+        match self {
+            Maps::U32(m) => Box::new(m.values_mut().filter_map(DynamicValue::boxed_mut)),
+            Maps::I32(m) => Box::new(m.values_mut().filter_map(DynamicValue::boxed_mut)),
+            Maps::U64(m) => Box::new(m.values_mut().filter_map(DynamicValue::boxed_mut)),
+            Maps::I64(m) => Box::new(m.values_mut().filter_map(DynamicValue::boxed_mut)),
+            Maps::Bool(m) => Box::new(m.values_mut().filter_map(DynamicValue::boxed_mut)),
+            Maps::String(m) => Box::new(m.values_mut().filter_map(DynamicValue::boxed_mut)),
+        }
+    }
+
     fn key_type(&self) -> RuntimeTypeBox {
         match self {
             Maps::U32(..) => RuntimeTypeBox::U32,
@@ -88,11 +126,31 @@ impl DynamicMap {
             },
         }
     }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut ReflectValueBox> {
+        self.maps.values_mut()
+    }
+
+    pub fn insert_value(&mut self, key: DynamicValue, value: DynamicValue) {
+        assert!(value.get_type() == self.value);
+        if let Maps::String(m) = &mut self.maps {
+            m.insert(string_key(key), value);
+            return;
+        }
+        let key = key.into_box();
+        match (&mut self.maps, &key) {
+            (Maps::U32(m), ReflectValueBox::U32(k)) => m.insert(*k, value),
+            (Maps::U64(m), ReflectValueBox::U64(k)) => m.insert(*k, value),
+            (Maps::I32(m), ReflectValueBox::I32(k)) => m.insert(*k, value),
+            (Maps::I64(m), ReflectValueBox::I64(k)) => m.insert(*k, value),
+            (Maps::Bool(m), ReflectValueBox::Bool(k)) => m.insert(*k, value),
+            _ => panic!("wrong key type"),
+        };
+    }
 }
 
 struct DynamicMapIterImpl<'a, K: ProtobufValue + Eq + Hash + 'static> {
-    iter: hash_map::Iter<'a, K, ReflectValueBox>,
-    value: &'a RuntimeTypeBox,
+    iter: hash_map::Iter<'a, K, DynamicValue>,
 }
 
 impl<'a, K: ProtobufValue + Eq + Hash + 'static> ReflectMapIterTrait<'a>
@@ -103,43 +161,17 @@ impl<'a, K: ProtobufValue + Eq + Hash + 'static> ReflectMapIterTrait<'a>
             .next()
             .map(|(k, v)| (K::as_ref(k), v.as_value_ref()))
     }
-
-    fn key_type(&self) -> RuntimeTypeBox {
-        K::RuntimeType::runtime_type_box()
-    }
-
-    fn value_type(&self) -> RuntimeTypeBox {
-        self.value.clone()
-    }
 }
 
 impl ReflectMap for DynamicMap {
     fn reflect_iter(&self) -> ReflectMapIter {
         match &self.maps {
-            Maps::U32(m) => ReflectMapIter::new(DynamicMapIterImpl {
-                iter: m.iter(),
-                value: &self.value,
-            }),
-            Maps::I32(m) => ReflectMapIter::new(DynamicMapIterImpl {
-                iter: m.iter(),
-                value: &self.value,
-            }),
-            Maps::U64(m) => ReflectMapIter::new(DynamicMapIterImpl {
-                iter: m.iter(),
-                value: &self.value,
-            }),
-            Maps::I64(m) => ReflectMapIter::new(DynamicMapIterImpl {
-                iter: m.iter(),
-                value: &self.value,
-            }),
-            Maps::Bool(m) => ReflectMapIter::new(DynamicMapIterImpl {
-                iter: m.iter(),
-                value: &self.value,
-            }),
-            Maps::String(m) => ReflectMapIter::new(DynamicMapIterImpl {
-                iter: m.iter(),
-                value: &self.value,
-            }),
+            Maps::U32(m) => ReflectMapIter::new(DynamicMapIterImpl { iter: m.iter() }),
+            Maps::I32(m) => ReflectMapIter::new(DynamicMapIterImpl { iter: m.iter() }),
+            Maps::U64(m) => ReflectMapIter::new(DynamicMapIterImpl { iter: m.iter() }),
+            Maps::I64(m) => ReflectMapIter::new(DynamicMapIterImpl { iter: m.iter() }),
+            Maps::Bool(m) => ReflectMapIter::new(DynamicMapIterImpl { iter: m.iter() }),
+            Maps::String(m) => ReflectMapIter::new(DynamicMapIterImpl { iter: m.iter() }),
         }
     }
 
@@ -161,23 +193,11 @@ impl ReflectMap for DynamicMap {
             (Maps::String(m), ReflectValueRef::String(v)) => m.get(&*v),
             _ => None,
         }
-        .map(ReflectValueBox::as_value_ref)
+        .map(DynamicValue::as_value_ref)
     }
 
     fn insert(&mut self, key: ReflectValueBox, value: ReflectValueBox) {
-        assert!(value.get_type() == self.value);
-        match (&mut self.maps, &key) {
-            (Maps::U32(m), ReflectValueBox::U32(k)) => m.insert(*k, value),
-            (Maps::U64(m), ReflectValueBox::U64(k)) => m.insert(*k, value),
-            (Maps::I32(m), ReflectValueBox::I32(k)) => m.insert(*k, value),
-            (Maps::I64(m), ReflectValueBox::I64(k)) => m.insert(*k, value),
-            (Maps::Bool(m), ReflectValueBox::Bool(k)) => m.insert(*k, value),
-            (Maps::String(m), _) => match key {
-                ReflectValueBox::String(k) => m.insert(k, value),
-                _ => panic!("wrong key type"),
-            },
-            _ => panic!("wrong key type"),
-        };
+        self.insert_value(key.into(), value.into());
     }
 
     fn clear(&mut self) {
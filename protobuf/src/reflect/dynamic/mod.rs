@@ -1,11 +1,23 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::mem;
+
 use crate::cached_size::CachedSize;
+use crate::error::WireError;
 use crate::message_dyn::MessageDyn;
+use crate::reflect::dynamic::arena::DynamicArena;
+use crate::reflect::dynamic::extension::DynamicExtensions;
 use crate::reflect::dynamic::map::DynamicMap;
 use crate::reflect::dynamic::optional::DynamicOptional;
 use crate::reflect::dynamic::repeated::DynamicRepeated;
+use crate::reflect::dynamic::value::DynamicValue;
 use crate::reflect::map::ReflectMap;
 use crate::reflect::repeated::ReflectRepeated;
+use crate::reflect::value::value_ref::debug_message;
 use crate::reflect::value::value_ref::ReflectValueMut;
+use crate::reflect::ExtensionDescriptor;
+use crate::reflect::ExtensionRegistry;
 use crate::reflect::ReflectFieldRef;
 use crate::reflect::ReflectMapMut;
 use crate::reflect::ReflectMapRef;
@@ -16,7 +28,8 @@ use crate::reflect::RuntimeFieldType;
 use crate::reflect::{FieldDescriptor, RuntimeTypeBox};
 use crate::reflect::{MessageDescriptor, ReflectValueRef};
 use crate::rt::{
-    bytes_size, compute_raw_varint32_size, string_size, tag_size, unexpected_wire_type, value_size,
+    bytes_size, compute_raw_varint32_size, read_unknown_or_skip_group, saturating_size,
+    string_size, tag_size, unexpected_wire_type, unknown_fields_size, value_size,
     value_varint_zigzag_size,
 };
 use crate::wire_format::WireType;
@@ -24,16 +37,19 @@ use crate::Clear;
 use crate::CodedInputStream;
 use crate::CodedOutputStream;
 use crate::Message;
+use crate::ProtobufError;
 use crate::ProtobufResult;
 use crate::UnknownFields;
 
-use super::EnumValueDescriptor;
+use crate::descriptor::field_descriptor_proto;
 use crate::descriptor::field_descriptor_proto::Type;
-use std::convert::TryInto;
 
+pub(crate) mod arena;
+pub(crate) mod extension;
 pub(crate) mod map;
 pub(crate) mod optional;
 pub(crate) mod repeated;
+pub(crate) mod value;
 
 #[derive(Debug, Clone)]
 enum DynamicFieldValue {
@@ -58,6 +74,14 @@ impl DynamicFieldValue {
             DynamicFieldValue::Map(m) => m.clear(),
         }
     }
+
+    fn values_mut<'a>(&'a mut self) -> Box<dyn Iterator<Item = &'a mut ReflectValueBox> + 'a> {
+        match self {
+            DynamicFieldValue::Singular(o) => Box::new(o.values_mut()),
+            DynamicFieldValue::Repeated(r) => Box::new(r.values_mut()),
+            DynamicFieldValue::Map(m) => Box::new(m.values_mut()),
+        }
+    }
 }
 
 impl DynamicFieldValue {
@@ -71,65 +95,96 @@ impl DynamicFieldValue {
 
     /// set default value for singular fields
     fn set_default_for_merge(&mut self, field: &FieldDescriptor) {
-        match field.runtime_field_type() {
-            RuntimeFieldType::Singular(rtb) => {
-                assert!(matches!(self, DynamicFieldValue::Singular(..)));
-                if let DynamicFieldValue::Singular(s) = self {
-                    match rtb {
-                        RuntimeTypeBox::I32 => {
-                            s.set(ReflectValueBox::from(0 as i32));
-                        }
-                        RuntimeTypeBox::I64 => {
-                            s.set(ReflectValueBox::from(0 as i64));
-                        }
-                        RuntimeTypeBox::U32 => {
-                            s.set(ReflectValueBox::from(0 as u32));
-                        }
-                        RuntimeTypeBox::U64 => {
-                            s.set(ReflectValueBox::from(0 as u64));
-                        }
-                        RuntimeTypeBox::F32 => {
-                            s.set(ReflectValueBox::from(0 as f32));
-                        }
-                        RuntimeTypeBox::F64 => {
-                            s.set(ReflectValueBox::from(0 as f64));
-                        }
-                        RuntimeTypeBox::Bool => {
-                            s.set(ReflectValueBox::from(false));
-                        }
-                        RuntimeTypeBox::String => {
-                            s.set(ReflectValueBox::from("".to_string()));
-                        }
-                        RuntimeTypeBox::VecU8 => {
-                            s.set(ReflectValueBox::from(Vec::default()));
-                        }
-                        RuntimeTypeBox::Enum(enum_desc) => {
-                            s.set(ReflectValueBox::from(EnumValueDescriptor::new(
-                                enum_desc, 0,
-                            )));
-                        }
-                        _ => {}
-                    }
+        if let RuntimeFieldType::Singular(rtb) = field.runtime_field_type() {
+            assert!(matches!(self, DynamicFieldValue::Singular(..)));
+            if let DynamicFieldValue::Singular(s) = self {
+                if !matches!(rtb, RuntimeTypeBox::Message(..)) {
+                    s.set(field.default_value().to_box());
                 }
             }
-            _ => {}
         }
     }
 }
 
-#[derive(Debug, Clone)]
-pub(crate) struct DynamicMessage {
+/// Field with type information needed to parse it
+#[derive(Clone)]
+struct ResolvedField {
+    field: FieldDescriptor,
+    field_type: Type,
+    runtime_type: RuntimeFieldType,
+}
+
+impl ResolvedField {
+    fn new(field: FieldDescriptor) -> ResolvedField {
+        ResolvedField {
+            field_type: field.get_proto().get_field_type(),
+            runtime_type: field.runtime_field_type(),
+            field,
+        }
+    }
+}
+
+/// Fields of message type resolved once to parse many messages of that type
+pub(crate) struct ResolvedFields {
+    /// Empty message with initialized fields, cloned for each parsed message
+    prototype: DynamicMessage,
+    by_number: HashMap<u32, ResolvedField>,
+}
+
+impl ResolvedFields {
+    pub(crate) fn new(descriptor: &MessageDescriptor) -> ResolvedFields {
+        let mut prototype = DynamicMessage::new(descriptor.clone());
+        prototype.set_fields_default();
+        ResolvedFields {
+            prototype,
+            by_number: descriptor
+                .fields()
+                .map(|f| (f.get_proto().get_number() as u32, ResolvedField::new(f)))
+                .collect(),
+        }
+    }
+
+    /// Parse message without checking it is initialized
+    pub(crate) fn parse(&self, bytes: &[u8]) -> ProtobufResult<DynamicMessage> {
+        let mut message = self.prototype.clone();
+        let mut is = CodedInputStream::from_bytes(bytes);
+        message.merge_fields_with(&mut is, None, None, |number| {
+            self.by_number.get(&number).map(Cow::Borrowed)
+        })?;
+        Ok(message)
+    }
+}
+
+/// Message of type loaded at runtime, e. g. with [`FileDescriptor::new_dynamic`].
+///
+/// Dynamic messages are created with [`MessageDescriptor::new_dynamic_instance`]
+/// and accessed with reflection, e. g. [`FieldDescriptor::get_singular`].
+/// Reference to dynamic message can be obtained from `dyn MessageDyn`
+/// with `downcast_ref::<DynamicMessage>()`.
+///
+/// [`FileDescriptor::new_dynamic`]: crate::reflect::FileDescriptor::new_dynamic
+#[derive(Clone)]
+pub struct DynamicMessage {
     pub(crate) descriptor: MessageDescriptor,
     fields: Box<[DynamicFieldValue]>,
+    extensions: DynamicExtensions,
     unknown_fields: UnknownFields,
     cached_size: CachedSize,
 }
 
+/// Message name and fields in text format, limited in depth and length.
+impl fmt::Debug for DynamicMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        debug_message(self, f)
+    }
+}
+
 impl DynamicMessage {
     pub(crate) fn new(descriptor: MessageDescriptor) -> DynamicMessage {
         DynamicMessage {
             descriptor,
             fields: Vec::new().into_boxed_slice(),
+            extensions: DynamicExtensions::default(),
             unknown_fields: UnknownFields::new(),
             cached_size: CachedSize::new(),
         }
@@ -148,12 +203,17 @@ impl DynamicMessage {
     pub(crate) fn get_reflect<'a>(&'a self, field: &FieldDescriptor) -> ReflectFieldRef<'a> {
         assert_eq!(self.descriptor, field.message_descriptor);
         if self.fields.is_empty() {
-            ReflectFieldRef::default_for_field(field)
+            ReflectFieldRef::default_for_index(&self.descriptor, field.index)
         } else {
             self.fields[field.index].as_ref()
         }
     }
 
+    /// Unset the field.
+    ///
+    /// # Panics
+    ///
+    /// If the field belongs to a different message type.
     pub fn clear_field(&mut self, field: &FieldDescriptor) {
         assert_eq!(field.message_descriptor, self.descriptor);
         if self.fields.is_empty() {
@@ -164,12 +224,13 @@ impl DynamicMessage {
     }
 
     fn check_singular_initialized(&self, rtb: &RuntimeTypeBox, f: &FieldDescriptor) -> bool {
-        if let RuntimeTypeBox::Message(_) = rtb {
-            if let Some(msg) = f.get_singular(self) {
-                return msg.to_message().unwrap().is_initialized_dyn();
-            }
+        match f.get_singular(self) {
+            Some(msg) => match rtb {
+                RuntimeTypeBox::Message(_) => msg.to_message().unwrap().is_initialized_dyn(),
+                _ => true,
+            },
+            None => f.get_proto().get_label() != field_descriptor_proto::Label::LABEL_REQUIRED,
         }
-        true
     }
 
     fn check_repeated_initialized(&self, rtb: &RuntimeTypeBox, f: &FieldDescriptor) -> bool {
@@ -185,6 +246,67 @@ impl DynamicMessage {
         true
     }
 
+    fn check_map_initialized(&self, value_rtb: &RuntimeTypeBox, f: &FieldDescriptor) -> bool {
+        // Map keys cannot be messages, so only values need to be checked
+        if let RuntimeTypeBox::Message(_) = value_rtb {
+            for (_, value) in &f.get_map(self) {
+                if !value.to_message().unwrap().is_initialized_dyn() {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Values of decoded extension, `None` if the extension is not decoded,
+    /// in which case it may still be stored in unknown fields.
+    pub(crate) fn get_extension(
+        &self,
+        extension: &ExtensionDescriptor,
+    ) -> Option<&[ReflectValueBox]> {
+        self.extensions.get(extension)
+    }
+
+    /// Decoded extensions with their values.
+    pub(crate) fn decoded_extensions(
+        &self,
+    ) -> impl Iterator<Item = (&ExtensionDescriptor, &[ReflectValueBox])> {
+        self.extensions.iter()
+    }
+
+    /// Replace values of the extension.
+    pub(crate) fn set_extension(
+        &mut self,
+        extension: &ExtensionDescriptor,
+        values: Vec<ReflectValueBox>,
+    ) {
+        self.unknown_fields.remove(extension.number());
+        self.extensions.set(extension.clone(), values);
+    }
+
+    /// Move extensions known to the registry from unknown fields
+    /// into decoded extensions, in this message and all nested dynamic messages.
+    pub(crate) fn decode_extensions(&mut self, registry: &ExtensionRegistry) {
+        let decoded =
+            registry.take_extensions(self.descriptor.full_name(), &mut self.unknown_fields);
+        for (extension, values) in decoded {
+            self.extensions.merge(extension, values);
+        }
+
+        let nested = self
+            .fields
+            .iter_mut()
+            .flat_map(|f| f.values_mut())
+            .chain(self.extensions.values_mut());
+        for v in nested {
+            if let ReflectValueBox::Message(m) = v {
+                if let Some(m) = <dyn MessageDyn>::downcast_mut::<DynamicMessage>(&mut **m) {
+                    m.decode_extensions(registry);
+                }
+            }
+        }
+    }
+
     /// Set all fields to default value
     pub fn set_fields_default(&mut self) {
         self.init_fields();
@@ -194,6 +316,9 @@ impl DynamicMessage {
         }
         if !self.fields.is_empty() {
             for field_desc in self.descriptor.fields() {
+                if has_explicit_presence(&field_desc) {
+                    continue; // unset until read
+                }
                 self.fields[field_desc.index].set_default_for_merge(&field_desc);
             }
         }
@@ -217,7 +342,6 @@ impl DynamicMessage {
         assert_eq!(field.message_descriptor, self.descriptor);
         self.init_fields();
         self.clear_oneof_group_fields_except(field);
-        // TODO: reset oneof group fields
         match &mut self.fields[field.index] {
             DynamicFieldValue::Singular(f) => f.mut_or_default(),
             _ => panic!("Not a singular field"),
@@ -230,7 +354,7 @@ impl DynamicMessage {
     ) -> ReflectRepeatedMut<'a> {
         assert_eq!(self.descriptor, field.message_descriptor);
         self.init_fields();
-        // TODO: reset oneof group fields
+        self.clear_oneof_group_fields_except(field);
         match &mut self.fields[field.index] {
             DynamicFieldValue::Repeated(r) => ReflectRepeatedMut::new(r),
             _ => panic!("Not a repeated field: {}", field),
@@ -240,7 +364,7 @@ impl DynamicMessage {
     pub(crate) fn mut_map<'a>(&'a mut self, field: &FieldDescriptor) -> ReflectMapMut<'a> {
         assert_eq!(field.message_descriptor, self.descriptor);
         self.init_fields();
-        // TODO: reset oneof group fields
+        self.clear_oneof_group_fields_except(field);
         match &mut self.fields[field.index] {
             DynamicFieldValue::Map(m) => ReflectMapMut::new(m),
             _ => panic!("Not a map field: {}", field),
@@ -248,118 +372,113 @@ impl DynamicMessage {
     }
 
     pub(crate) fn set_field(&mut self, field: &FieldDescriptor, value: ReflectValueBox) {
-        assert_eq!(field.message_descriptor, self.descriptor);
-        self.init_fields();
-        // TODO: reset oneof group fields
-        match &mut self.fields[field.index] {
+        match self.mut_field_value(field) {
             DynamicFieldValue::Singular(s) => s.set(value),
             _ => panic!("Not a singular field: {}", field),
         }
     }
 
-    pub fn downcast_ref(message: &dyn MessageDyn) -> &DynamicMessage {
-        <dyn MessageDyn>::downcast_ref(message).unwrap()
+    /// Field storage, other fields of the same oneof are cleared
+    fn mut_field_value(&mut self, field: &FieldDescriptor) -> &mut DynamicFieldValue {
+        assert_eq!(field.message_descriptor, self.descriptor);
+        self.init_fields();
+        self.clear_oneof_group_fields_except(field);
+        &mut self.fields[field.index]
     }
 
-    pub fn downcast_mut(message: &mut dyn MessageDyn) -> &mut DynamicMessage {
-        <dyn MessageDyn>::downcast_mut(message).unwrap()
+    /// Move nested messages out of this message, leaving fields in unspecified state.
+    pub(crate) fn take_nested_messages(&mut self, out: &mut Vec<Box<dyn MessageDyn>>) {
+        let nested = self
+            .fields
+            .iter_mut()
+            .flat_map(|f| f.values_mut())
+            .chain(self.extensions.values_mut());
+        for v in nested {
+            if let ReflectValueBox::Message(..) = v {
+                if let ReflectValueBox::Message(m) = mem::replace(v, ReflectValueBox::Bool(false)) {
+                    out.push(m);
+                }
+            }
+        }
     }
-}
 
-impl Clear for DynamicMessage {
-    fn clear(&mut self) {
-        unimplemented!()
+    /// Merge length-delimited message allocating nested messages from the arena
+    fn merge_length_delimited(
+        &mut self,
+        is: &mut CodedInputStream,
+        arena: &DynamicArena,
+    ) -> ProtobufResult<()> {
+        let len = is.read_raw_varint64()?;
+        let old_limit = is.push_limit(len)?;
+        self.set_fields_default();
+        self.merge_fields(is, None, Some(arena))?;
+        is.pop_limit(old_limit);
+        Ok(())
     }
-}
 
-impl Message for DynamicMessage {
-    fn descriptor_by_instance(&self) -> MessageDescriptor {
-        self.descriptor.clone()
+    /// Merge fields until EOF, or until end group tag if `end_group` is specified
+    pub(crate) fn merge_fields(
+        &mut self,
+        is: &mut CodedInputStream,
+        end_group: Option<u32>,
+        arena: Option<&DynamicArena>,
+    ) -> ProtobufResult<()> {
+        let desc = self.descriptor.clone();
+        self.merge_fields_with(is, end_group, arena, |number| {
+            desc.get_field_by_number(number)
+                .map(|f| Cow::Owned(ResolvedField::new(f)))
+        })
     }
 
-    fn is_initialized(&self) -> bool {
-        for f in self.descriptor.fields() {
-            match f.runtime_field_type() {
-                RuntimeFieldType::Singular(rtb) => {
-                    if !self.check_singular_initialized(&rtb, &f) {
-                        return false;
-                    }
-                }
-                RuntimeFieldType::Repeated(rtb) => {
-                    if !self.check_repeated_initialized(&rtb, &f) {
-                        return false;
-                    }
+    /// Merge fields using given function to find fields by number
+    fn merge_fields_with<'r>(
+        &mut self,
+        is: &mut CodedInputStream,
+        end_group: Option<u32>,
+        arena: Option<&DynamicArena>,
+        lookup: impl Fn(u32) -> Option<Cow<'r, ResolvedField>>,
+    ) -> ProtobufResult<()> {
+        loop {
+            if is.eof()? {
+                return match end_group {
+                    None => Ok(()),
+                    Some(_) => Err(ProtobufError::WireError(WireError::UnexpectedEof)),
+                };
+            }
+            let (field, wire_type) = is.read_tag_unpack()?;
+            if wire_type == WireType::WireTypeEndGroup {
+                if end_group == Some(field) {
+                    return Ok(());
                 }
-                RuntimeFieldType::Map(_, _) => {
-                    unimplemented!()
+                return Err(unexpected_wire_type(wire_type));
+            }
+            let resolved = match lookup(field) {
+                Some(resolved) => resolved,
+                None => {
+                    read_unknown_or_skip_group(field, wire_type, is, &mut self.unknown_fields)?;
+                    continue;
                 }
+            };
+            let field_desc = &resolved.field;
+            if resolved.field_type == Type::TYPE_GROUP && wire_type != WireType::WireTypeStartGroup
+            {
+                return Err(unexpected_wire_type(wire_type));
             }
-        }
-
-        true
-    }
-
-    fn merge_from(&mut self, is: &mut CodedInputStream) -> ProtobufResult<()> {
-        self.set_fields_default();
-        let desc = self.descriptor.clone();
-        while !is.eof()? {
-            let (field, wire_type) = is.read_tag_unpack()?;
-            let field_desc = desc
-                .get_field_by_number(field)
-                .expect("Invalid field number at decoding");
-            let field_desc_proto = field_desc.get_proto();
-            match field_desc.runtime_field_type() {
+            match &resolved.runtime_type {
                 RuntimeFieldType::Singular(rtb) => {
-                    let val = match field_desc_proto.get_field_type() {
-                        Type::TYPE_DOUBLE => ReflectValueBox::from(is.read_double()?),
-                        Type::TYPE_FLOAT => ReflectValueBox::from(is.read_float()?),
-                        Type::TYPE_INT64 => ReflectValueBox::from(is.read_int64()?),
-                        Type::TYPE_UINT64 => ReflectValueBox::from(is.read_uint64()?),
-                        Type::TYPE_INT32 => ReflectValueBox::from(is.read_int32()?),
-                        Type::TYPE_FIXED64 => ReflectValueBox::from(is.read_fixed64()?),
-                        Type::TYPE_FIXED32 => ReflectValueBox::from(is.read_fixed32()?),
-                        Type::TYPE_BOOL => ReflectValueBox::from(is.read_bool()?),
-                        Type::TYPE_STRING => ReflectValueBox::from(is.read_string()?),
-                        Type::TYPE_GROUP => {
-                            unimplemented!()
-                        }
-                        Type::TYPE_MESSAGE => {
-                            assert!(matches!(rtb, RuntimeTypeBox::Message(..)));
-                            if let RuntimeTypeBox::Message(msg_desc) = rtb {
-                                let mut msg_inst = msg_desc.new_instance();
-                                is.incr_recursion()?;
-                                is.merge_message_dyn(msg_inst.as_mut())?;
-                                is.decr_recursion();
-                                ReflectValueBox::from(msg_inst)
-                            } else {
-                                panic!("Protobuf type and Runtime type mismatch");
-                            }
-                        }
-                        Type::TYPE_BYTES => ReflectValueBox::from(is.read_bytes()?),
-                        Type::TYPE_UINT32 => ReflectValueBox::from(is.read_uint32()?),
-                        Type::TYPE_ENUM => {
-                            assert!(matches!(rtb, RuntimeTypeBox::Enum(..)));
-                            if let RuntimeTypeBox::Enum(enum_desc) = rtb {
-                                let enum_num = is.read_int32()?;
-                                ReflectValueBox::from(EnumValueDescriptor::new(
-                                    enum_desc,
-                                    enum_num as usize, // FIXME: might unsatisfied
-                                ))
-                            } else {
-                                panic!("Protobuf type and Runtime type mismatch");
-                            }
-                        }
-                        Type::TYPE_SFIXED32 => ReflectValueBox::from(is.read_sfixed32()?),
-                        Type::TYPE_SFIXED64 => ReflectValueBox::from(is.read_sfixed64()?),
-                        Type::TYPE_SINT32 => ReflectValueBox::from(is.read_sint32()?),
-                        Type::TYPE_SINT64 => ReflectValueBox::from(is.read_sint64()?),
-                    };
-                    self.set_field(&field_desc, val);
+                    let val = read_singular_in(is, resolved.field_type, rtb, field, arena)?;
+                    match self.mut_field_value(field_desc) {
+                        DynamicFieldValue::Singular(s) => s.set_value(val),
+                        _ => panic!("Not a singular field: {}", field_desc),
+                    }
                 }
                 RuntimeFieldType::Repeated(rtb) => {
-                    let mut repeated_mut = self.mut_repeated(&field_desc);
+                    let repeated_mut = match self.mut_field_value(field_desc) {
+                        DynamicFieldValue::Repeated(r) => r,
+                        _ => panic!("Not a repeated field: {}", field_desc),
+                    };
 
-                    match field_desc_proto.get_field_type() {
+                    match resolved.field_type {
                         Type::TYPE_FLOAT => match wire_type {
                             WireType::WireTypeFixed32 => {
                                 repeated_mut.push(ReflectValueBox::from(is.read_float()?));
@@ -478,10 +597,16 @@ impl Message for DynamicMessage {
                             _ => return Err(unexpected_wire_type(wire_type)),
                         },
                         Type::TYPE_STRING => {
-                            repeated_mut.push(ReflectValueBox::from(is.read_string()?));
+                            repeated_mut.push_value(read_string(is)?);
                         }
                         Type::TYPE_GROUP => {
-                            unimplemented!();
+                            repeated_mut.push_value(read_singular_in(
+                                is,
+                                Type::TYPE_GROUP,
+                                rtb,
+                                field,
+                                arena,
+                            )?);
                         }
                         Type::TYPE_SFIXED32 => match wire_type {
                             WireType::WireTypeFixed32 => {
@@ -536,48 +661,127 @@ impl Message for DynamicMessage {
                             _ => return Err(unexpected_wire_type(wire_type)),
                         },
                         Type::TYPE_BYTES => {
-                            repeated_mut.push(ReflectValueBox::from(is.read_bytes()?));
+                            repeated_mut.push_value(read_bytes(is)?);
                         }
                         Type::TYPE_ENUM => {
                             assert!(matches!(rtb, RuntimeTypeBox::Enum(..)));
                             if let RuntimeTypeBox::Enum(enum_desc) = rtb {
-                                let enum_num = is.read_int32()?;
-                                let enum_val = ReflectValueBox::from(EnumValueDescriptor::new(
-                                    enum_desc,
-                                    enum_num.try_into().unwrap(),
-                                ));
-                                repeated_mut.push(enum_val);
+                                let mut enum_nums: Vec<i32> = Vec::new();
+                                match wire_type {
+                                    WireType::WireTypeVarint => enum_nums.push(is.read_int32()?),
+                                    WireType::WireTypeLengthDelimited => {
+                                        is.read_repeated_packed_int32_into(&mut enum_nums)?;
+                                    }
+                                    _ => return Err(unexpected_wire_type(wire_type)),
+                                }
+                                for enum_num in enum_nums {
+                                    // Numbers unknown to the descriptor are preserved
+                                    repeated_mut
+                                        .push(ReflectValueBox::Enum(enum_desc.clone(), enum_num));
+                                }
                             } else {
                                 panic!("Protobuf type and Runtime type mismatch");
                             }
                         }
                         Type::TYPE_MESSAGE => {
-                            assert!(matches!(rtb, RuntimeTypeBox::Message(..)));
-                            if let RuntimeTypeBox::Message(msg_desc) = rtb {
-                                let mut msg_inst = msg_desc.new_instance();
-                                is.merge_message_dyn(msg_inst.as_mut())?;
-                                let msg_val = ReflectValueBox::from(msg_inst);
-                                repeated_mut.push(msg_val);
-                            } else {
-                                panic!("Protobuf type and Runtime type mismatch");
-                            }
+                            repeated_mut.push_value(read_singular_in(
+                                is,
+                                Type::TYPE_MESSAGE,
+                                rtb,
+                                field,
+                                arena,
+                            )?);
                         }
                     }
                 }
-                RuntimeFieldType::Map(_, _) => {}
+                RuntimeFieldType::Map(k, v) => {
+                    if wire_type != WireType::WireTypeLengthDelimited {
+                        return Err(unexpected_wire_type(wire_type));
+                    }
+                    let (key, value) = read_map_entry(is, field_desc, k, v, arena)?;
+                    match self.mut_field_value(field_desc) {
+                        DynamicFieldValue::Map(m) => m.insert_value(key, value),
+                        _ => panic!("Not a map field: {}", field_desc),
+                    }
+                }
             }
         }
-        Ok(())
+    }
+
+    pub(crate) fn downcast_ref(message: &dyn MessageDyn) -> &DynamicMessage {
+        <dyn MessageDyn>::downcast_ref(message).unwrap()
+    }
+
+    pub(crate) fn downcast_mut(message: &mut dyn MessageDyn) -> &mut DynamicMessage {
+        <dyn MessageDyn>::downcast_mut(message).unwrap()
+    }
+}
+
+impl Clear for DynamicMessage {
+    fn clear(&mut self) {
+        for field in self.fields.iter_mut() {
+            field.clear();
+        }
+        self.extensions.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl Message for DynamicMessage {
+    fn descriptor_by_instance(&self) -> MessageDescriptor {
+        self.descriptor.clone()
+    }
+
+    fn is_initialized(&self) -> bool {
+        for f in self.descriptor.fields() {
+            match f.runtime_field_type() {
+                RuntimeFieldType::Singular(rtb) => {
+                    if !self.check_singular_initialized(&rtb, &f) {
+                        return false;
+                    }
+                }
+                RuntimeFieldType::Repeated(rtb) => {
+                    if !self.check_repeated_initialized(&rtb, &f) {
+                        return false;
+                    }
+                }
+                RuntimeFieldType::Map(_, rtb) => {
+                    if !self.check_map_initialized(&rtb, &f) {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        for v in self.extensions.values() {
+            if let ReflectValueBox::Message(m) = v {
+                if !m.is_initialized_dyn() {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut CodedInputStream) -> ProtobufResult<()> {
+        self.set_fields_default();
+        self.merge_fields(is, None, None)
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut CodedOutputStream) -> ProtobufResult<()> {
-        for field_desc in self.descriptor.fields() {
+        // Extensions are written in field number order together with fields
+        let mut next_extension_number = 0;
+        for field_desc in self.descriptor.fields_sorted_by_number() {
             let field_number = field_desc.get_proto().get_number() as u32;
+            self.extensions
+                .write_range(next_extension_number..field_number, os)?;
+            next_extension_number = field_number;
             match field_desc.runtime_field_type() {
                 RuntimeFieldType::Singular(rtb) => {
                     if let Some(v) = field_desc.get_singular(self) {
-                        if v.is_non_zero() {
-                            // ignore default value
+                        if has_explicit_presence(&field_desc) || v.is_non_zero() {
+                            // ignore default value of proto3 implicit presence field
                             singular_write_to(
                                 &rtb,
                                 &field_desc.get_proto().get_field_type(),
@@ -590,6 +794,16 @@ impl Message for DynamicMessage {
                 }
                 RuntimeFieldType::Repeated(rtb) => {
                     let repeated = field_desc.get_repeated(self);
+                    if is_packed(&field_desc) {
+                        write_packed(
+                            &rtb,
+                            &field_desc.get_proto().get_field_type(),
+                            field_number,
+                            &repeated,
+                            os,
+                        )?;
+                        continue;
+                    }
                     for i in 0..repeated.len() {
                         let v = repeated.get(i);
                         singular_write_to(
@@ -601,36 +815,55 @@ impl Message for DynamicMessage {
                         )?;
                     }
                 }
-                RuntimeFieldType::Map(_, _) => {
-                    unimplemented!();
+                RuntimeFieldType::Map(k, v) => {
+                    let (key_type, value_type) = map_entry_types(&field_desc);
+                    for (key, value) in &field_desc.get_map(self) {
+                        let len = saturating_size(
+                            compute_singular_size(&k, &key_type, 1, &key) as u64
+                                + cached_singular_size(&v, &value_type, 2, &value) as u64,
+                        );
+                        os.write_tag(field_number, WireType::WireTypeLengthDelimited)?;
+                        os.write_raw_varint32(len)?;
+                        singular_write_to(&k, &key_type, 1, &key, os)?;
+                        singular_write_to(&v, &value_type, 2, &value, os)?;
+                    }
                 }
             }
         }
 
+        self.extensions.write_range(next_extension_number.., os)?;
+        os.write_unknown_fields(&self.unknown_fields)?;
         Ok(())
     }
 
     fn compute_size(&self) -> u32 {
-        let mut m_size = 0;
+        let mut m_size: u64 = 0;
         for field_desc in self.descriptor.fields() {
             let field_number = field_desc.get_proto().get_number() as u32;
             match field_desc.runtime_field_type() {
                 RuntimeFieldType::Singular(rtb) => {
                     if let Some(v) = field_desc.get_singular(self) {
-                        if v.is_non_zero() {
-                            // ignore default value
+                        if has_explicit_presence(&field_desc) || v.is_non_zero() {
+                            // ignore default value of proto3 implicit presence field
                             m_size += compute_singular_size(
                                 &rtb,
                                 &field_desc.get_proto().get_field_type(),
                                 field_number,
                                 &v,
-                            );
+                            ) as u64;
                         }
                     }
                 }
                 RuntimeFieldType::Repeated(rtb) => {
                     let repeated = field_desc.get_repeated(self);
-                    if !repeated.is_empty() {
+                    if is_packed(&field_desc) {
+                        m_size += compute_packed_size(
+                            &rtb,
+                            &field_desc.get_proto().get_field_type(),
+                            field_number,
+                            &repeated,
+                        ) as u64;
+                    } else if !repeated.is_empty() {
                         for i in 0..repeated.len() {
                             let v = repeated.get(i);
                             m_size += compute_singular_size(
@@ -638,17 +871,27 @@ impl Message for DynamicMessage {
                                 &field_desc.get_proto().get_field_type(),
                                 field_number,
                                 &v,
-                            );
+                            ) as u64;
                         }
                     }
                 }
-                RuntimeFieldType::Map(_, _) => {
-                    unimplemented!();
+                RuntimeFieldType::Map(k, v) => {
+                    let (key_type, value_type) = map_entry_types(&field_desc);
+                    for (key, value) in &field_desc.get_map(self) {
+                        let len =
+                            compute_map_entry_size(&k, &key_type, &key, &v, &value_type, &value);
+                        m_size += tag_size(field_number) as u64
+                            + compute_raw_varint32_size(len) as u64
+                            + len as u64;
+                    }
                 }
             }
         }
-        // TODO: unknown fields
-        m_size
+        m_size += self.extensions.compute_size();
+        m_size += unknown_fields_size(&self.unknown_fields) as u64;
+        let size = saturating_size(m_size);
+        self.cached_size.set(size);
+        size
     }
 
     fn get_cached_size(&self) -> u32 {
@@ -678,8 +921,343 @@ impl Message for DynamicMessage {
     }
 }
 
+/// Read `string` value, sharing memory with the input if it is a `Bytes` object.
+fn read_string(is: &mut CodedInputStream) -> ProtobufResult<DynamicValue> {
+    #[cfg(feature = "bytes")]
+    {
+        if is.is_carllerche_bytes() {
+            return Ok(DynamicValue::Chars(is.read_carllerche_chars()?));
+        }
+    }
+    Ok(DynamicValue::Box(ReflectValueBox::String(
+        is.read_string()?,
+    )))
+}
+
+/// Read `bytes` value, sharing memory with the input if it is a `Bytes` object.
+fn read_bytes(is: &mut CodedInputStream) -> ProtobufResult<DynamicValue> {
+    #[cfg(feature = "bytes")]
+    {
+        if is.is_carllerche_bytes() {
+            return Ok(DynamicValue::Bytes(is.read_carllerche_bytes()?));
+        }
+    }
+    Ok(DynamicValue::Box(ReflectValueBox::Bytes(is.read_bytes()?)))
+}
+
+/// Read singular value of given type from input stream
+pub(crate) fn read_singular(
+    is: &mut CodedInputStream,
+    proto_type: Type,
+    rtb: &RuntimeTypeBox,
+    field_number: u32,
+) -> ProtobufResult<ReflectValueBox> {
+    read_singular_in(is, proto_type, rtb, field_number, None).map(DynamicValue::into_box)
+}
+
+/// New message instance, reused from the arena if specified
+fn new_instance_in(
+    descriptor: &MessageDescriptor,
+    arena: Option<&DynamicArena>,
+) -> Box<dyn MessageDyn> {
+    match arena {
+        Some(arena) => arena.new_instance(descriptor),
+        None => descriptor.new_instance(),
+    }
+}
+
+/// Read singular value, allocating nested dynamic messages from the arena if specified
+fn read_singular_in(
+    is: &mut CodedInputStream,
+    proto_type: Type,
+    rtb: &RuntimeTypeBox,
+    field_number: u32,
+    arena: Option<&DynamicArena>,
+) -> ProtobufResult<DynamicValue> {
+    Ok(DynamicValue::Box(match proto_type {
+        Type::TYPE_DOUBLE => ReflectValueBox::from(is.read_double()?),
+        Type::TYPE_FLOAT => ReflectValueBox::from(is.read_float()?),
+        Type::TYPE_INT64 => ReflectValueBox::from(is.read_int64()?),
+        Type::TYPE_UINT64 => ReflectValueBox::from(is.read_uint64()?),
+        Type::TYPE_INT32 => ReflectValueBox::from(is.read_int32()?),
+        Type::TYPE_FIXED64 => ReflectValueBox::from(is.read_fixed64()?),
+        Type::TYPE_FIXED32 => ReflectValueBox::from(is.read_fixed32()?),
+        Type::TYPE_BOOL => ReflectValueBox::from(is.read_bool()?),
+        Type::TYPE_STRING => return read_string(is),
+        Type::TYPE_GROUP => {
+            // start group tag is already read
+            if let RuntimeTypeBox::Message(msg_desc) = rtb {
+                let mut msg_inst = new_instance_in(msg_desc, arena);
+                is.incr_recursion()?;
+                let r = merge_group(msg_inst.as_mut(), is, field_number, arena);
+                is.decr_recursion();
+                r?;
+                ReflectValueBox::from(msg_inst)
+            } else {
+                panic!("Protobuf type and Runtime type mismatch");
+            }
+        }
+        Type::TYPE_MESSAGE => {
+            assert!(matches!(rtb, RuntimeTypeBox::Message(..)));
+            if let RuntimeTypeBox::Message(msg_desc) = rtb {
+                let mut msg_inst = new_instance_in(msg_desc, arena);
+                is.incr_recursion()?;
+                match (arena, msg_inst.downcast_mut::<DynamicMessage>()) {
+                    (Some(arena), Some(m)) => m.merge_length_delimited(is, arena)?,
+                    _ => is.merge_message_dyn(msg_inst.as_mut())?,
+                }
+                is.decr_recursion();
+                ReflectValueBox::from(msg_inst)
+            } else {
+                panic!("Protobuf type and Runtime type mismatch");
+            }
+        }
+        Type::TYPE_BYTES => return read_bytes(is),
+        Type::TYPE_UINT32 => ReflectValueBox::from(is.read_uint32()?),
+        Type::TYPE_ENUM => {
+            assert!(matches!(rtb, RuntimeTypeBox::Enum(..)));
+            if let RuntimeTypeBox::Enum(enum_desc) = rtb {
+                // Numbers unknown to the descriptor are preserved
+                ReflectValueBox::Enum(enum_desc.clone(), is.read_int32()?)
+            } else {
+                panic!("Protobuf type and Runtime type mismatch");
+            }
+        }
+        Type::TYPE_SFIXED32 => ReflectValueBox::from(is.read_sfixed32()?),
+        Type::TYPE_SFIXED64 => ReflectValueBox::from(is.read_sfixed64()?),
+        Type::TYPE_SINT32 => ReflectValueBox::from(is.read_sint32()?),
+        Type::TYPE_SINT64 => ReflectValueBox::from(is.read_sint64()?),
+    }))
+}
+
+/// Merge group fields until the end group tag of `field_number`
+fn merge_group(
+    message: &mut dyn MessageDyn,
+    is: &mut CodedInputStream,
+    field_number: u32,
+    arena: Option<&DynamicArena>,
+) -> ProtobufResult<()> {
+    match message.downcast_mut::<DynamicMessage>() {
+        Some(group) => {
+            group.set_fields_default();
+            group.merge_fields(is, Some(field_number), arena)
+        }
+        None => {
+            // Generated messages cannot parse groups, so group content
+            // is copied and merged as a regular message
+            let mut bytes = Vec::new();
+            {
+                let mut os = CodedOutputStream::vec(&mut bytes);
+                copy_group(is, field_number, &mut os)?;
+                os.flush()?;
+            }
+            message.merge_from_bytes_dyn(&bytes)
+        }
+    }
+}
+
+/// Copy fields until the end group tag of `field_number`
+fn copy_group(
+    is: &mut CodedInputStream,
+    field_number: u32,
+    os: &mut CodedOutputStream,
+) -> ProtobufResult<()> {
+    loop {
+        let (field, wire_type) = is.read_tag_unpack()?;
+        match wire_type {
+            WireType::WireTypeEndGroup if field == field_number => return Ok(()),
+            WireType::WireTypeEndGroup => return Err(unexpected_wire_type(wire_type)),
+            WireType::WireTypeStartGroup => {
+                os.write_tag(field, wire_type)?;
+                is.incr_recursion()?;
+                let r = copy_group(is, field, os);
+                is.decr_recursion();
+                r?;
+                os.write_tag(field, WireType::WireTypeEndGroup)?;
+            }
+            _ => {
+                let value = is.read_unknown(wire_type)?;
+                os.write_unknown(field, value.get_ref())?;
+            }
+        }
+    }
+}
+
+/// Read map entry, missing key or value are set to default values
+fn read_map_entry(
+    is: &mut CodedInputStream,
+    field: &FieldDescriptor,
+    key_rtb: &RuntimeTypeBox,
+    value_rtb: &RuntimeTypeBox,
+    arena: Option<&DynamicArena>,
+) -> ProtobufResult<(DynamicValue, DynamicValue)> {
+    let (key_type, value_type) = map_entry_types(field);
+
+    let mut key = None;
+    let mut value = None;
+
+    let len = is.read_raw_varint32()?;
+    let old_limit = is.push_limit(len as u64)?;
+    while !is.eof()? {
+        let (field_number, wire_type) = is.read_tag_unpack()?;
+        match field_number {
+            1 => key = Some(read_singular_in(is, key_type, key_rtb, 1, arena)?),
+            2 => value = Some(read_singular_in(is, value_type, value_rtb, 2, arena)?),
+            _ => is.skip_field(wire_type)?,
+        }
+    }
+    is.pop_limit(old_limit);
+
+    let key = key.unwrap_or_else(|| key_rtb.default_value_ref().to_box().into());
+    let value = value.unwrap_or_else(|| value_rtb.default_value_ref().to_box().into());
+    Ok((key, value))
+}
+
+/// Protobuf types of key and value of map field.
+///
+/// Map entry type is validated when building the file descriptor.
+fn map_entry_types(field: &FieldDescriptor) -> (Type, Type) {
+    match field.map_entry_proto_types() {
+        Some(types) => types,
+        None => unreachable!("not a map field: {}", field),
+    }
+}
+
+/// Singular field is serialized when set even if it has default value.
+///
+/// All proto2 fields have explicit presence. In proto3 only message fields
+/// and fields in oneofs (including `optional` fields) have it,
+/// other fields are serialized only if their value is not default.
+pub(crate) fn has_explicit_presence(field: &FieldDescriptor) -> bool {
+    field.file().proto().get_syntax() != "proto3"
+        || field.get_proto().get_field_type() == Type::TYPE_MESSAGE
+        || field.containing_oneof().is_some()
+}
+
+/// Repeated field uses packed encoding.
+///
+/// Like in generated code, explicit `packed` option is used if present,
+/// otherwise proto3 repeated scalar fields are packed by default.
+fn is_packed(field: &FieldDescriptor) -> bool {
+    let options = field.get_proto().options.get_or_default();
+    if options.has_packed() {
+        return options.get_packed();
+    }
+    field.file().proto().get_syntax() == "proto3"
+        && !matches!(
+            field.get_proto().get_field_type(),
+            Type::TYPE_STRING | Type::TYPE_BYTES | Type::TYPE_MESSAGE | Type::TYPE_GROUP
+        )
+}
+
+/// Compute size of packed repeated field data, excluding tag and length
+fn compute_packed_data_size(
+    rtb: &RuntimeTypeBox,
+    proto_type: &Type,
+    field_number: u32,
+    repeated: &ReflectRepeatedRef,
+) -> u64 {
+    let tag_size = tag_size(field_number) as u64;
+    (0..repeated.len())
+        .map(|i| {
+            compute_singular_size(rtb, proto_type, field_number, &repeated.get(i)) as u64 - tag_size
+        })
+        .sum()
+}
+
+/// Compute size of packed repeated field
+fn compute_packed_size(
+    rtb: &RuntimeTypeBox,
+    proto_type: &Type,
+    field_number: u32,
+    repeated: &ReflectRepeatedRef,
+) -> u32 {
+    if repeated.is_empty() {
+        return 0;
+    }
+    let data_size = compute_packed_data_size(rtb, proto_type, field_number, repeated);
+    saturating_size(
+        tag_size(field_number) as u64
+            + compute_raw_varint32_size(saturating_size(data_size)) as u64
+            + data_size,
+    )
+}
+
+/// Write packed repeated field to output stream
+fn write_packed(
+    rtb: &RuntimeTypeBox,
+    proto_type: &Type,
+    field_number: u32,
+    repeated: &ReflectRepeatedRef,
+    os: &mut CodedOutputStream,
+) -> ProtobufResult<()> {
+    if repeated.is_empty() {
+        return Ok(());
+    }
+    let data_size = compute_packed_data_size(rtb, proto_type, field_number, repeated);
+    os.write_tag(field_number, WireType::WireTypeLengthDelimited)?;
+    os.write_raw_varint32(saturating_size(data_size))?;
+    for i in 0..repeated.len() {
+        let v = repeated.get(i);
+        match proto_type {
+            Type::TYPE_ENUM => os.write_enum_no_tag(v.to_enum_value().unwrap())?,
+            Type::TYPE_UINT32 => os.write_uint32_no_tag(v.to_u32().unwrap())?,
+            Type::TYPE_UINT64 => os.write_uint64_no_tag(v.to_u64().unwrap())?,
+            Type::TYPE_INT32 => os.write_int32_no_tag(v.to_i32().unwrap())?,
+            Type::TYPE_INT64 => os.write_int64_no_tag(v.to_i64().unwrap())?,
+            Type::TYPE_SINT32 => os.write_sint32_no_tag(v.to_i32().unwrap())?,
+            Type::TYPE_SINT64 => os.write_sint64_no_tag(v.to_i64().unwrap())?,
+            Type::TYPE_FIXED32 => os.write_fixed32_no_tag(v.to_u32().unwrap())?,
+            Type::TYPE_FIXED64 => os.write_fixed64_no_tag(v.to_u64().unwrap())?,
+            Type::TYPE_SFIXED32 => os.write_sfixed32_no_tag(v.to_i32().unwrap())?,
+            Type::TYPE_SFIXED64 => os.write_sfixed64_no_tag(v.to_i64().unwrap())?,
+            Type::TYPE_BOOL => os.write_bool_no_tag(v.to_bool().unwrap())?,
+            Type::TYPE_FLOAT => os.write_float_no_tag(v.to_f32().unwrap())?,
+            Type::TYPE_DOUBLE => os.write_double_no_tag(v.to_f64().unwrap())?,
+            Type::TYPE_STRING | Type::TYPE_BYTES | Type::TYPE_MESSAGE | Type::TYPE_GROUP => {
+                panic!("{:?} cannot be packed", proto_type)
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Compute size of map entry message, excluding tag and length
+fn compute_map_entry_size(
+    key_rtb: &RuntimeTypeBox,
+    key_type: &Type,
+    key: &ReflectValueRef,
+    value_rtb: &RuntimeTypeBox,
+    value_type: &Type,
+    value: &ReflectValueRef,
+) -> u32 {
+    saturating_size(
+        compute_singular_size(key_rtb, key_type, 1, key) as u64
+            + compute_singular_size(value_rtb, value_type, 2, value) as u64,
+    )
+}
+
+/// Size of singular field using cached size of message value,
+/// valid only after `compute_singular_size` was called for the value
+fn cached_singular_size(
+    rtb: &RuntimeTypeBox,
+    proto_type: &Type,
+    field_number: u32,
+    v: &ReflectValueRef,
+) -> u32 {
+    match proto_type {
+        Type::TYPE_MESSAGE => {
+            let len = v.to_message().unwrap().get_cached_size_dyn();
+            saturating_size(
+                tag_size(field_number) as u64 + compute_raw_varint32_size(len) as u64 + len as u64,
+            )
+        }
+        _ => compute_singular_size(rtb, proto_type, field_number, v),
+    }
+}
+
 /// Write singular field to output stream
-fn singular_write_to(
+pub(crate) fn singular_write_to(
     rtb: &RuntimeTypeBox,
     proto_type: &Type,
     field_number: u32,
@@ -700,13 +1278,19 @@ fn singular_write_to(
             assert!(matches!(rtb, RuntimeTypeBox::Message(..)));
             if let RuntimeTypeBox::Message(_) = rtb {
                 let msg_v = v.to_message().unwrap();
-                os.write_message_dyn(field_number, &*msg_v)?;
+                // Size is computed and cached by `compute_singular_size`
+                os.write_tag(field_number, WireType::WireTypeLengthDelimited)?;
+                os.write_raw_varint32(msg_v.get_cached_size_dyn())?;
+                msg_v.write_to_with_cached_sizes_dyn(os)?;
             } else {
                 panic!("Protobuf type and Runtime type mismatch");
             }
         }
         Type::TYPE_GROUP => {
-            unimplemented!()
+            let msg_v = v.to_message().unwrap();
+            os.write_tag(field_number, WireType::WireTypeStartGroup)?;
+            msg_v.write_to_with_cached_sizes_dyn(os)?;
+            os.write_tag(field_number, WireType::WireTypeEndGroup)?;
         }
         Type::TYPE_UINT32 => {
             os.write_uint32(field_number, v.to_u32().unwrap())?;
@@ -758,7 +1342,7 @@ fn singular_write_to(
 }
 
 /// Compute singular field size
-fn compute_singular_size(
+pub(crate) fn compute_singular_size(
     rtb: &RuntimeTypeBox,
     proto_type: &Type,
     field_number: u32,
@@ -780,13 +1364,18 @@ fn compute_singular_size(
             if let RuntimeTypeBox::Message(_) = rtb {
                 let msg_v = v.to_message().unwrap();
                 let len = msg_v.compute_size_dyn();
-                tag_size(field_number) + compute_raw_varint32_size(len) + len
+                saturating_size(
+                    tag_size(field_number) as u64
+                        + compute_raw_varint32_size(len) as u64
+                        + len as u64,
+                )
             } else {
                 panic!("Protobuf type and Runtime type mismatch");
             }
         }
         Type::TYPE_GROUP => {
-            unimplemented!()
+            let msg_v = v.to_message().unwrap();
+            saturating_size(tag_size(field_number) as u64 * 2 + msg_v.compute_size_dyn() as u64)
         }
         Type::TYPE_UINT32 => {
             let typed_v = v.to_u32().unwrap();
@@ -832,3 +1421,315 @@ fn compute_singular_size(
         Type::TYPE_DOUBLE => tag_size(field_number) + 8,
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::DynamicMessage;
+    use crate::descriptor::field_descriptor_proto::Label;
+    use crate::descriptor::field_descriptor_proto::Type;
+    use crate::descriptor::file_options::OptimizeMode;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FieldDescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::descriptor::FileOptions;
+    use crate::reflect::FileDescriptor;
+    use crate::reflect::ReflectValueBox;
+    use crate::CodedInputStream;
+    use crate::CodedOutputStream;
+    use crate::InputLimits;
+    use crate::Message;
+
+    #[test]
+    fn debug_depth_limited() {
+        let mut message = DescriptorProto::new();
+        message.set_name("M".to_owned());
+        for _ in 0..20 {
+            let mut outer = DescriptorProto::new();
+            outer.set_name("M".to_owned());
+            outer.nested_type.push(message);
+            message = outer;
+        }
+        let descriptor = FileDescriptor::new_dynamic(
+            DescriptorProto::descriptor_static()
+                .file_descriptor_proto()
+                .clone(),
+            Vec::new(),
+        )
+        .message_by_package_relative_name("DescriptorProto")
+        .unwrap();
+        let mut dynamic = descriptor.new_instance();
+        dynamic
+            .merge_from_bytes_dyn(&message.write_to_bytes().unwrap())
+            .unwrap();
+        let debug = format!("{:?}", dynamic.downcast_ref::<DynamicMessage>().unwrap());
+        assert!(debug.starts_with("google.protobuf.DescriptorProto { name: \"M\""));
+        assert!(debug.contains(crate::text_format::MAX_DEPTH_PLACEHOLDER));
+    }
+
+    #[test]
+    fn write_fields_sorted_by_number() {
+        let mut message = DescriptorProto::new();
+        message.set_name("M".to_owned());
+        for &(name, number) in &[("c", 3), ("a", 1), ("b", 2)] {
+            let mut field = FieldDescriptorProto::new();
+            field.set_name(name.to_owned());
+            field.set_number(number);
+            field.set_field_type(Type::TYPE_INT32);
+            message.field.push(field);
+        }
+        let mut file = FileDescriptorProto::new();
+        file.set_name("sorted.proto".to_owned());
+        file.set_syntax("proto3".to_owned());
+        file.message_type.push(message);
+        let file = FileDescriptor::new_dynamic(file, Vec::new());
+        let descriptor = file.message_by_package_relative_name("M").unwrap();
+
+        assert_eq!(
+            vec!["c", "a", "b"],
+            descriptor
+                .fields()
+                .map(|f| f.get_name().to_owned())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["a", "b", "c"],
+            descriptor
+                .fields_sorted_by_number()
+                .map(|f| f.get_name().to_owned())
+                .collect::<Vec<_>>()
+        );
+
+        let mut m = descriptor.new_instance();
+        for f in descriptor.fields() {
+            f.set_singular_field(&mut *m, ReflectValueBox::I32(10));
+        }
+        assert_eq!(
+            vec![0x08, 10, 0x10, 10, 0x18, 10],
+            m.write_to_bytes_dyn().unwrap()
+        );
+    }
+
+    #[test]
+    fn proto3_repeated_scalars_packed_by_default() {
+        let mut message = DescriptorProto::new();
+        message.set_name("M".to_owned());
+        for &(name, number, packed) in &[("a", 1, None), ("b", 2, Some(false)), ("c", 3, None)] {
+            let mut field = FieldDescriptorProto::new();
+            field.set_name(name.to_owned());
+            field.set_number(number);
+            field.set_label(Label::LABEL_REPEATED);
+            field.set_field_type(if name == "c" {
+                Type::TYPE_STRING
+            } else {
+                Type::TYPE_INT32
+            });
+            if let Some(packed) = packed {
+                field.options.mut_or_default().set_packed(packed);
+            }
+            message.field.push(field);
+        }
+        let mut file = FileDescriptorProto::new();
+        file.set_name("packed.proto".to_owned());
+        file.set_syntax("proto3".to_owned());
+        file.message_type.push(message);
+        let file = FileDescriptor::new_dynamic(file, Vec::new());
+        let descriptor = file.message_by_package_relative_name("M").unwrap();
+
+        let mut m = descriptor.new_instance();
+        for f in descriptor.fields() {
+            let v = match f.get_name() {
+                "c" => ReflectValueBox::String("x".to_owned()),
+                _ => ReflectValueBox::I32(7),
+            };
+            f.mut_repeated(&mut *m).push(v.clone());
+            f.mut_repeated(&mut *m).push(v);
+        }
+        let bytes = m.write_to_bytes_dyn().unwrap();
+        assert_eq!(
+            vec![0x0a, 2, 7, 7, 0x10, 7, 0x10, 7, 0x1a, 1, b'x', 0x1a, 1, b'x'],
+            bytes
+        );
+        assert_eq!(bytes.len() as u32, m.compute_size_dyn());
+    }
+
+    #[test]
+    fn cached_size_includes_unknown_fields() {
+        let descriptor = FileDescriptor::new_dynamic(
+            FileDescriptorProto::descriptor_static()
+                .file_descriptor_proto()
+                .clone(),
+            Vec::new(),
+        )
+        .message_by_package_relative_name("FileDescriptorProto")
+        .unwrap();
+
+        let mut field = FieldDescriptorProto::new();
+        field.set_name("f".to_owned());
+        let mut message = DescriptorProto::new();
+        message.set_name("M".to_owned());
+        message.field.push(field);
+        message.mut_unknown_fields().add_varint(1000, 7);
+        let mut file = FileDescriptorProto::new();
+        file.set_name("a.proto".to_owned());
+        file.message_type.push(message);
+        file.mut_unknown_fields()
+            .add_length_delimited(1001, vec![1, 2, 3]);
+        let bytes = file.write_to_bytes().unwrap();
+
+        let mut m = descriptor.new_instance();
+        m.merge_from_bytes_dyn(&bytes).unwrap();
+        assert_eq!(0, m.get_cached_size_dyn());
+        assert_eq!(bytes.len() as u32, m.compute_size_dyn());
+        assert_eq!(bytes.len() as u32, m.get_cached_size_dyn());
+
+        let mut written = Vec::new();
+        {
+            let mut os = CodedOutputStream::vec(&mut written);
+            m.write_to_with_cached_sizes_dyn(&mut os).unwrap();
+            os.flush().unwrap();
+        }
+        assert_eq!(bytes, written);
+    }
+
+    #[test]
+    fn repeated_message_recursion_limit() {
+        let descriptor = FileDescriptor::new_dynamic(
+            FileDescriptorProto::descriptor_static()
+                .file_descriptor_proto()
+                .clone(),
+            Vec::new(),
+        )
+        .message_by_package_relative_name("FileDescriptorProto")
+        .unwrap();
+
+        let mut file = FileDescriptorProto::new();
+        file.message_type.push(DescriptorProto::new());
+        let bytes = file.write_to_bytes().unwrap();
+
+        let parse = |recursion_limit| {
+            let mut is = CodedInputStream::from_bytes(&bytes).with_limits(InputLimits {
+                recursion_limit,
+                ..InputLimits::default()
+            });
+            let mut m = descriptor.new_instance();
+            m.merge_from_dyn(&mut is).map(|()| m)
+        };
+        assert!(parse(1).is_ok());
+        assert!(parse(0).is_err());
+    }
+
+    #[test]
+    fn group_of_generated_type() {
+        let mut field = FieldDescriptorProto::new();
+        field.set_name("options".to_owned());
+        field.set_number(1);
+        field.set_label(Label::LABEL_OPTIONAL);
+        field.set_field_type(Type::TYPE_GROUP);
+        field.set_type_name(".google.protobuf.FileOptions".to_owned());
+        let mut message = DescriptorProto::new();
+        message.set_name("M".to_owned());
+        message.field.push(field);
+        let mut file = FileDescriptorProto::new();
+        file.set_name("group.proto".to_owned());
+        file.dependency
+            .push("google/protobuf/descriptor.proto".to_owned());
+        file.message_type.push(message);
+        let file = FileDescriptor::new_dynamic(file, vec![crate::descriptor::file_descriptor()]);
+        let descriptor = file.message_by_package_relative_name("M").unwrap();
+        let options = descriptor.get_field_by_name("options").unwrap();
+
+        // group 1 { java_package = "p"; optimize_for = CODE_SIZE; group 2 {} }
+        let bytes = [0x0b, 0x0a, 0x01, b'p', 0x48, 0x02, 0x13, 0x14, 0x0c];
+        let parse = |bytes: &[u8]| {
+            let mut m = descriptor.new_instance();
+            m.merge_from_bytes_dyn(bytes).map(|()| m)
+        };
+        let m = parse(&bytes).unwrap();
+        let value = options.get_singular(&*m).unwrap().to_message().unwrap();
+        let value = value.downcast_ref::<FileOptions>().unwrap();
+        assert_eq!("p", value.get_java_package());
+        assert_eq!(OptimizeMode::CODE_SIZE, value.get_optimize_for());
+
+        // group is not terminated
+        assert!(parse(&bytes[..8]).is_err());
+        // end group tag of different field
+        assert!(parse(&[0x0b, 0x14]).is_err());
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn carllerche_bytes_share_input() {
+        use bytes::Bytes;
+
+        let descriptor = FileDescriptor::new_dynamic(
+            FileDescriptorProto::descriptor_static()
+                .file_descriptor_proto()
+                .clone(),
+            Vec::new(),
+        )
+        .message_by_package_relative_name("FileDescriptorProto")
+        .unwrap();
+
+        let mut file = FileDescriptorProto::new();
+        file.set_name("a.proto".to_owned());
+        file.dependency.push("b.proto".to_owned());
+        let bytes = Bytes::from(file.write_to_bytes().unwrap());
+        let input = bytes.as_ptr() as usize..bytes.as_ptr() as usize + bytes.len();
+
+        let m = descriptor.parse_from_carllerche_bytes(&bytes).unwrap();
+        let name = descriptor.get_field_by_name("name").unwrap();
+        let dependency = descriptor.get_field_by_name("dependency").unwrap();
+        let values = vec![
+            name.get_singular(&*m).unwrap(),
+            dependency.get_repeated(&*m).get(0),
+        ];
+        for v in values {
+            let s = v.to_str().unwrap();
+            assert!(input.contains(&(s.as_ptr() as usize)), "{:?} is a copy", s);
+        }
+
+        // Boxed values are copies
+        assert_eq!(
+            ReflectValueBox::String("a.proto".to_owned()),
+            name.get_singular(&*m).unwrap().to_box()
+        );
+        assert_eq!(
+            file.write_to_bytes().unwrap(),
+            m.write_to_bytes_dyn().unwrap()
+        );
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn carllerche_bytes_share_input_map_keys() {
+        use bytes::Bytes;
+
+        use crate::reflect::ReflectValueRef;
+        use crate::well_known_types::Struct;
+        use crate::well_known_types::Value;
+
+        let descriptor = FileDescriptor::new_dynamic(
+            Struct::descriptor_static().file_descriptor_proto().clone(),
+            Vec::new(),
+        )
+        .message_by_package_relative_name("Struct")
+        .unwrap();
+
+        let mut s = Struct::new();
+        let mut value = Value::new();
+        value.set_string_value("v".to_owned());
+        s.fields.insert("k".to_owned(), value);
+        let bytes = Bytes::from(s.write_to_bytes().unwrap());
+        let input = bytes.as_ptr() as usize..bytes.as_ptr() as usize + bytes.len();
+
+        let m = descriptor.parse_from_carllerche_bytes(&bytes).unwrap();
+        let fields = descriptor.get_field_by_name("fields").unwrap();
+        let map = fields.get_map(&*m);
+        let (k, _) = map.into_iter().next().unwrap();
+        let k = k.to_str().unwrap();
+        assert_eq!("k", k);
+        assert!(input.contains(&(k.as_ptr() as usize)), "key is a copy");
+        assert!(map.get(ReflectValueRef::String("k")).is_some());
+        assert_eq!(s.write_to_bytes().unwrap(), m.write_to_bytes_dyn().unwrap());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::reflect::dynamic::value::DynamicValue;
 use crate::reflect::value::value_ref::ReflectValueMut;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::ReflectValueRef;
@@ -6,7 +7,7 @@ use crate::reflect::RuntimeTypeBox;
 #[derive(Debug, Clone)]
 pub(crate) struct DynamicOptional {
     elem: RuntimeTypeBox,
-    value: Option<ReflectValueBox>,
+    value: Option<DynamicValue>,
 }
 
 impl DynamicOptional {
@@ -16,7 +17,7 @@ impl DynamicOptional {
 
     pub fn mut_or_default(&mut self) -> ReflectValueMut {
         if let None = self.value {
-            self.value = Some(self.elem.default_value_ref().to_box());
+            self.value = Some(self.elem.default_value_ref().to_box().into());
         }
         self.value.as_mut().unwrap().as_value_mut()
     }
@@ -26,10 +27,18 @@ impl DynamicOptional {
     }
 
     pub fn get(&self) -> Option<ReflectValueRef> {
-        self.value.as_ref().map(ReflectValueBox::as_value_ref)
+        self.value.as_ref().map(DynamicValue::as_value_ref)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut ReflectValueBox> {
+        self.value.iter_mut().filter_map(DynamicValue::boxed_mut)
     }
 
     pub fn set(&mut self, value: ReflectValueBox) {
+        self.set_value(value.into());
+    }
+
+    pub fn set_value(&mut self, value: DynamicValue) {
         assert_eq!(value.get_type(), self.elem);
         self.value = Some(value);
     }
@@ -1,3 +1,4 @@
+use crate::reflect::dynamic::value::DynamicValue;
 use crate::reflect::repeated::ReflectRepeated;
 use crate::reflect::repeated::ReflectRepeatedIter;
 use crate::reflect::ReflectValueBox;
@@ -7,7 +8,7 @@ use crate::reflect::RuntimeTypeBox;
 #[derive(Debug, Clone)]
 pub(crate) struct DynamicRepeated {
     elem: RuntimeTypeBox,
-    vec: Vec<ReflectValueBox>,
+    vec: Vec<DynamicValue>,
 }
 
 impl ReflectRepeated for DynamicRepeated {
@@ -25,18 +26,21 @@ impl ReflectRepeated for DynamicRepeated {
 
     fn set(&mut self, index: usize, value: ReflectValueBox) {
         assert_eq!(self.elem, value.get_type());
-        self.vec[index] = value;
+        self.vec[index] = value.into();
     }
 
     fn push(&mut self, value: ReflectValueBox) {
-        assert_eq!(self.elem, value.get_type());
-        self.vec.push(value);
+        self.push_value(value.into());
     }
 
     fn clear(&mut self) {
         self.vec.clear();
     }
 
+    fn swap(&mut self, a: usize, b: usize) {
+        self.vec.swap(a, b);
+    }
+
     fn element_type(&self) -> RuntimeTypeBox {
         self.elem.clone()
     }
@@ -49,4 +53,13 @@ impl DynamicRepeated {
             vec: Vec::new(),
         }
     }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut ReflectValueBox> {
+        self.vec.iter_mut().filter_map(DynamicValue::boxed_mut)
+    }
+
+    pub fn push_value(&mut self, value: DynamicValue) {
+        assert_eq!(self.elem, value.get_type());
+        self.vec.push(value);
+    }
 }
@@ -0,0 +1,86 @@
+#[cfg(feature = "bytes")]
+use bytes::Bytes;
+
+#[cfg(feature = "bytes")]
+use crate::chars::Chars;
+use crate::reflect::value::value_ref::ReflectValueMut;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::ReflectValueRef;
+use crate::reflect::RuntimeTypeBox;
+
+/// Value stored in a dynamic message field.
+///
+/// `string` and `bytes` values parsed from `Bytes` input share memory with it,
+/// and are converted to [`ReflectValueBox`] only when taken out of the message.
+#[derive(Debug, Clone)]
+pub(crate) enum DynamicValue {
+    Box(ReflectValueBox),
+    #[cfg(feature = "bytes")]
+    Chars(Chars),
+    #[cfg(feature = "bytes")]
+    Bytes(Bytes),
+}
+
+impl From<ReflectValueBox> for DynamicValue {
+    fn from(v: ReflectValueBox) -> Self {
+        DynamicValue::Box(v)
+    }
+}
+
+impl DynamicValue {
+    pub fn into_box(self) -> ReflectValueBox {
+        match self {
+            DynamicValue::Box(v) => v,
+            #[cfg(feature = "bytes")]
+            v => v.as_value_ref().to_box(),
+        }
+    }
+
+    pub fn get_type(&self) -> RuntimeTypeBox {
+        match self {
+            DynamicValue::Box(v) => v.get_type(),
+            #[cfg(feature = "bytes")]
+            DynamicValue::Chars(..) => RuntimeTypeBox::String,
+            #[cfg(feature = "bytes")]
+            DynamicValue::Bytes(..) => RuntimeTypeBox::VecU8,
+        }
+    }
+
+    pub fn as_value_ref(&self) -> ReflectValueRef<'_> {
+        match self {
+            DynamicValue::Box(v) => v.as_value_ref(),
+            #[cfg(feature = "bytes")]
+            DynamicValue::Chars(v) => ReflectValueRef::String(v),
+            #[cfg(feature = "bytes")]
+            DynamicValue::Bytes(v) => ReflectValueRef::Bytes(v),
+        }
+    }
+
+    /// Mutable reference to the boxed value, shared values are copied first.
+    pub fn as_box_mut(&mut self) -> &mut ReflectValueBox {
+        match self {
+            DynamicValue::Box(v) => v,
+            #[cfg(feature = "bytes")]
+            _ => {
+                *self = DynamicValue::Box(self.as_value_ref().to_box());
+                match self {
+                    DynamicValue::Box(v) => v,
+                    _ => unreachable!(),
+                }
+            }
+        }
+    }
+
+    pub fn as_value_mut(&mut self) -> ReflectValueMut<'_> {
+        self.as_box_mut().as_value_mut()
+    }
+
+    /// Boxed value, `None` for values sharing memory with the input.
+    pub fn boxed_mut(&mut self) -> Option<&mut ReflectValueBox> {
+        match self {
+            DynamicValue::Box(v) => Some(v),
+            #[cfg(feature = "bytes")]
+            _ => None,
+        }
+    }
+}
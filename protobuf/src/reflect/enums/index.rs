@@ -14,7 +14,8 @@ impl<S: Hash + Eq> EnumIndex<S> {
         let mut index_by_name = HashMap::new();
         let mut index_by_number = HashMap::new();
         for (i, v) in proto.value.iter().enumerate() {
-            index_by_number.insert(v.get_number(), i);
+            // With `allow_alias` the first value declared with the number is canonical
+            index_by_number.entry(v.get_number()).or_insert(i);
             index_by_name.insert(T::from(v.get_name()), i);
         }
 
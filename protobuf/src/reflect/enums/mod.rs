@@ -10,11 +10,25 @@ use crate::reflect::enums::dynamic::DynamicEnumDescriptor;
 use crate::reflect::enums::generated::GeneratedEnumDescriptor;
 use crate::reflect::file::FileDescriptorImpl;
 use crate::reflect::FileDescriptor;
+use crate::reflect::MessageDescriptor;
 
 pub(crate) mod dynamic;
 pub(crate) mod generated;
 pub(crate) mod index;
 
+/// Which name is printed for enum numbers with several names
+/// (enums with `allow_alias` option).
+///
+/// Any name is accepted when parsing regardless of this option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EnumAliasName {
+    /// First name declared with the number, like `protoc` prints.
+    #[default]
+    Canonical,
+    /// Last name declared with the number, as printed by earlier versions of this crate.
+    LastDeclared,
+}
+
 /// Description for enum variant.
 ///
 /// Used in reflection.
@@ -135,6 +149,16 @@ impl EnumDescriptor {
         }
     }
 
+    /// Descriptor objects of generated enum, which are never deallocated.
+    ///
+    /// `None` for dynamic enums.
+    pub(crate) fn get_static_proto(&self) -> Option<&'static EnumDescriptorProto> {
+        match &self.file_descriptor.imp {
+            FileDescriptorImpl::Generated(g) => Some(g.enums[self.index].proto),
+            FileDescriptorImpl::Dynamic(..) => None,
+        }
+    }
+
     /// Enum name as given in `.proto` file
     pub fn get_name(&self) -> &str {
         // TODO: get_proto is inefficient
@@ -149,6 +173,19 @@ impl EnumDescriptor {
         }
     }
 
+    /// [`FileDescriptor`] containing this enum.
+    pub fn file(&self) -> &FileDescriptor {
+        &self.file_descriptor
+    }
+
+    /// Message containing this enum, `None` for top-level enums.
+    pub fn containing_message(&self) -> Option<MessageDescriptor> {
+        self.file_descriptor
+            .enum_index_entry(self.index)
+            .containing_message
+            .map(|i| MessageDescriptor::new(self.file_descriptor.clone(), i))
+    }
+
     /// Get `EnumDescriptor` object for given enum type
     pub fn for_type<E: ProtobufEnum>() -> EnumDescriptor {
         E::enum_descriptor_static()
@@ -188,7 +225,10 @@ impl EnumDescriptor {
         })
     }
 
-    /// Find enum variant by number
+    /// Find enum variant by number.
+    ///
+    /// If several variants have the number (`allow_alias` option),
+    /// the first declared variant is returned.
     pub fn get_value_by_number(&self, number: i32) -> Option<EnumValueDescriptor> {
         let index = match self.get_impl() {
             EnumDescriptorImplRef::Generated(g) => *g.indices.index_by_number.get(&number)?,
@@ -200,6 +240,32 @@ impl EnumDescriptor {
         })
     }
 
+    /// Find enum variant by number, choosing among variants with the same number
+    /// as specified.
+    pub fn get_value_by_number_with_alias(
+        &self,
+        number: i32,
+        alias: EnumAliasName,
+    ) -> Option<EnumValueDescriptor> {
+        match alias {
+            EnumAliasName::Canonical => self.get_value_by_number(number),
+            EnumAliasName::LastDeclared => self.values().filter(|v| v.value() == number).last(),
+        }
+    }
+
+    /// Check if enum has a variant with given number.
+    pub fn contains_number(&self, number: i32) -> bool {
+        self.get_value_by_number(number).is_some()
+    }
+
+    /// Is this enum closed, i. e. defined in proto2 file.
+    ///
+    /// Fields of closed enum type can only hold numbers declared in the enum,
+    /// while fields of open (proto3) enum type can hold any `i32` number.
+    pub fn is_closed(&self) -> bool {
+        self.file_descriptor.proto().get_syntax() != "proto3"
+    }
+
     /// Default enum value (first variant)
     pub fn get_default_value(&self) -> EnumValueDescriptor {
         EnumValueDescriptor {
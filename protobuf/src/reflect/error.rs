@@ -0,0 +1,212 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::reflect::FieldDescriptor;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::RuntimeTypeBox;
+
+/// Error returned by fallible `try_` reflection functions,
+/// e. g. [`FieldDescriptor::try_set_singular_field`],
+/// which panicking counterparts abort on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReflectError {
+    /// Field belongs to a different message type.
+    WrongMessageType {
+        /// Full name of the field.
+        field: String,
+        /// Full name of the message type passed to the function.
+        message: String,
+    },
+    /// Field is not of the kind required by the function,
+    /// e. g. a repeated field is used as singular.
+    WrongFieldKind {
+        /// Full name of the field.
+        field: String,
+        /// Required kind: `singular`, `repeated`, `map` or `message`.
+        expected: &'static str,
+    },
+    /// Value type does not match field, element, map key or map value type.
+    WrongValueType {
+        /// Expected type.
+        expected: String,
+        /// Type of the value.
+        actual: String,
+    },
+    /// Number is not declared in [closed](crate::reflect::EnumDescriptor::is_closed) enum.
+    UnknownEnumValue {
+        /// Full name of the enum.
+        enum_name: String,
+        /// The number.
+        number: i32,
+    },
+    /// Index of repeated field element is out of range.
+    IndexOutOfRange {
+        /// The index.
+        index: usize,
+        /// Number of elements.
+        len: usize,
+    },
+    /// Message has no field with given name.
+    NoSuchField {
+        /// Full name of the message type.
+        message: String,
+        /// The field name.
+        field: String,
+    },
+    /// [`FieldPath`](crate::reflect::FieldPath) cannot be used with the operation.
+    InvalidPath {
+        /// The path.
+        path: String,
+        /// What is wrong.
+        reason: &'static str,
+    },
+}
+
+impl fmt::Display for ReflectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReflectError::WrongMessageType { field, message } => {
+                write!(f, "field {} does not belong to message {}", field, message)
+            }
+            ReflectError::WrongFieldKind { field, expected } => {
+                write!(f, "field {} is not {}", field, expected)
+            }
+            ReflectError::WrongValueType { expected, actual } => {
+                write!(f, "expected value of type {}, got {}", expected, actual)
+            }
+            ReflectError::UnknownEnumValue { enum_name, number } => write!(
+                f,
+                "number {} is not declared in closed enum {}",
+                number, enum_name
+            ),
+            ReflectError::IndexOutOfRange { index, len } => {
+                write!(f, "index {} is out of range of {} elements", index, len)
+            }
+            ReflectError::NoSuchField { message, field } => {
+                write!(f, "message {} has no field {}", message, field)
+            }
+            ReflectError::InvalidPath { path, reason } => {
+                write!(f, "invalid path `{}`: {}", path, reason)
+            }
+        }
+    }
+}
+
+impl Error for ReflectError {}
+
+impl ReflectError {
+    pub(crate) fn wrong_field_kind(
+        field: &FieldDescriptor,
+        expected: &'static str,
+    ) -> ReflectError {
+        ReflectError::WrongFieldKind {
+            field: field.to_string(),
+            expected,
+        }
+    }
+
+    /// Check value type and that enum number is allowed.
+    pub(crate) fn check_value(
+        expected: &RuntimeTypeBox,
+        value: &ReflectValueBox,
+    ) -> Result<(), ReflectError> {
+        let actual = value.get_type();
+        if &actual != expected {
+            return Err(ReflectError::WrongValueType {
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            });
+        }
+        value.check_enum_number_allowed()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ReflectError;
+    use crate::descriptor::file_descriptor;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::reflect::FileDescriptor;
+    use crate::reflect::MessageDescriptor;
+    use crate::reflect::ReflectValueBox;
+    use crate::Message;
+    use crate::MessageDyn;
+
+    fn check(descriptor: &MessageDescriptor, message: &mut dyn MessageDyn) {
+        let name = descriptor.get_field_by_name("name").unwrap();
+        let dependency = descriptor.get_field_by_name("dependency").unwrap();
+        let options = descriptor.get_field_by_name("options").unwrap();
+        let other = DescriptorProto::descriptor_static()
+            .get_field_by_name("name")
+            .unwrap();
+
+        assert!(matches!(
+            other.try_get_reflect(message),
+            Err(ReflectError::WrongMessageType { .. })
+        ));
+        assert!(matches!(
+            other.try_set_singular_field(message, ReflectValueBox::String("a".to_owned())),
+            Err(ReflectError::WrongMessageType { .. })
+        ));
+        assert_eq!(
+            Err(ReflectError::WrongFieldKind {
+                field: dependency.to_string(),
+                expected: "singular",
+            }),
+            dependency.try_get_singular(message).map(|_| ())
+        );
+        assert!(name.try_get_repeated(message).is_err());
+        assert!(name.try_get_map(message).is_err());
+        assert!(name.try_mut_repeated(message).is_err());
+        assert!(name.try_mut_map(message).is_err());
+        assert!(name.try_get_message(message).is_err());
+        assert!(name.try_mut_message(message).is_err());
+        assert_eq!(
+            Err(ReflectError::WrongValueType {
+                expected: "String".to_owned(),
+                actual: "i32".to_owned(),
+            }),
+            name.try_set_singular_field(message, ReflectValueBox::I32(1))
+        );
+
+        name.try_set_singular_field(message, ReflectValueBox::String("a".to_owned()))
+            .unwrap();
+        assert_eq!(
+            "a",
+            name.try_get_singular_field_or_default(message)
+                .unwrap()
+                .to_str()
+                .unwrap()
+        );
+        assert!(options.try_get_message(message).is_ok());
+        assert!(options.try_mut_message(message).is_ok());
+
+        let mut repeated = dependency.try_mut_repeated(message).unwrap();
+        assert!(repeated.try_push(ReflectValueBox::Bool(true)).is_err());
+        repeated
+            .try_push(ReflectValueBox::String("b".to_owned()))
+            .unwrap();
+        assert_eq!(
+            Err(ReflectError::IndexOutOfRange { index: 1, len: 1 }),
+            repeated.try_set(1, ReflectValueBox::String("c".to_owned()))
+        );
+        assert_eq!(1, dependency.try_get_repeated(message).unwrap().len());
+    }
+
+    #[test]
+    fn generated() {
+        let mut message = FileDescriptorProto::new();
+        check(&FileDescriptorProto::descriptor_static(), &mut message);
+    }
+
+    #[test]
+    fn dynamic() {
+        let dynamic = FileDescriptor::new_dynamic(file_descriptor().proto().clone(), Vec::new());
+        let descriptor = dynamic
+            .message_by_package_relative_name("FileDescriptorProto")
+            .unwrap();
+        let mut message = descriptor.new_instance();
+        check(&descriptor, &mut *message);
+    }
+}
@@ -0,0 +1,131 @@
+use std::fmt;
+
+use crate::descriptor::field_descriptor_proto;
+use crate::descriptor::FieldDescriptorProto;
+use crate::reflect::name::concat_paths;
+use crate::reflect::FileDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+
+/// Extension field declared in `.proto` file, at top level or inside a message.
+#[derive(Clone, Eq, PartialEq)]
+pub struct ExtensionDescriptor {
+    file_descriptor: FileDescriptor,
+    scope: Option<MessageDescriptor>,
+    index: usize,
+}
+
+impl fmt::Debug for ExtensionDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtensionDescriptor")
+            .field("full_name", &self.full_name())
+            .field("number", &self.number())
+            .finish()
+    }
+}
+
+impl ExtensionDescriptor {
+    pub(crate) fn new(
+        file_descriptor: FileDescriptor,
+        scope: Option<MessageDescriptor>,
+        index: usize,
+    ) -> ExtensionDescriptor {
+        ExtensionDescriptor {
+            file_descriptor,
+            scope,
+            index,
+        }
+    }
+
+    /// `.proto` part associated with this descriptor.
+    pub fn get_proto(&self) -> &FieldDescriptorProto {
+        match &self.scope {
+            Some(scope) => &scope.get_proto().extension[self.index],
+            None => &self.file_descriptor.proto().extension[self.index],
+        }
+    }
+
+    /// Extension name as specified in `.proto` file.
+    pub fn get_name(&self) -> &str {
+        self.get_proto().get_name()
+    }
+
+    /// Fully qualified protobuf name of the extension.
+    pub fn full_name(&self) -> String {
+        match &self.scope {
+            Some(scope) => concat_paths(scope.full_name(), self.get_name()),
+            None => concat_paths(self.file_descriptor.proto().get_package(), self.get_name()),
+        }
+    }
+
+    /// Extension field number.
+    pub fn number(&self) -> u32 {
+        self.get_proto().get_number() as u32
+    }
+
+    /// Full name of the extended message without leading dot.
+    pub fn extendee_name(&self) -> &str {
+        self.get_proto().get_extendee().trim_start_matches('.')
+    }
+
+    /// Extended message, searched in this file and its dependencies.
+    pub fn extendee(&self) -> Option<MessageDescriptor> {
+        let name = format!(".{}", self.extendee_name());
+        self.file_descriptor
+            .all_files()
+            .into_iter()
+            .find_map(|f| f.message_by_full_name(&name))
+    }
+
+    /// Dynamic representation of extension type.
+    ///
+    /// # Panics
+    ///
+    /// If extension type cannot be found in the file or its dependencies.
+    pub fn runtime_field_type(&self) -> RuntimeFieldType {
+        match self.try_runtime_field_type() {
+            Some(t) => t,
+            None => panic!("type not found: {}", self.get_proto().get_type_name()),
+        }
+    }
+
+    /// Same as [`runtime_field_type`](ExtensionDescriptor::runtime_field_type),
+    /// `None` if extension type cannot be found in the file or its dependencies.
+    pub(crate) fn try_runtime_field_type(&self) -> Option<RuntimeFieldType> {
+        let proto = self.get_proto();
+        let type_name = proto.get_type_name();
+        let find_in_files = || self.file_descriptor.all_files().into_iter();
+        let element = match proto.get_field_type() {
+            field_descriptor_proto::Type::TYPE_MESSAGE
+            | field_descriptor_proto::Type::TYPE_GROUP
+            | field_descriptor_proto::Type::TYPE_ENUM
+                if !type_name.starts_with('.') =>
+            {
+                return None;
+            }
+            field_descriptor_proto::Type::TYPE_MESSAGE
+            | field_descriptor_proto::Type::TYPE_GROUP => RuntimeTypeBox::Message(
+                find_in_files().find_map(|f| f.message_by_full_name(type_name))?,
+            ),
+            field_descriptor_proto::Type::TYPE_ENUM => {
+                RuntimeTypeBox::Enum(find_in_files().find_map(|f| f.enum_by_full_name(type_name))?)
+            }
+            t => RuntimeTypeBox::from_proto_type(t),
+        };
+        Some(match proto.get_label() {
+            field_descriptor_proto::Label::LABEL_REPEATED => RuntimeFieldType::Repeated(element),
+            _ => RuntimeFieldType::Singular(element),
+        })
+    }
+
+    /// Message in which this extension is declared, `None` for top-level extensions.
+    pub fn scope(&self) -> Option<&MessageDescriptor> {
+        self.scope.as_ref()
+    }
+
+    /// [`FileDescriptor`] containing this extension.
+    pub fn file(&self) -> &FileDescriptor {
+        &self.file_descriptor
+    }
+}
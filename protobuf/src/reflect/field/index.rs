@@ -62,6 +62,9 @@ pub(crate) enum FieldDefaultValue {
 pub(crate) struct FieldIndex {
     pub(crate) json_name: String,
     pub(crate) field_type: ForwardRuntimeFieldType,
+    /// Protobuf types of key and value of map field.
+    pub(crate) map_entry_types:
+        Option<(field_descriptor_proto::Type, field_descriptor_proto::Type)>,
     pub(crate) default_value: Option<FieldDefaultValue>,
 }
 
@@ -120,6 +123,7 @@ impl FieldIndex {
             default_value,
             json_name,
             field_type: building.resolve_field_type(field),
+            map_entry_types: building.map_entry_types(field),
         }
     }
 
@@ -13,6 +13,7 @@ use crate::reflect::field::dynamic::DynamicFieldDescriptorRef;
 use crate::reflect::field::index::FieldIndex;
 use crate::reflect::map::ReflectMapMut;
 use crate::reflect::map::ReflectMapRef;
+use crate::reflect::map_entry;
 use crate::reflect::message::message_ref::MessageRef;
 use crate::reflect::message::MessageDescriptorImplRef;
 use crate::reflect::oneof::OneofDescriptor;
@@ -21,7 +22,10 @@ use crate::reflect::reflect_eq::ReflectEqMode;
 use crate::reflect::repeated::ReflectRepeatedMut;
 use crate::reflect::repeated::ReflectRepeatedRef;
 use crate::reflect::value::value_ref::ReflectValueMut;
+use crate::reflect::CustomOption;
+use crate::reflect::FileDescriptor;
 use crate::reflect::MessageDescriptor;
+use crate::reflect::ReflectError;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::ReflectValueRef;
 use crate::reflect::RuntimeTypeBox;
@@ -41,13 +45,27 @@ pub enum ReflectFieldRef<'a> {
 }
 
 impl<'a> ReflectFieldRef<'a> {
-    pub(crate) fn default_for_field(field: &FieldDescriptor) -> ReflectFieldRef<'a> {
+    /// Value of unset field: `None` for singular fields, empty for repeated and map fields.
+    pub fn default_for_field(field: &'a FieldDescriptor) -> ReflectFieldRef<'a> {
+        ReflectFieldRef::default_for_index(&field.message_descriptor, field.index)
+    }
+
+    pub(crate) fn default_for_index(
+        message: &'a MessageDescriptor,
+        index: usize,
+    ) -> ReflectFieldRef<'a> {
+        let field = FieldDescriptor {
+            message_descriptor: message.clone(),
+            index,
+        };
         match field.runtime_field_type() {
             RuntimeFieldType::Singular(_) => ReflectFieldRef::Optional(None),
             RuntimeFieldType::Repeated(elem) => {
                 ReflectFieldRef::Repeated(ReflectRepeatedRef::new_empty(elem))
             }
-            RuntimeFieldType::Map(..) => unimplemented!(),
+            RuntimeFieldType::Map(..) => {
+                ReflectFieldRef::Map(ReflectMapRef::new_empty(message, index))
+            }
         }
     }
 }
@@ -68,6 +86,7 @@ impl<'a> ReflectEq for ReflectFieldRef<'a> {
 }
 
 /// Reflective representation of field type
+#[derive(Clone)]
 pub enum RuntimeFieldType {
     /// Singular field (required, optional for proto2 or singular for proto3)
     Singular(RuntimeTypeBox),
@@ -109,6 +128,16 @@ impl FieldDescriptor {
         self.get_proto().get_name()
     }
 
+    /// Message containing this field.
+    pub fn containing_message(&self) -> &MessageDescriptor {
+        &self.message_descriptor
+    }
+
+    /// [`FileDescriptor`] containing this field.
+    pub fn file(&self) -> &FileDescriptor {
+        self.message_descriptor.file_descriptor()
+    }
+
     /// Oneof descriptor containing this field.
     pub fn containing_oneof(&self) -> Option<OneofDescriptor> {
         let proto = self.get_proto();
@@ -122,10 +151,23 @@ impl FieldDescriptor {
         }
     }
 
+    /// Custom options of this field,
+    /// see [`FileDescriptor::custom_options`](crate::reflect::FileDescriptor::custom_options).
+    pub fn custom_options(&self) -> &[CustomOption] {
+        self.message_descriptor.field_custom_options(self.index)
+    }
+
     fn get_index(&self) -> &FieldIndex {
         &self.message_descriptor.get_index().fields[self.index]
     }
 
+    /// Protobuf types of key and value of map field.
+    pub(crate) fn map_entry_proto_types(
+        &self,
+    ) -> Option<(field_descriptor_proto::Type, field_descriptor_proto::Type)> {
+        self.get_index().map_entry_types
+    }
+
     /// JSON field name.
     ///
     /// Can be different from `.proto` field name.
@@ -178,6 +220,44 @@ impl FieldDescriptor {
         }
     }
 
+    /// Synthesized entry message type of a map field, with fields
+    /// `key = 1` and `value = 2`; `None` if this field is not a map field.
+    ///
+    /// Returned type is always dynamic, so instances of entry messages can be
+    /// created with [`new_instance`](MessageDescriptor::new_instance).
+    /// Map entries of generated messages have no Rust types,
+    /// so for them this is a type from a dynamic copy of the file.
+    pub fn map_entry_descriptor(&self) -> Option<MessageDescriptor> {
+        map_entry::entry_descriptor(self)
+    }
+
+    /// Map field as a repeated field of entry messages
+    /// of type [`map_entry_descriptor`](Self::map_entry_descriptor),
+    /// in map iteration order.
+    ///
+    /// Fail if this field belongs to a different message type or field is not `map`.
+    pub fn map_to_entries(
+        &self,
+        m: &dyn MessageDyn,
+    ) -> Result<Vec<Box<dyn MessageDyn>>, ReflectError> {
+        map_entry::map_to_entries(self, m)
+    }
+
+    /// Replace map field contents with entry messages.
+    ///
+    /// Like when parsing, missing keys and values are default,
+    /// and for duplicate keys the last entry wins.
+    ///
+    /// Fail if this field belongs to a different message type, field is not `map`
+    /// or entries are not of this field map entry type; map is unchanged then.
+    pub fn map_from_entries(
+        &self,
+        m: &mut dyn MessageDyn,
+        entries: &[Box<dyn MessageDyn>],
+    ) -> Result<(), ReflectError> {
+        map_entry::map_from_entries(self, m, entries)
+    }
+
     /// Check if field is set in given message.
     ///
     /// For repeated field or map field return `true` if
@@ -195,6 +275,25 @@ impl FieldDescriptor {
         }
     }
 
+    /// Unset the field: singular field becomes unset,
+    /// repeated and map fields become empty.
+    ///
+    /// # Panics
+    ///
+    /// If this field belongs to a different message type.
+    pub fn clear_field(&self, m: &mut dyn MessageDyn) {
+        match self.get_impl() {
+            FieldDescriptorImplRef::Generated(GeneratedFieldAccessor::V2(a)) => match a {
+                AccessorV2::Singular(a) => a.accessor.clear_field(m, self),
+                AccessorV2::Repeated(..) => self.mut_repeated(m).clear(),
+                AccessorV2::Map(..) => self.mut_map(m).clear(),
+            },
+            FieldDescriptorImplRef::Dynamic(..) => {
+                DynamicMessage::downcast_mut(m).clear_field(self)
+            }
+        }
+    }
+
     // accessors
 
     fn singular(&self) -> SingularFieldAccessorRef {
@@ -245,6 +344,17 @@ impl FieldDescriptor {
         }
     }
 
+    /// Fallible version of [`get_message`](FieldDescriptor::get_message).
+    pub fn try_get_message<'a>(
+        &self,
+        m: &'a dyn MessageDyn,
+    ) -> Result<MessageRef<'a>, ReflectError> {
+        match self.try_get_singular_field_or_default(m)? {
+            ReflectValueRef::Message(m) => Ok(m),
+            _ => Err(ReflectError::wrong_field_kind(self, "message")),
+        }
+    }
+
     /// Get a mutable reference to a message field.
     /// Initialize field with default message if unset.
     ///
@@ -258,6 +368,18 @@ impl FieldDescriptor {
         }
     }
 
+    /// Fallible version of [`mut_message`](FieldDescriptor::mut_message).
+    pub fn try_mut_message<'a>(
+        &self,
+        m: &'a mut dyn MessageDyn,
+    ) -> Result<&'a mut dyn MessageDyn, ReflectError> {
+        self.check_message(m)?;
+        match self.runtime_field_type() {
+            RuntimeFieldType::Singular(RuntimeTypeBox::Message(..)) => Ok(self.mut_message(m)),
+            _ => Err(ReflectError::wrong_field_kind(self, "message")),
+        }
+    }
+
     /// Default value.
     ///
     /// # Panics
@@ -267,6 +389,22 @@ impl FieldDescriptor {
         self.get_index().default_value(self)
     }
 
+    /// Default value of the field.
+    ///
+    /// This is explicit `[default = ...]` value if specified in proto2 file,
+    /// otherwise first declared value for enum fields, empty message for message fields
+    /// and zero for other fields. For repeated and map fields this is the default
+    /// value of the element type or map value type.
+    ///
+    /// The value borrows from the descriptor because explicit
+    /// `string` and `bytes` defaults are stored in the descriptor.
+    pub fn default_value(&self) -> ReflectValueRef<'_> {
+        match self.runtime_field_type() {
+            RuntimeFieldType::Singular(_) => self.singular_default_value(),
+            RuntimeFieldType::Repeated(t) | RuntimeFieldType::Map(_, t) => t.default_value_ref(),
+        }
+    }
+
     /// Get singular field value.
     ///
     /// Return field default value if field is unset.
@@ -291,6 +429,19 @@ impl FieldDescriptor {
         }
     }
 
+    /// Fallible version of
+    /// [`get_singular_field_or_default`](FieldDescriptor::get_singular_field_or_default).
+    pub fn try_get_singular_field_or_default<'a>(
+        &self,
+        m: &'a dyn MessageDyn,
+    ) -> Result<ReflectValueRef<'a>, ReflectError> {
+        self.check_message(m)?;
+        if !self.is_singular() {
+            return Err(ReflectError::wrong_field_kind(self, "singular"));
+        }
+        Ok(self.get_singular_field_or_default(m))
+    }
+
     // Not public because it is not implemented for all types
     fn mut_singular_field_or_default<'a>(&self, m: &'a mut dyn MessageDyn) -> ReflectValueMut<'a> {
         match self.singular() {
@@ -319,6 +470,11 @@ impl FieldDescriptor {
     ///
     /// If this field belongs to a different message type or
     /// field is not singular or value is of different type.
+    ///
+    /// Numbers not declared in [closed](crate::reflect::EnumDescriptor::is_closed) enum
+    /// are stored as is, like when such numbers are parsed;
+    /// use [`try_set_singular_field`](FieldDescriptor::try_set_singular_field)
+    /// to reject them.
     pub fn set_singular_field(&self, m: &mut dyn MessageDyn, value: ReflectValueBox) {
         match self.singular() {
             SingularFieldAccessorRef::Generated(g) => g.accessor.set_field(m, value),
@@ -326,6 +482,23 @@ impl FieldDescriptor {
         }
     }
 
+    /// Fallible version of [`set_singular_field`](FieldDescriptor::set_singular_field).
+    ///
+    /// Also fail if value is a number not declared in closed enum.
+    pub fn try_set_singular_field(
+        &self,
+        m: &mut dyn MessageDyn,
+        value: ReflectValueBox,
+    ) -> Result<(), ReflectError> {
+        self.check_message(m)?;
+        match self.runtime_field_type() {
+            RuntimeFieldType::Singular(t) => ReflectError::check_value(&t, &value)?,
+            _ => return Err(ReflectError::wrong_field_kind(self, "singular")),
+        }
+        self.set_singular_field(m, value);
+        Ok(())
+    }
+
     /// Dynamic representation of field type.
     pub fn runtime_field_type(&self) -> RuntimeFieldType {
         self.get_index().field_type.resolve(self)
@@ -343,6 +516,26 @@ impl FieldDescriptor {
         }
     }
 
+    /// Fallible version of [`get_reflect`](FieldDescriptor::get_reflect).
+    pub fn try_get_reflect<'a>(
+        &self,
+        m: &'a dyn MessageDyn,
+    ) -> Result<ReflectFieldRef<'a>, ReflectError> {
+        self.check_message(m)?;
+        Ok(self.get_reflect(m))
+    }
+
+    fn check_message(&self, m: &dyn MessageDyn) -> Result<(), ReflectError> {
+        let descriptor = m.descriptor_dyn();
+        if &descriptor != self.containing_message() {
+            return Err(ReflectError::WrongMessageType {
+                field: self.to_string(),
+                message: descriptor.full_name().to_owned(),
+            });
+        }
+        Ok(())
+    }
+
     /// Get singular field value.
     ///
     /// Return `None` if field is unset.
@@ -357,6 +550,17 @@ impl FieldDescriptor {
         }
     }
 
+    /// Fallible version of [`get_singular`](FieldDescriptor::get_singular).
+    pub fn try_get_singular<'a>(
+        &self,
+        m: &'a dyn MessageDyn,
+    ) -> Result<Option<ReflectValueRef<'a>>, ReflectError> {
+        match self.try_get_reflect(m)? {
+            ReflectFieldRef::Optional(o) => Ok(o),
+            _ => Err(ReflectError::wrong_field_kind(self, "singular")),
+        }
+    }
+
     // repeated
 
     /// Get repeated field.
@@ -371,6 +575,17 @@ impl FieldDescriptor {
         }
     }
 
+    /// Fallible version of [`get_repeated`](FieldDescriptor::get_repeated).
+    pub fn try_get_repeated<'a>(
+        &self,
+        m: &'a dyn MessageDyn,
+    ) -> Result<ReflectRepeatedRef<'a>, ReflectError> {
+        match self.try_get_reflect(m)? {
+            ReflectFieldRef::Repeated(r) => Ok(r),
+            _ => Err(ReflectError::wrong_field_kind(self, "repeated")),
+        }
+    }
+
     /// Get a mutable reference to `repeated` field.
     ///
     /// # Panics
@@ -383,6 +598,18 @@ impl FieldDescriptor {
         }
     }
 
+    /// Fallible version of [`mut_repeated`](FieldDescriptor::mut_repeated).
+    pub fn try_mut_repeated<'a>(
+        &self,
+        m: &'a mut dyn MessageDyn,
+    ) -> Result<ReflectRepeatedMut<'a>, ReflectError> {
+        self.check_message(m)?;
+        if !self.is_repeated() {
+            return Err(ReflectError::wrong_field_kind(self, "repeated"));
+        }
+        Ok(self.mut_repeated(m))
+    }
+
     // map
 
     /// Get `map` field.
@@ -397,6 +624,17 @@ impl FieldDescriptor {
         }
     }
 
+    /// Fallible version of [`get_map`](FieldDescriptor::get_map).
+    pub fn try_get_map<'a>(
+        &self,
+        m: &'a dyn MessageDyn,
+    ) -> Result<ReflectMapRef<'a>, ReflectError> {
+        match self.try_get_reflect(m)? {
+            ReflectFieldRef::Map(m) => Ok(m),
+            _ => Err(ReflectError::wrong_field_kind(self, "map")),
+        }
+    }
+
     /// Get a mutable reference to `map` field.
     ///
     /// # Panics
@@ -408,6 +646,18 @@ impl FieldDescriptor {
             MapFieldAccessorRef::Dynamic(d) => d.mut_map(m),
         }
     }
+
+    /// Fallible version of [`mut_map`](FieldDescriptor::mut_map).
+    pub fn try_mut_map<'a>(
+        &self,
+        m: &'a mut dyn MessageDyn,
+    ) -> Result<ReflectMapMut<'a>, ReflectError> {
+        self.check_message(m)?;
+        if !self.is_map() {
+            return Err(ReflectError::wrong_field_kind(self, "map"));
+        }
+        Ok(self.mut_map(m))
+    }
 }
 
 enum SingularFieldAccessorRef<'a> {
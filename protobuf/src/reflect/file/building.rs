@@ -8,6 +8,7 @@ use crate::descriptor::FileDescriptorProto;
 use crate::reflect::field::index::ForwardRuntimeFieldType;
 use crate::reflect::field::index::ForwardRuntimeTypeBox;
 use crate::reflect::file::index::FileIndex;
+use crate::reflect::file::pool::FileDescriptorPoolError;
 use crate::reflect::find_message_or_enum::find_message_or_enum;
 use crate::reflect::find_message_or_enum::MessageOrEnum;
 use crate::reflect::name::protobuf_name_starts_with_package;
@@ -63,108 +64,153 @@ impl<'a> FileDescriptorBuilding<'a> {
                 ForwardRuntimeFieldType::Singular(self.resolve_field_element_type(field))
             }
             field_descriptor_proto::Label::LABEL_REPEATED => {
-                let element = self.resolve_field_element_type(field);
-                let type_proto = match &element {
-                    ForwardRuntimeTypeBox::CurrentFileMessage(m) => Some(
-                        self.current_file_index.messages[*m]
-                            .path
-                            .eval(self.current_file_descriptor)
-                            .unwrap(),
-                    ),
-                    ForwardRuntimeTypeBox::RuntimeTypeBox(RuntimeTypeBox::Message(m)) => {
-                        Some(m.get_proto())
+                match self.with_map_entry(field, |m| self.map_field(m)) {
+                    Some(map) => map,
+                    None => {
+                        ForwardRuntimeFieldType::Repeated(self.resolve_field_element_type(field))
                     }
-                    _ => None,
-                };
-                match type_proto {
-                    Some(m) if m.options.get_or_default().get_map_entry() => self.map_field(m),
-                    _ => ForwardRuntimeFieldType::Repeated(element),
                 }
             }
         }
     }
 
+    /// Call the function with map entry message type if the field is a map field.
+    fn with_map_entry<R>(
+        &self,
+        field: &FieldDescriptorProto,
+        f: impl FnOnce(&DescriptorProto) -> R,
+    ) -> Option<R> {
+        if field.get_label() != field_descriptor_proto::Label::LABEL_REPEATED {
+            return None;
+        }
+        let dependency_message;
+        let type_proto = match self.resolve_field_element_type(field) {
+            ForwardRuntimeTypeBox::CurrentFileMessage(m) => self.current_file_index.messages[m]
+                .path
+                .eval(self.current_file_descriptor)
+                .unwrap(),
+            ForwardRuntimeTypeBox::RuntimeTypeBox(RuntimeTypeBox::Message(m)) => {
+                dependency_message = m;
+                dependency_message.get_proto()
+            }
+            _ => return None,
+        };
+        if type_proto.options.get_or_default().get_map_entry() {
+            Some(f(type_proto))
+        } else {
+            None
+        }
+    }
+
+    /// Protobuf types of key and value of map field, `None` if the field is not a map field.
+    ///
+    /// Entry type is validated by [`check`](Self::check) or [`resolve_field_type`](Self::resolve_field_type).
+    pub fn map_entry_types(
+        &self,
+        field: &FieldDescriptorProto,
+    ) -> Option<(field_descriptor_proto::Type, field_descriptor_proto::Type)> {
+        self.with_map_entry(field, |entry| {
+            (
+                entry.field[0].get_field_type(),
+                entry.field[1].get_field_type(),
+            )
+        })
+    }
+
     fn resolve_field_element_type(&self, field: &FieldDescriptorProto) -> ForwardRuntimeTypeBox {
+        match self.try_resolve_field_element_type(field) {
+            Some(t) => t,
+            None => panic!(
+                "{:?} not found: {}; files: {}",
+                field.get_field_type(),
+                field.get_type_name(),
+                self.all_files_str()
+            ),
+        }
+    }
+
+    /// Check that all types referenced by fields and extensions of the current file
+    /// can be resolved, and map entry types are valid.
+    ///
+    /// Building the file descriptor panics otherwise.
+    pub fn check(&self) -> Result<(), FileDescriptorPoolError> {
+        let file = self.current_file_descriptor;
+        let error = |field: &FieldDescriptorProto| FileDescriptorPoolError::UnresolvedType {
+            file: file.get_name().to_owned(),
+            type_name: field.get_type_name().to_owned(),
+        };
+        for field in &file.extension {
+            self.try_resolve_field_element_type(field)
+                .ok_or_else(|| error(field))?;
+        }
+        let mut messages: Vec<&DescriptorProto> = file.message_type.iter().collect();
+        while let Some(message) = messages.pop() {
+            for field in message.field.iter().chain(&message.extension) {
+                self.try_resolve_field_element_type(field)
+                    .ok_or_else(|| error(field))?;
+                if let Some(false) = self.with_map_entry(field, |m| map_entry_fields(m).is_some()) {
+                    return Err(FileDescriptorPoolError::InvalidMapEntry {
+                        file: file.get_name().to_owned(),
+                        type_name: field.get_type_name().to_owned(),
+                    });
+                }
+            }
+            messages.extend(&message.nested_type);
+        }
+        Ok(())
+    }
+
+    /// Resolve field type, `None` if the message or enum is not found.
+    fn try_resolve_field_element_type(
+        &self,
+        field: &FieldDescriptorProto,
+    ) -> Option<ForwardRuntimeTypeBox> {
+        let type_name = field.get_type_name();
+        let name_to_package = || {
+            protobuf_name_starts_with_package(type_name, self.current_file_descriptor.get_package())
+        };
         match field.get_field_type() {
+            field_descriptor_proto::Type::TYPE_MESSAGE
+            | field_descriptor_proto::Type::TYPE_GROUP
+            | field_descriptor_proto::Type::TYPE_ENUM
+                if !type_name.starts_with('.') =>
+            {
+                None
+            }
             field_descriptor_proto::Type::TYPE_MESSAGE
             | field_descriptor_proto::Type::TYPE_GROUP => {
-                if let Some(name_to_package) = protobuf_name_starts_with_package(
-                    field.get_type_name(),
-                    self.current_file_descriptor.get_package(),
-                ) {
-                    if let Some(index) = self
-                        .current_file_index
-                        .message_by_name_to_package
-                        .get(name_to_package)
-                    {
-                        return ForwardRuntimeTypeBox::CurrentFileMessage(*index);
-                    }
+                if let Some(index) = name_to_package()
+                    .and_then(|n| self.current_file_index.message_by_name_to_package.get(n))
+                {
+                    return Some(ForwardRuntimeTypeBox::CurrentFileMessage(*index));
                 }
-                for dep in self.deps_with_public {
-                    if let Some(m) = dep.message_by_full_name(field.get_type_name()) {
-                        return ForwardRuntimeTypeBox::RuntimeTypeBox(RuntimeTypeBox::Message(m));
-                    }
-                }
-                panic!(
-                    "message not found: {}; files: {}",
-                    field.get_type_name(),
-                    self.all_files_str()
-                );
+                self.deps_with_public
+                    .iter()
+                    .find_map(|dep| dep.message_by_full_name(type_name))
+                    .map(|m| ForwardRuntimeTypeBox::RuntimeTypeBox(RuntimeTypeBox::Message(m)))
             }
             field_descriptor_proto::Type::TYPE_ENUM => {
-                if let Some(name_to_package) = protobuf_name_starts_with_package(
-                    field.get_type_name(),
-                    self.current_file_descriptor.get_package(),
-                ) {
-                    if let Some(index) = self
-                        .current_file_index
-                        .enums_by_name_to_package
-                        .get(name_to_package)
-                    {
-                        return ForwardRuntimeTypeBox::CurrentFileEnum(*index);
-                    }
+                if let Some(index) = name_to_package()
+                    .and_then(|n| self.current_file_index.enums_by_name_to_package.get(n))
+                {
+                    return Some(ForwardRuntimeTypeBox::CurrentFileEnum(*index));
                 }
-                for dep in self.deps_with_public {
-                    if let Some(m) = dep.enum_by_full_name(field.get_type_name()) {
-                        return ForwardRuntimeTypeBox::RuntimeTypeBox(RuntimeTypeBox::Enum(m));
-                    }
-                }
-                panic!(
-                    "enum not found: {}; files: {}",
-                    field.get_type_name(),
-                    self.all_files_str()
-                );
+                self.deps_with_public
+                    .iter()
+                    .find_map(|dep| dep.enum_by_full_name(type_name))
+                    .map(|e| ForwardRuntimeTypeBox::RuntimeTypeBox(RuntimeTypeBox::Enum(e)))
             }
-            t => ForwardRuntimeTypeBox::RuntimeTypeBox(RuntimeTypeBox::from_proto_type(t)),
+            t => Some(ForwardRuntimeTypeBox::RuntimeTypeBox(
+                RuntimeTypeBox::from_proto_type(t),
+            )),
         }
     }
 
     fn map_field(&self, type_proto: &DescriptorProto) -> ForwardRuntimeFieldType {
-        assert!(type_proto.get_name().ends_with("Entry"));
-
-        assert_eq!(0, type_proto.extension.len());
-        assert_eq!(0, type_proto.extension_range.len());
-        assert_eq!(0, type_proto.nested_type.len());
-        assert_eq!(0, type_proto.enum_type.len());
-
-        assert_eq!(2, type_proto.field.len());
-        let key = &type_proto.field[0];
-        let value = &type_proto.field[1];
-
-        assert_eq!("key", key.get_name());
-        assert_eq!("value", value.get_name());
-
-        assert_eq!(1, key.get_number());
-        assert_eq!(2, value.get_number());
-
-        assert_eq!(
-            field_descriptor_proto::Label::LABEL_OPTIONAL,
-            key.get_label()
-        );
-        assert_eq!(
-            field_descriptor_proto::Label::LABEL_OPTIONAL,
-            value.get_label()
-        );
+        let (key, value) = match map_entry_fields(type_proto) {
+            Some(fields) => fields,
+            None => panic!("invalid map entry: {}", type_proto.get_name()),
+        };
 
         // It is OK to resolve using current descriptor because map field
         // should always point to the same file.
@@ -173,3 +219,30 @@ impl<'a> FileDescriptorBuilding<'a> {
         ForwardRuntimeFieldType::Map(key, value)
     }
 }
+
+/// Key and value fields of map entry message, `None` if the entry is malformed.
+fn map_entry_fields(
+    entry: &DescriptorProto,
+) -> Option<(&FieldDescriptorProto, &FieldDescriptorProto)> {
+    if !entry.get_name().ends_with("Entry")
+        || !entry.extension.is_empty()
+        || !entry.extension_range.is_empty()
+        || !entry.nested_type.is_empty()
+        || !entry.enum_type.is_empty()
+        || entry.field.len() != 2
+    {
+        return None;
+    }
+    let key = &entry.field[0];
+    let value = &entry.field[1];
+    let valid = |f: &FieldDescriptorProto, name, number| {
+        f.get_name() == name
+            && f.get_number() == number
+            && f.get_label() == field_descriptor_proto::Label::LABEL_OPTIONAL
+    };
+    if valid(key, "key", 1) && valid(value, "value", 2) {
+        Some((key, value))
+    } else {
+        None
+    }
+}
@@ -2,10 +2,13 @@ use std::sync::Arc;
 
 use crate::descriptor::DescriptorProto;
 use crate::descriptor::FileDescriptorProto;
+use crate::reflect::custom_options::CustomOptionsIndex;
+use crate::reflect::custom_options::ExtensionRegistry;
 use crate::reflect::enums::dynamic::DynamicEnumDescriptor;
 use crate::reflect::file::building::FileDescriptorBuilding;
 use crate::reflect::file::fds::fds_extend_with_public;
 use crate::reflect::file::index::FileIndex;
+use crate::reflect::file::pool::FileDescriptorPoolError;
 use crate::reflect::message::dynamic::DynamicMessageDescriptor;
 use crate::reflect::message::path::MessagePath;
 use crate::reflect::FileDescriptor;
@@ -17,40 +20,45 @@ pub(crate) struct DynamicFileDescriptor {
     pub messages: Vec<DynamicMessageDescriptor>,
     pub enums: Vec<DynamicEnumDescriptor>,
     pub index: FileIndex,
+    pub custom_options: CustomOptionsIndex,
 }
 
 impl DynamicFileDescriptor {
     pub fn new(
         proto: FileDescriptorProto,
         dependencies: Vec<FileDescriptor>,
-    ) -> DynamicFileDescriptor {
+        registry: &ExtensionRegistry,
+    ) -> Result<DynamicFileDescriptor, FileDescriptorPoolError> {
         let proto = Arc::new(proto);
 
         let index = FileIndex::index(&*proto);
 
+        let deps_with_public = fds_extend_with_public(dependencies.clone());
+        let building = FileDescriptorBuilding {
+            current_file_index: &index,
+            current_file_descriptor: &proto,
+            deps_with_public: &deps_with_public,
+        };
+        building.check()?;
+
         let messages = index
             .messages
             .iter()
             .map(|message_index_entry| {
-                DynamicMessageDescriptor::new(
-                    &*proto,
-                    &message_index_entry.path,
-                    &FileDescriptorBuilding {
-                        current_file_index: &index,
-                        current_file_descriptor: &proto,
-                        deps_with_public: &fds_extend_with_public(dependencies.clone()),
-                    },
-                )
+                DynamicMessageDescriptor::new(&proto, &message_index_entry.path, &building)
             })
             .collect();
 
-        DynamicFileDescriptor {
+        let custom_options = CustomOptionsIndex::index(&proto, &index, registry);
+
+        Ok(DynamicFileDescriptor {
             messages,
             enums: Self::enums(&proto),
             proto,
             dependencies,
             index,
-        }
+            custom_options,
+        })
     }
 
     fn enums(proto: &Arc<FileDescriptorProto>) -> Vec<DynamicEnumDescriptor> {
@@ -2,15 +2,17 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 
 use crate::descriptor::FileDescriptorProto;
+use crate::reflect::ExtensionRegistry;
 use crate::reflect::FileDescriptor;
 
-pub(crate) struct FdsBuilder {
+pub(crate) struct FdsBuilder<'a> {
+    registry: &'a ExtensionRegistry,
     names: Vec<String>,
     unprocessed: HashMap<String, FileDescriptorProto>,
     processed: HashMap<String, FileDescriptor>,
 }
 
-impl FdsBuilder {
+impl<'a> FdsBuilder<'a> {
     fn process_one(&mut self) {
         let (n, deps) = self
             .unprocessed
@@ -36,12 +38,18 @@ impl FdsBuilder {
             .unwrap();
         let n = n.clone();
         let proto = self.unprocessed.remove(&n).unwrap();
-        self.processed
-            .insert(n.clone(), FileDescriptor::new_dynamic(proto, deps));
+        self.processed.insert(
+            n.clone(),
+            FileDescriptor::new_dynamic_with_extensions(proto, deps, self.registry),
+        );
     }
 
-    pub fn build(protos: Vec<FileDescriptorProto>) -> Vec<FileDescriptor> {
+    pub fn build(
+        protos: Vec<FileDescriptorProto>,
+        registry: &ExtensionRegistry,
+    ) -> Vec<FileDescriptor> {
         let mut builder = FdsBuilder {
+            registry,
             names: protos.iter().map(|p| p.get_name().to_owned()).collect(),
             unprocessed: protos
                 .into_iter()
@@ -10,7 +10,7 @@ pub(crate) struct FileIndexMessageEntry {
     pub path: MessagePath,
     pub name_to_package: String,
     pub full_name: String,
-    pub _parent: Option<usize>,
+    pub parent: Option<usize>,
     pub nested_messages: Vec<usize>,
     pub _nested_enums: Vec<usize>,
     pub map_entry: bool,
@@ -20,6 +20,8 @@ pub(crate) struct FileIndexMessageEntry {
 #[derive(Debug)]
 pub(crate) struct FileIndexEnumEntry {
     pub _message_path: MessagePath,
+    /// Index of message containing this enum, `None` for top-level enums.
+    pub containing_message: Option<usize>,
     pub _enum_index: usize,
     pub name_to_package: String,
 }
@@ -47,6 +49,7 @@ impl FileIndex {
         for (_, e) in file.enum_type.iter().enumerate() {
             index.enums.push(FileIndexEnumEntry {
                 _message_path: MessagePath(Vec::new()),
+                containing_message: None,
                 _enum_index: index.enums.len(),
                 name_to_package: e.get_name().to_owned(),
             });
@@ -79,7 +82,7 @@ impl FileIndex {
             path: path.clone(),
             name_to_package: String::new(),
             full_name: String::new(),
-            _parent: parent,
+            parent,
             nested_messages: Vec::with_capacity(message.nested_type.len()),
             _nested_enums: Vec::with_capacity(message.enum_type.len()), // TODO
             map_entry: message.options.get_or_default().get_map_entry(),
@@ -89,6 +92,7 @@ impl FileIndex {
         for (_, e) in message.enum_type.iter().enumerate() {
             self.enums.push(FileIndexEnumEntry {
                 _message_path: path.clone(),
+                containing_message: Some(message_index),
                 _enum_index: self.enums.len(),
                 name_to_package: concat_paths(&name_to_package, e.get_name()),
             });
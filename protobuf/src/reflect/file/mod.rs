@@ -9,9 +9,14 @@ use crate::descriptor::FileDescriptorProto;
 use crate::reflect::file::dynamic::DynamicFileDescriptor;
 use crate::reflect::file::fds::FdsBuilder;
 use crate::reflect::file::index::FileIndex;
+use crate::reflect::file::index::FileIndexEnumEntry;
 use crate::reflect::file::index::FileIndexMessageEntry;
+use crate::reflect::file::pool::FileDescriptorPoolError;
 use crate::reflect::name::protobuf_name_starts_with_package;
+use crate::reflect::CustomOption;
 use crate::reflect::EnumDescriptor;
+use crate::reflect::ExtensionDescriptor;
+use crate::reflect::ExtensionRegistry;
 use crate::reflect::GeneratedFileDescriptor;
 use crate::reflect::MessageDescriptor;
 
@@ -20,6 +25,8 @@ pub(crate) mod dynamic;
 pub(crate) mod fds;
 pub(crate) mod generated;
 pub(crate) mod index;
+pub(crate) mod nesting;
+pub(crate) mod pool;
 
 #[derive(Clone, Debug)]
 pub(crate) enum FileDescriptorImpl {
@@ -78,6 +85,10 @@ impl FileDescriptor {
         &self.index().messages[index]
     }
 
+    pub(crate) fn enum_index_entry(&self, index: usize) -> &FileIndexEnumEntry {
+        &self.index().enums[index]
+    }
+
     pub(crate) fn message_proto(&self, index: usize) -> &DescriptorProto {
         self.message_index_entry(index)
             .path
@@ -104,6 +115,28 @@ impl FileDescriptor {
             .collect()
     }
 
+    /// Get all messages defined in this file, including nested messages.
+    pub fn all_messages<'a>(&'a self) -> impl ExactSizeIterator<Item = MessageDescriptor> + 'a {
+        (0..self.index().messages.len()).map(move |i| MessageDescriptor::new(self.clone(), i))
+    }
+
+    /// Get all enums defined in this file, including enums nested in messages.
+    pub fn all_enums<'a>(&'a self) -> impl ExactSizeIterator<Item = EnumDescriptor> + 'a {
+        (0..self.index().enums.len()).map(move |i| EnumDescriptor::new(self.clone(), i))
+    }
+
+    /// Get all extensions declared in this file,
+    /// including extensions declared inside messages.
+    pub fn extensions(&self) -> Vec<ExtensionDescriptor> {
+        let top_level = (0..self.proto().extension.len())
+            .map(|i| ExtensionDescriptor::new(self.clone(), None, i));
+        let nested = self.all_messages().flat_map(|m| {
+            (0..m.get_proto().extension.len())
+                .map(move |i| ExtensionDescriptor::new(m.file().clone(), Some(m.clone()), i))
+        });
+        top_level.chain(nested).collect()
+    }
+
     /// Find message by name relative to the package.
     ///
     /// Only search in the current file, not in any dependencies.
@@ -164,28 +197,87 @@ impl FileDescriptor {
         proto: FileDescriptorProto,
         dependencies: Vec<FileDescriptor>,
     ) -> FileDescriptor {
+        FileDescriptor::new_dynamic_with_extensions(proto, dependencies, &ExtensionRegistry::new())
+    }
+
+    /// Dynamic message created from [`FileDescriptorProto`] without generated files.
+    ///
+    /// Custom options of the file, messages and fields are decoded
+    /// using extensions from the registry, and are available with
+    /// `custom_options` functions of descriptors.
+    ///
+    /// # Panics
+    ///
+    /// If the file references missing dependencies or types,
+    /// see [`try_new_dynamic_with_extensions`](FileDescriptor::try_new_dynamic_with_extensions).
+    pub fn new_dynamic_with_extensions(
+        proto: FileDescriptorProto,
+        dependencies: Vec<FileDescriptor>,
+        registry: &ExtensionRegistry,
+    ) -> FileDescriptor {
+        match FileDescriptor::try_new_dynamic_with_extensions(proto, dependencies, registry) {
+            Ok(file) => file,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Same as [`new_dynamic_with_extensions`](FileDescriptor::new_dynamic_with_extensions),
+    /// but returns an error if a dependency of the file is not among `dependencies`,
+    /// a field type cannot be resolved, or a map entry type is malformed.
+    pub fn try_new_dynamic_with_extensions(
+        proto: FileDescriptorProto,
+        dependencies: Vec<FileDescriptor>,
+        registry: &ExtensionRegistry,
+    ) -> Result<FileDescriptor, FileDescriptorPoolError> {
         // remove undeclared dependencies
         let dependencies: HashMap<_, _> = dependencies
             .iter()
             .map(|d| (d.proto().get_name(), d))
             .collect();
-        let dependencies: Vec<_> = proto
+        let dependencies = proto
             .dependency
             .iter()
-            .map(|d| dependencies[d.as_str()].clone())
-            .collect();
+            .map(|d| match dependencies.get(d.as_str()) {
+                Some(&d) => Ok(d.clone()),
+                None => Err(FileDescriptorPoolError::MissingDependency {
+                    file: proto.get_name().to_owned(),
+                    dependency: d.clone(),
+                }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
-        FileDescriptor {
+        Ok(FileDescriptor {
             imp: FileDescriptorImpl::Dynamic(Arc::new(DynamicFileDescriptor::new(
                 proto,
                 dependencies,
-            ))),
-        }
+                registry,
+            )?)),
+        })
     }
 
     /// Create a set of file descriptors from individual file descriptors.
     pub fn new_dynamic_fds(protos: Vec<FileDescriptorProto>) -> Vec<FileDescriptor> {
-        FdsBuilder::build(protos)
+        FdsBuilder::build(protos, &ExtensionRegistry::new())
+    }
+
+    /// Create a set of file descriptors from individual file descriptors
+    /// decoding custom options using extensions from the registry.
+    pub fn new_dynamic_fds_with_extensions(
+        protos: Vec<FileDescriptorProto>,
+        registry: &ExtensionRegistry,
+    ) -> Vec<FileDescriptor> {
+        FdsBuilder::build(protos, registry)
+    }
+
+    /// Custom options of the file decoded when this file descriptor was created
+    /// with [`new_dynamic_with_extensions`](FileDescriptor::new_dynamic_with_extensions).
+    ///
+    /// Empty for generated files.
+    pub fn custom_options(&self) -> &[CustomOption] {
+        match &self.imp {
+            FileDescriptorImpl::Generated(_) => &[],
+            FileDescriptorImpl::Dynamic(d) => &d.custom_options.file,
+        }
     }
 
     /// `.proto` data for this file.
@@ -196,7 +288,9 @@ impl FileDescriptor {
         }
     }
 
-    fn deps(&self) -> &[FileDescriptor] {
+    /// Direct dependencies of this file in the order of `import` statements,
+    /// i. e. in the order of [`FileDescriptorProto::dependency`].
+    pub fn dependencies(&self) -> &[FileDescriptor] {
         match &self.imp {
             FileDescriptorImpl::Generated(g) => &g.dependencies,
             FileDescriptorImpl::Dynamic(d) => &d.dependencies,
@@ -204,15 +298,23 @@ impl FileDescriptor {
     }
 
     /// Subset of dependencies which are public
-    pub fn public_deps(&self) -> Vec<FileDescriptor> {
+    pub fn public_dependencies(&self) -> Vec<FileDescriptor> {
         self.proto()
             .public_dependency
             .iter()
-            .map(|&i| self.deps()[i as usize].clone())
+            .map(|&i| self.dependencies()[i as usize].clone())
             .collect()
     }
 
-    fn _all_files(&self) -> Vec<&FileDescriptor> {
+    /// Subset of dependencies which are public.
+    ///
+    /// Same as [`public_dependencies`](FileDescriptor::public_dependencies).
+    pub fn public_deps(&self) -> Vec<FileDescriptor> {
+        self.public_dependencies()
+    }
+
+    /// This file and all its transitive dependencies.
+    pub(crate) fn all_files(&self) -> Vec<&FileDescriptor> {
         let mut r = Vec::new();
         let mut visited = HashSet::new();
 
@@ -224,7 +326,7 @@ impl FileDescriptor {
             }
 
             r.push(file);
-            stack.extend(file.deps());
+            stack.extend(file.dependencies());
         }
 
         r
@@ -234,9 +336,59 @@ impl FileDescriptor {
 #[cfg(test)]
 mod test {
     use crate::descriptor;
+    use crate::descriptor::field_descriptor_proto;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FieldDescriptorProto;
+    use crate::Message;
+    use crate::ProtobufEnum;
 
     #[test]
     fn eq() {
         assert!(descriptor::file_descriptor() == descriptor::file_descriptor().clone());
     }
+
+    #[test]
+    fn all_messages_and_enums() {
+        let file = descriptor::file_descriptor();
+        let messages: Vec<_> = file.all_messages().collect();
+        assert!(messages.len() > file.messages().len());
+        assert!(
+            messages.contains(&descriptor::descriptor_proto::ExtensionRange::descriptor_static())
+        );
+        let enums: Vec<_> = file.all_enums().collect();
+        assert!(enums.len() > file.enums().len());
+        assert!(enums.contains(&field_descriptor_proto::Label::enum_descriptor_static()));
+        assert!(file.extensions().is_empty());
+    }
+
+    #[test]
+    fn dependencies() {
+        let plugin = crate::plugin::file_descriptor();
+        assert_eq!(&[descriptor::file_descriptor()], plugin.dependencies());
+        assert!(plugin.public_dependencies().is_empty());
+        assert!(descriptor::file_descriptor().dependencies().is_empty());
+    }
+
+    #[test]
+    fn containing_message() {
+        let file = descriptor::file_descriptor();
+
+        let message = FieldDescriptorProto::descriptor_static();
+        assert_eq!(&file, message.file());
+        assert_eq!(None, message.containing_message());
+
+        let field = message.get_field_by_name("label").unwrap();
+        assert_eq!(&message, field.containing_message());
+        assert_eq!(&file, field.file());
+
+        let label = field_descriptor_proto::Label::enum_descriptor_static();
+        assert_eq!(&file, label.file());
+        assert_eq!(Some(message), label.containing_message());
+
+        let nested = descriptor::descriptor_proto::ExtensionRange::descriptor_static();
+        assert_eq!(
+            Some(DescriptorProto::descriptor_static()),
+            nested.containing_message()
+        );
+    }
 }
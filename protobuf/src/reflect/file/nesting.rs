@@ -0,0 +1,203 @@
+use std::cmp;
+use std::collections::HashMap;
+use std::vec;
+
+use crate::descriptor::field_descriptor_proto::Label;
+use crate::reflect::file::pool::FileDescriptorPoolError;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+
+/// Message type of singular, repeated or map value field.
+fn field_message_type(field: &FieldDescriptor) -> Option<MessageDescriptor> {
+    match field.runtime_field_type() {
+        RuntimeFieldType::Singular(RuntimeTypeBox::Message(m))
+        | RuntimeFieldType::Repeated(RuntimeTypeBox::Message(m))
+        | RuntimeFieldType::Map(_, RuntimeTypeBox::Message(m)) => Some(m),
+        _ => None,
+    }
+}
+
+/// Message types of all message fields.
+fn all_fields(message: &MessageDescriptor) -> Vec<MessageDescriptor> {
+    message
+        .fields()
+        .filter_map(|f| field_message_type(&f))
+        .collect()
+}
+
+/// Message types of required message fields.
+fn required_fields(message: &MessageDescriptor) -> Vec<MessageDescriptor> {
+    message
+        .fields()
+        .filter(|f| f.get_proto().get_label() == Label::LABEL_REQUIRED)
+        .filter_map(|f| field_message_type(&f))
+        .collect()
+}
+
+/// Strongly connected components of message types graph (Tarjan's algorithm).
+///
+/// Components are numbered in reverse topological order:
+/// components reachable from a component have smaller numbers.
+struct Components {
+    edges: fn(&MessageDescriptor) -> Vec<MessageDescriptor>,
+    /// Visit order and lowest reachable visit order by message full name.
+    visited: HashMap<String, (usize, usize)>,
+    stack: Vec<MessageDescriptor>,
+    /// Component number by message full name.
+    component: HashMap<String, usize>,
+    components: Vec<Vec<MessageDescriptor>>,
+}
+
+/// Message being visited by [`Components::visit`] with its remaining edges.
+struct Frame {
+    message: MessageDescriptor,
+    index: usize,
+    lowlink: usize,
+    edges: vec::IntoIter<MessageDescriptor>,
+}
+
+impl Components {
+    fn new(edges: fn(&MessageDescriptor) -> Vec<MessageDescriptor>) -> Components {
+        Components {
+            edges,
+            visited: HashMap::new(),
+            stack: Vec::new(),
+            component: HashMap::new(),
+            components: Vec::new(),
+        }
+    }
+
+    /// Visit message and all messages reachable from it.
+    ///
+    /// Implemented without recursion, so deeply nested schemas
+    /// do not overflow the stack.
+    fn visit(&mut self, message: &MessageDescriptor) {
+        if self.visited.contains_key(message.full_name()) {
+            return;
+        }
+        let mut frames = vec![self.enter(message.clone())];
+        while let Some(frame) = frames.last_mut() {
+            match frame.edges.next() {
+                Some(next) => {
+                    if !self.visited.contains_key(next.full_name()) {
+                        let frame = self.enter(next);
+                        frames.push(frame);
+                    } else if !self.component.contains_key(next.full_name()) {
+                        // On stack: in the same component
+                        frame.lowlink = cmp::min(frame.lowlink, self.visited[next.full_name()].1);
+                    }
+                }
+                None => {
+                    let frame = frames.pop().unwrap();
+                    self.leave(&frame);
+                    if let Some(parent) = frames.last_mut() {
+                        if !self.component.contains_key(frame.message.full_name()) {
+                            parent.lowlink = cmp::min(parent.lowlink, frame.lowlink);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Start visiting the message.
+    fn enter(&mut self, message: MessageDescriptor) -> Frame {
+        let index = self.visited.len();
+        self.visited
+            .insert(message.full_name().to_owned(), (index, index));
+        self.stack.push(message.clone());
+        Frame {
+            edges: (self.edges)(&message).into_iter(),
+            message,
+            index,
+            lowlink: index,
+        }
+    }
+
+    /// All edges of the message are visited: pop the component if the message is its root.
+    fn leave(&mut self, frame: &Frame) {
+        let message = &frame.message;
+        self.visited
+            .insert(message.full_name().to_owned(), (frame.index, frame.lowlink));
+        if frame.lowlink == frame.index {
+            let number = self.components.len();
+            let mut component = Vec::new();
+            loop {
+                let m = self.stack.pop().unwrap();
+                self.component.insert(m.full_name().to_owned(), number);
+                let done = m == *message;
+                component.push(m);
+                if done {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+
+    /// Component is a cycle: has more than one message or a self-reference.
+    fn is_cycle(&self, component: &[MessageDescriptor]) -> bool {
+        component.len() > 1 || (self.edges)(&component[0]).contains(&component[0])
+    }
+}
+
+/// Check that messages (and message types reachable from them)
+/// do not contain themselves through required fields,
+/// and that message nesting depth does not exceed the limit.
+///
+/// Nesting depth is the number of message levels, mutually recursive
+/// message types count as a single level.
+pub(crate) fn check_nesting(
+    messages: &[MessageDescriptor],
+    max_depth: Option<usize>,
+) -> Result<(), FileDescriptorPoolError> {
+    // Map fields are followed directly to map value types
+    let messages: Vec<&MessageDescriptor> = messages.iter().filter(|m| !m.is_map_entry()).collect();
+
+    let mut required = Components::new(required_fields);
+    for &message in &messages {
+        required.visit(message);
+    }
+    for component in &required.components {
+        if required.is_cycle(component) {
+            let message = component.iter().map(|m| m.full_name()).min().unwrap();
+            return Err(FileDescriptorPoolError::RequiredFieldCycle(
+                message.to_owned(),
+            ));
+        }
+    }
+
+    let max_depth = match max_depth {
+        Some(max_depth) => max_depth,
+        None => return Ok(()),
+    };
+
+    let mut all = Components::new(all_fields);
+    for &message in &messages {
+        all.visit(message);
+    }
+    let mut depths: Vec<usize> = Vec::with_capacity(all.components.len());
+    for (number, component) in all.components.iter().enumerate() {
+        let nested = component
+            .iter()
+            .flat_map(all_fields)
+            .map(|m| all.component[m.full_name()])
+            .filter(|&c| c != number)
+            .map(|c| depths[c])
+            .max()
+            .unwrap_or(0);
+        depths.push(nested + 1);
+    }
+    for message in &messages {
+        let depth = depths[all.component[message.full_name()]];
+        if depth > max_depth {
+            return Err(FileDescriptorPoolError::NestingTooDeep {
+                message: message.full_name().to_owned(),
+                depth,
+            });
+        }
+    }
+    Ok(())
+}
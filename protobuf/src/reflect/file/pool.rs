@@ -0,0 +1,1212 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::mem;
+use std::sync::Arc;
+use std::sync::Weak;
+
+use crate::descriptor::DescriptorProto;
+use crate::descriptor::FileDescriptorProto;
+use crate::descriptor::FileDescriptorSet;
+use crate::reflect::custom_options::decode_extension;
+use crate::reflect::file::dynamic::DynamicFileDescriptor;
+use crate::reflect::file::nesting::check_nesting;
+use crate::reflect::file::FileDescriptorImpl;
+use crate::reflect::name::concat_paths;
+use crate::reflect::EnumDescriptor;
+use crate::reflect::ExtensionDescriptor;
+use crate::reflect::ExtensionRegistry;
+use crate::reflect::FileDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::ReflectValueBox;
+use crate::well_known_types_util::well_known_type_descriptor;
+use crate::well_known_types_util::well_known_type_enum;
+use crate::well_known_types_util::well_known_type_file;
+use crate::Message;
+
+/// Error returned when files cannot be added to [`FileDescriptorPool`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileDescriptorPoolError {
+    /// File with the same name but different content is already in the pool.
+    DuplicateFile(String),
+    /// Message, enum or service is already defined in another file.
+    DuplicateSymbol {
+        /// Name of the file being added.
+        file: String,
+        /// Full name of the symbol.
+        symbol: String,
+    },
+    /// Dependency is neither in the pool nor among the files being added.
+    MissingDependency {
+        /// Name of the file being added.
+        file: String,
+        /// Name of the missing dependency.
+        dependency: String,
+    },
+    /// Files being added depend on each other cyclically.
+    DependencyCycle(String),
+    /// Serialized `FileDescriptorSet` cannot be parsed.
+    InvalidFileDescriptorSet(String),
+    /// Message contains itself through required fields,
+    /// so no finite message is initialized.
+    RequiredFieldCycle(String),
+    /// Message nesting depth exceeds the limit set with
+    /// [`set_max_nesting_depth`](FileDescriptorPool::set_max_nesting_depth).
+    NestingTooDeep {
+        /// Full name of the message.
+        message: String,
+        /// Nesting depth of the message.
+        depth: usize,
+    },
+    /// Field type is not defined in the file or its dependencies.
+    UnresolvedType {
+        /// Name of the file being added.
+        file: String,
+        /// Type name of the field.
+        type_name: String,
+    },
+    /// Map field entry type is not a valid map entry message.
+    InvalidMapEntry {
+        /// Name of the file being added.
+        file: String,
+        /// Type name of the map entry.
+        type_name: String,
+    },
+}
+
+impl fmt::Display for FileDescriptorPoolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FileDescriptorPoolError::DuplicateFile(file) => {
+                write!(
+                    f,
+                    "file {} is already in the pool with different content",
+                    file
+                )
+            }
+            FileDescriptorPoolError::DuplicateSymbol { file, symbol } => write!(
+                f,
+                "symbol {} from file {} is already defined in the pool",
+                symbol, file
+            ),
+            FileDescriptorPoolError::MissingDependency { file, dependency } => {
+                write!(f, "dependency {} of file {} not found", dependency, file)
+            }
+            FileDescriptorPoolError::DependencyCycle(file) => {
+                write!(f, "cyclic dependency involving file {}", file)
+            }
+            FileDescriptorPoolError::InvalidFileDescriptorSet(e) => {
+                write!(f, "cannot parse file descriptor set: {}", e)
+            }
+            FileDescriptorPoolError::RequiredFieldCycle(message) => {
+                write!(
+                    f,
+                    "message {} contains itself through required fields",
+                    message
+                )
+            }
+            FileDescriptorPoolError::NestingTooDeep { message, depth } => write!(
+                f,
+                "message {} nesting depth {} exceeds the limit",
+                message, depth
+            ),
+            FileDescriptorPoolError::UnresolvedType { file, type_name } => {
+                write!(f, "type {} not found in file {}", type_name, file)
+            }
+            FileDescriptorPoolError::InvalidMapEntry { file, type_name } => {
+                write!(f, "invalid map entry {} in file {}", type_name, file)
+            }
+        }
+    }
+}
+
+impl Error for FileDescriptorPoolError {}
+
+/// Set of file descriptors which can be extended with new files.
+///
+/// Files are added together with their dependencies, so every file
+/// in the pool depends only on files from the same pool.
+/// Files already in the pool are never rebuilt when new files are added,
+/// so descriptors obtained from the pool remain valid.
+///
+/// Files are never removed from the pool unless
+/// [`collect_garbage`](FileDescriptorPool::collect_garbage) is called,
+/// which removes dynamic files no longer used outside of the pool,
+/// so long-lived pools receiving many transient schemas do not grow indefinitely.
+#[derive(Clone, Debug, Default)]
+pub struct FileDescriptorPool {
+    files: Vec<FileDescriptor>,
+    /// File name to index in `files`.
+    by_name: HashMap<String, usize>,
+    /// Full names of messages, enums and services (without leading dot)
+    /// to index of defining file in `files`.
+    symbols: HashMap<String, usize>,
+    /// Extendee full name (without leading dot) to extensions by number.
+    extensions: HashMap<String, HashMap<u32, ExtensionDescriptor>>,
+    registry: ExtensionRegistry,
+    max_nesting_depth: Option<usize>,
+    /// Names of files never removed by `collect_garbage`.
+    pinned: HashSet<String>,
+}
+
+/// Full names of messages, enums and services defined in the file.
+fn file_symbols(proto: &FileDescriptorProto) -> Vec<String> {
+    fn add_message(scope: &str, message: &DescriptorProto, r: &mut Vec<String>) {
+        let name = concat_paths(scope, message.get_name());
+        for nested in &message.nested_type {
+            add_message(&name, nested, r);
+        }
+        for e in &message.enum_type {
+            r.push(concat_paths(&name, e.get_name()));
+        }
+        r.push(name);
+    }
+
+    let mut r = Vec::new();
+    let package = proto.get_package();
+    for message in &proto.message_type {
+        add_message(package, message, &mut r);
+    }
+    for e in &proto.enum_type {
+        r.push(concat_paths(package, e.get_name()));
+    }
+    for service in &proto.service {
+        r.push(concat_paths(package, service.get_name()));
+    }
+    r
+}
+
+impl FileDescriptorPool {
+    /// Empty pool.
+    pub fn new() -> FileDescriptorPool {
+        FileDescriptorPool::default()
+    }
+
+    /// Empty pool which decodes custom options of added files
+    /// using extensions from the registry.
+    pub fn new_with_extensions(registry: ExtensionRegistry) -> FileDescriptorPool {
+        FileDescriptorPool {
+            registry,
+            ..FileDescriptorPool::default()
+        }
+    }
+
+    /// Reject files with messages nested deeper than given depth.
+    ///
+    /// Depth is the number of message levels: message without message fields
+    /// has depth 1, mutually recursive messages count as a single level.
+    /// The limit applies to files added after this call.
+    ///
+    /// Files with messages containing themselves through required fields
+    /// are always rejected.
+    pub fn set_max_nesting_depth(&mut self, max_depth: usize) {
+        self.max_nesting_depth = Some(max_depth);
+    }
+
+    /// All files in the pool, in the order they were added.
+    ///
+    /// Each file is preceded by its dependencies.
+    pub fn files(&self) -> &[FileDescriptor] {
+        &self.files
+    }
+
+    /// Find a file by name, e. g. `google/protobuf/descriptor.proto`.
+    pub fn file_by_name(&self, name: &str) -> Option<&FileDescriptor> {
+        self.by_name.get(name).map(|&i| &self.files[i])
+    }
+
+    /// All messages in all files of the pool, including nested messages.
+    pub fn messages<'a>(&'a self) -> impl Iterator<Item = MessageDescriptor> + 'a {
+        self.files.iter().flat_map(|f| f.all_messages())
+    }
+
+    /// Find a message by fully-qualified name, e. g. `google.protobuf.DescriptorProto`.
+    ///
+    /// Name may also start with a dot, like type names in `FieldDescriptorProto`.
+    ///
+    /// Well known types like `google.protobuf.Timestamp` are found
+    /// even if they are not in the pool.
+    pub fn message_by_full_name(&self, name: &str) -> Option<MessageDescriptor> {
+        match self.file_by_symbol(name) {
+            Some((file, name)) => file.message_by_full_name(&name),
+            None => well_known_type_descriptor(name.trim_start_matches('.')),
+        }
+    }
+
+    /// Find an enum by fully-qualified name, e. g. `google.protobuf.FieldDescriptorProto.Type`.
+    ///
+    /// Name may also start with a dot, like type names in `FieldDescriptorProto`.
+    ///
+    /// Well known enums like `google.protobuf.NullValue` are found
+    /// even if they are not in the pool.
+    pub fn enum_by_full_name(&self, name: &str) -> Option<EnumDescriptor> {
+        match self.file_by_symbol(name) {
+            Some((file, name)) => file.enum_by_full_name(&name),
+            None => well_known_type_enum(name.trim_start_matches('.')),
+        }
+    }
+
+    /// Find an extension of given message by field number.
+    ///
+    /// `extendee` is a fully-qualified name of the extended message,
+    /// e. g. `google.protobuf.FieldOptions`, with or without leading dot.
+    /// If several files in the pool extend the message with the same number,
+    /// extension from the file added first is returned.
+    pub fn extension_by_number(&self, extendee: &str, number: u32) -> Option<&ExtensionDescriptor> {
+        self.extensions
+            .get(extendee.trim_start_matches('.'))?
+            .get(&number)
+    }
+
+    /// Messages which have given custom message option set, with option values.
+    ///
+    /// `option` is a fully-qualified name of an extension of `google.protobuf.MessageOptions`,
+    /// e. g. `my.pkg.topic`, with or without leading dot; the extension must be declared
+    /// in one of the files of the pool. For options set multiple times the last value
+    /// is returned. If there is no such extension in the pool, or the extension type
+    /// cannot be resolved, result is empty.
+    ///
+    /// Options are decoded from `MessageOptions` unknown fields, so this works for
+    /// both generated and dynamic files regardless of [`ExtensionRegistry`].
+    pub fn messages_with_option(&self, option: &str) -> Vec<(MessageDescriptor, ReflectValueBox)> {
+        let option = option.trim_start_matches('.');
+        let extension = match self
+            .extensions
+            .get("google.protobuf.MessageOptions")
+            .and_then(|e| e.values().find(|e| e.full_name() == option))
+        {
+            Some(extension) => extension,
+            None => return Vec::new(),
+        };
+        self.messages()
+            .filter_map(|m| {
+                let options = m.get_proto().options.get_or_default();
+                let value = decode_extension(extension, options.get_unknown_fields()).pop()?;
+                Some((m, value))
+            })
+            .collect()
+    }
+
+    /// File defining the symbol and the symbol name with leading dot.
+    fn file_by_symbol(&self, name: &str) -> Option<(&FileDescriptor, String)> {
+        let name = name.trim_start_matches('.');
+        let file = &self.files[*self.symbols.get(name)?];
+        Some((file, format!(".{}", name)))
+    }
+
+    /// Add single file. All dependencies of the file must already be in the pool.
+    ///
+    /// If the same file is already in the pool, existing descriptor is returned.
+    pub fn add_file(
+        &mut self,
+        proto: FileDescriptorProto,
+    ) -> Result<FileDescriptor, FileDescriptorPoolError> {
+        Ok(self.add_files(vec![proto])?.pop().unwrap())
+    }
+
+    /// Add files which may depend on each other and on files already in the pool.
+    ///
+    /// Returned descriptors are in the same order as files passed to this function.
+    /// Files which are already in the pool are not added again,
+    /// and existing descriptors are returned for them.
+    ///
+    /// Files are validated before any file is added, so on error the pool is unchanged.
+    ///
+    /// Well known types files like `google/protobuf/timestamp.proto`
+    /// may be omitted: if such dependency is missing, generated file is added to the pool.
+    pub fn add_files(
+        &mut self,
+        protos: Vec<FileDescriptorProto>,
+    ) -> Result<Vec<FileDescriptor>, FileDescriptorPoolError> {
+        let names: Vec<String> = protos.iter().map(|p| p.get_name().to_owned()).collect();
+
+        let mut new: HashMap<String, FileDescriptorProto> = HashMap::new();
+        for proto in protos {
+            let name = proto.get_name().to_owned();
+            let existing = match self.file_by_name(&name) {
+                Some(file) => Some(file.proto()),
+                None => new.get(&name),
+            };
+            match existing {
+                Some(existing) if existing == &proto => {}
+                Some(_) => return Err(FileDescriptorPoolError::DuplicateFile(name)),
+                None => {
+                    new.insert(name, proto);
+                }
+            }
+        }
+
+        // Missing well known types files with their dependencies
+        let mut well_known: Vec<FileDescriptor> = Vec::new();
+        for proto in new.values() {
+            for dependency in &proto.dependency {
+                if self.by_name.contains_key(dependency) || new.contains_key(dependency) {
+                    continue;
+                }
+                if let Some(file) = well_known_type_file(dependency) {
+                    for f in file.all_files().into_iter().rev() {
+                        if !self.by_name.contains_key(f.proto().get_name())
+                            && !well_known.contains(f)
+                        {
+                            well_known.push(f.clone());
+                        }
+                    }
+                }
+            }
+        }
+        let well_known_names: HashSet<String> = well_known
+            .iter()
+            .map(|f| f.proto().get_name().to_owned())
+            .collect();
+
+        let mut new_symbols = HashSet::new();
+        let well_known_protos = well_known.iter().map(|f| (f.proto().get_name(), f.proto()));
+        let new_protos = new.iter().map(|(n, p)| (n.as_str(), p));
+        for (name, proto) in well_known_protos.chain(new_protos) {
+            for symbol in file_symbols(proto) {
+                if self.symbols.contains_key(&symbol) || !new_symbols.insert(symbol.clone()) {
+                    return Err(FileDescriptorPoolError::DuplicateSymbol {
+                        file: name.to_owned(),
+                        symbol,
+                    });
+                }
+            }
+            for dependency in &proto.dependency {
+                if !self.by_name.contains_key(dependency)
+                    && !new.contains_key(dependency)
+                    && !well_known_names.contains(dependency)
+                {
+                    return Err(FileDescriptorPoolError::MissingDependency {
+                        file: name.to_owned(),
+                        dependency: dependency.clone(),
+                    });
+                }
+            }
+        }
+
+        // Order new files so dependencies come first
+        let mut ordered: Vec<FileDescriptorProto> = Vec::new();
+        let mut ordered_names: HashSet<String> = HashSet::new();
+        while !new.is_empty() {
+            let next = new
+                .iter()
+                .filter(|(_, p)| {
+                    p.dependency.iter().all(|d| {
+                        self.by_name.contains_key(d)
+                            || well_known_names.contains(d)
+                            || ordered_names.contains(d)
+                    })
+                })
+                .map(|(n, _)| n.clone())
+                .min();
+            let next = match next {
+                Some(next) => next,
+                None => {
+                    let file = new.keys().min().unwrap().clone();
+                    return Err(FileDescriptorPoolError::DependencyCycle(file));
+                }
+            };
+            ordered_names.insert(next.clone());
+            ordered.push(new.remove(&next).unwrap());
+        }
+
+        let mut built: Vec<FileDescriptor> = Vec::new();
+        for proto in ordered {
+            let dependencies = proto
+                .dependency
+                .iter()
+                .map(|d| match self.file_by_name(d) {
+                    Some(file) => file.clone(),
+                    None => well_known
+                        .iter()
+                        .chain(&built)
+                        .find(|f| f.proto().get_name() == d)
+                        .unwrap()
+                        .clone(),
+                })
+                .collect();
+            built.push(FileDescriptor::try_new_dynamic_with_extensions(
+                proto,
+                dependencies,
+                &self.registry,
+            )?);
+        }
+        let messages: Vec<MessageDescriptor> =
+            built.iter().flat_map(|f| f.all_messages()).collect();
+        check_nesting(&messages, self.max_nesting_depth)?;
+
+        for file in well_known {
+            self.insert(file);
+        }
+        for file in built {
+            self.insert(file);
+        }
+
+        Ok(names
+            .iter()
+            .map(|n| self.file_by_name(n).unwrap().clone())
+            .collect())
+    }
+
+    /// Add all files from a `FileDescriptorSet`, e. g. one produced by
+    /// `protoc --include_imports --descriptor_set_out` or received from a schema registry.
+    ///
+    /// Files may be in any order. Same as [`add_files`](FileDescriptorPool::add_files).
+    pub fn add_file_descriptor_set(
+        &mut self,
+        file_descriptor_set: FileDescriptorSet,
+    ) -> Result<Vec<FileDescriptor>, FileDescriptorPoolError> {
+        self.add_files(file_descriptor_set.file)
+    }
+
+    /// Parse serialized `FileDescriptorSet` and add all its files to the pool.
+    pub fn add_file_descriptor_set_bytes(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<Vec<FileDescriptor>, FileDescriptorPoolError> {
+        let file_descriptor_set = FileDescriptorSet::parse_from_bytes(bytes)
+            .map_err(|e| FileDescriptorPoolError::InvalidFileDescriptorSet(e.to_string()))?;
+        self.add_file_descriptor_set(file_descriptor_set)
+    }
+
+    /// Add existing file descriptor (e. g. generated) with all its dependencies.
+    ///
+    /// Files with the same name and content already in the pool are kept,
+    /// so the pool may contain a different descriptor object for the same file.
+    pub fn add_file_descriptor(
+        &mut self,
+        file: &FileDescriptor,
+    ) -> Result<(), FileDescriptorPoolError> {
+        // Dependencies first
+        let mut files: Vec<&FileDescriptor> = file.all_files();
+        files.reverse();
+
+        for f in &files {
+            let name = f.proto().get_name();
+            match self.file_by_name(name) {
+                Some(existing) if existing.proto() == f.proto() => {}
+                Some(_) => return Err(FileDescriptorPoolError::DuplicateFile(name.to_owned())),
+                None => {
+                    for symbol in file_symbols(f.proto()) {
+                        if self.symbols.contains_key(&symbol) {
+                            return Err(FileDescriptorPoolError::DuplicateSymbol {
+                                file: name.to_owned(),
+                                symbol,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        let messages: Vec<MessageDescriptor> = files
+            .iter()
+            .filter(|f| self.file_by_name(f.proto().get_name()).is_none())
+            .flat_map(|f| f.all_messages())
+            .collect();
+        check_nesting(&messages, self.max_nesting_depth)?;
+
+        for f in files {
+            if self.file_by_name(f.proto().get_name()).is_none() {
+                self.insert(f.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Keep the file and its dependencies in the pool when collecting garbage,
+    /// even if they are not used outside of the pool.
+    ///
+    /// Returns `false` if there is no such file in the pool.
+    pub fn pin(&mut self, name: &str) -> bool {
+        if self.by_name.contains_key(name) {
+            self.pinned.insert(name.to_owned());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Allow the file pinned with [`pin`](FileDescriptorPool::pin) to be removed
+    /// by [`collect_garbage`](FileDescriptorPool::collect_garbage).
+    ///
+    /// Returns `false` if the file was not pinned.
+    pub fn unpin(&mut self, name: &str) -> bool {
+        self.pinned.remove(name)
+    }
+
+    /// Is the file pinned with [`pin`](FileDescriptorPool::pin).
+    pub fn is_pinned(&self, name: &str) -> bool {
+        self.pinned.contains(name)
+    }
+
+    /// Remove dynamic files which are not used outside of the pool,
+    /// and return names of removed files.
+    ///
+    /// File is used if a descriptor of the file or of anything defined in it
+    /// (message, enum, field, extension) is alive outside of the pool,
+    /// e. g. held by a dynamic message, by a file which depends on it,
+    /// or by a clone of this pool.
+    /// Pinned files, files they depend on, and generated files are never removed.
+    ///
+    /// Removed files can be added to the pool again, but new descriptors
+    /// are not equal to the descriptors of removed files.
+    pub fn collect_garbage(&mut self) -> Vec<String> {
+        enum Entry {
+            Strong(FileDescriptor),
+            Weak(String, Weak<DynamicFileDescriptor>),
+        }
+
+        self.by_name.clear();
+        self.symbols.clear();
+        self.extensions.clear();
+        // Drop all references held by the pool at once, so files
+        // used only by removed files are removed too
+        let entries: Vec<Entry> = mem::take(&mut self.files)
+            .into_iter()
+            .map(|file| match &file.imp {
+                FileDescriptorImpl::Dynamic(d)
+                    if !self.pinned.contains(file.proto().get_name()) =>
+                {
+                    Entry::Weak(file.proto().get_name().to_owned(), Arc::downgrade(d))
+                }
+                _ => Entry::Strong(file),
+            })
+            .collect();
+
+        let mut removed = Vec::new();
+        for entry in entries {
+            match entry {
+                Entry::Strong(file) => self.insert(file),
+                Entry::Weak(name, weak) => match weak.upgrade() {
+                    Some(d) => self.insert(FileDescriptor {
+                        imp: FileDescriptorImpl::Dynamic(d),
+                    }),
+                    None => removed.push(name),
+                },
+            }
+        }
+        removed
+    }
+
+    fn insert(&mut self, file: FileDescriptor) {
+        let index = self.files.len();
+        for symbol in file_symbols(file.proto()) {
+            self.symbols.insert(symbol, index);
+        }
+        for extension in file.extensions() {
+            self.extensions
+                .entry(extension.extendee_name().to_owned())
+                .or_default()
+                .entry(extension.number())
+                .or_insert(extension);
+        }
+        self.by_name
+            .insert(file.proto().get_name().to_owned(), index);
+        self.files.push(file);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::slice;
+
+    use super::FileDescriptorPool;
+    use super::FileDescriptorPoolError;
+    use crate::descriptor;
+    use crate::descriptor::field_descriptor_proto::Label;
+    use crate::descriptor::field_descriptor_proto::Type;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FieldDescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::descriptor::FileDescriptorSet;
+    use crate::plugin;
+    use crate::reflect::ReflectValueBox;
+    use crate::reflect::ReflectValueRef;
+    use crate::reflect::RuntimeTypeBox;
+    use crate::Message;
+
+    #[test]
+    fn add_incrementally() {
+        let mut pool = FileDescriptorPool::new();
+        let d = pool
+            .add_file(descriptor::file_descriptor().proto().clone())
+            .unwrap();
+        let p = pool
+            .add_file(plugin::file_descriptor().proto().clone())
+            .unwrap();
+        assert_eq!(slice::from_ref(&d), p.dependencies());
+        assert_eq!(&[d.clone(), p], pool.files());
+        assert!(pool
+            .file_by_name("google/protobuf/descriptor.proto")
+            .is_some());
+
+        // adding the same file again returns existing descriptor
+        let d2 = pool
+            .add_file(descriptor::file_descriptor().proto().clone())
+            .unwrap();
+        assert_eq!(d, d2);
+        assert_eq!(2, pool.files().len());
+    }
+
+    #[test]
+    fn add_files_out_of_order() {
+        let mut pool = FileDescriptorPool::new();
+        let files = pool
+            .add_files(vec![
+                plugin::file_descriptor().proto().clone(),
+                descriptor::file_descriptor().proto().clone(),
+            ])
+            .unwrap();
+        assert_eq!(
+            "google/protobuf/compiler/plugin.proto",
+            files[0].proto().get_name()
+        );
+        assert_eq!(&[files[1].clone()], files[0].dependencies());
+    }
+
+    #[test]
+    fn missing_dependency() {
+        let mut pool = FileDescriptorPool::new();
+        assert_eq!(
+            Err(FileDescriptorPoolError::MissingDependency {
+                file: "google/protobuf/compiler/plugin.proto".to_owned(),
+                dependency: "google/protobuf/descriptor.proto".to_owned(),
+            }),
+            pool.add_file(plugin::file_descriptor().proto().clone())
+        );
+        assert!(pool.files().is_empty());
+    }
+
+    #[test]
+    fn duplicates() {
+        let mut pool = FileDescriptorPool::new();
+        pool.add_file_descriptor(&plugin::file_descriptor())
+            .unwrap();
+        assert_eq!(2, pool.files().len());
+
+        let mut changed = descriptor::file_descriptor().proto().clone();
+        changed.message_type.pop();
+        assert_eq!(
+            Err(FileDescriptorPoolError::DuplicateFile(
+                "google/protobuf/descriptor.proto".to_owned()
+            )),
+            pool.add_file(changed)
+        );
+
+        let mut renamed = descriptor::file_descriptor().proto().clone();
+        renamed.set_name("copy.proto".to_owned());
+        assert_eq!(
+            Err(FileDescriptorPoolError::DuplicateSymbol {
+                file: "copy.proto".to_owned(),
+                symbol: "google.protobuf.FileDescriptorSet".to_owned(),
+            }),
+            pool.add_file(renamed)
+        );
+        assert_eq!(2, pool.files().len());
+    }
+
+    #[test]
+    fn unresolved_type() {
+        let mut field = descriptor::FieldDescriptorProto::new();
+        field.set_name("f".to_owned());
+        field.set_number(1);
+        field.set_label(descriptor::field_descriptor_proto::Label::LABEL_REPEATED);
+        field.set_field_type(descriptor::field_descriptor_proto::Type::TYPE_MESSAGE);
+        field.set_type_name(".p.Missing".to_owned());
+        let mut message = descriptor::DescriptorProto::new();
+        message.set_name("M".to_owned());
+        message.field.push(field);
+        let mut file = descriptor::FileDescriptorProto::new();
+        file.set_name("a.proto".to_owned());
+        file.set_package("p".to_owned());
+        file.message_type.push(message);
+
+        let mut pool = FileDescriptorPool::new();
+        assert_eq!(
+            Err(FileDescriptorPoolError::UnresolvedType {
+                file: "a.proto".to_owned(),
+                type_name: ".p.Missing".to_owned(),
+            }),
+            pool.add_file(file.clone())
+        );
+
+        // Entry message without key and value fields
+        let mut entry = descriptor::DescriptorProto::new();
+        entry.set_name("Missing".to_owned());
+        entry.options.mut_or_default().set_map_entry(true);
+        file.message_type.push(entry);
+        assert_eq!(
+            Err(FileDescriptorPoolError::InvalidMapEntry {
+                file: "a.proto".to_owned(),
+                type_name: ".p.Missing".to_owned(),
+            }),
+            pool.add_file(file)
+        );
+        assert!(pool.files().is_empty());
+    }
+
+    #[test]
+    fn dependency_cycle() {
+        let mut a = descriptor::FileDescriptorProto::new();
+        a.set_name("a.proto".to_owned());
+        a.dependency.push("b.proto".to_owned());
+        let mut b = descriptor::FileDescriptorProto::new();
+        b.set_name("b.proto".to_owned());
+        b.dependency.push("a.proto".to_owned());
+
+        let mut pool = FileDescriptorPool::new();
+        assert_eq!(
+            Err(FileDescriptorPoolError::DependencyCycle(
+                "a.proto".to_owned()
+            )),
+            pool.add_files(vec![a, b]).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn file_descriptor_set_bytes() {
+        let mut fds = FileDescriptorSet::new();
+        fds.file.push(plugin::file_descriptor().proto().clone());
+        fds.file.push(descriptor::file_descriptor().proto().clone());
+
+        let mut pool = FileDescriptorPool::new();
+        let files = pool
+            .add_file_descriptor_set_bytes(&fds.write_to_bytes().unwrap())
+            .unwrap();
+        assert_eq!(2, files.len());
+        assert_eq!(&[files[1].clone()], files[0].dependencies());
+
+        let request = files[0]
+            .message_by_package_relative_name("CodeGeneratorRequest")
+            .unwrap();
+        let mut generated = plugin::CodeGeneratorRequest::new();
+        generated.file_to_generate.push("a.proto".to_owned());
+        generated
+            .proto_file
+            .push(descriptor::file_descriptor().proto().clone());
+        let mut dynamic = request.new_instance();
+        dynamic
+            .merge_from_bytes_dyn(&generated.write_to_bytes().unwrap())
+            .unwrap();
+        let proto_file = request.get_field_by_name("proto_file").unwrap();
+        let proto_file = proto_file.get_repeated(&*dynamic);
+        assert_eq!(1, proto_file.len());
+        let name = files[1]
+            .message_by_package_relative_name("FileDescriptorProto")
+            .unwrap()
+            .get_field_by_name("name")
+            .unwrap();
+        assert_eq!(
+            Some(ReflectValueRef::String("google/protobuf/descriptor.proto")),
+            name.get_singular(&*proto_file.get(0).to_message().unwrap())
+        );
+    }
+
+    #[test]
+    fn file_descriptor_set_bytes_invalid() {
+        let mut pool = FileDescriptorPool::new();
+        match pool.add_file_descriptor_set_bytes(b"\x0f") {
+            Err(FileDescriptorPoolError::InvalidFileDescriptorSet(_)) => {}
+            r => panic!("{:?}", r),
+        }
+        assert!(pool.files().is_empty());
+    }
+
+    fn extensions_file() -> FileDescriptorProto {
+        fn extension(name: &str, number: i32) -> FieldDescriptorProto {
+            let mut extension = FieldDescriptorProto::new();
+            extension.set_name(name.to_owned());
+            extension.set_number(number);
+            extension.set_extendee(".google.protobuf.FieldOptions".to_owned());
+            extension.set_field_type(Type::TYPE_INT32);
+            extension
+        }
+
+        let mut file = FileDescriptorProto::new();
+        file.set_name("ext.proto".to_owned());
+        file.set_package("ext".to_owned());
+        file.dependency
+            .push("google/protobuf/descriptor.proto".to_owned());
+        file.extension.push(extension("top", 50000));
+        let mut scope = DescriptorProto::new();
+        scope.set_name("Scope".to_owned());
+        scope.extension.push(extension("nested", 50001));
+        file.message_type.push(scope);
+        file
+    }
+
+    #[test]
+    fn lookup_by_full_name() {
+        let mut pool = FileDescriptorPool::new();
+        pool.add_files(vec![
+            descriptor::file_descriptor().proto().clone(),
+            extensions_file(),
+        ])
+        .unwrap();
+
+        let range = pool
+            .message_by_full_name("google.protobuf.DescriptorProto.ExtensionRange")
+            .unwrap();
+        assert_eq!(
+            "google.protobuf.DescriptorProto.ExtensionRange",
+            range.full_name()
+        );
+        assert_eq!(
+            "google.protobuf.FileDescriptorSet",
+            pool.message_by_full_name(".google.protobuf.FileDescriptorSet")
+                .unwrap()
+                .full_name()
+        );
+        assert_eq!(
+            "ext.Scope",
+            pool.message_by_full_name("ext.Scope").unwrap().full_name()
+        );
+        assert_eq!(None, pool.message_by_full_name("google.protobuf.Missing"));
+        assert_eq!(
+            None,
+            pool.message_by_full_name("google.protobuf.FieldDescriptorProto.Type")
+        );
+
+        let t = pool
+            .enum_by_full_name("google.protobuf.FieldDescriptorProto.Type")
+            .unwrap();
+        assert_eq!("google.protobuf.FieldDescriptorProto.Type", t.full_name());
+        assert!(pool
+            .enum_by_full_name("google.protobuf.DescriptorProto")
+            .is_none());
+
+        let messages: Vec<_> = pool.messages().map(|m| m.full_name().to_owned()).collect();
+        assert!(messages.contains(&"google.protobuf.DescriptorProto.ExtensionRange".to_owned()));
+        assert!(messages.contains(&"ext.Scope".to_owned()));
+    }
+
+    #[test]
+    fn lookup_extension_by_number() {
+        let mut pool = FileDescriptorPool::new();
+        pool.add_files(vec![
+            descriptor::file_descriptor().proto().clone(),
+            extensions_file(),
+        ])
+        .unwrap();
+
+        let top = pool
+            .extension_by_number("google.protobuf.FieldOptions", 50000)
+            .unwrap();
+        assert_eq!("ext.top", top.full_name());
+        assert!(top.scope().is_none());
+        assert_eq!(
+            "google.protobuf.FieldOptions",
+            top.extendee().unwrap().full_name()
+        );
+
+        let nested = pool
+            .extension_by_number(".google.protobuf.FieldOptions", 50001)
+            .unwrap();
+        assert_eq!("ext.Scope.nested", nested.full_name());
+        assert_eq!("ext.Scope", nested.scope().unwrap().full_name());
+
+        assert!(pool
+            .extension_by_number("google.protobuf.FieldOptions", 50002)
+            .is_none());
+        assert!(pool
+            .extension_by_number("google.protobuf.MessageOptions", 50000)
+            .is_none());
+    }
+
+    #[test]
+    fn messages_with_option() {
+        let mut topic = FieldDescriptorProto::new();
+        topic.set_name("topic".to_owned());
+        topic.set_number(50002);
+        topic.set_extendee(".google.protobuf.MessageOptions".to_owned());
+        topic.set_field_type(Type::TYPE_STRING);
+
+        let mut file = FileDescriptorProto::new();
+        file.set_name("topics.proto".to_owned());
+        file.set_package("mycorp".to_owned());
+        file.dependency
+            .push("google/protobuf/descriptor.proto".to_owned());
+        file.extension.push(topic);
+        for (name, topic) in &[("Order", Some("orders")), ("Internal", None)] {
+            let mut message = DescriptorProto::new();
+            message.set_name(name.to_string());
+            if let Some(topic) = topic {
+                message
+                    .options
+                    .mut_or_default()
+                    .mut_unknown_fields()
+                    .add_length_delimited(50002, topic.as_bytes().to_vec());
+            }
+            file.message_type.push(message);
+        }
+
+        let mut pool = FileDescriptorPool::new();
+        pool.add_files(vec![descriptor::file_descriptor().proto().clone(), file])
+            .unwrap();
+
+        let routes: Vec<(String, ReflectValueBox)> = pool
+            .messages_with_option("mycorp.topic")
+            .into_iter()
+            .map(|(m, v)| (m.full_name().to_owned(), v))
+            .collect();
+        assert_eq!(
+            vec![(
+                "mycorp.Order".to_owned(),
+                ReflectValueBox::String("orders".to_owned())
+            )],
+            routes
+        );
+        assert_eq!(1, pool.messages_with_option(".mycorp.topic").len());
+        assert!(pool.messages_with_option("mycorp.missing").is_empty());
+    }
+
+    #[test]
+    fn well_known_types() {
+        let pool = FileDescriptorPool::new();
+        assert_eq!(
+            "google.protobuf.Any",
+            pool.message_by_full_name(".google.protobuf.Any")
+                .unwrap()
+                .full_name()
+        );
+        assert!(pool
+            .enum_by_full_name("google.protobuf.NullValue")
+            .is_some());
+        assert!(pool
+            .message_by_full_name("google.protobuf.Missing")
+            .is_none());
+        assert!(pool.files().is_empty());
+    }
+
+    #[test]
+    fn well_known_types_dependencies() {
+        let mut field = FieldDescriptorProto::new();
+        field.set_name("api".to_owned());
+        field.set_number(1);
+        field.set_field_type(Type::TYPE_MESSAGE);
+        field.set_type_name(".google.protobuf.Api".to_owned());
+        let mut message = DescriptorProto::new();
+        message.set_name("M".to_owned());
+        message.field.push(field);
+        let mut file = FileDescriptorProto::new();
+        file.set_name("a.proto".to_owned());
+        file.dependency.push("google/protobuf/api.proto".to_owned());
+        file.dependency
+            .push("google/protobuf/timestamp.proto".to_owned());
+        file.message_type.push(message);
+
+        let mut pool = FileDescriptorPool::new();
+        pool.add_file(file).unwrap();
+        let names: Vec<&str> = pool.files().iter().map(|f| f.proto().get_name()).collect();
+        assert_eq!("a.proto", *names.last().unwrap());
+        for name in &[
+            "google/protobuf/api.proto",
+            "google/protobuf/type.proto",
+            "google/protobuf/any.proto",
+            "google/protobuf/source_context.proto",
+            "google/protobuf/timestamp.proto",
+        ] {
+            assert!(names.contains(name), "{}", name);
+        }
+        assert_eq!(6, names.len());
+        let api = pool.message_by_full_name("google.protobuf.Api").unwrap();
+        match pool
+            .message_by_full_name("M")
+            .unwrap()
+            .get_field_by_name("api")
+            .unwrap()
+            .singular_runtime_type()
+        {
+            RuntimeTypeBox::Message(m) => assert_eq!(api, m),
+            t => panic!("{:?}", t),
+        }
+
+        // other missing dependencies are still errors
+        let mut file = FileDescriptorProto::new();
+        file.set_name("b.proto".to_owned());
+        file.dependency
+            .push("google/protobuf/missing.proto".to_owned());
+        assert_eq!(
+            Err(FileDescriptorPoolError::MissingDependency {
+                file: "b.proto".to_owned(),
+                dependency: "google/protobuf/missing.proto".to_owned(),
+            }),
+            pool.add_file(file)
+        );
+    }
+
+    /// Message with message fields of given types from `nesting.proto`.
+    fn nesting_message(name: &str, fields: &[(&str, Label)]) -> DescriptorProto {
+        let mut message = DescriptorProto::new();
+        message.set_name(name.to_owned());
+        for (i, &(type_name, label)) in fields.iter().enumerate() {
+            let mut field = FieldDescriptorProto::new();
+            field.set_name(format!("f{}", i));
+            field.set_number(i as i32 + 1);
+            field.set_label(label);
+            field.set_field_type(Type::TYPE_MESSAGE);
+            field.set_type_name(format!(".n.{}", type_name));
+            message.field.push(field);
+        }
+        message
+    }
+
+    fn nesting_file(messages: Vec<DescriptorProto>) -> FileDescriptorProto {
+        let mut file = FileDescriptorProto::new();
+        file.set_name("nesting.proto".to_owned());
+        file.set_package("n".to_owned());
+        file.message_type = messages;
+        file
+    }
+
+    #[test]
+    fn max_nesting_depth() {
+        let file = nesting_file(vec![
+            nesting_message("A", &[("B", Label::LABEL_OPTIONAL)]),
+            nesting_message("B", &[("C", Label::LABEL_REPEATED)]),
+            nesting_message("C", &[]),
+            // mutually recursive messages count as a single level
+            nesting_message(
+                "X",
+                &[("Y", Label::LABEL_OPTIONAL), ("C", Label::LABEL_OPTIONAL)],
+            ),
+            nesting_message("Y", &[("X", Label::LABEL_REPEATED)]),
+        ]);
+
+        let mut pool = FileDescriptorPool::new();
+        pool.set_max_nesting_depth(2);
+        assert_eq!(
+            Err(FileDescriptorPoolError::NestingTooDeep {
+                message: "n.A".to_owned(),
+                depth: 3,
+            }),
+            pool.add_file(file.clone())
+        );
+        assert!(pool.files().is_empty());
+
+        pool.set_max_nesting_depth(3);
+        pool.add_file(file).unwrap();
+
+        let mut pool = FileDescriptorPool::new();
+        pool.set_max_nesting_depth(5);
+        assert!(matches!(
+            pool.add_file_descriptor(&plugin::file_descriptor()),
+            Err(FileDescriptorPoolError::NestingTooDeep { .. })
+        ));
+    }
+
+    #[test]
+    fn deep_nesting() {
+        // Too deep for recursive traversal
+        let n = 20_000;
+        let messages = (0..n)
+            .map(|i| {
+                let next = format!("M{}", i + 1);
+                match i + 1 < n {
+                    true => nesting_message(&format!("M{}", i), &[(&next, Label::LABEL_REQUIRED)]),
+                    false => nesting_message(&format!("M{}", i), &[]),
+                }
+            })
+            .collect();
+        let mut pool = FileDescriptorPool::new();
+        pool.set_max_nesting_depth(n - 1);
+        assert_eq!(
+            Err(FileDescriptorPoolError::NestingTooDeep {
+                message: "n.M0".to_owned(),
+                depth: n,
+            }),
+            pool.add_file(nesting_file(messages))
+        );
+    }
+
+    #[test]
+    fn required_field_cycle() {
+        let mut pool = FileDescriptorPool::new();
+        assert_eq!(
+            Err(FileDescriptorPoolError::RequiredFieldCycle(
+                "n.A".to_owned()
+            )),
+            pool.add_file(nesting_file(vec![
+                nesting_message("A", &[("B", Label::LABEL_REQUIRED)]),
+                nesting_message("B", &[("A", Label::LABEL_REQUIRED)]),
+            ]))
+        );
+
+        // cycle through optional field is fine
+        pool.add_file(nesting_file(vec![
+            nesting_message("A", &[("B", Label::LABEL_REQUIRED)]),
+            nesting_message("B", &[("A", Label::LABEL_OPTIONAL)]),
+        ]))
+        .unwrap();
+    }
+
+    #[test]
+    fn collect_garbage() {
+        let mut pool = FileDescriptorPool::new();
+        pool.add_file(descriptor::file_descriptor().proto().clone())
+            .unwrap();
+        assert!(pool.pin("google/protobuf/descriptor.proto"));
+        assert!(!pool.pin("missing.proto"));
+
+        let ext = pool.add_file(extensions_file()).unwrap();
+        let scope = pool.message_by_full_name("ext.Scope").unwrap();
+        drop(ext);
+        // message descriptor keeps the file alive
+        assert!(pool.collect_garbage().is_empty());
+
+        drop(scope);
+        assert_eq!(vec!["ext.proto".to_owned()], pool.collect_garbage());
+        assert!(pool.file_by_name("ext.proto").is_none());
+        assert!(pool.message_by_full_name("ext.Scope").is_none());
+        assert!(pool
+            .extension_by_number("google.protobuf.FieldOptions", 50000)
+            .is_none());
+        assert_eq!(1, pool.files().len());
+
+        // file can be added again
+        pool.add_file(extensions_file()).unwrap();
+        assert!(pool
+            .extension_by_number("google.protobuf.FieldOptions", 50000)
+            .is_some());
+
+        assert!(pool.unpin("google/protobuf/descriptor.proto"));
+        assert!(!pool.unpin("google/protobuf/descriptor.proto"));
+        let mut removed = pool.collect_garbage();
+        removed.sort();
+        assert_eq!(
+            vec![
+                "ext.proto".to_owned(),
+                "google/protobuf/descriptor.proto".to_owned()
+            ],
+            removed
+        );
+        assert!(pool.files().is_empty());
+    }
+
+    #[test]
+    fn collect_garbage_keeps_dependencies() {
+        let mut pool = FileDescriptorPool::new();
+        let files = pool
+            .add_files(vec![
+                descriptor::file_descriptor().proto().clone(),
+                plugin::file_descriptor().proto().clone(),
+            ])
+            .unwrap();
+        let plugin = files[1].clone();
+        drop(files);
+
+        assert!(pool.collect_garbage().is_empty());
+        assert_eq!(2, pool.files().len());
+
+        drop(plugin);
+        assert_eq!(2, pool.collect_garbage().len());
+
+        // generated files are never removed
+        pool.add_file_descriptor(&plugin::file_descriptor())
+            .unwrap();
+        assert!(pool.collect_garbage().is_empty());
+        assert_eq!(2, pool.files().len());
+    }
+}
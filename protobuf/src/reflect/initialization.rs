@@ -0,0 +1,62 @@
+use crate::descriptor::field_descriptor_proto;
+use crate::message_dyn::MessageDyn;
+use crate::reflect::path::FieldPath;
+use crate::reflect::path::FieldPathElement;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueRef;
+
+fn find_in_message(m: &dyn MessageDyn, path: &mut FieldPath, errors: &mut Vec<FieldPath>) {
+    for field in m.descriptor_dyn().fields() {
+        path.push(FieldPathElement::Field(field.get_name().to_owned()));
+        match field.get_reflect(m) {
+            ReflectFieldRef::Optional(None) => {
+                if field.get_proto().get_label() == field_descriptor_proto::Label::LABEL_REQUIRED {
+                    errors.push(path.clone());
+                }
+            }
+            ReflectFieldRef::Optional(Some(v)) => {
+                if let ReflectValueRef::Message(v) = v {
+                    find_in_message(&*v, path, errors);
+                }
+            }
+            ReflectFieldRef::Repeated(repeated) => {
+                for (i, v) in repeated.into_iter().enumerate() {
+                    if let ReflectValueRef::Message(v) = v {
+                        path.push(FieldPathElement::Index(i));
+                        find_in_message(&*v, path, errors);
+                        path.pop();
+                    }
+                }
+            }
+            ReflectFieldRef::Map(map) => {
+                for (k, v) in &map {
+                    if let ReflectValueRef::Message(v) = v {
+                        path.push(FieldPathElement::MapKey(k.to_box()));
+                        find_in_message(&*v, path, errors);
+                        path.pop();
+                    }
+                }
+            }
+        }
+        path.pop();
+    }
+}
+
+/// Paths to all required fields which are not set in the message
+/// or in nested messages.
+pub(crate) fn find_initialization_errors(m: &dyn MessageDyn) -> Vec<FieldPath> {
+    let mut errors = Vec::new();
+    find_in_message(m, &mut FieldPath::new(), &mut errors);
+    errors
+}
+
+/// Error message for a message which is not initialized.
+pub(crate) fn not_initialized_message(m: &dyn MessageDyn) -> String {
+    let name = m.descriptor_dyn().name().to_owned();
+    let errors = find_initialization_errors(m);
+    if errors.is_empty() {
+        return name;
+    }
+    let errors: Vec<String> = errors.iter().map(|p| p.to_string()).collect();
+    format!("{} (missing: {})", name, errors.join(", "))
+}
@@ -0,0 +1,467 @@
+//! Compact catalog of types for code completion.
+//!
+//! Full descriptor sets contain a lot of information not needed
+//! to suggest names, e. g. options and source locations,
+//! so [`to_json`] exports only names, numbers, types and comments:
+//!
+//! ```json
+//! {"messages":[{"name":"pkg.Person","file":"person.proto","doc":"A person.","fields":[
+//!     {"name":"name","number":1,"label":"optional","type":"string"},
+//!     {"name":"phones","number":2,"label":"map","key_type":"string","type":"pkg.Phone"}]}],
+//!  "enums":[{"name":"pkg.Kind","file":"person.proto","values":[{"name":"HOME","number":0}]}]}
+//! ```
+//!
+//! Messages and enums are listed in the order of files in the pool,
+//! nested types after their containing message. Map entry messages are omitted,
+//! map fields have label `map` and `key_type`. `doc` is omitted when there
+//! is no comment (e. g. descriptors are compiled without source info).
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::descriptor::field_descriptor_proto::Label;
+use crate::descriptor::field_descriptor_proto::Type;
+use crate::descriptor::DescriptorProto;
+use crate::descriptor::EnumDescriptorProto;
+use crate::descriptor::FieldDescriptorProto;
+use crate::descriptor::FileDescriptorProto;
+use crate::json::quote_json_string_to;
+use crate::reflect::name::concat_paths;
+use crate::reflect::FileDescriptorPool;
+
+// Field numbers used in source code info paths
+const FILE_MESSAGE_TYPE: i32 = 4;
+const FILE_ENUM_TYPE: i32 = 5;
+const MESSAGE_FIELD: i32 = 2;
+const MESSAGE_NESTED_TYPE: i32 = 3;
+const MESSAGE_ENUM_TYPE: i32 = 4;
+const ENUM_VALUE: i32 = 2;
+
+/// Comments by source code info path.
+struct Docs<'a> {
+    comments: HashMap<&'a [i32], &'a str>,
+}
+
+impl<'a> Docs<'a> {
+    fn new(file: &'a FileDescriptorProto) -> Docs<'a> {
+        let mut comments = HashMap::new();
+        for location in &file.source_code_info.get_or_default().location {
+            let comment = if location.has_leading_comments() {
+                location.get_leading_comments()
+            } else {
+                location.get_trailing_comments()
+            };
+            let comment = comment.trim();
+            if !comment.is_empty() {
+                comments.insert(&location.path[..], comment);
+            }
+        }
+        Docs { comments }
+    }
+
+    fn write(&self, path: &[i32], object: &mut JsonObject) {
+        if let Some(doc) = self.comments.get(path) {
+            object.string("doc", doc);
+        }
+    }
+}
+
+/// Writer of JSON object members in insertion order.
+///
+/// Strings are escaped with the JSON printer escaping.
+struct JsonObject<'a> {
+    buf: &'a mut String,
+    empty: bool,
+}
+
+impl<'a> JsonObject<'a> {
+    fn new(buf: &'a mut String) -> JsonObject<'a> {
+        buf.push('{');
+        JsonObject { buf, empty: true }
+    }
+
+    /// Write the key and return the buffer to write the value to.
+    fn key(&mut self, key: &str) -> &mut String {
+        if !self.empty {
+            self.buf.push(',');
+        }
+        self.empty = false;
+        quote_json_string_to(key, self.buf);
+        self.buf.push(':');
+        self.buf
+    }
+
+    fn string(&mut self, key: &str, value: &str) {
+        quote_json_string_to(value, self.key(key));
+    }
+
+    fn number(&mut self, key: &str, value: i32) {
+        write!(self.key(key), "{}", value).unwrap();
+    }
+
+    /// Array of objects written by given function.
+    fn array<T>(
+        &mut self,
+        key: &str,
+        items: impl IntoIterator<Item = T>,
+        mut write: impl FnMut(T, &mut JsonObject),
+    ) {
+        let buf = self.key(key);
+        buf.push('[');
+        for (i, item) in items.into_iter().enumerate() {
+            if i != 0 {
+                buf.push(',');
+            }
+            let mut object = JsonObject::new(buf);
+            write(item, &mut object);
+            object.end();
+        }
+        buf.push(']');
+    }
+
+    fn end(self) {
+        self.buf.push('}');
+    }
+}
+
+struct Catalog {
+    messages: Vec<String>,
+    enums: Vec<String>,
+}
+
+fn scalar_type_name(t: Type) -> &'static str {
+    match t {
+        Type::TYPE_DOUBLE => "double",
+        Type::TYPE_FLOAT => "float",
+        Type::TYPE_INT64 => "int64",
+        Type::TYPE_UINT64 => "uint64",
+        Type::TYPE_INT32 => "int32",
+        Type::TYPE_FIXED64 => "fixed64",
+        Type::TYPE_FIXED32 => "fixed32",
+        Type::TYPE_BOOL => "bool",
+        Type::TYPE_STRING => "string",
+        Type::TYPE_GROUP => "group",
+        Type::TYPE_MESSAGE => "message",
+        Type::TYPE_BYTES => "bytes",
+        Type::TYPE_UINT32 => "uint32",
+        Type::TYPE_ENUM => "enum",
+        Type::TYPE_SFIXED32 => "sfixed32",
+        Type::TYPE_SFIXED64 => "sfixed64",
+        Type::TYPE_SINT32 => "sint32",
+        Type::TYPE_SINT64 => "sint64",
+    }
+}
+
+/// Full name of message or enum type without leading dot, or scalar type name.
+fn type_name(field: &FieldDescriptorProto) -> String {
+    match field.get_field_type() {
+        Type::TYPE_MESSAGE | Type::TYPE_ENUM | Type::TYPE_GROUP => {
+            field.get_type_name().trim_start_matches('.').to_owned()
+        }
+        t => scalar_type_name(t).to_owned(),
+    }
+}
+
+/// Map entry message if the field is a map field.
+fn map_entry<'a>(
+    message: &'a DescriptorProto,
+    field: &FieldDescriptorProto,
+) -> Option<&'a DescriptorProto> {
+    if field.get_label() != Label::LABEL_REPEATED || field.get_field_type() != Type::TYPE_MESSAGE {
+        return None;
+    }
+    // Map entry message is always nested in the message containing map field
+    let type_name = field.get_type_name();
+    let entry_name = &type_name[type_name.rfind('.').map_or(0, |i| i + 1)..];
+    message
+        .nested_type
+        .iter()
+        .find(|m| m.get_name() == entry_name && m.options.get_or_default().get_map_entry())
+}
+
+fn write_field(
+    message: &DescriptorProto,
+    field: &FieldDescriptorProto,
+    path: &[i32],
+    docs: &Docs,
+    object: &mut JsonObject,
+) {
+    object.string("name", field.get_name());
+    object.number("number", field.get_number());
+    match map_entry(message, field) {
+        Some(entry) => {
+            object.string("label", "map");
+            object.string("key_type", &type_name(&entry.field[0]));
+            object.string("type", &type_name(&entry.field[1]));
+        }
+        None => {
+            let label = match field.get_label() {
+                Label::LABEL_OPTIONAL => "optional",
+                Label::LABEL_REQUIRED => "required",
+                Label::LABEL_REPEATED => "repeated",
+            };
+            object.string("label", label);
+            object.string("type", &type_name(field));
+        }
+    }
+    if field.has_oneof_index() {
+        if let Some(oneof) = message.oneof_decl.get(field.get_oneof_index() as usize) {
+            object.string("oneof", oneof.get_name());
+        }
+    }
+    docs.write(path, object);
+}
+
+fn add_enum(
+    catalog: &mut Catalog,
+    file: &FileDescriptorProto,
+    scope: &str,
+    e: &EnumDescriptorProto,
+    path: &mut Vec<i32>,
+    docs: &Docs,
+) {
+    let mut buf = String::new();
+    let mut object = JsonObject::new(&mut buf);
+    object.string("name", &concat_paths(scope, e.get_name()));
+    object.string("file", file.get_name());
+    docs.write(path, &mut object);
+    object.array(
+        "values",
+        e.value.iter().enumerate(),
+        |(i, value), object| {
+            object.string("name", value.get_name());
+            object.number("number", value.get_number());
+            path.extend_from_slice(&[ENUM_VALUE, i as i32]);
+            docs.write(path, object);
+            path.truncate(path.len() - 2);
+        },
+    );
+    object.end();
+    catalog.enums.push(buf);
+}
+
+fn add_message(
+    catalog: &mut Catalog,
+    file: &FileDescriptorProto,
+    scope: &str,
+    message: &DescriptorProto,
+    path: &mut Vec<i32>,
+    docs: &Docs,
+) {
+    if message.options.get_or_default().get_map_entry() {
+        return;
+    }
+    let name = concat_paths(scope, message.get_name());
+
+    let mut buf = String::new();
+    let mut object = JsonObject::new(&mut buf);
+    object.string("name", &name);
+    object.string("file", file.get_name());
+    docs.write(path, &mut object);
+    object.array(
+        "fields",
+        message.field.iter().enumerate(),
+        |(i, field), object| {
+            path.extend_from_slice(&[MESSAGE_FIELD, i as i32]);
+            write_field(message, field, path, docs, object);
+            path.truncate(path.len() - 2);
+        },
+    );
+    object.end();
+    catalog.messages.push(buf);
+
+    for (i, nested) in message.nested_type.iter().enumerate() {
+        path.extend_from_slice(&[MESSAGE_NESTED_TYPE, i as i32]);
+        add_message(catalog, file, &name, nested, path, docs);
+        path.truncate(path.len() - 2);
+    }
+    for (i, e) in message.enum_type.iter().enumerate() {
+        path.extend_from_slice(&[MESSAGE_ENUM_TYPE, i as i32]);
+        add_enum(catalog, file, &name, e, path, docs);
+        path.truncate(path.len() - 2);
+    }
+}
+
+/// Export messages, fields and enums of all files in the pool as compact JSON.
+///
+/// See [module documentation](self) for the format.
+pub fn to_json(pool: &FileDescriptorPool) -> String {
+    let mut catalog = Catalog {
+        messages: Vec::new(),
+        enums: Vec::new(),
+    };
+    for file in pool.files() {
+        let proto = file.proto();
+        let docs = Docs::new(proto);
+        let mut path = Vec::new();
+        for (i, message) in proto.message_type.iter().enumerate() {
+            path.extend_from_slice(&[FILE_MESSAGE_TYPE, i as i32]);
+            add_message(
+                &mut catalog,
+                proto,
+                proto.get_package(),
+                message,
+                &mut path,
+                &docs,
+            );
+            path.clear();
+        }
+        for (i, e) in proto.enum_type.iter().enumerate() {
+            path.extend_from_slice(&[FILE_ENUM_TYPE, i as i32]);
+            add_enum(
+                &mut catalog,
+                proto,
+                proto.get_package(),
+                e,
+                &mut path,
+                &docs,
+            );
+            path.clear();
+        }
+    }
+
+    let mut buf = String::new();
+    let mut object = JsonObject::new(&mut buf);
+    for (key, items) in &[("messages", &catalog.messages), ("enums", &catalog.enums)] {
+        let buf = object.key(key);
+        buf.push('[');
+        buf.push_str(&items.join(","));
+        buf.push(']');
+    }
+    object.end();
+    buf
+}
+
+#[cfg(test)]
+mod test {
+    use super::to_json;
+    use crate::descriptor::field_descriptor_proto::Label;
+    use crate::descriptor::field_descriptor_proto::Type;
+    use crate::descriptor::source_code_info::Location;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::EnumDescriptorProto;
+    use crate::descriptor::EnumValueDescriptorProto;
+    use crate::descriptor::FieldDescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::descriptor::OneofDescriptorProto;
+    use crate::reflect::FileDescriptorPool;
+
+    fn field(
+        name: &str,
+        number: i32,
+        label: Label,
+        t: Type,
+        type_name: &str,
+    ) -> FieldDescriptorProto {
+        let mut f = FieldDescriptorProto::new();
+        f.set_name(name.to_owned());
+        f.set_number(number);
+        f.set_label(label);
+        f.set_field_type(t);
+        if !type_name.is_empty() {
+            f.set_type_name(type_name.to_owned());
+        }
+        f
+    }
+
+    fn file() -> FileDescriptorProto {
+        let mut entry = DescriptorProto::new();
+        entry.set_name("TagsEntry".to_owned());
+        entry.options.mut_or_default().set_map_entry(true);
+        entry.field.push(field(
+            "key",
+            1,
+            Label::LABEL_OPTIONAL,
+            Type::TYPE_STRING,
+            "",
+        ));
+        entry.field.push(field(
+            "value",
+            2,
+            Label::LABEL_OPTIONAL,
+            Type::TYPE_ENUM,
+            ".pkg.Kind",
+        ));
+
+        let mut person = DescriptorProto::new();
+        person.set_name("Person".to_owned());
+        person.field.push(field(
+            "name",
+            1,
+            Label::LABEL_OPTIONAL,
+            Type::TYPE_STRING,
+            "",
+        ));
+        person.field.push(field(
+            "tags",
+            2,
+            Label::LABEL_REPEATED,
+            Type::TYPE_MESSAGE,
+            ".pkg.Person.TagsEntry",
+        ));
+        person.nested_type.push(entry);
+
+        let mut kind = EnumDescriptorProto::new();
+        kind.set_name("Kind".to_owned());
+        let mut value = EnumValueDescriptorProto::new();
+        value.set_name("HOME".to_owned());
+        value.set_number(0);
+        kind.value.push(value);
+
+        let mut file = FileDescriptorProto::new();
+        file.set_name("person.proto".to_owned());
+        file.set_package("pkg".to_owned());
+        file.message_type.push(person);
+        file.enum_type.push(kind);
+
+        let mut location = Location::new();
+        location.path = vec![4, 0, 2, 0];
+        location.set_leading_comments(" The \"full\" name.\n".to_owned());
+        file.source_code_info
+            .mut_or_default()
+            .location
+            .push(location);
+        file
+    }
+
+    #[test]
+    fn catalog() {
+        let mut pool = FileDescriptorPool::new();
+        pool.add_file(file()).unwrap();
+        assert_eq!(
+            concat!(
+                r#"{"messages":[{"name":"pkg.Person","file":"person.proto","fields":["#,
+                r#"{"name":"name","number":1,"label":"optional","type":"string","doc":"The \"full\" name."},"#,
+                r#"{"name":"tags","number":2,"label":"map","key_type":"string","type":"pkg.Kind"}]}],"#,
+                r#""enums":[{"name":"pkg.Kind","file":"person.proto","values":[{"name":"HOME","number":0}]}]}"#,
+            ),
+            to_json(&pool)
+        );
+    }
+
+    #[test]
+    fn oneof_and_escaping() {
+        let mut file = file();
+        let person = &mut file.message_type[0];
+        let mut oneof = OneofDescriptorProto::new();
+        oneof.set_name("choice".to_owned());
+        person.oneof_decl.push(oneof);
+        person.field[0].set_oneof_index(0);
+        file.source_code_info.mut_or_default().location[0]
+            .set_leading_comments(" Tab\there, bell\u{7}.\n".to_owned());
+        let mut pool = FileDescriptorPool::new();
+        pool.add_file(file).unwrap();
+        assert!(to_json(&pool).contains(concat!(
+            r#"{"name":"name","number":1,"label":"optional","type":"string","#,
+            r#""oneof":"choice","doc":"Tab\there, bell\u0007."}"#,
+        )));
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            r#"{"messages":[],"enums":[]}"#,
+            to_json(&FileDescriptorPool::new())
+        );
+    }
+}
@@ -0,0 +1,470 @@
+use std::cell::RefCell;
+use std::fmt;
+
+use crate::descriptor::field_descriptor_proto::Type;
+use crate::error::WireError;
+use crate::reflect::dynamic::read_singular;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::rt::unexpected_wire_type;
+use crate::wire_format::WireType;
+use crate::wire_scan::message_layout;
+use crate::wire_scan::FieldSpan;
+use crate::CodedInputStream;
+use crate::MessageDyn;
+use crate::ProtobufError;
+use crate::ProtobufResult;
+
+/// Read-only view of serialized message which decodes fields on demand.
+///
+/// Nothing is parsed when the view is created. On first access
+/// offsets of top-level fields are scanned and cached, and then only
+/// requested fields are decoded. Nested messages are scanned only
+/// when accessed through [`get_message`](LazyMessageView::get_message)
+/// or [`LazyRepeatedView::get_message`].
+///
+/// This is faster than parsing the whole message when only
+/// a small fraction of fields is accessed. When most fields are needed,
+/// parse the message with [`to_message`](LazyMessageView::to_message) instead.
+///
+/// Map fields are not supported by the view.
+///
+/// ```
+/// # use protobuf::reflect::LazyMessageView;
+/// # use protobuf::reflect::MessageDescriptor;
+/// # fn foo(descriptor: &MessageDescriptor, bytes: &[u8]) -> protobuf::ProtobufResult<()> {
+/// let view = LazyMessageView::new(descriptor, bytes);
+/// let field = descriptor.get_field_by_name("items").unwrap();
+/// let items = view.get_repeated(&field)?;
+/// if !items.is_empty() {
+///     println!("first item: {:?}", items.get(0)?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct LazyMessageView<'a> {
+    descriptor: MessageDescriptor,
+    bytes: &'a [u8],
+    /// Spans of top-level fields, scanned on first access.
+    layout: RefCell<Option<Vec<FieldSpan>>>,
+}
+
+impl<'a> fmt::Debug for LazyMessageView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LazyMessageView")
+            .field("descriptor", &self.descriptor.full_name())
+            .field("len", &self.bytes.len())
+            .finish()
+    }
+}
+
+impl<'a> LazyMessageView<'a> {
+    /// View serialized message of given type.
+    pub fn new(descriptor: &MessageDescriptor, bytes: &'a [u8]) -> LazyMessageView<'a> {
+        LazyMessageView {
+            descriptor: descriptor.clone(),
+            bytes,
+            layout: RefCell::new(None),
+        }
+    }
+
+    /// Type of the viewed message.
+    pub fn descriptor(&self) -> &MessageDescriptor {
+        &self.descriptor
+    }
+
+    /// Values of all occurrences of the field, with wire types.
+    fn occurrences(&self, field: &FieldDescriptor) -> ProtobufResult<Vec<(WireType, &'a [u8])>> {
+        assert_eq!(field.message_descriptor, self.descriptor);
+
+        let mut layout = self.layout.borrow_mut();
+        if layout.is_none() {
+            *layout = Some(message_layout(self.bytes)?);
+        }
+
+        let number = field.get_proto().get_number() as u32;
+        Ok(layout
+            .as_ref()
+            .unwrap()
+            .iter()
+            .filter(|s| s.field_number == number)
+            .map(|s| (s.wire_type, &self.bytes[s.value.clone()]))
+            .collect())
+    }
+
+    /// Value of the last occurrence of singular field.
+    fn singular_value(&self, field: &FieldDescriptor) -> ProtobufResult<Option<&'a [u8]>> {
+        let expected = wire_type_for(field.get_proto().get_field_type());
+        let mut last = None;
+        for (wire_type, value) in self.occurrences(field)? {
+            if wire_type != expected {
+                return Err(unexpected_wire_type(wire_type));
+            }
+            last = Some(value);
+        }
+        Ok(last)
+    }
+
+    /// Field is present in the message.
+    ///
+    /// For repeated fields, field is present if it has at least one element
+    /// (or empty packed occurrence).
+    pub fn has_field(&self, field: &FieldDescriptor) -> ProtobufResult<bool> {
+        Ok(!self.occurrences(field)?.is_empty())
+    }
+
+    /// Decode singular field.
+    ///
+    /// Like in parsing, last occurrence of the field wins.
+    /// `None` is returned if the field is not present.
+    ///
+    /// # Panics
+    ///
+    /// If field belongs to a different message type or field is not singular.
+    pub fn get_singular(&self, field: &FieldDescriptor) -> ProtobufResult<Option<ReflectValueBox>> {
+        let rtb = match field.runtime_field_type() {
+            RuntimeFieldType::Singular(rtb) => rtb,
+            _ => panic!("not a singular field: {}", field),
+        };
+        match self.singular_value(field)? {
+            Some(value) => Ok(Some(read_value(field, &rtb, value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// View of singular message field, `None` if the field is not present.
+    ///
+    /// # Panics
+    ///
+    /// If field belongs to a different message type
+    /// or field is not a singular message field.
+    pub fn get_message(
+        &self,
+        field: &FieldDescriptor,
+    ) -> ProtobufResult<Option<LazyMessageView<'a>>> {
+        let m = match field.runtime_field_type() {
+            RuntimeFieldType::Singular(RuntimeTypeBox::Message(m)) => m,
+            _ => panic!("not a singular message field: {}", field),
+        };
+        Ok(self
+            .singular_value(field)?
+            .map(|value| LazyMessageView::new(&m, value)))
+    }
+
+    /// View of repeated field.
+    ///
+    /// Offsets of elements are computed, but elements are not decoded.
+    ///
+    /// # Panics
+    ///
+    /// If field belongs to a different message type or field is not repeated.
+    pub fn get_repeated(&self, field: &FieldDescriptor) -> ProtobufResult<LazyRepeatedView<'a>> {
+        let rtb = match field.runtime_field_type() {
+            RuntimeFieldType::Repeated(rtb) => rtb,
+            _ => panic!("not a repeated field: {}", field),
+        };
+        let expected = wire_type_for(field.get_proto().get_field_type());
+        let mut elements = Vec::new();
+        for (wire_type, value) in self.occurrences(field)? {
+            if wire_type == expected {
+                elements.push(value);
+            } else if wire_type == WireType::WireTypeLengthDelimited && is_packable(expected) {
+                split_packed(expected, value, &mut elements)?;
+            } else {
+                return Err(unexpected_wire_type(wire_type));
+            }
+        }
+        Ok(LazyRepeatedView {
+            field: field.clone(),
+            rtb,
+            elements,
+        })
+    }
+
+    /// Parse the whole message.
+    pub fn to_message(&self) -> ProtobufResult<Box<dyn MessageDyn>> {
+        let mut message = self.descriptor.new_instance();
+        message.merge_from_bytes_dyn(self.bytes)?;
+        Ok(message)
+    }
+}
+
+/// Read-only view of repeated field of [`LazyMessageView`].
+///
+/// Elements are decoded on each access.
+pub struct LazyRepeatedView<'a> {
+    field: FieldDescriptor,
+    rtb: RuntimeTypeBox,
+    /// Encoded elements: content of length-delimited and group values,
+    /// encoded varint or fixed-width value otherwise.
+    elements: Vec<&'a [u8]>,
+}
+
+impl<'a> fmt::Debug for LazyRepeatedView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LazyRepeatedView")
+            .field("field", &self.field.get_name())
+            .field("len", &self.elements.len())
+            .finish()
+    }
+}
+
+impl<'a> LazyRepeatedView<'a> {
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Field has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Element type.
+    pub fn element_type(&self) -> RuntimeTypeBox {
+        self.rtb.clone()
+    }
+
+    /// Decode element by index.
+    ///
+    /// # Panics
+    ///
+    /// If index is out of bounds.
+    pub fn get(&self, index: usize) -> ProtobufResult<ReflectValueBox> {
+        read_value(&self.field, &self.rtb, self.elements[index])
+    }
+
+    /// View of message element by index.
+    ///
+    /// # Panics
+    ///
+    /// If index is out of bounds or elements are not messages.
+    pub fn get_message(&self, index: usize) -> LazyMessageView<'a> {
+        match &self.rtb {
+            RuntimeTypeBox::Message(m) => LazyMessageView::new(m, self.elements[index]),
+            _ => panic!("not a repeated message field: {}", self.field),
+        }
+    }
+
+    /// Decode all elements.
+    pub fn iter<'b>(&'b self) -> impl Iterator<Item = ProtobufResult<ReflectValueBox>> + 'b {
+        (0..self.len()).map(move |i| self.get(i))
+    }
+}
+
+/// Wire type of unpacked value of given type.
+fn wire_type_for(field_type: Type) -> WireType {
+    match field_type {
+        Type::TYPE_DOUBLE | Type::TYPE_FIXED64 | Type::TYPE_SFIXED64 => WireType::WireTypeFixed64,
+        Type::TYPE_FLOAT | Type::TYPE_FIXED32 | Type::TYPE_SFIXED32 => WireType::WireTypeFixed32,
+        Type::TYPE_INT32
+        | Type::TYPE_INT64
+        | Type::TYPE_UINT32
+        | Type::TYPE_UINT64
+        | Type::TYPE_SINT32
+        | Type::TYPE_SINT64
+        | Type::TYPE_BOOL
+        | Type::TYPE_ENUM => WireType::WireTypeVarint,
+        Type::TYPE_STRING | Type::TYPE_BYTES | Type::TYPE_MESSAGE => {
+            WireType::WireTypeLengthDelimited
+        }
+        Type::TYPE_GROUP => WireType::WireTypeStartGroup,
+    }
+}
+
+fn is_packable(wire_type: WireType) -> bool {
+    matches!(
+        wire_type,
+        WireType::WireTypeVarint | WireType::WireTypeFixed32 | WireType::WireTypeFixed64
+    )
+}
+
+/// Split content of packed field into encoded elements.
+fn split_packed<'a>(
+    wire_type: WireType,
+    mut bytes: &'a [u8],
+    elements: &mut Vec<&'a [u8]>,
+) -> ProtobufResult<()> {
+    while !bytes.is_empty() {
+        let len = match wire_type {
+            WireType::WireTypeFixed32 => 4,
+            WireType::WireTypeFixed64 => 8,
+            _ => match bytes.iter().position(|b| b & 0x80 == 0) {
+                Some(i) if i < 10 => i + 1,
+                Some(_) => return Err(ProtobufError::WireError(WireError::IncorrectVarint)),
+                None => return Err(ProtobufError::WireError(WireError::UnexpectedEof)),
+            },
+        };
+        if len > bytes.len() {
+            return Err(ProtobufError::WireError(WireError::UnexpectedEof));
+        }
+        elements.push(&bytes[..len]);
+        bytes = &bytes[len..];
+    }
+    Ok(())
+}
+
+/// Decode value of field from wire value without tag and length.
+fn read_value(
+    field: &FieldDescriptor,
+    rtb: &RuntimeTypeBox,
+    bytes: &[u8],
+) -> ProtobufResult<ReflectValueBox> {
+    match rtb {
+        RuntimeTypeBox::Message(m) => {
+            // Group content is parsed like a message, end tag is not included
+            let mut message = m.new_instance();
+            message.merge_from_bytes_dyn(bytes)?;
+            Ok(ReflectValueBox::Message(message))
+        }
+        RuntimeTypeBox::String => match std::str::from_utf8(bytes) {
+            Ok(s) => Ok(ReflectValueBox::String(s.to_owned())),
+            Err(_) => Err(ProtobufError::WireError(WireError::Utf8Error)),
+        },
+        RuntimeTypeBox::VecU8 => Ok(ReflectValueBox::Bytes(bytes.to_vec())),
+        _ => {
+            let mut is = CodedInputStream::from_bytes(bytes);
+            read_singular(
+                &mut is,
+                field.get_proto().get_field_type(),
+                rtb,
+                field.get_proto().get_number() as u32,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LazyMessageView;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::hex::decode_hex;
+    use crate::reflect::MessageDescriptor;
+    use crate::reflect::ReflectValueBox;
+    use crate::Message;
+
+    fn descriptor() -> MessageDescriptor {
+        FileDescriptorProto::descriptor_static()
+    }
+
+    #[test]
+    fn singular() {
+        let d = descriptor();
+        // name = "a", package = "p", name = "b"
+        let bytes = decode_hex("0a 01 61 12 01 70 0a 01 62");
+        let view = LazyMessageView::new(&d, &bytes);
+
+        let name = d.get_field_by_name("name").unwrap();
+        let syntax = d.get_field_by_name("syntax").unwrap();
+        assert!(view.has_field(&name).unwrap());
+        assert!(!view.has_field(&syntax).unwrap());
+        assert_eq!(
+            Some(ReflectValueBox::String("b".to_owned())),
+            view.get_singular(&name).unwrap()
+        );
+        assert_eq!(None, view.get_singular(&syntax).unwrap());
+    }
+
+    #[test]
+    fn repeated_packed_and_unpacked() {
+        let d = descriptor();
+        // public_dependency = 1, packed public_dependency = [2, 300], public_dependency = 4
+        let bytes = decode_hex("50 01 52 03 02 ac 02 50 04");
+        let view = LazyMessageView::new(&d, &bytes);
+
+        let public_dependency = d.get_field_by_name("public_dependency").unwrap();
+        let repeated = view.get_repeated(&public_dependency).unwrap();
+        assert_eq!(4, repeated.len());
+        assert_eq!(ReflectValueBox::I32(300), repeated.get(2).unwrap());
+        let all: Vec<_> = repeated.iter().map(|v| v.unwrap()).collect();
+        assert_eq!(
+            vec![
+                ReflectValueBox::I32(1),
+                ReflectValueBox::I32(2),
+                ReflectValueBox::I32(300),
+                ReflectValueBox::I32(4),
+            ],
+            all
+        );
+
+        let dependency = d.get_field_by_name("dependency").unwrap();
+        assert!(view.get_repeated(&dependency).unwrap().is_empty());
+    }
+
+    #[test]
+    fn nested_messages() {
+        let d = descriptor();
+        // message_type { name = "A" }, message_type { name = "B", field { number = 3 } }
+        let bytes = decode_hex("22 03 0a 01 41 22 07 0a 01 42 12 02 18 03");
+        let view = LazyMessageView::new(&d, &bytes);
+
+        let message_type = d.get_field_by_name("message_type").unwrap();
+        let messages = view.get_repeated(&message_type).unwrap();
+        assert_eq!(2, messages.len());
+
+        let b = messages.get_message(1);
+        let message_d = b.descriptor().clone();
+        let name = message_d.get_field_by_name("name").unwrap();
+        assert_eq!(
+            Some(ReflectValueBox::String("B".to_owned())),
+            b.get_singular(&name).unwrap()
+        );
+        let field = message_d.get_field_by_name("field").unwrap();
+        let fields = b.get_repeated(&field).unwrap();
+        let field_d = fields.get_message(0).descriptor().clone();
+        let number = field_d.get_field_by_name("number").unwrap();
+        assert_eq!(
+            Some(ReflectValueBox::I32(3)),
+            fields.get_message(0).get_singular(&number).unwrap()
+        );
+    }
+
+    #[test]
+    fn singular_message_same_as_parsed() {
+        let d = descriptor();
+        // options { java_package = "a" }, options { java_outer_classname = "b" }
+        let bytes = decode_hex("42 03 0a 01 61 42 03 42 01 62");
+        let view = LazyMessageView::new(&d, &bytes);
+
+        let options = d.get_field_by_name("options").unwrap();
+        let options_view = view.get_message(&options).unwrap().unwrap();
+        let options_d = options_view.descriptor().clone();
+        let java_package = options_d.get_field_by_name("java_package").unwrap();
+        let java_outer_classname = options_d.get_field_by_name("java_outer_classname").unwrap();
+        assert!(!options_view.has_field(&java_package).unwrap());
+        assert!(options_view.has_field(&java_outer_classname).unwrap());
+
+        let parsed = FileDescriptorProto::parse_from_bytes(&bytes).unwrap();
+        let decoded = view.get_singular(&options).unwrap().unwrap();
+        assert_eq!(
+            ReflectValueBox::Message(Box::new(parsed.options.clone().unwrap())),
+            decoded
+        );
+        assert_eq!(
+            parsed.write_to_bytes().unwrap(),
+            view.to_message().unwrap().write_to_bytes_dyn().unwrap()
+        );
+    }
+
+    #[test]
+    fn wrong_wire_type() {
+        let d = descriptor();
+        // name as varint
+        let bytes = decode_hex("08 01");
+        let view = LazyMessageView::new(&d, &bytes);
+        let name = d.get_field_by_name("name").unwrap();
+        assert!(view.get_singular(&name).is_err());
+    }
+
+    #[test]
+    fn malformed_is_error() {
+        let d = descriptor();
+        let bytes = decode_hex("0a 05 61");
+        let view = LazyMessageView::new(&d, &bytes);
+        let name = d.get_field_by_name("name").unwrap();
+        assert!(view.has_field(&name).is_err());
+    }
+}
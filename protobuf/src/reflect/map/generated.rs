@@ -65,12 +65,4 @@ impl<'a, K: ProtobufValue + Eq + Hash, V: ProtobufValue> ReflectMapIterTrait<'a>
             None => None,
         }
     }
-
-    fn key_type(&self) -> RuntimeTypeBox {
-        K::runtime_type_box()
-    }
-
-    fn value_type(&self) -> RuntimeTypeBox {
-        V::runtime_type_box()
-    }
 }
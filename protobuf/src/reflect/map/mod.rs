@@ -1,7 +1,11 @@
 use crate::reflect::reflect_eq::ReflectEq;
 use crate::reflect::reflect_eq::ReflectEqMode;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::ReflectError;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::ReflectValueRef;
+use crate::reflect::RuntimeFieldType;
 use crate::reflect::RuntimeTypeBox;
 
 mod generated;
@@ -27,8 +31,6 @@ pub(crate) trait ReflectMap: Send + Sync + 'static {
 
 pub(crate) trait ReflectMapIterTrait<'a> {
     fn next(&mut self) -> Option<(ReflectValueRef<'a>, ReflectValueRef<'a>)>;
-    fn key_type(&self) -> RuntimeTypeBox;
-    fn value_type(&self) -> RuntimeTypeBox;
 }
 
 pub struct ReflectMapIter<'a> {
@@ -58,10 +60,26 @@ impl<'a> IntoIterator for &'a dyn ReflectMap {
     }
 }
 
+/// Iterator over map of a field which is not yet initialized in dynamic message
+struct DynamicEmptyMapIter;
+
+impl<'a> ReflectMapIterTrait<'a> for DynamicEmptyMapIter {
+    fn next(&mut self) -> Option<(ReflectValueRef<'a>, ReflectValueRef<'a>)> {
+        None
+    }
+}
+
+#[derive(Copy, Clone)]
+enum ReflectMapRefImpl<'a> {
+    Generated(&'a dyn ReflectMap),
+    /// Field with given index which is not yet initialized in dynamic message
+    DynamicEmpty(&'a MessageDescriptor, usize),
+}
+
 /// Dynamic reference to `map` field
 #[derive(Copy, Clone)]
 pub struct ReflectMapRef<'a> {
-    map: &'a dyn ReflectMap,
+    imp: ReflectMapRefImpl<'a>,
 }
 
 /// Dynamic mutable reference to `map` field
@@ -69,34 +87,68 @@ pub struct ReflectMapMut<'a> {
     map: &'a mut dyn ReflectMap,
 }
 
+fn empty_map_types(message: &MessageDescriptor, index: usize) -> (RuntimeTypeBox, RuntimeTypeBox) {
+    let field = FieldDescriptor {
+        message_descriptor: message.clone(),
+        index,
+    };
+    match field.runtime_field_type() {
+        RuntimeFieldType::Map(key, value) => (key, value),
+        _ => unreachable!("not a map field: {}", field),
+    }
+}
+
 impl<'a> ReflectMapRef<'a> {
     pub(crate) fn new(map: &'a dyn ReflectMap) -> ReflectMapRef<'a> {
-        ReflectMapRef { map }
+        ReflectMapRef {
+            imp: ReflectMapRefImpl::Generated(map),
+        }
+    }
+
+    pub(crate) fn new_empty(message: &'a MessageDescriptor, index: usize) -> ReflectMapRef<'a> {
+        ReflectMapRef {
+            imp: ReflectMapRefImpl::DynamicEmpty(message, index),
+        }
     }
 
     /// Size of the map
     pub fn len(&self) -> usize {
-        self.map.len()
+        match self.imp {
+            ReflectMapRefImpl::Generated(map) => map.len(),
+            ReflectMapRefImpl::DynamicEmpty(..) => 0,
+        }
     }
 
     /// Is map empty?
     pub fn is_empty(&self) -> bool {
-        self.map.is_empty()
+        match self.imp {
+            ReflectMapRefImpl::Generated(map) => map.is_empty(),
+            ReflectMapRefImpl::DynamicEmpty(..) => true,
+        }
     }
 
     /// Find a value by given key.
-    pub fn get(&self, key: ReflectValueRef) -> Option<ReflectValueRef> {
-        self.map.get(key)
+    pub fn get(&self, key: ReflectValueRef) -> Option<ReflectValueRef<'a>> {
+        match self.imp {
+            ReflectMapRefImpl::Generated(map) => map.get(key),
+            ReflectMapRefImpl::DynamicEmpty(..) => None,
+        }
     }
 
     /// Map key type
     pub fn key_type(&self) -> RuntimeTypeBox {
-        self.map.key_type()
+        match self.imp {
+            ReflectMapRefImpl::Generated(map) => map.key_type(),
+            ReflectMapRefImpl::DynamicEmpty(message, index) => empty_map_types(message, index).0,
+        }
     }
 
     /// Map value type
     pub fn value_type(&self) -> RuntimeTypeBox {
-        self.map.value_type()
+        match self.imp {
+            ReflectMapRefImpl::Generated(map) => map.value_type(),
+            ReflectMapRefImpl::DynamicEmpty(message, index) => empty_map_types(message, index).1,
+        }
     }
 }
 
@@ -129,7 +181,7 @@ impl<'a> ReflectMapMut<'a> {
     }
 
     fn as_ref(&'a self) -> ReflectMapRef<'a> {
-        ReflectMapRef { map: self.map }
+        ReflectMapRef::new(self.map)
     }
 
     /// Map key type
@@ -162,10 +214,26 @@ impl<'a> ReflectMapMut<'a> {
     /// # Panics
     ///
     /// If given key has an incompatible key type.
+    ///
+    /// Numbers not declared in closed enum are stored as is.
     pub fn insert(&mut self, key: ReflectValueBox, value: ReflectValueBox) {
         self.map.insert(key, value)
     }
 
+    /// Fallible version of [`insert`](ReflectMapMut::insert).
+    ///
+    /// Also fail if value is a number not declared in closed enum.
+    pub fn try_insert(
+        &mut self,
+        key: ReflectValueBox,
+        value: ReflectValueBox,
+    ) -> Result<(), ReflectError> {
+        ReflectError::check_value(&self.key_type(), &key)?;
+        ReflectError::check_value(&self.value_type(), &value)?;
+        self.map.insert(key, value);
+        Ok(())
+    }
+
     /// Clear
     pub fn clear(&mut self) {
         self.map.clear();
@@ -177,16 +245,6 @@ pub struct ReflectMapRefIter<'a> {
     iter: ReflectMapIter<'a>,
 }
 
-impl<'a> ReflectMapRefIter<'a> {
-    fn _key_type(&self) -> RuntimeTypeBox {
-        self.iter.imp.key_type()
-    }
-
-    fn _value_type(&self) -> RuntimeTypeBox {
-        self.iter.imp.value_type()
-    }
-}
-
 impl<'a> Iterator for ReflectMapRefIter<'a> {
     type Item = (ReflectValueRef<'a>, ReflectValueRef<'a>);
 
@@ -201,7 +259,10 @@ impl<'a, 'b> IntoIterator for &'b ReflectMapRef<'a> {
 
     fn into_iter(self) -> ReflectMapRefIter<'a> {
         ReflectMapRefIter {
-            iter: self.map.reflect_iter(),
+            iter: match self.imp {
+                ReflectMapRefImpl::Generated(map) => map.reflect_iter(),
+                ReflectMapRefImpl::DynamicEmpty(..) => ReflectMapIter::new(DynamicEmptyMapIter),
+            },
         }
     }
 }
@@ -0,0 +1,262 @@
+//! Conversion of map fields to repeated entry messages and back.
+//!
+//! On the wire map field `map<K, V> m = 1` is the same as
+//! `repeated MEntry m = 1` where `MEntry` is a synthesized message
+//! with fields `K key = 1` and `V value = 2`.
+
+use std::collections::HashMap;
+use std::iter;
+use std::sync::Mutex;
+
+use crate::lazy_v2::LazyV2;
+use crate::message_dyn::MessageDyn;
+use crate::reflect::pool_clone::clone_value;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::FileDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::ReflectError;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::ReflectValueRef;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+
+/// Entry message type of a map field, always dynamic.
+///
+/// `None` if field is not a map field.
+pub(crate) fn entry_descriptor(field: &FieldDescriptor) -> Option<MessageDescriptor> {
+    if !field.is_map() {
+        return None;
+    }
+    // Map entry is nested in the message containing map field,
+    // but this is not validated when building descriptors,
+    // so search everywhere the entry type could have been resolved from.
+    let type_name = field.get_proto().get_type_name();
+    let file = field.file();
+    let (file, entry) = iter::once(file)
+        .chain(file.dependencies())
+        .filter_map(|f| f.message_by_full_name(type_name).map(|m| (f, m)))
+        .next()?;
+    if entry.is_dynamic() {
+        return Some(entry);
+    }
+    dynamic_copy(file).message_by_full_name(type_name)
+}
+
+/// Dynamic copy of generated file, created once per file, so map entry
+/// descriptors of generated messages are equal between calls.
+///
+/// Generated map entries have no Rust types, so they can only be instantiated
+/// as dynamic messages.
+///
+/// Copies are keyed by file identity, and only generated files,
+/// which are never freed, are copied, so the cache is bounded
+/// by the number of generated files.
+fn dynamic_copy(file: &FileDescriptor) -> FileDescriptor {
+    static COPIES: LazyV2<Mutex<HashMap<FileDescriptor, FileDescriptor>>> = LazyV2::INIT;
+    let mut copies = COPIES.get(Default::default).lock().unwrap();
+    copies
+        .entry(file.clone())
+        .or_insert_with(|| {
+            FileDescriptor::new_dynamic(file.proto().clone(), file.dependencies().to_vec())
+        })
+        .clone()
+}
+
+fn singular_type(field: &FieldDescriptor) -> RuntimeTypeBox {
+    match field.runtime_field_type() {
+        RuntimeFieldType::Singular(t) => t,
+        _ => unreachable!(),
+    }
+}
+
+pub(crate) fn map_to_entries(
+    field: &FieldDescriptor,
+    m: &dyn MessageDyn,
+) -> Result<Vec<Box<dyn MessageDyn>>, ReflectError> {
+    let entry =
+        entry_descriptor(field).ok_or_else(|| ReflectError::wrong_field_kind(field, "map"))?;
+    let map = field.try_get_map(m)?;
+    let key_field = entry_field(&entry, 1)?;
+    let value_field = entry_field(&entry, 2)?;
+    let (key_type, value_type) = (singular_type(&key_field), singular_type(&value_field));
+    (&map)
+        .into_iter()
+        .map(|(k, v)| {
+            let mut e = entry.new_instance();
+            key_field.try_set_singular_field(&mut *e, clone_to(k, &key_type)?)?;
+            value_field.try_set_singular_field(&mut *e, clone_to(v, &value_type)?)?;
+            Ok(e)
+        })
+        .collect()
+}
+
+/// Copy value converting messages to the target descriptor pool.
+fn clone_to(
+    value: ReflectValueRef,
+    target: &RuntimeTypeBox,
+) -> Result<ReflectValueBox, ReflectError> {
+    let actual = value.get_type();
+    clone_value(value, target).ok_or_else(|| ReflectError::WrongValueType {
+        expected: target.to_string(),
+        actual: actual.to_string(),
+    })
+}
+
+fn entry_field(entry: &MessageDescriptor, number: u32) -> Result<FieldDescriptor, ReflectError> {
+    entry
+        .get_field_by_number(number)
+        .ok_or_else(|| ReflectError::NoSuchField {
+            message: entry.full_name().to_owned(),
+            field: number.to_string(),
+        })
+}
+
+pub(crate) fn map_from_entries(
+    field: &FieldDescriptor,
+    m: &mut dyn MessageDyn,
+    entries: &[Box<dyn MessageDyn>],
+) -> Result<(), ReflectError> {
+    let (key_type, value_type) = match field.runtime_field_type() {
+        RuntimeFieldType::Map(k, v) => (k, v),
+        _ => return Err(ReflectError::wrong_field_kind(field, "map")),
+    };
+    let entry_name = field.get_proto().get_type_name().trim_start_matches('.');
+
+    let mut entry_values = Vec::with_capacity(entries.len());
+    for e in entries {
+        let descriptor = e.descriptor_dyn();
+        if entry_name != descriptor.full_name() {
+            return Err(ReflectError::WrongValueType {
+                expected: entry_name.to_owned(),
+                actual: descriptor.full_name().to_owned(),
+            });
+        }
+        let key = entry_field(&descriptor, 1)?;
+        let value = entry_field(&descriptor, 2)?;
+        entry_values.push((
+            clone_to(key.get_singular_field_or_default(&**e), &key_type)?,
+            clone_to(value.get_singular_field_or_default(&**e), &value_type)?,
+        ));
+    }
+
+    let mut map = field.try_mut_map(m)?;
+    map.clear();
+    for (key, value) in entry_values {
+        map.insert(key, value);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::reflect::ReflectEqMode;
+    use crate::well_known_types::Struct;
+    use crate::well_known_types::Value;
+    use crate::Message;
+
+    fn structure() -> Struct {
+        let mut s = Struct::new();
+        let mut a = Value::new();
+        a.set_number_value(1.0);
+        s.fields.insert("a".to_owned(), a);
+        let mut b = Value::new();
+        b.set_string_value("b".to_owned());
+        s.fields.insert("b".to_owned(), b);
+        s
+    }
+
+    #[test]
+    fn generated() {
+        let field = Struct::descriptor_static()
+            .get_field_by_name("fields")
+            .unwrap();
+        let entry = field.map_entry_descriptor().unwrap();
+        assert_eq!("google.protobuf.Struct.FieldsEntry", entry.full_name());
+        assert!(entry.is_map_entry());
+        assert!(Value::descriptor_static()
+            .get_field_by_name("string_value")
+            .unwrap()
+            .map_entry_descriptor()
+            .is_none());
+
+        let s = structure();
+        let mut entries = field.map_to_entries(&s).unwrap();
+        entries.sort_by_key(|e| e.write_to_bytes_dyn().unwrap());
+        let key = entry.get_field_by_name("key").unwrap();
+        assert_eq!(
+            vec![ReflectValueRef::String("a"), ReflectValueRef::String("b")],
+            entries
+                .iter()
+                .map(|e| key.get_singular_field_or_default(&**e))
+                .collect::<Vec<_>>()
+        );
+
+        // Entries are serialized as a repeated field
+        let bytes: Vec<u8> = entries
+            .iter()
+            .flat_map(|e| {
+                let mut r = vec![0x0a];
+                r.extend(e.write_length_delimited_to_bytes_dyn().unwrap());
+                r
+            })
+            .collect();
+        assert_eq!(s, Struct::parse_from_bytes(&bytes).unwrap());
+
+        let mut copy = Struct::new();
+        copy.fields.insert("c".to_owned(), Value::new());
+        field.map_from_entries(&mut copy, &entries).unwrap();
+        assert_eq!(s, copy);
+    }
+
+    #[test]
+    fn duplicate_keys() {
+        let field = Struct::descriptor_static()
+            .get_field_by_name("fields")
+            .unwrap();
+        let entry = field.map_entry_descriptor().unwrap();
+        let value = entry.get_field_by_name("value").unwrap();
+        let mut entries = field.map_to_entries(&structure()).unwrap();
+        for e in &mut entries {
+            // Missing key is default
+            entry
+                .get_field_by_name("key")
+                .unwrap()
+                .clear_field(&mut **e);
+        }
+        let last = value.get_message(&*entries[1]).clone_box();
+
+        let mut s = Struct::new();
+        field.map_from_entries(&mut s, &entries).unwrap();
+        assert_eq!(1, s.fields.len());
+        // Last entry wins
+        assert!(last.reflect_eq_dyn(&s.fields[""], &ReflectEqMode::default()));
+    }
+
+    #[test]
+    fn errors() {
+        let fields = Struct::descriptor_static()
+            .get_field_by_name("fields")
+            .unwrap();
+        let string_value = Value::descriptor_static()
+            .get_field_by_name("string_value")
+            .unwrap();
+        assert!(matches!(
+            string_value.map_to_entries(&Value::new()),
+            Err(ReflectError::WrongFieldKind { .. })
+        ));
+        assert!(matches!(
+            fields.map_to_entries(&Value::new()),
+            Err(ReflectError::WrongMessageType { .. })
+        ));
+
+        // Entries of a different type leave the map unchanged
+        let mut s = structure();
+        let entries: Vec<Box<dyn MessageDyn>> = vec![Box::new(Value::new())];
+        assert!(matches!(
+            fields.map_from_entries(&mut s, &entries),
+            Err(ReflectError::WrongValueType { .. })
+        ));
+        assert_eq!(structure(), s);
+    }
+}
@@ -0,0 +1,472 @@
+//! Reflective merge with customizable per-field semantics.
+//!
+//! [`merge_with_policy`] merges one message into another following protobuf
+//! merge semantics: set singular fields replace fields of the destination,
+//! singular message fields are merged recursively, repeated fields are concatenated
+//! and map entries are inserted. A [`MergePolicy`] may override how values of particular fields are combined,
+//! for example, to keep the maximum of two counters or a union of two sets:
+//!
+//! ```
+//! # use protobuf::reflect::FieldDescriptor;
+//! # use protobuf::reflect::ReflectRepeatedRef;
+//! # use protobuf::reflect::ReflectValueBox;
+//! # use protobuf::reflect::ReflectValueRef;
+//! use protobuf::reflect::merge::*;
+//!
+//! struct CounterPolicy;
+//!
+//! impl MergePolicy for CounterPolicy {
+//!     fn merge_singular(
+//!         &self,
+//!         field: &FieldDescriptor,
+//!         dst: ReflectValueRef,
+//!         src: ReflectValueRef,
+//!     ) -> Option<ReflectValueBox> {
+//!         match field.get_name() {
+//!             "count" => max_value(dst, src),
+//!             _ => None,
+//!         }
+//!     }
+//!
+//!     fn merge_repeated(
+//!         &self,
+//!         field: &FieldDescriptor,
+//!         dst: &ReflectRepeatedRef,
+//!         src: &ReflectRepeatedRef,
+//!     ) -> Option<Vec<ReflectValueBox>> {
+//!         match field.get_name() {
+//!             "tags" => Some(union_repeated(dst, src)),
+//!             _ => None,
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::reflect::has_explicit_presence;
+use crate::reflect::pool_clone;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectMapRef;
+use crate::reflect::ReflectRepeatedRef;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::ReflectValueRef;
+use crate::MessageDyn;
+use crate::UnknownValue;
+use crate::UnknownValueRef;
+
+/// Customizes how [`merge_with_policy`] combines values of fields.
+///
+/// Returning `None` from a function means protobuf merge semantics
+/// for that field. Default implementations always return `None`.
+pub trait MergePolicy {
+    /// Combine values of a singular field set in both messages.
+    ///
+    /// Also called for values of map entries with the same key in both messages.
+    ///
+    /// By default singular message fields are merged recursively
+    /// with the same policy, other values, including map values, are replaced with `src`.
+    fn merge_singular(
+        &self,
+        _field: &FieldDescriptor,
+        _dst: ReflectValueRef,
+        _src: ReflectValueRef,
+    ) -> Option<ReflectValueBox> {
+        None
+    }
+
+    /// Combine elements of a repeated field non-empty in `src` message.
+    ///
+    /// Returned elements replace elements of `dst`.
+    /// By default elements of `src` are appended to elements of `dst`.
+    fn merge_repeated(
+        &self,
+        _field: &FieldDescriptor,
+        _dst: &ReflectRepeatedRef,
+        _src: &ReflectRepeatedRef,
+    ) -> Option<Vec<ReflectValueBox>> {
+        None
+    }
+}
+
+/// Policy with protobuf merge semantics for all fields.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultMergePolicy;
+
+impl MergePolicy for DefaultMergePolicy {}
+
+/// Merge fields of `src` message into `dst` message combining values
+/// according to the `policy`.
+///
+/// Unknown fields of `src` are appended to unknown fields of `dst`.
+///
+/// # Panics
+///
+/// If messages are of different types.
+pub fn merge_with_policy(dst: &mut dyn MessageDyn, src: &dyn MessageDyn, policy: &dyn MergePolicy) {
+    let descriptor = dst.descriptor_dyn();
+    assert_eq!(
+        descriptor,
+        src.descriptor_dyn(),
+        "merging messages of different types"
+    );
+
+    for field in descriptor.fields() {
+        match field.get_reflect(src) {
+            ReflectFieldRef::Optional(None) => {}
+            // Dynamic messages may store default values of fields without presence,
+            // these are not merged like they are not serialized
+            ReflectFieldRef::Optional(Some(src_value))
+                if !has_explicit_presence(&field) && !src_value.is_non_zero() => {}
+            ReflectFieldRef::Optional(Some(src_value)) => {
+                merge_singular(dst, &field, src_value, policy)
+            }
+            ReflectFieldRef::Repeated(src_repeated) => {
+                if !src_repeated.is_empty() {
+                    merge_repeated(dst, &field, src_repeated, policy);
+                }
+            }
+            ReflectFieldRef::Map(src_map) => merge_map(dst, &field, src_map, policy),
+        }
+    }
+
+    for (number, values) in src.get_unknown_fields_dyn() {
+        for value in values {
+            let value = match value {
+                UnknownValueRef::Fixed32(v) => UnknownValue::Fixed32(v),
+                UnknownValueRef::Fixed64(v) => UnknownValue::Fixed64(v),
+                UnknownValueRef::Varint(v) => UnknownValue::Varint(v),
+                UnknownValueRef::LengthDelimited(v) => UnknownValue::LengthDelimited(v.to_vec()),
+            };
+            dst.mut_unknown_fields_dyn().add_value(number, value);
+        }
+    }
+    for (number, groups) in src.get_unknown_fields_dyn().iter_groups() {
+        for contents in groups {
+            dst.mut_unknown_fields_dyn()
+                .add_group(number, contents.clone());
+        }
+    }
+}
+
+/// Implementation of [`MessageDyn::merge_from_message_dyn`](crate::MessageDyn).
+pub(crate) fn merge_from_message(dst: &mut dyn MessageDyn, src: &dyn MessageDyn) {
+    let descriptor = dst.descriptor_dyn();
+    let src_descriptor = src.descriptor_dyn();
+    if descriptor == src_descriptor {
+        return merge_with_policy(dst, src, &DefaultMergePolicy);
+    }
+
+    assert_eq!(
+        descriptor.full_name(),
+        src_descriptor.full_name(),
+        "merging messages of different types"
+    );
+    let src = pool_clone::clone_message(src, &descriptor)
+        .expect("merging messages with different fields");
+    merge_with_policy(dst, &*src, &DefaultMergePolicy)
+}
+
+fn merge_singular(
+    dst: &mut dyn MessageDyn,
+    field: &FieldDescriptor,
+    src_value: ReflectValueRef,
+    policy: &dyn MergePolicy,
+) {
+    let merged = match field.get_singular(dst) {
+        Some(dst_value) => policy.merge_singular(field, dst_value, src_value.clone()),
+        None => None,
+    };
+    match (merged, src_value) {
+        (Some(merged), _) => field.set_singular_field(dst, merged),
+        (None, ReflectValueRef::Message(src_message)) if field.has_field(dst) => {
+            merge_with_policy(field.mut_message(dst), &*src_message, policy)
+        }
+        (None, src_value) => field.set_singular_field(dst, src_value.to_box()),
+    }
+}
+
+fn merge_repeated(
+    dst: &mut dyn MessageDyn,
+    field: &FieldDescriptor,
+    src_repeated: ReflectRepeatedRef,
+    policy: &dyn MergePolicy,
+) {
+    let merged = policy.merge_repeated(field, &field.get_repeated(dst), &src_repeated);
+    let mut dst_repeated = field.mut_repeated(dst);
+    match merged {
+        Some(merged) => {
+            dst_repeated.clear();
+            for value in merged {
+                dst_repeated.push(value);
+            }
+        }
+        None => {
+            for value in &src_repeated {
+                dst_repeated.push(value.to_box());
+            }
+        }
+    }
+}
+
+fn merge_map(
+    dst: &mut dyn MessageDyn,
+    field: &FieldDescriptor,
+    src_map: ReflectMapRef,
+    policy: &dyn MergePolicy,
+) {
+    for (key, src_value) in &src_map {
+        let merged = {
+            let dst_map = field.get_map(dst);
+            match dst_map.get(key.clone()) {
+                Some(dst_value) => policy.merge_singular(field, dst_value, src_value.clone()),
+                None => None,
+            }
+        };
+        let value = merged.unwrap_or_else(|| src_value.to_box());
+        field.mut_map(dst).insert(key.to_box(), value);
+    }
+}
+
+/// Larger of two numeric values of the same type, `None` for other values.
+///
+/// If floating point values are not comparable (i. e. one is NaN), `a` is returned.
+pub fn max_value(a: ReflectValueRef, b: ReflectValueRef) -> Option<ReflectValueBox> {
+    use self::ReflectValueRef::*;
+    let b_is_larger = match (&a, &b) {
+        (U32(a), U32(b)) => b > a,
+        (U64(a), U64(b)) => b > a,
+        (I32(a), I32(b)) => b > a,
+        (I64(a), I64(b)) => b > a,
+        (F32(a), F32(b)) => b > a,
+        (F64(a), F64(b)) => b > a,
+        _ => return None,
+    };
+    Some(if b_is_larger { b.to_box() } else { a.to_box() })
+}
+
+/// Elements of `dst` followed by elements of `src` not present in `dst`.
+pub fn union_repeated(dst: &ReflectRepeatedRef, src: &ReflectRepeatedRef) -> Vec<ReflectValueBox> {
+    let mut r: Vec<ReflectValueBox> = dst.into_iter().map(|v| v.to_box()).collect();
+    for value in src {
+        if !r.iter().any(|v| *v == value) {
+            r.push(value.to_box());
+        }
+    }
+    r
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::FieldDescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::reflect::FileDescriptor;
+    use crate::reflect::MessageDescriptor;
+    use crate::well_known_types::DoubleValue;
+    use crate::well_known_types::Struct;
+    use crate::well_known_types::Value;
+    use crate::Message;
+
+    struct TestPolicy;
+
+    impl MergePolicy for TestPolicy {
+        fn merge_singular(
+            &self,
+            field: &FieldDescriptor,
+            dst: ReflectValueRef,
+            src: ReflectValueRef,
+        ) -> Option<ReflectValueBox> {
+            match field.get_name() {
+                "number" => max_value(dst, src),
+                "fields" => Some(dst.to_box()),
+                _ => None,
+            }
+        }
+
+        fn merge_repeated(
+            &self,
+            field: &FieldDescriptor,
+            dst: &ReflectRepeatedRef,
+            src: &ReflectRepeatedRef,
+        ) -> Option<Vec<ReflectValueBox>> {
+            match field.get_name() {
+                "dependency" => Some(union_repeated(dst, src)),
+                _ => None,
+            }
+        }
+    }
+
+    fn file(dependencies: &[&str], public_dependencies: &[i32]) -> FileDescriptorProto {
+        let mut file = FileDescriptorProto::new();
+        file.dependency = dependencies.iter().map(|d| d.to_string()).collect();
+        file.public_dependency = public_dependencies.to_vec();
+        file
+    }
+
+    #[test]
+    fn default() {
+        let mut a = file(&["a", "b"], &[0]);
+        a.set_name("a.proto".to_owned());
+        a.options.mut_or_default().set_java_package("a".to_owned());
+        let mut b = file(&["b", "c"], &[1]);
+        b.set_package("b".to_owned());
+        b.options.mut_or_default().set_go_package("b".to_owned());
+        b.mut_unknown_fields().add_varint(1000, 7);
+
+        let mut expected = file(&["a", "b", "b", "c"], &[0, 1]);
+        expected.set_name("a.proto".to_owned());
+        expected.set_package("b".to_owned());
+        expected
+            .options
+            .mut_or_default()
+            .set_java_package("a".to_owned());
+        expected
+            .options
+            .mut_or_default()
+            .set_go_package("b".to_owned());
+        expected.mut_unknown_fields().add_varint(1000, 7);
+
+        merge_with_policy(&mut a, &b, &DefaultMergePolicy);
+        assert_eq!(expected, a);
+    }
+
+    #[test]
+    fn custom_singular() {
+        let mut a = FieldDescriptorProto::new();
+        a.set_name("a".to_owned());
+        a.set_number(5);
+        a.options.mut_or_default().set_deprecated(true);
+        let mut b = FieldDescriptorProto::new();
+        b.set_number(3);
+        b.set_json_name("j".to_owned());
+        b.options.mut_or_default().set_packed(true);
+
+        merge_with_policy(&mut a, &b, &TestPolicy);
+        assert_eq!("a", a.get_name());
+        assert_eq!(5, a.get_number());
+        assert_eq!("j", a.get_json_name());
+        assert!(a.options.get_or_default().get_deprecated());
+        assert!(a.options.get_or_default().get_packed());
+
+        b.set_number(10);
+        merge_with_policy(&mut a, &b, &TestPolicy);
+        assert_eq!(10, a.get_number());
+    }
+
+    #[test]
+    fn custom_repeated() {
+        let mut a = file(&["a", "b"], &[0]);
+        let b = file(&["b", "c"], &[1]);
+        merge_with_policy(&mut a, &b, &TestPolicy);
+        assert_eq!(vec!["a", "b", "c"], a.dependency);
+        assert_eq!(vec![0, 1], a.public_dependency);
+    }
+
+    #[test]
+    fn custom_map() {
+        fn value(number: f64) -> Value {
+            let mut value = Value::new();
+            value.set_number_value(number);
+            value
+        }
+
+        let mut a = Struct::new();
+        a.fields.insert("a".to_owned(), value(1.0));
+        a.fields.insert("b".to_owned(), value(2.0));
+        let mut b = Struct::new();
+        b.fields.insert("b".to_owned(), value(5.0));
+        b.fields.insert("c".to_owned(), value(3.0));
+
+        let mut default = a.clone();
+        merge_with_policy(&mut default, &b, &DefaultMergePolicy);
+        assert_eq!(3, default.fields.len());
+        assert_eq!(5.0, default.fields["b"].get_number_value());
+
+        merge_with_policy(&mut a, &b, &TestPolicy);
+        assert_eq!(3, a.fields.len());
+        assert_eq!(2.0, a.fields["b"].get_number_value());
+        assert_eq!(3.0, a.fields["c"].get_number_value());
+    }
+
+    fn dynamic_descriptor(descriptor: &MessageDescriptor) -> MessageDescriptor {
+        FileDescriptor::new_dynamic(descriptor.file_descriptor_proto().clone(), Vec::new())
+            .message_by_package_relative_name(descriptor.name())
+            .unwrap()
+    }
+
+    #[test]
+    fn generated_and_dynamic() {
+        let mut a = file(&["a"], &[0]);
+        a.options.mut_or_default().set_java_package("a".to_owned());
+        let mut b = file(&["b"], &[1]);
+        b.set_name("b.proto".to_owned());
+        b.options.mut_or_default().set_go_package("b".to_owned());
+
+        let mut expected = file(&["a", "b"], &[0, 1]);
+        expected.set_name("b.proto".to_owned());
+        expected
+            .options
+            .mut_or_default()
+            .set_java_package("a".to_owned());
+        expected
+            .options
+            .mut_or_default()
+            .set_go_package("b".to_owned());
+
+        let descriptor = dynamic_descriptor(&FileDescriptorProto::descriptor_static());
+        let mut dynamic = descriptor.new_instance();
+        dynamic.merge_from_message_dyn(&a);
+        dynamic.merge_from_message_dyn(&b);
+        assert_eq!(descriptor, dynamic.descriptor_dyn());
+        assert_eq!(
+            expected,
+            FileDescriptorProto::parse_from_bytes(&dynamic.write_to_bytes_dyn().unwrap()).unwrap()
+        );
+
+        let mut generated = FileDescriptorProto::new();
+        (&mut generated as &mut dyn MessageDyn).merge_from_message_dyn(&*dynamic);
+        assert_eq!(expected, generated);
+    }
+
+    #[test]
+    fn proto3_default() {
+        let descriptor = dynamic_descriptor(&DoubleValue::descriptor_static());
+        let mut dynamic = descriptor.new_instance();
+        descriptor
+            .get_field_by_name("value")
+            .unwrap()
+            .set_singular_field(&mut *dynamic, ReflectValueBox::F64(0.0));
+
+        let mut message = DoubleValue::new();
+        message.value = 1.5;
+        (&mut message as &mut dyn MessageDyn).merge_from_message_dyn(&*dynamic);
+        assert_eq!(1.5, message.value);
+    }
+
+    #[test]
+    #[should_panic(expected = "merging messages of different types")]
+    fn different_types() {
+        let mut a = FileDescriptorProto::new();
+        (&mut a as &mut dyn MessageDyn).merge_from_message_dyn(&Struct::new());
+    }
+
+    #[test]
+    fn values() {
+        assert_eq!(
+            Some(ReflectValueBox::I32(3)),
+            max_value(ReflectValueRef::I32(-1), ReflectValueRef::I32(3))
+        );
+        assert_eq!(
+            Some(ReflectValueBox::F64(1.5)),
+            max_value(ReflectValueRef::F64(1.5), ReflectValueRef::F64(f64::NAN))
+        );
+        assert_eq!(
+            None,
+            max_value(ReflectValueRef::I32(1), ReflectValueRef::I64(3))
+        );
+        assert_eq!(
+            None,
+            max_value(ReflectValueRef::String("a"), ReflectValueRef::String("b"))
+        );
+    }
+}
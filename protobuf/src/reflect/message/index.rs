@@ -10,6 +10,8 @@ pub(crate) struct MessageIndex {
     pub index_by_name: HashMap<String, usize>,
     pub index_by_name_or_json_name: HashMap<String, usize>,
     pub index_by_number: HashMap<u32, usize>,
+    /// Field indices ordered by field number.
+    pub indices_sorted_by_number: Vec<usize>,
 }
 
 impl MessageIndex {
@@ -39,8 +41,12 @@ impl MessageIndex {
             }
         }
 
+        let mut indices_sorted_by_number: Vec<usize> = (0..proto.field.len()).collect();
+        indices_sorted_by_number.sort_by_key(|&i| proto.field[i].get_number());
+
         MessageIndex {
             fields,
+            indices_sorted_by_number,
             index_by_name,
             index_by_name_or_json_name,
             index_by_number,
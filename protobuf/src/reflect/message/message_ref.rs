@@ -35,11 +35,20 @@ impl<'a, M: Message> From<&'a M> for MessageRef<'a> {
     }
 }
 
+impl<'a> MessageRef<'a> {
+    /// Referenced message with the lifetime of this reference,
+    /// `None` if this is an empty dynamic message.
+    pub(crate) fn as_dyn(&self) -> Option<&'a dyn MessageDyn> {
+        match self.imp {
+            MessageRefImpl::Message(m) => Some(m),
+            MessageRefImpl::EmptyDynamic(..) => None,
+        }
+    }
+}
+
 impl<'a> ReflectEq for MessageRef<'a> {
     fn reflect_eq(&self, that: &Self, mode: &ReflectEqMode) -> bool {
-        let ad = self.descriptor_dyn();
-        let bd = that.descriptor_dyn();
-        ad == bd && ad.reflect_eq(&**self, &**that, mode)
+        MessageDescriptor::reflect_eq_maybe_unrelated(&**self, &**that, mode)
     }
 }
 
@@ -51,6 +60,15 @@ impl<'a> MessageRef<'a> {
         }
     }
 
+    /// Wrapped message reference with the original lifetime,
+    /// `None` if this is a container for an empty dynamic message.
+    pub(crate) fn as_borrowed(&self) -> Option<&'a dyn MessageDyn> {
+        match &self.imp {
+            MessageRefImpl::Message(m) => Some(*m),
+            MessageRefImpl::EmptyDynamic(..) => None,
+        }
+    }
+
     /// Default (empty) instance of given message type.
     pub fn default_instance(message: &MessageDescriptor) -> MessageRef<'static> {
         // Note we create a native generated instance for generated types
@@ -1,5 +1,8 @@
 use std::fmt;
 
+#[cfg(feature = "bytes")]
+use bytes::Bytes;
+
 use crate::descriptor::DescriptorProto;
 use crate::descriptor::FileDescriptorProto;
 use crate::message::Message;
@@ -7,15 +10,22 @@ use crate::message_dyn::MessageDyn;
 use crate::reflect::dynamic::DynamicMessage;
 use crate::reflect::file::index::FileIndexMessageEntry;
 use crate::reflect::file::FileDescriptorImpl;
+use crate::reflect::has_explicit_presence;
 use crate::reflect::message::dynamic::DynamicMessageDescriptor;
 use crate::reflect::message::generated::GeneratedMessageDescriptor;
 use crate::reflect::message::index::MessageIndex;
 use crate::reflect::reflect_eq::ReflectEq;
 use crate::reflect::reflect_eq::ReflectEqMode;
+use crate::reflect::CustomOption;
 use crate::reflect::EnumDescriptor;
+use crate::reflect::ExtensionRegistry;
 use crate::reflect::FieldDescriptor;
 use crate::reflect::FileDescriptor;
 use crate::reflect::OneofDescriptor;
+use crate::reflect::ReflectFieldRef;
+#[cfg(feature = "bytes")]
+use crate::CodedInputStream;
+use crate::ProtobufResult;
 
 pub(crate) mod dynamic;
 pub(crate) mod generated;
@@ -26,7 +36,7 @@ pub(crate) mod path;
 /// Dynamic representation of message type.
 ///
 /// Used for reflection.
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq, Hash)]
 pub struct MessageDescriptor {
     file_descriptor: FileDescriptor,
     index: usize,
@@ -122,6 +132,36 @@ impl MessageDescriptor {
         &self.file_descriptor
     }
 
+    /// [`FileDescriptor`] containing this message.
+    ///
+    /// Same as [`file_descriptor`](Self::file_descriptor).
+    pub fn file(&self) -> &FileDescriptor {
+        &self.file_descriptor
+    }
+
+    /// Message containing this message, `None` for top-level messages.
+    pub fn containing_message(&self) -> Option<MessageDescriptor> {
+        self.get_index_entry()
+            .parent
+            .map(|i| MessageDescriptor::new(self.file_descriptor.clone(), i))
+    }
+
+    /// Custom options of this message,
+    /// see [`FileDescriptor::custom_options`].
+    pub fn custom_options(&self) -> &[CustomOption] {
+        match &self.file_descriptor.imp {
+            FileDescriptorImpl::Generated(_) => &[],
+            FileDescriptorImpl::Dynamic(d) => &d.custom_options.messages[self.index],
+        }
+    }
+
+    pub(crate) fn field_custom_options(&self, field_index: usize) -> &[CustomOption] {
+        match &self.file_descriptor.imp {
+            FileDescriptorImpl::Generated(_) => &[],
+            FileDescriptorImpl::Dynamic(d) => &d.custom_options.fields[self.index][field_index],
+        }
+    }
+
     /// `FileDescriptorProto` containg this message type
     pub fn file_descriptor_proto(&self) -> &FileDescriptorProto {
         self.file_descriptor().proto()
@@ -144,15 +184,81 @@ impl MessageDescriptor {
     ///
     /// # Panics
     ///
-    /// If this message is a map entry message.
+    /// If this message is a generated map entry message.
     pub fn new_instance(&self) -> Box<dyn MessageDyn> {
-        self.assert_not_map_entry();
         match self.get_impl() {
-            MessageDescriptorImplRef::Generated(g) => g.non_map().factory.new_instance(),
+            MessageDescriptorImplRef::Generated(g) => {
+                self.assert_not_map_entry();
+                g.non_map().factory.new_instance()
+            }
             MessageDescriptorImplRef::Dynamic(..) => Box::new(DynamicMessage::new(self.clone())),
         }
     }
 
+    /// Parse message of this type, decoding extensions known to the registry.
+    ///
+    /// In dynamic messages, including nested dynamic messages, registered extensions
+    /// are decoded into values available with
+    /// [`get_extension_dyn`](crate::MessageDyn::get_extension_dyn) instead of
+    /// being stored in unknown fields. Generated messages keep extensions in unknown fields.
+    pub fn parse_from_bytes_with_extensions(
+        &self,
+        bytes: &[u8],
+        registry: &ExtensionRegistry,
+    ) -> ProtobufResult<Box<dyn MessageDyn>> {
+        let mut message = self.new_instance();
+        message.merge_from_bytes_dyn(bytes)?;
+        if let Some(m) = message.downcast_mut::<DynamicMessage>() {
+            m.decode_extensions(registry);
+        }
+        message.check_initialized_dyn()?;
+        Ok(message)
+    }
+
+    /// Parse message of this type from `Bytes` object.
+    ///
+    /// `string` and `bytes` fields of dynamic messages share memory with the input,
+    /// values returned by field getters borrow it without copying.
+    #[cfg(feature = "bytes")]
+    pub fn parse_from_carllerche_bytes(
+        &self,
+        bytes: &Bytes,
+    ) -> ProtobufResult<Box<dyn MessageDyn>> {
+        let mut message = self.new_instance();
+        let mut is = CodedInputStream::from_carllerche_bytes(bytes);
+        message.merge_from_dyn(&mut is)?;
+        is.check_eof()?;
+        message.check_initialized_dyn()?;
+        Ok(message)
+    }
+
+    /// New empty [`DynamicMessage`] of this type.
+    ///
+    /// Same as [`new_instance`](Self::new_instance), but guarantees
+    /// that returned message can be downcasted to [`DynamicMessage`].
+    ///
+    /// # Panics
+    ///
+    /// If this message type is generated (see [`is_dynamic`](Self::is_dynamic)).
+    pub fn new_dynamic_instance(&self) -> Box<dyn MessageDyn> {
+        assert!(
+            self.is_dynamic(),
+            "message type is generated: {}",
+            self.full_name()
+        );
+        self.new_instance()
+    }
+
+    /// Message type is created at runtime (e. g. with
+    /// [`FileDescriptor::new_dynamic`](crate::reflect::FileDescriptor::new_dynamic))
+    /// rather than generated, so its instances are [`DynamicMessage`].
+    pub fn is_dynamic(&self) -> bool {
+        match self.get_impl() {
+            MessageDescriptorImplRef::Generated(..) => false,
+            MessageDescriptorImplRef::Dynamic(..) => true,
+        }
+    }
+
     /// Shared immutable empty message.
     ///
     /// Returns `None` for dynamic message.
@@ -208,15 +314,17 @@ impl MessageDescriptor {
         assert_eq!(self, &b.descriptor_dyn());
 
         for field in self.fields() {
-            let af = field.get_reflect(a);
-            let bf = field.get_reflect(b);
-            if !af.reflect_eq(&bf, mode) {
+            if !field_reflect_eq(&field, a, &field, b, mode) {
                 return false;
             }
         }
-        true
+        !mode.unknown_fields || a.get_unknown_fields_dyn() == b.get_unknown_fields_dyn()
     }
 
+    /// Compare messages which may have different types.
+    ///
+    /// Messages of different descriptors of the same message type,
+    /// e. g. generated message and dynamic message, are compared by field numbers.
     pub(crate) fn reflect_eq_maybe_unrelated(
         a: &dyn MessageDyn,
         b: &dyn MessageDyn,
@@ -224,7 +332,23 @@ impl MessageDescriptor {
     ) -> bool {
         let ad = a.descriptor_dyn();
         let bd = b.descriptor_dyn();
-        ad == bd && ad.reflect_eq(a, b, mode)
+        if ad == bd {
+            return ad.reflect_eq(a, b, mode);
+        }
+
+        if ad.full_name() != bd.full_name() || ad.fields().len() != bd.fields().len() {
+            return false;
+        }
+        for af in ad.fields() {
+            let bf = match bd.get_field_by_number(af.get_proto().get_number() as u32) {
+                Some(bf) => bf,
+                None => return false,
+            };
+            if !field_reflect_eq(&af, a, &bf, b, mode) {
+                return false;
+            }
+        }
+        !mode.unknown_fields || a.get_unknown_fields_dyn() == b.get_unknown_fields_dyn()
     }
 
     /// Message name as given in `.proto` file
@@ -245,6 +369,23 @@ impl MessageDescriptor {
         })
     }
 
+    /// Message field descriptors in ascending order of field numbers.
+    ///
+    /// This is the order in which dynamic messages are serialized,
+    /// which may differ from [`fields`](Self::fields) declaration order.
+    /// Generated messages are serialized in declaration order.
+    pub fn fields_sorted_by_number<'a>(
+        &'a self,
+    ) -> impl ExactSizeIterator<Item = FieldDescriptor> + 'a {
+        self.get_index()
+            .indices_sorted_by_number
+            .iter()
+            .map(move |&index| FieldDescriptor {
+                message_descriptor: self.clone(),
+                index,
+            })
+    }
+
     /// Nested oneofs
     pub fn oneofs<'a>(&'a self) -> impl ExactSizeIterator<Item = OneofDescriptor> + 'a {
         self.get_proto()
@@ -302,6 +443,31 @@ impl MessageDescriptor {
     }
 }
 
+/// Compare field of two messages.
+///
+/// Default values of fields without presence are considered unset,
+/// because dynamic messages may store them.
+fn field_reflect_eq(
+    af: &FieldDescriptor,
+    a: &dyn MessageDyn,
+    bf: &FieldDescriptor,
+    b: &dyn MessageDyn,
+    mode: &ReflectEqMode,
+) -> bool {
+    match (af.get_reflect(a), bf.get_reflect(b)) {
+        (ReflectFieldRef::Optional(av), ReflectFieldRef::Optional(bv)) => {
+            let av = av.filter(|v| has_explicit_presence(af) || v.is_non_zero());
+            let bv = bv.filter(|v| has_explicit_presence(bf) || v.is_non_zero());
+            match (av, bv) {
+                (Some(av), Some(bv)) => av.reflect_eq(&bv, mode),
+                (None, None) => true,
+                _ => false,
+            }
+        }
+        (af, bf) => af.reflect_eq(&bf, mode),
+    }
+}
+
 pub(crate) enum MessageDescriptorImplRef<'a> {
     Generated(&'static GeneratedMessageDescriptor),
     Dynamic(&'a DynamicMessageDescriptor),
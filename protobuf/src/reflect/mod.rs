@@ -1,38 +1,81 @@
 //! Reflection implementation for protobuf types.
 
 mod acc;
-mod dynamic;
+mod anonymize;
+mod batch;
+mod builder;
+pub(crate) mod custom_options;
+pub(crate) mod dynamic;
 mod enums;
+mod error;
+mod extension;
 mod field;
 mod file;
 mod find_message_or_enum;
+pub(crate) mod initialization;
+mod lazy_view;
 mod map;
+mod map_entry;
 pub(crate) mod message;
 mod oneof;
+#[cfg(feature = "with-rayon")]
+mod parallel_write;
+mod path;
+pub(crate) mod pool_clone;
 mod repeated;
+mod repeated_set;
+mod roundtrip;
 mod runtime_type_box;
+mod tracked;
+mod truncate;
 mod type_dynamic;
 pub(crate) mod value;
 
+pub mod diff;
+pub mod introspect;
+pub mod merge;
 pub mod runtime_types;
 pub mod types;
+pub mod visit;
 
 pub(crate) mod reflect_eq;
+pub(crate) mod reflect_hash;
 
 pub mod rt;
 
 pub(crate) mod name;
 
+pub use self::anonymize::AnonymizePolicy;
+pub use self::anonymize::Anonymizer;
+pub use self::batch::decode_batch;
+#[cfg(feature = "with-rayon")]
+pub use self::batch::decode_batch_parallel;
+pub use self::builder::BuildError;
+pub use self::builder::BuildErrorKind;
+pub use self::builder::BuilderLimits;
+pub use self::builder::CheckedMessageBuilder;
+pub use self::custom_options::CustomOption;
+pub use self::custom_options::ExtensionRegistry;
+pub use self::dynamic::arena::DynamicArena;
+pub(crate) use self::dynamic::has_explicit_presence;
+pub use self::dynamic::DynamicMessage;
 #[doc(hidden)]
 pub use self::enums::generated::GeneratedEnumDescriptorData;
+pub use self::enums::EnumAliasName;
 pub use self::enums::EnumDescriptor;
 pub use self::enums::EnumValueDescriptor;
+pub use self::error::ReflectError;
+pub use self::extension::ExtensionDescriptor;
 pub use self::field::FieldDescriptor;
 pub use self::field::ReflectFieldRef;
 pub use self::field::RuntimeFieldType;
 #[doc(hidden)]
 pub use self::file::generated::GeneratedFileDescriptor;
+pub use self::file::pool::FileDescriptorPool;
+pub use self::file::pool::FileDescriptorPoolError;
 pub use self::file::FileDescriptor;
+pub use self::lazy_view::LazyMessageView;
+pub use self::lazy_view::LazyRepeatedView;
 pub use self::map::ReflectMapMut;
 pub use self::map::ReflectMapRef;
 #[doc(hidden)]
@@ -40,11 +83,28 @@ pub use self::message::generated::GeneratedMessageDescriptorData;
 pub use self::message::message_ref::MessageRef;
 pub use self::message::MessageDescriptor;
 pub use self::oneof::OneofDescriptor;
+#[cfg(feature = "with-rayon")]
+pub use self::parallel_write::write_to_bytes_parallel;
+pub use self::path::FieldPath;
+pub use self::path::FieldPathElement;
+pub use self::path::ParseFieldPathError;
 pub use self::reflect_eq::ReflectEq;
 pub use self::reflect_eq::ReflectEqMode;
 pub use self::repeated::ReflectRepeatedMut;
 pub use self::repeated::ReflectRepeatedRef;
+pub use self::repeated_set::dedup_repeated;
+pub use self::repeated_set::dedup_repeated_by_key;
+pub use self::repeated_set::intersect_repeated;
+pub use self::repeated_set::union_repeated;
+pub use self::roundtrip::check_roundtrip;
+pub use self::roundtrip::RoundtripChange;
+pub use self::roundtrip::RoundtripChangeKind;
+pub use self::roundtrip::RoundtripReport;
 pub use self::runtime_type_box::RuntimeTypeBox;
+pub use self::tracked::TrackedMessage;
+pub use self::truncate::truncate_large_fields;
+pub use self::truncate::TRUNCATED_MARKER;
 pub use self::value::value_box::ReflectValueBox;
+pub use self::value::value_ref::ReflectValueKind;
 pub use self::value::value_ref::ReflectValueRef;
 pub use self::value::ProtobufValue;
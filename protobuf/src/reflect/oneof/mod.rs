@@ -20,6 +20,11 @@ impl OneofDescriptor {
         self.get_proto().get_name()
     }
 
+    /// Message containing this oneof.
+    pub fn containing_message(&self) -> &MessageDescriptor {
+        &self.message_descriptor
+    }
+
     /// Fields in this oneof.
     pub fn fields<'a>(&'a self) -> impl Iterator<Item = FieldDescriptor> + 'a {
         self.message_descriptor
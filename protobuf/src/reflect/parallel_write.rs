@@ -0,0 +1,225 @@
+use std::convert::TryFrom;
+
+use rayon::prelude::*;
+
+use crate::descriptor::field_descriptor_proto::Type;
+use crate::message::check_message_size;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::MessageRef;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::wire_format::WireType;
+use crate::wire_scan::scan_fields;
+use crate::CodedOutputStream;
+use crate::MessageDyn;
+use crate::ProtobufResult;
+
+/// Number of chunks per rayon thread, more chunks balance load better
+const CHUNKS_PER_THREAD: usize = 4;
+
+/// Serialize message like `write_to_bytes_dyn`,
+/// but encode elements of given repeated message field in parallel.
+///
+/// This is useful for messages dominated by a huge repeated message field.
+/// Elements are split into chunks which are sized and encoded in rayon
+/// thread pool, other fields are encoded serially. Output is identical
+/// to the output of the serial serialization.
+///
+/// Result in error if message is not fully initialized.
+///
+/// # Panics
+///
+/// If field belongs to a different message type
+/// or field is not a repeated message field.
+pub fn write_to_bytes_parallel(
+    message: &dyn MessageDyn,
+    field: &FieldDescriptor,
+) -> ProtobufResult<Vec<u8>> {
+    assert_eq!(field.containing_message(), &message.descriptor_dyn());
+    let element_type = match field.runtime_field_type() {
+        RuntimeFieldType::Repeated(RuntimeTypeBox::Message(m)) => m,
+        _ => panic!("not a repeated message field: {}", field),
+    };
+    let number = field.get_proto().get_number() as u32;
+    let group = field.get_proto().get_field_type() == Type::TYPE_GROUP;
+
+    // Message without the field, but with single empty element
+    // to find where elements are written
+    let mut rest = shallow_clone_without_field(message, field);
+    rest.check_initialized_dyn()?;
+    field
+        .mut_repeated(&mut *rest)
+        .push(element_type.new_instance().into());
+    let mut rest_bytes = Vec::new();
+    {
+        let mut os = CodedOutputStream::vec(&mut rest_bytes);
+        rest.compute_size_dyn();
+        rest.write_to_with_cached_sizes_dyn(&mut os)?;
+        os.flush()?;
+    }
+    let placeholder = scan_fields(&rest_bytes)
+        .find(|f| match f {
+            Ok(f) => f.field_number == number,
+            Err(..) => true,
+        })
+        .expect("placeholder element is written")?;
+
+    let elements: Vec<MessageRef> = field
+        .get_repeated(message)
+        .into_iter()
+        .map(|e| e.to_message().unwrap())
+        .collect();
+    let chunk_size = elements.len() / (rayon::current_num_threads() * CHUNKS_PER_THREAD) + 1;
+    let chunks = elements
+        .par_chunks(chunk_size)
+        .map(|chunk| write_elements(number, group, chunk))
+        .collect::<ProtobufResult<Vec<Vec<u8>>>>()?;
+
+    let len =
+        rest_bytes.len() - placeholder.field.len() + chunks.iter().map(|c| c.len()).sum::<usize>();
+    check_message_size(u32::try_from(len).unwrap_or(u32::MAX))?;
+
+    let mut bytes = Vec::with_capacity(len);
+    bytes.extend_from_slice(&rest_bytes[..placeholder.field.start]);
+    for chunk in &chunks {
+        bytes.extend_from_slice(chunk);
+    }
+    bytes.extend_from_slice(&rest_bytes[placeholder.field.end..]);
+    Ok(bytes)
+}
+
+/// New message with all fields except given field and unknown fields copied.
+fn shallow_clone_without_field(
+    message: &dyn MessageDyn,
+    skip: &FieldDescriptor,
+) -> Box<dyn MessageDyn> {
+    let descriptor = message.descriptor_dyn();
+    let mut clone = descriptor.new_instance();
+    for field in descriptor.fields() {
+        if &field == skip {
+            continue;
+        }
+        match field.get_reflect(message) {
+            ReflectFieldRef::Optional(Some(v)) => field.set_singular_field(&mut *clone, v.to_box()),
+            ReflectFieldRef::Optional(None) => {}
+            ReflectFieldRef::Repeated(r) => {
+                let mut clone_r = field.mut_repeated(&mut *clone);
+                for v in &r {
+                    clone_r.push(v.to_box());
+                }
+            }
+            ReflectFieldRef::Map(m) => {
+                let mut clone_m = field.mut_map(&mut *clone);
+                for (k, v) in &m {
+                    clone_m.insert(k.to_box(), v.to_box());
+                }
+            }
+        }
+    }
+    *clone.mut_unknown_fields_dyn() = message.get_unknown_fields_dyn().clone();
+    clone
+}
+
+/// Encode elements of repeated message field including tags.
+fn write_elements(number: u32, group: bool, elements: &[MessageRef]) -> ProtobufResult<Vec<u8>> {
+    let mut bytes = Vec::new();
+    {
+        let mut os = CodedOutputStream::vec(&mut bytes);
+        for element in elements {
+            element.check_initialized_dyn()?;
+            if group {
+                os.write_tag(number, WireType::WireTypeStartGroup)?;
+                element.compute_size_dyn();
+                element.write_to_with_cached_sizes_dyn(&mut os)?;
+                os.write_tag(number, WireType::WireTypeEndGroup)?;
+            } else {
+                os.write_tag(number, WireType::WireTypeLengthDelimited)?;
+                element.write_length_delimited_to_dyn(&mut os)?;
+            }
+        }
+        os.flush()?;
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::write_to_bytes_parallel;
+    use crate::descriptor::file_descriptor;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::descriptor::UninterpretedOption;
+    use crate::reflect::FileDescriptor;
+    use crate::Message;
+    use crate::MessageDyn;
+    use crate::UnknownValue;
+
+    fn file(messages: usize) -> FileDescriptorProto {
+        let mut file = FileDescriptorProto::new();
+        file.set_name("a.proto".to_owned());
+        file.set_package("p".to_owned());
+        file.dependency.push("b.proto".to_owned());
+        file.options
+            .mut_or_default()
+            .set_java_package("j".to_owned());
+        for i in 0..messages {
+            let mut message = DescriptorProto::new();
+            message.set_name(format!("M{}", i));
+            file.message_type.push(message);
+        }
+        file.set_syntax("proto3".to_owned());
+        file.mut_unknown_fields()
+            .add_value(1000, UnknownValue::Varint(7));
+        file
+    }
+
+    fn check_same_as_serial(message: &dyn MessageDyn) {
+        let field = message
+            .descriptor_dyn()
+            .get_field_by_name("message_type")
+            .unwrap();
+        assert_eq!(
+            message.write_to_bytes_dyn().unwrap(),
+            write_to_bytes_parallel(message, &field).unwrap()
+        );
+    }
+
+    #[test]
+    fn generated() {
+        for &n in &[0, 1, 2, 1000] {
+            check_same_as_serial(&file(n));
+        }
+    }
+
+    #[test]
+    fn dynamic() {
+        let dynamic = FileDescriptor::new_dynamic(file_descriptor().proto().clone(), Vec::new());
+        let d = dynamic
+            .message_by_package_relative_name("FileDescriptorProto")
+            .unwrap();
+        for &n in &[0, 1, 1000] {
+            let mut m = d.new_instance();
+            m.merge_from_bytes_dyn(&file(n).write_to_bytes().unwrap())
+                .unwrap();
+            check_same_as_serial(&*m);
+        }
+    }
+
+    #[test]
+    fn not_initialized() {
+        let mut file = file(10);
+        let mut option = UninterpretedOption::new();
+        option.name.push(Default::default());
+        file.message_type[5]
+            .options
+            .mut_or_default()
+            .uninterpreted_option
+            .push(option);
+        let field = FileDescriptorProto::descriptor_static()
+            .get_field_by_name("message_type")
+            .unwrap();
+        assert!(file.write_to_bytes().is_err());
+        assert!(write_to_bytes_parallel(&file, &field).is_err());
+    }
+}
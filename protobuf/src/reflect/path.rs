@@ -0,0 +1,748 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::message_dyn::MessageDyn;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::ReflectError;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::ReflectValueRef;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+
+/// Element of [`FieldPath`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldPathElement {
+    /// Message field by name.
+    Field(String),
+    /// Element of repeated field by index.
+    Index(usize),
+    /// Value of map field by key.
+    MapKey(ReflectValueBox),
+}
+
+/// Path to a field inside nested messages, e.g. `config.servers[2].port`.
+///
+/// Path can be parsed from string and used to access the field:
+///
+/// ```
+/// # use protobuf::descriptor::FileDescriptorProto;
+/// # use protobuf::reflect::FieldPath;
+/// # use protobuf::reflect::ReflectValueBox;
+/// let path: FieldPath = "message_type[0].name".parse().unwrap();
+///
+/// let mut file = FileDescriptorProto::new();
+/// file.message_type.push(Default::default());
+/// path.set(&mut file, ReflectValueBox::String("Foo".to_owned()))
+///     .unwrap();
+/// assert_eq!("Foo", file.message_type[0].get_name());
+/// ```
+///
+/// Map keys are written in brackets like indices, string keys are quoted:
+/// `labels["env"]`.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct FieldPath {
+    elements: Vec<FieldPathElement>,
+}
+
+impl FieldPath {
+    /// Empty path which refers to the message itself.
+    pub fn new() -> FieldPath {
+        FieldPath::default()
+    }
+
+    /// Path elements from outermost to innermost.
+    pub fn elements(&self) -> &[FieldPathElement] {
+        &self.elements
+    }
+
+    /// Is this path empty.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Append an element to the path.
+    pub fn push(&mut self, element: FieldPathElement) {
+        self.elements.push(element);
+    }
+
+    /// Remove the last element of the path.
+    pub fn pop(&mut self) -> Option<FieldPathElement> {
+        self.elements.pop()
+    }
+
+    /// Value at this path in the message.
+    ///
+    /// Path must end with a singular field, an element of a repeated field
+    /// or a value of a map field.
+    /// Return `None` if a field on the path is unset, or map has no value for the key.
+    pub fn get<'a>(
+        &self,
+        m: &'a dyn MessageDyn,
+    ) -> Result<Option<ReflectValueRef<'a>>, ReflectError> {
+        let steps = self.steps()?;
+        let mut message = m;
+        for (i, step) in steps.iter().enumerate() {
+            let field = find_field(message, step.field)?;
+            let value = match (field.get_reflect(message), step.selector) {
+                (ReflectFieldRef::Optional(v), None) => v,
+                (ReflectFieldRef::Repeated(r), Some(FieldPathElement::Index(index))) => {
+                    if *index >= r.len() {
+                        return Err(ReflectError::IndexOutOfRange {
+                            index: *index,
+                            len: r.len(),
+                        });
+                    }
+                    Some(r.get(*index))
+                }
+                (ReflectFieldRef::Map(map), Some(key)) => {
+                    let key = map_key(key, &map.key_type())?;
+                    map.get(key.as_value_ref())
+                }
+                (_, selector) => return Err(wrong_selector(&field, selector)),
+            };
+            let value = match value {
+                Some(value) => value,
+                None => return Ok(None),
+            };
+            if i + 1 == steps.len() {
+                return Ok(Some(value));
+            }
+            message = match value {
+                ReflectValueRef::Message(m) => match m.as_dyn() {
+                    Some(m) => m,
+                    None => return Ok(None),
+                },
+                _ => return Err(ReflectError::wrong_field_kind(&field, "message")),
+            };
+        }
+        unreachable!()
+    }
+
+    /// Set value at this path in the message.
+    ///
+    /// Unset message fields and missing map values on the path are created,
+    /// elements of repeated fields must exist.
+    pub fn set(&self, m: &mut dyn MessageDyn, value: ReflectValueBox) -> Result<(), ReflectError> {
+        update_message(m, &self.steps()?, Update::Set(value))
+    }
+
+    /// Unset the field, remove the element of repeated field or
+    /// the entry of map field at this path in the message.
+    /// Path may end with repeated or map field, which is cleared.
+    ///
+    /// Nothing is done if a message field on the path is unset
+    /// or map has no value for the key.
+    pub fn clear(&self, m: &mut dyn MessageDyn) -> Result<(), ReflectError> {
+        update_message(m, &self.steps()?, Update::Clear)
+    }
+
+    /// Split path into fields each optionally followed by index or key.
+    fn steps(&self) -> Result<Vec<Step<'_>>, ReflectError> {
+        let invalid = |reason| ReflectError::InvalidPath {
+            path: self.to_string(),
+            reason,
+        };
+        let mut steps: Vec<Step> = Vec::new();
+        for element in &self.elements {
+            match element {
+                FieldPathElement::Field(field) => steps.push(Step {
+                    field,
+                    selector: None,
+                }),
+                selector => match steps.last_mut() {
+                    Some(step) if step.selector.is_none() => step.selector = Some(selector),
+                    Some(_) => return Err(invalid("index or key follows index or key")),
+                    None => return Err(invalid("path starts with index or key")),
+                },
+            }
+        }
+        if steps.is_empty() {
+            return Err(invalid("path is empty"));
+        }
+        Ok(steps)
+    }
+}
+
+/// Field name and index or key of repeated or map field.
+struct Step<'p> {
+    field: &'p str,
+    selector: Option<&'p FieldPathElement>,
+}
+
+enum Update {
+    Set(ReflectValueBox),
+    Clear,
+}
+
+fn find_field(m: &dyn MessageDyn, name: &str) -> Result<FieldDescriptor, ReflectError> {
+    let descriptor = m.descriptor_dyn();
+    descriptor
+        .get_field_by_name(name)
+        .ok_or_else(|| ReflectError::NoSuchField {
+            message: descriptor.full_name().to_owned(),
+            field: name.to_owned(),
+        })
+}
+
+fn wrong_selector(field: &FieldDescriptor, selector: Option<&FieldPathElement>) -> ReflectError {
+    let expected = match selector {
+        None => "singular",
+        Some(FieldPathElement::MapKey(..)) => "map",
+        Some(_) => "repeated or map",
+    };
+    ReflectError::wrong_field_kind(field, expected)
+}
+
+/// Convert index or key from the path to the map key type.
+fn map_key(
+    selector: &FieldPathElement,
+    key_type: &RuntimeTypeBox,
+) -> Result<ReflectValueBox, ReflectError> {
+    let key = match selector {
+        FieldPathElement::Index(index) => ReflectValueBox::U64(*index as u64),
+        FieldPathElement::MapKey(key) => key.clone(),
+        FieldPathElement::Field(..) => unreachable!(),
+    };
+    let number = match key {
+        ReflectValueBox::I32(v) => Some(v as i128),
+        ReflectValueBox::I64(v) => Some(v as i128),
+        ReflectValueBox::U32(v) => Some(v as i128),
+        ReflectValueBox::U64(v) => Some(v as i128),
+        _ => None,
+    };
+    let converted = match (key_type, number) {
+        (RuntimeTypeBox::I32, Some(n)) => i32::try_from(n).ok().map(ReflectValueBox::I32),
+        (RuntimeTypeBox::I64, Some(n)) => i64::try_from(n).ok().map(ReflectValueBox::I64),
+        (RuntimeTypeBox::U32, Some(n)) => u32::try_from(n).ok().map(ReflectValueBox::U32),
+        (RuntimeTypeBox::U64, Some(n)) => u64::try_from(n).ok().map(ReflectValueBox::U64),
+        _ if &key.get_type() == key_type => Some(key.clone()),
+        _ => None,
+    };
+    converted.ok_or_else(|| ReflectError::WrongValueType {
+        expected: key_type.to_string(),
+        actual: key.get_type().to_string(),
+    })
+}
+
+fn update_message(
+    m: &mut dyn MessageDyn,
+    steps: &[Step],
+    update: Update,
+) -> Result<(), ReflectError> {
+    let (step, rest) = steps.split_first().unwrap();
+    let field = find_field(m, step.field)?;
+    match (field.runtime_field_type(), step.selector) {
+        (RuntimeFieldType::Singular(..), None) => match (rest.is_empty(), update) {
+            (true, Update::Set(value)) => field.try_set_singular_field(m, value),
+            (true, Update::Clear) => {
+                field.clear_field(m);
+                Ok(())
+            }
+            (false, Update::Clear) if !field.has_field(m) => Ok(()),
+            (false, update) => update_message(field.try_mut_message(m)?, rest, update),
+        },
+        (RuntimeFieldType::Repeated(..), Some(FieldPathElement::Index(index))) => {
+            let index = *index;
+            let len = field.get_repeated(m).len();
+            if index >= len {
+                return Err(ReflectError::IndexOutOfRange { index, len });
+            }
+            match (rest.is_empty(), update) {
+                (true, Update::Set(value)) => field.mut_repeated(m).try_set(index, value),
+                (true, Update::Clear) => {
+                    let values: Vec<ReflectValueBox> = field
+                        .get_repeated(m)
+                        .into_iter()
+                        .enumerate()
+                        .filter(|(i, _)| *i != index)
+                        .map(|(_, v)| v.to_box())
+                        .collect();
+                    let mut repeated = field.mut_repeated(m);
+                    repeated.clear();
+                    for value in values {
+                        repeated.push(value);
+                    }
+                    Ok(())
+                }
+                (false, update) => {
+                    let mut element = field.get_repeated(m).get(index).to_box();
+                    update_nested(&field, &mut element, rest, update)?;
+                    field.mut_repeated(m).set(index, element);
+                    Ok(())
+                }
+            }
+        }
+        (RuntimeFieldType::Map(key_type, value_type), Some(selector)) => {
+            let key = map_key(selector, &key_type)?;
+            let existing = field.get_map(m).get(key.as_value_ref()).map(|v| v.to_box());
+            match (rest.is_empty(), update, existing) {
+                (true, Update::Set(value), _) => field.mut_map(m).try_insert(key, value),
+                (_, Update::Clear, None) => Ok(()),
+                (true, Update::Clear, Some(..)) => {
+                    let entries: Vec<(ReflectValueBox, ReflectValueBox)> = field
+                        .get_map(m)
+                        .into_iter()
+                        .filter(|(k, _)| *k != key.as_value_ref())
+                        .map(|(k, v)| (k.to_box(), v.to_box()))
+                        .collect();
+                    let mut map = field.mut_map(m);
+                    map.clear();
+                    for (k, v) in entries {
+                        map.insert(k, v);
+                    }
+                    Ok(())
+                }
+                (false, update, existing) => {
+                    let mut value = match (existing, value_type) {
+                        (Some(value), _) => value,
+                        (None, RuntimeTypeBox::Message(d)) => {
+                            ReflectValueBox::Message(d.new_instance())
+                        }
+                        (None, _) => return Err(ReflectError::wrong_field_kind(&field, "message")),
+                    };
+                    update_nested(&field, &mut value, rest, update)?;
+                    field.mut_map(m).insert(key, value);
+                    Ok(())
+                }
+            }
+        }
+        (_, None) if rest.is_empty() && matches!(update, Update::Clear) => {
+            field.clear_field(m);
+            Ok(())
+        }
+        (_, selector) => Err(wrong_selector(&field, selector)),
+    }
+}
+
+/// Update element of repeated field or value of map field.
+fn update_nested(
+    field: &FieldDescriptor,
+    value: &mut ReflectValueBox,
+    steps: &[Step],
+    update: Update,
+) -> Result<(), ReflectError> {
+    match value {
+        ReflectValueBox::Message(m) => update_message(&mut **m, steps, update),
+        _ => Err(ReflectError::wrong_field_kind(field, "message")),
+    }
+}
+
+impl From<Vec<FieldPathElement>> for FieldPath {
+    fn from(elements: Vec<FieldPathElement>) -> FieldPath {
+        FieldPath { elements }
+    }
+}
+
+fn fmt_map_key(key: &ReflectValueBox, f: &mut fmt::Formatter) -> fmt::Result {
+    match key {
+        ReflectValueBox::U32(v) => write!(f, "{}", v),
+        ReflectValueBox::U64(v) => write!(f, "{}", v),
+        ReflectValueBox::I32(v) => write!(f, "{}", v),
+        ReflectValueBox::I64(v) => write!(f, "{}", v),
+        ReflectValueBox::Bool(v) => write!(f, "{}", v),
+        ReflectValueBox::String(v) => write!(f, "{:?}", v),
+        v => write!(f, "{:?}", v),
+    }
+}
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, element) in self.elements.iter().enumerate() {
+            match element {
+                FieldPathElement::Field(name) => {
+                    if i != 0 {
+                        write!(f, ".")?;
+                    }
+                    write!(f, "{}", name)?;
+                }
+                FieldPathElement::Index(index) => write!(f, "[{}]", index)?,
+                FieldPathElement::MapKey(key) => {
+                    write!(f, "[")?;
+                    fmt_map_key(key, f)?;
+                    write!(f, "]")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when [`FieldPath`] cannot be parsed from string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFieldPathError {
+    path: String,
+    position: usize,
+}
+
+impl fmt::Display for ParseFieldPathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "cannot parse field path `{}` at position {}",
+            self.path, self.position
+        )
+    }
+}
+
+impl Error for ParseFieldPathError {}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rem(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn next_char_if(&mut self, c: char) -> bool {
+        if self.rem().starts_with(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn ident(&mut self) -> Option<String> {
+        let rem = self.rem();
+        let len = rem
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(rem.len());
+        if len == 0 || rem.as_bytes()[0].is_ascii_digit() {
+            return None;
+        }
+        self.pos += len;
+        Some(rem[..len].to_owned())
+    }
+
+    /// String in Rust syntax, as printed by `Display` of `FieldPath`.
+    fn string(&mut self) -> Option<String> {
+        let mut chars = self.rem().char_indices().skip(1);
+        let mut r = String::new();
+        loop {
+            let c = match chars.next()? {
+                (i, '"') => {
+                    self.pos += i + 1;
+                    return Some(r);
+                }
+                (_, '\\') => match chars.next()?.1 {
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    '0' => '\0',
+                    c @ '\\' | c @ '"' | c @ '\'' => c,
+                    'u' => {
+                        if chars.next()?.1 != '{' {
+                            return None;
+                        }
+                        let mut code = 0u32;
+                        loop {
+                            match chars.next()?.1 {
+                                '}' => break,
+                                c => code = code.checked_mul(16)?.checked_add(c.to_digit(16)?)?,
+                            }
+                        }
+                        char::from_u32(code)?
+                    }
+                    _ => return None,
+                },
+                (_, c) => c,
+            };
+            r.push(c);
+        }
+    }
+
+    fn key(&mut self) -> Option<FieldPathElement> {
+        if self.rem().starts_with('"') {
+            return Some(FieldPathElement::MapKey(ReflectValueBox::String(
+                self.string()?,
+            )));
+        }
+        let rem = self.rem();
+        let token = &rem[..rem.find(']')?];
+        let key = match token {
+            "true" => FieldPathElement::MapKey(ReflectValueBox::Bool(true)),
+            "false" => FieldPathElement::MapKey(ReflectValueBox::Bool(false)),
+            _ => match token.parse::<usize>() {
+                Ok(index) => FieldPathElement::Index(index),
+                Err(_) => FieldPathElement::MapKey(ReflectValueBox::I64(token.parse().ok()?)),
+            },
+        };
+        self.pos += token.len();
+        Some(key)
+    }
+
+    fn path(&mut self) -> Option<FieldPath> {
+        let mut path = FieldPath::new();
+        if self.input.is_empty() {
+            return Some(path);
+        }
+        loop {
+            path.push(FieldPathElement::Field(self.ident()?));
+            while self.next_char_if('[') {
+                path.push(self.key()?);
+                if !self.next_char_if(']') {
+                    return None;
+                }
+            }
+            if self.rem().is_empty() {
+                return Some(path);
+            }
+            if !self.next_char_if('.') {
+                return None;
+            }
+        }
+    }
+}
+
+/// Parse path in the format of `Display`, e.g. `config.servers[2].labels["env"]`.
+///
+/// Non-negative integers in brackets are parsed as [`FieldPathElement::Index`],
+/// which can be used for map fields with integer keys too.
+impl FromStr for FieldPath {
+    type Err = ParseFieldPathError;
+
+    fn from_str(s: &str) -> Result<FieldPath, ParseFieldPathError> {
+        let mut parser = Parser { input: s, pos: 0 };
+        parser.path().ok_or_else(|| ParseFieldPathError {
+            path: s.to_owned(),
+            position: parser.pos,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::reflect::FileDescriptor;
+    use crate::well_known_types::Struct;
+    use crate::well_known_types::Value;
+    use crate::Message;
+
+    fn path(s: &str) -> FieldPath {
+        s.parse().unwrap()
+    }
+
+    fn string(s: &str) -> ReflectValueBox {
+        ReflectValueBox::String(s.to_owned())
+    }
+
+    #[test]
+    fn parse() {
+        for s in &[
+            "",
+            "a",
+            "a.b_c.d1",
+            "a[0].b[10]",
+            "labels[\"env\"].x",
+            "labels[\"a\\\"b\\n\\u{7f}\"]",
+            "ids[-1]",
+            "flags[true]",
+        ] {
+            assert_eq!(*s, path(s).to_string());
+        }
+        assert_eq!(
+            FieldPath::from(vec![
+                FieldPathElement::Field("a".to_owned()),
+                FieldPathElement::MapKey(string("x]")),
+                FieldPathElement::Field("b".to_owned()),
+                FieldPathElement::Index(3),
+            ]),
+            path("a[\"x]\"].b[3]")
+        );
+
+        for s in &[
+            "a.", ".a", "a[", "a[x]", "a[1", "1a", "a..b", "a[\"x]", "a b",
+        ] {
+            assert!(s.parse::<FieldPath>().is_err(), "{}", s);
+        }
+        assert_eq!(
+            "cannot parse field path `a[x]` at position 2",
+            "a[x]".parse::<FieldPath>().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn generated() {
+        let mut file = FileDescriptorProto::new();
+        file.message_type.push(DescriptorProto::new());
+        file.message_type.push(DescriptorProto::new());
+        file.dependency.push("a.proto".to_owned());
+        file.dependency.push("b.proto".to_owned());
+
+        assert_eq!(None, path("options.java_package").get(&file).unwrap());
+        path("options.java_package")
+            .set(&mut file, string("p"))
+            .unwrap();
+        assert_eq!("p", file.options.get_or_default().get_java_package());
+        assert_eq!(
+            Some(ReflectValueRef::String("p")),
+            path("options.java_package").get(&file).unwrap()
+        );
+
+        path("message_type[1].name")
+            .set(&mut file, string("M"))
+            .unwrap();
+        assert_eq!("M", file.message_type[1].get_name());
+        assert_eq!(
+            Some(ReflectValueRef::String("M")),
+            path("message_type[1].name").get(&file).unwrap()
+        );
+
+        path("options.java_package").clear(&mut file).unwrap();
+        assert!(!file.options.get_or_default().has_java_package());
+        assert!(file.options.is_some());
+        path("options").clear(&mut file).unwrap();
+        assert!(file.options.is_none());
+        path("options.java_package").clear(&mut file).unwrap();
+        assert!(file.options.is_none());
+
+        path("dependency[0]").clear(&mut file).unwrap();
+        assert_eq!(vec!["b.proto"], file.dependency);
+        path("message_type[0]").clear(&mut file).unwrap();
+        assert_eq!(1, file.message_type.len());
+        assert_eq!("M", file.message_type[0].get_name());
+        path("message_type").clear(&mut file).unwrap();
+        assert!(file.message_type.is_empty());
+    }
+
+    #[test]
+    fn map() {
+        let mut s = Struct::new();
+        assert_eq!(None, path("fields[\"a\"]").get(&s).unwrap());
+        path("fields[\"a\"].number_value")
+            .set(&mut s, ReflectValueBox::F64(1.5))
+            .unwrap();
+        assert_eq!(1.5, s.fields["a"].get_number_value());
+        assert_eq!(
+            Some(ReflectValueRef::F64(1.5)),
+            path("fields[\"a\"].number_value").get(&s).unwrap()
+        );
+
+        let mut value = Value::new();
+        value.set_string_value("x".to_owned());
+        path("fields[\"b\"]")
+            .set(&mut s, ReflectValueBox::Message(Box::new(value)))
+            .unwrap();
+        assert_eq!("x", s.fields["b"].get_string_value());
+
+        path("fields[\"c\"].number_value").clear(&mut s).unwrap();
+        path("fields[\"a\"]").clear(&mut s).unwrap();
+        assert_eq!(vec!["b"], s.fields.keys().collect::<Vec<_>>());
+
+        // oneof field of generated message, other case is not cleared
+        path("fields[\"b\"].number_value").clear(&mut s).unwrap();
+        assert_eq!("x", s.fields["b"].get_string_value());
+        path("fields[\"b\"].string_value").clear(&mut s).unwrap();
+        assert!(!s.fields["b"].has_string_value());
+    }
+
+    #[test]
+    fn dynamic() {
+        let file = FileDescriptor::new_dynamic(
+            FileDescriptorProto::descriptor_static()
+                .file_descriptor_proto()
+                .clone(),
+            Vec::new(),
+        );
+        let descriptor = file
+            .message_by_package_relative_name("FileDescriptorProto")
+            .unwrap();
+        let mut message = descriptor.new_instance();
+
+        path("options.go_package")
+            .set(&mut *message, string("g"))
+            .unwrap();
+        path("message_type")
+            .set(&mut *message, string("x"))
+            .unwrap_err();
+        assert_eq!(
+            Some(ReflectValueRef::String("g")),
+            path("options.go_package").get(&*message).unwrap()
+        );
+        path("options.go_package").clear(&mut *message).unwrap();
+        assert_eq!(None, path("options.go_package").get(&*message).unwrap());
+    }
+
+    #[test]
+    fn errors() {
+        let mut file = FileDescriptorProto::new();
+        assert_eq!(
+            Err(ReflectError::NoSuchField {
+                message: "google.protobuf.FileDescriptorProto".to_owned(),
+                field: "nope".to_owned(),
+            }),
+            path("nope").get(&file)
+        );
+        assert_eq!(
+            Err(ReflectError::IndexOutOfRange { index: 0, len: 0 }),
+            path("message_type[0].name").set(&mut file, string("M"))
+        );
+        assert!(matches!(
+            path("name[0]").get(&file),
+            Err(ReflectError::WrongFieldKind { .. })
+        ));
+        assert!(matches!(
+            path("dependency").get(&file),
+            Err(ReflectError::WrongFieldKind { .. })
+        ));
+        assert!(matches!(
+            path("name.x").set(&mut file, string("M")),
+            Err(ReflectError::WrongFieldKind { .. })
+        ));
+        assert!(matches!(
+            path("").clear(&mut file),
+            Err(ReflectError::InvalidPath { .. })
+        ));
+        assert!(matches!(
+            path("name").set(&mut file, ReflectValueBox::I32(1)),
+            Err(ReflectError::WrongValueType { .. })
+        ));
+    }
+
+    #[test]
+    fn map_keys() {
+        assert_eq!(
+            Ok(ReflectValueBox::I32(-1)),
+            map_key(
+                &FieldPathElement::MapKey(ReflectValueBox::I64(-1)),
+                &RuntimeTypeBox::I32
+            )
+        );
+        assert_eq!(
+            Ok(ReflectValueBox::U64(3)),
+            map_key(&FieldPathElement::Index(3), &RuntimeTypeBox::U64)
+        );
+        assert!(map_key(
+            &FieldPathElement::MapKey(ReflectValueBox::I64(-1)),
+            &RuntimeTypeBox::U32
+        )
+        .is_err());
+        assert!(map_key(&FieldPathElement::Index(3), &RuntimeTypeBox::String).is_err());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("", FieldPath::new().to_string());
+        let path = FieldPath::from(vec![
+            FieldPathElement::Field("config".to_owned()),
+            FieldPathElement::Field("servers".to_owned()),
+            FieldPathElement::Index(2),
+            FieldPathElement::Field("labels".to_owned()),
+            FieldPathElement::MapKey(ReflectValueBox::String("env".to_owned())),
+            FieldPathElement::Field("ids".to_owned()),
+            FieldPathElement::MapKey(ReflectValueBox::I32(-1)),
+        ]);
+        assert_eq!(
+            "config.servers[2].labels[\"env\"].ids[-1]",
+            path.to_string()
+        );
+    }
+}
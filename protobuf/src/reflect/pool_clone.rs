@@ -0,0 +1,110 @@
+use crate::message_dyn::MessageDyn;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::FileDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::ReflectValueRef;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+
+fn same_type(a: &RuntimeTypeBox, b: &RuntimeTypeBox) -> bool {
+    match (a, b) {
+        (RuntimeTypeBox::Enum(a), RuntimeTypeBox::Enum(b)) => a.full_name() == b.full_name(),
+        (RuntimeTypeBox::Message(a), RuntimeTypeBox::Message(b)) => a.full_name() == b.full_name(),
+        (a, b) => a == b,
+    }
+}
+
+fn same_field_type(a: &RuntimeFieldType, b: &RuntimeFieldType) -> bool {
+    match (a, b) {
+        (RuntimeFieldType::Singular(a), RuntimeFieldType::Singular(b)) => same_type(a, b),
+        (RuntimeFieldType::Repeated(a), RuntimeFieldType::Repeated(b)) => same_type(a, b),
+        (RuntimeFieldType::Map(ak, av), RuntimeFieldType::Map(bk, bv)) => {
+            same_type(ak, bk) && same_type(av, bv)
+        }
+        _ => false,
+    }
+}
+
+/// Field of target message with the same number, name and type.
+fn target_field(source: &FieldDescriptor, target: &MessageDescriptor) -> Option<FieldDescriptor> {
+    let field = target.get_field_by_number(source.get_proto().get_number() as u32)?;
+    if field.get_name() != source.get_name()
+        || !same_field_type(&source.runtime_field_type(), &field.runtime_field_type())
+    {
+        return None;
+    }
+    Some(field)
+}
+
+pub(crate) fn clone_value(
+    value: ReflectValueRef,
+    target: &RuntimeTypeBox,
+) -> Option<ReflectValueBox> {
+    Some(match (value, target) {
+        (ReflectValueRef::Message(m), RuntimeTypeBox::Message(d)) => {
+            ReflectValueBox::Message(clone_message(&*m, d)?)
+        }
+        (ReflectValueRef::Enum(_, v), RuntimeTypeBox::Enum(d)) => {
+            ReflectValueBox::Enum(d.clone(), v)
+        }
+        (value, _) => value.to_box(),
+    })
+}
+
+fn clone_field(
+    source: &dyn MessageDyn,
+    field: &FieldDescriptor,
+    target: &mut dyn MessageDyn,
+    target_field: &FieldDescriptor,
+) -> Option<()> {
+    match (field.get_reflect(source), target_field.runtime_field_type()) {
+        (ReflectFieldRef::Optional(None), _) => {}
+        (ReflectFieldRef::Optional(Some(v)), RuntimeFieldType::Singular(t)) => {
+            target_field.set_singular_field(target, clone_value(v, &t)?);
+        }
+        (ReflectFieldRef::Repeated(r), RuntimeFieldType::Repeated(t)) => {
+            let mut target_repeated = target_field.mut_repeated(target);
+            for v in r {
+                target_repeated.push(clone_value(v, &t)?);
+            }
+        }
+        (ReflectFieldRef::Map(m), RuntimeFieldType::Map(kt, vt)) => {
+            let mut target_map = target_field.mut_map(target);
+            for (k, v) in &m {
+                target_map.insert(clone_value(k, &kt)?, clone_value(v, &vt)?);
+            }
+        }
+        _ => unreachable!(),
+    }
+    Some(())
+}
+
+/// Deep clone message into a message of `target` type, matching fields by number.
+pub(crate) fn clone_message(
+    m: &dyn MessageDyn,
+    target: &MessageDescriptor,
+) -> Option<Box<dyn MessageDyn>> {
+    let descriptor = m.descriptor_dyn();
+    if descriptor.fields().len() != target.fields().len() {
+        return None;
+    }
+    let mut r = target.new_instance();
+    for field in descriptor.fields() {
+        let target_field = target_field(&field, target)?;
+        clone_field(m, &field, &mut *r, &target_field)?;
+    }
+    *r.mut_unknown_fields_dyn() = m.get_unknown_fields_dyn().clone();
+    Some(r)
+}
+
+/// Implementation of [`MessageDyn::clone_into_pool`](crate::MessageDyn).
+pub(crate) fn clone_into_pool(
+    m: &dyn MessageDyn,
+    pool: &[FileDescriptor],
+) -> Option<Box<dyn MessageDyn>> {
+    let name = format!(".{}", m.descriptor_dyn().full_name());
+    let target = pool.iter().find_map(|f| f.message_by_full_name(&name))?;
+    clone_message(m, &target)
+}
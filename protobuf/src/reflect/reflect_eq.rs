@@ -3,6 +3,8 @@
 pub struct ReflectEqMode {
     /// When `true`, `NaN` values are considered equal to each other.
     pub nan_equal: bool,
+    /// When `true`, unknown fields of messages are compared too.
+    pub unknown_fields: bool,
     _non_exhausitve: (),
 }
 
@@ -21,6 +23,14 @@ impl ReflectEqMode {
             ..Default::default()
         }
     }
+
+    /// Same mode, but also compare unknown fields.
+    pub fn with_unknown_fields(self) -> ReflectEqMode {
+        ReflectEqMode {
+            unknown_fields: true,
+            ..self
+        }
+    }
 }
 
 /// Special version of eq.
@@ -0,0 +1,171 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use crate::message_dyn::MessageDyn;
+use crate::reflect::has_explicit_presence;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueRef;
+
+/// Hash message consistently with reflective equality:
+/// messages equal with any [`ReflectEqMode`](crate::reflect::ReflectEqMode)
+/// have equal hashes.
+///
+/// Fields are hashed by numbers, so generated and dynamic messages
+/// of the same type have equal hashes. Unknown fields are not hashed.
+pub(crate) fn hash_message<H: Hasher>(message: &dyn MessageDyn, state: &mut H) {
+    let descriptor = message.descriptor_dyn();
+    descriptor.full_name().hash(state);
+
+    for field in descriptor.fields_sorted_by_number() {
+        let number = field.get_proto().get_number();
+        match field.get_reflect(message) {
+            ReflectFieldRef::Optional(v) => {
+                // Default values of fields without presence are considered unset
+                if let Some(v) = v.filter(|v| has_explicit_presence(&field) || v.is_non_zero()) {
+                    number.hash(state);
+                    hash_value(&v, state);
+                }
+            }
+            ReflectFieldRef::Repeated(r) => {
+                if !r.is_empty() {
+                    number.hash(state);
+                    r.len().hash(state);
+                    for v in &r {
+                        hash_value(&v, state);
+                    }
+                }
+            }
+            ReflectFieldRef::Map(m) => {
+                if !m.is_empty() {
+                    number.hash(state);
+                    m.len().hash(state);
+                    // Map iteration order is unspecified, so combine entry hashes
+                    // with commutative operation
+                    let mut entries = 0u64;
+                    for (k, v) in &m {
+                        let mut entry = DefaultHasher::new();
+                        hash_value(&k, &mut entry);
+                        hash_value(&v, &mut entry);
+                        entries = entries.wrapping_add(entry.finish());
+                    }
+                    entries.hash(state);
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn hash_value<H: Hasher>(value: &ReflectValueRef, state: &mut H) {
+    match value {
+        ReflectValueRef::F32(v) => hash_float(*v as f64, state),
+        ReflectValueRef::F64(v) => hash_float(*v, state),
+        ReflectValueRef::Enum(_, v) => v.hash(state),
+        ReflectValueRef::Message(m) => hash_message(&**m, state),
+        v => v.hash(state),
+    }
+}
+
+/// Equal floats have equal hashes, all `NaN` values have equal hashes.
+fn hash_float<H: Hasher>(v: f64, state: &mut H) {
+    let bits = if v == 0.0 {
+        0
+    } else if v.is_nan() {
+        f64::NAN.to_bits()
+    } else {
+        v.to_bits()
+    };
+    bits.hash(state);
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::Hasher;
+
+    use crate::descriptor::file_descriptor;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::reflect::FileDescriptor;
+    use crate::reflect::MessageDescriptor;
+    use crate::reflect::ReflectEqMode;
+    use crate::reflect::ReflectValueBox;
+    use crate::well_known_types::DoubleValue;
+    use crate::Message;
+    use crate::MessageDyn;
+
+    fn eq(a: &dyn MessageDyn, b: &dyn MessageDyn, mode: &ReflectEqMode) -> bool {
+        a.reflect_eq_dyn(b, mode)
+    }
+
+    fn hash(message: &dyn MessageDyn) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        message.reflect_hash_dyn(&mut hasher);
+        hasher.finish()
+    }
+
+    fn dynamic_descriptor(descriptor: &MessageDescriptor) -> MessageDescriptor {
+        FileDescriptor::new_dynamic(descriptor.file_descriptor_proto().clone(), Vec::new())
+            .message_by_package_relative_name(descriptor.name())
+            .unwrap()
+    }
+
+    #[test]
+    fn generated_and_dynamic() {
+        let mut message = FileDescriptorProto::new();
+        message.set_name("a.proto".to_owned());
+        message.dependency.push("b.proto".to_owned());
+        message
+            .options
+            .mut_or_default()
+            .set_java_package("c".to_owned());
+
+        let descriptor = dynamic_descriptor(&FileDescriptorProto::descriptor_static());
+        let mut dynamic = descriptor.new_instance();
+        dynamic
+            .merge_from_bytes_dyn(&message.write_to_bytes().unwrap())
+            .unwrap();
+
+        let mode = ReflectEqMode::default();
+        assert!(eq(&message, &*dynamic, &mode));
+        assert!(eq(&*dynamic, &message, &mode));
+        assert_eq!(hash(&message), hash(&*dynamic));
+
+        message.dependency.push("d.proto".to_owned());
+        assert!(!eq(&message, &*dynamic, &mode));
+        assert_ne!(hash(&message), hash(&*dynamic));
+
+        let mut other = FileDescriptorProto::new();
+        other.set_name("a.proto".to_owned());
+        assert!(!eq(&other, file_descriptor().proto(), &mode));
+    }
+
+    #[test]
+    fn unknown_fields() {
+        let a = FileDescriptorProto::new();
+        let mut b = FileDescriptorProto::new();
+        b.mut_unknown_fields_dyn().add_varint(1000, 1);
+
+        assert!(eq(&a, &b, &ReflectEqMode::default()));
+        assert!(!eq(&a, &b, &ReflectEqMode::default().with_unknown_fields()));
+        assert_eq!(hash(&a), hash(&b));
+    }
+
+    #[test]
+    fn proto3_default_and_nan() {
+        let descriptor = dynamic_descriptor(&DoubleValue::descriptor_static());
+        let value = descriptor.get_field_by_name("value").unwrap();
+
+        let mut dynamic = descriptor.new_instance();
+        value.set_singular_field(&mut *dynamic, ReflectValueBox::F64(0.0));
+        let mut message = DoubleValue::new();
+        let mode = ReflectEqMode::default();
+        assert!(eq(&message, &*dynamic, &mode));
+        assert_eq!(hash(&message), hash(&*dynamic));
+
+        value.set_singular_field(&mut *dynamic, ReflectValueBox::F64(f64::NAN));
+        message.value = f64::NAN;
+        assert!(!eq(&message, &*dynamic, &mode));
+        assert!(eq(&message, &*dynamic, &ReflectEqMode::nan_equal()));
+        assert_eq!(hash(&message), hash(&*dynamic));
+    }
+}
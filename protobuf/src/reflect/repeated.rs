@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::fmt;
 use std::slice;
 
@@ -5,7 +6,9 @@ use crate::reflect::dynamic::repeated::DynamicRepeated;
 use crate::reflect::reflect_eq::ReflectEq;
 use crate::reflect::reflect_eq::ReflectEqMode;
 use crate::reflect::value::value_ref::ReflectValueRef;
+use crate::reflect::FieldPath;
 use crate::reflect::ProtobufValue;
+use crate::reflect::ReflectError;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::RuntimeTypeBox;
 
@@ -16,6 +19,7 @@ pub(crate) trait ReflectRepeated: Sync + 'static + fmt::Debug {
     fn set(&mut self, index: usize, value: ReflectValueBox);
     fn push(&mut self, value: ReflectValueBox);
     fn clear(&mut self);
+    fn swap(&mut self, a: usize, b: usize);
     fn element_type(&self) -> RuntimeTypeBox;
 }
 
@@ -48,6 +52,10 @@ impl<V: ProtobufValue> ReflectRepeated for Vec<V> {
         self.clear()
     }
 
+    fn swap(&mut self, a: usize, b: usize) {
+        <[V]>::swap(self, a, b)
+    }
+
     fn element_type(&self) -> RuntimeTypeBox {
         V::runtime_type_box()
     }
@@ -82,6 +90,10 @@ impl<V: ProtobufValue> ReflectRepeated for [V] {
         panic!("clear is not possible for [V]");
     }
 
+    fn swap(&mut self, a: usize, b: usize) {
+        <[V]>::swap(self, a, b)
+    }
+
     fn element_type(&self) -> RuntimeTypeBox {
         V::runtime_type_box()
     }
@@ -299,24 +311,141 @@ impl<'a> ReflectRepeatedMut<'a> {
     ///
     /// # Panics
     ///
-    /// If index if out of range or value type does not match container element type
+    /// If index if out of range or value type does not match container element type.
+    ///
+    /// Numbers not declared in closed enum are stored as is.
     pub fn set(&mut self, index: usize, value: ReflectValueBox) {
         self.repeated.set(index, value);
     }
 
+    /// Fallible version of [`set`](ReflectRepeatedMut::set).
+    ///
+    /// Also fail if value is a number not declared in closed enum.
+    pub fn try_set(&mut self, index: usize, value: ReflectValueBox) -> Result<(), ReflectError> {
+        ReflectError::check_value(&self.element_type(), &value)?;
+        if index >= self.len() {
+            return Err(ReflectError::IndexOutOfRange {
+                index,
+                len: self.len(),
+            });
+        }
+        self.repeated.set(index, value);
+        Ok(())
+    }
+
     /// Push an item to repeated field.
     ///
     /// # Panics
     ///
-    /// If index if out of range or value type does not match container element type
+    /// If index if out of range or value type does not match container element type.
+    ///
+    /// Numbers not declared in closed enum are stored as is.
     pub fn push(&mut self, value: ReflectValueBox) {
         self.repeated.push(value);
     }
 
+    /// Fallible version of [`push`](ReflectRepeatedMut::push).
+    ///
+    /// Also fail if value is a number not declared in closed enum.
+    pub fn try_push(&mut self, value: ReflectValueBox) -> Result<(), ReflectError> {
+        ReflectError::check_value(&self.element_type(), &value)?;
+        self.repeated.push(value);
+        Ok(())
+    }
+
     /// Self-explanatory
     pub fn clear(&mut self) {
         self.repeated.clear();
     }
+
+    /// Swap two elements.
+    ///
+    /// # Panics
+    ///
+    /// If either index is out of range.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.repeated.swap(a, b);
+    }
+
+    /// Stable sort of repeated message field by the value at `path`
+    /// in each element, e. g. `name` or `options.id`.
+    ///
+    /// Elements where the value is unset are ordered first.
+    /// Numbers are compared numerically with `NaN` last, enums by number,
+    /// strings and bytes lexicographically.
+    ///
+    /// Error is returned if elements are not messages, the path is invalid
+    /// for the element type or the value at the path is a message.
+    pub fn sort_by_path(&mut self, path: &FieldPath) -> Result<(), ReflectError> {
+        match self.element_type() {
+            RuntimeTypeBox::Message(..) => {}
+            t => {
+                return Err(ReflectError::WrongValueType {
+                    expected: "message".to_owned(),
+                    actual: t.to_string(),
+                })
+            }
+        }
+
+        // `order[i]` is the index of element which should be at position `i`
+        let order = {
+            let repeated: &dyn ReflectRepeated = self.repeated;
+            let mut keys = Vec::with_capacity(repeated.len());
+            for i in 0..repeated.len() {
+                let message = repeated.get(i).to_message().and_then(|m| m.as_dyn());
+                let key = path.get(message.expect("message element"))?;
+                if let Some(key @ ReflectValueRef::Message(..)) = &key {
+                    return Err(ReflectError::WrongValueType {
+                        expected: "scalar".to_owned(),
+                        actual: key.get_type().to_string(),
+                    });
+                }
+                keys.push(key);
+            }
+            let mut order: Vec<usize> = (0..keys.len()).collect();
+            order.sort_by(|&a, &b| compare_sort_keys(&keys[a], &keys[b]));
+            order
+        };
+
+        // Apply permutation with swaps, elements before `i` are already in place,
+        // so the element which was at `order[i]` was moved by earlier swaps
+        // along the cycle to the first index not less than `i`.
+        for i in 0..order.len() {
+            let mut j = order[i];
+            while j < i {
+                j = order[j];
+            }
+            self.repeated.swap(i, j);
+        }
+        Ok(())
+    }
+}
+
+/// Order of sort keys of the same type, unset keys are first.
+fn compare_sort_keys(a: &Option<ReflectValueRef>, b: &Option<ReflectValueRef>) -> Ordering {
+    let (a, b) = match (a, b) {
+        (Some(a), Some(b)) => (a, b),
+        (a, b) => return a.is_some().cmp(&b.is_some()),
+    };
+    match (a, b) {
+        (ReflectValueRef::U32(a), ReflectValueRef::U32(b)) => a.cmp(b),
+        (ReflectValueRef::U64(a), ReflectValueRef::U64(b)) => a.cmp(b),
+        (ReflectValueRef::I32(a), ReflectValueRef::I32(b)) => a.cmp(b),
+        (ReflectValueRef::I64(a), ReflectValueRef::I64(b)) => a.cmp(b),
+        (ReflectValueRef::F32(a), ReflectValueRef::F32(b)) => compare_floats(*a as f64, *b as f64),
+        (ReflectValueRef::F64(a), ReflectValueRef::F64(b)) => compare_floats(*a, *b),
+        (ReflectValueRef::Bool(a), ReflectValueRef::Bool(b)) => a.cmp(b),
+        (ReflectValueRef::String(a), ReflectValueRef::String(b)) => a.cmp(b),
+        (ReflectValueRef::Bytes(a), ReflectValueRef::Bytes(b)) => a.cmp(b),
+        (ReflectValueRef::Enum(_, a), ReflectValueRef::Enum(_, b)) => a.cmp(b),
+        _ => Ordering::Equal,
+    }
+}
+
+/// `NaN` values are greater than any other value and equal to each other.
+fn compare_floats(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b)
+        .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
 }
 
 /// Iterator over repeated field.
@@ -420,3 +549,96 @@ impl<'a> PartialEq<ReflectRepeatedMut<'a>> for Vec<ReflectValueBox> {
         self.as_slice() == other
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::reflect::FieldPath;
+    use crate::reflect::FileDescriptor;
+    use crate::reflect::ReflectError;
+    use crate::Message;
+    use crate::MessageDyn;
+
+    fn file(names: &[&str]) -> FileDescriptorProto {
+        let mut file = FileDescriptorProto::new();
+        for (i, name) in names.iter().enumerate() {
+            let mut message = DescriptorProto::new();
+            if !name.is_empty() {
+                message.set_name(name.to_string());
+            }
+            // Remember original position
+            message.options.mut_or_default().set_deprecated(i % 2 == 0);
+            file.message_type.push(message);
+        }
+        file
+    }
+
+    fn names(file: &dyn MessageDyn) -> Vec<String> {
+        let field = file
+            .descriptor_dyn()
+            .get_field_by_name("message_type")
+            .unwrap();
+        let name: FieldPath = "name".parse().unwrap();
+        field
+            .get_repeated(file)
+            .into_iter()
+            .map(|m| match name.get(&*m.to_message().unwrap()).unwrap() {
+                Some(v) => v.to_str().unwrap().to_owned(),
+                None => "-".to_owned(),
+            })
+            .collect()
+    }
+
+    fn sort(file: &mut dyn MessageDyn, path: &str) -> Result<(), ReflectError> {
+        let field = file
+            .descriptor_dyn()
+            .get_field_by_name("message_type")
+            .unwrap();
+        field
+            .mut_repeated(file)
+            .sort_by_path(&path.parse().unwrap())
+    }
+
+    #[test]
+    fn sort_by_path() {
+        let mut f = file(&["d", "b", "", "e", "a", "c", "b"]);
+        sort(&mut f, "name").unwrap();
+        assert_eq!(vec!["-", "a", "b", "b", "c", "d", "e"], names(&f));
+        // Sort is stable
+        assert!(!f.message_type[2].options.get_or_default().get_deprecated());
+        assert!(f.message_type[3].options.get_or_default().get_deprecated());
+
+        sort(&mut f, "options.deprecated").unwrap();
+        assert_eq!(vec!["b", "c", "e", "-", "a", "b", "d"], names(&f));
+
+        assert!(sort(&mut f, "options").is_err());
+        assert!(sort(&mut f, "nope").is_err());
+        let mut f = FileDescriptorProto::new();
+        f.dependency.push("a".to_owned());
+        assert!(FileDescriptorProto::descriptor_static()
+            .get_field_by_name("dependency")
+            .unwrap()
+            .mut_repeated(&mut f)
+            .sort_by_path(&"name".parse().unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn sort_dynamic() {
+        let descriptor = FileDescriptor::new_dynamic(
+            FileDescriptorProto::descriptor_static()
+                .file_descriptor_proto()
+                .clone(),
+            Vec::new(),
+        )
+        .message_by_package_relative_name("FileDescriptorProto")
+        .unwrap();
+        let mut dynamic = descriptor.new_instance();
+        dynamic
+            .merge_from_bytes_dyn(&file(&["c", "a", "b"]).write_to_bytes().unwrap())
+            .unwrap();
+        sort(&mut *dynamic, "name").unwrap();
+        assert_eq!(vec!["a", "b", "c"], names(&*dynamic));
+    }
+}
@@ -0,0 +1,296 @@
+//! Set operations over repeated fields.
+//!
+//! Elements are compared with reflective equality where all `NaN` values
+//! are equal, so a repeated field can be normalized before persistence
+//! regardless of its element type. Message elements can be compared
+//! by a key at a [`FieldPath`] instead of the whole message.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::Hasher;
+
+use crate::message_dyn::MessageDyn;
+use crate::reflect::reflect_hash::hash_value;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::FieldPath;
+use crate::reflect::ReflectEq;
+use crate::reflect::ReflectEqMode;
+use crate::reflect::ReflectError;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::ReflectValueRef;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+
+/// Keys of elements already seen, bucketed by hash.
+///
+/// Missing key (message element with unset key field) is a key too,
+/// so all elements with unset key are equal.
+#[derive(Default)]
+struct KeySet<'a> {
+    buckets: HashMap<u64, Vec<Option<ReflectValueRef<'a>>>>,
+}
+
+impl<'a> KeySet<'a> {
+    fn hash(key: &Option<ReflectValueRef>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        if let Some(key) = key {
+            hasher.write_u8(1);
+            hash_value(key, &mut hasher);
+        }
+        hasher.finish()
+    }
+
+    fn contains(&self, key: &Option<ReflectValueRef<'a>>) -> bool {
+        let mode = ReflectEqMode::nan_equal();
+        match self.buckets.get(&KeySet::hash(key)) {
+            Some(bucket) => bucket.iter().any(|k| match (k, key) {
+                (Some(a), Some(b)) => a.reflect_eq(b, &mode),
+                (None, None) => true,
+                _ => false,
+            }),
+            None => false,
+        }
+    }
+
+    /// Add the key, return `false` if it was already present.
+    fn insert(&mut self, key: Option<ReflectValueRef<'a>>) -> bool {
+        if self.contains(&key) {
+            return false;
+        }
+        self.buckets
+            .entry(KeySet::hash(&key))
+            .or_default()
+            .push(key);
+        true
+    }
+}
+
+/// Key of the repeated field element: the element itself,
+/// or the value at the path in the message element.
+fn element_key<'a>(
+    element: &ReflectValueRef<'a>,
+    key: Option<&FieldPath>,
+) -> Result<Option<ReflectValueRef<'a>>, ReflectError> {
+    let key = match key {
+        Some(key) => key,
+        None => return Ok(Some(element.clone())),
+    };
+    let m = element
+        .to_message()
+        .and_then(|m| m.as_dyn())
+        .expect("repeated message element");
+    key.get(m)
+}
+
+/// Keep elements of `target` passing the filter and then append elements
+/// of `extra`, skipping elements with already seen keys.
+fn replace_unique(
+    m: &mut dyn MessageDyn,
+    field: &FieldDescriptor,
+    key: Option<&FieldPath>,
+    filter: Option<&dyn MessageDyn>,
+    extra: Option<&dyn MessageDyn>,
+) -> Result<(), ReflectError> {
+    if key.is_some() {
+        match field.runtime_field_type() {
+            RuntimeFieldType::Repeated(RuntimeTypeBox::Message(..)) => {}
+            _ => return Err(ReflectError::wrong_field_kind(field, "repeated message")),
+        }
+    }
+
+    let values: Vec<ReflectValueBox> = {
+        let mut allowed = KeySet::default();
+        if let Some(filter) = filter {
+            for e in field.get_repeated(filter) {
+                allowed.insert(element_key(&e, key)?);
+            }
+        }
+
+        let mut seen = KeySet::default();
+        let mut values = Vec::new();
+        let target = field.get_repeated(m);
+        let target_len = target.len();
+        let appended = extra.map(|extra| field.get_repeated(extra));
+        for e in target.into_iter().chain(appended.into_iter().flatten()) {
+            let k = element_key(&e, key)?;
+            if filter.is_some() && !allowed.contains(&k) {
+                continue;
+            }
+            if seen.insert(k) {
+                values.push(e.to_box());
+            }
+        }
+        if values.len() == target_len && extra.is_none() {
+            // Nothing removed
+            return Ok(());
+        }
+        values
+    };
+
+    let mut repeated = field.mut_repeated(m);
+    repeated.clear();
+    for v in values {
+        repeated.push(v);
+    }
+    Ok(())
+}
+
+/// Remove duplicate elements of repeated field, keeping the first occurrence.
+///
+/// # Panics
+///
+/// If this field belongs to a different message type or field is not repeated.
+pub fn dedup_repeated(m: &mut dyn MessageDyn, field: &FieldDescriptor) {
+    replace_unique(m, field, None, None, None).expect("no key");
+}
+
+/// Remove elements of repeated message field with duplicate key,
+/// keeping the first occurrence.
+///
+/// Key is the value at `key` path in each element,
+/// all elements where the key is unset are considered duplicates.
+/// Error is returned if the field is not a repeated message field
+/// or the path is invalid for the element type.
+///
+/// # Panics
+///
+/// If this field belongs to a different message type or field is not repeated.
+pub fn dedup_repeated_by_key(
+    m: &mut dyn MessageDyn,
+    field: &FieldDescriptor,
+    key: &FieldPath,
+) -> Result<(), ReflectError> {
+    replace_unique(m, field, Some(key), None, None)
+}
+
+/// Replace repeated field of `m` with the union of the field values
+/// in `m` and `other`.
+///
+/// Result contains elements of `m` followed by elements of `other`
+/// whose keys are not in `m`, without duplicates.
+/// Elements are compared by the value at `key` path if it is specified,
+/// or by the whole value otherwise.
+///
+/// # Panics
+///
+/// If this field belongs to a different message type or field is not repeated.
+pub fn union_repeated(
+    m: &mut dyn MessageDyn,
+    other: &dyn MessageDyn,
+    field: &FieldDescriptor,
+    key: Option<&FieldPath>,
+) -> Result<(), ReflectError> {
+    replace_unique(m, field, key, None, Some(other))
+}
+
+/// Keep in repeated field of `m` only elements whose keys
+/// are also present in the field of `other`, without duplicates.
+///
+/// Elements are compared by the value at `key` path if it is specified,
+/// or by the whole value otherwise.
+///
+/// # Panics
+///
+/// If this field belongs to a different message type or field is not repeated.
+pub fn intersect_repeated(
+    m: &mut dyn MessageDyn,
+    other: &dyn MessageDyn,
+    field: &FieldDescriptor,
+    key: Option<&FieldPath>,
+) -> Result<(), ReflectError> {
+    replace_unique(m, field, key, Some(other), None)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FieldDescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::well_known_types::ListValue;
+    use crate::well_known_types::Value;
+    use crate::Message;
+
+    fn file(dependencies: &[&str]) -> FileDescriptorProto {
+        let mut file = FileDescriptorProto::new();
+        file.dependency = dependencies.iter().map(|d| d.to_string()).collect();
+        file
+    }
+
+    fn dependency() -> FieldDescriptor {
+        FileDescriptorProto::descriptor_static()
+            .get_field_by_name("dependency")
+            .unwrap()
+    }
+
+    fn message_type(names: &[(&str, &str)]) -> FileDescriptorProto {
+        let mut file = FileDescriptorProto::new();
+        for (name, field_name) in names {
+            let mut message = DescriptorProto::new();
+            message.set_name(name.to_string());
+            let mut field = FieldDescriptorProto::new();
+            field.set_name(field_name.to_string());
+            message.field.push(field);
+            file.message_type.push(message);
+        }
+        file
+    }
+
+    #[test]
+    fn dedup() {
+        let mut f = file(&["a", "b", "a", "c", "b"]);
+        dedup_repeated(&mut f, &dependency());
+        assert_eq!(vec!["a", "b", "c"], f.dependency);
+
+        let field = ListValue::descriptor_static()
+            .get_field_by_name("values")
+            .unwrap();
+        let mut list = ListValue::new();
+        for &v in &[f64::NAN, 1.0, f64::NAN, 1.0] {
+            let mut value = Value::new();
+            value.set_number_value(v);
+            list.values.push(value);
+        }
+        dedup_repeated(&mut list, &field);
+        assert_eq!(2, list.values.len());
+        assert!(list.values[0].get_number_value().is_nan());
+    }
+
+    #[test]
+    fn dedup_by_key() {
+        let field = FileDescriptorProto::descriptor_static()
+            .get_field_by_name("message_type")
+            .unwrap();
+        let mut f = message_type(&[("A", "x"), ("B", "y"), ("A", "z")]);
+        dedup_repeated_by_key(&mut f, &field, &"name".parse().unwrap()).unwrap();
+        assert_eq!(message_type(&[("A", "x"), ("B", "y")]), f);
+
+        let mut f = message_type(&[("A", "x"), ("B", "x")]);
+        dedup_repeated_by_key(&mut f, &field, &"field[0].name".parse().unwrap()).unwrap();
+        assert_eq!(message_type(&[("A", "x")]), f);
+
+        assert!(dedup_repeated_by_key(&mut f, &field, &"nope".parse().unwrap()).is_err());
+        assert!(dedup_repeated_by_key(&mut f, &dependency(), &"name".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn union_intersect() {
+        let mut f = file(&["a", "b", "a"]);
+        union_repeated(&mut f, &file(&["c", "b", "d"]), &dependency(), None).unwrap();
+        assert_eq!(vec!["a", "b", "c", "d"], f.dependency);
+
+        intersect_repeated(&mut f, &file(&["d", "x", "b"]), &dependency(), None).unwrap();
+        assert_eq!(vec!["b", "d"], f.dependency);
+
+        let field = FileDescriptorProto::descriptor_static()
+            .get_field_by_name("message_type")
+            .unwrap();
+        let name = "name".parse().unwrap();
+        let mut f = message_type(&[("A", "x"), ("B", "y")]);
+        let other = message_type(&[("B", "z"), ("C", "z")]);
+        union_repeated(&mut f, &other, &field, Some(&name)).unwrap();
+        assert_eq!(message_type(&[("A", "x"), ("B", "y"), ("C", "z")]), f);
+        intersect_repeated(&mut f, &other, &field, Some(&name)).unwrap();
+        assert_eq!(message_type(&[("B", "y"), ("C", "z")]), f);
+    }
+}
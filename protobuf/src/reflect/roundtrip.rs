@@ -0,0 +1,375 @@
+use std::fmt;
+
+use crate::reflect::FieldDescriptor;
+use crate::reflect::FieldPath;
+use crate::reflect::FieldPathElement;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::wire_format;
+use crate::wire_scan::message_layout;
+use crate::wire_scan::FieldSpan;
+use crate::ProtobufResult;
+
+/// How a field changed after parse and serialize.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundtripChangeKind {
+    /// Field is not known to the message type and was not preserved,
+    /// e. g. unknown group.
+    UnknownFieldDropped,
+    /// Known field is missing in the output,
+    /// e. g. explicitly written default value of proto3 field.
+    FieldDropped,
+    /// Field is present in the output, but not in the input.
+    FieldAdded,
+    /// Repeated field switched between packed and unpacked encoding.
+    PackingChanged,
+    /// Field is written at different position relative to other fields,
+    /// or occurrences of repeated field are written in different order.
+    Reordered,
+    /// Field is encoded differently, e. g. several occurrences
+    /// of singular field were merged, or varint was not encoded minimally.
+    ValueChanged,
+}
+
+impl fmt::Display for RoundtripChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            RoundtripChangeKind::UnknownFieldDropped => "unknown field dropped",
+            RoundtripChangeKind::FieldDropped => "field dropped",
+            RoundtripChangeKind::FieldAdded => "field added",
+            RoundtripChangeKind::PackingChanged => "packing changed",
+            RoundtripChangeKind::Reordered => "reordered",
+            RoundtripChangeKind::ValueChanged => "value changed",
+        })
+    }
+}
+
+/// Field changed by parse and serialize.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundtripChange {
+    /// Path to the field. Unknown fields are named by their numbers.
+    pub path: FieldPath,
+    /// Number of the changed field.
+    pub field_number: u32,
+    /// What changed.
+    pub kind: RoundtripChangeKind,
+}
+
+impl fmt::Display for RoundtripChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.kind)
+    }
+}
+
+/// Result of [`check_roundtrip`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundtripReport {
+    /// Bytes of reserialized message.
+    pub output: Vec<u8>,
+    /// Fields changed by the round trip.
+    pub changes: Vec<RoundtripChange>,
+}
+
+impl RoundtripReport {
+    /// Serialized message is identical to the input.
+    pub fn is_identical(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Parse message of given type, serialize it again,
+/// and report which fields are not passed through unchanged.
+///
+/// Nested messages are compared recursively when the number
+/// of their occurrences is unchanged.
+///
+/// Error is returned if the input cannot be parsed or the parsed message
+/// cannot be serialized (e. g. because required fields are missing).
+pub fn check_roundtrip(
+    descriptor: &MessageDescriptor,
+    bytes: &[u8],
+) -> ProtobufResult<RoundtripReport> {
+    let mut message = descriptor.new_instance();
+    message.merge_from_bytes_dyn(bytes)?;
+    let output = message.write_to_bytes_dyn()?;
+
+    let mut changes = Vec::new();
+    compare_messages(
+        descriptor,
+        bytes,
+        &output,
+        &mut FieldPath::new(),
+        &mut changes,
+    )?;
+    Ok(RoundtripReport { output, changes })
+}
+
+/// Occurrences of a field in serialized message.
+struct Occurrences<'a> {
+    /// Serialized message.
+    message: &'a [u8],
+    spans: Vec<&'a FieldSpan>,
+}
+
+impl<'a> Occurrences<'a> {
+    fn new(message: &'a [u8], layout: &'a [FieldSpan], number: u32) -> Occurrences<'a> {
+        Occurrences {
+            message,
+            spans: layout.iter().filter(|f| f.field_number == number).collect(),
+        }
+    }
+
+    /// Bytes of each occurrence including tag.
+    fn fields(&self) -> Vec<&'a [u8]> {
+        self.spans
+            .iter()
+            .map(|f| &self.message[f.field.clone()])
+            .collect()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    fn is_packed(&self) -> bool {
+        self.spans
+            .iter()
+            .any(|f| f.wire_type == wire_format::WireTypeLengthDelimited)
+    }
+}
+
+/// Indices of fields with given number among unchanged fields.
+fn positions(layout: &[FieldSpan], number: u32, unchanged: &[u32]) -> Vec<usize> {
+    layout
+        .iter()
+        .filter(|f| unchanged.contains(&f.field_number))
+        .enumerate()
+        .filter(|(_, f)| f.field_number == number)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn compare_messages(
+    descriptor: &MessageDescriptor,
+    input: &[u8],
+    output: &[u8],
+    path: &mut FieldPath,
+    changes: &mut Vec<RoundtripChange>,
+) -> ProtobufResult<()> {
+    if input == output {
+        return Ok(());
+    }
+
+    let input_layout = message_layout(input)?;
+    let output_layout = message_layout(output)?;
+
+    let mut numbers: Vec<u32> = input_layout
+        .iter()
+        .chain(&output_layout)
+        .map(|f| f.field_number)
+        .collect();
+    numbers.sort();
+    numbers.dedup();
+
+    let mut unchanged = Vec::new();
+    for &number in &numbers {
+        let ins = Occurrences::new(input, &input_layout, number);
+        let outs = Occurrences::new(output, &output_layout, number);
+        if ins.fields() == outs.fields() {
+            unchanged.push(number);
+            continue;
+        }
+
+        let field = descriptor.get_field_by_number(number);
+        path.push(field_path_element(field.as_ref(), number));
+        compare_field(field.as_ref(), number, &ins, &outs, path, changes)?;
+        path.pop();
+    }
+
+    // Fields which are not changed themselves, but moved relative to each other
+    for &number in &unchanged {
+        if positions(&input_layout, number, &unchanged)
+            != positions(&output_layout, number, &unchanged)
+        {
+            path.push(field_path_element(
+                descriptor.get_field_by_number(number).as_ref(),
+                number,
+            ));
+            changes.push(RoundtripChange {
+                path: path.clone(),
+                field_number: number,
+                kind: RoundtripChangeKind::Reordered,
+            });
+            path.pop();
+        }
+    }
+
+    Ok(())
+}
+
+fn field_path_element(field: Option<&FieldDescriptor>, number: u32) -> FieldPathElement {
+    match field {
+        Some(field) => FieldPathElement::Field(field.get_name().to_owned()),
+        None => FieldPathElement::Field(number.to_string()),
+    }
+}
+
+/// Message type of singular or repeated message field.
+fn message_type(field: &FieldDescriptor) -> Option<MessageDescriptor> {
+    match field.runtime_field_type() {
+        RuntimeFieldType::Singular(RuntimeTypeBox::Message(m))
+        | RuntimeFieldType::Repeated(RuntimeTypeBox::Message(m)) => Some(m),
+        _ => None,
+    }
+}
+
+fn compare_field(
+    field: Option<&FieldDescriptor>,
+    number: u32,
+    ins: &Occurrences,
+    outs: &Occurrences,
+    path: &mut FieldPath,
+    changes: &mut Vec<RoundtripChange>,
+) -> ProtobufResult<()> {
+    let mut sorted_ins = ins.fields();
+    let mut sorted_outs = outs.fields();
+    sorted_ins.sort();
+    sorted_outs.sort();
+
+    let kind = match field {
+        _ if outs.is_empty() && field.is_none() => RoundtripChangeKind::UnknownFieldDropped,
+        _ if outs.is_empty() => RoundtripChangeKind::FieldDropped,
+        _ if ins.is_empty() => RoundtripChangeKind::FieldAdded,
+        Some(field) if field.is_repeated() && ins.is_packed() != outs.is_packed() => {
+            RoundtripChangeKind::PackingChanged
+        }
+        _ if sorted_ins == sorted_outs => RoundtripChangeKind::Reordered,
+        Some(field) => match message_type(field) {
+            Some(m)
+                if ins.spans.len() == outs.spans.len()
+                    && ins
+                        .spans
+                        .iter()
+                        .zip(&outs.spans)
+                        .all(|(i, o)| i.wire_type == o.wire_type) =>
+            {
+                for (index, (i, o)) in ins.spans.iter().zip(&outs.spans).enumerate() {
+                    if field.is_repeated() {
+                        path.push(FieldPathElement::Index(index));
+                    }
+                    compare_messages(
+                        &m,
+                        &ins.message[i.value.clone()],
+                        &outs.message[o.value.clone()],
+                        path,
+                        changes,
+                    )?;
+                    if field.is_repeated() {
+                        path.pop();
+                    }
+                }
+                return Ok(());
+            }
+            _ => RoundtripChangeKind::ValueChanged,
+        },
+        None => RoundtripChangeKind::ValueChanged,
+    };
+
+    changes.push(RoundtripChange {
+        path: path.clone(),
+        field_number: number,
+        kind,
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::check_roundtrip;
+    use super::RoundtripChangeKind;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::hex::decode_hex;
+    use crate::well_known_types::Duration;
+    use crate::Message;
+
+    fn changes(descriptor_bytes: &str) -> Vec<(String, RoundtripChangeKind)> {
+        let report = check_roundtrip(
+            &FileDescriptorProto::descriptor_static(),
+            &decode_hex(descriptor_bytes),
+        )
+        .unwrap();
+        report
+            .changes
+            .iter()
+            .map(|c| (c.path.to_string(), c.kind))
+            .collect()
+    }
+
+    #[test]
+    fn identical() {
+        let mut m = FileDescriptorProto::new();
+        m.set_name("a.proto".to_owned());
+        m.public_dependency.push(1);
+        let bytes = m.write_to_bytes().unwrap();
+        let report = check_roundtrip(&FileDescriptorProto::descriptor_static(), &bytes).unwrap();
+        assert!(report.is_identical());
+        assert_eq!(bytes, report.output);
+    }
+
+    #[test]
+    fn reordered() {
+        // package = "p", name = "n"
+        assert_eq!(
+            vec![
+                ("name".to_owned(), RoundtripChangeKind::Reordered),
+                ("package".to_owned(), RoundtripChangeKind::Reordered),
+            ],
+            changes("12 01 70 0a 01 6e")
+        );
+    }
+
+    #[test]
+    fn unknown_group_dropped() {
+        // name = "n", unknown group 100 { 1: 1 }
+        assert_eq!(
+            vec![("100".to_owned(), RoundtripChangeKind::UnknownFieldDropped)],
+            changes("0a 01 6e a3 06 08 01 a4 06")
+        );
+    }
+
+    #[test]
+    fn packing_changed() {
+        // packed public_dependency = [1, 2]
+        assert_eq!(
+            vec![(
+                "public_dependency".to_owned(),
+                RoundtripChangeKind::PackingChanged
+            )],
+            changes("52 02 01 02")
+        );
+    }
+
+    #[test]
+    fn nested_value_changed() {
+        // message_type { name = "a", name = "b" }
+        assert_eq!(
+            vec![(
+                "message_type[0].name".to_owned(),
+                RoundtripChangeKind::ValueChanged
+            )],
+            changes("22 06 0a 01 61 0a 01 62")
+        );
+    }
+
+    #[test]
+    fn default_value_dropped() {
+        // proto3 seconds = 0
+        let report = check_roundtrip(&Duration::descriptor_static(), &decode_hex("08 00")).unwrap();
+        assert_eq!(1, report.changes.len());
+        assert_eq!("seconds", report.changes[0].path.to_string());
+        assert_eq!(1, report.changes[0].field_number);
+        assert_eq!(RoundtripChangeKind::FieldDropped, report.changes[0].kind);
+        assert!(report.output.is_empty());
+    }
+}
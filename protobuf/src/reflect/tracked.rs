@@ -0,0 +1,205 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::message_dyn::MessageDyn;
+use crate::reflect::FieldPath;
+use crate::reflect::FieldPathElement;
+use crate::reflect::ReflectError;
+use crate::reflect::ReflectValueBox;
+use crate::well_known_types::FieldMask;
+
+/// Message wrapper which records paths of fields modified
+/// since the last checkpoint.
+///
+/// Changes are reported as a [`FieldMask`], e. g. to send
+/// only modified fields in an update request:
+///
+/// ```
+/// # use protobuf::descriptor::FileDescriptorProto;
+/// # use protobuf::reflect::ReflectValueBox;
+/// # use protobuf::reflect::TrackedMessage;
+/// let mut tracked = TrackedMessage::new(Box::new(FileDescriptorProto::new()));
+/// tracked
+///     .set(&"options.java_package".parse().unwrap(), ReflectValueBox::String("a".to_owned()))
+///     .unwrap();
+/// tracked
+///     .set(&"name".parse().unwrap(), ReflectValueBox::String("b".to_owned()))
+///     .unwrap();
+/// assert_eq!(vec!["name", "options.java_package"], tracked.changes().paths);
+/// ```
+///
+/// Only modifications made through the wrapper are recorded.
+/// Field mask paths cannot address elements of repeated and map fields,
+/// so modification of an element is recorded as modification of the whole field.
+pub struct TrackedMessage {
+    message: Box<dyn MessageDyn>,
+    /// Field mask paths, sorted.
+    changed: BTreeSet<String>,
+}
+
+impl fmt::Debug for TrackedMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TrackedMessage")
+            .field("message", &self.message)
+            .field("changed", &self.changed)
+            .finish()
+    }
+}
+
+/// Field mask path of the field path: field names up to
+/// the first repeated element or map value.
+fn mask_path(path: &FieldPath) -> String {
+    let mut r = String::new();
+    for element in path.elements() {
+        match element {
+            FieldPathElement::Field(name) => {
+                if !r.is_empty() {
+                    r.push('.');
+                }
+                r.push_str(name);
+            }
+            FieldPathElement::Index(..) | FieldPathElement::MapKey(..) => break,
+        }
+    }
+    r
+}
+
+impl TrackedMessage {
+    /// Start tracking changes of the message, no fields are modified initially.
+    pub fn new(message: Box<dyn MessageDyn>) -> TrackedMessage {
+        TrackedMessage {
+            message,
+            changed: BTreeSet::new(),
+        }
+    }
+
+    /// The tracked message.
+    pub fn message(&self) -> &dyn MessageDyn {
+        &*self.message
+    }
+
+    /// Stop tracking and return the message.
+    pub fn into_message(self) -> Box<dyn MessageDyn> {
+        self.message
+    }
+
+    /// Set value at the path, see [`FieldPath::set`].
+    ///
+    /// The path is recorded as modified if the value is set.
+    pub fn set(&mut self, path: &FieldPath, value: ReflectValueBox) -> Result<(), ReflectError> {
+        path.set(&mut *self.message, value)?;
+        self.mark_changed(path);
+        Ok(())
+    }
+
+    /// Clear value at the path, see [`FieldPath::clear`].
+    ///
+    /// The path is recorded as modified if the path is valid.
+    pub fn clear(&mut self, path: &FieldPath) -> Result<(), ReflectError> {
+        path.clear(&mut *self.message)?;
+        self.mark_changed(path);
+        Ok(())
+    }
+
+    /// Modify the message with a function and record the path as modified.
+    ///
+    /// The function may modify any field, but only the path is recorded.
+    pub fn modify<R>(&mut self, path: &FieldPath, f: impl FnOnce(&mut dyn MessageDyn) -> R) -> R {
+        self.mark_changed(path);
+        f(&mut *self.message)
+    }
+
+    /// Record the path as modified without modifying the message.
+    pub fn mark_changed(&mut self, path: &FieldPath) {
+        self.changed.insert(mask_path(path));
+    }
+
+    /// Is any field modified since the last checkpoint.
+    pub fn is_changed(&self) -> bool {
+        !self.changed.is_empty()
+    }
+
+    /// Paths of fields modified since the last checkpoint.
+    ///
+    /// Paths are sorted, a path is omitted if its parent message field is
+    /// also modified: if both `a` and `a.b` are modified, only `a` is reported.
+    pub fn changes(&self) -> FieldMask {
+        let mut mask = FieldMask::new();
+        for path in &self.changed {
+            let covered = match mask.paths.last() {
+                Some(last) => path.starts_with(&format!("{}.", last)),
+                None => false,
+            };
+            if !covered {
+                mask.paths.push(path.clone());
+            }
+        }
+        mask
+    }
+
+    /// Forget recorded changes, subsequent modifications are reported
+    /// relative to the current state.
+    pub fn checkpoint(&mut self) {
+        self.changed.clear();
+    }
+
+    /// Return changes since the last checkpoint and start a new checkpoint.
+    pub fn take_changes(&mut self) -> FieldMask {
+        let changes = self.changes();
+        self.checkpoint();
+        changes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::FileDescriptorProto;
+
+    fn path(path: &str) -> FieldPath {
+        path.parse().unwrap()
+    }
+
+    #[test]
+    fn changes() {
+        let mut tracked = TrackedMessage::new(Box::new(FileDescriptorProto::new()));
+        assert!(!tracked.is_changed());
+
+        tracked
+            .set(&path("package"), ReflectValueBox::String("p".to_owned()))
+            .unwrap();
+        tracked
+            .set(
+                &path("options.java_package"),
+                ReflectValueBox::String("j".to_owned()),
+            )
+            .unwrap();
+        tracked.modify(&path("dependency[0]"), |m| {
+            let file: &mut FileDescriptorProto = m.downcast_mut().unwrap();
+            file.dependency.push("d.proto".to_owned());
+        });
+        assert!(tracked.set(&path("nope"), ReflectValueBox::I32(1)).is_err());
+        assert_eq!(
+            vec!["dependency", "options.java_package", "package"],
+            tracked.changes().paths
+        );
+
+        tracked.clear(&path("options")).unwrap();
+        assert_eq!(
+            vec!["dependency", "options", "package"],
+            tracked.take_changes().paths
+        );
+        assert!(!tracked.is_changed());
+
+        tracked.mark_changed(&path("options"));
+        tracked.mark_changed(&path("optionsx"));
+        assert_eq!(vec!["options", "optionsx"], tracked.changes().paths);
+
+        let mut expected = FileDescriptorProto::new();
+        expected.set_package("p".to_owned());
+        expected.dependency.push("d.proto".to_owned());
+        let message = tracked.into_message();
+        let file: &FileDescriptorProto = message.downcast_ref().unwrap();
+        assert_eq!(&expected, file);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::message_dyn::MessageDyn;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::ReflectValueRef;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+
+/// Suffix appended to values shortened by [`truncate_large_fields`].
+pub const TRUNCATED_MARKER: &str = "...<truncated>";
+
+fn truncate_str(s: &str, max_len: usize) -> Option<String> {
+    if s.len() <= max_len {
+        return None;
+    }
+    let mut end = max_len;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    let mut r = String::with_capacity(end + TRUNCATED_MARKER.len());
+    r.push_str(&s[..end]);
+    r.push_str(TRUNCATED_MARKER);
+    Some(r)
+}
+
+fn truncate_bytes(b: &[u8], max_len: usize) -> Option<Vec<u8>> {
+    if b.len() <= max_len {
+        return None;
+    }
+    let mut r = Vec::with_capacity(max_len + TRUNCATED_MARKER.len());
+    r.extend_from_slice(&b[..max_len]);
+    r.extend_from_slice(TRUNCATED_MARKER.as_bytes());
+    Some(r)
+}
+
+/// Return truncated copy of the value if it needs to be modified.
+fn truncate_value(value: ReflectValueRef, max_len: usize) -> Option<ReflectValueBox> {
+    match value {
+        ReflectValueRef::String(s) => truncate_str(s, max_len).map(ReflectValueBox::from),
+        ReflectValueRef::Bytes(b) => truncate_bytes(b, max_len).map(ReflectValueBox::from),
+        ReflectValueRef::Message(m) => {
+            let mut m = m.clone_box();
+            truncate_large_fields(&mut *m, max_len);
+            Some(ReflectValueBox::from(m))
+        }
+        _ => None,
+    }
+}
+
+fn may_need_truncation(t: &RuntimeTypeBox) -> bool {
+    matches!(
+        t,
+        RuntimeTypeBox::String | RuntimeTypeBox::VecU8 | RuntimeTypeBox::Message(..)
+    )
+}
+
+fn truncate_field(m: &mut dyn MessageDyn, field: &FieldDescriptor, max_len: usize) {
+    match field.runtime_field_type() {
+        RuntimeFieldType::Singular(t) => {
+            if !may_need_truncation(&t) {
+                return;
+            }
+            if let RuntimeTypeBox::Message(..) = t {
+                if field.has_field(m) {
+                    truncate_large_fields(field.mut_message(m), max_len);
+                }
+                return;
+            }
+            let truncated = match field.get_singular(m) {
+                Some(v) => truncate_value(v, max_len),
+                None => None,
+            };
+            if let Some(truncated) = truncated {
+                field.set_singular_field(m, truncated);
+            }
+        }
+        RuntimeFieldType::Repeated(t) => {
+            if !may_need_truncation(&t) {
+                return;
+            }
+            let truncated: Vec<(usize, ReflectValueBox)> = field
+                .get_repeated(m)
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, v)| truncate_value(v, max_len).map(|v| (i, v)))
+                .collect();
+            if truncated.is_empty() {
+                return;
+            }
+            let mut repeated = field.mut_repeated(m);
+            for (i, v) in truncated {
+                repeated.set(i, v);
+            }
+        }
+        RuntimeFieldType::Map(_, v) => {
+            if !may_need_truncation(&v) {
+                return;
+            }
+            let truncated: Vec<(ReflectValueBox, ReflectValueBox)> = match field.get_reflect(m) {
+                ReflectFieldRef::Map(map) => (&map)
+                    .into_iter()
+                    .filter_map(|(k, v)| truncate_value(v, max_len).map(|v| (k.to_box(), v)))
+                    .collect(),
+                _ => unreachable!(),
+            };
+            if truncated.is_empty() {
+                return;
+            }
+            let mut map = field.mut_map(m);
+            for (k, v) in truncated {
+                map.insert(k, v);
+            }
+        }
+    }
+}
+
+/// Shorten all `string` and `bytes` values longer than `max_len` bytes.
+///
+/// Oversized values are replaced with their first `max_len` bytes
+/// followed by [`TRUNCATED_MARKER`]. Strings are cut at a character boundary,
+/// so the prefix may be slightly shorter than `max_len`.
+/// Nested messages (singular, repeated and map values) are processed recursively.
+/// Map keys are left untouched.
+///
+/// This is useful to produce bounded-size copies of huge messages for debug output.
+pub fn truncate_large_fields(m: &mut dyn MessageDyn, max_len: usize) {
+    let descriptor = m.descriptor_dyn();
+    for field in descriptor.fields() {
+        truncate_field(m, &field, max_len);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_truncate_str() {
+        assert_eq!(None, truncate_str("abc", 3));
+        assert_eq!(
+            Some(format!("ab{}", TRUNCATED_MARKER)),
+            truncate_str("abc", 2)
+        );
+        // do not split multibyte characters
+        assert_eq!(
+            Some(format!("a{}", TRUNCATED_MARKER)),
+            truncate_str("aя", 2)
+        );
+    }
+
+    #[test]
+    fn test_truncate_bytes() {
+        assert_eq!(None, truncate_bytes(b"abc", 5));
+        let mut expected = b"a".to_vec();
+        expected.extend_from_slice(TRUNCATED_MARKER.as_bytes());
+        assert_eq!(Some(expected), truncate_bytes(b"abc", 1));
+    }
+}
@@ -54,7 +54,7 @@ pub trait ProtobufType: Send + Sync + Clone + 'static {
     fn compute_size_with_length_delimiter(value: &Self::ProtobufValue) -> u32 {
         let size = Self::compute_size(value);
         if Self::WIRE_TYPE == WireType::WireTypeLengthDelimited {
-            rt::compute_raw_varint32_size(size) + size
+            rt::compute_raw_varint32_size(size).saturating_add(size)
         } else {
             size
         }
@@ -71,7 +71,7 @@ pub trait ProtobufType: Send + Sync + Clone + 'static {
     fn get_cached_size_with_length_delimiter(value: &Self::ProtobufValue) -> u32 {
         let size = Self::get_cached_size(value);
         if Self::WIRE_TYPE == WireType::WireTypeLengthDelimited {
-            rt::compute_raw_varint32_size(size) + size
+            rt::compute_raw_varint32_size(size).saturating_add(size)
         } else {
             size
         }
@@ -550,7 +550,7 @@ impl ProtobufType for ProtobufTypeString {
     }
 
     fn compute_size(value: &String) -> u32 {
-        value.len() as u32
+        rt::saturating_size(value.len() as u64)
     }
 
     fn write_with_cached_size(
@@ -576,7 +576,7 @@ impl ProtobufType for ProtobufTypeBytes {
     }
 
     fn compute_size(value: &Vec<u8>) -> u32 {
-        value.len() as u32
+        rt::saturating_size(value.len() as u64)
     }
 
     fn write_with_cached_size(
@@ -603,7 +603,7 @@ impl ProtobufType for ProtobufTypeCarllercheBytes {
     }
 
     fn compute_size(value: &Bytes) -> u32 {
-        value.len() as u32
+        rt::saturating_size(value.len() as u64)
     }
 
     fn write_with_cached_size(
@@ -630,7 +630,7 @@ impl ProtobufType for ProtobufTypeCarllercheChars {
     }
 
     fn compute_size(value: &Chars) -> u32 {
-        value.len() as u32
+        rt::saturating_size(value.len() as u64)
     }
 
     fn write_with_cached_size(
@@ -1,14 +1,17 @@
+use std::fmt;
+
 use crate::reflect::message::message_ref::MessageRef;
 use crate::reflect::value::value_ref::ReflectValueMut;
 use crate::reflect::value::value_ref::ReflectValueRef;
 use crate::reflect::EnumDescriptor;
 use crate::reflect::EnumValueDescriptor;
 use crate::reflect::ProtobufValue;
+use crate::reflect::ReflectError;
 use crate::reflect::RuntimeTypeBox;
 use crate::MessageDyn;
 
 /// Owner value of any elementary type
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub enum ReflectValueBox {
     /// `u32`
     U32(u32),
@@ -34,6 +37,14 @@ pub enum ReflectValueBox {
     Message(Box<dyn MessageDyn>),
 }
 
+/// Same as `Debug` of [`ReflectValueRef`]: enums are printed by name,
+/// messages in text format and long bytes are truncated.
+impl fmt::Debug for ReflectValueBox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.as_value_ref(), f)
+    }
+}
+
 impl From<u32> for ReflectValueBox {
     fn from(v: u32) -> Self {
         ReflectValueBox::U32(v)
@@ -113,6 +124,29 @@ fn _assert_value_box_send_sync() {
 }
 
 impl ReflectValueBox {
+    /// Enum value with given number.
+    ///
+    /// Return `None` if enum [is closed](EnumDescriptor::is_closed)
+    /// and the number is not declared in the enum.
+    pub fn enum_checked(descriptor: EnumDescriptor, number: i32) -> Option<ReflectValueBox> {
+        if descriptor.is_closed() && !descriptor.contains_number(number) {
+            return None;
+        }
+        Some(ReflectValueBox::Enum(descriptor, number))
+    }
+
+    pub(crate) fn check_enum_number_allowed(&self) -> Result<(), ReflectError> {
+        match self {
+            ReflectValueBox::Enum(d, v) if d.is_closed() && !d.contains_number(*v) => {
+                Err(ReflectError::UnknownEnumValue {
+                    enum_name: d.full_name().to_owned(),
+                    number: *v,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Type of this value.
     pub fn get_type(&self) -> RuntimeTypeBox {
         self.as_value_ref().get_type()
@@ -1,3 +1,4 @@
+use std::fmt;
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::mem;
@@ -11,10 +12,45 @@ use crate::reflect::ProtobufValue;
 use crate::reflect::ReflectEq;
 use crate::reflect::ReflectEqMode;
 use crate::reflect::RuntimeTypeBox;
+use crate::text_format;
 use crate::MessageDyn;
 
+/// Kind of a value, coarser than its type.
+///
+/// Useful to handle values of several types uniformly,
+/// for example, to aggregate numeric fields of any numeric type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReflectValueKind {
+    /// `i32` or `i64`
+    SignedInt,
+    /// `u32` or `u64`
+    UnsignedInt,
+    /// `f32` or `f64`
+    Float,
+    /// `bool`
+    Bool,
+    /// `string`
+    String,
+    /// `bytes`
+    Bytes,
+    /// `enum`
+    Enum,
+    /// `message`
+    Message,
+}
+
+impl ReflectValueKind {
+    /// Is this integer or floating point kind.
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            ReflectValueKind::SignedInt | ReflectValueKind::UnsignedInt | ReflectValueKind::Float
+        )
+    }
+}
+
 /// A reference to a value
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub enum ReflectValueRef<'a> {
     /// `u32`
     U32(u32),
@@ -58,6 +94,69 @@ impl<'a> ReflectValueRef<'a> {
         }
     }
 
+    /// Get kind of this value.
+    pub fn kind(&self) -> ReflectValueKind {
+        match self {
+            ReflectValueRef::I32(..) | ReflectValueRef::I64(..) => ReflectValueKind::SignedInt,
+            ReflectValueRef::U32(..) | ReflectValueRef::U64(..) => ReflectValueKind::UnsignedInt,
+            ReflectValueRef::F32(..) | ReflectValueRef::F64(..) => ReflectValueKind::Float,
+            ReflectValueRef::Bool(..) => ReflectValueKind::Bool,
+            ReflectValueRef::String(..) => ReflectValueKind::String,
+            ReflectValueRef::Bytes(..) => ReflectValueKind::Bytes,
+            ReflectValueRef::Enum(..) => ReflectValueKind::Enum,
+            ReflectValueRef::Message(..) => ReflectValueKind::Message,
+        }
+    }
+
+    /// Convert integer, floating point, `bool` or enum value to `i64`.
+    ///
+    /// `u64` values greater than `i64::MAX` saturate, floating point values
+    /// are truncated towards zero and saturate (`NaN` is converted to zero),
+    /// `bool` is converted to `0` or `1`, and enum value is converted to its number.
+    ///
+    /// Return `None` for strings, bytes and messages.
+    pub fn to_i64_lossy(&self) -> Option<i64> {
+        match *self {
+            ReflectValueRef::I32(v) => Some(v as i64),
+            ReflectValueRef::I64(v) => Some(v),
+            ReflectValueRef::U32(v) => Some(v as i64),
+            ReflectValueRef::U64(v) => Some(if v > i64::MAX as u64 {
+                i64::MAX
+            } else {
+                v as i64
+            }),
+            ReflectValueRef::F32(v) => Some(v as i64),
+            ReflectValueRef::F64(v) => Some(v as i64),
+            ReflectValueRef::Bool(v) => Some(v as i64),
+            ReflectValueRef::Enum(_, v) => Some(v as i64),
+            ReflectValueRef::String(..)
+            | ReflectValueRef::Bytes(..)
+            | ReflectValueRef::Message(..) => None,
+        }
+    }
+
+    /// Convert integer, floating point, `bool` or enum value to `f64`.
+    ///
+    /// Large 64-bit integers are rounded to the nearest representable value,
+    /// `bool` is converted to `0.0` or `1.0`, and enum value is converted to its number.
+    ///
+    /// Return `None` for strings, bytes and messages.
+    pub fn to_f64_lossy(&self) -> Option<f64> {
+        match *self {
+            ReflectValueRef::I32(v) => Some(v as f64),
+            ReflectValueRef::I64(v) => Some(v as f64),
+            ReflectValueRef::U32(v) => Some(v as f64),
+            ReflectValueRef::U64(v) => Some(v as f64),
+            ReflectValueRef::F32(v) => Some(v as f64),
+            ReflectValueRef::F64(v) => Some(v),
+            ReflectValueRef::Bool(v) => Some(v as i32 as f64),
+            ReflectValueRef::Enum(_, v) => Some(v as f64),
+            ReflectValueRef::String(..)
+            | ReflectValueRef::Bytes(..)
+            | ReflectValueRef::Message(..) => None,
+        }
+    }
+
     /// Value is "non-zero"?
     pub(crate) fn is_non_zero(&self) -> bool {
         match self {
@@ -190,6 +289,82 @@ pub enum ReflectValueMut<'a> {
     Message(&'a mut dyn MessageDyn),
 }
 
+/// Bytes values longer than this are truncated in `Debug` output.
+const DEBUG_BYTES_LIMIT: usize = 32;
+/// Messages nested deeper than this are not printed in `Debug` output.
+const DEBUG_MESSAGE_MAX_DEPTH: usize = 8;
+/// Text of messages longer than this is truncated in `Debug` output.
+const DEBUG_MESSAGE_LIMIT: usize = 1024;
+
+/// `Debug` of a message: full name and fields in text format.
+///
+/// Output is limited in depth and length, so debug printing
+/// of a huge message does not flood logs.
+pub(crate) fn debug_message(m: &dyn MessageDyn, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let options = text_format::PrintOptions {
+        max_depth: Some(DEBUG_MESSAGE_MAX_DEPTH),
+        ..Default::default()
+    };
+    let text = text_format::print_to_string_with_options(m, &options);
+    let name = m.descriptor_dyn();
+    if text.is_empty() {
+        return write!(f, "{} {{}}", name.full_name());
+    }
+    if text.len() <= DEBUG_MESSAGE_LIMIT {
+        return write!(f, "{} {{ {} }}", name.full_name(), text);
+    }
+    let mut end = DEBUG_MESSAGE_LIMIT;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    write!(
+        f,
+        "{} {{ {}... {} chars }}",
+        name.full_name(),
+        &text[..end],
+        text.len()
+    )
+}
+
+impl<'a> fmt::Debug for ReflectValueRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReflectValueRef::U32(v) => write!(f, "U32({:?})", v),
+            ReflectValueRef::U64(v) => write!(f, "U64({:?})", v),
+            ReflectValueRef::I32(v) => write!(f, "I32({:?})", v),
+            ReflectValueRef::I64(v) => write!(f, "I64({:?})", v),
+            ReflectValueRef::F32(v) => write!(f, "F32({:?})", v),
+            ReflectValueRef::F64(v) => write!(f, "F64({:?})", v),
+            ReflectValueRef::Bool(v) => write!(f, "Bool({:?})", v),
+            ReflectValueRef::String(v) => write!(f, "String({:?})", v),
+            ReflectValueRef::Bytes(v) => {
+                let mut quoted = String::new();
+                text_format::quote_bytes_to(&v[..v.len().min(DEBUG_BYTES_LIMIT)], &mut quoted);
+                if v.len() > DEBUG_BYTES_LIMIT {
+                    write!(f, "Bytes(\"{}\"... {} bytes)", quoted, v.len())
+                } else {
+                    write!(f, "Bytes(\"{}\")", quoted)
+                }
+            }
+            ReflectValueRef::Enum(d, v) => match d.get_value_by_number(*v) {
+                Some(value) => write!(f, "Enum({}::{} = {})", d.full_name(), value.get_name(), v),
+                None => write!(f, "Enum({}::<unknown> = {})", d.full_name(), v),
+            },
+            ReflectValueRef::Message(m) => {
+                write!(f, "Message(")?;
+                debug_message(&**m, f)?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Same enum type, possibly described by different descriptors,
+/// e. g. generated and dynamic.
+fn enum_type_eq(a: &EnumDescriptor, b: &EnumDescriptor) -> bool {
+    a == b || a.full_name() == b.full_name()
+}
+
 impl<'a> ReflectEq for ReflectValueRef<'a> {
     fn reflect_eq(&self, that: &Self, mode: &ReflectEqMode) -> bool {
         use crate::reflect::value::value_ref::ReflectValueRef::*;
@@ -215,7 +390,7 @@ impl<'a> ReflectEq for ReflectValueRef<'a> {
             (Bool(a), Bool(b)) => a == b,
             (String(a), String(b)) => a == b,
             (Bytes(a), Bytes(b)) => a == b,
-            (Enum(ad, a), Enum(bd, b)) => ad == bd && a == b,
+            (Enum(ad, a), Enum(bd, b)) => enum_type_eq(ad, bd) && a == b,
             (Message(a), Message(b)) => a.reflect_eq(b, mode),
             _ => false,
         }
@@ -236,7 +411,7 @@ impl<'a> PartialEq for ReflectValueRef<'a> {
             (Bool(a), Bool(b)) => a == b,
             (String(a), String(b)) => a == b,
             (Bytes(a), Bytes(b)) => a == b,
-            (Enum(da, a), Enum(db, b)) => da == db && a == b,
+            (Enum(da, a), Enum(db, b)) => enum_type_eq(da, db) && a == b,
             (Message(a), Message(b)) => {
                 MessageDescriptor::reflect_eq_maybe_unrelated(&**a, &**b, &ReflectEqMode::default())
             }
@@ -330,3 +505,85 @@ impl<'a> From<&'a [u8]> for ReflectValueRef<'a> {
         ReflectValueRef::Bytes(v)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::field_descriptor_proto::Type;
+    use crate::descriptor::FileDescriptorProto;
+
+    #[test]
+    fn lossy() {
+        assert_eq!(Some(-3), ReflectValueRef::I32(-3).to_i64_lossy());
+        assert_eq!(
+            Some(i64::MAX),
+            ReflectValueRef::U64(u64::MAX).to_i64_lossy()
+        );
+        assert_eq!(Some(-2), ReflectValueRef::F64(-2.7).to_i64_lossy());
+        assert_eq!(Some(0), ReflectValueRef::F32(f32::NAN).to_i64_lossy());
+        assert_eq!(Some(1), ReflectValueRef::Bool(true).to_i64_lossy());
+        assert_eq!(None, ReflectValueRef::String("1").to_i64_lossy());
+
+        assert_eq!(
+            Some(4294967295.0),
+            ReflectValueRef::U32(u32::MAX).to_f64_lossy()
+        );
+        assert_eq!(Some(0.5), ReflectValueRef::F32(0.5).to_f64_lossy());
+        assert_eq!(None, ReflectValueRef::Bytes(b"").to_f64_lossy());
+    }
+
+    #[test]
+    fn kind() {
+        assert_eq!(ReflectValueKind::SignedInt, ReflectValueRef::I64(1).kind());
+        assert_eq!(
+            ReflectValueKind::UnsignedInt,
+            ReflectValueRef::U32(1).kind()
+        );
+        assert_eq!(ReflectValueKind::Float, ReflectValueRef::F64(1.0).kind());
+        assert!(ReflectValueRef::F32(1.0).kind().is_numeric());
+        assert!(!ReflectValueRef::Bool(true).kind().is_numeric());
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!("I32(-3)", format!("{:?}", ReflectValueRef::I32(-3)));
+        assert_eq!(
+            "String(\"a\\nb\")",
+            format!("{:?}", ReflectValueRef::String("a\nb"))
+        );
+        assert_eq!(
+            "Bytes(\"a\\001\")",
+            format!("{:?}", ReflectValueRef::Bytes(b"a\x01"))
+        );
+        assert_eq!(
+            format!("Bytes(\"{}\"... 40 bytes)", "x".repeat(32)),
+            format!("{:?}", ReflectValueRef::Bytes(&[b'x'; 40]))
+        );
+
+        let t = EnumDescriptor::for_type::<Type>();
+        assert_eq!(
+            "Enum(google.protobuf.FieldDescriptorProto.Type::TYPE_INT32 = 5)",
+            format!("{:?}", ReflectValueRef::Enum(t.clone(), 5))
+        );
+        assert_eq!(
+            "Enum(google.protobuf.FieldDescriptorProto.Type::<unknown> = 100)",
+            format!("{:?}", ReflectValueRef::Enum(t, 100))
+        );
+
+        let mut m = FileDescriptorProto::new();
+        assert_eq!(
+            "Message(google.protobuf.FileDescriptorProto {})",
+            format!("{:?}", ReflectValueRef::Message(MessageRef::from(&m)))
+        );
+        m.set_name("a.proto".to_owned());
+        assert_eq!(
+            "Message(google.protobuf.FileDescriptorProto { name: \"a.proto\" })",
+            format!("{:?}", ReflectValueBox::Message(Box::new(m.clone())))
+        );
+
+        m.set_package("p".repeat(2000));
+        let debug = format!("{:?}", ReflectValueRef::Message(MessageRef::from(&m)));
+        assert!(debug.ends_with("... 2027 chars })"), "{}", debug);
+        assert!(debug.len() < 1100);
+    }
+}
@@ -0,0 +1,286 @@
+//! Walk a message tree with a visitor.
+//!
+//! [`walk`] recursively traverses any [`MessageDyn`], generated or dynamic,
+//! and reports messages and values to a [`MessageVisitor`]:
+//!
+//! ```
+//! # use protobuf::reflect::visit::{walk, MessageVisitor};
+//! # use protobuf::reflect::{FieldDescriptor, ReflectValueRef};
+//! # use protobuf::MessageDyn;
+//! /// Count all string values in a message tree.
+//! #[derive(Default)]
+//! struct CountStrings(usize);
+//!
+//! impl MessageVisitor for CountStrings {
+//!     fn visit_scalar(&mut self, _field: &FieldDescriptor, value: &ReflectValueRef) {
+//!         if let ReflectValueRef::String(..) = value {
+//!             self.0 += 1;
+//!         }
+//!     }
+//!
+//!     fn visit_repeated_item(
+//!         &mut self,
+//!         field: &FieldDescriptor,
+//!         _index: usize,
+//!         value: &ReflectValueRef,
+//!     ) {
+//!         self.visit_scalar(field, value);
+//!     }
+//! }
+//!
+//! # fn foo(message: &dyn MessageDyn) -> usize {
+//! let mut count = CountStrings::default();
+//! walk(message, &mut count);
+//! count.0
+//! # }
+//! ```
+
+use crate::message_dyn::MessageDyn;
+use crate::reflect::has_explicit_presence;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueRef;
+
+/// Callbacks invoked by [`walk`].
+///
+/// All methods do nothing by default, so implementations
+/// override only the methods they are interested in.
+pub trait MessageVisitor {
+    /// Called before fields of a message are visited.
+    ///
+    /// `field` is the field containing the message,
+    /// or `None` for the message passed to [`walk`].
+    fn enter_message(&mut self, field: Option<&FieldDescriptor>, message: &dyn MessageDyn) {
+        let _ = (field, message);
+    }
+
+    /// Called after all fields of a message are visited.
+    fn leave_message(&mut self, field: Option<&FieldDescriptor>, message: &dyn MessageDyn) {
+        let _ = (field, message);
+    }
+
+    /// Called for a set singular field of non-message type.
+    ///
+    /// Singular message fields are reported with
+    /// [`enter_message`](Self::enter_message) instead.
+    fn visit_scalar(&mut self, field: &FieldDescriptor, value: &ReflectValueRef) {
+        let _ = (field, value);
+    }
+
+    /// Called for each element of a repeated field.
+    ///
+    /// If the element is a message, the message is walked after this call.
+    fn visit_repeated_item(
+        &mut self,
+        field: &FieldDescriptor,
+        index: usize,
+        value: &ReflectValueRef,
+    ) {
+        let _ = (field, index, value);
+    }
+
+    /// Called for each entry of a map field.
+    ///
+    /// If the value is a message, the message is walked after this call.
+    fn visit_map_entry(
+        &mut self,
+        field: &FieldDescriptor,
+        key: &ReflectValueRef,
+        value: &ReflectValueRef,
+    ) {
+        let _ = (field, key, value);
+    }
+}
+
+/// Recursively walk the message, calling the visitor for
+/// the message itself, all set fields and all nested messages.
+///
+/// Fields are visited in field number order. Only the set field of a oneof
+/// is visited, and default values of fields without presence are skipped,
+/// so generated and dynamic messages of the same type are walked the same way.
+/// Unknown fields are not visited.
+pub fn walk(message: &dyn MessageDyn, visitor: &mut dyn MessageVisitor) {
+    walk_message(None, message, visitor);
+}
+
+fn walk_message(
+    field: Option<&FieldDescriptor>,
+    message: &dyn MessageDyn,
+    visitor: &mut dyn MessageVisitor,
+) {
+    visitor.enter_message(field, message);
+    for f in message.descriptor_dyn().fields_sorted_by_number() {
+        walk_field(&f, message, visitor);
+    }
+    visitor.leave_message(field, message);
+}
+
+fn walk_nested(field: &FieldDescriptor, value: &ReflectValueRef, visitor: &mut dyn MessageVisitor) {
+    if let ReflectValueRef::Message(m) = value {
+        walk_message(Some(field), &**m, visitor);
+    }
+}
+
+fn walk_field(field: &FieldDescriptor, message: &dyn MessageDyn, visitor: &mut dyn MessageVisitor) {
+    match field.get_reflect(message) {
+        ReflectFieldRef::Optional(v) => {
+            let v = match v.filter(|v| has_explicit_presence(field) || v.is_non_zero()) {
+                Some(v) => v,
+                None => return,
+            };
+            match v {
+                ReflectValueRef::Message(..) => walk_nested(field, &v, visitor),
+                _ => visitor.visit_scalar(field, &v),
+            }
+        }
+        ReflectFieldRef::Repeated(repeated) => {
+            for (i, v) in repeated.into_iter().enumerate() {
+                visitor.visit_repeated_item(field, i, &v);
+                walk_nested(field, &v, visitor);
+            }
+        }
+        ReflectFieldRef::Map(map) => {
+            for (k, v) in &map {
+                visitor.visit_map_entry(field, &k, &v);
+                walk_nested(field, &v, visitor);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FieldDescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::reflect::FileDescriptor;
+    use crate::well_known_types::Struct;
+    use crate::well_known_types::Value;
+    use crate::Message;
+
+    /// Record all callbacks as strings.
+    #[derive(Default)]
+    struct Log(Vec<String>);
+
+    impl MessageVisitor for Log {
+        fn enter_message(&mut self, field: Option<&FieldDescriptor>, message: &dyn MessageDyn) {
+            self.0.push(format!(
+                "enter {} {}",
+                field.map_or("-", |f| f.get_name()),
+                message.descriptor_dyn().name()
+            ));
+        }
+
+        fn leave_message(&mut self, _field: Option<&FieldDescriptor>, message: &dyn MessageDyn) {
+            self.0
+                .push(format!("leave {}", message.descriptor_dyn().name()));
+        }
+
+        fn visit_scalar(&mut self, field: &FieldDescriptor, value: &ReflectValueRef) {
+            self.0.push(format!("{}={:?}", field.get_name(), value));
+        }
+
+        fn visit_repeated_item(
+            &mut self,
+            field: &FieldDescriptor,
+            index: usize,
+            value: &ReflectValueRef,
+        ) {
+            if let ReflectValueRef::Message(..) = value {
+                self.0.push(format!("{}[{}]", field.get_name(), index));
+            } else {
+                self.0
+                    .push(format!("{}[{}]={:?}", field.get_name(), index, value));
+            }
+        }
+
+        fn visit_map_entry(
+            &mut self,
+            field: &FieldDescriptor,
+            key: &ReflectValueRef,
+            _value: &ReflectValueRef,
+        ) {
+            self.0.push(format!("{}[{:?}]", field.get_name(), key));
+        }
+    }
+
+    fn file() -> FileDescriptorProto {
+        let mut message = DescriptorProto::new();
+        message.set_name("M".to_owned());
+        let mut field = FieldDescriptorProto::new();
+        field.set_name("f".to_owned());
+        message.field.push(field);
+
+        let mut file = FileDescriptorProto::new();
+        file.set_name("a.proto".to_owned());
+        file.dependency.push("b.proto".to_owned());
+        file.message_type.push(message);
+        file
+    }
+
+    fn log(message: &dyn MessageDyn) -> Vec<String> {
+        let mut log = Log::default();
+        walk(message, &mut log);
+        log.0
+    }
+
+    #[test]
+    fn generated() {
+        assert_eq!(
+            vec![
+                "enter - FileDescriptorProto",
+                "name=String(\"a.proto\")",
+                "dependency[0]=String(\"b.proto\")",
+                "message_type[0]",
+                "enter message_type DescriptorProto",
+                "name=String(\"M\")",
+                "field[0]",
+                "enter field FieldDescriptorProto",
+                "name=String(\"f\")",
+                "leave FieldDescriptorProto",
+                "leave DescriptorProto",
+                "leave FileDescriptorProto",
+            ],
+            log(&file())
+        );
+    }
+
+    #[test]
+    fn dynamic() {
+        let file_descriptor = FileDescriptor::new_dynamic(
+            FileDescriptorProto::descriptor_static()
+                .file_descriptor_proto()
+                .clone(),
+            Vec::new(),
+        );
+        let descriptor = file_descriptor
+            .message_by_package_relative_name("FileDescriptorProto")
+            .unwrap();
+        let mut dynamic = descriptor.new_instance();
+        dynamic
+            .merge_from_bytes_dyn(&file().write_to_bytes().unwrap())
+            .unwrap();
+        assert_eq!(log(&file()), log(&*dynamic));
+    }
+
+    #[test]
+    fn map_and_oneof() {
+        let mut value = Value::new();
+        value.set_bool_value(false);
+        let mut s = Struct::new();
+        s.fields.insert("k".to_owned(), value);
+        assert_eq!(
+            vec![
+                "enter - Struct",
+                "fields[String(\"k\")]",
+                "enter fields Value",
+                // oneof field is set, so default value is visited
+                "bool_value=Bool(false)",
+                "leave Value",
+                "leave Struct",
+            ],
+            log(&s)
+        );
+    }
+}
@@ -13,6 +13,7 @@ pub use crate::cached_size::CachedSize;
 use crate::chars::Chars;
 use crate::coded_input_stream::CodedInputStream;
 use crate::coded_output_stream::CodedOutputStream;
+use crate::enums::ParseEnumError;
 use crate::enums::ProtobufEnum;
 use crate::error::ProtobufError;
 use crate::error::ProtobufResult;
@@ -29,6 +30,7 @@ use crate::wire_format::WireTypeFixed64;
 use crate::wire_format::WireTypeLengthDelimited;
 use crate::wire_format::WireTypeVarint;
 use crate::zigzag::*;
+use crate::LazyMessage;
 use crate::MessageField;
 use crate::ProtobufEnumOrUnknown;
 
@@ -133,32 +135,61 @@ impl<E:ProtobufEnum> ProtobufVarint for E {
 }
 */
 
+/// Convert accumulated size to `u32`, saturating at `u32::MAX`.
+///
+/// Serialized size of any valid message fits in `u32`, so saturated
+/// value is only returned for messages which are too large to be serialized;
+/// such messages are rejected by `Message::write_to` and similar functions.
+pub fn saturating_size(size: u64) -> u32 {
+    if size > u32::MAX as u64 {
+        u32::MAX
+    } else {
+        size as u32
+    }
+}
+
+/// Size of tag, length prefix and data of length-delimited field.
+fn length_delimited_size(field_number: u32, data_size: u32) -> u32 {
+    saturating_size(
+        tag_size(field_number) as u64
+            + compute_raw_varint32_size(data_size) as u64
+            + data_size as u64,
+    )
+}
+
 /// Size of serialized repeated packed field, excluding length and tag.
 pub fn vec_packed_varint_data_size<T: ProtobufVarint>(vec: &[T]) -> u32 {
-    vec.iter().map(|v| v.len_varint()).fold(0, |a, i| a + i)
+    saturating_size(vec.iter().map(|v| v.len_varint() as u64).sum())
 }
 
 /// Size of serialized repeated packed field, excluding length and tag.
 pub fn vec_packed_varint_zigzag_data_size<T: ProtobufVarintZigzag>(vec: &[T]) -> u32 {
-    vec.iter()
-        .map(|v| v.len_varint_zigzag())
-        .fold(0, |a, i| a + i)
+    saturating_size(vec.iter().map(|v| v.len_varint_zigzag() as u64).sum())
 }
 
 /// Size of serialized repeated packed enum field, excluding length and tag.
 pub fn vec_packed_enum_data_size<E: ProtobufEnum>(vec: &[E]) -> u32 {
-    vec.iter()
-        .map(|e| compute_raw_varint32_size(e.value() as u32))
-        .fold(0, |a, i| a + i)
+    saturating_size(
+        vec.iter()
+            .map(|e| compute_raw_varint32_size(e.value() as u32) as u64)
+            .sum(),
+    )
 }
 
 /// Size of serialized repeated packed enum field, excluding length and tag.
 pub fn vec_packed_enum_or_unknown_data_size<E: ProtobufEnum>(
     vec: &[ProtobufEnumOrUnknown<E>],
 ) -> u32 {
-    vec.iter()
-        .map(|e| compute_raw_varint32_size(e.value() as u32))
-        .fold(0, |a, i| a + i)
+    saturating_size(
+        vec.iter()
+            .map(|e| compute_raw_varint32_size(e.value() as u32) as u64)
+            .sum(),
+    )
+}
+
+/// Size of serialized repeated packed fixed-width field, excluding length and tag.
+pub fn vec_packed_fixed_data_size(len: usize, element_size: u32) -> u32 {
+    saturating_size((len as u64).saturating_mul(element_size as u64))
 }
 
 /// Size of serialized data with length prefix and tag
@@ -166,8 +197,7 @@ pub fn vec_packed_varint_size<T: ProtobufVarint>(field_number: u32, vec: &[T]) -
     if vec.is_empty() {
         0
     } else {
-        let data_size = vec_packed_varint_data_size(vec);
-        tag_size(field_number) + data_size.len_varint() + data_size
+        length_delimited_size(field_number, vec_packed_varint_data_size(vec))
     }
 }
 
@@ -176,8 +206,7 @@ pub fn vec_packed_varint_zigzag_size<T: ProtobufVarintZigzag>(field_number: u32,
     if vec.is_empty() {
         0
     } else {
-        let data_size = vec_packed_varint_zigzag_data_size(vec);
-        tag_size(field_number) + data_size.len_varint() + data_size
+        length_delimited_size(field_number, vec_packed_varint_zigzag_data_size(vec))
     }
 }
 
@@ -186,8 +215,7 @@ pub fn vec_packed_enum_size<E: ProtobufEnum>(field_number: u32, vec: &[E]) -> u3
     if vec.is_empty() {
         0
     } else {
-        let data_size = vec_packed_enum_data_size(vec);
-        tag_size(field_number) + data_size.len_varint() + data_size
+        length_delimited_size(field_number, vec_packed_enum_data_size(vec))
     }
 }
 
@@ -199,8 +227,16 @@ pub fn vec_packed_enum_or_unknown_size<E: ProtobufEnum>(
     if vec.is_empty() {
         0
     } else {
-        let data_size = vec_packed_enum_or_unknown_data_size(vec);
-        tag_size(field_number) + data_size.len_varint() + data_size
+        length_delimited_size(field_number, vec_packed_enum_or_unknown_data_size(vec))
+    }
+}
+
+/// Size of serialized repeated packed fixed-width field with length prefix and tag
+pub fn vec_packed_fixed_size(field_number: u32, len: usize, element_size: u32) -> u32 {
+    if len == 0 {
+        0
+    } else {
+        length_delimited_size(field_number, vec_packed_fixed_data_size(len, element_size))
     }
 }
 
@@ -258,12 +294,12 @@ pub fn enum_or_unknown_size<E: ProtobufEnum>(
 }
 
 fn bytes_size_no_tag(bytes: &[u8]) -> u32 {
-    compute_raw_varint64_size(bytes.len() as u64) + bytes.len() as u32
+    saturating_size(compute_raw_varint64_size(bytes.len() as u64) as u64 + bytes.len() as u64)
 }
 
 /// Size of encoded bytes field.
 pub fn bytes_size(field_number: u32, bytes: &[u8]) -> u32 {
-    tag_size(field_number) + bytes_size_no_tag(bytes)
+    tag_size(field_number).saturating_add(bytes_size_no_tag(bytes))
 }
 
 fn string_size_no_tag(s: &str) -> u32 {
@@ -272,27 +308,47 @@ fn string_size_no_tag(s: &str) -> u32 {
 
 /// Size of encoded string field.
 pub fn string_size(field_number: u32, s: &str) -> u32 {
-    tag_size(field_number) + string_size_no_tag(s)
+    tag_size(field_number).saturating_add(string_size_no_tag(s))
 }
 
 /// Size of encoded unknown fields size.
 pub fn unknown_fields_size(unknown_fields: &UnknownFields) -> u32 {
-    let mut r = 0;
+    let mut r: u64 = 0;
     for (number, values) in unknown_fields {
-        r += (tag_size(number) + 4) * values.fixed32.len() as u32;
-        r += (tag_size(number) + 8) * values.fixed64.len() as u32;
+        let tag_size = tag_size(number) as u64;
+
+        r += (tag_size + 4) * values.fixed32.len() as u64;
+        r += (tag_size + 8) * values.fixed64.len() as u64;
 
-        r += tag_size(number) * values.varint.len() as u32;
+        r += tag_size * values.varint.len() as u64;
         for varint in &values.varint {
-            r += varint.len_varint();
+            r += varint.len_varint() as u64;
         }
 
-        r += tag_size(number) * values.length_delimited.len() as u32;
+        r += tag_size * values.length_delimited.len() as u64;
         for bytes in &values.length_delimited {
-            r += bytes_size_no_tag(&bytes);
+            r += bytes_size_no_tag(bytes) as u64;
         }
     }
-    r
+    for (number, groups) in unknown_fields.iter_groups() {
+        // start and end tags
+        let tags_size = 2 * tag_size(number) as u64;
+        for contents in groups {
+            r += tags_size + contents.len() as u64;
+        }
+    }
+    saturating_size(r)
+}
+
+/// Add element to repeated field, and notify allocation monitor.
+#[inline]
+fn push_repeated<T>(
+    is: &mut CodedInputStream,
+    target: &mut Vec<T>,
+    value: T,
+) -> ProtobufResult<()> {
+    target.push(value);
+    is.report_repeated_len(target.len())
 }
 
 /// Read repeated `int32` field into given vec.
@@ -304,8 +360,8 @@ pub fn read_repeated_int32_into(
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_int32_into(target),
         WireTypeVarint => {
-            target.push(is.read_int32()?);
-            Ok(())
+            let v = is.read_int32()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -320,8 +376,8 @@ pub fn read_repeated_int64_into(
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_int64_into(target),
         WireTypeVarint => {
-            target.push(is.read_int64()?);
-            Ok(())
+            let v = is.read_int64()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -336,8 +392,8 @@ pub fn read_repeated_uint32_into(
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_uint32_into(target),
         WireTypeVarint => {
-            target.push(is.read_uint32()?);
-            Ok(())
+            let v = is.read_uint32()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -352,8 +408,8 @@ pub fn read_repeated_uint64_into(
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_uint64_into(target),
         WireTypeVarint => {
-            target.push(is.read_uint64()?);
-            Ok(())
+            let v = is.read_uint64()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -368,8 +424,8 @@ pub fn read_repeated_sint32_into(
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_sint32_into(target),
         WireTypeVarint => {
-            target.push(is.read_sint32()?);
-            Ok(())
+            let v = is.read_sint32()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -384,8 +440,8 @@ pub fn read_repeated_sint64_into(
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_sint64_into(target),
         WireTypeVarint => {
-            target.push(is.read_sint64()?);
-            Ok(())
+            let v = is.read_sint64()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -400,8 +456,8 @@ pub fn read_repeated_fixed32_into(
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_fixed32_into(target),
         WireTypeFixed32 => {
-            target.push(is.read_fixed32()?);
-            Ok(())
+            let v = is.read_fixed32()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -416,8 +472,8 @@ pub fn read_repeated_fixed64_into(
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_fixed64_into(target),
         WireTypeFixed64 => {
-            target.push(is.read_fixed64()?);
-            Ok(())
+            let v = is.read_fixed64()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -432,8 +488,8 @@ pub fn read_repeated_sfixed32_into(
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_sfixed32_into(target),
         WireTypeFixed32 => {
-            target.push(is.read_sfixed32()?);
-            Ok(())
+            let v = is.read_sfixed32()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -448,8 +504,8 @@ pub fn read_repeated_sfixed64_into(
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_sfixed64_into(target),
         WireTypeFixed64 => {
-            target.push(is.read_sfixed64()?);
-            Ok(())
+            let v = is.read_sfixed64()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -464,8 +520,8 @@ pub fn read_repeated_double_into(
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_double_into(target),
         WireTypeFixed64 => {
-            target.push(is.read_double()?);
-            Ok(())
+            let v = is.read_double()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -480,8 +536,8 @@ pub fn read_repeated_float_into(
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_float_into(target),
         WireTypeFixed32 => {
-            target.push(is.read_float()?);
-            Ok(())
+            let v = is.read_float()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -496,8 +552,8 @@ pub fn read_repeated_bool_into(
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_bool_into(target),
         WireTypeVarint => {
-            target.push(is.read_bool()?);
-            Ok(())
+            let v = is.read_bool()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -513,8 +569,8 @@ pub fn read_repeated_enum_into<E: ProtobufEnum + ProtobufValue>(
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_enum_into(target),
         WireTypeVarint => {
-            target.push(is.read_enum()?);
-            Ok(())
+            let v = is.read_enum()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -539,6 +595,20 @@ where
     Ok(())
 }
 
+fn read_repeated_enum_value_with_unknown_fields_into<E: ProtobufEnum>(
+    is: &mut CodedInputStream,
+    target: &mut Vec<E>,
+    field_number: u32,
+    unknown_fields: &mut UnknownFields,
+) -> ProtobufResult<()> {
+    let len = target.len();
+    read_enum_with_unknown_fields_into(is, |e| target.push(e), field_number, unknown_fields)?;
+    if target.len() != len {
+        is.report_repeated_len(target.len())?;
+    }
+    Ok(())
+}
+
 fn read_repeated_packed_enum_with_unknown_fields_into<E: ProtobufEnum>(
     is: &mut CodedInputStream,
     target: &mut Vec<E>,
@@ -548,7 +618,12 @@ fn read_repeated_packed_enum_with_unknown_fields_into<E: ProtobufEnum>(
     let len = is.read_raw_varint64()?;
     let old_limit = is.push_limit(len)?;
     while !is.eof()? {
-        read_enum_with_unknown_fields_into(is, |e| target.push(e), field_number, unknown_fields)?;
+        read_repeated_enum_value_with_unknown_fields_into(
+            is,
+            target,
+            field_number,
+            unknown_fields,
+        )?;
     }
     is.pop_limit(old_limit);
     Ok(())
@@ -561,7 +636,8 @@ fn read_repeated_packed_enum_or_unknown_into<E: ProtobufEnum>(
     let len = is.read_raw_varint64()?;
     let old_limit = is.push_limit(len)?;
     while !is.eof()? {
-        target.push(is.read_enum_or_unknown()?);
+        let v = is.read_enum_or_unknown()?;
+        push_repeated(is, target, v)?;
     }
     is.pop_limit(old_limit);
     Ok(())
@@ -587,9 +663,12 @@ pub fn read_repeated_enum_with_unknown_fields_into<E: ProtobufEnum>(
             field_number,
             unknown_fields,
         ),
-        WireTypeVarint => {
-            read_enum_with_unknown_fields_into(is, |e| target.push(e), field_number, unknown_fields)
-        }
+        WireTypeVarint => read_repeated_enum_value_with_unknown_fields_into(
+            is,
+            target,
+            field_number,
+            unknown_fields,
+        ),
         _ => Err(unexpected_wire_type(wire_type)),
     }
 }
@@ -608,8 +687,8 @@ pub fn read_repeated_enum_or_unknown_into<E: ProtobufEnum>(
     match wire_type {
         WireTypeLengthDelimited => read_repeated_packed_enum_or_unknown_into(is, target),
         WireTypeVarint => {
-            target.push(is.read_enum_or_unknown()?);
-            Ok(())
+            let v = is.read_enum_or_unknown()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -663,8 +742,8 @@ pub fn read_repeated_string_into(
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => {
-            target.push(is.read_string()?);
-            Ok(())
+            let v = is.read_string()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -679,8 +758,8 @@ pub fn read_repeated_carllerche_string_into(
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => {
-            target.push(is.read_carllerche_chars()?);
-            Ok(())
+            let v = is.read_carllerche_chars()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -738,8 +817,8 @@ pub fn read_repeated_bytes_into(
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => {
-            target.push(is.read_bytes()?);
-            Ok(())
+            let v = is.read_bytes()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -754,8 +833,8 @@ pub fn read_repeated_carllerche_bytes_into(
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => {
-            target.push(is.read_carllerche_bytes()?);
-            Ok(())
+            let v = is.read_carllerche_bytes()?;
+            push_repeated(is, target, v)
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -815,10 +894,7 @@ pub fn read_repeated_message_into_vec<M: Message + Default>(
         WireTypeLengthDelimited => {
             is.incr_recursion()?;
             let res = match is.read_message() {
-                Ok(m) => {
-                    target.push(m);
-                    Ok(())
-                }
+                Ok(m) => push_repeated(is, target, m),
                 Err(e) => Err(e),
             };
             is.decr_recursion();
@@ -850,13 +926,36 @@ where
     }
 }
 
-fn skip_group(is: &mut CodedInputStream) -> ProtobufResult<()> {
+/// Read singular lazy `message` field.
+///
+/// Message bytes are stored in the field without parsing.
+pub fn read_singular_lazy_message_into_field<M>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut LazyMessage<M>,
+) -> ProtobufResult<()>
+where
+    M: Message,
+{
+    match wire_type {
+        WireTypeLengthDelimited => {
+            *target = LazyMessage::from_bytes(is.read_bytes()?);
+            Ok(())
+        }
+        _ => Err(unexpected_wire_type(wire_type)),
+    }
+}
+
+pub(crate) fn skip_group(is: &mut CodedInputStream) -> ProtobufResult<()> {
+    let start_pos = is.pos();
     loop {
-        let (_, wire_type) = is.read_tag_unpack()?;
+        let (_, wire_type) = is.read_tag_not_counted()?.unpack();
         if wire_type == wire_format::WireTypeEndGroup {
+            // Whole group is a single unknown field
+            is.report_unknown_field(start_pos);
             return Ok(());
         }
-        is.skip_field(wire_type)?;
+        is.read_unknown(wire_type)?;
     }
 }
 
@@ -871,7 +970,9 @@ pub fn read_unknown_or_skip_group(
     match wire_type {
         wire_format::WireTypeStartGroup => skip_group(is),
         _ => {
+            let start_pos = is.pos();
             let unknown = is.read_unknown(wire_type)?;
+            is.report_unknown_field(start_pos);
             unknown_fields.add_value(field_number, unknown);
             Ok(())
         }
@@ -886,6 +987,14 @@ pub fn unexpected_wire_type(wire_type: WireType) -> ProtobufError {
     ProtobufError::WireError(WireError::UnexpectedWireType(wire_type))
 }
 
+/// Implementation of `FromStr` for generated enums.
+pub fn enum_from_str<E: ProtobufEnum>(
+    enum_name: &'static str,
+    name: &str,
+) -> Result<E, ParseEnumError> {
+    E::from_name(name).ok_or_else(|| ParseEnumError::new(enum_name, name))
+}
+
 /// Compute serialized size of `map` field and cache nested field sizes.
 pub fn compute_map_size<K, V>(
     field_number: u32,
@@ -896,7 +1005,7 @@ where
     V: ProtobufType,
     K::ProtobufValue: Eq + Hash,
 {
-    let mut sum = 0;
+    let mut sum: u64 = 0;
     for (k, v) in map {
         let key_tag_size = 1;
         let value_tag_size = 1;
@@ -904,10 +1013,11 @@ where
         let key_len = K::compute_size_with_length_delimiter(k);
         let value_len = V::compute_size_with_length_delimiter(v);
 
-        let entry_len = key_tag_size + key_len + value_tag_size + value_len;
-        sum += tag_size(field_number) + compute_raw_varint32_size(entry_len) + entry_len;
+        let entry_len =
+            saturating_size(key_tag_size + key_len as u64 + value_tag_size + value_len as u64);
+        sum += length_delimited_size(field_number, entry_len) as u64;
     }
-    sum
+    saturating_size(sum)
 }
 
 /// Write map, message sizes must be already known.
@@ -952,6 +1062,41 @@ where
     message.write_to_with_cached_sizes(os)
 }
 
+/// Compute size of lazy message field including tag and length, zero if the field is not set.
+///
+/// Size of original bytes is used if the message was not modified after parsing.
+pub fn lazy_message_size<M>(field_number: u32, field: &LazyMessage<M>) -> u64
+where
+    M: Message,
+{
+    match field.compute_size() {
+        Some(len) => {
+            tag_size(field_number) as u64 + compute_raw_varint32_size(len) as u64 + len as u64
+        }
+        None => 0,
+    }
+}
+
+/// Write lazy message field with field number and length to the stream.
+///
+/// Original bytes are written if the message was not modified after parsing.
+pub fn write_lazy_message_field_with_cached_size<M>(
+    field_number: u32,
+    field: &LazyMessage<M>,
+    os: &mut CodedOutputStream,
+) -> ProtobufResult<()>
+where
+    M: Message,
+{
+    if let Some(bytes) = field.raw_bytes() {
+        os.write_bytes(field_number, bytes)
+    } else if let Some(message) = field.message() {
+        write_message_field_with_cached_size(field_number, message, os)
+    } else {
+        Ok(())
+    }
+}
+
 /// Read `map` field.
 pub fn read_map_into<K, V>(
     wire_type: WireType,
@@ -992,7 +1137,120 @@ where
     }
     is.pop_limit(old_limit);
 
+    let len = target.len();
     target.insert(key, value);
+    if target.len() != len {
+        is.report_repeated_len(target.len())?;
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::corpus::RandomMessageGenerator;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::message::check_message_size;
+    use crate::message::MESSAGE_SIZE_LIMIT;
+    use crate::reflect::FileDescriptor;
+    use crate::wire_format::FIELD_NUMBER_MAX;
+
+    #[test]
+    fn saturating_size() {
+        assert_eq!(10, super::saturating_size(10));
+        assert_eq!(u32::MAX, super::saturating_size(u32::MAX as u64));
+        assert_eq!(u32::MAX, super::saturating_size(u32::MAX as u64 + 1));
+        assert_eq!(u32::MAX, super::saturating_size(u64::MAX));
+    }
+
+    #[test]
+    fn vec_packed_fixed_size_overflow() {
+        assert_eq!(0, vec_packed_fixed_size(1, 0, 8));
+        assert_eq!(1 + 1 + 16, vec_packed_fixed_size(1, 2, 8));
+        assert_eq!(u32::MAX, vec_packed_fixed_data_size(usize::MAX, 8));
+        assert_eq!(u32::MAX, vec_packed_fixed_data_size(1 << 30, 4));
+        assert_eq!(u32::MAX, vec_packed_fixed_size(1, (1 << 30) - 1, 4));
+    }
+
+    #[test]
+    fn length_delimited_size_overflow() {
+        assert_eq!(1 + 1 + 5, length_delimited_size(1, 5));
+        assert_eq!(u32::MAX, length_delimited_size(1, u32::MAX - 5));
+        assert_eq!(u32::MAX, length_delimited_size(1, u32::MAX));
+    }
+
+    #[test]
+    fn overflowed_size_is_rejected() {
+        assert_eq!(100, check_message_size(100).unwrap());
+        assert!(check_message_size(i32::MAX as u32).is_ok());
+        assert!(check_message_size(i32::MAX as u32 + 1).is_err());
+        assert!(check_message_size(vec_packed_fixed_size(1, usize::MAX, 8)).is_err());
+    }
+
+    #[test]
+    fn random_messages_compute_size() {
+        let generated = FileDescriptorProto::descriptor_static();
+        let dynamic =
+            FileDescriptor::new_dynamic(generated.file_descriptor_proto().clone(), Vec::new())
+                .message_by_package_relative_name(generated.name())
+                .unwrap();
+        let mut generator = RandomMessageGenerator::new(472);
+        for descriptor in &[generated, dynamic] {
+            for i in 0..100 {
+                let m = generator.generate(descriptor);
+                let bytes = m.write_to_bytes_dyn().unwrap();
+                assert_eq!(bytes.len(), m.compute_size_dyn() as usize, "{}", i);
+            }
+        }
+    }
+
+    /// Sizes near `u32::MAX` must be equal to exact sizes saturated to `u32`,
+    /// and rejected iff exact size exceeds the limit.
+    #[test]
+    fn random_sizes_near_limit() {
+        let mut rng = RandomMessageGenerator::new(4720);
+        for _ in 0..10000 {
+            let field_number = (rng.next_u64() % FIELD_NUMBER_MAX as u64) as u32 + 1;
+            let distance = (rng.next_u64() as u32) >> (rng.next_u64() % 32);
+            let data_size = u32::MAX - distance;
+
+            let exact = tag_size(field_number) as u64
+                + compute_raw_varint32_size(data_size) as u64
+                + data_size as u64;
+            let size = length_delimited_size(field_number, data_size);
+            assert_eq!(super::saturating_size(exact), size);
+            assert_eq!(
+                exact > MESSAGE_SIZE_LIMIT as u64,
+                matches!(
+                    check_message_size(size),
+                    Err(ProtobufError::MessageTooLarge)
+                )
+            );
+
+            let element_size = if rng.next_u64().is_multiple_of(2) {
+                4
+            } else {
+                8
+            };
+            let len = (data_size / element_size) as usize;
+            let exact_data = len as u64 * element_size as u64;
+            let exact = if len == 0 {
+                0
+            } else {
+                tag_size(field_number) as u64
+                    + compute_raw_varint64_size(exact_data) as u64
+                    + exact_data
+            };
+            let size = vec_packed_fixed_size(field_number, len, element_size);
+            assert_eq!(super::saturating_size(exact), size);
+            assert_eq!(
+                exact > MESSAGE_SIZE_LIMIT as u64,
+                matches!(
+                    check_message_size(size),
+                    Err(ProtobufError::MessageTooLarge)
+                )
+            );
+        }
+    }
+}
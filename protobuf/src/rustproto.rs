@@ -40,6 +40,12 @@ pub mod exts {
 
     pub const serde_derive_cfg_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17031, phantom: ::std::marker::PhantomData };
 
+    pub const rkyv_derive_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17040, phantom: ::std::marker::PhantomData };
+
+    pub const rkyv_derive_cfg_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17041, phantom: ::std::marker::PhantomData };
+
+    pub const impl_bytes_conversions_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17042, phantom: ::std::marker::PhantomData };
+
     pub const lite_runtime_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17035, phantom: ::std::marker::PhantomData };
 
     pub const expose_oneof: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17001, phantom: ::std::marker::PhantomData };
@@ -58,6 +64,12 @@ pub mod exts {
 
     pub const serde_derive_cfg: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17031, phantom: ::std::marker::PhantomData };
 
+    pub const rkyv_derive: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17040, phantom: ::std::marker::PhantomData };
+
+    pub const rkyv_derive_cfg: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17041, phantom: ::std::marker::PhantomData };
+
+    pub const impl_bytes_conversions: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17042, phantom: ::std::marker::PhantomData };
+
     pub const expose_fields_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17003, phantom: ::std::marker::PhantomData };
 
     pub const generate_accessors_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17004, phantom: ::std::marker::PhantomData };
@@ -84,131 +96,166 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     sForStringAll:H\n\x10serde_derive_all\x18\x86\x85\x01\x20\x01(\x08\x12\
     \x1c.google.protobuf.FileOptionsR\x0eserdeDeriveAll:O\n\x14serde_derive_\
     cfg_all\x18\x87\x85\x01\x20\x01(\t\x12\x1c.google.protobuf.FileOptionsR\
-    \x11serdeDeriveCfgAll:H\n\x10lite_runtime_all\x18\x8b\x85\x01\x20\x01(\
-    \x08\x12\x1c.google.protobuf.FileOptionsR\x0eliteRuntimeAll:D\n\x0cexpos\
-    e_oneof\x18\xe9\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOpti\
-    onsR\x0bexposeOneof:F\n\rexpose_fields\x18\xeb\x84\x01\x20\x01(\x08\x12\
-    \x1f.google.protobuf.MessageOptionsR\x0cexposeFields:P\n\x12generate_acc\
-    essors\x18\xec\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptio\
-    nsR\x11generateAccessors:J\n\x0fgenerate_getter\x18\xed\x84\x01\x20\x01(\
-    \x08\x12\x1f.google.protobuf.MessageOptionsR\x0egenerateGetter:^\n\x1aca\
-    rllerche_bytes_for_bytes\x18\xf3\x84\x01\x20\x01(\x08\x12\x1f.google.pro\
-    tobuf.MessageOptionsR\x17carllercheBytesForBytes:`\n\x1bcarllerche_bytes\
-    _for_string\x18\xf4\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.Message\
-    OptionsR\x18carllercheBytesForString:D\n\x0cserde_derive\x18\x86\x85\x01\
-    \x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x0bserdeDerive:K\n\
-    \x10serde_derive_cfg\x18\x87\x85\x01\x20\x01(\t\x12\x1f.google.protobuf.\
-    MessageOptionsR\x0eserdeDeriveCfg:O\n\x13expose_fields_field\x18\xeb\x84\
-    \x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x11exposeFieldsF\
-    ield:Y\n\x18generate_accessors_field\x18\xec\x84\x01\x20\x01(\x08\x12\
-    \x1d.google.protobuf.FieldOptionsR\x16generateAccessorsField:S\n\x15gene\
-    rate_getter_field\x18\xed\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.F\
-    ieldOptionsR\x13generateGetterField:g\n\x20carllerche_bytes_for_bytes_fi\
-    eld\x18\xf3\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\
-    \x1ccarllercheBytesForBytesField:i\n!carllerche_bytes_for_string_field\
-    \x18\xf4\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x1dc\
-    arllercheBytesForStringFieldJ\x8e\x18\n\x06\x12\x04\0\0A\x01\n\x08\n\x01\
-    \x0c\x12\x03\0\0\x12\n\t\n\x02\x03\0\x12\x03\x02\0*\n\xe5\x01\n\x01\x02\
-    \x12\x03\n\0\x122^\x20see\x20https://github.com/gogo/protobuf/blob/maste\
-    r/gogoproto/gogo.proto\n\x20for\x20the\x20original\x20idea\n2{\x20Genera\
-    ted\x20files\x20can\x20be\x20customized\x20using\x20this\x20proto\n\x20o\
-    r\x20using\x20`Customize`\x20struct\x20when\x20codegen\x20is\x20invoked\
-    \x20programmatically.\n\n\t\n\x01\x07\x12\x04\x0c\0!\x01\n7\n\x02\x07\0\
-    \x12\x03\x0e\x04+\x1a,\x20When\x20true,\x20oneof\x20field\x20is\x20gener\
-    ated\x20public\n\n\n\n\x03\x07\0\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\0\
-    \x04\x12\x03\x0e\x04\x0c\n\n\n\x03\x07\0\x05\x12\x03\x0e\r\x11\n\n\n\x03\
-    \x07\0\x01\x12\x03\x0e\x12\"\n\n\n\x03\x07\0\x03\x12\x03\x0e%*\nI\n\x02\
-    \x07\x01\x12\x03\x10\x04,\x1a>\x20When\x20true\x20all\x20fields\x20are\
-    \x20public,\x20and\x20not\x20accessors\x20generated\n\n\n\n\x03\x07\x01\
-    \x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x01\x04\x12\x03\x10\x04\x0c\n\n\n\
-    \x03\x07\x01\x05\x12\x03\x10\r\x11\n\n\n\x03\x07\x01\x01\x12\x03\x10\x12\
-    #\n\n\n\x03\x07\x01\x03\x12\x03\x10&+\nP\n\x02\x07\x02\x12\x03\x12\x041\
-    \x1aE\x20When\x20false,\x20`get_`,\x20`set_`,\x20`mut_`\x20etc.\x20acces\
-    sors\x20are\x20not\x20generated\n\n\n\n\x03\x07\x02\x02\x12\x03\x0c\x07\
-    \"\n\n\n\x03\x07\x02\x04\x12\x03\x12\x04\x0c\n\n\n\x03\x07\x02\x05\x12\
-    \x03\x12\r\x11\n\n\n\x03\x07\x02\x01\x12\x03\x12\x12(\n\n\n\x03\x07\x02\
-    \x03\x12\x03\x12+0\nL\n\x02\x07\x03\x12\x03\x14\x04.\x1aA\x20When\x20fal\
-    se,\x20`get_`\x20is\x20not\x20generated\x20even\x20if\x20`syntax\x20=\
-    \x20\"proto2\"`\n\n\n\n\x03\x07\x03\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\
-    \x03\x04\x12\x03\x14\x04\x0c\n\n\n\x03\x07\x03\x05\x12\x03\x14\r\x11\n\n\
-    \n\x03\x07\x03\x01\x12\x03\x14\x12%\n\n\n\x03\x07\x03\x03\x12\x03\x14(-\
-    \n2\n\x02\x07\x04\x12\x03\x16\x049\x1a'\x20Use\x20`bytes::Bytes`\x20for\
-    \x20`bytes`\x20fields\n\n\n\n\x03\x07\x04\x02\x12\x03\x0c\x07\"\n\n\n\
-    \x03\x07\x04\x04\x12\x03\x16\x04\x0c\n\n\n\x03\x07\x04\x05\x12\x03\x16\r\
-    \x11\n\n\n\x03\x07\x04\x01\x12\x03\x16\x120\n\n\n\x03\x07\x04\x03\x12\
-    \x03\x1638\n3\n\x02\x07\x05\x12\x03\x18\x04:\x1a(\x20Use\x20`bytes::Byte\
-    s`\x20for\x20`string`\x20fields\n\n\n\n\x03\x07\x05\x02\x12\x03\x0c\x07\
-    \"\n\n\n\x03\x07\x05\x04\x12\x03\x18\x04\x0c\n\n\n\x03\x07\x05\x05\x12\
-    \x03\x18\r\x11\n\n\n\x03\x07\x05\x01\x12\x03\x18\x121\n\n\n\x03\x07\x05\
-    \x03\x12\x03\x1849\nJ\n\x02\x07\x06\x12\x03\x1b\x04+\x1a?\x20Use\x20`ser\
-    de_derive`\x20to\x20implement\x20`Serialize`\x20and\x20`Deserialize`\n\n\
-    \n\n\x03\x07\x06\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x06\x04\x12\x03\x1b\
-    \x04\x0c\n\n\n\x03\x07\x06\x05\x12\x03\x1b\r\x11\n\n\n\x03\x07\x06\x01\
-    \x12\x03\x1b\x12\"\n\n\n\x03\x07\x06\x03\x12\x03\x1b%*\n3\n\x02\x07\x07\
-    \x12\x03\x1d\x041\x1a(\x20Guard\x20serde\x20annotations\x20with\x20cfg\
-    \x20attr.\n\n\n\n\x03\x07\x07\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x07\
-    \x04\x12\x03\x1d\x04\x0c\n\n\n\x03\x07\x07\x05\x12\x03\x1d\r\x13\n\n\n\
-    \x03\x07\x07\x01\x12\x03\x1d\x14(\n\n\n\x03\x07\x07\x03\x12\x03\x1d+0\nN\
-    \n\x02\x07\x08\x12\x03\x20\x04+\x1aC\x20When\x20true,\x20will\x20only\
-    \x20generate\x20codes\x20that\x20works\x20with\x20lite\x20runtime.\n\n\n\
-    \n\x03\x07\x08\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x08\x04\x12\x03\x20\
-    \x04\x0c\n\n\n\x03\x07\x08\x05\x12\x03\x20\r\x11\n\n\n\x03\x07\x08\x01\
-    \x12\x03\x20\x12\"\n\n\n\x03\x07\x08\x03\x12\x03\x20%*\n\t\n\x01\x07\x12\
-    \x04#\04\x01\n7\n\x02\x07\t\x12\x03%\x04'\x1a,\x20When\x20true,\x20oneof\
-    \x20field\x20is\x20generated\x20public\n\n\n\n\x03\x07\t\x02\x12\x03#\
-    \x07%\n\n\n\x03\x07\t\x04\x12\x03%\x04\x0c\n\n\n\x03\x07\t\x05\x12\x03%\
-    \r\x11\n\n\n\x03\x07\t\x01\x12\x03%\x12\x1e\n\n\n\x03\x07\t\x03\x12\x03%\
-    !&\nI\n\x02\x07\n\x12\x03'\x04(\x1a>\x20When\x20true\x20all\x20fields\
-    \x20are\x20public,\x20and\x20not\x20accessors\x20generated\n\n\n\n\x03\
-    \x07\n\x02\x12\x03#\x07%\n\n\n\x03\x07\n\x04\x12\x03'\x04\x0c\n\n\n\x03\
-    \x07\n\x05\x12\x03'\r\x11\n\n\n\x03\x07\n\x01\x12\x03'\x12\x1f\n\n\n\x03\
-    \x07\n\x03\x12\x03'\"'\nP\n\x02\x07\x0b\x12\x03)\x04-\x1aE\x20When\x20fa\
-    lse,\x20`get_`,\x20`set_`,\x20`mut_`\x20etc.\x20accessors\x20are\x20not\
-    \x20generated\n\n\n\n\x03\x07\x0b\x02\x12\x03#\x07%\n\n\n\x03\x07\x0b\
-    \x04\x12\x03)\x04\x0c\n\n\n\x03\x07\x0b\x05\x12\x03)\r\x11\n\n\n\x03\x07\
-    \x0b\x01\x12\x03)\x12$\n\n\n\x03\x07\x0b\x03\x12\x03)',\nL\n\x02\x07\x0c\
-    \x12\x03+\x04*\x1aA\x20When\x20false,\x20`get_`\x20is\x20not\x20generate\
-    d\x20even\x20if\x20`syntax\x20=\x20\"proto2\"`\n\n\n\n\x03\x07\x0c\x02\
-    \x12\x03#\x07%\n\n\n\x03\x07\x0c\x04\x12\x03+\x04\x0c\n\n\n\x03\x07\x0c\
-    \x05\x12\x03+\r\x11\n\n\n\x03\x07\x0c\x01\x12\x03+\x12!\n\n\n\x03\x07\
-    \x0c\x03\x12\x03+$)\n2\n\x02\x07\r\x12\x03-\x045\x1a'\x20Use\x20`bytes::\
-    Bytes`\x20for\x20`bytes`\x20fields\n\n\n\n\x03\x07\r\x02\x12\x03#\x07%\n\
-    \n\n\x03\x07\r\x04\x12\x03-\x04\x0c\n\n\n\x03\x07\r\x05\x12\x03-\r\x11\n\
-    \n\n\x03\x07\r\x01\x12\x03-\x12,\n\n\n\x03\x07\r\x03\x12\x03-/4\n3\n\x02\
-    \x07\x0e\x12\x03/\x046\x1a(\x20Use\x20`bytes::Bytes`\x20for\x20`string`\
-    \x20fields\n\n\n\n\x03\x07\x0e\x02\x12\x03#\x07%\n\n\n\x03\x07\x0e\x04\
-    \x12\x03/\x04\x0c\n\n\n\x03\x07\x0e\x05\x12\x03/\r\x11\n\n\n\x03\x07\x0e\
-    \x01\x12\x03/\x12-\n\n\n\x03\x07\x0e\x03\x12\x03/05\nJ\n\x02\x07\x0f\x12\
-    \x031\x04'\x1a?\x20Use\x20`serde_derive`\x20to\x20implement\x20`Serializ\
-    e`\x20and\x20`Deserialize`\n\n\n\n\x03\x07\x0f\x02\x12\x03#\x07%\n\n\n\
-    \x03\x07\x0f\x04\x12\x031\x04\x0c\n\n\n\x03\x07\x0f\x05\x12\x031\r\x11\n\
-    \n\n\x03\x07\x0f\x01\x12\x031\x12\x1e\n\n\n\x03\x07\x0f\x03\x12\x031!&\n\
-    3\n\x02\x07\x10\x12\x033\x04-\x1a(\x20Guard\x20serde\x20annotations\x20w\
-    ith\x20cfg\x20attr.\n\n\n\n\x03\x07\x10\x02\x12\x03#\x07%\n\n\n\x03\x07\
-    \x10\x04\x12\x033\x04\x0c\n\n\n\x03\x07\x10\x05\x12\x033\r\x13\n\n\n\x03\
-    \x07\x10\x01\x12\x033\x14$\n\n\n\x03\x07\x10\x03\x12\x033',\n\t\n\x01\
-    \x07\x12\x046\0A\x01\nI\n\x02\x07\x11\x12\x038\x04.\x1a>\x20When\x20true\
+    \x11serdeDeriveCfgAll:F\n\x0frkyv_derive_all\x18\x90\x85\x01\x20\x01(\
+    \x08\x12\x1c.google.protobuf.FileOptionsR\rrkyvDeriveAll:M\n\x13rkyv_der\
+    ive_cfg_all\x18\x91\x85\x01\x20\x01(\t\x12\x1c.google.protobuf.FileOptio\
+    nsR\x10rkyvDeriveCfgAll:[\n\x1aimpl_bytes_conversions_all\x18\x92\x85\
+    \x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x17implBytesConve\
+    rsionsAll:H\n\x10lite_runtime_all\x18\x8b\x85\x01\x20\x01(\x08\x12\x1c.g\
+    oogle.protobuf.FileOptionsR\x0eliteRuntimeAll:D\n\x0cexpose_oneof\x18\
+    \xe9\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x0bexp\
+    oseOneof:F\n\rexpose_fields\x18\xeb\x84\x01\x20\x01(\x08\x12\x1f.google.\
+    protobuf.MessageOptionsR\x0cexposeFields:P\n\x12generate_accessors\x18\
+    \xec\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x11gen\
+    erateAccessors:J\n\x0fgenerate_getter\x18\xed\x84\x01\x20\x01(\x08\x12\
+    \x1f.google.protobuf.MessageOptionsR\x0egenerateGetter:^\n\x1acarllerche\
+    _bytes_for_bytes\x18\xf3\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.Me\
+    ssageOptionsR\x17carllercheBytesForBytes:`\n\x1bcarllerche_bytes_for_str\
+    ing\x18\xf4\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\
+    \x18carllercheBytesForString:D\n\x0cserde_derive\x18\x86\x85\x01\x20\x01\
+    (\x08\x12\x1f.google.protobuf.MessageOptionsR\x0bserdeDerive:K\n\x10serd\
+    e_derive_cfg\x18\x87\x85\x01\x20\x01(\t\x12\x1f.google.protobuf.MessageO\
+    ptionsR\x0eserdeDeriveCfg:B\n\x0brkyv_derive\x18\x90\x85\x01\x20\x01(\
+    \x08\x12\x1f.google.protobuf.MessageOptionsR\nrkyvDerive:I\n\x0frkyv_der\
+    ive_cfg\x18\x91\x85\x01\x20\x01(\t\x12\x1f.google.protobuf.MessageOption\
+    sR\rrkyvDeriveCfg:W\n\x16impl_bytes_conversions\x18\x92\x85\x01\x20\x01(\
+    \x08\x12\x1f.google.protobuf.MessageOptionsR\x14implBytesConversions:O\n\
+    \x13expose_fields_field\x18\xeb\x84\x01\x20\x01(\x08\x12\x1d.google.prot\
+    obuf.FieldOptionsR\x11exposeFieldsField:Y\n\x18generate_accessors_field\
+    \x18\xec\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x16g\
+    enerateAccessorsField:S\n\x15generate_getter_field\x18\xed\x84\x01\x20\
+    \x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x13generateGetterField:g\
+    \n\x20carllerche_bytes_for_bytes_field\x18\xf3\x84\x01\x20\x01(\x08\x12\
+    \x1d.google.protobuf.FieldOptionsR\x1ccarllercheBytesForBytesField:i\n!c\
+    arllerche_bytes_for_string_field\x18\xf4\x84\x01\x20\x01(\x08\x12\x1d.go\
+    ogle.protobuf.FieldOptionsR\x1dcarllercheBytesForStringFieldJ\xb4\x1e\n\
+    \x06\x12\x04\0\0N\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\t\n\x02\x03\0\
+    \x12\x03\x02\0*\n\xe5\x01\n\x01\x02\x12\x03\n\0\x122^\x20see\x20https://\
+    github.com/gogo/protobuf/blob/master/gogoproto/gogo.proto\n\x20for\x20th\
+    e\x20original\x20idea\n2{\x20Generated\x20files\x20can\x20be\x20customiz\
+    ed\x20using\x20this\x20proto\n\x20or\x20using\x20`Customize`\x20struct\
+    \x20when\x20codegen\x20is\x20invoked\x20programmatically.\n\n\t\n\x01\
+    \x07\x12\x04\x0c\0(\x01\n7\n\x02\x07\0\x12\x03\x0e\x04+\x1a,\x20When\x20\
+    true,\x20oneof\x20field\x20is\x20generated\x20public\n\n\n\n\x03\x07\0\
+    \x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\0\x04\x12\x03\x0e\x04\x0c\n\n\n\x03\
+    \x07\0\x05\x12\x03\x0e\r\x11\n\n\n\x03\x07\0\x01\x12\x03\x0e\x12\"\n\n\n\
+    \x03\x07\0\x03\x12\x03\x0e%*\nI\n\x02\x07\x01\x12\x03\x10\x04,\x1a>\x20W\
+    hen\x20true\x20all\x20fields\x20are\x20public,\x20and\x20not\x20accessor\
+    s\x20generated\n\n\n\n\x03\x07\x01\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\
+    \x01\x04\x12\x03\x10\x04\x0c\n\n\n\x03\x07\x01\x05\x12\x03\x10\r\x11\n\n\
+    \n\x03\x07\x01\x01\x12\x03\x10\x12#\n\n\n\x03\x07\x01\x03\x12\x03\x10&+\
+    \nP\n\x02\x07\x02\x12\x03\x12\x041\x1aE\x20When\x20false,\x20`get_`,\x20\
+    `set_`,\x20`mut_`\x20etc.\x20accessors\x20are\x20not\x20generated\n\n\n\
+    \n\x03\x07\x02\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x02\x04\x12\x03\x12\
+    \x04\x0c\n\n\n\x03\x07\x02\x05\x12\x03\x12\r\x11\n\n\n\x03\x07\x02\x01\
+    \x12\x03\x12\x12(\n\n\n\x03\x07\x02\x03\x12\x03\x12+0\nL\n\x02\x07\x03\
+    \x12\x03\x14\x04.\x1aA\x20When\x20false,\x20`get_`\x20is\x20not\x20gener\
+    ated\x20even\x20if\x20`syntax\x20=\x20\"proto2\"`\n\n\n\n\x03\x07\x03\
+    \x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x03\x04\x12\x03\x14\x04\x0c\n\n\n\
+    \x03\x07\x03\x05\x12\x03\x14\r\x11\n\n\n\x03\x07\x03\x01\x12\x03\x14\x12\
+    %\n\n\n\x03\x07\x03\x03\x12\x03\x14(-\n2\n\x02\x07\x04\x12\x03\x16\x049\
+    \x1a'\x20Use\x20`bytes::Bytes`\x20for\x20`bytes`\x20fields\n\n\n\n\x03\
+    \x07\x04\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x04\x04\x12\x03\x16\x04\x0c\
+    \n\n\n\x03\x07\x04\x05\x12\x03\x16\r\x11\n\n\n\x03\x07\x04\x01\x12\x03\
+    \x16\x120\n\n\n\x03\x07\x04\x03\x12\x03\x1638\n3\n\x02\x07\x05\x12\x03\
+    \x18\x04:\x1a(\x20Use\x20`bytes::Bytes`\x20for\x20`string`\x20fields\n\n\
+    \n\n\x03\x07\x05\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x05\x04\x12\x03\x18\
+    \x04\x0c\n\n\n\x03\x07\x05\x05\x12\x03\x18\r\x11\n\n\n\x03\x07\x05\x01\
+    \x12\x03\x18\x121\n\n\n\x03\x07\x05\x03\x12\x03\x1849\nJ\n\x02\x07\x06\
+    \x12\x03\x1b\x04+\x1a?\x20Use\x20`serde_derive`\x20to\x20implement\x20`S\
+    erialize`\x20and\x20`Deserialize`\n\n\n\n\x03\x07\x06\x02\x12\x03\x0c\
+    \x07\"\n\n\n\x03\x07\x06\x04\x12\x03\x1b\x04\x0c\n\n\n\x03\x07\x06\x05\
+    \x12\x03\x1b\r\x11\n\n\n\x03\x07\x06\x01\x12\x03\x1b\x12\"\n\n\n\x03\x07\
+    \x06\x03\x12\x03\x1b%*\n3\n\x02\x07\x07\x12\x03\x1d\x041\x1a(\x20Guard\
+    \x20serde\x20annotations\x20with\x20cfg\x20attr.\n\n\n\n\x03\x07\x07\x02\
+    \x12\x03\x0c\x07\"\n\n\n\x03\x07\x07\x04\x12\x03\x1d\x04\x0c\n\n\n\x03\
+    \x07\x07\x05\x12\x03\x1d\r\x13\n\n\n\x03\x07\x07\x01\x12\x03\x1d\x14(\n\
+    \n\n\x03\x07\x07\x03\x12\x03\x1d+0\nM\n\x02\x07\x08\x12\x03\x20\x04*\x1a\
+    B\x20Use\x20`rkyv`\x20to\x20implement\x20`Archive`,\x20`Serialize`\x20an\
+    d\x20`Deserialize`\n\n\n\n\x03\x07\x08\x02\x12\x03\x0c\x07\"\n\n\n\x03\
+    \x07\x08\x04\x12\x03\x20\x04\x0c\n\n\n\x03\x07\x08\x05\x12\x03\x20\r\x11\
+    \n\n\n\x03\x07\x08\x01\x12\x03\x20\x12!\n\n\n\x03\x07\x08\x03\x12\x03\
+    \x20$)\n2\n\x02\x07\t\x12\x03\"\x040\x1a'\x20Guard\x20rkyv\x20annotation\
+    s\x20with\x20cfg\x20attr.\n\n\n\n\x03\x07\t\x02\x12\x03\x0c\x07\"\n\n\n\
+    \x03\x07\t\x04\x12\x03\"\x04\x0c\n\n\n\x03\x07\t\x05\x12\x03\"\r\x13\n\n\
+    \n\x03\x07\t\x01\x12\x03\"\x14'\n\n\n\x03\x07\t\x03\x12\x03\"*/\nZ\n\x02\
+    \x07\n\x12\x03$\x045\x1aO\x20Implement\x20`TryFrom<&[u8]>`\x20for\x20mes\
+    sages\x20and\x20`TryFrom<&Message>`\x20for\x20`Vec<u8>`\n\n\n\n\x03\x07\
+    \n\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\n\x04\x12\x03$\x04\x0c\n\n\n\x03\
+    \x07\n\x05\x12\x03$\r\x11\n\n\n\x03\x07\n\x01\x12\x03$\x12,\n\n\n\x03\
+    \x07\n\x03\x12\x03$/4\nN\n\x02\x07\x0b\x12\x03'\x04+\x1aC\x20When\x20tru\
+    e,\x20will\x20only\x20generate\x20codes\x20that\x20works\x20with\x20lite\
+    \x20runtime.\n\n\n\n\x03\x07\x0b\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x0b\
+    \x04\x12\x03'\x04\x0c\n\n\n\x03\x07\x0b\x05\x12\x03'\r\x11\n\n\n\x03\x07\
+    \x0b\x01\x12\x03'\x12\"\n\n\n\x03\x07\x0b\x03\x12\x03'%*\n\t\n\x01\x07\
+    \x12\x04*\0A\x01\n7\n\x02\x07\x0c\x12\x03,\x04'\x1a,\x20When\x20true,\
+    \x20oneof\x20field\x20is\x20generated\x20public\n\n\n\n\x03\x07\x0c\x02\
+    \x12\x03*\x07%\n\n\n\x03\x07\x0c\x04\x12\x03,\x04\x0c\n\n\n\x03\x07\x0c\
+    \x05\x12\x03,\r\x11\n\n\n\x03\x07\x0c\x01\x12\x03,\x12\x1e\n\n\n\x03\x07\
+    \x0c\x03\x12\x03,!&\nI\n\x02\x07\r\x12\x03.\x04(\x1a>\x20When\x20true\
     \x20all\x20fields\x20are\x20public,\x20and\x20not\x20accessors\x20genera\
-    ted\n\n\n\n\x03\x07\x11\x02\x12\x036\x07#\n\n\n\x03\x07\x11\x04\x12\x038\
-    \x04\x0c\n\n\n\x03\x07\x11\x05\x12\x038\r\x11\n\n\n\x03\x07\x11\x01\x12\
-    \x038\x12%\n\n\n\x03\x07\x11\x03\x12\x038(-\nP\n\x02\x07\x12\x12\x03:\
-    \x043\x1aE\x20When\x20false,\x20`get_`,\x20`set_`,\x20`mut_`\x20etc.\x20\
-    accessors\x20are\x20not\x20generated\n\n\n\n\x03\x07\x12\x02\x12\x036\
-    \x07#\n\n\n\x03\x07\x12\x04\x12\x03:\x04\x0c\n\n\n\x03\x07\x12\x05\x12\
-    \x03:\r\x11\n\n\n\x03\x07\x12\x01\x12\x03:\x12*\n\n\n\x03\x07\x12\x03\
-    \x12\x03:-2\nL\n\x02\x07\x13\x12\x03<\x040\x1aA\x20When\x20false,\x20`ge\
-    t_`\x20is\x20not\x20generated\x20even\x20if\x20`syntax\x20=\x20\"proto2\
-    \"`\n\n\n\n\x03\x07\x13\x02\x12\x036\x07#\n\n\n\x03\x07\x13\x04\x12\x03<\
-    \x04\x0c\n\n\n\x03\x07\x13\x05\x12\x03<\r\x11\n\n\n\x03\x07\x13\x01\x12\
-    \x03<\x12'\n\n\n\x03\x07\x13\x03\x12\x03<*/\n2\n\x02\x07\x14\x12\x03>\
-    \x04;\x1a'\x20Use\x20`bytes::Bytes`\x20for\x20`bytes`\x20fields\n\n\n\n\
-    \x03\x07\x14\x02\x12\x036\x07#\n\n\n\x03\x07\x14\x04\x12\x03>\x04\x0c\n\
-    \n\n\x03\x07\x14\x05\x12\x03>\r\x11\n\n\n\x03\x07\x14\x01\x12\x03>\x122\
-    \n\n\n\x03\x07\x14\x03\x12\x03>5:\n3\n\x02\x07\x15\x12\x03@\x04<\x1a(\
-    \x20Use\x20`bytes::Bytes`\x20for\x20`string`\x20fields\n\n\n\n\x03\x07\
-    \x15\x02\x12\x036\x07#\n\n\n\x03\x07\x15\x04\x12\x03@\x04\x0c\n\n\n\x03\
-    \x07\x15\x05\x12\x03@\r\x11\n\n\n\x03\x07\x15\x01\x12\x03@\x123\n\n\n\
-    \x03\x07\x15\x03\x12\x03@6;\
+    ted\n\n\n\n\x03\x07\r\x02\x12\x03*\x07%\n\n\n\x03\x07\r\x04\x12\x03.\x04\
+    \x0c\n\n\n\x03\x07\r\x05\x12\x03.\r\x11\n\n\n\x03\x07\r\x01\x12\x03.\x12\
+    \x1f\n\n\n\x03\x07\r\x03\x12\x03.\"'\nP\n\x02\x07\x0e\x12\x030\x04-\x1aE\
+    \x20When\x20false,\x20`get_`,\x20`set_`,\x20`mut_`\x20etc.\x20accessors\
+    \x20are\x20not\x20generated\n\n\n\n\x03\x07\x0e\x02\x12\x03*\x07%\n\n\n\
+    \x03\x07\x0e\x04\x12\x030\x04\x0c\n\n\n\x03\x07\x0e\x05\x12\x030\r\x11\n\
+    \n\n\x03\x07\x0e\x01\x12\x030\x12$\n\n\n\x03\x07\x0e\x03\x12\x030',\nL\n\
+    \x02\x07\x0f\x12\x032\x04*\x1aA\x20When\x20false,\x20`get_`\x20is\x20not\
+    \x20generated\x20even\x20if\x20`syntax\x20=\x20\"proto2\"`\n\n\n\n\x03\
+    \x07\x0f\x02\x12\x03*\x07%\n\n\n\x03\x07\x0f\x04\x12\x032\x04\x0c\n\n\n\
+    \x03\x07\x0f\x05\x12\x032\r\x11\n\n\n\x03\x07\x0f\x01\x12\x032\x12!\n\n\
+    \n\x03\x07\x0f\x03\x12\x032$)\n2\n\x02\x07\x10\x12\x034\x045\x1a'\x20Use\
+    \x20`bytes::Bytes`\x20for\x20`bytes`\x20fields\n\n\n\n\x03\x07\x10\x02\
+    \x12\x03*\x07%\n\n\n\x03\x07\x10\x04\x12\x034\x04\x0c\n\n\n\x03\x07\x10\
+    \x05\x12\x034\r\x11\n\n\n\x03\x07\x10\x01\x12\x034\x12,\n\n\n\x03\x07\
+    \x10\x03\x12\x034/4\n3\n\x02\x07\x11\x12\x036\x046\x1a(\x20Use\x20`bytes\
+    ::Bytes`\x20for\x20`string`\x20fields\n\n\n\n\x03\x07\x11\x02\x12\x03*\
+    \x07%\n\n\n\x03\x07\x11\x04\x12\x036\x04\x0c\n\n\n\x03\x07\x11\x05\x12\
+    \x036\r\x11\n\n\n\x03\x07\x11\x01\x12\x036\x12-\n\n\n\x03\x07\x11\x03\
+    \x12\x03605\nJ\n\x02\x07\x12\x12\x038\x04'\x1a?\x20Use\x20`serde_derive`\
+    \x20to\x20implement\x20`Serialize`\x20and\x20`Deserialize`\n\n\n\n\x03\
+    \x07\x12\x02\x12\x03*\x07%\n\n\n\x03\x07\x12\x04\x12\x038\x04\x0c\n\n\n\
+    \x03\x07\x12\x05\x12\x038\r\x11\n\n\n\x03\x07\x12\x01\x12\x038\x12\x1e\n\
+    \n\n\x03\x07\x12\x03\x12\x038!&\n3\n\x02\x07\x13\x12\x03:\x04-\x1a(\x20G\
+    uard\x20serde\x20annotations\x20with\x20cfg\x20attr.\n\n\n\n\x03\x07\x13\
+    \x02\x12\x03*\x07%\n\n\n\x03\x07\x13\x04\x12\x03:\x04\x0c\n\n\n\x03\x07\
+    \x13\x05\x12\x03:\r\x13\n\n\n\x03\x07\x13\x01\x12\x03:\x14$\n\n\n\x03\
+    \x07\x13\x03\x12\x03:',\nM\n\x02\x07\x14\x12\x03<\x04&\x1aB\x20Use\x20`r\
+    kyv`\x20to\x20implement\x20`Archive`,\x20`Serialize`\x20and\x20`Deserial\
+    ize`\n\n\n\n\x03\x07\x14\x02\x12\x03*\x07%\n\n\n\x03\x07\x14\x04\x12\x03\
+    <\x04\x0c\n\n\n\x03\x07\x14\x05\x12\x03<\r\x11\n\n\n\x03\x07\x14\x01\x12\
+    \x03<\x12\x1d\n\n\n\x03\x07\x14\x03\x12\x03<\x20%\n2\n\x02\x07\x15\x12\
+    \x03>\x04,\x1a'\x20Guard\x20rkyv\x20annotations\x20with\x20cfg\x20attr.\
+    \n\n\n\n\x03\x07\x15\x02\x12\x03*\x07%\n\n\n\x03\x07\x15\x04\x12\x03>\
+    \x04\x0c\n\n\n\x03\x07\x15\x05\x12\x03>\r\x13\n\n\n\x03\x07\x15\x01\x12\
+    \x03>\x14#\n\n\n\x03\x07\x15\x03\x12\x03>&+\nZ\n\x02\x07\x16\x12\x03@\
+    \x041\x1aO\x20Implement\x20`TryFrom<&[u8]>`\x20for\x20messages\x20and\
+    \x20`TryFrom<&Message>`\x20for\x20`Vec<u8>`\n\n\n\n\x03\x07\x16\x02\x12\
+    \x03*\x07%\n\n\n\x03\x07\x16\x04\x12\x03@\x04\x0c\n\n\n\x03\x07\x16\x05\
+    \x12\x03@\r\x11\n\n\n\x03\x07\x16\x01\x12\x03@\x12(\n\n\n\x03\x07\x16\
+    \x03\x12\x03@+0\n\t\n\x01\x07\x12\x04C\0N\x01\nI\n\x02\x07\x17\x12\x03E\
+    \x04.\x1a>\x20When\x20true\x20all\x20fields\x20are\x20public,\x20and\x20\
+    not\x20accessors\x20generated\n\n\n\n\x03\x07\x17\x02\x12\x03C\x07#\n\n\
+    \n\x03\x07\x17\x04\x12\x03E\x04\x0c\n\n\n\x03\x07\x17\x05\x12\x03E\r\x11\
+    \n\n\n\x03\x07\x17\x01\x12\x03E\x12%\n\n\n\x03\x07\x17\x03\x12\x03E(-\nP\
+    \n\x02\x07\x18\x12\x03G\x043\x1aE\x20When\x20false,\x20`get_`,\x20`set_`\
+    ,\x20`mut_`\x20etc.\x20accessors\x20are\x20not\x20generated\n\n\n\n\x03\
+    \x07\x18\x02\x12\x03C\x07#\n\n\n\x03\x07\x18\x04\x12\x03G\x04\x0c\n\n\n\
+    \x03\x07\x18\x05\x12\x03G\r\x11\n\n\n\x03\x07\x18\x01\x12\x03G\x12*\n\n\
+    \n\x03\x07\x18\x03\x12\x03G-2\nL\n\x02\x07\x19\x12\x03I\x040\x1aA\x20Whe\
+    n\x20false,\x20`get_`\x20is\x20not\x20generated\x20even\x20if\x20`syntax\
+    \x20=\x20\"proto2\"`\n\n\n\n\x03\x07\x19\x02\x12\x03C\x07#\n\n\n\x03\x07\
+    \x19\x04\x12\x03I\x04\x0c\n\n\n\x03\x07\x19\x05\x12\x03I\r\x11\n\n\n\x03\
+    \x07\x19\x01\x12\x03I\x12'\n\n\n\x03\x07\x19\x03\x12\x03I*/\n2\n\x02\x07\
+    \x1a\x12\x03K\x04;\x1a'\x20Use\x20`bytes::Bytes`\x20for\x20`bytes`\x20fi\
+    elds\n\n\n\n\x03\x07\x1a\x02\x12\x03C\x07#\n\n\n\x03\x07\x1a\x04\x12\x03\
+    K\x04\x0c\n\n\n\x03\x07\x1a\x05\x12\x03K\r\x11\n\n\n\x03\x07\x1a\x01\x12\
+    \x03K\x122\n\n\n\x03\x07\x1a\x03\x12\x03K5:\n3\n\x02\x07\x1b\x12\x03M\
+    \x04<\x1a(\x20Use\x20`bytes::Bytes`\x20for\x20`string`\x20fields\n\n\n\n\
+    \x03\x07\x1b\x02\x12\x03C\x07#\n\n\n\x03\x07\x1b\x04\x12\x03M\x04\x0c\n\
+    \n\n\x03\x07\x1b\x05\x12\x03M\r\x11\n\n\n\x03\x07\x1b\x01\x12\x03M\x123\
+    \n\n\n\x03\x07\x1b\x03\x12\x03M6;\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
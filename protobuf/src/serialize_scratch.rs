@@ -0,0 +1,162 @@
+use std::cell::RefCell;
+
+/// Buffers reused between serializations to [`Write`](std::io::Write).
+///
+/// [`CodedOutputStream::new`](crate::CodedOutputStream::new) allocates an output
+/// buffer each time it is created, which is noticeable when many small messages
+/// are written, e. g. one message per request. Stream created with
+/// [`CodedOutputStream::with_scratch`](crate::CodedOutputStream::with_scratch)
+/// takes that buffer from the scratch instead, so it is allocated only once.
+///
+/// Sizes of nested messages are cached in the messages themselves.
+/// When nested message sizes are not known before writing, like in
+/// [`FieldNumberRemap::transcode_with_scratch`](crate::transcode::FieldNumberRemap::transcode_with_scratch),
+/// nested messages are encoded into temporary buffers to find their lengths.
+/// The scratch keeps a stack of these buffers, one per nesting level,
+/// so they are allocated only once too.
+#[derive(Debug, Default)]
+pub struct SerializeScratch {
+    output_buffer: Vec<u8>,
+    /// Empty buffers for nested message encodings.
+    nested_buffers: Vec<Vec<u8>>,
+}
+
+thread_local! {
+    static THREAD_LOCAL_SCRATCH: RefCell<SerializeScratch> = RefCell::new(SerializeScratch::new());
+}
+
+impl SerializeScratch {
+    /// New empty scratch. Buffers are allocated on first use.
+    pub fn new() -> SerializeScratch {
+        SerializeScratch::default()
+    }
+
+    /// Invoke callback with scratch of the current thread.
+    ///
+    /// If thread-local scratch is already in use (i. e. this function is called
+    /// from the callback), callback gets new empty scratch.
+    pub fn with_thread_local<R, F>(f: F) -> R
+    where
+        F: FnOnce(&mut SerializeScratch) -> R,
+    {
+        THREAD_LOCAL_SCRATCH.with(|scratch| match scratch.try_borrow_mut() {
+            Ok(mut scratch) => f(&mut scratch),
+            Err(_) => f(&mut SerializeScratch::new()),
+        })
+    }
+
+    /// Output buffer of given length.
+    pub(crate) fn output_buffer(&mut self, len: usize) -> &mut [u8] {
+        if self.output_buffer.len() < len {
+            self.output_buffer.resize(len, 0);
+        }
+        &mut self.output_buffer[..len]
+    }
+
+    /// Empty buffer to encode a nested message,
+    /// must be returned with [`push_nested_buffer`](Self::push_nested_buffer).
+    pub(crate) fn pop_nested_buffer(&mut self) -> Vec<u8> {
+        self.nested_buffers.pop().unwrap_or_default()
+    }
+
+    /// Return buffer taken with [`pop_nested_buffer`](Self::pop_nested_buffer).
+    pub(crate) fn push_nested_buffer(&mut self, mut buffer: Vec<u8>) {
+        buffer.clear();
+        self.nested_buffers.push(buffer);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SerializeScratch;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::transcode::FieldNumberRemap;
+    use crate::CodedInputStream;
+    use crate::CodedOutputStream;
+    use crate::Message;
+
+    fn message() -> FileDescriptorProto {
+        let mut m = FileDescriptorProto::new();
+        m.set_name("scratch.proto".to_owned());
+        m.set_package("scratch".to_owned());
+        m
+    }
+
+    #[test]
+    fn same_output_as_without_scratch() {
+        let m = message();
+        let mut scratch = SerializeScratch::new();
+
+        let mut expected = Vec::new();
+        m.write_length_delimited_to_writer(&mut expected).unwrap();
+        m.write_to_writer(&mut expected).unwrap();
+
+        let mut written = Vec::new();
+        m.write_length_delimited_to_writer_with_scratch(&mut written, &mut scratch)
+            .unwrap();
+        m.write_to_writer_with_scratch(&mut written, &mut scratch)
+            .unwrap();
+
+        assert_eq!(expected, written);
+    }
+
+    #[test]
+    fn buffer_reused() {
+        let m = message();
+        let mut scratch = SerializeScratch::new();
+        let mut written = Vec::new();
+
+        m.write_to_writer_with_scratch(&mut written, &mut scratch)
+            .unwrap();
+        let buffer = scratch.output_buffer.as_ptr();
+        m.write_to_writer_with_scratch(&mut written, &mut scratch)
+            .unwrap();
+        assert_eq!(buffer, scratch.output_buffer.as_ptr());
+    }
+
+    #[test]
+    fn nested_buffers_reused() {
+        let mut m = message();
+        let mut nested = DescriptorProto::new();
+        nested.set_name("M".to_owned());
+        m.message_type.push(nested);
+        let bytes = m.write_to_bytes().unwrap();
+        let descriptor = FileDescriptorProto::descriptor_static();
+        let remap = FieldNumberRemap::by_name(&descriptor, &descriptor);
+        let mut scratch = SerializeScratch::new();
+
+        let transcode = |scratch: &mut SerializeScratch| {
+            let mut is = CodedInputStream::from_bytes(&bytes);
+            let mut written = Vec::new();
+            {
+                let mut os = CodedOutputStream::vec(&mut written);
+                remap
+                    .transcode_with_scratch(&mut is, &mut os, scratch)
+                    .unwrap();
+                os.flush().unwrap();
+            }
+            assert_eq!(bytes, written);
+        };
+
+        transcode(&mut scratch);
+        assert_eq!(1, scratch.nested_buffers.len());
+        let buffer = scratch.nested_buffers[0].as_ptr();
+        transcode(&mut scratch);
+        assert_eq!(1, scratch.nested_buffers.len());
+        assert_eq!(buffer, scratch.nested_buffers[0].as_ptr());
+    }
+
+    #[test]
+    fn thread_local_reentrant() {
+        let m = message();
+        let mut written = Vec::new();
+        SerializeScratch::with_thread_local(|outer| {
+            SerializeScratch::with_thread_local(|inner| {
+                m.write_to_writer_with_scratch(&mut written, inner).unwrap();
+            });
+            m.write_to_writer_with_scratch(&mut written, outer).unwrap();
+        });
+        assert_eq!(m.write_to_bytes().unwrap().repeat(2), written);
+    }
+}
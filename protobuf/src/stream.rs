@@ -0,0 +1,316 @@
+//! Read and write sequences of length-delimited messages.
+//!
+//! Each message is prefixed with its length encoded as varint,
+//! the same framing as Java `writeDelimitedTo` and `parseDelimitedFrom`,
+//! so streams can be exchanged with other protobuf implementations.
+//!
+//! ```
+//! # use protobuf::descriptor::FileDescriptorProto;
+//! # use protobuf::stream::LengthDelimitedReader;
+//! # use protobuf::stream::LengthDelimitedWriter;
+//! let mut writer = LengthDelimitedWriter::new(Vec::new());
+//! for name in &["a.proto", "b.proto"] {
+//!     let mut file = FileDescriptorProto::new();
+//!     file.set_name(name.to_string());
+//!     writer.write(&file).unwrap();
+//! }
+//! let bytes = writer.into_inner();
+//!
+//! let reader = LengthDelimitedReader::new(&bytes[..]);
+//! let names: Vec<String> = reader
+//!     .messages::<FileDescriptorProto>()
+//!     .map(|file| file.map(|file| file.get_name().to_owned()))
+//!     .collect::<Result<_, _>>()
+//!     .unwrap();
+//! assert_eq!(vec!["a.proto", "b.proto"], names);
+//! ```
+//!
+//! See [`framing`](crate::framing) to read or write a single message
+//! with other framings.
+
+use std::io::Read;
+use std::io::Write;
+use std::marker::PhantomData;
+
+use crate::framing::read_message_bytes_or_eof;
+use crate::framing::write_one_dyn;
+use crate::framing::Framing;
+use crate::reflect::MessageDescriptor;
+use crate::Message;
+use crate::MessageDyn;
+use crate::ProtobufResult;
+
+/// Write messages prefixed with varint length.
+///
+/// Each message is written to the underlying writer with one or more
+/// write calls as soon as it is serialized, so the writer need not be buffered.
+#[derive(Debug)]
+pub struct LengthDelimitedWriter<W: Write> {
+    w: W,
+}
+
+impl<W: Write> LengthDelimitedWriter<W> {
+    /// Write messages to the writer.
+    pub fn new(w: W) -> LengthDelimitedWriter<W> {
+        LengthDelimitedWriter { w }
+    }
+
+    /// Write a message.
+    pub fn write<M: Message>(&mut self, m: &M) -> ProtobufResult<()> {
+        self.write_dyn(m)
+    }
+
+    /// Write a message.
+    pub fn write_dyn(&mut self, m: &dyn MessageDyn) -> ProtobufResult<()> {
+        write_one_dyn(m, &mut self.w, Framing::Varint)
+    }
+
+    /// Write all messages from the iterator.
+    pub fn write_all<'a, M: Message>(
+        &mut self,
+        messages: impl IntoIterator<Item = &'a M>,
+    ) -> ProtobufResult<()> {
+        for m in messages {
+            self.write(m)?;
+        }
+        Ok(())
+    }
+
+    /// Flush the underlying writer.
+    pub fn flush(&mut self) -> ProtobufResult<()> {
+        self.w.flush()?;
+        Ok(())
+    }
+
+    /// The underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.w
+    }
+
+    /// The underlying writer.
+    ///
+    /// Writing to it directly breaks the framing unless
+    /// complete length-delimited messages are written.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.w
+    }
+
+    /// Return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.w
+    }
+}
+
+/// Read messages prefixed with varint length.
+///
+/// The length prefix is read byte by byte to not read past the message,
+/// so reading from unbuffered source like a file or a socket is slow,
+/// wrap it in [`BufReader`](std::io::BufReader).
+#[derive(Debug)]
+pub struct LengthDelimitedReader<R: Read> {
+    r: R,
+}
+
+impl<R: Read> LengthDelimitedReader<R> {
+    /// Read messages from the reader.
+    pub fn new(r: R) -> LengthDelimitedReader<R> {
+        LengthDelimitedReader { r }
+    }
+
+    /// Read the next message, `None` is returned at the end of the stream.
+    ///
+    /// End of the stream in the middle of a message is an error.
+    pub fn read<M: Message>(&mut self) -> ProtobufResult<Option<M>> {
+        match read_message_bytes_or_eof(&mut self.r, Framing::Varint)? {
+            Some(bytes) => M::parse_from_bytes(&bytes).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Read the next message of given type, `None` is returned at the end of the stream.
+    ///
+    /// End of the stream in the middle of a message is an error.
+    pub fn read_dyn(
+        &mut self,
+        descriptor: &MessageDescriptor,
+    ) -> ProtobufResult<Option<Box<dyn MessageDyn>>> {
+        let bytes = match read_message_bytes_or_eof(&mut self.r, Framing::Varint)? {
+            Some(bytes) => bytes,
+            None => return Ok(None),
+        };
+        let mut message = descriptor.new_instance();
+        message.merge_from_bytes_dyn(&bytes)?;
+        message.check_initialized_dyn()?;
+        Ok(Some(message))
+    }
+
+    /// Iterate over all remaining messages.
+    ///
+    /// Iteration stops after the first error.
+    pub fn messages<M: Message>(self) -> Messages<R, M> {
+        Messages {
+            reader: self,
+            done: false,
+            marker: PhantomData,
+        }
+    }
+
+    /// Iterate over all remaining messages of given type.
+    ///
+    /// Iteration stops after the first error.
+    pub fn messages_dyn(self, descriptor: MessageDescriptor) -> MessagesDyn<R> {
+        MessagesDyn {
+            reader: self,
+            descriptor,
+            done: false,
+        }
+    }
+
+    /// The underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.r
+    }
+
+    /// The underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.r
+    }
+
+    /// Return the underlying reader.
+    ///
+    /// The reader is positioned after the last message read.
+    pub fn into_inner(self) -> R {
+        self.r
+    }
+}
+
+/// Iterator over messages returned by [`LengthDelimitedReader::messages`].
+#[derive(Debug)]
+pub struct Messages<R: Read, M> {
+    reader: LengthDelimitedReader<R>,
+    done: bool,
+    marker: PhantomData<fn() -> M>,
+}
+
+impl<R: Read, M: Message> Iterator for Messages<R, M> {
+    type Item = ProtobufResult<M>;
+
+    fn next(&mut self) -> Option<ProtobufResult<M>> {
+        if self.done {
+            return None;
+        }
+        let r = self.reader.read().transpose();
+        self.done = !matches!(r, Some(Ok(..)));
+        r
+    }
+}
+
+/// Iterator over messages returned by [`LengthDelimitedReader::messages_dyn`].
+#[derive(Debug)]
+pub struct MessagesDyn<R: Read> {
+    reader: LengthDelimitedReader<R>,
+    descriptor: MessageDescriptor,
+    done: bool,
+}
+
+impl<R: Read> Iterator for MessagesDyn<R> {
+    type Item = ProtobufResult<Box<dyn MessageDyn>>;
+
+    fn next(&mut self) -> Option<ProtobufResult<Box<dyn MessageDyn>>> {
+        if self.done {
+            return None;
+        }
+        let r = self.reader.read_dyn(&self.descriptor).transpose();
+        self.done = !matches!(r, Some(Ok(..)));
+        r
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::FileDescriptorProto;
+
+    fn message(name: &str) -> FileDescriptorProto {
+        let mut m = FileDescriptorProto::new();
+        m.set_name(name.to_owned());
+        m
+    }
+
+    fn messages() -> Vec<FileDescriptorProto> {
+        vec![message("a.proto"), FileDescriptorProto::new(), message("c")]
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut writer = LengthDelimitedWriter::new(Vec::new());
+        writer.write_all(&messages()).unwrap();
+        writer.flush().unwrap();
+        let bytes = writer.into_inner();
+
+        // Same as writing messages one by one
+        let expected: Vec<u8> = messages()
+            .iter()
+            .flat_map(|m| m.write_length_delimited_to_bytes().unwrap())
+            .collect();
+        assert_eq!(expected, bytes);
+
+        let mut reader = LengthDelimitedReader::new(&bytes[..]);
+        for m in messages() {
+            assert_eq!(Some(m), reader.read().unwrap());
+        }
+        assert_eq!(None, reader.read::<FileDescriptorProto>().unwrap());
+        assert_eq!(None, reader.read::<FileDescriptorProto>().unwrap());
+
+        let read: Vec<FileDescriptorProto> = LengthDelimitedReader::new(&bytes[..])
+            .messages()
+            .collect::<ProtobufResult<_>>()
+            .unwrap();
+        assert_eq!(messages(), read);
+    }
+
+    #[test]
+    fn dyn_round_trip() {
+        let descriptor = FileDescriptorProto::descriptor_static();
+        let mut writer = LengthDelimitedWriter::new(Vec::new());
+        for m in messages() {
+            writer.write_dyn(&m).unwrap();
+        }
+        let bytes = writer.into_inner();
+
+        let read: Vec<FileDescriptorProto> = LengthDelimitedReader::new(&bytes[..])
+            .messages_dyn(descriptor)
+            .map(|m| *m.unwrap().downcast_box().unwrap())
+            .collect();
+        assert_eq!(messages(), read);
+    }
+
+    #[test]
+    fn truncated() {
+        let mut writer = LengthDelimitedWriter::new(Vec::new());
+        writer.write(&message("a.proto")).unwrap();
+        writer.write(&message("b.proto")).unwrap();
+        let mut bytes = writer.into_inner();
+        bytes.pop();
+
+        let mut iter = LengthDelimitedReader::new(&bytes[..]).messages::<FileDescriptorProto>();
+        assert_eq!(message("a.proto"), iter.next().unwrap().unwrap());
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+
+        // Incomplete length prefix
+        let mut reader = LengthDelimitedReader::new(&[0x80u8][..]);
+        assert!(reader.read::<FileDescriptorProto>().is_err());
+    }
+
+    #[test]
+    fn reader_position() {
+        let mut bytes = message("a.proto")
+            .write_length_delimited_to_bytes()
+            .unwrap();
+        bytes.extend_from_slice(b"rest");
+        let mut reader = LengthDelimitedReader::new(&bytes[..]);
+        assert_eq!(Some(message("a.proto")), reader.read().unwrap());
+        assert_eq!(b"rest", reader.into_inner());
+    }
+}
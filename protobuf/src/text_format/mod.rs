@@ -21,6 +21,8 @@
 
 mod parse;
 mod print;
+#[cfg(feature = "with-test-macros")]
+mod proto_msg;
 
 // Used by text format parser and by pure-rust codegen parsed
 // this it is public but hidden module.
@@ -29,12 +31,19 @@ mod print;
 pub mod lexer;
 
 pub use self::parse::merge_from_str;
+pub use self::parse::parse_dyn;
 pub use self::parse::parse_from_str;
 pub use self::parse::ParseError;
+pub(crate) use self::print::compare_map_keys;
 pub use self::print::fmt;
+pub use self::print::print_normalized;
 pub use self::print::print_to;
 pub use self::print::print_to_string;
+pub use self::print::print_to_string_with_options;
+pub use self::print::print_to_with_options;
 #[doc(hidden)]
 pub use self::print::quote_bytes_to;
 #[doc(hidden)]
 pub use self::print::quote_escape_bytes;
+pub use self::print::PrintOptions;
+pub use self::print::MAX_DEPTH_PLACEHOLDER;
@@ -15,6 +15,7 @@ use crate::text_format::lexer::ParserLanguage;
 use crate::text_format::lexer::StrLitDecodeError;
 use crate::text_format::lexer::Tokenizer;
 use crate::text_format::lexer::TokenizerError;
+use crate::well_known_types::Any;
 
 #[derive(Debug)]
 pub enum ParseErrorWithoutLoc {
@@ -26,6 +27,7 @@ pub enum ParseErrorWithoutLoc {
     IntegerOverflow,
     ExpectingBool,
     MessageNotInitialized,
+    AnyTypeNotFound(String),
 }
 
 impl From<TokenizerError> for ParseErrorWithoutLoc {
@@ -55,7 +57,16 @@ pub struct ParseError {
 
 impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}: {:?}", self.loc, self.error)
+        match &self.error {
+            ParseErrorWithoutLoc::AnyTypeNotFound(type_url) => {
+                write!(
+                    f,
+                    "{}: message type of Any not found: {}",
+                    self.loc, type_url
+                )
+            }
+            e => write!(f, "{}: {:?}", self.loc, e),
+        }
     }
 }
 
@@ -67,6 +78,8 @@ pub type ParseWithLocResult<A> = Result<A, ParseError>;
 #[derive(Clone)]
 struct Parser<'a> {
     tokenizer: Tokenizer<'a>,
+    /// Type of the parsed message, types of expanded `Any` are searched in its files
+    root: MessageDescriptor,
 }
 
 impl<'a> Parser<'a> {
@@ -81,8 +94,6 @@ impl<'a> Parser<'a> {
     }
 
     fn read_enum<'e>(&mut self, e: &'e EnumDescriptor) -> ParseResult<EnumValueDescriptor> {
-        self.read_colon()?;
-
         // TODO: read integer?
         let ident = self.tokenizer.next_ident()?;
         let value = match e.get_value_by_name(&ident) {
@@ -93,14 +104,10 @@ impl<'a> Parser<'a> {
     }
 
     fn read_u64(&mut self) -> ParseResult<u64> {
-        self.read_colon()?;
-
         Ok(self.tokenizer.next_int_lit()?)
     }
 
     fn read_u32(&mut self) -> ParseResult<u32> {
-        self.read_colon()?;
-
         let int_lit = self.tokenizer.next_int_lit()?;
         let value_u32 = int_lit as u32;
         if value_u32 as u64 != int_lit {
@@ -110,8 +117,6 @@ impl<'a> Parser<'a> {
     }
 
     fn read_i64(&mut self) -> ParseResult<i64> {
-        self.read_colon()?;
-
         if self.tokenizer.next_symbol_if_eq('-')? {
             let int_lit = self.tokenizer.next_int_lit()?;
             Ok(int::neg(int_lit)?)
@@ -133,8 +138,6 @@ impl<'a> Parser<'a> {
     }
 
     fn read_f64(&mut self) -> ParseResult<f64> {
-        self.read_colon()?;
-
         let minus = self.tokenizer.next_symbol_if_eq('-')?;
 
         let value = if let Ok(value) = self.tokenizer.next_int_lit() {
@@ -151,8 +154,6 @@ impl<'a> Parser<'a> {
     }
 
     fn read_bool(&mut self) -> ParseResult<bool> {
-        self.read_colon()?;
-
         if self.tokenizer.next_ident_if_eq("true")? {
             Ok(true)
         } else if self.tokenizer.next_ident_if_eq("false")? {
@@ -163,8 +164,6 @@ impl<'a> Parser<'a> {
     }
 
     fn read_string(&mut self) -> ParseResult<String> {
-        self.read_colon()?;
-
         Ok(self
             .tokenizer
             .next_str_lit()
@@ -172,8 +171,6 @@ impl<'a> Parser<'a> {
     }
 
     fn read_bytes(&mut self) -> ParseResult<Vec<u8>> {
-        self.read_colon()?;
-
         Ok(self
             .tokenizer
             .next_str_lit()
@@ -220,6 +217,7 @@ impl<'a> Parser<'a> {
             let field_value = self.read_value_of_type(field_type)?;
 
             *field = Some(field_value);
+            self.next_field_separator()?;
         }
         self.tokenizer.next_symbol_expect_eq('}')?;
         let key = match key {
@@ -233,7 +231,56 @@ impl<'a> Parser<'a> {
         Ok((key, value))
     }
 
+    /// Read field value including preceding colon, which is optional for messages.
     fn read_value_of_type(&mut self, t: &RuntimeTypeBox) -> ParseResult<ReflectValueBox> {
+        match t {
+            RuntimeTypeBox::Message(..) => {
+                self.tokenizer.next_symbol_if_eq(':')?;
+            }
+            _ => self.read_colon()?,
+        }
+        self.read_value(t)
+    }
+
+    /// Read `:` and `[` of list of repeated field values if list follows.
+    fn next_list_start(&mut self) -> ParseResult<bool> {
+        let mut lookahead = self.clone();
+        lookahead.tokenizer.next_symbol_if_eq(':')?;
+        if lookahead.tokenizer.next_symbol_if_eq('[')? {
+            *self = lookahead;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Read comma-separated list elements after `[` and closing `]`.
+    fn read_list_elements(
+        &mut self,
+        mut read_element: impl FnMut(&mut Self) -> ParseResult<()>,
+    ) -> ParseResult<()> {
+        if self.tokenizer.next_symbol_if_eq(']')? {
+            return Ok(());
+        }
+        loop {
+            read_element(self)?;
+            if self.tokenizer.next_symbol_if_eq(']')? {
+                return Ok(());
+            }
+            self.tokenizer.next_symbol_expect_eq(',')?;
+        }
+    }
+
+    /// Fields may be optionally separated with `,` or `;`.
+    fn next_field_separator(&mut self) -> ParseResult<()> {
+        if !self.tokenizer.next_symbol_if_eq(',')? {
+            self.tokenizer.next_symbol_if_eq(';')?;
+        }
+        Ok(())
+    }
+
+    /// Read field value without preceding colon.
+    fn read_value(&mut self, t: &RuntimeTypeBox) -> ParseResult<ReflectValueBox> {
         Ok(match t {
             RuntimeTypeBox::Enum(d) => {
                 let value = self.read_enum(&d)?.value();
@@ -252,11 +299,67 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn read_any_type_url(&mut self) -> ParseResult<String> {
+        self.tokenizer.next_symbol_expect_eq('[')?;
+        let mut type_url = String::new();
+        while !self.tokenizer.next_symbol_if_eq(']')? {
+            match self.tokenizer.lookahead_if_symbol()? {
+                Some(c @ '.') | Some(c @ '/') => {
+                    self.tokenizer.advance()?;
+                    type_url.push(c);
+                }
+                _ => type_url.push_str(&self.next_field_name()?),
+            }
+        }
+        Ok(type_url)
+    }
+
+    fn find_any_type(&self, type_url: &str) -> Option<MessageDescriptor> {
+        Any::find_type_with(type_url, |type_name| {
+            let type_name = format!(".{}", type_name);
+            self.root
+                .file_descriptor()
+                .all_files()
+                .into_iter()
+                .find_map(|f| f.message_by_full_name(&type_name))
+        })
+    }
+
+    /// Expanded `Any`: `[type.googleapis.com/foo.Bar] { ... }`
+    fn merge_any_expanded(
+        &mut self,
+        message: &mut dyn MessageDyn,
+        descriptor: &MessageDescriptor,
+    ) -> ParseResult<()> {
+        let type_url = self.read_any_type_url()?;
+        let value_descriptor = match self.find_any_type(&type_url) {
+            Some(d) => d,
+            None => return Err(ParseErrorWithoutLoc::AnyTypeNotFound(type_url)),
+        };
+        self.tokenizer.next_symbol_if_eq(':')?;
+        let value = self.read_message(&value_descriptor)?;
+        let value = value
+            .write_to_bytes_dyn()
+            .map_err(|_| ParseErrorWithoutLoc::MessageNotInitialized)?;
+
+        let field = |name| descriptor.get_field_by_name(name).unwrap();
+        field("type_url").set_singular_field(message, ReflectValueBox::String(type_url));
+        field("value").set_singular_field(message, ReflectValueBox::Bytes(value));
+        self.next_field_separator()?;
+        Ok(())
+    }
+
     fn merge_field(
         &mut self,
         message: &mut dyn MessageDyn,
         descriptor: &MessageDescriptor,
     ) -> ParseResult<()> {
+        if descriptor.full_name() == "google.protobuf.Any"
+            && self.tokenizer.lookahead_is_symbol('[')?
+        {
+            return self.merge_any_expanded(message, descriptor);
+        }
+
         let field_name = self.next_field_name()?;
 
         let field = match descriptor.get_field_by_name(&field_name) {
@@ -273,14 +376,32 @@ impl<'a> Parser<'a> {
                 field.set_singular_field(message, value);
             }
             RuntimeFieldType::Repeated(t) => {
-                let value = self.read_value_of_type(&t)?;
-                field.mut_repeated(message).push(value);
+                if self.next_list_start()? {
+                    self.read_list_elements(|p| {
+                        let value = p.read_value(&t)?;
+                        field.mut_repeated(message).push(value);
+                        Ok(())
+                    })?;
+                } else {
+                    let value = self.read_value_of_type(&t)?;
+                    field.mut_repeated(message).push(value);
+                }
             }
             RuntimeFieldType::Map(k, v) => {
-                let (k, v) = self.read_map_entry(&k, &v)?;
-                field.mut_map(message).insert(k, v);
+                if self.next_list_start()? {
+                    self.read_list_elements(|p| {
+                        let (k, v) = p.read_map_entry(&k, &v)?;
+                        field.mut_map(message).insert(k, v);
+                        Ok(())
+                    })?;
+                } else {
+                    self.tokenizer.next_symbol_if_eq(':')?;
+                    let (k, v) = self.read_map_entry(&k, &v)?;
+                    field.mut_map(message).insert(k, v);
+                }
             }
         };
+        self.next_field_separator()?;
 
         Ok(())
     }
@@ -310,13 +431,37 @@ impl<'a> Parser<'a> {
 /// Parse text format message.
 ///
 /// This function does not check if message required fields are set.
+///
+/// Types of expanded `Any` messages (`[type.googleapis.com/foo.Bar] { ... }`)
+/// are searched in the file of the message and its dependencies.
 pub fn merge_from_str(message: &mut dyn MessageDyn, input: &str) -> ParseWithLocResult<()> {
     let mut parser = Parser {
         tokenizer: Tokenizer::new(input, ParserLanguage::TextFormat),
+        root: message.descriptor_dyn(),
     };
     parser.merge(message)
 }
 
+/// Parse text format message of given type.
+///
+/// This is useful for messages which types are known only at runtime,
+/// e. g. when descriptor is loaded from a file with
+/// [`FileDescriptor::new_dynamic`](crate::reflect::FileDescriptor::new_dynamic).
+pub fn parse_dyn(
+    descriptor: &MessageDescriptor,
+    input: &str,
+) -> ParseWithLocResult<Box<dyn MessageDyn>> {
+    let mut m = descriptor.new_instance();
+    merge_from_str(&mut *m, input)?;
+    if m.check_initialized_dyn().is_err() {
+        return Err(ParseError {
+            error: ParseErrorWithoutLoc::MessageNotInitialized,
+            loc: Loc::start(),
+        });
+    }
+    Ok(m)
+}
+
 /// Parse text format message.
 pub fn parse_from_str<M: Message>(input: &str) -> ParseWithLocResult<M> {
     let mut m = M::new();
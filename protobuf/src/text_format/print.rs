@@ -1,10 +1,19 @@
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::Write;
 
+use crate::message::Message;
 use crate::message_dyn::MessageDyn;
+use crate::reflect::has_explicit_presence;
+use crate::reflect::EnumAliasName;
+use crate::reflect::ExtensionRegistry;
 use crate::reflect::MessageRef;
 use crate::reflect::ReflectFieldRef;
 use crate::reflect::ReflectValueRef;
+use crate::text_format::lexer::float::PROTOBUF_INF;
+use crate::text_format::lexer::float::PROTOBUF_NAN;
+use crate::unknown::UnknownValueRef;
+use crate::well_known_types::Empty;
 
 #[doc(hidden)]
 pub fn quote_bytes_to(bytes: &[u8], buf: &mut String) {
@@ -56,6 +65,7 @@ fn do_indent(buf: &mut String, pretty: bool, indent: usize) {
 trait FieldName: fmt::Display {}
 impl<'a> FieldName for &'a str {}
 impl FieldName for u32 {}
+impl FieldName for String {}
 
 fn print_start_field<F: FieldName>(
     buf: &mut String,
@@ -80,29 +90,39 @@ fn print_end_field(buf: &mut String, pretty: bool) {
 
 fn print_field<F: FieldName>(
     buf: &mut String,
-    pretty: bool,
+    options: &PrintOptions,
+    depth: usize,
     indent: usize,
     first: &mut bool,
     field_name: F,
     value: ReflectValueRef,
 ) {
+    let pretty = options.pretty;
     print_start_field(buf, pretty, indent, first, field_name);
 
     match value {
         ReflectValueRef::Message(m) => {
-            buf.push_str(" {");
-            if pretty {
-                buf.push_str("\n");
+            if options
+                .max_depth
+                .is_some_and(|max_depth| depth >= max_depth)
+            {
+                buf.push_str(": ");
+                buf.push_str(MAX_DEPTH_PLACEHOLDER);
+            } else {
+                buf.push_str(" {");
+                if pretty {
+                    buf.push_str("\n");
+                }
+                print_to_internal(&m, buf, options, depth + 1, indent + 1);
+                do_indent(buf, pretty, indent);
+                buf.push_str("}");
             }
-            print_to_internal(&m, buf, pretty, indent + 1);
-            do_indent(buf, pretty, indent);
-            buf.push_str("}");
         }
         ReflectValueRef::Enum(d, v) => {
             buf.push_str(": ");
-            match d.get_value_by_number(v) {
+            match d.get_value_by_number_with_alias(v, options.enum_alias_name) {
                 Some(e) => buf.push_str(e.get_name()),
-                None => write!(buf, ": {}", v).unwrap(),
+                None => write!(buf, "{}", v).unwrap(),
             }
         }
         ReflectValueRef::String(s) => {
@@ -128,6 +148,14 @@ fn print_field<F: FieldName>(
         ReflectValueRef::Bool(v) => {
             write!(buf, ": {}", v).unwrap();
         }
+        ReflectValueRef::F32(v) if options.normalized => {
+            buf.push_str(": ");
+            print_normalized_float(v, buf);
+        }
+        ReflectValueRef::F64(v) if options.normalized => {
+            buf.push_str(": ");
+            print_normalized_float(v, buf);
+        }
         ReflectValueRef::F32(v) => {
             write!(buf, ": {}", v).unwrap();
         }
@@ -139,13 +167,61 @@ fn print_field<F: FieldName>(
     print_end_field(buf, pretty);
 }
 
-fn print_to_internal(m: &MessageRef, buf: &mut String, pretty: bool, indent: usize) {
+/// Print float which is parseable by text format parser
+/// and always contains either decimal point or exponent.
+fn print_normalized_float<F: fmt::Debug + Into<f64> + Copy>(v: F, buf: &mut String) {
+    let f: f64 = v.into();
+    if f.is_nan() {
+        buf.push_str(PROTOBUF_NAN);
+    } else if f.is_infinite() {
+        if f < 0.0 {
+            buf.push('-');
+        }
+        buf.push_str(PROTOBUF_INF);
+    } else {
+        write!(buf, "{:?}", v).unwrap();
+    }
+}
+
+/// Order of map keys of the same type.
+pub(crate) fn compare_map_keys(a: &ReflectValueRef, b: &ReflectValueRef) -> Ordering {
+    match (a, b) {
+        (ReflectValueRef::U32(a), ReflectValueRef::U32(b)) => a.cmp(b),
+        (ReflectValueRef::U64(a), ReflectValueRef::U64(b)) => a.cmp(b),
+        (ReflectValueRef::I32(a), ReflectValueRef::I32(b)) => a.cmp(b),
+        (ReflectValueRef::I64(a), ReflectValueRef::I64(b)) => a.cmp(b),
+        (ReflectValueRef::Bool(a), ReflectValueRef::Bool(b)) => a.cmp(b),
+        (ReflectValueRef::String(a), ReflectValueRef::String(b)) => a.cmp(b),
+        _ => Ordering::Equal,
+    }
+}
+
+/// Parse bytes as a message of unknown fields, like `protoc --decode_raw` does.
+fn decode_unknown_message(bytes: &[u8]) -> Option<Empty> {
+    if bytes.is_empty() {
+        return None;
+    }
+    Empty::parse_from_bytes(bytes).ok()
+}
+
+fn print_to_internal(
+    m: &MessageRef,
+    buf: &mut String,
+    options: &PrintOptions,
+    depth: usize,
+    indent: usize,
+) {
+    let pretty = options.pretty;
     let d = m.descriptor_dyn();
     let mut first = true;
     for f in d.fields() {
         match f.get_reflect(&**m) {
             ReflectFieldRef::Map(map) => {
-                for (k, v) in &map {
+                let mut entries: Vec<_> = (&map).into_iter().collect();
+                if options.normalized {
+                    entries.sort_by(|(a, _), (b, _)| compare_map_keys(a, b));
+                }
+                for (k, v) in entries {
                     print_start_field(buf, pretty, indent, &mut first, f.get_name());
                     buf.push_str(" {");
                     if pretty {
@@ -154,8 +230,16 @@ fn print_to_internal(m: &MessageRef, buf: &mut String, pretty: bool, indent: usi
 
                     let mut entry_first = true;
 
-                    print_field(buf, pretty, indent + 1, &mut entry_first, "key", k);
-                    print_field(buf, pretty, indent + 1, &mut entry_first, "value", v);
+                    print_field(buf, options, depth, indent + 1, &mut entry_first, "key", k);
+                    print_field(
+                        buf,
+                        options,
+                        depth,
+                        indent + 1,
+                        &mut entry_first,
+                        "value",
+                        v,
+                    );
                     do_indent(buf, pretty, indent);
                     buf.push_str("}");
                     print_end_field(buf, pretty);
@@ -164,55 +248,184 @@ fn print_to_internal(m: &MessageRef, buf: &mut String, pretty: bool, indent: usi
             ReflectFieldRef::Repeated(repeated) => {
                 // TODO: do not print zeros for v3
                 for v in repeated {
-                    print_field(buf, pretty, indent, &mut first, f.get_name(), v);
+                    print_field(buf, options, depth, indent, &mut first, f.get_name(), v);
                 }
             }
             ReflectFieldRef::Optional(optional) => {
                 if let Some(v) = optional {
-                    print_field(buf, pretty, indent, &mut first, f.get_name(), v);
+                    // Dynamic messages may hold default values of proto3 fields
+                    if !has_explicit_presence(&f) && !v.is_non_zero() {
+                        continue;
+                    }
+                    print_field(buf, options, depth, indent, &mut first, f.get_name(), v);
                 }
             }
         }
     }
 
+    let empty_registry = ExtensionRegistry::new();
+    let registry = options
+        .extension_registry
+        .as_ref()
+        .unwrap_or(&empty_registry);
+    let extensions = m.extensions(registry);
+    for (e, values) in &extensions {
+        for v in values {
+            let name = format!("[{}]", e.full_name());
+            print_field(
+                buf,
+                options,
+                depth,
+                indent,
+                &mut first,
+                name,
+                v.as_value_ref(),
+            );
+        }
+    }
+
     let unknown_fields = m.get_unknown_fields_dyn();
-    let mut numbers: Vec<u32> = m.get_unknown_fields_dyn().iter().map(|(n, _)| n).collect();
+    let mut numbers: Vec<u32> = m
+        .get_unknown_fields_dyn()
+        .iter()
+        .map(|(n, _)| n)
+        .filter(|&n| extensions.iter().all(|(e, _)| e.number() != n))
+        .collect();
     // Sort for stable output
     numbers.sort();
     for &n in &numbers {
         for v in unknown_fields.get(n).unwrap() {
-            // TODO: try decode nested message for length-delimited
-            print_field(buf, pretty, indent, &mut first, n, v.to_reflect_value_ref());
+            let nested = match v {
+                UnknownValueRef::LengthDelimited(bytes) if options.decode_unknown_messages => {
+                    decode_unknown_message(bytes)
+                }
+                _ => None,
+            };
+            if let Some(nested) = nested {
+                let nested = MessageRef::from(&nested as &dyn MessageDyn);
+                print_field(
+                    buf,
+                    options,
+                    depth,
+                    indent,
+                    &mut first,
+                    n,
+                    ReflectValueRef::Message(nested),
+                );
+                continue;
+            }
+            print_field(
+                buf,
+                options,
+                depth,
+                indent,
+                &mut first,
+                n,
+                v.to_reflect_value_ref(),
+            );
         }
     }
 }
 
+/// Printed instead of messages nested deeper than [`PrintOptions::max_depth`].
+pub const MAX_DEPTH_PLACEHOLDER: &str = "<max depth exceeded>";
+
+/// Options for printing text format.
+///
+/// # Examples
+///
+/// ```
+/// use protobuf::text_format;
+/// let print_options = text_format::PrintOptions {
+///     max_depth: Some(10),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct PrintOptions {
+    /// Print each field on a separate line with indentation.
+    pub pretty: bool,
+    /// Maximum number of levels of messages nested in the printed message.
+    ///
+    /// Messages nested deeper are printed as [`MAX_DEPTH_PLACEHOLDER`]
+    /// instead of being traversed, e. g. with `Some(0)` all message fields
+    /// of the printed message are replaced. Map entries are not separate levels.
+    /// `None` means unlimited.
+    pub max_depth: Option<usize>,
+    /// Print the same output for equal messages.
+    ///
+    /// Map entries are sorted by key, and floating point numbers
+    /// are printed in a fixed format (e. g. `1.0`, `1e20`, `nan`).
+    /// Unknown fields are always printed sorted by field number.
+    pub normalized: bool,
+    /// Print length-delimited unknown fields which are valid serialized messages
+    /// as nested messages instead of bytes, like `protoc --decode_raw` does.
+    ///
+    /// This is useful to debug messages parsed with incomplete descriptors,
+    /// but strings may be accidentally printed as messages.
+    pub decode_unknown_messages: bool,
+    /// Name printed for enum numbers with several names (`allow_alias` option).
+    pub enum_alias_name: EnumAliasName,
+    /// Extensions printed as `[full.name]` fields instead of unknown fields.
+    ///
+    /// Extensions decoded when parsing dynamic messages are printed
+    /// even without the registry.
+    pub extension_registry: Option<ExtensionRegistry>,
+    /// Prevent initializing `PrintOptions` enumerating all field.
+    pub _future_options: (),
+}
+
 /// Text-format
 pub fn print_to(m: &dyn MessageDyn, buf: &mut String) {
-    print_to_internal(&MessageRef::from(m), buf, false, 0)
+    print_to_with_options(m, buf, &PrintOptions::default())
 }
 
-fn print_to_string_internal(m: &dyn MessageDyn, pretty: bool) -> String {
+/// Text-format with options.
+pub fn print_to_with_options(m: &dyn MessageDyn, buf: &mut String, options: &PrintOptions) {
+    print_to_internal(&MessageRef::from(m), buf, options, 0, 0)
+}
+
+/// Text-format with options.
+pub fn print_to_string_with_options(m: &dyn MessageDyn, options: &PrintOptions) -> String {
     let mut r = String::new();
-    print_to_internal(&MessageRef::from(m), &mut r, pretty, 0);
-    r.to_string()
+    print_to_with_options(m, &mut r, options);
+    r
 }
 
 /// Text-format
 pub fn print_to_string(m: &dyn MessageDyn) -> String {
-    print_to_string_internal(m, false)
+    print_to_string_with_options(m, &PrintOptions::default())
+}
+
+/// Text-format in normalized form, suitable for golden (snapshot) tests.
+///
+/// Output is pretty-printed, and equal messages are printed identically
+/// regardless of map iteration order. See [`PrintOptions::normalized`].
+pub fn print_normalized(m: &dyn MessageDyn) -> String {
+    let options = PrintOptions {
+        pretty: true,
+        normalized: true,
+        ..Default::default()
+    };
+    print_to_string_with_options(m, &options)
 }
 
 /// Text-format to `fmt::Formatter`.
 pub fn fmt(m: &dyn MessageDyn, f: &mut fmt::Formatter) -> fmt::Result {
-    let pretty = f.alternate();
-    f.write_str(&print_to_string_internal(m, pretty))
+    let options = PrintOptions {
+        pretty: f.alternate(),
+        ..Default::default()
+    };
+    f.write_str(&print_to_string_with_options(m, &options))
 }
 
 #[cfg(test)]
 mod test {
 
     use crate::text_format::lexer::StrLit;
+    use crate::well_known_types::value;
+    use crate::well_known_types::Struct;
+    use crate::well_known_types::Value;
 
     fn escape(data: &[u8]) -> String {
         let mut s = String::with_capacity(data.len() * 4);
@@ -257,4 +470,55 @@ mod test {
         assert_eq!(b"aaa\"bbb", &unescape_string("aaa\\\"bbb")[..]);
         assert_eq!(b"aaa\'bbb", &unescape_string("aaa\\\'bbb")[..]);
     }
+
+    fn number_value(n: f64) -> Value {
+        let mut value = Value::new();
+        value.kind = Some(value::Kind::number_value(n));
+        value
+    }
+
+    #[test]
+    fn test_print_normalized() {
+        let mut s = Struct::new();
+        for (i, k) in ["d", "b", "c", "a"].iter().enumerate() {
+            s.fields.insert(k.to_string(), number_value(i as f64));
+        }
+        s.fields
+            .insert("e".to_owned(), number_value(f64::NEG_INFINITY));
+        assert_eq!(
+            "\
+fields {
+  key: \"a\"
+  value {
+    number_value: 3.0
+  }
+}
+fields {
+  key: \"b\"
+  value {
+    number_value: 1.0
+  }
+}
+fields {
+  key: \"c\"
+  value {
+    number_value: 2.0
+  }
+}
+fields {
+  key: \"d\"
+  value {
+    number_value: 0.0
+  }
+}
+fields {
+  key: \"e\"
+  value {
+    number_value: -inf
+  }
+}
+",
+            super::print_normalized(&s)
+        );
+    }
 }
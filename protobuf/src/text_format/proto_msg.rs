@@ -0,0 +1,44 @@
+/// Build a message from text format literal, for test fixtures.
+///
+/// Message body is written in [text format](crate::text_format),
+/// including nested messages, `[...]` lists of repeated field values
+/// and map entries with `key` and `value`. Fields may be separated with commas.
+///
+/// Generated message is built with `proto_msg!(Type { ... })`,
+/// dynamic message of given [`MessageDescriptor`](crate::reflect::MessageDescriptor)
+/// is built with `proto_msg!(dyn descriptor => { ... })`.
+///
+/// Body is converted to string with `stringify!`, so string literals
+/// must be regular `"..."` literals (byte strings are written as strings with escapes).
+///
+/// # Panics
+///
+/// If body cannot be parsed or message is not initialized.
+///
+/// ```
+/// # use protobuf::proto_msg;
+/// # use protobuf::descriptor::FileDescriptorProto;
+/// # use protobuf::Message;
+/// let file = proto_msg!(FileDescriptorProto {
+///     name: "a.proto",
+///     dependency: ["b.proto", "c.proto"],
+///     message_type { name: "Foo" field: [{ name: "bar", number: 1, type: TYPE_INT32 }] },
+/// });
+/// assert_eq!("Foo", file.message_type[0].get_name());
+///
+/// let dynamic = proto_msg!(dyn FileDescriptorProto::descriptor_static() => {
+///     name: "a.proto",
+/// });
+/// assert_eq!(file.get_name(), dynamic.downcast_ref::<FileDescriptorProto>().unwrap().get_name());
+/// ```
+#[macro_export]
+macro_rules! proto_msg {
+    (dyn $descriptor:expr => { $($body:tt)* }) => {
+        $crate::text_format::parse_dyn(&$descriptor, stringify!($($body)*))
+            .unwrap_or_else(|e| panic!("proto_msg!: {}", e))
+    };
+    ($message:path { $($body:tt)* }) => {
+        $crate::text_format::parse_from_str::<$message>(stringify!($($body)*))
+            .unwrap_or_else(|e| panic!("proto_msg!({}): {}", stringify!($message), e))
+    };
+}
@@ -0,0 +1,261 @@
+//! Rewrite field numbers of serialized messages.
+//!
+//! Transcoding operates on the wire format directly, without parsing
+//! messages into generated or dynamic messages. It is useful to migrate data
+//! written with a schema where fields were mistakenly renumbered.
+//!
+//! ```
+//! # use protobuf::reflect::MessageDescriptor;
+//! # use protobuf::transcode::FieldNumberRemap;
+//! # fn foo(old: &MessageDescriptor, new: &MessageDescriptor, bytes: &[u8]) -> protobuf::ProtobufResult<()> {
+//! let remap = FieldNumberRemap::by_name(old, new);
+//! let migrated = remap.transcode_bytes(bytes)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+
+use crate::error::ProtobufError;
+use crate::error::ProtobufResult;
+use crate::error::WireError;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::wire_format;
+use crate::CodedInputStream;
+use crate::CodedOutputStream;
+use crate::SerializeScratch;
+
+#[derive(Debug, Clone)]
+struct FieldRemap {
+    number: u32,
+    /// Key of remap of nested message.
+    message: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct MessageRemap {
+    fields: HashMap<u32, FieldRemap>,
+}
+
+/// Mapping of field numbers of a message type and nested message types.
+///
+/// Fields without mapping keep their numbers.
+#[derive(Debug, Clone)]
+pub struct FieldNumberRemap {
+    root: String,
+    /// By source message full name.
+    messages: HashMap<String, MessageRemap>,
+}
+
+fn message_type(t: &RuntimeTypeBox) -> Option<&MessageDescriptor> {
+    match t {
+        RuntimeTypeBox::Message(m) => Some(m),
+        _ => None,
+    }
+}
+
+impl FieldNumberRemap {
+    /// Map fields of `from` message to fields of `to` message with the same names.
+    ///
+    /// Message-typed fields (including repeated fields and map values)
+    /// are mapped recursively.
+    pub fn by_name(from: &MessageDescriptor, to: &MessageDescriptor) -> FieldNumberRemap {
+        let mut remap = FieldNumberRemap {
+            root: from.full_name().to_owned(),
+            messages: HashMap::new(),
+        };
+        remap.add_message(from, to);
+        remap
+    }
+
+    fn add_message(&mut self, from: &MessageDescriptor, to: &MessageDescriptor) {
+        if self.messages.contains_key(from.full_name()) {
+            return;
+        }
+        self.messages
+            .insert(from.full_name().to_owned(), MessageRemap::default());
+
+        let mut fields = HashMap::new();
+        for field in from.fields() {
+            let to_field = match to.get_field_by_name(field.get_name()) {
+                Some(to_field) => to_field,
+                None => continue,
+            };
+            let message = match (field.runtime_field_type(), to_field.runtime_field_type()) {
+                (RuntimeFieldType::Singular(a), RuntimeFieldType::Singular(b))
+                | (RuntimeFieldType::Repeated(a), RuntimeFieldType::Repeated(b)) => {
+                    match (message_type(&a), message_type(&b)) {
+                        (Some(a), Some(b)) => {
+                            self.add_message(a, b);
+                            Some(a.full_name().to_owned())
+                        }
+                        _ => None,
+                    }
+                }
+                (RuntimeFieldType::Map(_, a), RuntimeFieldType::Map(_, b)) => {
+                    match (message_type(&a), message_type(&b)) {
+                        (Some(a), Some(b)) => {
+                            self.add_message(a, b);
+                            Some(self.add_map_entry(field.get_proto().get_type_name(), a))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            fields.insert(
+                field.get_proto().get_number() as u32,
+                FieldRemap {
+                    number: to_field.get_proto().get_number() as u32,
+                    message,
+                },
+            );
+        }
+        self.messages.get_mut(from.full_name()).unwrap().fields = fields;
+    }
+
+    /// Map entry keeps field numbers, and remaps the value message.
+    fn add_map_entry(&mut self, entry_type_name: &str, value: &MessageDescriptor) -> String {
+        let key = entry_type_name.trim_start_matches('.').to_owned();
+        let mut entry = MessageRemap::default();
+        entry.fields.insert(
+            1,
+            FieldRemap {
+                number: 1,
+                message: None,
+            },
+        );
+        entry.fields.insert(
+            2,
+            FieldRemap {
+                number: 2,
+                message: Some(value.full_name().to_owned()),
+            },
+        );
+        self.messages.insert(key.clone(), entry);
+        key
+    }
+
+    /// Override mapping of a field of given source message type.
+    ///
+    /// `message` is a full name of the message type without leading dot.
+    /// Nested message mapping of the field, if any, is kept.
+    pub fn set_field_number(&mut self, message: &str, from: u32, to: u32) {
+        let fields = &mut self.messages.entry(message.to_owned()).or_default().fields;
+        fields
+            .entry(from)
+            .or_insert(FieldRemap {
+                number: to,
+                message: None,
+            })
+            .number = to;
+    }
+
+    /// Number of the field in target message, or `None` if the field is not mapped.
+    pub fn field_number(&self, message: &str, from: u32) -> Option<u32> {
+        Some(self.messages.get(message)?.fields.get(&from)?.number)
+    }
+
+    /// Transcode a message read from input stream until EOF.
+    pub fn transcode(
+        &self,
+        is: &mut CodedInputStream,
+        os: &mut CodedOutputStream,
+    ) -> ProtobufResult<()> {
+        SerializeScratch::with_thread_local(|scratch| self.transcode_with_scratch(is, os, scratch))
+    }
+
+    /// Transcode a message read from input stream until EOF,
+    /// encoding nested messages in buffers from the scratch.
+    ///
+    /// See [`SerializeScratch`].
+    pub fn transcode_with_scratch(
+        &self,
+        is: &mut CodedInputStream,
+        os: &mut CodedOutputStream,
+        scratch: &mut SerializeScratch,
+    ) -> ProtobufResult<()> {
+        self.transcode_message(Some(&self.root), is, os, None, scratch)
+    }
+
+    /// Transcode serialized message.
+    pub fn transcode_bytes(&self, bytes: &[u8]) -> ProtobufResult<Vec<u8>> {
+        let mut is = CodedInputStream::from_bytes(bytes);
+        let mut r = Vec::with_capacity(bytes.len());
+        {
+            let mut os = CodedOutputStream::vec(&mut r);
+            self.transcode(&mut is, &mut os)?;
+            os.flush()?;
+        }
+        Ok(r)
+    }
+
+    /// Transcode message fields until EOF, or until end of group
+    /// with given field number.
+    fn transcode_message(
+        &self,
+        message: Option<&str>,
+        is: &mut CodedInputStream,
+        os: &mut CodedOutputStream,
+        group: Option<u32>,
+        scratch: &mut SerializeScratch,
+    ) -> ProtobufResult<()> {
+        let remap = message.and_then(|m| self.messages.get(m));
+        loop {
+            if group.is_none() && is.eof()? {
+                return Ok(());
+            }
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            if wire_type == wire_format::WireTypeEndGroup {
+                if group == Some(field_number) {
+                    return Ok(());
+                }
+                return Err(ProtobufError::WireError(WireError::UnexpectedWireType(
+                    wire_type,
+                )));
+            }
+
+            let field = remap.and_then(|r| r.fields.get(&field_number));
+            let number = field.map_or(field_number, |f| f.number);
+            let nested = field.and_then(|f| f.message.as_deref());
+
+            match wire_type {
+                wire_format::WireTypeStartGroup => {
+                    is.incr_recursion()?;
+                    os.write_tag(number, wire_format::WireTypeStartGroup)?;
+                    let r = self.transcode_message(nested, is, os, Some(field_number), scratch);
+                    is.decr_recursion();
+                    r?;
+                    os.write_tag(number, wire_format::WireTypeEndGroup)?;
+                }
+                wire_format::WireTypeLengthDelimited if nested.is_some() => {
+                    // Nested message size may change, so it is transcoded into a buffer
+                    // Length is not trusted, so the buffer is not preallocated
+                    let len = is.read_raw_varint64()?;
+                    let mut transcoded = scratch.pop_nested_buffer();
+                    let r = {
+                        let mut nested_os = CodedOutputStream::vec(&mut transcoded);
+                        let old_limit = is.push_limit(len)?;
+                        let r = is.incr_recursion().and_then(|()| {
+                            let r =
+                                self.transcode_message(nested, is, &mut nested_os, None, scratch);
+                            is.decr_recursion();
+                            r
+                        });
+                        is.pop_limit(old_limit);
+                        r.and_then(|()| nested_os.flush())
+                    };
+                    let r = r.and_then(|()| os.write_bytes(number, &transcoded));
+                    scratch.push_nested_buffer(transcoded);
+                    r?;
+                }
+                wire_type => {
+                    let value = is.read_unknown(wire_type)?;
+                    os.write_unknown(number, value.get_ref())?;
+                }
+            }
+        }
+    }
+}
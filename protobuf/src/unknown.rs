@@ -1,5 +1,6 @@
 use std::collections::hash_map;
 use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::default::Default;
 use std::hash::BuildHasherDefault;
@@ -225,6 +226,12 @@ pub struct UnknownFields {
     // and Google Protobuf Java uses tree map to store unknown fields
     // (which is more expensive than hashmap).
     fields: Option<Box<HashMap<u32, UnknownValues, BuildHasherDefault<DefaultHasher>>>>,
+    /// Encoded contents of groups (fields between group start and end tags) by field number.
+    ///
+    /// Groups are skipped when parsing, they are only stored here
+    /// by [`set_extension_dyn`](crate::MessageDyn::set_extension_dyn)
+    /// for group-typed extensions of generated messages.
+    groups: BTreeMap<u32, Vec<Vec<u8>>>,
 }
 
 /// Very simple hash implementation of `Hash` for `UnknownFields`.
@@ -244,13 +251,19 @@ impl Hash for UnknownFields {
                 Hash::hash(&hash, state);
             }
         }
+        if !self.groups.is_empty() {
+            Hash::hash(&self.groups, state);
+        }
     }
 }
 
 impl UnknownFields {
     /// Empty unknown fields
     pub const fn new() -> UnknownFields {
-        UnknownFields { fields: None }
+        UnknownFields {
+            fields: None,
+            groups: BTreeMap::new(),
+        }
     }
 
     fn init_map(&mut self) {
@@ -295,11 +308,32 @@ impl UnknownFields {
         self.find_field(&number).add_value(value);
     }
 
+    /// Add encoded group contents.
+    pub(crate) fn add_group(&mut self, number: u32, contents: Vec<u8>) {
+        self.groups.entry(number).or_default().push(contents);
+    }
+
+    /// Encoded contents of groups with given field number.
+    pub(crate) fn groups(&self, number: u32) -> &[Vec<u8>] {
+        match self.groups.get(&number) {
+            Some(groups) => groups,
+            None => &[],
+        }
+    }
+
+    /// Encoded contents of all groups by field number.
+    pub(crate) fn iter_groups(&self) -> impl Iterator<Item = (u32, &[Vec<u8>])> {
+        self.groups
+            .iter()
+            .map(|(&number, groups)| (number, &groups[..]))
+    }
+
     /// Remove unknown field by number
     pub fn remove(&mut self, field_number: u32) {
         if let Some(fields) = &mut self.fields {
             fields.remove(&field_number);
         }
+        self.groups.remove(&field_number);
     }
 
     /// Iterate over all unknowns
@@ -334,6 +368,7 @@ impl Clear for UnknownFields {
         if let Some(ref mut fields) = self.fields {
             fields.clear();
         }
+        self.groups.clear();
     }
 }
 
@@ -74,6 +74,12 @@ impl Any {
         ::std::default::Default::default()
     }
 
+    /// Field number of `type_url` field.
+    pub const FIELD_TYPE_URL_NUMBER: u32 = 1;
+
+    /// Field number of `value` field.
+    pub const FIELD_VALUE_NUMBER: u32 = 2;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -126,14 +132,15 @@ impl crate::Message for Any {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if !self.type_url.is_empty() {
-            my_size += crate::rt::string_size(1, &self.type_url);
+            my_size += crate::rt::string_size(1, &self.type_url) as u64;
         }
         if !self.value.is_empty() {
-            my_size += crate::rt::bytes_size(2, &self.value);
+            my_size += crate::rt::bytes_size(2, &self.value) as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -198,6 +205,18 @@ impl crate::reflect::ProtobufValue for Any {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `Any`
+pub mod any {
+
+    /// Field numbers of message `Any`
+    pub mod field_numbers {
+        /// Field number of `type_url` field.
+        pub const TYPE_URL: u32 = 1;
+        /// Field number of `value` field.
+        pub const VALUE: u32 = 2;
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\x19google/protobuf/any.proto\x12\x0fgoogle.protobuf\"6\n\x03Any\x12\
     \x19\n\x08type_url\x18\x01\x20\x01(\tR\x07typeUrl\x12\x14\n\x05value\x18\
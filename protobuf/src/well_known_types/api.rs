@@ -88,6 +88,27 @@ impl Api {
         ::std::default::Default::default()
     }
 
+    /// Field number of `name` field.
+    pub const FIELD_NAME_NUMBER: u32 = 1;
+
+    /// Field number of `methods` field.
+    pub const FIELD_METHODS_NUMBER: u32 = 2;
+
+    /// Field number of `options` field.
+    pub const FIELD_OPTIONS_NUMBER: u32 = 3;
+
+    /// Field number of `version` field.
+    pub const FIELD_VERSION_NUMBER: u32 = 4;
+
+    /// Field number of `source_context` field.
+    pub const FIELD_SOURCE_CONTEXT_NUMBER: u32 = 5;
+
+    /// Field number of `mixins` field.
+    pub const FIELD_MIXINS_NUMBER: u32 = 6;
+
+    /// Field number of `syntax` field.
+    pub const FIELD_SYNTAX_NUMBER: u32 = 7;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -203,33 +224,34 @@ impl crate::Message for Api {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if !self.name.is_empty() {
-            my_size += crate::rt::string_size(1, &self.name);
+            my_size += crate::rt::string_size(1, &self.name) as u64;
         }
         for value in &self.methods {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         for value in &self.options {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         if !self.version.is_empty() {
-            my_size += crate::rt::string_size(4, &self.version);
+            my_size += crate::rt::string_size(4, &self.version) as u64;
         }
         if let Some(v) = self.source_context.as_ref() {
             let len = v.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         }
         for value in &self.mixins {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         if self.syntax != crate::ProtobufEnumOrUnknown::new(crate::well_known_types::Syntax::SYNTAX_PROTO2) {
-            my_size += crate::rt::enum_or_unknown_size(7, self.syntax);
+            my_size += crate::rt::enum_or_unknown_size(7, self.syntax) as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -319,6 +341,28 @@ impl crate::reflect::ProtobufValue for Api {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `Api`
+pub mod api {
+
+    /// Field numbers of message `Api`
+    pub mod field_numbers {
+        /// Field number of `name` field.
+        pub const NAME: u32 = 1;
+        /// Field number of `methods` field.
+        pub const METHODS: u32 = 2;
+        /// Field number of `options` field.
+        pub const OPTIONS: u32 = 3;
+        /// Field number of `version` field.
+        pub const VERSION: u32 = 4;
+        /// Field number of `source_context` field.
+        pub const SOURCE_CONTEXT: u32 = 5;
+        /// Field number of `mixins` field.
+        pub const MIXINS: u32 = 6;
+        /// Field number of `syntax` field.
+        pub const SYNTAX: u32 = 7;
+    }
+}
+
 ///  Method represents a method of an API interface.
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
@@ -356,6 +400,27 @@ impl Method {
         ::std::default::Default::default()
     }
 
+    /// Field number of `name` field.
+    pub const FIELD_NAME_NUMBER: u32 = 1;
+
+    /// Field number of `request_type_url` field.
+    pub const FIELD_REQUEST_TYPE_URL_NUMBER: u32 = 2;
+
+    /// Field number of `request_streaming` field.
+    pub const FIELD_REQUEST_STREAMING_NUMBER: u32 = 3;
+
+    /// Field number of `response_type_url` field.
+    pub const FIELD_RESPONSE_TYPE_URL_NUMBER: u32 = 4;
+
+    /// Field number of `response_streaming` field.
+    pub const FIELD_RESPONSE_STREAMING_NUMBER: u32 = 5;
+
+    /// Field number of `options` field.
+    pub const FIELD_OPTIONS_NUMBER: u32 = 6;
+
+    /// Field number of `syntax` field.
+    pub const FIELD_SYNTAX_NUMBER: u32 = 7;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -465,30 +530,31 @@ impl crate::Message for Method {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if !self.name.is_empty() {
-            my_size += crate::rt::string_size(1, &self.name);
+            my_size += crate::rt::string_size(1, &self.name) as u64;
         }
         if !self.request_type_url.is_empty() {
-            my_size += crate::rt::string_size(2, &self.request_type_url);
+            my_size += crate::rt::string_size(2, &self.request_type_url) as u64;
         }
         if self.request_streaming != false {
             my_size += 2;
         }
         if !self.response_type_url.is_empty() {
-            my_size += crate::rt::string_size(4, &self.response_type_url);
+            my_size += crate::rt::string_size(4, &self.response_type_url) as u64;
         }
         if self.response_streaming != false {
             my_size += 2;
         }
         for value in &self.options {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         if self.syntax != crate::ProtobufEnumOrUnknown::new(crate::well_known_types::Syntax::SYNTAX_PROTO2) {
-            my_size += crate::rt::enum_or_unknown_size(7, self.syntax);
+            my_size += crate::rt::enum_or_unknown_size(7, self.syntax) as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -578,6 +644,28 @@ impl crate::reflect::ProtobufValue for Method {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `Method`
+pub mod method {
+
+    /// Field numbers of message `Method`
+    pub mod field_numbers {
+        /// Field number of `name` field.
+        pub const NAME: u32 = 1;
+        /// Field number of `request_type_url` field.
+        pub const REQUEST_TYPE_URL: u32 = 2;
+        /// Field number of `request_streaming` field.
+        pub const REQUEST_STREAMING: u32 = 3;
+        /// Field number of `response_type_url` field.
+        pub const RESPONSE_TYPE_URL: u32 = 4;
+        /// Field number of `response_streaming` field.
+        pub const RESPONSE_STREAMING: u32 = 5;
+        /// Field number of `options` field.
+        pub const OPTIONS: u32 = 6;
+        /// Field number of `syntax` field.
+        pub const SYNTAX: u32 = 7;
+    }
+}
+
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct Mixin {
@@ -605,6 +693,12 @@ impl Mixin {
         ::std::default::Default::default()
     }
 
+    /// Field number of `name` field.
+    pub const FIELD_NAME_NUMBER: u32 = 1;
+
+    /// Field number of `root` field.
+    pub const FIELD_ROOT_NUMBER: u32 = 2;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -657,14 +751,15 @@ impl crate::Message for Mixin {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if !self.name.is_empty() {
-            my_size += crate::rt::string_size(1, &self.name);
+            my_size += crate::rt::string_size(1, &self.name) as u64;
         }
         if !self.root.is_empty() {
-            my_size += crate::rt::string_size(2, &self.root);
+            my_size += crate::rt::string_size(2, &self.root) as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -729,6 +824,18 @@ impl crate::reflect::ProtobufValue for Mixin {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `Mixin`
+pub mod mixin {
+
+    /// Field numbers of message `Mixin`
+    pub mod field_numbers {
+        /// Field number of `name` field.
+        pub const NAME: u32 = 1;
+        /// Field number of `root` field.
+        pub const ROOT: u32 = 2;
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\x19google/protobuf/api.proto\x12\x0fgoogle.protobuf\x1a$google/protob\
     uf/source_context.proto\x1a\x1agoogle/protobuf/type.proto\"\xc1\x02\n\
@@ -54,6 +54,12 @@ impl Duration {
         ::std::default::Default::default()
     }
 
+    /// Field number of `seconds` field.
+    pub const FIELD_SECONDS_NUMBER: u32 = 1;
+
+    /// Field number of `nanos` field.
+    pub const FIELD_NANOS_NUMBER: u32 = 2;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -106,14 +112,15 @@ impl crate::Message for Duration {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if self.seconds != 0 {
-            my_size += crate::rt::value_size(1, self.seconds, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(1, self.seconds, crate::wire_format::WireTypeVarint) as u64;
         }
         if self.nanos != 0 {
-            my_size += crate::rt::value_size(2, self.nanos, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(2, self.nanos, crate::wire_format::WireTypeVarint) as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -178,6 +185,18 @@ impl crate::reflect::ProtobufValue for Duration {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `Duration`
+pub mod duration {
+
+    /// Field numbers of message `Duration`
+    pub mod field_numbers {
+        /// Field number of `seconds` field.
+        pub const SECONDS: u32 = 1;
+        /// Field number of `nanos` field.
+        pub const NANOS: u32 = 2;
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\x1egoogle/protobuf/duration.proto\x12\x0fgoogle.protobuf\":\n\x08Dura\
     tion\x12\x18\n\x07seconds\x18\x01\x20\x01(\x03R\x07seconds\x12\x14\n\x05\
@@ -72,8 +72,9 @@ impl crate::Message for Empty {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        let mut my_size: u64 = 0;
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -45,6 +45,9 @@ impl FieldMask {
         ::std::default::Default::default()
     }
 
+    /// Field number of `paths` field.
+    pub const FIELD_PATHS_NUMBER: u32 = 1;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
@@ -83,11 +86,12 @@ impl crate::Message for FieldMask {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         for value in &self.paths {
-            my_size += crate::rt::string_size(1, &value);
+            my_size += crate::rt::string_size(1, &value) as u64;
         };
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -147,6 +151,16 @@ impl crate::reflect::ProtobufValue for FieldMask {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `FieldMask`
+pub mod field_mask {
+
+    /// Field numbers of message `FieldMask`
+    pub mod field_numbers {
+        /// Field number of `paths` field.
+        pub const PATHS: u32 = 1;
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\x20google/protobuf/field_mask.proto\x12\x0fgoogle.protobuf\"!\n\tFiel\
     dMask\x12\x14\n\x05paths\x18\x01\x20\x03(\tR\x05pathsB\x85\x01\n\x13com.\
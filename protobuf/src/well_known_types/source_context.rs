@@ -48,6 +48,9 @@ impl SourceContext {
         ::std::default::Default::default()
     }
 
+    /// Field number of `file_name` field.
+    pub const FIELD_FILE_NAME_NUMBER: u32 = 1;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -89,11 +92,12 @@ impl crate::Message for SourceContext {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if !self.file_name.is_empty() {
-            my_size += crate::rt::string_size(1, &self.file_name);
+            my_size += crate::rt::string_size(1, &self.file_name) as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -153,6 +157,16 @@ impl crate::reflect::ProtobufValue for SourceContext {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `SourceContext`
+pub mod source_context {
+
+    /// Field numbers of message `SourceContext`
+    pub mod field_numbers {
+        /// Field number of `file_name` field.
+        pub const FILE_NAME: u32 = 1;
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n$google/protobuf/source_context.proto\x12\x0fgoogle.protobuf\",\n\rSou\
     rceContext\x12\x1b\n\tfile_name\x18\x01\x20\x01(\tR\x08fileNameB\x8a\x01\
@@ -54,6 +54,9 @@ impl Struct {
         ::std::default::Default::default()
     }
 
+    /// Field number of `fields` field.
+    pub const FIELD_FIELDS_NUMBER: u32 = 1;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_map_simpler_accessor::<_, _, _>(
@@ -92,9 +95,10 @@ impl crate::Message for Struct {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
-        my_size += crate::rt::compute_map_size::<crate::reflect::types::ProtobufTypeString, crate::reflect::types::ProtobufTypeMessage<Value>>(1, &self.fields);
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        let mut my_size: u64 = 0;
+        my_size += crate::rt::compute_map_size::<crate::reflect::types::ProtobufTypeString, crate::reflect::types::ProtobufTypeMessage<Value>>(1, &self.fields) as u64;
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -148,6 +152,16 @@ impl crate::reflect::ProtobufValue for Struct {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `Struct`
+pub mod mod_struct {
+
+    /// Field numbers of message `Struct`
+    pub mod field_numbers {
+        /// Field number of `fields` field.
+        pub const FIELDS: u32 = 1;
+    }
+}
+
 ///  `Value` represents a dynamically typed value which can be either
 ///  null, a number, a string, a boolean, a recursive struct value, or a
 ///  list of values. A producer of value is expected to set one of that
@@ -177,6 +191,24 @@ impl Value {
         ::std::default::Default::default()
     }
 
+    /// Field number of `null_value` field.
+    pub const FIELD_NULL_VALUE_NUMBER: u32 = 1;
+
+    /// Field number of `number_value` field.
+    pub const FIELD_NUMBER_VALUE_NUMBER: u32 = 2;
+
+    /// Field number of `string_value` field.
+    pub const FIELD_STRING_VALUE_NUMBER: u32 = 3;
+
+    /// Field number of `bool_value` field.
+    pub const FIELD_BOOL_VALUE_NUMBER: u32 = 4;
+
+    /// Field number of `struct_value` field.
+    pub const FIELD_STRUCT_VALUE_NUMBER: u32 = 5;
+
+    /// Field number of `list_value` field.
+    pub const FIELD_LIST_VALUE_NUMBER: u32 = 6;
+
     // .google.protobuf.NullValue null_value = 1;
 
     pub fn get_null_value(&self) -> NullValue {
@@ -406,24 +438,28 @@ impl Value {
             Value::has_null_value,
             Value::get_null_value,
             Value::set_null_value,
+            Value::clear_null_value,
         ));
         fields.push(crate::reflect::rt::v2::make_oneof_copy_has_get_set_simpler_accessors::<_, _>(
             "number_value",
             Value::has_number_value,
             Value::get_number_value,
             Value::set_number_value,
+            Value::clear_number_value,
         ));
         fields.push(crate::reflect::rt::v2::make_oneof_deref_has_get_set_simpler_accessor::<_, _>(
             "string_value",
             Value::has_string_value,
             Value::get_string_value,
             Value::set_string_value,
+            Value::clear_string_value,
         ));
         fields.push(crate::reflect::rt::v2::make_oneof_copy_has_get_set_simpler_accessors::<_, _>(
             "bool_value",
             Value::has_bool_value,
             Value::get_bool_value,
             Value::set_bool_value,
+            Value::clear_bool_value,
         ));
         fields.push(crate::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, Struct>(
             "struct_value",
@@ -431,6 +467,7 @@ impl Value {
             Value::get_struct_value,
             Value::mut_struct_value,
             Value::set_struct_value,
+            Value::clear_struct_value,
         ));
         fields.push(crate::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, ListValue>(
             "list_value",
@@ -438,6 +475,7 @@ impl Value {
             Value::get_list_value,
             Value::mut_list_value,
             Value::set_list_value,
+            Value::clear_list_value,
         ));
         crate::reflect::GeneratedMessageDescriptorData::new_2::<Value>(
             "Value",
@@ -513,32 +551,33 @@ impl crate::Message for Value {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if let ::std::option::Option::Some(ref v) = self.kind {
             match v {
                 &value::Kind::null_value(v) => {
-                    my_size += crate::rt::enum_or_unknown_size(1, v);
+                    my_size += crate::rt::enum_or_unknown_size(1, v) as u64;
                 },
                 &value::Kind::number_value(v) => {
                     my_size += 9;
                 },
                 &value::Kind::string_value(ref v) => {
-                    my_size += crate::rt::string_size(3, &v);
+                    my_size += crate::rt::string_size(3, &v) as u64;
                 },
                 &value::Kind::bool_value(v) => {
                     my_size += 2;
                 },
                 &value::Kind::struct_value(ref v) => {
                     let len = v.compute_size();
-                    my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+                    my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
                 },
                 &value::Kind::list_value(ref v) => {
                     let len = v.compute_size();
-                    my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+                    my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
                 },
             };
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -625,6 +664,22 @@ impl crate::reflect::ProtobufValue for Value {
 /// Nested message and enums of message `Value`
 pub mod value {
 
+    /// Field numbers of message `Value`
+    pub mod field_numbers {
+        /// Field number of `null_value` field.
+        pub const NULL_VALUE: u32 = 1;
+        /// Field number of `number_value` field.
+        pub const NUMBER_VALUE: u32 = 2;
+        /// Field number of `string_value` field.
+        pub const STRING_VALUE: u32 = 3;
+        /// Field number of `bool_value` field.
+        pub const BOOL_VALUE: u32 = 4;
+        /// Field number of `struct_value` field.
+        pub const STRUCT_VALUE: u32 = 5;
+        /// Field number of `list_value` field.
+        pub const LIST_VALUE: u32 = 6;
+    }
+
     #[derive(Clone,PartialEq,Debug)]
     #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
     pub enum Kind {
@@ -667,6 +722,9 @@ impl ListValue {
         ::std::default::Default::default()
     }
 
+    /// Field number of `values` field.
+    pub const FIELD_VALUES_NUMBER: u32 = 1;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
@@ -710,12 +768,13 @@ impl crate::Message for ListValue {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         for value in &self.values {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -775,6 +834,16 @@ impl crate::reflect::ProtobufValue for ListValue {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `ListValue`
+pub mod list_value {
+
+    /// Field numbers of message `ListValue`
+    pub mod field_numbers {
+        /// Field number of `values` field.
+        pub const VALUES: u32 = 1;
+    }
+}
+
 ///  `NullValue` is a singleton enumeration to represent the null value for the
 ///  `Value` type union.
 ///
@@ -797,6 +866,19 @@ impl crate::ProtobufEnum for NullValue {
         }
     }
 
+    fn name(&self) -> &'static str {
+        match *self {
+            NullValue::NULL_VALUE => "NULL_VALUE",
+        }
+    }
+
+    fn from_name(name: &str) -> ::std::option::Option<NullValue> {
+        match name {
+            "NULL_VALUE" => ::std::option::Option::Some(NullValue::NULL_VALUE),
+            _ => ::std::option::Option::None
+        }
+    }
+
     fn values() -> &'static [Self] {
         static values: &'static [NullValue] = &[
             NullValue::NULL_VALUE,
@@ -815,6 +897,14 @@ impl ::std::default::Default for NullValue {
     }
 }
 
+impl ::std::str::FromStr for NullValue {
+    type Err = crate::ParseEnumError;
+
+    fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+        crate::rt::enum_from_str("NullValue", s)
+    }
+}
+
 impl crate::reflect::ProtobufValue for NullValue {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
 }
@@ -56,6 +56,24 @@ impl Type {
         ::std::default::Default::default()
     }
 
+    /// Field number of `name` field.
+    pub const FIELD_NAME_NUMBER: u32 = 1;
+
+    /// Field number of `fields` field.
+    pub const FIELD_FIELDS_NUMBER: u32 = 2;
+
+    /// Field number of `oneofs` field.
+    pub const FIELD_ONEOFS_NUMBER: u32 = 3;
+
+    /// Field number of `options` field.
+    pub const FIELD_OPTIONS_NUMBER: u32 = 4;
+
+    /// Field number of `source_context` field.
+    pub const FIELD_SOURCE_CONTEXT_NUMBER: u32 = 5;
+
+    /// Field number of `syntax` field.
+    pub const FIELD_SYNTAX_NUMBER: u32 = 6;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -155,29 +173,30 @@ impl crate::Message for Type {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if !self.name.is_empty() {
-            my_size += crate::rt::string_size(1, &self.name);
+            my_size += crate::rt::string_size(1, &self.name) as u64;
         }
         for value in &self.fields {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         for value in &self.oneofs {
-            my_size += crate::rt::string_size(3, &value);
+            my_size += crate::rt::string_size(3, &value) as u64;
         };
         for value in &self.options {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         if let Some(v) = self.source_context.as_ref() {
             let len = v.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         }
         if self.syntax != crate::ProtobufEnumOrUnknown::new(Syntax::SYNTAX_PROTO2) {
-            my_size += crate::rt::enum_or_unknown_size(6, self.syntax);
+            my_size += crate::rt::enum_or_unknown_size(6, self.syntax) as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -262,6 +281,26 @@ impl crate::reflect::ProtobufValue for Type {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `Type`
+pub mod mod_type {
+
+    /// Field numbers of message `Type`
+    pub mod field_numbers {
+        /// Field number of `name` field.
+        pub const NAME: u32 = 1;
+        /// Field number of `fields` field.
+        pub const FIELDS: u32 = 2;
+        /// Field number of `oneofs` field.
+        pub const ONEOFS: u32 = 3;
+        /// Field number of `options` field.
+        pub const OPTIONS: u32 = 4;
+        /// Field number of `source_context` field.
+        pub const SOURCE_CONTEXT: u32 = 5;
+        /// Field number of `syntax` field.
+        pub const SYNTAX: u32 = 6;
+    }
+}
+
 ///  A single field of a message type.
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
@@ -307,6 +346,36 @@ impl Field {
         ::std::default::Default::default()
     }
 
+    /// Field number of `kind` field.
+    pub const FIELD_KIND_NUMBER: u32 = 1;
+
+    /// Field number of `cardinality` field.
+    pub const FIELD_CARDINALITY_NUMBER: u32 = 2;
+
+    /// Field number of `number` field.
+    pub const FIELD_NUMBER_NUMBER: u32 = 3;
+
+    /// Field number of `name` field.
+    pub const FIELD_NAME_NUMBER: u32 = 4;
+
+    /// Field number of `type_url` field.
+    pub const FIELD_TYPE_URL_NUMBER: u32 = 6;
+
+    /// Field number of `oneof_index` field.
+    pub const FIELD_ONEOF_INDEX_NUMBER: u32 = 7;
+
+    /// Field number of `packed` field.
+    pub const FIELD_PACKED_NUMBER: u32 = 8;
+
+    /// Field number of `options` field.
+    pub const FIELD_OPTIONS_NUMBER: u32 = 9;
+
+    /// Field number of `json_name` field.
+    pub const FIELD_JSON_NAME_NUMBER: u32 = 10;
+
+    /// Field number of `default_value` field.
+    pub const FIELD_DEFAULT_VALUE_NUMBER: u32 = 11;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -449,39 +518,40 @@ impl crate::Message for Field {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if self.kind != crate::ProtobufEnumOrUnknown::new(field::Kind::TYPE_UNKNOWN) {
-            my_size += crate::rt::enum_or_unknown_size(1, self.kind);
+            my_size += crate::rt::enum_or_unknown_size(1, self.kind) as u64;
         }
         if self.cardinality != crate::ProtobufEnumOrUnknown::new(field::Cardinality::CARDINALITY_UNKNOWN) {
-            my_size += crate::rt::enum_or_unknown_size(2, self.cardinality);
+            my_size += crate::rt::enum_or_unknown_size(2, self.cardinality) as u64;
         }
         if self.number != 0 {
-            my_size += crate::rt::value_size(3, self.number, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(3, self.number, crate::wire_format::WireTypeVarint) as u64;
         }
         if !self.name.is_empty() {
-            my_size += crate::rt::string_size(4, &self.name);
+            my_size += crate::rt::string_size(4, &self.name) as u64;
         }
         if !self.type_url.is_empty() {
-            my_size += crate::rt::string_size(6, &self.type_url);
+            my_size += crate::rt::string_size(6, &self.type_url) as u64;
         }
         if self.oneof_index != 0 {
-            my_size += crate::rt::value_size(7, self.oneof_index, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(7, self.oneof_index, crate::wire_format::WireTypeVarint) as u64;
         }
         if self.packed != false {
             my_size += 2;
         }
         for value in &self.options {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         if !self.json_name.is_empty() {
-            my_size += crate::rt::string_size(10, &self.json_name);
+            my_size += crate::rt::string_size(10, &self.json_name) as u64;
         }
         if !self.default_value.is_empty() {
-            my_size += crate::rt::string_size(11, &self.default_value);
+            my_size += crate::rt::string_size(11, &self.default_value) as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -588,6 +658,30 @@ impl crate::reflect::ProtobufValue for Field {
 
 /// Nested message and enums of message `Field`
 pub mod field {
+
+    /// Field numbers of message `Field`
+    pub mod field_numbers {
+        /// Field number of `kind` field.
+        pub const KIND: u32 = 1;
+        /// Field number of `cardinality` field.
+        pub const CARDINALITY: u32 = 2;
+        /// Field number of `number` field.
+        pub const NUMBER: u32 = 3;
+        /// Field number of `name` field.
+        pub const NAME: u32 = 4;
+        /// Field number of `type_url` field.
+        pub const TYPE_URL: u32 = 6;
+        /// Field number of `oneof_index` field.
+        pub const ONEOF_INDEX: u32 = 7;
+        /// Field number of `packed` field.
+        pub const PACKED: u32 = 8;
+        /// Field number of `options` field.
+        pub const OPTIONS: u32 = 9;
+        /// Field number of `json_name` field.
+        pub const JSON_NAME: u32 = 10;
+        /// Field number of `default_value` field.
+        pub const DEFAULT_VALUE: u32 = 11;
+    }
     ///  Basic field types.
     #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
     #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
@@ -643,6 +737,55 @@ pub mod field {
             }
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                Kind::TYPE_UNKNOWN => "TYPE_UNKNOWN",
+                Kind::TYPE_DOUBLE => "TYPE_DOUBLE",
+                Kind::TYPE_FLOAT => "TYPE_FLOAT",
+                Kind::TYPE_INT64 => "TYPE_INT64",
+                Kind::TYPE_UINT64 => "TYPE_UINT64",
+                Kind::TYPE_INT32 => "TYPE_INT32",
+                Kind::TYPE_FIXED64 => "TYPE_FIXED64",
+                Kind::TYPE_FIXED32 => "TYPE_FIXED32",
+                Kind::TYPE_BOOL => "TYPE_BOOL",
+                Kind::TYPE_STRING => "TYPE_STRING",
+                Kind::TYPE_GROUP => "TYPE_GROUP",
+                Kind::TYPE_MESSAGE => "TYPE_MESSAGE",
+                Kind::TYPE_BYTES => "TYPE_BYTES",
+                Kind::TYPE_UINT32 => "TYPE_UINT32",
+                Kind::TYPE_ENUM => "TYPE_ENUM",
+                Kind::TYPE_SFIXED32 => "TYPE_SFIXED32",
+                Kind::TYPE_SFIXED64 => "TYPE_SFIXED64",
+                Kind::TYPE_SINT32 => "TYPE_SINT32",
+                Kind::TYPE_SINT64 => "TYPE_SINT64",
+            }
+        }
+
+        fn from_name(name: &str) -> ::std::option::Option<Kind> {
+            match name {
+                "TYPE_UNKNOWN" => ::std::option::Option::Some(Kind::TYPE_UNKNOWN),
+                "TYPE_DOUBLE" => ::std::option::Option::Some(Kind::TYPE_DOUBLE),
+                "TYPE_FLOAT" => ::std::option::Option::Some(Kind::TYPE_FLOAT),
+                "TYPE_INT64" => ::std::option::Option::Some(Kind::TYPE_INT64),
+                "TYPE_UINT64" => ::std::option::Option::Some(Kind::TYPE_UINT64),
+                "TYPE_INT32" => ::std::option::Option::Some(Kind::TYPE_INT32),
+                "TYPE_FIXED64" => ::std::option::Option::Some(Kind::TYPE_FIXED64),
+                "TYPE_FIXED32" => ::std::option::Option::Some(Kind::TYPE_FIXED32),
+                "TYPE_BOOL" => ::std::option::Option::Some(Kind::TYPE_BOOL),
+                "TYPE_STRING" => ::std::option::Option::Some(Kind::TYPE_STRING),
+                "TYPE_GROUP" => ::std::option::Option::Some(Kind::TYPE_GROUP),
+                "TYPE_MESSAGE" => ::std::option::Option::Some(Kind::TYPE_MESSAGE),
+                "TYPE_BYTES" => ::std::option::Option::Some(Kind::TYPE_BYTES),
+                "TYPE_UINT32" => ::std::option::Option::Some(Kind::TYPE_UINT32),
+                "TYPE_ENUM" => ::std::option::Option::Some(Kind::TYPE_ENUM),
+                "TYPE_SFIXED32" => ::std::option::Option::Some(Kind::TYPE_SFIXED32),
+                "TYPE_SFIXED64" => ::std::option::Option::Some(Kind::TYPE_SFIXED64),
+                "TYPE_SINT32" => ::std::option::Option::Some(Kind::TYPE_SINT32),
+                "TYPE_SINT64" => ::std::option::Option::Some(Kind::TYPE_SINT64),
+                _ => ::std::option::Option::None
+            }
+        }
+
         fn values() -> &'static [Self] {
             static values: &'static [Kind] = &[
                 Kind::TYPE_UNKNOWN,
@@ -679,6 +822,14 @@ pub mod field {
         }
     }
 
+    impl ::std::str::FromStr for Kind {
+        type Err = crate::ParseEnumError;
+
+        fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+            crate::rt::enum_from_str("Field.Kind", s)
+        }
+    }
+
     impl crate::reflect::ProtobufValue for Kind {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
     }
@@ -714,6 +865,25 @@ pub mod field {
             }
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                Cardinality::CARDINALITY_UNKNOWN => "CARDINALITY_UNKNOWN",
+                Cardinality::CARDINALITY_OPTIONAL => "CARDINALITY_OPTIONAL",
+                Cardinality::CARDINALITY_REQUIRED => "CARDINALITY_REQUIRED",
+                Cardinality::CARDINALITY_REPEATED => "CARDINALITY_REPEATED",
+            }
+        }
+
+        fn from_name(name: &str) -> ::std::option::Option<Cardinality> {
+            match name {
+                "CARDINALITY_UNKNOWN" => ::std::option::Option::Some(Cardinality::CARDINALITY_UNKNOWN),
+                "CARDINALITY_OPTIONAL" => ::std::option::Option::Some(Cardinality::CARDINALITY_OPTIONAL),
+                "CARDINALITY_REQUIRED" => ::std::option::Option::Some(Cardinality::CARDINALITY_REQUIRED),
+                "CARDINALITY_REPEATED" => ::std::option::Option::Some(Cardinality::CARDINALITY_REPEATED),
+                _ => ::std::option::Option::None
+            }
+        }
+
         fn values() -> &'static [Self] {
             static values: &'static [Cardinality] = &[
                 Cardinality::CARDINALITY_UNKNOWN,
@@ -735,6 +905,14 @@ pub mod field {
         }
     }
 
+    impl ::std::str::FromStr for Cardinality {
+        type Err = crate::ParseEnumError;
+
+        fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+            crate::rt::enum_from_str("Field.Cardinality", s)
+        }
+    }
+
     impl crate::reflect::ProtobufValue for Cardinality {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
     }
@@ -779,6 +957,21 @@ impl Enum {
         ::std::default::Default::default()
     }
 
+    /// Field number of `name` field.
+    pub const FIELD_NAME_NUMBER: u32 = 1;
+
+    /// Field number of `enumvalue` field.
+    pub const FIELD_ENUMVALUE_NUMBER: u32 = 2;
+
+    /// Field number of `options` field.
+    pub const FIELD_OPTIONS_NUMBER: u32 = 3;
+
+    /// Field number of `source_context` field.
+    pub const FIELD_SOURCE_CONTEXT_NUMBER: u32 = 4;
+
+    /// Field number of `syntax` field.
+    pub const FIELD_SYNTAX_NUMBER: u32 = 5;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -870,26 +1063,27 @@ impl crate::Message for Enum {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if !self.name.is_empty() {
-            my_size += crate::rt::string_size(1, &self.name);
+            my_size += crate::rt::string_size(1, &self.name) as u64;
         }
         for value in &self.enumvalue {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         for value in &self.options {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
         if let Some(v) = self.source_context.as_ref() {
             let len = v.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         }
         if self.syntax != crate::ProtobufEnumOrUnknown::new(Syntax::SYNTAX_PROTO2) {
-            my_size += crate::rt::enum_or_unknown_size(5, self.syntax);
+            my_size += crate::rt::enum_or_unknown_size(5, self.syntax) as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -969,6 +1163,24 @@ impl crate::reflect::ProtobufValue for Enum {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `Enum`
+pub mod mod_enum {
+
+    /// Field numbers of message `Enum`
+    pub mod field_numbers {
+        /// Field number of `name` field.
+        pub const NAME: u32 = 1;
+        /// Field number of `enumvalue` field.
+        pub const ENUMVALUE: u32 = 2;
+        /// Field number of `options` field.
+        pub const OPTIONS: u32 = 3;
+        /// Field number of `source_context` field.
+        pub const SOURCE_CONTEXT: u32 = 4;
+        /// Field number of `syntax` field.
+        pub const SYNTAX: u32 = 5;
+    }
+}
+
 ///  Enum value definition.
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
@@ -998,6 +1210,15 @@ impl EnumValue {
         ::std::default::Default::default()
     }
 
+    /// Field number of `name` field.
+    pub const FIELD_NAME_NUMBER: u32 = 1;
+
+    /// Field number of `number` field.
+    pub const FIELD_NUMBER_NUMBER: u32 = 2;
+
+    /// Field number of `options` field.
+    pub const FIELD_OPTIONS_NUMBER: u32 = 3;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -1063,18 +1284,19 @@ impl crate::Message for EnumValue {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if !self.name.is_empty() {
-            my_size += crate::rt::string_size(1, &self.name);
+            my_size += crate::rt::string_size(1, &self.name) as u64;
         }
         if self.number != 0 {
-            my_size += crate::rt::value_size(2, self.number, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(2, self.number, crate::wire_format::WireTypeVarint) as u64;
         }
         for value in &self.options {
             let len = value.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         };
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -1144,6 +1366,20 @@ impl crate::reflect::ProtobufValue for EnumValue {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `EnumValue`
+pub mod enum_value {
+
+    /// Field numbers of message `EnumValue`
+    pub mod field_numbers {
+        /// Field number of `name` field.
+        pub const NAME: u32 = 1;
+        /// Field number of `number` field.
+        pub const NUMBER: u32 = 2;
+        /// Field number of `options` field.
+        pub const OPTIONS: u32 = 3;
+    }
+}
+
 ///  A protocol buffer option, which can be attached to a message, field,
 ///  enumeration, etc.
 #[derive(PartialEq,Clone,Default)]
@@ -1178,6 +1414,12 @@ impl Option {
         ::std::default::Default::default()
     }
 
+    /// Field number of `name` field.
+    pub const FIELD_NAME_NUMBER: u32 = 1;
+
+    /// Field number of `value` field.
+    pub const FIELD_VALUE_NUMBER: u32 = 2;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -1232,15 +1474,16 @@ impl crate::Message for Option {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if !self.name.is_empty() {
-            my_size += crate::rt::string_size(1, &self.name);
+            my_size += crate::rt::string_size(1, &self.name) as u64;
         }
         if let Some(v) = self.value.as_ref() {
             let len = v.compute_size();
-            my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + crate::rt::compute_raw_varint32_size(len) as u64 + len as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -1305,6 +1548,18 @@ impl crate::reflect::ProtobufValue for Option {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `Option`
+pub mod option {
+
+    /// Field numbers of message `Option`
+    pub mod field_numbers {
+        /// Field number of `name` field.
+        pub const NAME: u32 = 1;
+        /// Field number of `value` field.
+        pub const VALUE: u32 = 2;
+    }
+}
+
 ///  The syntax in which a protocol buffer element is defined.
 #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
 #[cfg_attr(serde, derive(::serde::Serialize, ::serde::Deserialize))]
@@ -1326,6 +1581,21 @@ impl crate::ProtobufEnum for Syntax {
         }
     }
 
+    fn name(&self) -> &'static str {
+        match *self {
+            Syntax::SYNTAX_PROTO2 => "SYNTAX_PROTO2",
+            Syntax::SYNTAX_PROTO3 => "SYNTAX_PROTO3",
+        }
+    }
+
+    fn from_name(name: &str) -> ::std::option::Option<Syntax> {
+        match name {
+            "SYNTAX_PROTO2" => ::std::option::Option::Some(Syntax::SYNTAX_PROTO2),
+            "SYNTAX_PROTO3" => ::std::option::Option::Some(Syntax::SYNTAX_PROTO3),
+            _ => ::std::option::Option::None
+        }
+    }
+
     fn values() -> &'static [Self] {
         static values: &'static [Syntax] = &[
             Syntax::SYNTAX_PROTO2,
@@ -1345,6 +1615,14 @@ impl ::std::default::Default for Syntax {
     }
 }
 
+impl ::std::str::FromStr for Syntax {
+    type Err = crate::ParseEnumError;
+
+    fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+        crate::rt::enum_from_str("Syntax", s)
+    }
+}
+
 impl crate::reflect::ProtobufValue for Syntax {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
 }
@@ -48,6 +48,9 @@ impl DoubleValue {
         ::std::default::Default::default()
     }
 
+    /// Field number of `value` field.
+    pub const FIELD_VALUE_NUMBER: u32 = 1;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -89,11 +92,12 @@ impl crate::Message for DoubleValue {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if self.value != 0. {
             my_size += 9;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -153,6 +157,16 @@ impl crate::reflect::ProtobufValue for DoubleValue {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `DoubleValue`
+pub mod double_value {
+
+    /// Field numbers of message `DoubleValue`
+    pub mod field_numbers {
+        /// Field number of `value` field.
+        pub const VALUE: u32 = 1;
+    }
+}
+
 ///  Wrapper message for `float`.
 ///
 ///  The JSON representation for `FloatValue` is JSON number.
@@ -180,6 +194,9 @@ impl FloatValue {
         ::std::default::Default::default()
     }
 
+    /// Field number of `value` field.
+    pub const FIELD_VALUE_NUMBER: u32 = 1;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -221,11 +238,12 @@ impl crate::Message for FloatValue {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if self.value != 0. {
             my_size += 5;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -285,6 +303,16 @@ impl crate::reflect::ProtobufValue for FloatValue {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `FloatValue`
+pub mod float_value {
+
+    /// Field numbers of message `FloatValue`
+    pub mod field_numbers {
+        /// Field number of `value` field.
+        pub const VALUE: u32 = 1;
+    }
+}
+
 ///  Wrapper message for `int64`.
 ///
 ///  The JSON representation for `Int64Value` is JSON string.
@@ -312,6 +340,9 @@ impl Int64Value {
         ::std::default::Default::default()
     }
 
+    /// Field number of `value` field.
+    pub const FIELD_VALUE_NUMBER: u32 = 1;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -353,11 +384,12 @@ impl crate::Message for Int64Value {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if self.value != 0 {
-            my_size += crate::rt::value_size(1, self.value, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(1, self.value, crate::wire_format::WireTypeVarint) as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -417,6 +449,16 @@ impl crate::reflect::ProtobufValue for Int64Value {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `Int64Value`
+pub mod int64value {
+
+    /// Field numbers of message `Int64Value`
+    pub mod field_numbers {
+        /// Field number of `value` field.
+        pub const VALUE: u32 = 1;
+    }
+}
+
 ///  Wrapper message for `uint64`.
 ///
 ///  The JSON representation for `UInt64Value` is JSON string.
@@ -444,6 +486,9 @@ impl UInt64Value {
         ::std::default::Default::default()
     }
 
+    /// Field number of `value` field.
+    pub const FIELD_VALUE_NUMBER: u32 = 1;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -485,11 +530,12 @@ impl crate::Message for UInt64Value {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if self.value != 0 {
-            my_size += crate::rt::value_size(1, self.value, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(1, self.value, crate::wire_format::WireTypeVarint) as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -549,6 +595,16 @@ impl crate::reflect::ProtobufValue for UInt64Value {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `UInt64Value`
+pub mod uint64value {
+
+    /// Field numbers of message `UInt64Value`
+    pub mod field_numbers {
+        /// Field number of `value` field.
+        pub const VALUE: u32 = 1;
+    }
+}
+
 ///  Wrapper message for `int32`.
 ///
 ///  The JSON representation for `Int32Value` is JSON number.
@@ -576,6 +632,9 @@ impl Int32Value {
         ::std::default::Default::default()
     }
 
+    /// Field number of `value` field.
+    pub const FIELD_VALUE_NUMBER: u32 = 1;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -617,11 +676,12 @@ impl crate::Message for Int32Value {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if self.value != 0 {
-            my_size += crate::rt::value_size(1, self.value, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(1, self.value, crate::wire_format::WireTypeVarint) as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -681,6 +741,16 @@ impl crate::reflect::ProtobufValue for Int32Value {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `Int32Value`
+pub mod int32value {
+
+    /// Field numbers of message `Int32Value`
+    pub mod field_numbers {
+        /// Field number of `value` field.
+        pub const VALUE: u32 = 1;
+    }
+}
+
 ///  Wrapper message for `uint32`.
 ///
 ///  The JSON representation for `UInt32Value` is JSON number.
@@ -708,6 +778,9 @@ impl UInt32Value {
         ::std::default::Default::default()
     }
 
+    /// Field number of `value` field.
+    pub const FIELD_VALUE_NUMBER: u32 = 1;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -749,11 +822,12 @@ impl crate::Message for UInt32Value {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if self.value != 0 {
-            my_size += crate::rt::value_size(1, self.value, crate::wire_format::WireTypeVarint);
+            my_size += crate::rt::value_size(1, self.value, crate::wire_format::WireTypeVarint) as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -813,6 +887,16 @@ impl crate::reflect::ProtobufValue for UInt32Value {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `UInt32Value`
+pub mod uint32value {
+
+    /// Field numbers of message `UInt32Value`
+    pub mod field_numbers {
+        /// Field number of `value` field.
+        pub const VALUE: u32 = 1;
+    }
+}
+
 ///  Wrapper message for `bool`.
 ///
 ///  The JSON representation for `BoolValue` is JSON `true` and `false`.
@@ -840,6 +924,9 @@ impl BoolValue {
         ::std::default::Default::default()
     }
 
+    /// Field number of `value` field.
+    pub const FIELD_VALUE_NUMBER: u32 = 1;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -881,11 +968,12 @@ impl crate::Message for BoolValue {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if self.value != false {
             my_size += 2;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -945,6 +1033,16 @@ impl crate::reflect::ProtobufValue for BoolValue {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `BoolValue`
+pub mod bool_value {
+
+    /// Field numbers of message `BoolValue`
+    pub mod field_numbers {
+        /// Field number of `value` field.
+        pub const VALUE: u32 = 1;
+    }
+}
+
 ///  Wrapper message for `string`.
 ///
 ///  The JSON representation for `StringValue` is JSON string.
@@ -972,6 +1070,9 @@ impl StringValue {
         ::std::default::Default::default()
     }
 
+    /// Field number of `value` field.
+    pub const FIELD_VALUE_NUMBER: u32 = 1;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -1013,11 +1114,12 @@ impl crate::Message for StringValue {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if !self.value.is_empty() {
-            my_size += crate::rt::string_size(1, &self.value);
+            my_size += crate::rt::string_size(1, &self.value) as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -1077,6 +1179,16 @@ impl crate::reflect::ProtobufValue for StringValue {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `StringValue`
+pub mod string_value {
+
+    /// Field numbers of message `StringValue`
+    pub mod field_numbers {
+        /// Field number of `value` field.
+        pub const VALUE: u32 = 1;
+    }
+}
+
 ///  Wrapper message for `bytes`.
 ///
 ///  The JSON representation for `BytesValue` is JSON string.
@@ -1104,6 +1216,9 @@ impl BytesValue {
         ::std::default::Default::default()
     }
 
+    /// Field number of `value` field.
+    pub const FIELD_VALUE_NUMBER: u32 = 1;
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
@@ -1145,11 +1260,12 @@ impl crate::Message for BytesValue {
     // Compute sizes of nested messages
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
+        let mut my_size: u64 = 0;
         if !self.value.is_empty() {
-            my_size += crate::rt::bytes_size(1, &self.value);
+            my_size += crate::rt::bytes_size(1, &self.value) as u64;
         }
-        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
+        my_size += crate::rt::unknown_fields_size(self.get_unknown_fields()) as u64;
+        let my_size = crate::rt::saturating_size(my_size);
         self.cached_size.set(my_size);
         my_size
     }
@@ -1209,6 +1325,16 @@ impl crate::reflect::ProtobufValue for BytesValue {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeMessage<Self>;
 }
 
+/// Nested message and enums of message `BytesValue`
+pub mod bytes_value {
+
+    /// Field numbers of message `BytesValue`
+    pub mod field_numbers {
+        /// Field number of `value` field.
+        pub const VALUE: u32 = 1;
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\x1egoogle/protobuf/wrappers.proto\x12\x0fgoogle.protobuf\"#\n\x0bDoub\
     leValue\x12\x14\n\x05value\x18\x01\x20\x01(\x01R\x05value\"\"\n\nFloatVa\
@@ -1,21 +1,78 @@
 use crate::message_dyn::MessageDyn;
+use crate::reflect::FileDescriptorPool;
 use crate::reflect::MessageDescriptor;
 use crate::well_known_types::Any;
+use crate::well_known_types_util::well_known_type_descriptor;
 use crate::Message;
 use crate::ProtobufResult;
 
 impl Any {
+    /// Type URL prefix used by [`Any::pack`] and [`Any::pack_dyn`].
+    pub const DEFAULT_TYPE_URL_PREFIX: &str = "type.googleapis.com";
+
     fn type_url(type_url_prefix: &str, descriptor: &MessageDescriptor) -> String {
-        format!("{}/{}", type_url_prefix, descriptor.full_name())
+        format!(
+            "{}/{}",
+            type_url_prefix.trim_end_matches('/'),
+            descriptor.full_name()
+        )
     }
 
-    fn get_type_name_from_type_url(type_url: &str) -> Option<&str> {
+    pub(crate) fn get_type_name_from_type_url(type_url: &str) -> Option<&str> {
         match type_url.rfind('/') {
             Some(i) => Some(&type_url[i + 1..]),
             None => None,
         }
     }
 
+    /// Find message type referenced from type URL.
+    ///
+    /// Well known types are always found, other types are searched in the pool.
+    pub(crate) fn find_type(
+        type_url: &str,
+        pool: Option<&FileDescriptorPool>,
+    ) -> Option<MessageDescriptor> {
+        Any::find_type_with(type_url, |type_name| pool?.message_by_full_name(type_name))
+    }
+
+    /// Find message type referenced from type URL.
+    ///
+    /// Well known types are always found, other types are searched
+    /// with given function by full name without leading dot.
+    pub(crate) fn find_type_with(
+        type_url: &str,
+        find: impl FnOnce(&str) -> Option<MessageDescriptor>,
+    ) -> Option<MessageDescriptor> {
+        let type_name = Any::get_type_name_from_type_url(type_url)?;
+        if let Some(descriptor) = well_known_type_descriptor(type_name) {
+            return Some(descriptor);
+        }
+        find(type_name)
+    }
+
+    /// Full name of the packed message type, e. g. `foo.Bar`
+    /// for type URL `type.googleapis.com/foo.Bar`.
+    ///
+    /// `None` if type URL does not contain `/`.
+    pub fn type_name(&self) -> Option<&str> {
+        Any::get_type_name_from_type_url(&self.type_url)
+    }
+
+    /// Type URL without the type name, e. g. `type.googleapis.com`
+    /// for type URL `type.googleapis.com/foo.Bar`.
+    ///
+    /// `None` if type URL does not contain `/`.
+    pub fn type_url_prefix(&self) -> Option<&str> {
+        self.type_url.rfind('/').map(|i| &self.type_url[..i])
+    }
+
+    /// Find the descriptor of the packed message type in the pool.
+    ///
+    /// Well known types are found even if they are not in the pool.
+    pub fn find_descriptor(&self, pool: &FileDescriptorPool) -> Option<MessageDescriptor> {
+        Any::find_type(&self.type_url, Some(pool))
+    }
+
     /// Pack any message into `well_known_types::Any` value.
     ///
     /// # Examples
@@ -53,10 +110,27 @@ impl Any {
     /// # }
     /// ```
     pub fn pack_dyn(message: &dyn MessageDyn) -> ProtobufResult<Any> {
-        Any::pack_with_type_url_prefix(message, "type.googleapis.com")
+        Any::pack_dyn_with_type_url_prefix(message, Any::DEFAULT_TYPE_URL_PREFIX)
     }
 
-    fn pack_with_type_url_prefix(
+    /// Pack any message into `well_known_types::Any` value
+    /// with custom type URL prefix, e. g. `example.com/types`.
+    ///
+    /// Trailing `/` of the prefix is optional.
+    /// Unpacking and type resolution ignore the prefix, so the result
+    /// can be unpacked with [`Any::unpack`] or [`Any::unpack_with_pool`].
+    pub fn pack_with_type_url_prefix<M: Message>(
+        message: &M,
+        type_url_prefix: &str,
+    ) -> ProtobufResult<Any> {
+        Any::pack_dyn_with_type_url_prefix(message, type_url_prefix)
+    }
+
+    /// Pack any message into `well_known_types::Any` value
+    /// with custom type URL prefix.
+    ///
+    /// See [`Any::pack_with_type_url_prefix`].
+    pub fn pack_dyn_with_type_url_prefix(
         message: &dyn MessageDyn,
         type_url_prefix: &str,
     ) -> ProtobufResult<Any> {
@@ -111,4 +185,23 @@ impl Any {
         message.check_initialized_dyn()?;
         Ok(Some(message))
     }
+
+    /// Extract a message which type is found in the pool.
+    ///
+    /// This is useful when type of the packed message is not known in advance,
+    /// e. g. when descriptors are loaded from a schema registry.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(None)` when message type is not found in the pool
+    /// * `Err` when parse failed
+    pub fn unpack_with_pool(
+        &self,
+        pool: &FileDescriptorPool,
+    ) -> ProtobufResult<Option<Box<dyn MessageDyn>>> {
+        match self.find_descriptor(pool) {
+            Some(descriptor) => self.unpack_dyn(&descriptor),
+            None => Ok(None),
+        }
+    }
 }
@@ -1,5 +1,9 @@
+#[cfg(any(feature = "with-chrono", feature = "with-time"))]
+use std::convert::TryFrom;
+
 use crate::cached_size::CachedSize;
 use crate::well_known_types::Duration;
+use crate::well_known_types_util::TimeConversionError;
 use crate::UnknownFields;
 
 impl Duration {
@@ -10,6 +14,66 @@ impl Duration {
         unknown_fields: UnknownFields::new(),
         cached_size: CachedSize::new(),
     };
+
+    /// Duration from given number of seconds, possibly negative.
+    ///
+    /// Fractional part is rounded to nanoseconds,
+    /// `seconds` and `nanos` fields get the same sign.
+    ///
+    /// # Panics
+    ///
+    /// If `secs` is not finite or does not fit in `i64` seconds.
+    pub fn from_secs_f64(secs: f64) -> Duration {
+        assert!(
+            secs.is_finite() && secs.abs() < i64::MAX as f64,
+            "duration seconds out of range: {}",
+            secs
+        );
+        let mut seconds = secs.trunc() as i64;
+        let mut nanos = (secs.fract() * 1e9).round() as i32;
+        if nanos == 1_000_000_000 {
+            seconds += 1;
+            nanos = 0;
+        } else if nanos == -1_000_000_000 {
+            seconds -= 1;
+            nanos = 0;
+        }
+        Duration {
+            seconds,
+            nanos,
+            ..Default::default()
+        }
+    }
+
+    /// Duration as a number of seconds, possibly negative.
+    pub fn as_secs_f64(&self) -> f64 {
+        self.seconds as f64 + self.nanos as f64 / 1e9
+    }
+
+    /// Convert to `std::time::Duration`.
+    ///
+    /// Same as `Into<std::time::Duration>`, but fails if `Duration` is negative
+    /// or if `nanos` is invalid.
+    pub fn to_std_duration(&self) -> Result<std::time::Duration, TimeConversionError> {
+        self.check_nanos()?;
+        if self.seconds < 0 || self.nanos < 0 {
+            return Err(TimeConversionError::OutOfRange);
+        }
+        Ok(std::time::Duration::new(
+            self.seconds as u64,
+            self.nanos as u32,
+        ))
+    }
+
+    /// `nanos` is in `-999_999_999..=999_999_999` range with the same sign as `seconds`.
+    fn check_nanos(&self) -> Result<(), TimeConversionError> {
+        let same_sign =
+            (self.seconds <= 0 && self.nanos <= 0) || (self.seconds >= 0 && self.nanos >= 0);
+        if !(-999_999_999..1_000_000_000).contains(&self.nanos) || !same_sign {
+            return Err(TimeConversionError::InvalidNanos(self.nanos));
+        }
+        Ok(())
+    }
 }
 
 /// Convert from `std::time::Duration`.
@@ -34,6 +98,8 @@ impl From<std::time::Duration> for Duration {
 /// # Panics
 ///
 /// If `Duration` value is outside of `std::time::Duration` supported range.
+///
+/// Use [`Duration::to_std_duration`] to handle this error.
 impl Into<std::time::Duration> for Duration {
     fn into(self) -> std::time::Duration {
         assert!(self.seconds >= 0);
@@ -42,14 +108,69 @@ impl Into<std::time::Duration> for Duration {
     }
 }
 
+/// Convert from `chrono::Duration`.
+#[cfg(feature = "with-chrono")]
+impl From<chrono::Duration> for Duration {
+    fn from(duration: chrono::Duration) -> Self {
+        Duration {
+            seconds: duration.num_seconds(),
+            nanos: duration.subsec_nanos(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Convert to `chrono::Duration`.
+///
+/// Fails if `Duration` is outside of `chrono::Duration` range
+/// or if `nanos` is invalid.
+#[cfg(feature = "with-chrono")]
+impl TryFrom<Duration> for chrono::Duration {
+    type Error = TimeConversionError;
+
+    fn try_from(duration: Duration) -> Result<Self, TimeConversionError> {
+        duration.check_nanos()?;
+        chrono::Duration::try_seconds(duration.seconds)
+            .and_then(|d| d.checked_add(&chrono::Duration::nanoseconds(duration.nanos as i64)))
+            .ok_or(TimeConversionError::OutOfRange)
+    }
+}
+
+/// Convert from `time::Duration`.
+#[cfg(feature = "with-time")]
+impl From<time::Duration> for Duration {
+    fn from(duration: time::Duration) -> Self {
+        Duration {
+            seconds: duration.whole_seconds(),
+            nanos: duration.subsec_nanoseconds(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Convert to `time::Duration`.
+///
+/// Fails if `nanos` is invalid.
+#[cfg(feature = "with-time")]
+impl TryFrom<Duration> for time::Duration {
+    type Error = TimeConversionError;
+
+    fn try_from(duration: Duration) -> Result<Self, TimeConversionError> {
+        duration.check_nanos()?;
+        Ok(time::Duration::new(duration.seconds, duration.nanos))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::well_known_types::Duration;
+    use crate::well_known_types_util::TimeConversionError;
 
     #[test]
     fn to_from_duration() {
         fn to_from(duration: Duration, std_time_duration: std::time::Duration) {
             assert_eq!(duration, Duration::from(std_time_duration));
+            assert_eq!(Ok(std_time_duration), duration.to_std_duration());
             assert_eq!(
                 std_time_duration,
                 Into::<std::time::Duration>::into(duration)
@@ -66,4 +187,93 @@ mod test {
             std::time::Duration::from_millis(4_123),
         );
     }
+
+    #[test]
+    fn to_duration_errors() {
+        let negative = Duration {
+            seconds: -1,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(TimeConversionError::OutOfRange),
+            negative.to_std_duration()
+        );
+        let invalid_nanos = Duration {
+            nanos: 1_000_000_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(TimeConversionError::InvalidNanos(1_000_000_000)),
+            invalid_nanos.to_std_duration()
+        );
+    }
+
+    #[test]
+    fn secs_f64() {
+        let d = Duration::from_secs_f64(4.123);
+        assert_eq!((4, 123_000_000), (d.seconds, d.nanos));
+        assert_eq!(4.123, d.as_secs_f64());
+
+        let d = Duration::from_secs_f64(-1.5);
+        assert_eq!((-1, -500_000_000), (d.seconds, d.nanos));
+        assert_eq!(-1.5, d.as_secs_f64());
+
+        let d = Duration::from_secs_f64(0.999_999_999_9);
+        assert_eq!((1, 0), (d.seconds, d.nanos));
+    }
+
+    #[test]
+    #[should_panic]
+    fn secs_f64_nan() {
+        Duration::from_secs_f64(f64::NAN);
+    }
+
+    #[cfg(feature = "with-chrono")]
+    #[test]
+    fn to_from_chrono() {
+        use std::convert::TryFrom;
+
+        let duration = Duration {
+            seconds: -3,
+            nanos: -200_000_000,
+            ..Default::default()
+        };
+        let chrono_duration = chrono::Duration::milliseconds(-3_200);
+        assert_eq!(duration, Duration::from(chrono_duration));
+        assert_eq!(Ok(chrono_duration), chrono::Duration::try_from(duration));
+
+        let out_of_range = Duration {
+            seconds: i64::MAX,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(TimeConversionError::OutOfRange),
+            chrono::Duration::try_from(out_of_range)
+        );
+    }
+
+    #[cfg(feature = "with-time")]
+    #[test]
+    fn to_from_time() {
+        use std::convert::TryFrom;
+
+        let duration = Duration {
+            seconds: -3,
+            nanos: -200_000_000,
+            ..Default::default()
+        };
+        let time_duration = time::Duration::milliseconds(-3_200);
+        assert_eq!(duration, Duration::from(time_duration));
+        assert_eq!(Ok(time_duration), time::Duration::try_from(duration));
+
+        let mixed_signs = Duration {
+            seconds: 1,
+            nanos: -1,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(TimeConversionError::InvalidNanos(-1)),
+            time::Duration::try_from(mixed_signs)
+        );
+    }
 }
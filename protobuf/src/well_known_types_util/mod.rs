@@ -1,3 +1,83 @@
 mod any;
 mod duration;
+#[cfg(feature = "with-serde-json")]
+mod struct_value;
 mod timestamp;
+
+use std::error::Error;
+use std::fmt;
+
+use crate::reflect::EnumDescriptor;
+use crate::reflect::FileDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::well_known_types::file_descriptors;
+
+/// Error converting `Timestamp` or `Duration` to `std::time`, `chrono` or `time` types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeConversionError {
+    /// Value is outside of the target type range.
+    OutOfRange,
+    /// `nanos` field is outside of the valid range.
+    InvalidNanos(i32),
+}
+
+impl fmt::Display for TimeConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TimeConversionError::OutOfRange => write!(f, "time value is out of range"),
+            TimeConversionError::InvalidNanos(nanos) => write!(f, "invalid nanos: {}", nanos),
+        }
+    }
+}
+
+impl Error for TimeConversionError {}
+
+/// Generated files of well known types.
+fn well_known_type_files() -> [FileDescriptor; 10] {
+    [
+        file_descriptors::any(),
+        file_descriptors::api(),
+        file_descriptors::duration(),
+        file_descriptors::empty(),
+        file_descriptors::field_mask(),
+        file_descriptors::source_context(),
+        file_descriptors::struct_pb(),
+        file_descriptors::timestamp(),
+        file_descriptors::type_pb(),
+        file_descriptors::wrappers(),
+    ]
+}
+
+/// Generated well known types file by name, e. g. `google/protobuf/any.proto`.
+pub(crate) fn well_known_type_file(name: &str) -> Option<FileDescriptor> {
+    if !name.starts_with("google/protobuf/") {
+        return None;
+    }
+    well_known_type_files()
+        .iter()
+        .find(|f| f.proto().get_name() == name)
+        .cloned()
+}
+
+/// Descriptor of generated well known type by full name without leading dot.
+pub(crate) fn well_known_type_descriptor(full_name: &str) -> Option<MessageDescriptor> {
+    if !full_name.starts_with("google.protobuf.") {
+        return None;
+    }
+    let full_name = format!(".{}", full_name);
+    well_known_type_files()
+        .iter()
+        .find_map(|f| f.message_by_full_name(&full_name))
+}
+
+/// Descriptor of generated well known enum, e. g. `google.protobuf.NullValue`,
+/// by full name without leading dot.
+pub(crate) fn well_known_type_enum(full_name: &str) -> Option<EnumDescriptor> {
+    if !full_name.starts_with("google.protobuf.") {
+        return None;
+    }
+    let full_name = format!(".{}", full_name);
+    well_known_type_files()
+        .iter()
+        .find_map(|f| f.enum_by_full_name(&full_name))
+}
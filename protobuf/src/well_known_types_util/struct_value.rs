@@ -0,0 +1,127 @@
+use crate::well_known_types::value;
+use crate::well_known_types::ListValue;
+use crate::well_known_types::NullValue;
+use crate::well_known_types::Struct;
+use crate::well_known_types::Value;
+
+/// Convert from `serde_json::Value`.
+///
+/// Numbers are converted to `f64`, which could be lossy for large integers.
+impl From<serde_json::Value> for Value {
+    fn from(json: serde_json::Value) -> Value {
+        let kind = match json {
+            serde_json::Value::Null => value::Kind::null_value(NullValue::NULL_VALUE.into()),
+            serde_json::Value::Bool(b) => value::Kind::bool_value(b),
+            serde_json::Value::Number(n) => value::Kind::number_value(n.as_f64().unwrap()),
+            serde_json::Value::String(s) => value::Kind::string_value(s),
+            serde_json::Value::Array(a) => value::Kind::list_value(a.into()),
+            serde_json::Value::Object(o) => value::Kind::struct_value(o.into()),
+        };
+        Value {
+            kind: Some(kind),
+            ..Default::default()
+        }
+    }
+}
+
+/// Convert to `serde_json::Value`.
+///
+/// Unset value and non-finite numbers are converted to `null`.
+impl From<Value> for serde_json::Value {
+    fn from(value: Value) -> serde_json::Value {
+        match value.kind {
+            None | Some(value::Kind::null_value(..)) => serde_json::Value::Null,
+            Some(value::Kind::bool_value(b)) => serde_json::Value::Bool(b),
+            Some(value::Kind::number_value(n)) => serde_json::Number::from_f64(n)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+            Some(value::Kind::string_value(s)) => serde_json::Value::String(s),
+            Some(value::Kind::list_value(l)) => serde_json::Value::Array(l.into()),
+            Some(value::Kind::struct_value(s)) => serde_json::Value::Object(s.into()),
+        }
+    }
+}
+
+/// Convert from JSON array.
+impl From<Vec<serde_json::Value>> for ListValue {
+    fn from(json: Vec<serde_json::Value>) -> ListValue {
+        ListValue {
+            values: json.into_iter().map(Value::from).collect(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Convert to JSON array.
+impl From<ListValue> for Vec<serde_json::Value> {
+    fn from(list: ListValue) -> Vec<serde_json::Value> {
+        list.values
+            .into_iter()
+            .map(serde_json::Value::from)
+            .collect()
+    }
+}
+
+/// Convert from JSON object.
+impl From<serde_json::Map<String, serde_json::Value>> for Struct {
+    fn from(json: serde_json::Map<String, serde_json::Value>) -> Struct {
+        Struct {
+            fields: json.into_iter().map(|(k, v)| (k, Value::from(v))).collect(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Convert to JSON object.
+impl From<Struct> for serde_json::Map<String, serde_json::Value> {
+    fn from(s: Struct) -> serde_json::Map<String, serde_json::Value> {
+        s.fields
+            .into_iter()
+            .map(|(k, v)| (k, serde_json::Value::from(v)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::well_known_types::value;
+    use crate::well_known_types::Struct;
+    use crate::well_known_types::Value;
+
+    #[test]
+    fn round_trip() {
+        let json = json!({
+            "null": null,
+            "bool": true,
+            "number": 1.5,
+            "string": "s",
+            "list": [1.0, "a", [], {}],
+            "struct": {"nested": {"x": false}},
+        });
+        let value = Value::from(json.clone());
+        let s = match &value.kind {
+            Some(value::Kind::struct_value(s)) => s.clone(),
+            kind => panic!("not a struct: {:?}", kind),
+        };
+        assert_eq!(6, s.fields.len());
+        assert_eq!(1.5, s.fields["number"].get_number_value());
+        assert_eq!(json, serde_json::Value::from(value));
+        assert_eq!(
+            json.as_object().unwrap(),
+            &serde_json::Map::from(Struct::from(json.as_object().unwrap().clone()))
+        );
+    }
+
+    #[test]
+    fn to_json_null() {
+        assert_eq!(
+            serde_json::Value::Null,
+            serde_json::Value::from(Value::new())
+        );
+        let mut nan = Value::new();
+        nan.set_number_value(f64::NAN);
+        assert_eq!(serde_json::Value::Null, serde_json::Value::from(nan));
+    }
+}
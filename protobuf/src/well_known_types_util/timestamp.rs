@@ -1,8 +1,11 @@
+#[cfg(any(feature = "with-chrono", feature = "with-time"))]
+use std::convert::TryFrom;
 use std::time::Duration;
 use std::time::SystemTime;
 
 use crate::cached_size::CachedSize;
 use crate::well_known_types::Timestamp;
+use crate::well_known_types_util::TimeConversionError;
 use crate::UnknownFields;
 
 impl Timestamp {
@@ -18,6 +21,29 @@ impl Timestamp {
     pub fn now() -> Timestamp {
         Timestamp::from(SystemTime::now())
     }
+
+    /// Convert into [`SystemTime`].
+    ///
+    /// Same as `Into<SystemTime>`, but fails if `Timestamp` is outside of `SystemTime` range
+    /// or if `nanos` is not in `0..1_000_000_000` range.
+    pub fn to_system_time(&self) -> Result<SystemTime, TimeConversionError> {
+        self.check_nanos()?;
+        let seconds = Duration::from_secs(self.seconds.unsigned_abs());
+        let time = if self.seconds >= 0 {
+            SystemTime::UNIX_EPOCH.checked_add(seconds)
+        } else {
+            SystemTime::UNIX_EPOCH.checked_sub(seconds)
+        };
+        time.and_then(|t| t.checked_add(Duration::from_nanos(self.nanos as u64)))
+            .ok_or(TimeConversionError::OutOfRange)
+    }
+
+    fn check_nanos(&self) -> Result<(), TimeConversionError> {
+        if !(0..1_000_000_000).contains(&self.nanos) {
+            return Err(TimeConversionError::InvalidNanos(self.nanos));
+        }
+        Ok(())
+    }
 }
 
 /// Convert from [`Timestamp`].
@@ -55,6 +81,8 @@ impl From<SystemTime> for Timestamp {
 /// This function panics:
 /// * if given `Timestamp` is outside of `SystemTime` range
 /// * if `Timestamp` is malformed
+///
+/// Use [`Timestamp::to_system_time`] to handle these errors.
 impl Into<SystemTime> for Timestamp {
     fn into(self) -> SystemTime {
         if self.seconds >= 0 {
@@ -69,17 +97,78 @@ impl Into<SystemTime> for Timestamp {
     }
 }
 
+/// Convert from `chrono::DateTime<Utc>`.
+///
+/// Leap second is represented as the first second of the next minute.
+#[cfg(feature = "with-chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for Timestamp {
+    fn from(time: chrono::DateTime<chrono::Utc>) -> Self {
+        let nanos = time.timestamp_subsec_nanos();
+        Timestamp {
+            seconds: time.timestamp() + (nanos / 1_000_000_000) as i64,
+            nanos: (nanos % 1_000_000_000) as i32,
+            ..Default::default()
+        }
+    }
+}
+
+/// Convert into `chrono::DateTime<Utc>`.
+///
+/// Fails if `Timestamp` is outside of `DateTime` range or if `nanos` is not
+/// in `0..1_000_000_000` range.
+#[cfg(feature = "with-chrono")]
+impl TryFrom<Timestamp> for chrono::DateTime<chrono::Utc> {
+    type Error = TimeConversionError;
+
+    fn try_from(timestamp: Timestamp) -> Result<Self, TimeConversionError> {
+        timestamp.check_nanos()?;
+        chrono::DateTime::from_timestamp(timestamp.seconds, timestamp.nanos as u32)
+            .ok_or(TimeConversionError::OutOfRange)
+    }
+}
+
+/// Convert from `time::OffsetDateTime`.
+#[cfg(feature = "with-time")]
+impl From<time::OffsetDateTime> for Timestamp {
+    fn from(time: time::OffsetDateTime) -> Self {
+        let nanos = time.unix_timestamp_nanos();
+        Timestamp {
+            seconds: nanos.div_euclid(1_000_000_000) as i64,
+            nanos: nanos.rem_euclid(1_000_000_000) as i32,
+            ..Default::default()
+        }
+    }
+}
+
+/// Convert into `time::OffsetDateTime` with UTC offset.
+///
+/// Fails if `Timestamp` is outside of `OffsetDateTime` range or if `nanos` is not
+/// in `0..1_000_000_000` range.
+#[cfg(feature = "with-time")]
+impl TryFrom<Timestamp> for time::OffsetDateTime {
+    type Error = TimeConversionError;
+
+    fn try_from(timestamp: Timestamp) -> Result<Self, TimeConversionError> {
+        timestamp.check_nanos()?;
+        let nanos = timestamp.seconds as i128 * 1_000_000_000 + timestamp.nanos as i128;
+        time::OffsetDateTime::from_unix_timestamp_nanos(nanos)
+            .map_err(|_| TimeConversionError::OutOfRange)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::time::Duration;
     use std::time::SystemTime;
 
     use crate::well_known_types::Timestamp;
+    use crate::well_known_types_util::TimeConversionError;
 
     #[test]
     fn to_from_system_time() {
         fn to_from(timestamp: Timestamp, system_time: SystemTime) {
             assert_eq!(timestamp, Timestamp::from(system_time));
+            assert_eq!(Ok(system_time), timestamp.to_system_time());
             assert_eq!(system_time, Into::<SystemTime>::into(timestamp));
         }
 
@@ -117,4 +206,73 @@ mod test {
             SystemTime::UNIX_EPOCH - Duration::from_millis(3_200),
         );
     }
+
+    #[test]
+    fn to_system_time_errors() {
+        let invalid_nanos = Timestamp {
+            seconds: 1,
+            nanos: -1,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(TimeConversionError::InvalidNanos(-1)),
+            invalid_nanos.to_system_time()
+        );
+        let invalid_nanos = Timestamp {
+            nanos: 1_000_000_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(TimeConversionError::InvalidNanos(1_000_000_000)),
+            invalid_nanos.to_system_time()
+        );
+    }
+
+    #[cfg(feature = "with-chrono")]
+    #[test]
+    fn to_from_chrono() {
+        use std::convert::TryFrom;
+
+        let timestamp = Timestamp {
+            seconds: -4,
+            nanos: 800_000_000,
+            ..Default::default()
+        };
+        let time = chrono::DateTime::from_timestamp(-4, 800_000_000).unwrap();
+        assert_eq!(timestamp, Timestamp::from(time));
+        assert_eq!(Ok(time), chrono::DateTime::try_from(timestamp));
+
+        let out_of_range = Timestamp {
+            seconds: i64::MAX,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(TimeConversionError::OutOfRange),
+            chrono::DateTime::<chrono::Utc>::try_from(out_of_range)
+        );
+    }
+
+    #[cfg(feature = "with-time")]
+    #[test]
+    fn to_from_time() {
+        use std::convert::TryFrom;
+
+        let timestamp = Timestamp {
+            seconds: -4,
+            nanos: 800_000_000,
+            ..Default::default()
+        };
+        let time = time::OffsetDateTime::from_unix_timestamp_nanos(-3_200_000_000).unwrap();
+        assert_eq!(timestamp, Timestamp::from(time));
+        assert_eq!(Ok(time), time::OffsetDateTime::try_from(timestamp));
+
+        let out_of_range = Timestamp {
+            seconds: i64::MAX,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(TimeConversionError::OutOfRange),
+            time::OffsetDateTime::try_from(out_of_range)
+        );
+    }
 }
@@ -0,0 +1,264 @@
+//! Low-level encoding primitives of protobuf wire format.
+//!
+//! These are the functions used by [`CodedInputStream`](crate::CodedInputStream)
+//! and [`CodedOutputStream`](crate::CodedOutputStream), exposed for custom
+//! formats which need the same encodings without the streams:
+//!
+//! ```
+//! # use protobuf::wire::*;
+//! let mut buf = [0; MAX_VARINT_ENCODED_LEN];
+//! let len = encode_varint64(encode_zig_zag_64(-300), &mut buf);
+//! assert_eq!(&[0xd7, 0x04][..], &buf[..len]);
+//!
+//! let (value, consumed) = decode_varint64(&buf[..len]).unwrap();
+//! assert_eq!((-300, 2), (decode_zig_zag_64(value), consumed));
+//! ```
+//!
+//! Decoding functions read from the beginning of a slice and return
+//! the number of bytes consumed, so several values can be decoded
+//! from one buffer by advancing it.
+
+pub use crate::rt::compute_raw_varint32_size;
+pub use crate::rt::compute_raw_varint64_size;
+pub use crate::varint::encode_varint32;
+pub use crate::varint::encode_varint64;
+pub use crate::wire_format::MAX_VARINT_ENCODED_LEN;
+pub use crate::zigzag::decode_zig_zag_32;
+pub use crate::zigzag::decode_zig_zag_64;
+pub use crate::zigzag::encode_zig_zag_32;
+pub use crate::zigzag::encode_zig_zag_64;
+
+use crate::error::WireError;
+use crate::ProtobufError;
+use crate::ProtobufResult;
+
+/// Decode varint from the beginning of the buffer.
+///
+/// Return the value and the number of bytes consumed.
+/// Error is returned if the buffer ends before the varint does,
+/// or if the varint is longer than [`MAX_VARINT_ENCODED_LEN`] bytes
+/// or does not fit in `u64`.
+pub fn decode_varint64(buf: &[u8]) -> ProtobufResult<(u64, usize)> {
+    let mut r: u64 = 0;
+    for (i, &b) in buf.iter().enumerate() {
+        if i == MAX_VARINT_ENCODED_LEN || i == MAX_VARINT_ENCODED_LEN - 1 && (b & 0x7f) > 1 {
+            return Err(ProtobufError::WireError(WireError::IncorrectVarint));
+        }
+        r |= ((b & 0x7f) as u64) << (i * 7);
+        if b < 0x80 {
+            return Ok((r, i + 1));
+        }
+    }
+    Err(ProtobufError::WireError(WireError::UnexpectedEof))
+}
+
+/// Decode varint from the beginning of the buffer, truncating the value to `u32`.
+///
+/// Negative `int32` values are encoded as ten byte varints,
+/// so decoding does not fail if the value does not fit in `u32`,
+/// same as [`CodedInputStream::read_raw_varint32`](crate::CodedInputStream::read_raw_varint32).
+pub fn decode_varint32(buf: &[u8]) -> ProtobufResult<(u32, usize)> {
+    decode_varint64(buf).map(|(v, len)| (v as u32, len))
+}
+
+/// Encode `fixed32` value as four little-endian bytes.
+///
+/// # Panics
+///
+/// If buffer length is less than 4.
+pub fn encode_fixed32(value: u32, buf: &mut [u8]) {
+    buf[..4].copy_from_slice(&value.to_le_bytes());
+}
+
+/// Encode `fixed64` value as eight little-endian bytes.
+///
+/// # Panics
+///
+/// If buffer length is less than 8.
+pub fn encode_fixed64(value: u64, buf: &mut [u8]) {
+    buf[..8].copy_from_slice(&value.to_le_bytes());
+}
+
+/// Decode `fixed32` value from the first four bytes of the buffer.
+pub fn decode_fixed32(buf: &[u8]) -> ProtobufResult<u32> {
+    let mut bytes = [0; 4];
+    match buf.get(..4) {
+        Some(b) => bytes.copy_from_slice(b),
+        None => return Err(ProtobufError::WireError(WireError::UnexpectedEof)),
+    }
+    Ok(u32::from_le_bytes(bytes))
+}
+
+/// Decode `fixed64` value from the first eight bytes of the buffer.
+pub fn decode_fixed64(buf: &[u8]) -> ProtobufResult<u64> {
+    let mut bytes = [0; 8];
+    match buf.get(..8) {
+        Some(b) => bytes.copy_from_slice(b),
+        None => return Err(ProtobufError::WireError(WireError::UnexpectedEof)),
+    }
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::CodedInputStream;
+    use crate::CodedOutputStream;
+
+    const VALUES64: &[u64] = &[
+        0,
+        1,
+        0x7f,
+        0x80,
+        0x3fff,
+        0x4000,
+        0x1fffff,
+        0x200000,
+        u32::MAX as u64 - 1,
+        u32::MAX as u64,
+        u32::MAX as u64 + 1,
+        1 << 56,
+        (1 << 63) - 1,
+        1 << 63,
+        u64::MAX - 1,
+        u64::MAX,
+    ];
+
+    fn encode64(value: u64) -> Vec<u8> {
+        let mut buf = [0; MAX_VARINT_ENCODED_LEN];
+        let len = encode_varint64(value, &mut buf);
+        buf[..len].to_vec()
+    }
+
+    #[test]
+    fn varint64_same_as_stream() {
+        for &v in VALUES64 {
+            let encoded = encode64(v);
+            assert_eq!(compute_raw_varint64_size(v) as usize, encoded.len());
+
+            let mut expected = Vec::new();
+            {
+                let mut os = CodedOutputStream::vec(&mut expected);
+                os.write_raw_varint64(v).unwrap();
+                os.flush().unwrap();
+            }
+            assert_eq!(expected, encoded, "{}", v);
+
+            let mut with_suffix = encoded.clone();
+            with_suffix.extend_from_slice(&[0xff, 0x01]);
+            assert_eq!(
+                (v, encoded.len()),
+                decode_varint64(&with_suffix).unwrap(),
+                "{}",
+                v
+            );
+            assert_eq!(
+                v,
+                CodedInputStream::from_bytes(&encoded)
+                    .read_raw_varint64()
+                    .unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn varint32() {
+        for &v in &[0, 1, 0x7f, 0x80, 0x3fff, 0x4000, u32::MAX - 1, u32::MAX] {
+            let mut buf = [0; 5];
+            let len = encode_varint32(v, &mut buf);
+            assert_eq!(compute_raw_varint32_size(v) as usize, len);
+            assert_eq!(encode64(v as u64), &buf[..len]);
+            assert_eq!((v, len), decode_varint32(&buf[..len]).unwrap());
+        }
+
+        // Negative int32 encoded as 64-bit value
+        let encoded = encode64(-2i32 as u64);
+        assert_eq!(10, encoded.len());
+        assert_eq!((-2i32 as u32, 10), decode_varint32(&encoded).unwrap());
+    }
+
+    #[test]
+    fn varint_errors() {
+        fn is_eof(buf: &[u8]) -> bool {
+            matches!(
+                decode_varint64(buf),
+                Err(ProtobufError::WireError(WireError::UnexpectedEof))
+            )
+        }
+
+        fn is_incorrect(buf: &[u8]) -> bool {
+            matches!(
+                decode_varint64(buf),
+                Err(ProtobufError::WireError(WireError::IncorrectVarint))
+            )
+        }
+
+        assert!(is_eof(&[]));
+        assert!(is_eof(&[0x80]));
+        assert!(is_eof(&[0xff; 9]));
+        // Eleven bytes
+        assert!(is_incorrect(&[0x80; 11]));
+        // Tenth byte has more than one significant bit
+        let mut overflow = [0xff; 10];
+        overflow[9] = 0x02;
+        assert!(is_incorrect(&overflow));
+        overflow[9] = 0x01;
+        assert_eq!((u64::MAX, 10), decode_varint64(&overflow).unwrap());
+        // Non-canonical encoding with padding is accepted
+        assert_eq!((1, 3), decode_varint64(&[0x81, 0x80, 0x00]).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn encode_varint_short_buffer() {
+        encode_varint64(1, &mut [0; 9]);
+    }
+
+    #[test]
+    fn zig_zag_varint() {
+        for &v in &[0, -1, 1, i64::MIN, i64::MAX] {
+            let encoded = encode64(encode_zig_zag_64(v));
+            let (decoded, _) = decode_varint64(&encoded).unwrap();
+            assert_eq!(v, decode_zig_zag_64(decoded));
+        }
+        for &v in &[0, -1, 1, i32::MIN, i32::MAX] {
+            let mut buf = [0; 5];
+            let len = encode_varint32(encode_zig_zag_32(v), &mut buf);
+            let (decoded, _) = decode_varint32(&buf[..len]).unwrap();
+            assert_eq!(v, decode_zig_zag_32(decoded));
+        }
+    }
+
+    #[test]
+    fn fixed() {
+        let mut buf = [0; 9];
+        encode_fixed32(0x01020304, &mut buf);
+        assert_eq!([4, 3, 2, 1], buf[..4]);
+        assert_eq!(0x01020304, decode_fixed32(&buf).unwrap());
+        assert!(decode_fixed32(&buf[..3]).is_err());
+
+        encode_fixed64(0x0102030405060708, &mut buf);
+        assert_eq!([8, 7, 6, 5, 4, 3, 2, 1, 0], buf);
+        assert_eq!(0x0102030405060708, decode_fixed64(&buf).unwrap());
+        assert!(decode_fixed64(&buf[..7]).is_err());
+
+        let mut expected = Vec::new();
+        {
+            let mut os = CodedOutputStream::vec(&mut expected);
+            os.write_raw_little_endian32(u32::MAX - 1).unwrap();
+            os.write_raw_little_endian64(u64::MAX - 1).unwrap();
+            os.flush().unwrap();
+        }
+        let mut buf = [0; 12];
+        encode_fixed32(u32::MAX - 1, &mut buf);
+        encode_fixed64(u64::MAX - 1, &mut buf[4..]);
+        assert_eq!(&expected[..], &buf[..]);
+        assert_eq!(u64::MAX - 1, decode_fixed64(&buf[4..]).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn encode_fixed_short_buffer() {
+        encode_fixed32(1, &mut [0; 3]);
+    }
+}
@@ -1,4 +1,8 @@
-//! Constants used in serializations.
+//! Constants and helpers of protobuf wire format.
+//!
+//! Each field in serialized message is prefixed with a tag, which is a varint
+//! encoding field number and [`WireType`]. [`Tag`] can be used to encode and
+//! decode tags without reimplementing bit manipulations.
 
 // TODO: temporary
 pub use self::WireType::*;
@@ -7,11 +11,28 @@ pub use self::WireType::*;
 pub const TAG_TYPE_BITS: u32 = 3;
 /// Apply this mask to varint value to obtain a tag.
 pub const TAG_TYPE_MASK: u32 = (1u32 << TAG_TYPE_BITS as usize) - 1;
+/// Min possible field number
+pub const FIELD_NUMBER_MIN: u32 = 1;
 /// Max possible field number
 pub const FIELD_NUMBER_MAX: u32 = 0x1fffffff;
+/// First field number reserved for protobuf implementation.
+///
+/// Field numbers in range
+/// [`FIRST_RESERVED_FIELD_NUMBER`]`..=`[`LAST_RESERVED_FIELD_NUMBER`]
+/// are valid on the wire, but cannot be used in `.proto` files.
+pub const FIRST_RESERVED_FIELD_NUMBER: u32 = 19000;
+/// Last field number reserved for protobuf implementation.
+pub const LAST_RESERVED_FIELD_NUMBER: u32 = 19999;
+/// Max length of encoded varint (which is length of encoded `u64::MAX`).
+pub const MAX_VARINT_ENCODED_LEN: usize = 10;
+
+/// Check if field number can be encoded in a tag.
+pub fn is_valid_field_number(field_number: u32) -> bool {
+    (FIELD_NUMBER_MIN..=FIELD_NUMBER_MAX).contains(&field_number)
+}
 
 /// All supported "wire types" are listed in this enum.
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
 pub enum WireType {
     /// Variable-length integer
     WireTypeVarint = 0,
@@ -40,10 +61,15 @@ impl WireType {
             _ => None,
         }
     }
+
+    /// Number of this wire type as stored in tag.
+    pub fn value(self) -> u32 {
+        self as u32
+    }
 }
 
 /// Parsed field tag (a pair of field number and wire type)
-#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
 pub struct Tag {
     field_number: u32,
     wire_type: WireType,
@@ -63,7 +89,7 @@ impl Tag {
             return None;
         }
         let field_number = value >> TAG_TYPE_BITS;
-        if field_number == 0 {
+        if field_number < FIELD_NUMBER_MIN {
             return None;
         }
         Some(Tag {
@@ -78,20 +104,34 @@ impl Tag {
     ///
     /// If field number is outside of valid range.
     pub fn make(field_number: u32, wire_type: WireType) -> Tag {
-        assert!(field_number > 0 && field_number <= FIELD_NUMBER_MAX);
+        assert!(is_valid_field_number(field_number));
         Tag {
             field_number,
             wire_type,
         }
     }
 
+    /// Construct a tag from a field number and wire type.
+    ///
+    /// Return `None` if field number is outside of valid range.
+    pub fn try_make(field_number: u32, wire_type: WireType) -> Option<Tag> {
+        if is_valid_field_number(field_number) {
+            Some(Tag {
+                field_number,
+                wire_type,
+            })
+        } else {
+            None
+        }
+    }
+
     /// Get field number and wire type
     pub fn unpack(self) -> (u32, WireType) {
         (self.field_number(), self.wire_type())
     }
 
     /// Get wire type
-    fn wire_type(self) -> WireType {
+    pub fn wire_type(self) -> WireType {
         self.wire_type
     }
 
@@ -100,3 +140,27 @@ impl Tag {
         self.field_number
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tag_round_trip() {
+        for &field_number in &[FIELD_NUMBER_MIN, 15, 16, FIELD_NUMBER_MAX] {
+            for wire_type in (0..6).map(|n| WireType::new(n).unwrap()) {
+                let tag = Tag::make(field_number, wire_type);
+                assert_eq!(Some(tag), Tag::new(tag.value()));
+                assert_eq!((field_number, wire_type), tag.unpack());
+            }
+        }
+    }
+
+    #[test]
+    fn tag_invalid() {
+        assert_eq!(None, Tag::new(WireTypeVarint.value()));
+        assert_eq!(None, Tag::new((1 << TAG_TYPE_BITS) | 7));
+        assert_eq!(None, Tag::try_make(0, WireTypeVarint));
+        assert_eq!(None, Tag::try_make(FIELD_NUMBER_MAX + 1, WireTypeVarint));
+    }
+}
@@ -0,0 +1,167 @@
+//! Scan serialized message fields without parsing values.
+//!
+//! Scanning reads only tags and lengths, so it is cheap and does not allocate
+//! (except for the result of [`message_layout`]). Field spans can be used
+//! to slice serialized message, or to analyze field size distribution.
+//!
+//! ```
+//! # use protobuf::wire_scan::message_layout;
+//! # fn foo(bytes: &[u8]) -> protobuf::ProtobufResult<()> {
+//! for field in message_layout(bytes)? {
+//!     println!("field {}: {} bytes", field.field_number, field.field.len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::ops::Range;
+
+use crate::error::ProtobufResult;
+use crate::rt;
+use crate::wire_format;
+use crate::wire_format::WireType;
+use crate::CodedInputStream;
+
+/// Location of a top-level field in serialized message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSpan {
+    /// Field number.
+    pub field_number: u32,
+    /// Wire type of the field.
+    pub wire_type: WireType,
+    /// Bytes of the whole field including tag.
+    pub field: Range<usize>,
+    /// Bytes of the field value: varint or fixed-width value,
+    /// content of length-delimited field without length prefix,
+    /// or content of group without end tag.
+    pub value: Range<usize>,
+}
+
+/// Iterator over top-level fields of serialized message.
+///
+/// Iteration stops after the first error.
+pub struct FieldSpans<'a> {
+    is: CodedInputStream<'a>,
+    failed: bool,
+}
+
+/// Iterate over top-level fields of serialized message.
+pub fn scan_fields(bytes: &[u8]) -> FieldSpans<'_> {
+    FieldSpans {
+        is: CodedInputStream::from_bytes(bytes),
+        failed: false,
+    }
+}
+
+impl<'a> FieldSpans<'a> {
+    fn next_field(&mut self) -> ProtobufResult<FieldSpan> {
+        let start = self.is.pos() as usize;
+        let (field_number, wire_type) = self.is.read_tag_unpack()?;
+        let value = match wire_type {
+            wire_format::WireTypeLengthDelimited => {
+                let len = self.is.read_raw_varint32()?;
+                let value_start = self.is.pos() as usize;
+                self.is.skip_raw_bytes(len)?;
+                value_start..self.is.pos() as usize
+            }
+            wire_format::WireTypeStartGroup => {
+                let value_start = self.is.pos() as usize;
+                rt::skip_group(&mut self.is)?;
+                let end_tag_len = rt::compute_raw_varint32_size(
+                    wire_format::Tag::make(field_number, wire_format::WireTypeEndGroup).value(),
+                ) as usize;
+                value_start..self.is.pos() as usize - end_tag_len
+            }
+            _ => {
+                let value_start = self.is.pos() as usize;
+                self.is.skip_field(wire_type)?;
+                value_start..self.is.pos() as usize
+            }
+        };
+        Ok(FieldSpan {
+            field_number,
+            wire_type,
+            field: start..self.is.pos() as usize,
+            value,
+        })
+    }
+}
+
+impl<'a> Iterator for FieldSpans<'a> {
+    type Item = ProtobufResult<FieldSpan>;
+
+    fn next(&mut self) -> Option<ProtobufResult<FieldSpan>> {
+        if self.failed {
+            return None;
+        }
+        match self.is.eof() {
+            Ok(true) => return None,
+            Ok(false) => {}
+            Err(e) => {
+                self.failed = true;
+                return Some(Err(e));
+            }
+        }
+        let r = self.next_field();
+        self.failed = r.is_err();
+        Some(r)
+    }
+}
+
+/// Spans of all top-level fields of serialized message in order.
+pub fn message_layout(bytes: &[u8]) -> ProtobufResult<Vec<FieldSpan>> {
+    scan_fields(bytes).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hex::decode_hex;
+
+    #[test]
+    fn layout() {
+        // 1: varint 150, 2: "ab", 3: group { 1: 1 }, 4: fixed32
+        let bytes = decode_hex("08 96 01 12 02 61 62 1b 08 01 1c 25 01 00 00 00");
+        let layout = message_layout(&bytes).unwrap();
+        assert_eq!(
+            vec![
+                FieldSpan {
+                    field_number: 1,
+                    wire_type: wire_format::WireTypeVarint,
+                    field: 0..3,
+                    value: 1..3,
+                },
+                FieldSpan {
+                    field_number: 2,
+                    wire_type: wire_format::WireTypeLengthDelimited,
+                    field: 3..7,
+                    value: 5..7,
+                },
+                FieldSpan {
+                    field_number: 3,
+                    wire_type: wire_format::WireTypeStartGroup,
+                    field: 7..11,
+                    value: 8..10,
+                },
+                FieldSpan {
+                    field_number: 4,
+                    wire_type: wire_format::WireTypeFixed32,
+                    field: 11..16,
+                    value: 12..16,
+                },
+            ],
+            layout
+        );
+        assert_eq!(b"ab", &bytes[layout[1].value.clone()]);
+    }
+
+    #[test]
+    fn truncated() {
+        let bytes = decode_hex("08 96 01 12 05 61 62");
+        let mut spans = scan_fields(&bytes);
+        assert!(spans.next().unwrap().is_ok());
+        assert!(spans.next().unwrap().is_err());
+        assert!(spans.next().is_none());
+        assert!(message_layout(&bytes).is_err());
+    }
+}
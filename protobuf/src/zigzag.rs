@@ -1,18 +1,24 @@
 // ZigZag endoging used for efficient transfer of signed integers
 // https://developers.google.com/protocol-buffers/docs/encoding#types
 
+/// Decode zigzag-encoded `sint32` value.
 pub fn decode_zig_zag_32(n: u32) -> i32 {
     ((n >> 1) as i32) ^ (-((n & 1) as i32))
 }
 
+/// Decode zigzag-encoded `sint64` value.
 pub fn decode_zig_zag_64(n: u64) -> i64 {
     ((n >> 1) as i64) ^ (-((n & 1) as i64))
 }
 
+/// Encode `sint32` value with zigzag encoding,
+/// so values of small magnitude have short varint encoding.
 pub fn encode_zig_zag_32(n: i32) -> u32 {
     ((n << 1) ^ (n >> 31)) as u32
 }
 
+/// Encode `sint64` value with zigzag encoding,
+/// so values of small magnitude have short varint encoding.
 pub fn encode_zig_zag_64(n: i64) -> u64 {
     ((n << 1) ^ (n >> 63)) as u64
 }